@@ -1,9 +1,10 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::wrong_self_convention)]
 
+use meilisearch_types::document_formats::PayloadType;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{DuplicateIdsPolicy, IndexDocumentsMethod};
 use meilisearch_types::settings::Unchecked;
 use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
 use meilisearch_types::InstanceUid;
@@ -96,6 +97,25 @@ pub enum KindDump {
         method: IndexDocumentsMethod,
         documents_count: u64,
         allow_index_creation: bool,
+        #[serde(default)]
+        duplicate_ids_policy: Option<DuplicateIdsPolicy>,
+    },
+    DocumentImportFromUrl {
+        primary_key: Option<String>,
+        method: IndexDocumentsMethod,
+        url: String,
+        #[serde(default)]
+        has_authorization: bool,
+        format: PayloadType,
+        allow_index_creation: bool,
+        #[serde(default)]
+        duplicate_ids_policy: Option<DuplicateIdsPolicy>,
+    },
+    DocumentImportDryRun {
+        primary_key: Option<String>,
+        documents_count: u64,
+        #[serde(default)]
+        duplicate_ids_policy: Option<DuplicateIdsPolicy>,
     },
     DocumentDeletion {
         documents_ids: Vec<String>,
@@ -116,6 +136,11 @@ pub enum KindDump {
     IndexUpdate {
         primary_key: Option<String>,
     },
+    /// The task is associated with the new index, the duplicated one is `source_index_uid`.
+    IndexDuplication {
+        source_index_uid: String,
+    },
+    IndexCompaction,
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
@@ -159,12 +184,42 @@ impl From<KindWithContent> for KindDump {
                 method,
                 documents_count,
                 allow_index_creation,
+                duplicate_ids_policy,
                 ..
             } => KindDump::DocumentImport {
                 primary_key,
                 method,
                 documents_count,
                 allow_index_creation,
+                duplicate_ids_policy,
+            },
+            KindWithContent::DocumentAdditionFromUrl {
+                primary_key,
+                method,
+                url,
+                has_authorization,
+                format,
+                allow_index_creation,
+                duplicate_ids_policy,
+                ..
+            } => KindDump::DocumentImportFromUrl {
+                primary_key,
+                method,
+                url,
+                has_authorization,
+                format,
+                allow_index_creation,
+                duplicate_ids_policy,
+            },
+            KindWithContent::DocumentAdditionDryRun {
+                primary_key,
+                documents_count,
+                duplicate_ids_policy,
+                ..
+            } => KindDump::DocumentImportDryRun {
+                primary_key,
+                documents_count,
+                duplicate_ids_policy,
             },
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
                 KindDump::DocumentDeletion { documents_ids }
@@ -186,6 +241,10 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 KindDump::IndexUpdate { primary_key }
             }
+            KindWithContent::IndexDuplication { index_uid, .. } => {
+                KindDump::IndexDuplication { source_index_uid: index_uid }
+            }
+            KindWithContent::IndexCompaction { .. } => KindDump::IndexCompaction,
             KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
             KindWithContent::TaskCancelation { query, tasks } => {
                 KindDump::TaskCancelation { query, tasks }
@@ -203,6 +262,7 @@ impl From<KindWithContent> for KindDump {
 
 #[cfg(test)]
 pub(crate) mod test {
+    use std::collections::BTreeMap;
     use std::fs::File;
     use std::io::Seek;
     use std::str::FromStr;
@@ -211,12 +271,14 @@ pub(crate) mod test {
     use maplit::{btreemap, btreeset};
     use meilisearch_types::facet_values_sort::FacetValuesSort;
     use meilisearch_types::features::RuntimeTogglableFeatures;
+    use meilisearch_types::index_templates::IndexTemplate;
     use meilisearch_types::index_uid_pattern::IndexUidPattern;
     use meilisearch_types::keys::{Action, Key};
     use meilisearch_types::milli;
     use meilisearch_types::milli::update::Setting;
+    use meilisearch_types::schedules::{CronExpression, Schedule};
     use meilisearch_types::settings::{Checked, FacetingSettings, Settings};
-    use meilisearch_types::tasks::{Details, Status};
+    use meilisearch_types::tasks::{Details, KindWithContent, Status};
     use serde_json::{json, Map, Value};
     use time::macros::datetime;
     use uuid::Uuid;
@@ -257,11 +319,12 @@ pub(crate) mod test {
     pub fn create_test_settings() -> Settings<Checked> {
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![S("race"), S("name")]),
-            searchable_attributes: Setting::Set(vec![S("name"), S("race")]),
+            searchable_attributes: Setting::Set(vec![S("name").into(), S("race").into()]),
             filterable_attributes: Setting::Set(btreeset! { S("race"), S("age") }),
             sortable_attributes: Setting::Set(btreeset! { S("age") }),
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            index_stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
@@ -275,6 +338,24 @@ pub(crate) mod test {
                 ),
             }),
             pagination: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
+            prefix_search: Setting::NotSet,
+            split_words: Setting::NotSet,
+            concatenate_words: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            normalize_numbers: Setting::NotSet,
+            normalization: Setting::NotSet,
+            percolate_queries: Setting::NotSet,
+            stemming: Setting::NotSet,
+            geo_fields: Setting::NotSet,
+            geo_index: Setting::NotSet,
+            binary_quantized_vectors: Setting::NotSet,
+            vector_index: Setting::NotSet,
+            reranker: Setting::NotSet,
+            version_attribute: Setting::NotSet,
+            document_timestamps: Setting::NotSet,
+            facet_search: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -292,11 +373,14 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: Some(S("bone")),
                         documents_count: 12,
+                        duplicate_ids_policy: None,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        matched_percolate_queries: BTreeMap::new(),
+                        duplicated_documents: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -315,11 +399,14 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: None,
                         documents_count: 2,
+                        duplicate_ids_policy: None,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        matched_percolate_queries: BTreeMap::new(),
+                        duplicated_documents: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -427,6 +514,16 @@ pub(crate) mod test {
 
         dump.create_experimental_features(features).unwrap();
 
+        // ========== index templates
+        let index_templates = create_test_index_templates();
+
+        dump.create_index_templates(&index_templates).unwrap();
+
+        // ========== schedules
+        let schedules = create_test_schedules();
+
+        dump.create_schedules(&schedules).unwrap();
+
         // create the dump
         let mut file = tempfile::tempfile().unwrap();
         dump.persist_to(&mut file).unwrap();
@@ -436,7 +533,24 @@ pub(crate) mod test {
     }
 
     fn create_test_features() -> RuntimeTogglableFeatures {
-        RuntimeTogglableFeatures { vector_store: true, ..Default::default() }
+        RuntimeTogglableFeatures { vector_store: true }
+    }
+
+    fn create_test_schedules() -> BTreeMap<String, Schedule> {
+        let cron: CronExpression = "0 3 * * *".parse().unwrap();
+        btreemap! {
+            S("snapshots") => Schedule::new(cron, KindWithContent::SnapshotCreation, datetime!(2022-11-20 12:00 UTC)),
+        }
+    }
+
+    fn create_test_index_templates() -> BTreeMap<String, IndexTemplate> {
+        let settings = Settings { stop_words: Setting::Reset, ..Settings::default() };
+        btreemap! {
+            S("logs") => IndexTemplate {
+                uid_pattern: Some(IndexUidPattern::new_unchecked("logs-*")),
+                settings,
+            },
+        }
     }
 
     #[test]
@@ -487,5 +601,11 @@ pub(crate) mod test {
         // ==== checking the features
         let expected = create_test_features();
         assert_eq!(dump.features().unwrap().unwrap(), expected);
+
+        // ==== checking the index templates
+        assert_eq!(dump.index_templates().unwrap(), create_test_index_templates());
+
+        // ==== checking the schedules
+        assert_eq!(dump.schedules().unwrap(), create_test_schedules());
     }
 }
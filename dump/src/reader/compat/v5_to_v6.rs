@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use super::v4_to_v5::{CompatIndexV4ToV5, CompatV4ToV5};
@@ -103,6 +104,7 @@ impl CompatV5ToV6 {
                                 }
                             },
                             allow_index_creation,
+                            duplicate_ids_policy: None,
                         },
                         v5::tasks::TaskContent::DocumentDeletion { deletion, .. } => match deletion
                         {
@@ -132,6 +134,8 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                matched_percolate_queries: BTreeMap::new(),
+                                duplicated_documents: None,
                             }
                         }
                         v5::Details::Settings { settings } => {
@@ -195,6 +199,14 @@ impl CompatV5ToV6 {
     pub fn features(&self) -> Result<Option<v6::RuntimeTogglableFeatures>> {
         Ok(None)
     }
+
+    pub fn index_templates(&self) -> Result<BTreeMap<String, v6::IndexTemplate>> {
+        Ok(BTreeMap::new())
+    }
+
+    pub fn schedules(&self) -> Result<BTreeMap<String, v6::Schedule>> {
+        Ok(BTreeMap::new())
+    }
 }
 
 pub enum CompatIndexV5ToV6 {
@@ -316,7 +328,13 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
     fn from(settings: v5::Settings<T>) -> Self {
         v6::Settings {
             displayed_attributes: settings.displayed_attributes.into(),
-            searchable_attributes: settings.searchable_attributes.into(),
+            searchable_attributes: match settings.searchable_attributes {
+                v5::Setting::Set(attributes) => {
+                    v6::Setting::Set(attributes.into_iter().map(Into::into).collect())
+                }
+                v5::Setting::Reset => v6::Setting::Reset,
+                v5::Setting::NotSet => v6::Setting::NotSet,
+            },
             filterable_attributes: settings.filterable_attributes.into(),
             sortable_attributes: settings.sortable_attributes.into(),
             ranking_rules: {
@@ -333,13 +351,18 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                                 }
                             }
                         }
-                        v6::Setting::Set(new_ranking_rules)
+                        v6::Setting::Set(v6::dedup_ranking_rules(new_ranking_rules))
                     }
                     v5::settings::Setting::Reset => v6::Setting::Reset,
                     v5::settings::Setting::NotSet => v6::Setting::NotSet,
                 }
             },
-            stop_words: settings.stop_words.into(),
+            stop_words: match settings.stop_words {
+                v5::Setting::Set(words) => v6::Setting::Set(v6::StopWordsView::List(words)),
+                v5::Setting::Reset => v6::Setting::Reset,
+                v5::Setting::NotSet => v6::Setting::NotSet,
+            },
+            index_stop_words: v6::Setting::NotSet,
             non_separator_tokens: v6::Setting::NotSet,
             separator_tokens: v6::Setting::NotSet,
             dictionary: v6::Setting::NotSet,
@@ -358,6 +381,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     },
                     disable_on_words: typo.disable_on_words.into(),
                     disable_on_attributes: typo.disable_on_attributes.into(),
+                    disable_on_numbers: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -377,6 +401,24 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
             },
+            search_cutoff_ms: v6::Setting::NotSet,
+            proximity_precision: v6::Setting::NotSet,
+            prefix_search: v6::Setting::NotSet,
+            split_words: v6::Setting::NotSet,
+            concatenate_words: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
+            normalize_numbers: v6::Setting::NotSet,
+            normalization: v6::Setting::NotSet,
+            percolate_queries: v6::Setting::NotSet,
+            stemming: v6::Setting::NotSet,
+            geo_fields: v6::Setting::NotSet,
+            geo_index: v6::Setting::NotSet,
+            binary_quantized_vectors: v6::Setting::NotSet,
+            vector_index: v6::Setting::NotSet,
+            reranker: v6::Setting::NotSet,
+            version_attribute: v6::Setting::NotSet,
+            document_timestamps: v6::Setting::NotSet,
+            facet_search: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
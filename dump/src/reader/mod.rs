@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
 
@@ -114,6 +115,20 @@ impl DumpReader {
             DumpReader::Compat(compat) => compat.features(),
         }
     }
+
+    pub fn index_templates(&self) -> Result<BTreeMap<String, v6::IndexTemplate>> {
+        match self {
+            DumpReader::Current(current) => Ok(current.index_templates()),
+            DumpReader::Compat(compat) => compat.index_templates(),
+        }
+    }
+
+    pub fn schedules(&self) -> Result<BTreeMap<String, v6::Schedule>> {
+        match self {
+            DumpReader::Current(current) => Ok(current.schedules()),
+            DumpReader::Compat(compat) => compat.schedules(),
+        }
+    }
 }
 
 impl From<V6Reader> for DumpReader {
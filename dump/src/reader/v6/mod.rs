@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, ErrorKind};
 use std::path::Path;
@@ -20,6 +21,8 @@ pub type Unchecked = meilisearch_types::settings::Unchecked;
 pub type Task = crate::TaskDump;
 pub type Key = meilisearch_types::keys::Key;
 pub type RuntimeTogglableFeatures = meilisearch_types::features::RuntimeTogglableFeatures;
+pub type IndexTemplate = meilisearch_types::index_templates::IndexTemplate;
+pub type Schedule = meilisearch_types::schedules::Schedule;
 
 // ===== Other types to clarify the code of the compat module
 // everything related to the tasks
@@ -33,6 +36,7 @@ pub type TypoTolerance = meilisearch_types::settings::TypoSettings;
 pub type MinWordSizeForTypos = meilisearch_types::settings::MinWordSizeTyposSetting;
 pub type FacetingSettings = meilisearch_types::settings::FacetingSettings;
 pub type PaginationSettings = meilisearch_types::settings::PaginationSettings;
+pub type StopWordsView = meilisearch_types::settings::StopWordsView;
 
 // everything related to the api keys
 pub type Action = meilisearch_types::keys::Action;
@@ -50,6 +54,8 @@ pub struct V6Reader {
     tasks: BufReader<File>,
     keys: BufReader<File>,
     features: Option<RuntimeTogglableFeatures>,
+    index_templates: BTreeMap<String, IndexTemplate>,
+    schedules: BTreeMap<String, Schedule>,
 }
 
 impl V6Reader {
@@ -78,12 +84,28 @@ impl V6Reader {
             None
         };
 
+        let index_templates = match fs::read(dump.path().join("index-templates.json")) {
+            Ok(templates_file) => serde_json::from_reader(&*templates_file)?,
+            // The dumps created before the index templates don't contain any.
+            Err(error) if error.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error.into()),
+        };
+
+        let schedules = match fs::read(dump.path().join("schedules.json")) {
+            Ok(schedules_file) => serde_json::from_reader(&*schedules_file)?,
+            // The dumps created before the schedules don't contain any.
+            Err(error) if error.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error.into()),
+        };
+
         Ok(V6Reader {
             metadata: serde_json::from_reader(&*meta_file)?,
             instance_uid,
             tasks: BufReader::new(File::open(dump.path().join("tasks").join("queue.jsonl"))?),
             keys: BufReader::new(File::open(dump.path().join("keys.jsonl"))?),
             features,
+            index_templates,
+            schedules,
             dump,
         })
     }
@@ -154,6 +176,14 @@ impl V6Reader {
     pub fn features(&self) -> Option<RuntimeTogglableFeatures> {
         self.features
     }
+
+    pub fn index_templates(&self) -> BTreeMap<String, IndexTemplate> {
+        self.index_templates.clone()
+    }
+
+    pub fn schedules(&self) -> BTreeMap<String, Schedule> {
+        self.schedules.clone()
+    }
 }
 
 pub struct UpdateFile {
@@ -210,7 +240,49 @@ impl V6IndexReader {
     }
 
     pub fn settings(&mut self) -> Result<Settings<Checked>> {
-        let settings: Settings<Unchecked> = serde_json::from_reader(&mut self.settings)?;
+        let mut settings: Settings<Unchecked> = serde_json::from_reader(&mut self.settings)?;
+        if let Setting::Set(ranking_rules) = settings.ranking_rules {
+            settings.ranking_rules = Setting::Set(dedup_ranking_rules(ranking_rules));
+        }
         Ok(settings.check())
     }
 }
+
+/// Drops the ranking rules that are present more than once or that apply to an attribute
+/// already used by a previous custom ranking rule.
+///
+/// The older versions accepted them, they would otherwise make the import of the settings fail.
+pub(crate) fn dedup_ranking_rules(
+    ranking_rules: impl IntoIterator<Item = RankingRuleView>,
+) -> Vec<RankingRuleView> {
+    let mut deduped: Vec<RankingRuleView> = Vec::new();
+    for rule in ranking_rules {
+        if deduped.contains(&rule) {
+            log::warn!("Error while importing settings. The ranking rule `{rule}` is present more than once, only its first occurrence is kept.");
+        } else if let Some(field) =
+            rule.field_name().filter(|&field| deduped.iter().any(|r| r.field_name() == Some(field)))
+        {
+            log::warn!("Error while importing settings. The ranking rule `{rule}` applies to the `{field}` attribute which is already used by another custom ranking rule, it is ignored.");
+        } else {
+            deduped.push(rule);
+        }
+    }
+    deduped
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn dedup_ranking_rules_keeps_the_first_occurrences() {
+        let ranking_rules = ["words", "price:asc", "typo", "words", "price:desc", "rank:desc"]
+            .into_iter()
+            .map(|rule| RankingRuleView::from_str(rule).unwrap());
+        let deduped: Vec<_> =
+            dedup_ranking_rules(ranking_rules).into_iter().map(|rule| rule.to_string()).collect();
+        assert_eq!(deduped, ["words", "price:asc", "typo", "rank:desc"]);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
@@ -5,7 +6,9 @@ use std::path::PathBuf;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use meilisearch_types::features::RuntimeTogglableFeatures;
+use meilisearch_types::index_templates::IndexTemplate;
 use meilisearch_types::keys::Key;
+use meilisearch_types::schedules::Schedule;
 use meilisearch_types::settings::{Checked, Settings};
 use serde_json::{Map, Value};
 use tempfile::TempDir;
@@ -61,6 +64,23 @@ impl DumpWriter {
         )?)
     }
 
+    pub fn create_index_templates(
+        &self,
+        templates: &BTreeMap<String, IndexTemplate>,
+    ) -> Result<()> {
+        Ok(std::fs::write(
+            self.dir.path().join("index-templates.json"),
+            serde_json::to_string(templates)?,
+        )?)
+    }
+
+    pub fn create_schedules(&self, schedules: &BTreeMap<String, Schedule>) -> Result<()> {
+        Ok(std::fs::write(
+            self.dir.path().join("schedules.json"),
+            serde_json::to_string(schedules)?,
+        )?)
+    }
+
     pub fn persist_to(self, mut writer: impl Write) -> Result<()> {
         let gz_encoder = GzEncoder::new(&mut writer, Compression::default());
         let mut tar_encoder = tar::Builder::new(gz_encoder);
@@ -2,6 +2,8 @@ use std::fs::File as StdFile;
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use tempfile::NamedTempFile;
 use uuid::Uuid;
@@ -39,13 +41,17 @@ impl DerefMut for File {
 #[derive(Clone, Debug)]
 pub struct FileStore {
     path: PathBuf,
+    /// The size, in bytes, of the persisted update files.
+    size: Arc<AtomicU64>,
 }
 
 impl FileStore {
     pub fn new(path: impl AsRef<Path>) -> Result<FileStore> {
         let path = path.as_ref().to_path_buf();
         std::fs::create_dir_all(&path)?;
-        Ok(FileStore { path })
+        let store = FileStore { path, size: Arc::default() };
+        store.size.store(store.compute_total_size()?, Ordering::Relaxed);
+        Ok(store)
     }
 }
 
@@ -56,7 +62,7 @@ impl FileStore {
         let file = NamedTempFile::new_in(&self.path)?;
         let uuid = Uuid::new_v4();
         let path = self.path.join(uuid.to_string());
-        let update_file = File { file, path };
+        let update_file = File { file, path, store_size: self.size.clone() };
 
         Ok((uuid, update_file))
     }
@@ -67,7 +73,7 @@ impl FileStore {
         let file = NamedTempFile::new_in(&self.path)?;
         let uuid = Uuid::from_u128(uuid);
         let path = self.path.join(uuid.to_string());
-        let update_file = File { file, path };
+        let update_file = File { file, path, store_size: self.size.clone() };
 
         Ok((uuid, update_file))
     }
@@ -108,9 +114,16 @@ impl FileStore {
         Ok(self.get_update(uuid)?.metadata()?.len())
     }
 
+    /// Returns the size, in bytes, of the persisted update files, without reading the disk.
+    pub fn size(&self) -> u64 {
+        self.size.load(Ordering::Relaxed)
+    }
+
     pub fn delete(&self, uuid: Uuid) -> Result<()> {
         let path = self.path.join(uuid.to_string());
+        let size = std::fs::metadata(&path)?.len();
         std::fs::remove_file(path)?;
+        sub_size(&self.size, size);
         Ok(())
     }
 
@@ -137,15 +150,28 @@ impl FileStore {
 pub struct File {
     path: PathBuf,
     file: NamedTempFile,
+    /// The size of the store the file is persisted in.
+    store_size: Arc<AtomicU64>,
 }
 
 impl File {
     pub fn persist(self) -> Result<()> {
+        let size = self.file.as_file().metadata()?.len();
+        // the file replaces the previous update file with the same uuid
+        let replaced_size = std::fs::metadata(&self.path).map_or(0, |metadata| metadata.len());
         self.file.persist(&self.path)?;
+        self.store_size.fetch_add(size, Ordering::Relaxed);
+        sub_size(&self.store_size, replaced_size);
         Ok(())
     }
 }
 
+fn sub_size(store_size: &AtomicU64, size: u64) {
+    let _ = store_size.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        Some(current.saturating_sub(size))
+    });
+}
+
 #[cfg(test)]
 mod test {
     use std::io::Write;
@@ -175,4 +201,29 @@ mod test {
         expected.sort();
         assert_eq!(all_uuids, expected);
     }
+
+    #[test]
+    fn size() {
+        let dir = TempDir::new().unwrap();
+        let fs = FileStore::new(dir.path()).unwrap();
+        let (uuid, mut file) = fs.new_update().unwrap();
+        file.write_all(b"Hello world").unwrap();
+        assert_eq!(fs.size(), 0);
+        file.persist().unwrap();
+        assert_eq!(fs.size(), 11);
+
+        let (uuid2, mut file) = fs.new_update().unwrap();
+        file.write_all(b"Hello").unwrap();
+        file.persist().unwrap();
+        assert_eq!(fs.size(), 16);
+
+        // the size of the existing files is computed when the store is opened
+        let reopened = FileStore::new(dir.path()).unwrap();
+        assert_eq!(reopened.size(), 16);
+
+        fs.delete(uuid).unwrap();
+        assert_eq!(fs.size(), 5);
+        fs.delete(uuid2).unwrap();
+        assert_eq!(fs.size(), 0);
+    }
 }
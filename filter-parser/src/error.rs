@@ -58,9 +58,12 @@ pub enum ExpectedValueKind {
 pub enum ErrorKind<'a> {
     ReservedGeo(&'a str),
     GeoRadius,
+    InvalidGeohash,
     GeoBoundingBox,
+    GeoIntersects,
     MisusedGeoRadius,
     MisusedGeoBoundingBox,
+    MisusedGeoIntersects,
     InvalidPrimary,
     InvalidEscapedNumber,
     ExpectedEof,
@@ -155,11 +158,17 @@ impl<'a> Display for Error<'a> {
                 writeln!(f, "Found unexpected characters at the end of the filter: `{}`. You probably forgot an `OR` or an `AND` rule.", escaped_input)?
             }
             ErrorKind::GeoRadius => {
-                writeln!(f, "The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`.")?
+                writeln!(f, "The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`, or two when the center is a geohash or a document: `_geoRadius(geohash:u09t, radius)` or `_geoRadius(document:id, radius)`.")?
+            }
+            ErrorKind::InvalidGeohash => {
+                writeln!(f, "The geohash `{}` is invalid. A geohash only contains the characters `0123456789bcdefghjkmnpqrstuvwxyz`.", escaped_input)?
             }
             ErrorKind::GeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter expects two pairs of arguments: `_geoBoundingBox([latitude, longitude], [latitude, longitude])`.")?
             }
+            ErrorKind::GeoIntersects => {
+                writeln!(f, "The `_geoIntersects` filter expects a polygon of at least three points: `_geoIntersects([[latitude, longitude], [latitude, longitude], [latitude, longitude]])`.")?
+            }
             ErrorKind::ReservedGeo(name) => {
                 writeln!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.", name.escape_debug())?
             }
@@ -169,6 +178,9 @@ impl<'a> Display for Error<'a> {
             ErrorKind::MisusedGeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter is an operation and can't be used as a value.")?
             }
+            ErrorKind::MisusedGeoIntersects => {
+                writeln!(f, "The `_geoIntersects` filter is an operation and can't be used as a value.")?
+            }
             ErrorKind::ReservedKeyword(word) => {
                 writeln!(f, "`{word}` is a reserved keyword and thus cannot be used as a field name unless it is put inside quotes. Use \"{word}\" or \'{word}\' instead.")?
             }
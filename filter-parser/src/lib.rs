@@ -17,8 +17,12 @@
 //! singleQuoted   = "'" .* all but quotes "'"
 //! doubleQuoted   = "\"" .* all but double quotes "\""
 //! word           = (alphanumeric | _ | - | .)+
-//! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
-//! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoRadius      = "_geoRadius(" WS* (word WS* "," WS*)? ((float WS* "," WS* float) | geohash | document) WS* "," distance WS* ")"
+//! distance       = float ("m" | "km" | "mi")?
+//! geohash        = "geohash:" alphanumeric+
+//! document       = "document:" (alphanumeric | _ | -)+
+//! geoBoundingBox = "_geoBoundingBox(" WS* (word WS* "," WS*)? "[" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoIntersects  = "_geoIntersects([" WS* "[" WS* float WS* "," WS* float WS* "]" ("," WS* "[" WS* float WS* "," WS* float WS* "]")+ WS* "])"
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -54,12 +58,12 @@ use condition::{
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{char, multispace0};
-use nom::combinator::{cut, eof, map, opt};
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{alphanumeric1, char, multispace0};
+use nom::combinator::{consumed, cut, eof, map, opt};
 use nom::multi::{many0, separated_list1};
 use nom::number::complete::recognize_float;
-use nom::sequence::{delimited, preceded, terminated, tuple};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::Finish;
 use nom_locate::LocatedSpan;
 pub(crate) use value::parse_value;
@@ -139,12 +143,33 @@ impl<'a> From<&'a str> for Token<'a> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterCondition<'a> {
     Not(Box<Self>),
-    Condition { fid: Token<'a>, op: Condition<'a> },
-    In { fid: Token<'a>, els: Vec<Token<'a>> },
+    Condition {
+        fid: Token<'a>,
+        op: Condition<'a>,
+    },
+    In {
+        fid: Token<'a>,
+        els: Vec<Token<'a>>,
+    },
     Or(Vec<Self>),
     And(Vec<Self>),
-    GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
-    GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    GeoLowerThan {
+        field: Option<Token<'a>>,
+        point: [Token<'a>; 2],
+        radius: Token<'a>,
+    },
+    GeoLowerThanDocument {
+        document_id: Token<'a>,
+        radius: Token<'a>,
+    },
+    GeoBoundingBox {
+        field: Option<Token<'a>>,
+        top_right_point: [Token<'a>; 2],
+        bottom_left_point: [Token<'a>; 2],
+    },
+    GeoIntersects {
+        polygon: Vec<[Token<'a>; 2]>,
+    },
 }
 
 impl<'a> FilterCondition<'a> {
@@ -171,6 +196,12 @@ impl<'a> FilterCondition<'a> {
                 None
             }
             FilterCondition::GeoLowerThan { point: [point, _], .. } if depth == 0 => Some(point),
+            FilterCondition::GeoLowerThanDocument { document_id, .. } if depth == 0 => {
+                Some(document_id)
+            }
+            FilterCondition::GeoIntersects { polygon } if depth == 0 => {
+                polygon.first().map(|[lat, _]| lat)
+            }
             _ => None,
         }
     }
@@ -316,31 +347,123 @@ fn parse_not(input: Span, depth: usize) -> IResult<FilterCondition> {
     ))(input)
 }
 
-/// geoRadius      = WS* "_geoRadius(float WS* "," WS* float WS* "," WS* float)
+/// An argument of the `_geoRadius` filter.
+enum GeoRadiusArg<'a> {
+    Float(Span<'a>),
+    /// A number followed by a distance unit, along with its value in meters.
+    Distance(Span<'a>, Option<f64>),
+    Geohash(Span<'a>),
+    Document(Span<'a>),
+    Field(Span<'a>),
+}
+
+/// The base 32 alphabet of the geohashes.
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Decodes a geohash into the latitude and longitude of the center of its cell,
+/// returns `None` if the geohash contains a character outside of the alphabet.
+fn decode_geohash(geohash: &str) -> Option<[f64; 2]> {
+    let mut lat = (-90.0, 90.0);
+    let mut lng = (-180.0, 180.0);
+    // the bits alternately halve the longitude and the latitude, starting with the longitude
+    let mut is_lng = true;
+    for c in geohash.bytes() {
+        let bits = GEOHASH_ALPHABET.iter().position(|&b| b == c.to_ascii_lowercase())?;
+        for shift in (0..5).rev() {
+            let range: &mut (f64, f64) = if is_lng { &mut lng } else { &mut lat };
+            let middle = (range.0 + range.1) / 2.0;
+            if bits >> shift & 1 == 1 {
+                range.0 = middle;
+            } else {
+                range.1 = middle;
+            }
+            is_lng = !is_lng;
+        }
+    }
+    Some([(lat.0 + lat.1) / 2.0, (lng.0 + lng.1) / 2.0])
+}
+
+/// geoRadius      = WS* "_geoRadius(" (word WS* "," WS*)? ((float WS* "," WS* float) | geohash | document) WS* "," WS* distance)
 /// If we parse `_geoRadius` we MUST parse the rest of the expression.
 fn parse_geo_radius(input: Span) -> IResult<FilterCondition> {
+    let arg = alt((
+        map(preceded(tag("geohash:"), alphanumeric1), GeoRadiusArg::Geohash),
+        map(
+            preceded(
+                tag("document:"),
+                take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            ),
+            GeoRadiusArg::Document,
+        ),
+        map(consumed(pair(recognize_float, distance_unit)), |(distance, (number, meters))| {
+            GeoRadiusArg::Distance(
+                distance,
+                number.fragment().parse::<f64>().ok().map(|n| n * meters),
+            )
+        }),
+        map(recognize_float, GeoRadiusArg::Float),
+        map(
+            take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'),
+            GeoRadiusArg::Field,
+        ),
+    ));
     // we want to allow space BEFORE the _geoRadius but not after
     let parsed = preceded(
         tuple((multispace0, word_exact("_geoRadius"))),
         // if we were able to parse `_geoRadius` and can't parse the rest of the input we return a failure
-        cut(delimited(char('('), separated_list1(tag(","), ws(recognize_float)), char(')'))),
+        cut(delimited(char('('), separated_list1(tag(","), ws(arg)), char(')'))),
     )(input)
     .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoRadius)));
 
     let (input, args) = parsed?;
 
-    if args.len() != 3 {
-        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoRadius)));
-    }
+    // the first argument may name the geo field holding the points
+    let (field, args) = match args.as_slice() {
+        [GeoRadiusArg::Field(field), args @ ..] => (Some(Token::from(*field)), args),
+        args => (None, args),
+    };
 
-    let res = FilterCondition::GeoLowerThan {
-        point: [args[0].into(), args[1].into()],
-        radius: args[2].into(),
+    // the radius is the last argument, it is converted to meters when it has a unit
+    let (args, radius) = match args {
+        [args @ .., GeoRadiusArg::Float(radius)] => (args, Token::from(*radius)),
+        [args @ .., GeoRadiusArg::Distance(radius, meters)] => {
+            (args, Token::new(*radius, meters.map(|meters| meters.to_string())))
+        }
+        _ => return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoRadius))),
+    };
+
+    let res = match args {
+        [GeoRadiusArg::Float(lat), GeoRadiusArg::Float(lng)] => {
+            FilterCondition::GeoLowerThan { field, point: [(*lat).into(), (*lng).into()], radius }
+        }
+        // the geohash is resolved right away to the center of its cell
+        [GeoRadiusArg::Geohash(geohash)] => {
+            let [lat, lng] = decode_geohash(geohash.fragment()).ok_or_else(|| {
+                nom::Err::Failure(Error::new_from_kind(*geohash, ErrorKind::InvalidGeohash))
+            })?;
+            FilterCondition::GeoLowerThan {
+                field,
+                point: [
+                    Token::new(*geohash, Some(lat.to_string())),
+                    Token::new(*geohash, Some(lng.to_string())),
+                ],
+                radius,
+            }
+        }
+        [GeoRadiusArg::Document(document_id)] if field.is_none() => {
+            FilterCondition::GeoLowerThanDocument { document_id: (*document_id).into(), radius }
+        }
+        _ => return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoRadius))),
     };
     Ok((input, res))
 }
 
-/// geoBoundingBox      = WS* "_geoBoundingBox([float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "]")
+/// Parses the unit of a distance into its length in meters.
+fn distance_unit(input: Span) -> IResult<f64> {
+    alt((map(tag("km"), |_| 1000.0), map(tag("mi"), |_| 1609.344), map(tag("m"), |_| 1.0)))(input)
+}
+
+/// geoBoundingBox      = WS* "_geoBoundingBox(" (word WS* "," WS*)? "[float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "]")
 /// If we parse `_geoBoundingBox` we MUST parse the rest of the expression.
 fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     // we want to allow space BEFORE the _geoBoundingBox but not after
@@ -349,28 +472,72 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
         // if we were able to parse `_geoBoundingBox` and can't parse the rest of the input we return a failure
         cut(delimited(
             char('('),
-            separated_list1(
-                tag(","),
-                ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']'))),
+            pair(
+                // the first argument may name the geo field holding the points
+                opt(terminated(
+                    ws(take_while1(|c: char| {
+                        c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'
+                    })),
+                    tag(","),
+                )),
+                separated_list1(
+                    tag(","),
+                    ws(delimited(
+                        char('['),
+                        separated_list1(tag(","), ws(recognize_float)),
+                        char(']'),
+                    )),
+                ),
             ),
             char(')'),
         )),
     )(input)
     .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoBoundingBox)));
 
-    let (input, args) = parsed?;
+    let (input, (field, args)) = parsed?;
 
     if args.len() != 2 || args[0].len() != 2 || args[1].len() != 2 {
         return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoBoundingBox)));
     }
 
     let res = FilterCondition::GeoBoundingBox {
+        field: field.map(Token::from),
         top_right_point: [args[0][0].into(), args[0][1].into()],
         bottom_left_point: [args[1][0].into(), args[1][1].into()],
     };
     Ok((input, res))
 }
 
+/// geoIntersects      = WS* "_geoIntersects([" ("[" float WS* "," WS* float "]") ("," WS* "[" float WS* "," WS* float "]")+ "])"
+/// If we parse `_geoIntersects` we MUST parse the rest of the expression.
+fn parse_geo_intersects(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoIntersects but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoIntersects"))),
+        // if we were able to parse `_geoIntersects` and can't parse the rest of the input we return a failure
+        cut(delimited(
+            tuple((char('('), multispace0, char('['))),
+            separated_list1(
+                tag(","),
+                ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']'))),
+            ),
+            tuple((char(']'), multispace0, char(')'))),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoIntersects)));
+
+    let (input, args) = parsed?;
+
+    if args.len() < 3 || args.iter().any(|point| point.len() != 2) {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoIntersects)));
+    }
+
+    let res = FilterCondition::GeoIntersects {
+        polygon: args.into_iter().map(|point| [point[0].into(), point[1].into()]).collect(),
+    };
+    Ok((input, res))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -442,6 +609,7 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         ),
         parse_geo_radius,
         parse_geo_bounding_box,
+        parse_geo_intersects,
         parse_in,
         parse_not_in,
         parse_condition,
@@ -502,22 +670,41 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                 }
                 write!(f, "]")
             }
-            FilterCondition::GeoLowerThan { point, radius } => {
+            FilterCondition::GeoLowerThan { field: Some(field), point, radius } => {
+                write!(f, "_geoRadius({}, {}, {}, {})", field, point[0], point[1], radius)
+            }
+            FilterCondition::GeoLowerThan { field: None, point, radius } => {
                 write!(f, "_geoRadius({}, {}, {})", point[0], point[1], radius)
             }
+            FilterCondition::GeoLowerThanDocument { document_id, radius } => {
+                write!(f, "_geoRadius(document:{}, {})", document_id, radius)
+            }
             FilterCondition::GeoBoundingBox {
+                field,
                 top_right_point: top_left_point,
                 bottom_left_point: bottom_right_point,
             } => {
+                if let Some(field) = field {
+                    write!(f, "_geoBoundingBox({}, ", field)?;
+                } else {
+                    write!(f, "_geoBoundingBox(")?;
+                }
                 write!(
                     f,
-                    "_geoBoundingBox([{}, {}], [{}, {}])",
+                    "[{}, {}], [{}, {}])",
                     top_left_point[0],
                     top_left_point[1],
                     bottom_right_point[0],
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::GeoIntersects { polygon } => {
+                write!(f, "_geoIntersects([")?;
+                for [lat, lng] in polygon {
+                    write!(f, "[{lat}, {lng}], ")?;
+                }
+                write!(f, "])")
+            }
         }
     }
 }
@@ -647,11 +834,25 @@ pub mod tests {
         insta::assert_display_snapshot!(p("_geoRadius(12, 13, 14)"), @"_geoRadius({12}, {13}, {14})");
         insta::assert_display_snapshot!(p("NOT _geoRadius(12, 13, 14)"), @"NOT (_geoRadius({12}, {13}, {14}))");
         insta::assert_display_snapshot!(p("_geoRadius(12,13,14)"), @"_geoRadius({12}, {13}, {14})");
+        insta::assert_display_snapshot!(p("_geoRadius(geohash:u09t, 500)"), @"_geoRadius({48.779296875}, {2.28515625}, {500})");
+        insta::assert_display_snapshot!(p("_geoRadius( geohash:U09T , 500)"), @"_geoRadius({48.779296875}, {2.28515625}, {500})");
+        insta::assert_display_snapshot!(p("_geoRadius(document:paris-12, 500)"), @"_geoRadius(document:{paris-12}, {500})");
+        insta::assert_display_snapshot!(p("_geoRadius(48.8, 2.3, 5km)"), @"_geoRadius({48.8}, {2.3}, {5000})");
+        insta::assert_display_snapshot!(p("_geoRadius(48.8, 2.3, 3mi)"), @"_geoRadius({48.8}, {2.3}, {4828.032})");
+        insta::assert_display_snapshot!(p("_geoRadius(48.8, 2.3, 1.5km )"), @"_geoRadius({48.8}, {2.3}, {1500})");
+        insta::assert_display_snapshot!(p("_geoRadius(geohash:u09t, 200m)"), @"_geoRadius({48.779296875}, {2.28515625}, {200})");
+        insta::assert_display_snapshot!(p("_geoRadius(document:paris-12, 0.5mi)"), @"_geoRadius(document:{paris-12}, {804.672})");
+        insta::assert_display_snapshot!(p("_geoRadius(pickup, 12, 13, 14)"), @"_geoRadius({pickup}, {12}, {13}, {14})");
+        insta::assert_display_snapshot!(p("_geoRadius( dropoff ,geohash:u09t, 500)"), @"_geoRadius({dropoff}, {48.779296875}, {2.28515625}, {500})");
+        insta::assert_display_snapshot!(p("_geoIntersects([[0, 0], [0, 10], [10, 10]])"), @"_geoIntersects([[{0}, {0}], [{0}, {10}], [{10}, {10}], ])");
+        insta::assert_display_snapshot!(p("_geoIntersects( [ [0,0],[0, 10] ,[10,10.5] ] )"), @"_geoIntersects([[{0}, {0}], [{0}, {10}], [{10}, {10.5}], ])");
 
         // Test geo bounding box
         insta::assert_display_snapshot!(p("_geoBoundingBox([12, 13], [14, 15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
         insta::assert_display_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_display_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
+        insta::assert_display_snapshot!(p("_geoBoundingBox(pickup, [12, 13], [14, 15])"), @"_geoBoundingBox({pickup}, [{12}, {13}], [{14}, {15}])");
+        insta::assert_display_snapshot!(p("_geoBoundingBox( dropoff ,[12,13],[14,15])"), @"_geoBoundingBox({dropoff}, [{12}, {13}], [{14}, {15}])");
 
         // Test OR + AND
         insta::assert_display_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -730,15 +931,25 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("_geoRadius"), @r###"
-        The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`.
+        The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`, or two when the center is a geohash or a document: `_geoRadius(geohash:u09t, radius)` or `_geoRadius(document:id, radius)`.
         1:11 _geoRadius
         "###);
 
         insta::assert_display_snapshot!(p("_geoRadius = 12"), @r###"
-        The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`.
+        The `_geoRadius` filter expects three arguments: `_geoRadius(latitude, longitude, radius)`, or two when the center is a geohash or a document: `_geoRadius(geohash:u09t, radius)` or `_geoRadius(document:id, radius)`.
         1:16 _geoRadius = 12
         "###);
 
+        insta::assert_display_snapshot!(p("_geoIntersects([[0, 0], [0, 10]])"), @r###"
+        The `_geoIntersects` filter expects a polygon of at least three points: `_geoIntersects([[latitude, longitude], [latitude, longitude], [latitude, longitude]])`.
+        34:34 _geoIntersects([[0, 0], [0, 10]])
+        "###);
+
+        insta::assert_display_snapshot!(p("position = _geoIntersects([[0, 0], [0, 10], [10, 10]])"), @r###"
+        The `_geoIntersects` filter is an operation and can't be used as a value.
+        12:55 position = _geoIntersects([[0, 0], [0, 10], [10, 10]])
+        "###);
+
         insta::assert_display_snapshot!(p("_geoBoundingBox"), @r###"
         The `_geoBoundingBox` filter expects two pairs of arguments: `_geoBoundingBox([latitude, longitude], [latitude, longitude])`.
         1:16 _geoBoundingBox
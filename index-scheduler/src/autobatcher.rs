@@ -8,7 +8,7 @@ The main function of the autobatcher is [`next_autobatch`].
 use std::ops::ControlFlow::{self, Break, Continue};
 
 use meilisearch_types::milli::update::IndexDocumentsMethod::{
-    self, ReplaceDocuments, UpdateDocuments,
+    self, MergeDocuments, ReplaceDocuments, UpdateDocuments,
 };
 use meilisearch_types::tasks::TaskId;
 
@@ -24,6 +24,10 @@ enum AutobatchKind {
         allow_index_creation: bool,
         primary_key: Option<String>,
     },
+    DocumentAdditionFromUrl {
+        allow_index_creation: bool,
+    },
+    DocumentAdditionDryRun,
     DocumentDeletion,
     DocumentDeletionByFilter,
     DocumentClear,
@@ -33,6 +37,8 @@ enum AutobatchKind {
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
+    IndexDuplication,
+    IndexCompaction,
     IndexSwap,
 }
 
@@ -41,6 +47,7 @@ impl AutobatchKind {
     fn allow_index_creation(&self) -> Option<bool> {
         match self {
             AutobatchKind::DocumentImport { allow_index_creation, .. }
+            | AutobatchKind::DocumentAdditionFromUrl { allow_index_creation }
             | AutobatchKind::Settings { allow_index_creation, .. } => Some(*allow_index_creation),
             _ => None,
         }
@@ -63,6 +70,10 @@ impl From<KindWithContent> for AutobatchKind {
                 primary_key,
                 ..
             } => AutobatchKind::DocumentImport { method, allow_index_creation, primary_key },
+            KindWithContent::DocumentAdditionFromUrl { allow_index_creation, .. } => {
+                AutobatchKind::DocumentAdditionFromUrl { allow_index_creation }
+            }
+            KindWithContent::DocumentAdditionDryRun { .. } => AutobatchKind::DocumentAdditionDryRun,
             KindWithContent::DocumentDeletion { .. } => AutobatchKind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => AutobatchKind::DocumentClear,
             KindWithContent::DocumentDeletionByFilter { .. } => {
@@ -76,6 +87,8 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexDeletion { .. } => AutobatchKind::IndexDeletion,
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
+            KindWithContent::IndexDuplication { .. } => AutobatchKind::IndexDuplication,
+            KindWithContent::IndexCompaction { .. } => AutobatchKind::IndexCompaction,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
@@ -104,6 +117,12 @@ pub enum BatchKind {
     DocumentDeletionByFilter {
         id: TaskId,
     },
+    DocumentAdditionFromUrl {
+        id: TaskId,
+    },
+    DocumentAdditionDryRun {
+        id: TaskId,
+    },
     ClearAndSettings {
         other: Vec<TaskId>,
         allow_index_creation: bool,
@@ -129,6 +148,12 @@ pub enum BatchKind {
     IndexUpdate {
         id: TaskId,
     },
+    IndexDuplication {
+        id: TaskId,
+    },
+    IndexCompaction {
+        id: TaskId,
+    },
     IndexSwap {
         id: TaskId,
     },
@@ -175,6 +200,9 @@ impl BatchKind {
             K::IndexDeletion => (Break(BatchKind::IndexDeletion { ids: vec![task_id] }), false),
             K::IndexUpdate => (Break(BatchKind::IndexUpdate { id: task_id }), false),
             K::IndexSwap => (Break(BatchKind::IndexSwap { id: task_id }), false),
+            // the new index is created by copying the duplicated one
+            K::IndexDuplication => (Break(BatchKind::IndexDuplication { id: task_id }), false),
+            K::IndexCompaction => (Break(BatchKind::IndexCompaction { id: task_id }), false),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { method, allow_index_creation, primary_key: pk }
                 if primary_key.is_none() || pk.is_none() || primary_key == pk.as_deref() =>
@@ -205,6 +233,14 @@ impl BatchKind {
             K::DocumentDeletionByFilter => {
                 (Break(BatchKind::DocumentDeletionByFilter { id: task_id }), false)
             }
+            // the downloaded documents are indexed on their own
+            K::DocumentAdditionFromUrl { allow_index_creation } => {
+                (Break(BatchKind::DocumentAdditionFromUrl { id: task_id }), allow_index_creation)
+            }
+            // the validated documents are never indexed, so neither is the index created
+            K::DocumentAdditionDryRun => {
+                (Break(BatchKind::DocumentAdditionDryRun { id: task_id }), false)
+            }
             K::Settings { allow_index_creation } => (
                 Continue(BatchKind::Settings { allow_index_creation, settings_ids: vec![task_id] }),
                 allow_index_creation,
@@ -222,7 +258,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexDuplication | K::IndexCompaction | K::IndexSwap | K::DocumentDeletionByFilter | K::DocumentAdditionFromUrl { .. } | K::DocumentAdditionDryRun) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -320,6 +356,18 @@ impl BatchKind {
                     operation_ids,
                 })
             }
+            (
+                BatchKind::DocumentOperation { method: MergeDocuments, allow_index_creation, primary_key: _, mut operation_ids },
+                K::DocumentImport { method: MergeDocuments, primary_key: pk, .. },
+            ) => {
+                operation_ids.push(id);
+                Continue(BatchKind::DocumentOperation {
+                    method: MergeDocuments,
+                    allow_index_creation,
+                    primary_key: pk,
+                    operation_ids,
+                })
+            }
             (
                 BatchKind::DocumentOperation { method, allow_index_creation, primary_key, mut operation_ids },
                 K::DocumentDeletion,
@@ -495,6 +543,19 @@ impl BatchKind {
                     operation_ids,
                 })
             }
+            (
+                BatchKind::SettingsAndDocumentOperation { settings_ids, method: MergeDocuments, allow_index_creation, primary_key: _, mut operation_ids },
+                K::DocumentImport { method: MergeDocuments, primary_key: pk2, .. },
+            ) => {
+                operation_ids.push(id);
+                Continue(BatchKind::SettingsAndDocumentOperation {
+                    settings_ids,
+                    method: MergeDocuments,
+                    allow_index_creation,
+                    primary_key: pk2,
+                    operation_ids,
+                })
+            }
             // But we can't batch a settings and a doc op with another doc op
             // this MUST be AFTER the two previous branch
             (
@@ -518,8 +579,12 @@ impl BatchKind {
                 BatchKind::IndexCreation { .. }
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
+                | BatchKind::IndexDuplication { .. }
+                | BatchKind::IndexCompaction { .. }
                 | BatchKind::IndexSwap { .. }
-                | BatchKind::DocumentDeletionByFilter { .. },
+                | BatchKind::DocumentDeletionByFilter { .. }
+                | BatchKind::DocumentAdditionFromUrl { .. }
+                | BatchKind::DocumentAdditionDryRun { .. },
                 _,
             ) => {
                 unreachable!()
@@ -599,6 +664,7 @@ mod tests {
             content_file: Uuid::new_v4(),
             documents_count: 0,
             allow_index_creation,
+            duplicate_ids_policy: None,
         }
     }
 
@@ -640,6 +706,73 @@ mod tests {
         }
     }
 
+    fn idx_dup() -> KindWithContent {
+        KindWithContent::IndexDuplication {
+            index_uid: String::from("doggo"),
+            new_index_uid: String::from("catto"),
+        }
+    }
+
+    fn idx_compact() -> KindWithContent {
+        KindWithContent::IndexCompaction { index_uid: String::from("doggo") }
+    }
+
+    fn doc_url(allow_index_creation: bool) -> KindWithContent {
+        KindWithContent::DocumentAdditionFromUrl {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            method: ReplaceDocuments,
+            url: String::from("http://localhost/doggos.ndjson"),
+            authorization: None,
+            has_authorization: false,
+            format: meilisearch_types::document_formats::PayloadType::Ndjson,
+            allow_index_creation,
+            duplicate_ids_policy: None,
+        }
+    }
+
+    fn doc_dry_run() -> KindWithContent {
+        KindWithContent::DocumentAdditionDryRun {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            content_file: Uuid::new_v4(),
+            documents_count: 0,
+            duplicate_ids_policy: None,
+        }
+    }
+
+    #[test]
+    fn autobatch_document_addition_from_url() {
+        // the additions from an url are never batched with the other tasks
+        debug_snapshot!(autobatch_from(true, None, [doc_url(true), doc_url(true)]), @"Some((DocumentAdditionFromUrl { id: 0 }, true))");
+        debug_snapshot!(autobatch_from(false, None, [doc_url(false), doc_imp(ReplaceDocuments, true, None)]), @"Some((DocumentAdditionFromUrl { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_url(true)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), doc_url(true)]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
+    #[test]
+    fn autobatch_document_addition_dry_run() {
+        // the dry runs are never batched with the other tasks and never create the index
+        debug_snapshot!(autobatch_from(false, None, [doc_dry_run(), doc_dry_run()]), @"Some((DocumentAdditionDryRun { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_dry_run(), doc_imp(ReplaceDocuments, true, None)]), @"Some((DocumentAdditionDryRun { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_dry_run()]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+    }
+
+    #[test]
+    fn autobatch_index_duplication() {
+        // the duplications are never batched with the other tasks of the duplicated index
+        debug_snapshot!(autobatch_from(true, None, [idx_dup(), settings(true)]), @"Some((IndexDuplication { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), idx_dup()]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), idx_dup(), settings(true)]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
+    #[test]
+    fn autobatch_index_compaction() {
+        // the compactions are processed alone
+        debug_snapshot!(autobatch_from(true, None, [idx_compact(), settings(true)]), @"Some((IndexCompaction { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), idx_compact(), settings(true)]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
     #[test]
     fn autobatch_simple_operation_together() {
         // we can autobatch one or multiple `ReplaceDocuments` together.
@@ -668,6 +801,10 @@ mod tests {
         debug_snapshot!(autobatch_from(false,None,  [doc_imp(UpdateDocuments, false, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0] }, false))");
         debug_snapshot!(autobatch_from(false,None,  [doc_imp(UpdateDocuments, false, None), doc_imp(UpdateDocuments, false, None), doc_imp(UpdateDocuments, false, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0, 1, 2] }, false))");
 
+        // we can autobatch one or multiple `MergeDocuments` together but not with the other methods.
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(MergeDocuments, true, None), doc_imp(MergeDocuments, true, None)]), @"Some((DocumentOperation { method: MergeDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(MergeDocuments, true, None), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentOperation { method: MergeDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+
         // we can autobatch one or multiple DocumentDeletion together
         debug_snapshot!(autobatch_from(true, None, [doc_del()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
         debug_snapshot!(autobatch_from(true, None, [doc_del(), doc_del(), doc_del()]), @"Some((DocumentDeletion { deletion_ids: [0, 1, 2] }, false))");
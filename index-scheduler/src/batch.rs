@@ -17,24 +17,32 @@ tasks individally, but should be much faster since we are only performing
 one indexing operation.
 */
 
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::ffi::OsStr;
 use std::fs::{self, File};
-use std::io::BufWriter;
+use std::io::{BufWriter, Read, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
 
 use dump::IndexMetadata;
 use log::{debug, error, info};
+use meilisearch_types::document_formats::{
+    read_csv, read_json, read_ndjson, read_parquet, PayloadType,
+};
 use meilisearch_types::error::Code;
 use meilisearch_types::heed::{RoTxn, RwTxn};
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
 use meilisearch_types::milli::heed::CompactionOption;
+use meilisearch_types::milli::percolator::percolate;
 use meilisearch_types::milli::update::{
-    DeleteDocuments, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    validate_documents_batch, DeleteDocuments, DocumentDeletionResult, DocumentValidationError,
+    IndexDocumentsConfig, IndexDocumentsMethod, Settings as MilliSettings, UpdateIndexingStep,
 };
 use meilisearch_types::milli::{self, Filter, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, DocumentError, IndexSwap, Kind, KindWithContent, Status, Task,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
 use time::macros::format_description;
@@ -43,7 +51,19 @@ use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
 use crate::utils::{self, swap_index_uid_in_task};
-use crate::{Error, IndexScheduler, ProcessingTasks, Result, TaskId};
+use crate::{Error, IndexScheduler, Result, TaskId};
+
+/// The maximum number of invalid documents whose error is reported by a dry run.
+const MAX_DRY_RUN_DOCUMENT_ERRORS: usize = 100;
+
+/// The maximum time spent connecting to the url of a documents addition.
+const DOCUMENT_DOWNLOAD_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// The maximum time spent downloading the documents of a documents addition from an url.
+const DOCUMENT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+/// The size of the chunks in which the downloaded documents are written to the disk.
+const DOCUMENT_DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+/// The maximum number of redirections followed when downloading the documents from an url.
+const DOCUMENT_DOWNLOAD_MAX_REDIRECTS: usize = 10;
 
 /// Represents a combination of tasks that can all be processed at the same time.
 ///
@@ -55,10 +75,9 @@ pub(crate) enum Batch {
     TaskCancelation {
         /// The task cancelation itself.
         task: Task,
-        /// The date and time at which the previously processing tasks started.
-        previous_started_at: OffsetDateTime,
-        /// The list of tasks that were processing when this task cancelation appeared.
-        previous_processing_tasks: RoaringBitmap,
+        /// The list of tasks that were processing when this task cancelation appeared,
+        /// with the date and time at which their batch started.
+        previous_processing_tasks: Vec<(OffsetDateTime, RoaringBitmap)>,
     },
     TaskDeletion(Task),
     SnapshotCreation(Vec<Task>),
@@ -82,9 +101,25 @@ pub(crate) enum Batch {
         tasks: Vec<Task>,
         index_has_been_created: bool,
     },
+    IndexDuplication {
+        task: Task,
+    },
+    IndexCompaction {
+        index_uid: String,
+        task: Task,
+    },
     IndexSwap {
         task: Task,
     },
+    /// The documents are downloaded before being indexed like a document addition.
+    DocumentAdditionFromUrl {
+        task: Task,
+        must_create_index: bool,
+    },
+    /// The documents are validated without being indexed.
+    DocumentAdditionDryRun {
+        task: Task,
+    },
 }
 
 #[derive(Debug)]
@@ -150,35 +185,26 @@ pub(crate) enum IndexOperation {
 impl Batch {
     /// Return the task ids associated with this batch.
     pub fn ids(&self) -> Vec<TaskId> {
+        self.tasks().iter().map(|task| task.uid).collect()
+    }
+
+    /// Return the tasks associated with this batch.
+    pub fn tasks(&self) -> Vec<&Task> {
         match self {
             Batch::TaskCancelation { task, .. }
             | Batch::TaskDeletion(task)
             | Batch::Dump(task)
             | Batch::IndexCreation { task, .. }
-            | Batch::IndexUpdate { task, .. } => vec![task.uid],
+            | Batch::IndexUpdate { task, .. }
+            | Batch::IndexDuplication { task }
+            | Batch::IndexCompaction { task, .. } => vec![task],
             Batch::SnapshotCreation(tasks) | Batch::IndexDeletion { tasks, .. } => {
-                tasks.iter().map(|task| task.uid).collect()
+                tasks.iter().collect()
             }
-            Batch::IndexOperation { op, .. } => match op {
-                IndexOperation::DocumentOperation { tasks, .. }
-                | IndexOperation::DocumentDeletion { tasks, .. }
-                | IndexOperation::Settings { tasks, .. }
-                | IndexOperation::DocumentClear { tasks, .. } => {
-                    tasks.iter().map(|task| task.uid).collect()
-                }
-                IndexOperation::IndexDocumentDeletionByFilter { task, .. } => vec![task.uid],
-                IndexOperation::SettingsAndDocumentOperation {
-                    document_import_tasks: tasks,
-                    settings_tasks: other,
-                    ..
-                }
-                | IndexOperation::DocumentClearAndSetting {
-                    cleared_tasks: tasks,
-                    settings_tasks: other,
-                    ..
-                } => tasks.iter().chain(other).map(|task| task.uid).collect(),
-            },
-            Batch::IndexSwap { task } => vec![task.uid],
+            Batch::IndexOperation { op, .. } => op.tasks(),
+            Batch::IndexSwap { task }
+            | Batch::DocumentAdditionFromUrl { task, .. }
+            | Batch::DocumentAdditionDryRun { task } => vec![task],
         }
     }
 
@@ -194,12 +220,37 @@ impl Batch {
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid, .. }
             | IndexDeletion { index_uid, .. } => Some(index_uid),
+            DocumentAdditionFromUrl { task, .. }
+            | DocumentAdditionDryRun { task }
+            | IndexDuplication { task } => task.index_uid(),
         }
     }
 }
 
 impl IndexOperation {
+    /// Return the tasks associated with this operation.
+    pub fn tasks(&self) -> Vec<&Task> {
+        match self {
+            IndexOperation::DocumentOperation { tasks, .. }
+            | IndexOperation::DocumentDeletion { tasks, .. }
+            | IndexOperation::Settings { tasks, .. }
+            | IndexOperation::DocumentClear { tasks, .. } => tasks.iter().collect(),
+            IndexOperation::IndexDocumentDeletionByFilter { task, .. } => vec![task],
+            IndexOperation::SettingsAndDocumentOperation {
+                document_import_tasks: tasks,
+                settings_tasks: other,
+                ..
+            }
+            | IndexOperation::DocumentClearAndSetting {
+                cleared_tasks: tasks,
+                settings_tasks: other,
+                ..
+            } => tasks.iter().chain(other).collect(),
+        }
+    }
+
     pub fn index_uid(&self) -> &str {
         match self {
             IndexOperation::DocumentOperation { index_uid, .. }
@@ -250,6 +301,14 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
             }
+            BatchKind::DocumentAdditionFromUrl { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::DocumentAdditionFromUrl { task, must_create_index }))
+            }
+            BatchKind::DocumentAdditionDryRun { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::DocumentAdditionDryRun { task }))
+            }
             BatchKind::DocumentOperation { method, operation_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, operation_ids)?;
                 let primary_key = tasks
@@ -457,6 +516,14 @@ impl IndexScheduler {
                 index_has_been_created: must_create_index,
                 tasks: self.get_existing_tasks(rtxn, ids)?,
             })),
+            BatchKind::IndexDuplication { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexDuplication { task }))
+            }
+            BatchKind::IndexCompaction { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexCompaction { index_uid, task }))
+            }
             BatchKind::IndexSwap { id } => {
                 let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
                 Ok(Some(Batch::IndexSwap { task }))
@@ -470,25 +537,65 @@ impl IndexScheduler {
     /// 3. We get the *next* snapshot to process.
     /// 4. We get the *next* dump to process.
     /// 5. We get the *next* tasks to process for a specific index.
+    ///
+    /// While batches are processing concurrently, the next batch operates on another index
+    /// and the tasks operating on the whole instance or on several indexes must wait.
     pub(crate) fn create_next_batch(&self, rtxn: &RoTxn) -> Result<Option<Batch>> {
         #[cfg(test)]
         self.maybe_fail(crate::tests::FailureLocation::InsideCreateBatch)?;
 
         puffin::profile_function!();
 
-        let enqueued = &self.get_status(rtxn, Status::Enqueued)?;
+        // The indexes of the batches processing concurrently to the next one.
+        let running_indexes: Vec<_> = self
+            .processing_tasks
+            .read()
+            .unwrap()
+            .running_batches()
+            .map(|batch| batch.index_uid.clone())
+            .collect();
+        if running_indexes.len() >= self.max_concurrent_batches {
+            return Ok(None);
+        }
+        let idle = running_indexes.is_empty();
+
+        // The tasks of the running batches are still enqueued on disk until their batch is committed.
+        let running_tasks = self.processing_tasks.read().unwrap().running_tasks();
+        let mut enqueued = self.get_status(rtxn, Status::Enqueued)? - running_tasks;
+        // The tasks of a batch interrupted by a crash are processed again before any other task,
+        // so that the same batch is created and no task is applied twice.
+        if let Some(interrupted) = self.get_interrupted_batch(rtxn, &enqueued)? {
+            enqueued = interrupted;
+        }
+        let enqueued = &enqueued;
         let to_cancel = self.get_kind(rtxn, Kind::TaskCancelation)? & enqueued;
 
+        // The tasks operating on the whole instance wait for the end of the processing batches.
+        if !idle {
+            let mut instance_tasks = to_cancel.clone();
+            for kind in [Kind::TaskDeletion, Kind::SnapshotCreation, Kind::DumpCreation] {
+                instance_tasks |= self.get_kind(rtxn, kind)? & enqueued;
+            }
+            if !instance_tasks.is_empty() {
+                return Ok(None);
+            }
+        }
+
         // 1. we get the last task to cancel.
         if let Some(task_id) = to_cancel.max() {
             // We retrieve the tasks that were processing before this tasks cancelation started.
             // We must *not* reset the processing tasks before calling this method.
-            let ProcessingTasks { started_at, processing } =
-                &*self.processing_tasks.read().unwrap();
+            let previous_processing_tasks = self
+                .processing_tasks
+                .read()
+                .unwrap()
+                .batches
+                .iter()
+                .map(|batch| (batch.started_at, batch.tasks.clone()))
+                .collect();
             return Ok(Some(Batch::TaskCancelation {
                 task: self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?,
-                previous_started_at: *started_at,
-                previous_processing_tasks: processing.clone(),
+                previous_processing_tasks,
             }));
         }
 
@@ -513,10 +620,20 @@ impl IndexScheduler {
             )));
         }
 
-        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
-        let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
+        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task
+        //    that does not operate on the index of a processing batch.
+        let mut available = enqueued.clone();
+        for index_uid in running_indexes.iter().flatten() {
+            available -= self.index_tasks(rtxn, index_uid)?;
+        }
+        let task_id = if let Some(task_id) = available.min() { task_id } else { return Ok(None) };
         let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
 
+        // The tasks operating on several indexes wait for the end of the processing batches.
+        if !idle && task.indexes().len() != 1 {
+            return Ok(None);
+        }
+
         // If the task is not associated with any index, verify that it is an index swap and
         // create the batch directly. Otherwise, get the index name associated with the task
         // and use the autobatcher to batch the enqueued tasks associated with it
@@ -584,7 +701,7 @@ impl IndexScheduler {
         puffin::profile_function!(format!("{:?}", batch));
 
         match batch {
-            Batch::TaskCancelation { mut task, previous_started_at, previous_processing_tasks } => {
+            Batch::TaskCancelation { mut task, previous_processing_tasks } => {
                 // 1. Retrieve the tasks that matched the query at enqueue-time.
                 let matched_tasks =
                     if let KindWithContent::TaskCancelation { tasks, query: _ } = &task.kind {
@@ -598,7 +715,6 @@ impl IndexScheduler {
                     &mut wtxn,
                     task.uid,
                     matched_tasks,
-                    previous_started_at,
                     &previous_processing_tasks,
                 )?;
 
@@ -851,6 +967,18 @@ impl IndexScheduler {
                 let features = self.features()?.runtime_features();
                 dump.create_experimental_features(features)?;
 
+                // 5. Dump the index templates
+                let rtxn = self.env.read_txn()?;
+                let index_templates = self.index_templates.all(&rtxn)?;
+                drop(rtxn);
+                dump.create_index_templates(&index_templates)?;
+
+                // 6. Dump the schedules, without the credentials of their url downloads
+                let rtxn = self.env.read_txn()?;
+                let schedules = self.schedules.all(&rtxn)?;
+                drop(rtxn);
+                dump.create_schedules(&schedules)?;
+
                 let dump_uid = started_at.format(format_description!(
                     "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
                 )).unwrap();
@@ -875,9 +1003,22 @@ impl IndexScheduler {
                     self.index_mapper.index(&rtxn, &index_uid)?
                 };
 
+                // The batch is replayed after a crash that happened once the index was updated but
+                // before the tasks were, we return the tasks of the committed batch instead of
+                // applying the operations a second time.
+                if let Some(tasks) = self.already_applied_batch(&index, &op.tasks())? {
+                    info!("A batch of tasks was already applied to the index `{index_uid}`.");
+                    return Ok(tasks);
+                }
+
                 let mut index_wtxn = index.write_txn()?;
+                if must_create_index {
+                    self.apply_index_template(&mut index_wtxn, &index, &index_uid)?;
+                }
                 let tasks = self.apply_index_operation(&mut index_wtxn, &index, op)?;
+                index.put_last_applied_batch(&mut index_wtxn, &tasks)?;
                 index_wtxn.commit()?;
+                self.search_cache.invalidate(&index_uid);
 
                 // if the update processed successfully, we're going to store the new
                 // stats of the index. Since the tasks have already been processed and
@@ -899,63 +1040,161 @@ impl IndexScheduler {
 
                 Ok(tasks)
             }
-            Batch::IndexCreation { index_uid, primary_key, task } => {
-                let wtxn = self.env.write_txn()?;
-                if self.index_mapper.exists(&wtxn, &index_uid)? {
-                    return Err(Error::IndexAlreadyExists(index_uid));
+            Batch::DocumentAdditionFromUrl { mut task, must_create_index } => {
+                let KindWithContent::DocumentAdditionFromUrl {
+                    index_uid,
+                    primary_key,
+                    method,
+                    url,
+                    has_authorization,
+                    format,
+                    ..
+                } = task.kind.clone()
+                else {
+                    unreachable!()
+                };
+                // The documents are not downloaded again if the batch was already applied
+                // to the index before a crash.
+                let rtxn = self.env.read_txn()?;
+                let index = if self.index_mapper.exists(&rtxn, &index_uid)? {
+                    Some(self.index_mapper.index(&rtxn, &index_uid)?)
+                } else {
+                    None
+                };
+                drop(rtxn);
+                if let Some(index) = index {
+                    if let Some(tasks) = self.already_applied_batch(&index, &[&task])? {
+                        info!("A batch of tasks was already applied to the index `{index_uid}`.");
+                        return Ok(tasks);
+                    }
                 }
-                self.index_mapper.create_index(wtxn, &index_uid, None)?;
 
-                self.process_batch(Batch::IndexUpdate { index_uid, primary_key, task })
-            }
-            Batch::IndexUpdate { index_uid, primary_key, mut task } => {
-                let rtxn = self.env.read_txn()?;
-                let index = self.index_mapper.index(&rtxn, &index_uid)?;
+                // The credentials are only kept in memory, the documents are not downloaded
+                // without them when they were lost with a restart.
+                let authorization =
+                    self.download_credentials.read().unwrap().get(&task.uid).cloned();
+                if has_authorization && authorization.is_none() {
+                    return Err(Error::DocumentDownload {
+                        url,
+                        error: String::from(
+                            "the authorization was lost when Meilisearch restarted, the documents must be added again",
+                        ),
+                    });
+                }
+
+                // the documents are downloaded before opening the write transaction of the index.
+                let (content_file, documents_count) =
+                    self.download_documents(&url, authorization.as_deref(), format)?;
+                task.details = Some(Details::DocumentAdditionOrUpdate {
+                    received_documents: documents_count,
+                    indexed_documents: None,
+                    matched_percolate_queries: BTreeMap::new(),
+                    duplicated_documents: None,
+                });
 
-                if let Some(primary_key) = primary_key.clone() {
-                    let mut index_wtxn = index.write_txn()?;
-                    let mut builder = MilliSettings::new(
-                        &mut index_wtxn,
-                        &index,
-                        self.index_mapper.indexer_config(),
-                    );
-                    builder.set_primary_key(primary_key);
-                    let must_stop_processing = self.must_stop_processing.clone();
-                    builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
-                        || must_stop_processing.get(),
-                    )?;
-                    index_wtxn.commit()?;
+                let op = IndexOperation::DocumentOperation {
+                    index_uid,
+                    primary_key,
+                    method,
+                    documents_counts: vec![documents_count],
+                    operations: vec![DocumentOperation::Add(content_file)],
+                    tasks: vec![task],
+                };
+                let result = self.process_batch(Batch::IndexOperation { op, must_create_index });
+                if let Err(e) = self.file_store.delete(content_file) {
+                    error!("Could not delete the update file {content_file}: {e}");
                 }
+                result
+            }
+            Batch::DocumentAdditionDryRun { mut task } => {
+                let KindWithContent::DocumentAdditionDryRun {
+                    index_uid,
+                    primary_key,
+                    content_file,
+                    documents_count,
+                    duplicate_ids_policy,
+                } = task.kind.clone()
+                else {
+                    unreachable!()
+                };
 
-                // drop rtxn before starting a new wtxn on the same db
-                rtxn.commit()?;
+                // the documents of an index that doesn't exist yet are validated without settings.
+                let rtxn = self.env.read_txn()?;
+                let index = if self.index_mapper.exists(&rtxn, &index_uid)? {
+                    Some(self.index_mapper.index(&rtxn, &index_uid)?)
+                } else {
+                    None
+                };
+                drop(rtxn);
+                let index_rtxn = index.as_ref().map(|index| index.read_txn()).transpose()?;
+
+                let content_file = self.file_store.get_update(content_file)?;
+                let reader =
+                    DocumentsBatchReader::from_reader(content_file).map_err(milli::Error::from)?;
+                let validation = validate_documents_batch(
+                    index_rtxn.as_ref().zip(index.as_ref()),
+                    primary_key.as_deref(),
+                    self.index_mapper.indexer_config(),
+                    duplicate_ids_policy
+                        .unwrap_or(self.index_mapper.indexer_config().duplicate_ids_policy),
+                    MAX_DRY_RUN_DOCUMENT_ERRORS,
+                    reader,
+                )?
+                .map_err(milli::Error::from)?;
 
                 task.status = Status::Succeeded;
-                task.details = Some(Details::IndexInfo { primary_key });
-
-                // if the update processed successfully, we're going to store the new
-                // stats of the index. Since the tasks have already been processed and
-                // this is a non-critical operation. If it fails, we should not fail
-                // the entire batch.
-                let res = || -> Result<()> {
-                    let mut wtxn = self.env.write_txn()?;
-                    let index_rtxn = index.read_txn()?;
-                    let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
-                    self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
-                    wtxn.commit()?;
-                    Ok(())
-                }();
+                task.details = Some(Details::DocumentAdditionDryRun {
+                    received_documents: documents_count,
+                    invalid_documents: Some(validation.invalid_documents),
+                    document_errors: validation
+                        .errors
+                        .into_iter()
+                        .map(|DocumentValidationError { position, document_id, error }| {
+                            DocumentError {
+                                position,
+                                document_id,
+                                error: milli::Error::from(error).into(),
+                            }
+                        })
+                        .collect(),
+                });
 
-                match res {
-                    Ok(_) => (),
-                    Err(e) => error!("Could not write the stats of the index {}", e),
+                Ok(vec![task])
+            }
+            Batch::IndexCreation { index_uid, primary_key, task } => {
+                let wtxn = self.env.write_txn()?;
+                if self.index_mapper.exists(&wtxn, &index_uid)? {
+                    // The index was created by this batch before a crash.
+                    let index = self.index_mapper.index(&wtxn, &index_uid)?;
+                    drop(wtxn);
+                    return match self.already_applied_batch(&index, &[&task])? {
+                        Some(tasks) => Ok(tasks),
+                        None => Err(Error::IndexAlreadyExists(index_uid)),
+                    };
                 }
+                let index = self.index_mapper.create_index(wtxn, &index_uid, None)?;
+                self.update_index_info(&index, index_uid, primary_key, task, true)
+            }
+            Batch::IndexUpdate { index_uid, primary_key, task } => {
+                let rtxn = self.env.read_txn()?;
+                let index = self.index_mapper.index(&rtxn, &index_uid)?;
+                drop(rtxn);
 
-                Ok(vec![task])
+                if let Some(tasks) = self.already_applied_batch(&index, &[&task])? {
+                    info!("A batch of tasks was already applied to the index `{index_uid}`.");
+                    return Ok(tasks);
+                }
+                self.update_index_info(&index, index_uid, primary_key, task, false)
             }
             Batch::IndexDeletion { index_uid, index_has_been_created, mut tasks } => {
-                let wtxn = self.env.write_txn()?;
+                if let Some(tasks) =
+                    self.already_applied_indexes_batch(&tasks.iter().collect::<Vec<_>>())?
+                {
+                    info!("A batch of tasks was already applied to the index `{index_uid}`.");
+                    return Ok(tasks);
+                }
+
+                let mut wtxn = self.env.write_txn()?;
 
                 // it's possible that the index doesn't exist
                 let number_of_documents = || -> Result<u64> {
@@ -965,13 +1204,6 @@ impl IndexScheduler {
                 }()
                 .unwrap_or_default();
 
-                // The write transaction is directly owned and committed inside.
-                match self.index_mapper.delete_index(wtxn, &index_uid) {
-                    Ok(()) => (),
-                    Err(Error::IndexNotFound(_)) if index_has_been_created => (),
-                    Err(e) => return Err(e),
-                }
-
                 // We set all the tasks details to the default value.
                 for task in &mut tasks {
                     task.status = Status::Succeeded;
@@ -982,18 +1214,119 @@ impl IndexScheduler {
                         otherwise => otherwise.default_finished_details(),
                     };
                 }
+                self.put_applied_indexes_batch(&mut wtxn, &tasks)?;
+
+                // The write transaction is directly owned and committed inside.
+                match self.index_mapper.delete_index(wtxn, &index_uid) {
+                    Ok(()) => (),
+                    Err(Error::IndexNotFound(_)) if index_has_been_created => (),
+                    Err(e) => return Err(e),
+                }
+                self.search_analytics.delete_index(&index_uid);
+                self.query_suggestions.delete_index(&index_uid);
+                self.search_cache.invalidate(&index_uid);
 
                 Ok(tasks)
             }
+            Batch::IndexDuplication { mut task } => {
+                let (index_uid, new_index_uid) = match &task.kind {
+                    KindWithContent::IndexDuplication { index_uid, new_index_uid } => {
+                        (index_uid.clone(), new_index_uid.clone())
+                    }
+                    _ => unreachable!(),
+                };
+
+                if let Some(tasks) = self.already_applied_indexes_batch(&[&task])? {
+                    info!("A batch of tasks was already applied to the index `{new_index_uid}`.");
+                    return Ok(tasks);
+                }
+
+                let rtxn = self.env.read_txn()?;
+                let index = self.index_mapper.index(&rtxn, &index_uid)?;
+                drop(rtxn);
+                let primary_key = index.primary_key(&index.read_txn()?)?.map(String::from);
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::IndexInfo { primary_key });
+
+                let index = self.index_mapper.duplicate_index(
+                    &self.env,
+                    &index_uid,
+                    &new_index_uid,
+                    |wtxn| self.put_applied_indexes_batch(wtxn, std::slice::from_ref(&task)),
+                )?;
+                let index_rtxn = index.read_txn()?;
+
+                // Storing the stats of the new index is a non-critical operation.
+                let res = || -> Result<()> {
+                    let mut wtxn = self.env.write_txn()?;
+                    let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                    self.index_mapper.store_stats_of(&mut wtxn, &new_index_uid, &stats)?;
+                    wtxn.commit()?;
+                    Ok(())
+                }();
+
+                match res {
+                    Ok(_) => (),
+                    Err(e) => error!("Could not write the stats of the index {}", e),
+                }
+
+                Ok(vec![task])
+            }
+            Batch::IndexCompaction { index_uid, mut task } => {
+                if let Some(tasks) = self.already_applied_indexes_batch(&[&task])? {
+                    info!("A batch of tasks was already applied to the index `{index_uid}`.");
+                    return Ok(tasks);
+                }
+
+                self.index_mapper.compact_index(
+                    &self.env,
+                    &index_uid,
+                    |copied, total| self.progress.update_copy(copied, total),
+                    |wtxn, pre_size, post_size| {
+                        task.status = Status::Succeeded;
+                        task.details = Some(Details::IndexCompaction {
+                            pre_compaction_size: Some(pre_size),
+                            post_compaction_size: Some(post_size),
+                        });
+                        self.put_applied_indexes_batch(wtxn, std::slice::from_ref(&task))
+                    },
+                )?;
+
+                // Storing the stats of the compacted index is a non-critical operation.
+                let res = || -> Result<()> {
+                    let rtxn = self.env.read_txn()?;
+                    let index = self.index_mapper.index(&rtxn, &index_uid)?;
+                    drop(rtxn);
+                    let index_rtxn = index.read_txn()?;
+                    let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                    let mut wtxn = self.env.write_txn()?;
+                    self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+                    wtxn.commit()?;
+                    Ok(())
+                }();
+
+                match res {
+                    Ok(_) => (),
+                    Err(e) => error!("Could not write the stats of the index {}", e),
+                }
+
+                Ok(vec![task])
+            }
             Batch::IndexSwap { mut task } => {
+                if let Some(tasks) = self.already_applied_indexes_batch(&[&task])? {
+                    info!("A batch of tasks swapping indexes was already applied.");
+                    return Ok(tasks);
+                }
+
                 let mut wtxn = self.env.write_txn()?;
                 let swaps = if let KindWithContent::IndexSwap { swaps } = &task.kind {
-                    swaps
+                    swaps.clone()
                 } else {
                     unreachable!()
                 };
                 let mut not_found_indexes = BTreeSet::new();
-                for IndexSwap { indexes: (lhs, rhs) } in swaps {
+                for IndexSwap { indexes: (lhs, rhs) } in &swaps {
                     for index in [lhs, rhs] {
                         let index_exists = self.index_mapper.index_exists(&wtxn, index)?;
                         if !index_exists {
@@ -1012,16 +1345,71 @@ impl IndexScheduler {
                         ));
                     }
                 }
-                for swap in swaps {
+                for swap in &swaps {
                     self.apply_index_swap(&mut wtxn, task.uid, &swap.indexes.0, &swap.indexes.1)?;
                 }
-                wtxn.commit()?;
                 task.status = Status::Succeeded;
+                self.put_applied_indexes_batch(&mut wtxn, std::slice::from_ref(&task))?;
+                wtxn.commit()?;
+                for IndexSwap { indexes: (lhs, rhs) } in &swaps {
+                    self.search_cache.invalidate(lhs);
+                    self.search_cache.invalidate(rhs);
+                }
                 Ok(vec![task])
             }
         }
     }
 
+    /// Sets the primary key of the index, and applies the matching template to a newly created
+    /// index, the processed task is recorded in the same transaction.
+    fn update_index_info(
+        &self,
+        index: &Index,
+        index_uid: String,
+        primary_key: Option<String>,
+        mut task: Task,
+        must_apply_template: bool,
+    ) -> Result<Vec<Task>> {
+        let mut index_wtxn = index.write_txn()?;
+        if must_apply_template {
+            self.apply_index_template(&mut index_wtxn, index, &index_uid)?;
+        }
+        if let Some(primary_key) = primary_key.clone() {
+            let mut builder =
+                MilliSettings::new(&mut index_wtxn, index, self.index_mapper.indexer_config());
+            builder.set_primary_key(primary_key);
+            let must_stop_processing = self.must_stop_processing.clone();
+            builder.execute(self.indexing_step_callback(), || must_stop_processing.get())?;
+        }
+
+        task.status = Status::Succeeded;
+        task.details = Some(Details::IndexInfo { primary_key });
+        let tasks = vec![task];
+        index.put_last_applied_batch(&mut index_wtxn, &tasks)?;
+        index_wtxn.commit()?;
+        self.search_cache.invalidate(&index_uid);
+
+        // if the update processed successfully, we're going to store the new
+        // stats of the index. Since the tasks have already been processed and
+        // this is a non-critical operation. If it fails, we should not fail
+        // the entire batch.
+        let res = || -> Result<()> {
+            let mut wtxn = self.env.write_txn()?;
+            let index_rtxn = index.read_txn()?;
+            let stats = crate::index_mapper::IndexStats::new(index, &index_rtxn)?;
+            self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+            wtxn.commit()?;
+            Ok(())
+        }();
+
+        match res {
+            Ok(_) => (),
+            Err(e) => error!("Could not write the stats of the index {}", e),
+        }
+
+        Ok(tasks)
+    }
+
     /// Swap the index `lhs` with the index `rhs`.
     fn apply_index_swap(&self, wtxn: &mut RwTxn, task_id: u32, lhs: &str, rhs: &str) -> Result<()> {
         // 1. Verify that both lhs and rhs are existing indexes
@@ -1064,6 +1452,162 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Downloads the documents of a `DocumentAdditionFromUrl` task into a new update file.
+    ///
+    /// ## Return
+    /// The uuid of the update file and the number of documents it contains.
+    fn download_documents(
+        &self,
+        url: &str,
+        authorization: Option<&str>,
+        format: PayloadType,
+    ) -> Result<(Uuid, u64)> {
+        let download_error =
+            |error: String| Error::DocumentDownload { url: url.to_string(), error };
+
+        let too_large = |limit: u64| {
+            download_error(format!("the documents are larger than the limit of {limit} bytes"))
+        };
+        let max_size = self.max_indexing_payload_size;
+
+        // The documents are not downloaded from the private network unless it is allowed, the
+        // checked addresses of the host are the ones connected to and the redirections are checked.
+        let allow_private_network = self.allow_private_documents_urls;
+        let mut builder = reqwest::blocking::Client::builder()
+            .connect_timeout(DOCUMENT_DOWNLOAD_CONNECT_TIMEOUT)
+            .timeout(DOCUMENT_DOWNLOAD_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() >= DOCUMENT_DOWNLOAD_MAX_REDIRECTS {
+                    return attempt.error("too many redirections");
+                }
+                match resolve_download_host(attempt.url(), allow_private_network) {
+                    Ok(_) => attempt.follow(),
+                    Err(error) => attempt.error(error),
+                }
+            }));
+        let parsed_url = reqwest::Url::parse(url).map_err(|e| download_error(e.to_string()))?;
+        let addresses =
+            resolve_download_host(&parsed_url, allow_private_network).map_err(download_error)?;
+        if let (Some(domain), Some(address)) = (parsed_url.domain(), addresses.first()) {
+            builder = builder.resolve(domain, *address);
+        }
+        let client = builder.build().map_err(|e| download_error(e.to_string()))?;
+        let mut request = client.get(url);
+        if let Some(authorization) = authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
+        let mut response = request
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| download_error(e.to_string()))?;
+        if let Some(limit) = max_size
+            .filter(|limit| response.content_length().map_or(false, |length| length > *limit))
+        {
+            return Err(too_large(limit));
+        }
+
+        // the body is streamed to the disk, it is never entirely held in memory,
+        // and the download stops as soon as the task is canceled.
+        let mut payload = tempfile::tempfile()?;
+        let mut chunk = vec![0; DOCUMENT_DOWNLOAD_CHUNK_SIZE];
+        let mut size = 0;
+        loop {
+            if self.must_stop_processing.get() {
+                return Err(
+                    milli::Error::InternalError(milli::InternalError::AbortedIndexation).into()
+                );
+            }
+            let read = response.read(&mut chunk).map_err(|e| download_error(e.to_string()))?;
+            if read == 0 {
+                break;
+            }
+            size += read as u64;
+            if let Some(limit) = max_size.filter(|limit| size > *limit) {
+                return Err(too_large(limit));
+            }
+            payload.write_all(&chunk[..read])?;
+        }
+        if size == 0 {
+            return Err(download_error(String::from("the response is empty")));
+        }
+
+        let (uuid, mut update_file) = self.file_store.new_update()?;
+        let documents_count = match format {
+            PayloadType::Json => read_json(&payload, update_file.as_file_mut())?,
+            PayloadType::Ndjson => read_ndjson(&payload, update_file.as_file_mut())?,
+            PayloadType::Csv { delimiter } => {
+                read_csv(&payload, update_file.as_file_mut(), delimiter)?
+            }
+            PayloadType::Parquet => read_parquet(&payload, update_file.as_file_mut())?,
+        };
+        update_file.persist()?;
+
+        Ok((uuid, documents_count))
+    }
+
+    /// Returns the processed tasks of the batch last applied to the index if it is made
+    /// of the given tasks.
+    fn already_applied_batch(&self, index: &Index, tasks: &[&Task]) -> Result<Option<Vec<Task>>> {
+        let rtxn = index.read_txn()?;
+        let applied_tasks: Option<Vec<Task>> = index.last_applied_batch(&rtxn)?;
+        Ok(applied_tasks.filter(|applied_tasks| same_tasks(applied_tasks, tasks)))
+    }
+
+    /// Returns the processed tasks of the batch if it was already applied to the indexes
+    /// themselves, which are created, deleted, duplicated, compacted or swapped.
+    fn already_applied_indexes_batch(&self, tasks: &[&Task]) -> Result<Option<Vec<Task>>> {
+        let Some(first_task) = tasks.iter().map(|task| task.uid).min() else { return Ok(None) };
+        let rtxn = self.env.read_txn()?;
+        let applied_tasks = self.applied_batches.get(&rtxn, &BEU32::new(first_task))?;
+        Ok(applied_tasks.filter(|applied_tasks| same_tasks(applied_tasks, tasks)))
+    }
+
+    /// Records the processed tasks of a batch applied to the indexes themselves,
+    /// in the transaction applying the batch.
+    fn put_applied_indexes_batch(&self, wtxn: &mut RwTxn, tasks: &[Task]) -> Result<()> {
+        if let Some(first_task) = tasks.iter().map(|task| task.uid).min() {
+            self.applied_batches.put(wtxn, &BEU32::new(first_task), &tasks.to_vec())?;
+        }
+        Ok(())
+    }
+
+    /// Applies the settings of the template matching the uid of a newly created index.
+    fn apply_index_template<'i>(
+        &self,
+        index_wtxn: &mut RwTxn<'i, '_>,
+        index: &'i Index,
+        index_uid: &str,
+    ) -> Result<()> {
+        let rtxn = self.env.read_txn()?;
+        let template = self.index_templates.matching(&rtxn, index_uid)?;
+        drop(rtxn);
+
+        let Some(template) = template else { return Ok(()) };
+        let indexer_config = self.index_mapper.indexer_config();
+        let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
+        apply_settings_to_builder(&template.settings.check(), &mut builder);
+
+        let must_stop_processing = self.must_stop_processing.clone();
+        builder.execute(self.indexing_step_callback(), || must_stop_processing.get())?;
+        Ok(())
+    }
+
+    /// Returns the progress callback of the indexing operations, it logs the indexing steps
+    /// and measures the time spent in each of them for the stats of the batch.
+    ///
+    /// The live progress is stored in the progress handle, the lock of the processing tasks
+    /// is only taken when a new step starts.
+    fn indexing_step_callback(&self) -> impl Fn(UpdateIndexingStep) + Sync {
+        let processing_tasks = self.processing_tasks.clone();
+        let progress = self.progress.clone();
+        move |indexing_step| {
+            debug!("update: {:?}", indexing_step);
+            if progress.update(indexing_step) {
+                processing_tasks.write().unwrap().update_indexing_step(&progress, indexing_step);
+            }
+        }
+    }
+
     /// Process the index operation on the given index.
     ///
     /// ## Return
@@ -1127,10 +1671,9 @@ impl IndexScheduler {
                             let mut builder =
                                 milli::update::Settings::new(index_wtxn, index, indexer_config);
                             builder.set_primary_key(primary_key);
-                            builder.execute(
-                                |indexing_step| debug!("update: {:?}", indexing_step),
-                                || must_stop_processing.clone().get(),
-                            )?;
+                            builder.execute(self.indexing_step_callback(), || {
+                                must_stop_processing.clone().get()
+                            })?;
                             primary_key_has_been_set = true;
                         }
                     }
@@ -1143,13 +1686,32 @@ impl IndexScheduler {
                     index,
                     indexer_config,
                     config,
-                    |indexing_step| debug!("update: {:?}", indexing_step),
+                    self.indexing_step_callback(),
                     || must_stop_processing.get(),
                 )?;
 
-                for (operation, task) in operations.into_iter().zip(tasks.iter_mut()) {
+                // the position of the task of every successful document addition.
+                let mut additions_tasks = Vec::new();
+                for (i, (operation, task)) in
+                    operations.into_iter().zip(tasks.iter_mut()).enumerate()
+                {
                     match operation {
                         DocumentOperation::Add(content_uuid) => {
+                            let duplicate_ids_policy = match task.kind {
+                                KindWithContent::DocumentAdditionOrUpdate {
+                                    duplicate_ids_policy,
+                                    ..
+                                }
+                                | KindWithContent::DocumentAdditionFromUrl {
+                                    duplicate_ids_policy,
+                                    ..
+                                } => duplicate_ids_policy,
+                                _ => None,
+                            };
+                            builder.set_duplicate_ids_policy(
+                                duplicate_ids_policy.unwrap_or(indexer_config.duplicate_ids_policy),
+                            );
+
                             let content_file = self.file_store.get_update(content_uuid)?;
                             let reader = DocumentsBatchReader::from_reader(content_file)
                                 .map_err(milli::Error::from)?;
@@ -1170,10 +1732,18 @@ impl IndexScheduler {
 
                             match user_result {
                                 Ok(count) => {
+                                    additions_tasks.push(i);
+                                    let duplicated_documents = builder
+                                        .additions_duplicated_documents()
+                                        .last()
+                                        .copied()
+                                        .filter(|&count| count > 0);
                                     task.status = Status::Succeeded;
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(count),
+                                        matched_percolate_queries: BTreeMap::new(),
+                                        duplicated_documents,
                                     })
                                 }
                                 Err(e) => {
@@ -1181,6 +1751,8 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(0),
+                                        matched_percolate_queries: BTreeMap::new(),
+                                        duplicated_documents: None,
                                     });
                                     task.error = Some(milli::Error::from(e).into());
                                 }
@@ -1223,18 +1795,32 @@ impl IndexScheduler {
                 }
 
                 if !tasks.iter().all(|res| res.error.is_some()) {
+                    let additions_documents_ids = builder.additions_documents_ids().to_vec();
                     let addition = builder.execute()?;
                     info!("document addition done: {:?}", addition);
+
+                    // report the percolate queries matched by the documents of each addition.
+                    for (i, documents_ids) in
+                        additions_tasks.into_iter().zip(additions_documents_ids)
+                    {
+                        let matches = percolate(index, index_wtxn, &documents_ids)?;
+                        if let Some(Details::DocumentAdditionOrUpdate {
+                            matched_percolate_queries,
+                            ..
+                        }) = &mut tasks[i].details
+                        {
+                            *matched_percolate_queries = matches;
+                        }
+                    }
                 } else if primary_key_has_been_set {
                     // Everything failed but we've set a primary key.
                     // We need to remove it.
                     let mut builder =
                         milli::update::Settings::new(index_wtxn, index, indexer_config);
                     builder.reset_primary_key();
-                    builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
-                        || must_stop_processing.clone().get(),
-                    )?;
+                    builder.execute(self.indexing_step_callback(), || {
+                        must_stop_processing.clone().get()
+                    })?;
                 }
 
                 Ok(tasks)
@@ -1313,10 +1899,7 @@ impl IndexScheduler {
                 }
 
                 let must_stop_processing = self.must_stop_processing.clone();
-                builder.execute(
-                    |indexing_step| debug!("update: {:?}", indexing_step),
-                    || must_stop_processing.get(),
-                )?;
+                builder.execute(self.indexing_step_callback(), || must_stop_processing.get())?;
 
                 Ok(tasks)
             }
@@ -1388,7 +1971,11 @@ impl IndexScheduler {
     /// Delete each given task from all the databases (if it is deleteable).
     ///
     /// Return the number of tasks that were actually deleted.
-    fn delete_matched_tasks(&self, wtxn: &mut RwTxn, matched_tasks: &RoaringBitmap) -> Result<u64> {
+    pub(crate) fn delete_matched_tasks(
+        &self,
+        wtxn: &mut RwTxn,
+        matched_tasks: &RoaringBitmap,
+    ) -> Result<u64> {
         // 1. Remove from this list the tasks that we are not allowed to delete
         let enqueued_tasks = self.get_status(wtxn, Status::Enqueued)?;
         let processing_tasks = &self.processing_tasks.read().unwrap().processing.clone();
@@ -1454,6 +2041,18 @@ impl IndexScheduler {
             }
         }
 
+        // 3. The batches are deleted along with the last of their tasks
+        let remaining_tasks = self.all_task_ids(wtxn)?;
+        let mut iter = self.batches.iter_mut(wtxn)?;
+        while let Some(result) = iter.next() {
+            let (_, batch) = result?;
+            if batch.task_uids.iter().all(|uid| !remaining_tasks.contains(*uid)) {
+                // safety: we don't keep references from inside the LMDB database.
+                unsafe { iter.del_current()? };
+            }
+        }
+        drop(iter);
+
         Ok(to_delete_tasks.len())
     }
 
@@ -1465,8 +2064,7 @@ impl IndexScheduler {
         wtxn: &mut RwTxn,
         cancel_task_id: TaskId,
         matched_tasks: &RoaringBitmap,
-        previous_started_at: OffsetDateTime,
-        previous_processing_tasks: &RoaringBitmap,
+        previous_processing_tasks: &[(OffsetDateTime, RoaringBitmap)],
     ) -> Result<Vec<Uuid>> {
         let now = OffsetDateTime::now_utc();
 
@@ -1482,8 +2080,10 @@ impl IndexScheduler {
             if let Some(uuid) = task.content_uuid() {
                 content_files_to_delete.push(uuid);
             }
-            if previous_processing_tasks.contains(task.uid) {
-                task.started_at = Some(previous_started_at);
+            if let Some((previous_started_at, _)) =
+                previous_processing_tasks.iter().find(|(_, tasks)| tasks.contains(task.uid))
+            {
+                task.started_at = Some(*previous_started_at);
             }
             task.status = Status::Canceled;
             task.canceled_by = Some(cancel_task_id);
@@ -1517,3 +2117,65 @@ fn delete_document_by_filter<'a>(
         0
     })
 }
+
+/// Whether the applied tasks are the given tasks, they are identified by their uid and
+/// enqueue date as the uids of the deleted tasks can be given again to new tasks.
+fn same_tasks(applied_tasks: &[Task], tasks: &[&Task]) -> bool {
+    let identify = |task: &Task| (task.uid, task.enqueued_at);
+    let applied: HashSet<_> = applied_tasks.iter().map(identify).collect();
+    let batched: HashSet<_> = tasks.iter().copied().map(identify).collect();
+    applied == batched
+}
+
+/// Resolves the host of the url the documents are downloaded from and returns its addresses.
+///
+/// Unless the private network is allowed, an error is returned if one of the addresses is not
+/// public, so that the tasks can't reach the loopback, private or link-local services.
+fn resolve_download_host(
+    url: &reqwest::Url,
+    allow_private_network: bool,
+) -> std::result::Result<Vec<SocketAddr>, String> {
+    let addresses = url.socket_addrs(|| None).map_err(|e| e.to_string())?;
+    if allow_private_network {
+        return Ok(addresses);
+    }
+    match addresses.iter().find(|address| !is_public_address(address.ip())) {
+        Some(address) => Err(format!(
+            "the host resolves to the non-public address `{}`, which is not allowed",
+            address.ip()
+        )),
+        None => Ok(addresses),
+    }
+}
+
+/// Whether the address can be reached from the internet, the loopback, private, link-local,
+/// shared and otherwise reserved addresses are not public.
+fn is_public_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => {
+            let [first, second, ..] = address.octets();
+            !(address.is_unspecified()
+                || address.is_loopback()
+                || address.is_private()
+                || address.is_link_local()
+                || address.is_broadcast()
+                || address.is_documentation()
+                // the shared address space, 100.64.0.0/10
+                || (first == 100 && (second & 0b1100_0000) == 0b0100_0000)
+                || first == 0
+                || first >= 240)
+        }
+        IpAddr::V6(address) => match address.to_ipv4_mapped() {
+            Some(address) => is_public_address(IpAddr::V4(address)),
+            None => {
+                let first_segment = address.segments()[0];
+                !(address.is_unspecified()
+                    || address.is_loopback()
+                    // the unique local addresses, fc00::/7
+                    || (first_segment & 0xfe00) == 0xfc00
+                    // the link-local addresses, fe80::/10
+                    || (first_segment & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
@@ -29,38 +29,51 @@ impl RoFeatures {
         self.runtime
     }
 
-    pub fn check_score_details(&self) -> Result<()> {
-        if self.runtime.score_details {
+    pub fn check_metrics(&self) -> Result<()> {
+        if self.instance.metrics {
             Ok(())
         } else {
             Err(FeatureNotEnabledError {
-                disabled_action: "Computing score details",
-                feature: "score details",
-                issue_link: "https://github.com/meilisearch/product/discussions/674",
+                disabled_action: "Getting metrics",
+                feature: "metrics",
+                issue_link: "https://github.com/meilisearch/meilisearch/discussions/3518",
             }
             .into())
         }
     }
 
-    pub fn check_metrics(&self) -> Result<()> {
-        if self.instance.metrics {
+    pub fn check_search_analytics(&self) -> Result<()> {
+        if self.instance.search_analytics {
             Ok(())
         } else {
             Err(FeatureNotEnabledError {
-                disabled_action: "Getting metrics",
-                feature: "metrics",
-                issue_link: "https://github.com/meilisearch/meilisearch/discussions/3518",
+                disabled_action: "Using the search analytics",
+                feature: "search analytics",
+                issue_link: "https://www.meilisearch.com/docs/learn/configuration/instance_options",
+            }
+            .into())
+        }
+    }
+
+    pub fn check_query_suggestions(&self) -> Result<()> {
+        if self.instance.query_suggestions {
+            Ok(())
+        } else {
+            Err(FeatureNotEnabledError {
+                disabled_action: "Using the query suggestions",
+                feature: "query suggestions",
+                issue_link: "https://www.meilisearch.com/docs/learn/configuration/instance_options",
             }
             .into())
         }
     }
 
-    pub fn check_vector(&self) -> Result<()> {
+    pub fn check_vector(&self, disabled_action: &'static str) -> Result<()> {
         if self.runtime.vector_store {
             Ok(())
         } else {
             Err(FeatureNotEnabledError {
-                disabled_action: "Passing `vector` as a query parameter",
+                disabled_action,
                 feature: "vector store",
                 issue_link: "https://github.com/meilisearch/product/discussions/677",
             }
@@ -5,7 +5,7 @@ use std::{fs, thread};
 
 use log::error;
 use meilisearch_types::heed::types::{SerdeJson, Str};
-use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn};
+use meilisearch_types::heed::{CompactionOption, Database, Env, RoTxn, RwTxn};
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::{FieldDistribution, Index};
 use serde::{Deserialize, Serialize};
@@ -22,6 +22,9 @@ mod index_map;
 const INDEX_MAPPING: &str = "index-mapping";
 const INDEX_STATS: &str = "index-stats";
 
+/// How often the size of an index copy is reported while it is written.
+const COPY_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Structure managing meilisearch's indexes.
 ///
 /// It is responsible for:
@@ -208,6 +211,13 @@ impl IndexMapper {
 
         wtxn.commit()?;
 
+        self.close_and_remove_index(uuid, name)
+    }
+
+    /// Closes the environment of the index and removes it from the disk in a separate thread.
+    ///
+    /// The index must have been removed from the mapping table beforehand.
+    fn close_and_remove_index(&self, uuid: Uuid, name: &str) -> Result<()> {
         let mut tries = 0;
         // Attempts to remove the index from the in-memory index map in a loop.
         //
@@ -411,6 +421,131 @@ impl IndexMapper {
             .collect()
     }
 
+    /// Creates the `new_name` index with a copy of the LMDB environment of the `name` index.
+    ///
+    /// The copy keeps the documents, the settings and the dates of the duplicated index.
+    /// It is made before opening the write transaction mapping the new name to it, and the
+    /// given function is called right before this transaction is committed.
+    pub fn duplicate_index(
+        &self,
+        env: &Env,
+        name: &str,
+        new_name: &str,
+        before_commit: impl FnOnce(&mut RwTxn) -> Result<()>,
+    ) -> Result<Index> {
+        let rtxn = env.read_txn()?;
+        let index = self.index(&rtxn, name)?;
+        if self.index_exists(&rtxn, new_name)? {
+            return Err(Error::IndexAlreadyExists(new_name.to_string()));
+        }
+        drop(rtxn);
+
+        let uuid = Uuid::new_v4();
+        let new_index = self.copy_index(&index, uuid, |_| ())?;
+
+        let result = env.write_txn().map_err(Error::from).and_then(|mut wtxn| {
+            // The name may have been taken by a batch processed during the copy.
+            if self.index_exists(&wtxn, new_name)? {
+                return Err(Error::IndexAlreadyExists(new_name.to_string()));
+            }
+            self.index_mapping.put(&mut wtxn, new_name, &uuid)?;
+            before_commit(&mut wtxn)?;
+            Ok(wtxn.commit()?)
+        });
+
+        match result {
+            Ok(()) => Ok(new_index),
+            Err(error) => {
+                drop(new_index);
+                self.close_and_remove_index(uuid, new_name)?;
+                Err(error)
+            }
+        }
+    }
+
+    /// Replaces the LMDB environment of the index by a compacted copy, which reclaims
+    /// the free pages left by the deletions and updates.
+    ///
+    /// The copy is written in a new directory before opening the write transaction mapping
+    /// the index name to it, and the previous environment is removed once it is closed.
+    ///
+    /// The `progress` function receives the number of bytes copied and the size of the index
+    /// on the disk before the compaction while the copy is written. The `before_commit` function
+    /// receives the size of the index on the disk before and after the compaction, in bytes,
+    /// and is called right before the transaction is committed.
+    pub fn compact_index(
+        &self,
+        env: &Env,
+        name: &str,
+        progress: impl Fn(u64, u64),
+        before_commit: impl FnOnce(&mut RwTxn, u64, u64) -> Result<()>,
+    ) -> Result<()> {
+        let rtxn = env.read_txn()?;
+        let index = self.index(&rtxn, name)?;
+        let old_uuid = self
+            .index_mapping
+            .get(&rtxn, name)?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        drop(rtxn);
+        let pre_compaction_size = index.on_disk_size()?;
+
+        let uuid = Uuid::new_v4();
+        let compacted =
+            self.copy_index(&index, uuid, |copied| progress(copied, pre_compaction_size))?;
+        let post_compaction_size = compacted.on_disk_size()?;
+        drop(compacted);
+
+        let result = env.write_txn().map_err(Error::from).and_then(|mut wtxn| {
+            // The stats are stored by uuid, the caller computes the ones of the compacted index.
+            self.index_stats.delete(&mut wtxn, &old_uuid)?;
+            self.index_mapping.put(&mut wtxn, name, &uuid)?;
+            before_commit(&mut wtxn, pre_compaction_size, post_compaction_size)?;
+            Ok(wtxn.commit()?)
+        });
+
+        match result {
+            Ok(()) => self.close_and_remove_index(old_uuid, name),
+            Err(error) => {
+                self.close_and_remove_index(uuid, name)?;
+                Err(error)
+            }
+        }
+    }
+
+    /// Writes a compacted copy of the LMDB environment of the index in a new directory,
+    /// and opens it.
+    ///
+    /// The given function receives the number of bytes copied while the copy is written.
+    fn copy_index(&self, index: &Index, uuid: Uuid, progress: impl Fn(u64)) -> Result<Index> {
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+        let data_path = index_path.join("data.mdb");
+
+        // LMDB opens its own read transaction to copy a consistent state of the environment.
+        let copied = thread::scope(|s| {
+            let copy = s.spawn(|| index.copy_to_path(&data_path, CompactionOption::Enabled));
+            while !copy.is_finished() {
+                if let Ok(metadata) = fs::metadata(&data_path) {
+                    progress(metadata.len());
+                }
+                thread::sleep(COPY_PROGRESS_INTERVAL);
+            }
+            copy.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        });
+        if let Err(error) = copied {
+            let _ = fs::remove_dir_all(&index_path);
+            return Err(error.into());
+        }
+
+        self.index_map.write().unwrap().create(
+            &uuid,
+            &index_path,
+            None,
+            self.enable_mdb_writemap,
+            self.index_base_map_size,
+        )
+    }
+
     /// Swap two index names.
     pub fn swap(&self, wtxn: &mut RwTxn, lhs: &str, rhs: &str) -> Result<()> {
         let lhs_uuid = self
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+
+use meilisearch_types::heed::types::{SerdeJson, Str};
+use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn};
+use meilisearch_types::index_templates::IndexTemplate;
+
+use crate::Result;
+
+const INDEX_TEMPLATES: &str = "index-templates";
+
+#[derive(Clone)]
+pub(crate) struct IndexTemplates {
+    templates: Database<Str, SerdeJson<IndexTemplate>>,
+}
+
+impl IndexTemplates {
+    pub fn new(env: &Env) -> Result<Self> {
+        let mut wtxn = env.write_txn()?;
+        let templates = env.create_database(&mut wtxn, Some(INDEX_TEMPLATES))?;
+        wtxn.commit()?;
+
+        Ok(Self { templates })
+    }
+
+    pub fn all(&self, rtxn: &RoTxn) -> Result<BTreeMap<String, IndexTemplate>> {
+        let mut templates = BTreeMap::new();
+        for result in self.templates.iter(rtxn)? {
+            let (name, template) = result?;
+            templates.insert(name.to_string(), template);
+        }
+        Ok(templates)
+    }
+
+    pub fn get(&self, rtxn: &RoTxn, name: &str) -> Result<Option<IndexTemplate>> {
+        Ok(self.templates.get(rtxn, name)?)
+    }
+
+    pub fn put(&self, wtxn: &mut RwTxn, name: &str, template: &IndexTemplate) -> Result<()> {
+        Ok(self.templates.put(wtxn, name, template)?)
+    }
+
+    pub fn delete(&self, wtxn: &mut RwTxn, name: &str) -> Result<bool> {
+        Ok(self.templates.delete(wtxn, name)?)
+    }
+
+    /// Returns the template applied to a newly created index.
+    ///
+    /// The templates with an uid pattern take precedence over the ones without,
+    /// then the first matching template by name order is returned.
+    pub fn matching(&self, rtxn: &RoTxn, index_uid: &str) -> Result<Option<IndexTemplate>> {
+        let mut fallback = None;
+        for result in self.templates.iter(rtxn)? {
+            let (_name, template) = result?;
+            if !template.matches(index_uid) {
+                continue;
+            }
+            if template.uid_pattern.is_some() {
+                return Ok(Some(template));
+            }
+            fallback.get_or_insert(template);
+        }
+        Ok(fallback)
+    }
+}
@@ -15,7 +15,12 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
 
     let IndexScheduler {
         autobatching_enabled,
+        max_concurrent_batches: _,
         must_stop_processing: _,
+        shutting_down: _,
+        download_credentials: _,
+        schedule_credentials: _,
+        progress: _,
         processing_tasks,
         file_store,
         env,
@@ -27,9 +32,22 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         enqueued_at,
         started_at,
         finished_at,
+        batches: _,
+        processing_batches: _,
+        applied_batches: _,
         index_mapper,
         features: _,
+        index_templates: _,
+        schedules: _,
+        search_analytics: _,
+        query_suggestions: _,
+        search_cache: _,
+        search_thread_pool: _,
         max_number_of_tasks: _,
+        task_retention_period: _,
+        task_retention_count: _,
+        max_indexing_payload_size: _,
+        allow_private_documents_urls: _,
         wake_up: _,
         dumps_path: _,
         snapshots_path: _,
@@ -148,6 +166,7 @@ pub fn snapshot_task(task: &Task) -> String {
         details,
         status,
         kind,
+        request_id: _,
     } = task;
     snap.push('{');
     snap.push_str(&format!("uid: {uid}, "));
@@ -172,8 +191,21 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            matched_percolate_queries,
+            duplicated_documents,
         } => {
-            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            let mut snap = format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}");
+            if !matched_percolate_queries.is_empty() {
+                snap.push_str(&format!(", matched_percolate_queries: {matched_percolate_queries:?}"));
+            }
+            if let Some(duplicated_documents) = duplicated_documents {
+                snap.push_str(&format!(", duplicated_documents: {duplicated_documents}"));
+            }
+            snap.push_str(" }");
+            snap
+        }
+        Details::DocumentAdditionDryRun { received_documents, invalid_documents, document_errors } => {
+            format!("{{ received_documents: {received_documents}, invalid_documents: {invalid_documents:?}, document_errors: {document_errors:?} }}")
         }
         Details::SettingsUpdate { settings } => {
             format!("{{ settings: {settings:?} }}")
@@ -211,6 +243,9 @@ fn snapshot_details(d: &Details) -> String {
         Details::IndexSwap { swaps } => {
             format!("{{ swaps: {swaps:?} }}")
         }
+        Details::IndexCompaction { pre_compaction_size, post_compaction_size } => {
+            format!("{{ pre_compaction_size: {pre_compaction_size:?}, post_compaction_size: {post_compaction_size:?} }}")
+        }
     }
 }
 
@@ -23,35 +23,48 @@ mod batch;
 pub mod error;
 mod features;
 mod index_mapper;
+mod index_templates;
 #[cfg(test)]
 mod insta_snapshot;
 mod lru;
+mod progress;
+pub mod query_suggestions;
+pub mod request_ids;
+mod schedules;
+pub mod search_analytics;
+pub mod search_cache;
 mod utils;
 mod uuid_codec;
 
 pub type Result<T> = std::result::Result<T, Error>;
 pub type TaskId = u32;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
 pub use features::RoFeatures;
 use file_store::FileStore;
+use meilisearch_types::batches::{Batch, BatchId, BatchStats};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::features::{InstanceTogglableFeatures, RuntimeTogglableFeatures};
 use meilisearch_types::heed::types::{OwnedType, SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{self, Database, Env, RoTxn, RwTxn};
+pub use meilisearch_types::index_templates::IndexTemplate;
 use meilisearch_types::milli::documents::DocumentsBatchBuilder;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::update::{IndexerConfig, UpdateIndexingStep};
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
+pub use meilisearch_types::schedules::{CronExpression, InvalidCronExpression, Schedule};
+use meilisearch_types::settings::apply_settings_to_builder;
 use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+pub use progress::TaskProgress;
+use rayon::ThreadPool;
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
 use time::format_description::well_known::Rfc3339;
@@ -60,11 +73,27 @@ use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, m
 use uuid::Uuid;
 
 use crate::index_mapper::IndexMapper;
+use crate::progress::ProgressHandle;
+use crate::query_suggestions::QuerySuggestions;
+use crate::search_analytics::SearchAnalytics;
+use crate::search_cache::SearchCache;
 use crate::utils::{check_index_swap_validity, clamp_to_page_size};
 
 pub(crate) type BEI128 =
     meilisearch_types::heed::zerocopy::I128<meilisearch_types::heed::byteorder::BE>;
 
+/// How often the schedules are checked for tasks to register.
+const SCHEDULES_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often the scheduler is woken up to record the queries of the searches in the suggestions of their index.
+const QUERY_SUGGESTIONS_RECORD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the end of the processing batches is checked while shutting down.
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the aborted batches are waited for before giving up on the shutdown.
+const SHUTDOWN_ABORT_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Defines a subset of tasks to be retrieved from the [`IndexScheduler`].
 ///
 /// An empty/default query (where each field is set to `None`) matches all tasks.
@@ -146,34 +175,162 @@ impl Query {
     }
 }
 
+/// A batch of tasks currently processing.
 #[derive(Debug, Clone)]
-struct ProcessingTasks {
+struct ProcessingBatch {
+    /// The index the batch operates on, `None` if it operates on the whole instance.
+    index_uid: Option<String>,
     /// The date and time at which the indexation started.
     started_at: OffsetDateTime,
-    /// The list of tasks ids that are currently running.
+    /// The list of tasks ids of the batch.
+    tasks: RoaringBitmap,
+    /// `false` once the processing stopped without finishing the tasks.
+    running: bool,
+    /// The live progress of the indexing operations of the batch.
+    progress: ProgressHandle,
+    /// Raised to abort the processing of this batch only.
+    must_stop_processing: MustStopProcessing,
+    /// The indexing step currently running and the time at which it was reached.
+    current_indexing_step: Option<(&'static str, Instant)>,
+    /// The stats of the tasks, filled with the time spent in each indexing step.
+    stats: BatchStats,
+}
+
+impl ProcessingBatch {
+    fn new(
+        index_uid: Option<String>,
+        started_at: OffsetDateTime,
+        tasks: RoaringBitmap,
+        progress: ProgressHandle,
+    ) -> ProcessingBatch {
+        ProcessingBatch {
+            index_uid,
+            started_at,
+            tasks,
+            running: true,
+            progress,
+            must_stop_processing: MustStopProcessing::default(),
+            current_indexing_step: None,
+            stats: BatchStats::default(),
+        }
+    }
+
+    /// Measures the time spent in each indexing step from the progress of the indexing operations.
+    ///
+    /// A step lasts until the next one is reached or the processing ends.
+    fn update_indexing_step(&mut self, step: UpdateIndexingStep) {
+        let now = Instant::now();
+        match self.current_indexing_step {
+            Some((current, _)) if current == step.name() => return,
+            Some((current, since)) => self.stats.add_indexing_step(current, now - since),
+            None => (),
+        }
+        self.current_indexing_step = Some((step.name(), now));
+    }
+
+    /// Ends the current indexing step and returns the stats of the processed tasks.
+    fn take_stats(&mut self) -> BatchStats {
+        if let Some((current, since)) = self.current_indexing_step.take() {
+            self.stats.add_indexing_step(current, since.elapsed());
+        }
+        std::mem::take(&mut self.stats)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct ProcessingTasks {
+    /// The list of tasks ids that are currently running, in all the batches.
     processing: RoaringBitmap,
+    /// The batches currently processing, on distinct indexes.
+    batches: Vec<ProcessingBatch>,
 }
 
 impl ProcessingTasks {
-    /// Creates an empty `ProcessingAt` struct.
+    /// Creates an empty `ProcessingTasks` struct.
     fn new() -> ProcessingTasks {
-        ProcessingTasks { started_at: OffsetDateTime::now_utc(), processing: RoaringBitmap::new() }
+        ProcessingTasks::default()
     }
 
-    /// Stores the currently processing tasks, and the date time at which it started.
-    fn start_processing_at(&mut self, started_at: OffsetDateTime, processing: RoaringBitmap) {
-        self.started_at = started_at;
-        self.processing = processing;
+    /// Stores a newly processing batch.
+    ///
+    /// The batches that stopped without finishing are forgotten, their tasks are
+    /// either processed again or canceled by the new batch.
+    fn start_processing(&mut self, batch: ProcessingBatch) {
+        self.batches.retain(|batch| batch.running);
+        self.batches.push(batch);
+        self.update_processing();
     }
 
-    /// Set the processing tasks to an empty list
-    fn stop_processing(&mut self) {
-        self.processing = RoaringBitmap::new();
+    /// Returns the batch reporting its progress with the given handle.
+    fn batch_mut(&mut self, progress: &ProgressHandle) -> Option<&mut ProcessingBatch> {
+        self.batches.iter_mut().find(|batch| batch.progress.is_same(progress))
     }
 
-    /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
-    fn must_cancel_processing_tasks(&self, canceled_tasks: &RoaringBitmap) -> bool {
-        !self.processing.is_disjoint(canceled_tasks)
+    fn update_indexing_step(&mut self, progress: &ProgressHandle, step: UpdateIndexingStep) {
+        if let Some(batch) = self.batch_mut(progress) {
+            batch.update_indexing_step(step);
+        }
+    }
+
+    /// Ends the current indexing step of the batch and returns the stats of its tasks.
+    fn take_stats(&mut self, progress: &ProgressHandle) -> BatchStats {
+        self.batch_mut(progress).map_or_else(BatchStats::default, ProcessingBatch::take_stats)
+    }
+
+    /// Removes the finished batch from the processing tasks.
+    fn stop_processing(&mut self, progress: &ProgressHandle) {
+        self.batches.retain(|batch| !batch.progress.is_same(progress));
+        self.update_processing();
+    }
+
+    /// Keeps the tasks of the stopped batch processing until the next batch starts,
+    /// so that the task cancelation that stopped it knows when they started.
+    fn interrupt_processing(&mut self, progress: &ProgressHandle) {
+        if let Some(batch) = self.batch_mut(progress) {
+            batch.running = false;
+        }
+    }
+
+    fn update_processing(&mut self) {
+        self.processing = self
+            .batches
+            .iter()
+            .fold(RoaringBitmap::new(), |processing, batch| processing | &batch.tasks);
+    }
+
+    /// Returns the batches whose tasks are being processed.
+    fn running_batches(&self) -> impl Iterator<Item = &ProcessingBatch> {
+        self.batches.iter().filter(|batch| batch.running)
+    }
+
+    /// Returns the date and time at which the given processing task started.
+    fn started_at(&self, task_id: TaskId) -> Option<OffsetDateTime> {
+        self.batches
+            .iter()
+            .find(|batch| batch.tasks.contains(task_id))
+            .map(|batch| batch.started_at)
+    }
+
+    /// Returns the tasks of the batches whose processing is still running.
+    fn running_tasks(&self) -> RoaringBitmap {
+        self.running_batches().fold(RoaringBitmap::new(), |tasks, batch| tasks | &batch.tasks)
+    }
+
+    /// Stops the running batches containing at least one of the canceled tasks,
+    /// the batches processing on the other indexes continue.
+    fn stop_canceled_batches(&self, canceled_tasks: &RoaringBitmap) {
+        for batch in self.running_batches() {
+            if !batch.tasks.is_disjoint(canceled_tasks) {
+                batch.must_stop_processing.must_stop();
+            }
+        }
+    }
+
+    /// Stops all the running batches.
+    fn stop_running_batches(&self) {
+        for batch in self.running_batches() {
+            batch.must_stop_processing.must_stop();
+        }
     }
 }
 
@@ -188,9 +345,12 @@ impl MustStopProcessing {
     fn must_stop(&self) {
         self.0.store(true, Relaxed);
     }
+}
 
-    fn reset(&self) {
-        self.0.store(false, Relaxed);
+fn is_batch_authorized(batch: &Batch, filters: &meilisearch_auth::AuthFilter) -> bool {
+    match &batch.index_uid {
+        Some(index_uid) => filters.is_index_authorized(index_uid),
+        None => filters.all_indexes_authorized(),
     }
 }
 
@@ -204,6 +364,9 @@ mod db_name {
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
+    pub const BATCHES: &str = "batches";
+    pub const PROCESSING_BATCHES: &str = "processing-batches";
+    pub const APPLIED_BATCHES: &str = "applied-batches";
 }
 
 #[cfg(test)]
@@ -256,8 +419,24 @@ pub struct IndexSchedulerOptions {
     /// The maximum number of tasks stored in the task queue before starting
     /// to auto schedule task deletions.
     pub max_number_of_tasks: usize,
+    /// The finished tasks older than this duration are deleted when the scheduler is idle.
+    pub task_retention_period: Option<Duration>,
+    /// The maximum number of finished tasks kept, the oldest ones are deleted when the scheduler is idle.
+    pub task_retention_count: Option<u64>,
+    /// The maximum size, in bytes, of the documents downloaded from an url, `None` for no limit.
+    pub max_indexing_payload_size: Option<u64>,
+    /// Whether the documents can be downloaded from the loopback, private or link-local addresses.
+    pub allow_private_documents_urls: bool,
+    /// The maximum number of batches processed at the same time, on distinct indexes.
+    pub max_concurrent_batches: usize,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
+    /// The maximum number of search responses kept in the search cache, zero disables it.
+    pub search_cache_size: usize,
+    /// The duration after which a cached search response is outdated.
+    pub search_cache_ttl: Duration,
+    /// The threads a search can use to parallelize its independent computations.
+    pub search_thread_pool: Option<ThreadPool>,
 }
 
 /// Structure which holds meilisearch's indexes and schedules the tasks
@@ -266,12 +445,34 @@ pub struct IndexScheduler {
     /// The LMDB environment which the DBs are associated with.
     pub(crate) env: Env,
 
-    /// A boolean that can be set to true to stop the currently processing tasks.
+    /// A boolean that can be set to true to stop the batch processed by this scheduler.
+    ///
+    /// Each processing batch has its own, see [`ProcessingBatch::must_stop_processing`].
     pub(crate) must_stop_processing: MustStopProcessing,
 
+    /// Set when the engine is shutting down, the tasks are not registered nor processed anymore.
+    shutting_down: Arc<AtomicBool>,
+
+    /// The `Authorization` headers of the enqueued documents additions from an url.
+    ///
+    /// They are only kept in memory so that the credentials are never written to the disk,
+    /// the tasks enqueued before a restart are processed without them.
+    pub(crate) download_credentials: Arc<RwLock<HashMap<TaskId, String>>>,
+
+    /// The `Authorization` headers of the documents additions from an url registered
+    /// by the schedules, by schedule name.
+    ///
+    /// They are only kept in memory like the download credentials,
+    /// the schedules register their tasks without them after a restart.
+    pub(crate) schedule_credentials: Arc<RwLock<HashMap<String, String>>>,
+
     /// The list of tasks currently processing
     pub(crate) processing_tasks: Arc<RwLock<ProcessingTasks>>,
 
+    /// The progress of the indexing operations of the batch processed by this scheduler,
+    /// each batch is processed by a scheduler reporting its progress with its own handle.
+    pub(crate) progress: ProgressHandle,
+
     /// The list of files referenced by the tasks
     pub(crate) file_store: FileStore,
 
@@ -298,22 +499,68 @@ pub struct IndexScheduler {
     /// Store the task ids of tasks which finished at a specific date
     pub(crate) finished_at: Database<OwnedType<BEI128>, CboRoaringBitmapCodec>,
 
+    /// The processed batches of tasks accessible by their Id.
+    pub(crate) batches: Database<OwnedType<BEU32>, SerdeJson<Batch>>,
+
+    /// The tasks of the batches being processed, accessible by their smallest task id.
+    ///
+    /// A batch is removed in the transaction storing the result of its tasks, so the batches
+    /// left after a crash are processed again before any other task.
+    pub(crate) processing_batches: Database<OwnedType<BEU32>, RoaringBitmapCodec>,
+
+    /// The processed tasks of the batches creating, deleting, duplicating, compacting or
+    /// swapping indexes, accessible by their smallest task id.
+    ///
+    /// They are written in the transaction applying the batch and removed with the processing
+    /// batch, so that a batch interrupted by a crash once applied is not applied twice.
+    pub(crate) applied_batches: Database<OwnedType<BEU32>, SerdeJson<Vec<Task>>>,
+
     /// In charge of creating, opening, storing and returning indexes.
     pub(crate) index_mapper: IndexMapper,
 
     /// In charge of fetching and setting the status of experimental features.
     features: features::FeatureData,
 
+    /// The templates giving their settings to the newly created indexes.
+    pub(crate) index_templates: index_templates::IndexTemplates,
+
+    /// The tasks registered periodically.
+    pub(crate) schedules: schedules::Schedules,
+
+    /// Records the searches made on the indexes, when the search analytics are enabled.
+    pub(crate) search_analytics: Arc<SearchAnalytics>,
+
+    /// Buffers the queries to record in the suggestions of the indexes, when they are enabled.
+    pub(crate) query_suggestions: Arc<QuerySuggestions>,
+
+    /// Caches the responses of the identical repeated searches.
+    pub(crate) search_cache: Arc<SearchCache>,
+
+    /// The threads shared by the searches to parallelize their computations.
+    pub(crate) search_thread_pool: Option<Arc<ThreadPool>>,
+
     /// Get a signal when a batch needs to be processed.
     pub(crate) wake_up: Arc<SignalEvent>,
 
     /// Whether auto-batching is enabled or not.
     pub(crate) autobatching_enabled: bool,
 
+    /// The maximum number of batches processed at the same time, on distinct indexes.
+    pub(crate) max_concurrent_batches: usize,
+
     /// The max number of tasks allowed before the scheduler starts to delete
     /// the finished tasks automatically.
     pub(crate) max_number_of_tasks: usize,
 
+    /// The retention policy of the finished tasks, applied when the scheduler is idle.
+    pub(crate) task_retention_period: Option<Duration>,
+    pub(crate) task_retention_count: Option<u64>,
+
+    /// The maximum size, in bytes, of the documents downloaded from an url.
+    pub(crate) max_indexing_payload_size: Option<u64>,
+    /// Whether the documents can be downloaded from the loopback, private or link-local addresses.
+    pub(crate) allow_private_documents_urls: bool,
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
@@ -350,7 +597,11 @@ impl IndexScheduler {
         IndexScheduler {
             env: self.env.clone(),
             must_stop_processing: self.must_stop_processing.clone(),
+            shutting_down: self.shutting_down.clone(),
+            download_credentials: self.download_credentials.clone(),
+            schedule_credentials: self.schedule_credentials.clone(),
             processing_tasks: self.processing_tasks.clone(),
+            progress: self.progress.clone(),
             file_store: self.file_store.clone(),
             all_tasks: self.all_tasks,
             status: self.status,
@@ -360,10 +611,18 @@ impl IndexScheduler {
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
+            batches: self.batches,
+            processing_batches: self.processing_batches,
+            applied_batches: self.applied_batches,
             index_mapper: self.index_mapper.clone(),
             wake_up: self.wake_up.clone(),
             autobatching_enabled: self.autobatching_enabled,
+            max_concurrent_batches: self.max_concurrent_batches,
             max_number_of_tasks: self.max_number_of_tasks,
+            task_retention_period: self.task_retention_period,
+            task_retention_count: self.task_retention_count,
+            max_indexing_payload_size: self.max_indexing_payload_size,
+            allow_private_documents_urls: self.allow_private_documents_urls,
             snapshots_path: self.snapshots_path.clone(),
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
@@ -375,6 +634,12 @@ impl IndexScheduler {
             #[cfg(test)]
             run_loop_iteration: self.run_loop_iteration.clone(),
             features: self.features.clone(),
+            index_templates: self.index_templates.clone(),
+            schedules: self.schedules.clone(),
+            search_analytics: self.search_analytics.clone(),
+            query_suggestions: self.query_suggestions.clone(),
+            search_cache: self.search_cache.clone(),
+            search_thread_pool: self.search_thread_pool.clone(),
         }
     }
 }
@@ -413,11 +678,13 @@ impl IndexScheduler {
         };
 
         let env = heed::EnvOpenOptions::new()
-            .max_dbs(11)
+            .max_dbs(16)
             .map_size(budget.task_db_size)
             .open(options.tasks_path)?;
 
         let features = features::FeatureData::new(&env, options.instance_features)?;
+        let index_templates = index_templates::IndexTemplates::new(&env)?;
+        let schedules = schedules::Schedules::new(&env)?;
 
         let file_store = FileStore::new(&options.update_file_path)?;
 
@@ -430,12 +697,20 @@ impl IndexScheduler {
         let enqueued_at = env.create_database(&mut wtxn, Some(db_name::ENQUEUED_AT))?;
         let started_at = env.create_database(&mut wtxn, Some(db_name::STARTED_AT))?;
         let finished_at = env.create_database(&mut wtxn, Some(db_name::FINISHED_AT))?;
+        let batches = env.create_database(&mut wtxn, Some(db_name::BATCHES))?;
+        let processing_batches =
+            env.create_database(&mut wtxn, Some(db_name::PROCESSING_BATCHES))?;
+        let applied_batches = env.create_database(&mut wtxn, Some(db_name::APPLIED_BATCHES))?;
         wtxn.commit()?;
 
         // allow unreachable_code to get rids of the warning in the case of a test build.
         let this = Self {
             must_stop_processing: MustStopProcessing::default(),
+            shutting_down: Arc::default(),
+            download_credentials: Arc::default(),
+            schedule_credentials: Arc::default(),
             processing_tasks: Arc::new(RwLock::new(ProcessingTasks::new())),
+            progress: ProgressHandle::default(),
             file_store,
             all_tasks,
             status,
@@ -445,6 +720,9 @@ impl IndexScheduler {
             enqueued_at,
             started_at,
             finished_at,
+            batches,
+            processing_batches,
+            applied_batches,
             index_mapper: IndexMapper::new(
                 &env,
                 options.indexes_path,
@@ -458,7 +736,12 @@ impl IndexScheduler {
             // we want to start the loop right away in case meilisearch was ctrl+Ced while processing things
             wake_up: Arc::new(SignalEvent::auto(true)),
             autobatching_enabled: options.autobatching_enabled,
+            max_concurrent_batches: options.max_concurrent_batches.max(1),
             max_number_of_tasks: options.max_number_of_tasks,
+            task_retention_period: options.task_retention_period,
+            task_retention_count: options.task_retention_count,
+            max_indexing_payload_size: options.max_indexing_payload_size,
+            allow_private_documents_urls: options.allow_private_documents_urls,
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
             auth_path: options.auth_path,
@@ -471,8 +754,21 @@ impl IndexScheduler {
             #[cfg(test)]
             run_loop_iteration: Arc::new(RwLock::new(0)),
             features,
+            index_templates,
+            schedules,
+            search_analytics: Arc::default(),
+            query_suggestions: Arc::default(),
+            search_cache: Arc::new(SearchCache::new(
+                options.search_cache_size,
+                options.search_cache_ttl,
+            )),
+            search_thread_pool: options.search_thread_pool.map(Arc::new),
         };
 
+        if let Err(e) = this.delete_orphan_update_files() {
+            log::error!("Failed to delete the orphan update files: {e}");
+        }
+
         this.run();
         Ok(this)
     }
@@ -586,6 +882,70 @@ impl IndexScheduler {
                 }
             })
             .unwrap();
+
+        let run = self.private_clone();
+        std::thread::Builder::new()
+            .name(String::from("schedules"))
+            .spawn(move || loop {
+                if let Err(e) = run.register_scheduled_tasks(OffsetDateTime::now_utc()) {
+                    log::error!("{}", e);
+                }
+                if let Err(e) = run.apply_task_retention_policy(OffsetDateTime::now_utc()) {
+                    log::error!("{}", e);
+                }
+                std::thread::sleep(SCHEDULES_CHECK_INTERVAL);
+            })
+            .unwrap();
+
+        // the queries are recorded by the scheduler loop, it is only woken up when some are waiting
+        if self.features().check_query_suggestions().is_ok() {
+            let run = self.private_clone();
+            std::thread::Builder::new()
+                .name(String::from("query-suggestions"))
+                .spawn(move || loop {
+                    if !run.query_suggestions.is_empty() {
+                        run.wake_up.signal();
+                    }
+                    std::thread::sleep(QUERY_SUGGESTIONS_RECORD_INTERVAL);
+                })
+                .unwrap();
+        }
+    }
+
+    /// Stops registering and processing new tasks and waits for the end of the processing batches.
+    ///
+    /// The batches still processing after the grace period are aborted, their tasks stay enqueued
+    /// and are processed again at the next start of the engine. The operations that can't be
+    /// aborted are not waited for more than [`SHUTDOWN_ABORT_TIMEOUT`].
+    ///
+    /// Returns `false` if some batches were still processing when giving up.
+    pub fn shutdown(&self, grace_period: Duration) -> bool {
+        self.shutting_down.store(true, Relaxed);
+
+        let is_processing =
+            || self.processing_tasks.read().unwrap().running_batches().next().is_some();
+        let wait_until = |deadline: Instant| {
+            while is_processing() {
+                if Instant::now() >= deadline {
+                    return false;
+                }
+                std::thread::sleep(SHUTDOWN_CHECK_INTERVAL);
+            }
+            true
+        };
+
+        if wait_until(Instant::now() + grace_period) {
+            return true;
+        }
+
+        log::warn!("Aborting the processing tasks at the end of the shutdown grace period.");
+        self.processing_tasks.read().unwrap().stop_running_batches();
+        if wait_until(Instant::now() + SHUTDOWN_ABORT_TIMEOUT) {
+            return true;
+        }
+
+        log::error!("The processing tasks could not be aborted, shutting down without them.");
+        false
     }
 
     pub fn indexer_config(&self) -> &IndexerConfig {
@@ -646,9 +1006,8 @@ impl IndexScheduler {
 
     /// Return the task ids matched by the given query from the index scheduler's point of view.
     pub(crate) fn get_task_ids(&self, rtxn: &RoTxn, query: &Query) -> Result<RoaringBitmap> {
-        let ProcessingTasks {
-            started_at: started_at_processing, processing: processing_tasks, ..
-        } = self.processing_tasks.read().unwrap().clone();
+        let ProcessingTasks { processing: processing_tasks, batches: processing_batches } =
+            self.processing_tasks.read().unwrap().clone();
 
         let mut tasks = self.all_task_ids(rtxn)?;
 
@@ -722,17 +1081,19 @@ impl IndexScheduler {
                 (&tasks - &processing_tasks, &tasks & &processing_tasks);
 
             // special case for Processing tasks
-            // A closure that clears the filtered_processing_tasks if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_tasks of the batches whose started_at date falls outside the given bounds
             let mut clear_filtered_processing_tasks =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &started_at_processing.unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_tasks.clear();
+                    for batch in &processing_batches {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_tasks -= &batch.tasks;
+                        }
                     }
                 };
             match (query.after_started_at, query.before_started_at) {
@@ -905,20 +1266,19 @@ impl IndexScheduler {
             tasks.into_iter().rev().take(query.limit.unwrap_or(u32::MAX) as usize),
         )?;
 
-        let ProcessingTasks { started_at, processing, .. } =
+        let processing_tasks =
             self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?.clone();
 
         let ret = tasks.into_iter();
-        if processing.is_empty() {
+        if processing_tasks.processing.is_empty() {
             Ok((ret.collect(), total))
         } else {
             Ok((
-                ret.map(|task| {
-                    if processing.contains(task.uid) {
+                ret.map(|task| match processing_tasks.started_at(task.uid) {
+                    Some(started_at) => {
                         Task { status: Status::Processing, started_at: Some(started_at), ..task }
-                    } else {
-                        task
                     }
+                    None => task,
                 })
                 .collect(),
                 total,
@@ -926,10 +1286,75 @@ impl IndexScheduler {
         }
     }
 
+    /// Returns the progress of the indexing operations of the given task,
+    /// `None` if it is not processing or if its processing has no indexing step.
+    pub fn task_progress(&self, task_uid: TaskId) -> Result<Option<TaskProgress>> {
+        let processing_tasks =
+            self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?;
+        Ok(processing_tasks
+            .running_batches()
+            .find(|batch| batch.tasks.contains(task_uid))
+            .and_then(|batch| batch.progress.get()))
+    }
+
+    /// Returns the processed batches from the most recent one, starting at the `from` batch.
+    ///
+    /// Only the batches processed on an index authorized by the filters are returned,
+    /// the batches unrelated to one index require every index to be authorized.
+    pub fn get_batches_from_authorized_indexes(
+        &self,
+        from: Option<BatchId>,
+        limit: usize,
+        filters: &meilisearch_auth::AuthFilter,
+    ) -> Result<Vec<Batch>> {
+        let rtxn = self.env.read_txn()?;
+
+        let range = ..=BEU32::new(from.unwrap_or(BatchId::MAX));
+        let mut batches = Vec::new();
+        for result in self.batches.rev_range(&rtxn, &range)? {
+            if batches.len() == limit {
+                break;
+            }
+            let (_, batch) = result?;
+            if is_batch_authorized(&batch, filters) {
+                batches.push(batch);
+            }
+        }
+
+        Ok(batches)
+    }
+
+    /// Returns the processed batch with the given uid if its index is authorized by the filters.
+    pub fn get_batch_from_authorized_indexes(
+        &self,
+        uid: BatchId,
+        filters: &meilisearch_auth::AuthFilter,
+    ) -> Result<Batch> {
+        let rtxn = self.env.read_txn()?;
+        match self.batches.get(&rtxn, &BEU32::new(uid))? {
+            Some(batch) if is_batch_authorized(&batch, filters) => Ok(batch),
+            _ => Err(Error::BatchNotFound(uid)),
+        }
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
     pub fn register(&self, kind: KindWithContent) -> Result<Task> {
+        self.register_with_request_id(kind, None)
+    }
+
+    /// Register a new task in the scheduler like [`Self::register`], the log events
+    /// emitted while processing it are correlated with the given request id.
+    pub fn register_with_request_id(
+        &self,
+        kind: KindWithContent,
+        request_id: Option<String>,
+    ) -> Result<Task> {
+        if self.shutting_down.load(Relaxed) {
+            return Err(Error::ShuttingDown);
+        }
+
         let mut wtxn = self.env.write_txn()?;
 
         // if the task doesn't delete anything and 50% of the task queue is full, we must refuse to enqueue the incomming task
@@ -949,6 +1374,7 @@ impl IndexScheduler {
             details: kind.default_details(),
             status: Status::Enqueued,
             kind: kind.clone(),
+            request_id,
         };
         // For deletion and cancelation tasks, we want to make extra sure that they
         // don't attempt to delete/cancel tasks that are newer than themselves.
@@ -978,6 +1404,14 @@ impl IndexScheduler {
 
         utils::insert_task_datetime(&mut wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
 
+        // The credentials are not serialized with the task, they are kept aside.
+        if let KindWithContent::DocumentAdditionFromUrl {
+            authorization: Some(authorization), ..
+        } = &task.kind
+        {
+            self.download_credentials.write().unwrap().insert(task.uid, authorization.clone());
+        }
+
         if let Err(e) = wtxn.commit() {
             self.delete_persisted_task_data(&task)?;
             return Err(e.into());
@@ -987,10 +1421,7 @@ impl IndexScheduler {
         // we inform the processing tasks to stop (if necessary).
         if let KindWithContent::TaskCancelation { tasks, .. } = kind {
             let tasks_to_cancel = RoaringBitmap::from_iter(tasks);
-            if self.processing_tasks.read().unwrap().must_cancel_processing_tasks(&tasks_to_cancel)
-            {
-                self.must_stop_processing.must_stop();
-            }
+            self.processing_tasks.read().unwrap().stop_canceled_batches(&tasks_to_cancel);
         }
 
         // notify the scheduler loop to execute a new tick
@@ -1030,9 +1461,9 @@ impl IndexScheduler {
         Ok(self.file_store.new_update_with_uuid(uuid)?)
     }
 
-    /// The size on disk taken by all the updates files contained in the `IndexScheduler`, in bytes.
-    pub fn compute_update_file_size(&self) -> Result<u64> {
-        Ok(self.file_store.compute_total_size()?)
+    /// The size on disk taken by the update files of the tasks waiting to be processed, in bytes.
+    pub fn update_files_size(&self) -> u64 {
+        self.file_store.size()
     }
 
     /// Delete a file from the index scheduler.
@@ -1064,9 +1495,16 @@ impl IndexScheduler {
 
         puffin::GlobalProfiler::lock().new_frame();
 
+        if self.shutting_down.load(Relaxed) {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
         self.cleanup_task_queue()?;
 
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+        if let Err(e) = self.record_query_suggestions(&rtxn) {
+            log::error!("Could not record the query suggestions: {e}");
+        }
         let batch =
             match self.create_next_batch(&rtxn).map_err(|e| Error::CreateBatch(Box::new(e)))? {
                 Some(batch) => batch,
@@ -1078,26 +1516,97 @@ impl IndexScheduler {
         // 1. store the starting date with the bitmap of processing tasks.
         let mut ids = batch.ids();
         ids.sort_unstable();
-        let processed_tasks = ids.len();
         let processing_tasks = RoaringBitmap::from_sorted_iter(ids.iter().copied()).unwrap();
         let started_at = OffsetDateTime::now_utc();
+        let progress = ProgressHandle::default();
+
+        // The tasks of the batch are persisted to be processed again if the engine crashes.
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.processing_batches.put(&mut wtxn, &BEU32::new(ids[0]), &processing_tasks)?;
+        wtxn.commit().map_err(Error::HeedTransaction)?;
 
-        // We reset the must_stop flag to be sure that we don't stop processing tasks
-        self.must_stop_processing.reset();
-        self.processing_tasks.write().unwrap().start_processing_at(started_at, processing_tasks);
+        let processing_batch =
+            ProcessingBatch::new(index_uid.clone(), started_at, processing_tasks, progress.clone());
+        let must_stop_processing = processing_batch.must_stop_processing.clone();
+        self.processing_tasks.write().unwrap().start_processing(processing_batch);
 
         #[cfg(test)]
         self.breakpoint(Breakpoint::BatchCreated);
 
-        // 2. Process the tasks
-        let res = {
-            let cloned_index_scheduler = self.private_clone();
-            let handle = std::thread::Builder::new()
-                .name(String::from("batch-operation"))
-                .spawn(move || cloned_index_scheduler.process_batch(batch))
+        // 2. Process the tasks, a batch operating on an index is processed
+        // while the next batches are created for the other indexes.
+        if self.max_concurrent_batches > 1 && index_uid.is_some() {
+            let index_scheduler = self.private_clone();
+            std::thread::Builder::new()
+                .name(String::from("concurrent-batch"))
+                .spawn(move || {
+                    let res = index_scheduler.process_batch_in_thread(
+                        batch,
+                        &progress,
+                        must_stop_processing,
+                    );
+                    if let Err(e) =
+                        index_scheduler.finish_batch(ids, index_uid, started_at, &progress, res)
+                    {
+                        log::error!("{}", e);
+                        index_scheduler
+                            .processing_tasks
+                            .write()
+                            .unwrap()
+                            .interrupt_processing(&progress);
+                    }
+                    // notify the scheduler loop that the index can be processed again
+                    index_scheduler.wake_up.signal();
+                })
                 .unwrap();
-            handle.join().unwrap_or(Err(Error::ProcessBatchPanicked))
-        };
+            return Ok(TickOutcome::TickAgain(0));
+        }
+
+        let res = self.process_batch_in_thread(batch, &progress, must_stop_processing);
+        let outcome = self.finish_batch(ids, index_uid, started_at, &progress, res);
+        if outcome.is_err() {
+            self.processing_tasks.write().unwrap().interrupt_processing(&progress);
+        }
+        outcome
+    }
+
+    /// Processes the batch in a dedicated thread, the indexing operations report
+    /// their progress with the given handle and stop when the given flag is raised.
+    fn process_batch_in_thread(
+        &self,
+        batch: Batch,
+        progress: &ProgressHandle,
+        must_stop_processing: MustStopProcessing,
+    ) -> Result<Vec<Task>> {
+        let mut cloned_index_scheduler = self.private_clone();
+        cloned_index_scheduler.progress = progress.clone();
+        cloned_index_scheduler.must_stop_processing = must_stop_processing;
+        let request_ids =
+            batch.tasks().into_iter().filter_map(|task| task.request_id.clone()).collect();
+        let handle = std::thread::Builder::new()
+            .name(String::from("batch-operation"))
+            .spawn(move || {
+                request_ids::scope_batch_request_ids(request_ids, || {
+                    cloned_index_scheduler.process_batch(batch)
+                })
+            })
+            .unwrap();
+        handle.join().unwrap_or(Err(Error::ProcessBatchPanicked))
+    }
+
+    /// Updates the information of the tasks of a batch following the end of their processing,
+    /// and stores the batch.
+    ///
+    /// Returns the number of processed tasks.
+    fn finish_batch(
+        &self,
+        ids: Vec<TaskId>,
+        index_uid: Option<String>,
+        started_at: OffsetDateTime,
+        progress: &ProgressHandle,
+        res: Result<Vec<Task>>,
+    ) -> Result<TickOutcome> {
+        let processed_tasks = ids.len();
 
         #[cfg(test)]
         self.maybe_fail(tests::FailureLocation::AcquiringWtxn)?;
@@ -1105,6 +1614,10 @@ impl IndexScheduler {
         let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
 
         let finished_at = OffsetDateTime::now_utc();
+        let mut stats = self.processing_tasks.write().unwrap().take_stats(progress);
+        // The content files are only deleted once the tasks are committed,
+        // they are needed to process the batch again after a crash.
+        let mut finished_tasks = Vec::new();
         match res {
             Ok(tasks) => {
                 #[cfg(test)]
@@ -1124,9 +1637,8 @@ impl IndexScheduler {
 
                     self.update_task(&mut wtxn, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
-                    if let Err(e) = self.delete_persisted_task_data(&task) {
-                        log::error!("Failure to delete the content files associated with task {}. Error: {e}", task.uid);
-                    }
+                    stats.add_task(&task);
+                    finished_tasks.push(task);
                 }
                 log::info!("A batch of tasks was successfully completed.");
             }
@@ -1136,13 +1648,17 @@ impl IndexScheduler {
             ))) => {
                 #[cfg(test)]
                 self.breakpoint(Breakpoint::AbortedIndexation);
-                wtxn.abort().map_err(Error::HeedTransaction)?;
+                // Nothing was written by the batch, it is created again by the next tick.
+                self.processing_batches.delete(&mut wtxn, &BEU32::new(ids[0]))?;
+                self.applied_batches.delete(&mut wtxn, &BEU32::new(ids[0]))?;
+                wtxn.commit().map_err(Error::HeedTransaction)?;
 
                 // We make sure that we don't call `stop_processing` on the `processing_tasks`,
                 // this is because we want to let the next tick call `create_next_batch` and keep
                 // the `started_at` date times and `processings` of the current processing tasks.
                 // This date time is used by the task cancelation to store the right `started_at`
                 // date in the task on disk.
+                self.processing_tasks.write().unwrap().interrupt_processing(progress);
                 return Ok(TickOutcome::TickAgain(0));
             }
             // If an index said it was full, we need to:
@@ -1157,8 +1673,11 @@ impl IndexScheduler {
                 let index_uid = index_uid.unwrap();
                 // fixme: handle error more gracefully? not sure when this could happen
                 self.index_mapper.resize_index(&wtxn, &index_uid)?;
-                wtxn.abort().map_err(Error::HeedTransaction)?;
+                self.processing_batches.delete(&mut wtxn, &BEU32::new(ids[0]))?;
+                self.applied_batches.delete(&mut wtxn, &BEU32::new(ids[0]))?;
+                wtxn.commit().map_err(Error::HeedTransaction)?;
 
+                self.processing_tasks.write().unwrap().interrupt_processing(progress);
                 return Ok(TickOutcome::TickAgain(0));
             }
             // In case of a failure we must get back and patch all the tasks with the error.
@@ -1166,7 +1685,7 @@ impl IndexScheduler {
                 #[cfg(test)]
                 self.breakpoint(Breakpoint::ProcessBatchFailed);
                 let error: ResponseError = err.into();
-                for id in ids {
+                for &id in &ids {
                     let mut task = self
                         .get_task(&wtxn, id)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?
@@ -1180,28 +1699,134 @@ impl IndexScheduler {
                     #[cfg(test)]
                     self.maybe_fail(tests::FailureLocation::UpdatingTaskAfterProcessBatchFailure)?;
 
-                    if let Err(e) = self.delete_persisted_task_data(&task) {
-                        log::error!("Failure to delete the content files associated with task {}. Error: {e}", task.uid);
-                    }
                     self.update_task(&mut wtxn, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
+                    stats.add_task(&task);
+                    finished_tasks.push(task);
                 }
             }
         }
 
-        self.processing_tasks.write().unwrap().stop_processing();
+        self.processing_batches.delete(&mut wtxn, &BEU32::new(ids[0]))?;
+        self.applied_batches.delete(&mut wtxn, &BEU32::new(ids[0]))?;
+        let batch = Batch {
+            uid: self.next_batch_id(&wtxn)?,
+            index_uid,
+            task_uids: ids,
+            stats,
+            started_at,
+            finished_at,
+        };
+        self.batches.put(&mut wtxn, &BEU32::new(batch.uid), &batch)?;
 
         #[cfg(test)]
         self.maybe_fail(tests::FailureLocation::CommittingWtxn)?;
 
         wtxn.commit().map_err(Error::HeedTransaction)?;
 
+        // The tasks are only removed from the processing ones once their new status is committed,
+        // otherwise the next batch could be created with the tasks still enqueued on disk.
+        self.processing_tasks.write().unwrap().stop_processing(progress);
+
+        for task in finished_tasks {
+            if let Err(e) = self.delete_persisted_task_data(&task) {
+                log::error!(
+                    "Failure to delete the content files associated with task {}. Error: {e}",
+                    task.uid
+                );
+            }
+        }
+
         #[cfg(test)]
         self.breakpoint(Breakpoint::AfterProcessing);
 
         Ok(TickOutcome::TickAgain(processed_tasks))
     }
 
+    /// Deletes the finished tasks beyond the retention policy of the instance.
+    ///
+    /// It only runs when the scheduler is idle, so the purge never delays the processing of the tasks.
+    pub(crate) fn apply_task_retention_policy(&self, now: OffsetDateTime) -> Result<()> {
+        if self.task_retention_period.is_none() && self.task_retention_count.is_none() {
+            return Ok(());
+        }
+
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        let idle = self.processing_tasks.read().unwrap().processing.is_empty()
+            && self.get_status(&wtxn, Status::Enqueued)?.is_empty();
+        if !idle {
+            return Ok(());
+        }
+
+        let finished = self.get_status(&wtxn, Status::Succeeded)?
+            | self.get_status(&wtxn, Status::Failed)?
+            | self.get_status(&wtxn, Status::Canceled)?;
+
+        let mut to_delete = RoaringBitmap::new();
+        // no task can be older than a period going back before the earliest representable date
+        let before = self
+            .task_retention_period
+            .and_then(|period| time::Duration::try_from(period).ok())
+            .and_then(|period| now.checked_sub(period));
+        if let Some(before) = before {
+            let mut expired = finished.clone();
+            keep_tasks_within_datetimes(&wtxn, &mut expired, self.finished_at, None, Some(before))?;
+            to_delete |= expired;
+        }
+        if let Some(count) = self.task_retention_count {
+            // the task ids are increasing, the oldest tasks come first
+            let excess = finished.len().saturating_sub(count);
+            to_delete.extend(finished.iter().take(excess as usize));
+        }
+
+        if to_delete.is_empty() {
+            return Ok(());
+        }
+
+        let deleted_tasks = self.delete_matched_tasks(&mut wtxn, &to_delete)?;
+        wtxn.commit().map_err(Error::HeedTransaction)?;
+        log::info!("Deleted {deleted_tasks} finished tasks beyond the task retention policy.");
+
+        Ok(())
+    }
+
+    /// Records the buffered queries of the searches in the suggestions of their index.
+    ///
+    /// It is called by the scheduler loop so that the indexes are never written outside of
+    /// their batches: the queries made on the indexes processing a batch are kept until the
+    /// next call, and the ones made on the indexes deleted in the meantime are ignored.
+    pub(crate) fn record_query_suggestions(&self, rtxn: &RoTxn) -> Result<()> {
+        if self.query_suggestions.is_empty() {
+            return Ok(());
+        }
+
+        let processing = self.processing_tasks.read().unwrap().processing.clone();
+        let mut processing_indexes = HashSet::new();
+        for task in self.get_existing_tasks(rtxn, processing)? {
+            processing_indexes.extend(task.indexes().into_iter().map(String::from));
+        }
+
+        let queries_per_index =
+            self.query_suggestions.take(|index_uid| !processing_indexes.contains(index_uid));
+        for (index_uid, queries) in queries_per_index {
+            let index = match self.index_mapper.index(rtxn, &index_uid) {
+                Ok(index) => index,
+                Err(Error::IndexNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            };
+
+            let mut wtxn = index.write_txn()?;
+            let mut builder = milli::update::RecordSuggestions::new(&mut wtxn, &index);
+            for (query, popularity) in &queries {
+                builder.add_with_popularity(query, *popularity);
+            }
+            builder.execute()?;
+            wtxn.commit()?;
+        }
+
+        Ok(())
+    }
+
     /// Register a task to cleanup the task queue if needed
     fn cleanup_task_queue(&self) -> Result<()> {
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
@@ -1264,13 +1889,179 @@ impl IndexScheduler {
         self.features.features(rtxn)
     }
 
+    /// The searches recorded on the indexes, see [`RoFeatures::check_search_analytics`].
+    pub fn search_analytics(&self) -> &SearchAnalytics {
+        &self.search_analytics
+    }
+
+    /// The queries to record in the suggestions of the indexes, see [`RoFeatures::check_query_suggestions`].
+    pub fn query_suggestions(&self) -> &QuerySuggestions {
+        &self.query_suggestions
+    }
+
+    /// The responses of the previous searches, invalidated when their index is modified.
+    pub fn search_cache(&self) -> &SearchCache {
+        &self.search_cache
+    }
+
+    /// The threads the searches can use, `None` when the searches are not parallelized.
+    pub fn search_thread_pool(&self) -> Option<Arc<ThreadPool>> {
+        self.search_thread_pool.clone()
+    }
+
     pub fn put_runtime_features(&self, features: RuntimeTogglableFeatures) -> Result<()> {
         let wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
         self.features.put_runtime_features(wtxn, features)?;
         Ok(())
     }
 
+    /// Returns the index templates by name.
+    pub fn index_templates(&self) -> Result<BTreeMap<String, IndexTemplate>> {
+        let rtxn = self.read_txn()?;
+        self.index_templates.all(&rtxn)
+    }
+
+    pub fn index_template(&self, name: &str) -> Result<IndexTemplate> {
+        let rtxn = self.read_txn()?;
+        self.index_templates
+            .get(&rtxn, name)?
+            .ok_or_else(|| Error::IndexTemplateNotFound(name.to_string()))
+    }
+
+    /// Creates or replaces the template, it only applies to the indexes created afterward.
+    /// Creates or replaces the template once its settings are known to be valid.
+    pub fn put_index_template(&self, name: &str, template: &IndexTemplate) -> Result<()> {
+        self.validate_index_template(template)?;
+
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.index_templates.put(&mut wtxn, name, template)?;
+        wtxn.commit().map_err(Error::HeedTransaction)?;
+        Ok(())
+    }
+
+    /// Applies the settings of the template to a temporary empty index, so an invalid template
+    /// is rejected when it is created instead of failing the creation of the matching indexes.
+    fn validate_index_template(&self, template: &IndexTemplate) -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut options = heed::EnvOpenOptions::new();
+        options.map_size(clamp_to_page_size(10 * 1024 * 1024));
+        let index = Index::new(options, dir.path())?;
+
+        let mut wtxn = index.write_txn()?;
+        let indexer_config = self.index_mapper.indexer_config();
+        let mut builder = milli::update::Settings::new(&mut wtxn, &index, indexer_config);
+        apply_settings_to_builder(&template.settings.clone().check(), &mut builder);
+        builder.execute(|_| (), || false)?;
+        wtxn.abort().map_err(Error::HeedTransaction)?;
+
+        drop(index);
+        Ok(())
+    }
+
+    pub fn delete_index_template(&self, name: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        if !self.index_templates.delete(&mut wtxn, name)? {
+            return Err(Error::IndexTemplateNotFound(name.to_string()));
+        }
+        wtxn.commit().map_err(Error::HeedTransaction)?;
+        Ok(())
+    }
+
+    /// Returns the schedules by name.
+    pub fn schedules(&self) -> Result<BTreeMap<String, Schedule>> {
+        let rtxn = self.read_txn()?;
+        self.schedules.all(&rtxn)
+    }
+
+    pub fn schedule(&self, name: &str) -> Result<Schedule> {
+        let rtxn = self.read_txn()?;
+        self.schedules.get(&rtxn, name)?.ok_or_else(|| Error::ScheduleNotFound(name.to_string()))
+    }
+
+    /// Creates or replaces the schedule, the task is first registered at the next date
+    /// matching the cron expression.
+    pub fn put_schedule(
+        &self,
+        name: &str,
+        cron: CronExpression,
+        kind: KindWithContent,
+    ) -> Result<Schedule> {
+        let schedule = Schedule::new(cron, kind, OffsetDateTime::now_utc());
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.schedules.put(&mut wtxn, name, &schedule)?;
+        wtxn.commit().map_err(Error::HeedTransaction)?;
+
+        // The credentials are not serialized with the schedule, they are kept aside.
+        let mut credentials = self.schedule_credentials.write().unwrap();
+        match &schedule.kind {
+            KindWithContent::DocumentAdditionFromUrl {
+                authorization: Some(authorization), ..
+            } => credentials.insert(name.to_string(), authorization.clone()),
+            _ => credentials.remove(name),
+        };
+
+        Ok(schedule)
+    }
+
+    pub fn delete_schedule(&self, name: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        if !self.schedules.delete(&mut wtxn, name)? {
+            return Err(Error::ScheduleNotFound(name.to_string()));
+        }
+        wtxn.commit().map_err(Error::HeedTransaction)?;
+        self.schedule_credentials.write().unwrap().remove(name);
+        Ok(())
+    }
+
+    /// Registers the tasks of the schedules whose date has come and moves them to their next date.
+    pub(crate) fn register_scheduled_tasks(&self, now: OffsetDateTime) -> Result<()> {
+        let rtxn = self.read_txn()?;
+        let due = self.schedules.due(&rtxn, now)?;
+        drop(rtxn);
+
+        for (name, schedule) in due {
+            let mut kind = schedule.kind.clone();
+            if let KindWithContent::DocumentAdditionFromUrl { authorization, .. } = &mut kind {
+                *authorization = self.schedule_credentials.read().unwrap().get(&name).cloned();
+            }
+            self.register(kind)?;
+
+            let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+            // the schedule may have been replaced or deleted in the meantime
+            if self.schedules.get(&wtxn, &name)?.as_ref() == Some(&schedule) {
+                let next_run_at = schedule.cron.next_after(now);
+                self.schedules.put(&mut wtxn, &name, &Schedule { next_run_at, ..schedule })?;
+            }
+            wtxn.commit().map_err(Error::HeedTransaction)?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the update files that are not referenced by any enqueued task.
+    ///
+    /// They are left behind when Meilisearch stops between the creation of a file and the
+    /// registration of its task, or between the end of a task and the deletion of its file.
+    fn delete_orphan_update_files(&self) -> Result<()> {
+        let rtxn = self.env.read_txn()?;
+        let enqueued = self.get_status(&rtxn, Status::Enqueued)?;
+        let referenced: HashSet<_> = self
+            .get_existing_tasks(&rtxn, enqueued)?
+            .iter()
+            .filter_map(|task| task.content_uuid())
+            .collect();
+
+        let uuids = self.file_store.all_uuids()?.collect::<file_store::Result<Vec<_>>>()?;
+        for uuid in uuids.into_iter().filter(|uuid| !referenced.contains(uuid)) {
+            log::warn!("Deleting the update file {uuid} that is not used by any enqueued task.");
+            self.delete_update_file(uuid)?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn delete_persisted_task_data(&self, task: &Task) -> Result<()> {
+        self.download_credentials.write().unwrap().remove(&task.uid);
         match task.content_uuid() {
             Some(content_file) => self.delete_update_file(content_file),
             None => Ok(()),
@@ -1365,6 +2156,7 @@ impl<'a> Dump<'a> {
                     method,
                     documents_count,
                     allow_index_creation,
+                    duplicate_ids_policy,
                 } => KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
@@ -1372,6 +2164,37 @@ impl<'a> Dump<'a> {
                     content_file: content_uuid.ok_or(Error::CorruptedDump)?,
                     documents_count,
                     allow_index_creation,
+                    duplicate_ids_policy,
+                },
+                KindDump::DocumentImportFromUrl {
+                    primary_key,
+                    method,
+                    url,
+                    has_authorization,
+                    format,
+                    allow_index_creation,
+                    duplicate_ids_policy,
+                } => KindWithContent::DocumentAdditionFromUrl {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    primary_key,
+                    method,
+                    url,
+                    authorization: None,
+                    has_authorization,
+                    format,
+                    allow_index_creation,
+                    duplicate_ids_policy,
+                },
+                KindDump::DocumentImportDryRun {
+                    primary_key,
+                    documents_count,
+                    duplicate_ids_policy,
+                } => KindWithContent::DocumentAdditionDryRun {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    primary_key,
+                    content_file: content_uuid.ok_or(Error::CorruptedDump)?,
+                    documents_count,
+                    duplicate_ids_policy,
                 },
                 KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
                     documents_ids,
@@ -1405,6 +2228,15 @@ impl<'a> Dump<'a> {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
                 },
+                KindDump::IndexDuplication { source_index_uid } => {
+                    KindWithContent::IndexDuplication {
+                        index_uid: source_index_uid,
+                        new_index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    }
+                }
+                KindDump::IndexCompaction => KindWithContent::IndexCompaction {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                },
                 KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
                 KindDump::TaskCancelation { query, tasks } => {
                     KindWithContent::TaskCancelation { query, tasks }
@@ -1417,6 +2249,7 @@ impl<'a> Dump<'a> {
                 }
                 KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
             },
+            request_id: None,
         };
 
         self.index_scheduler.all_tasks.put(&mut self.wtxn, &BEU32::new(task.uid), &task)?;
@@ -1519,6 +2352,7 @@ pub struct IndexStats {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
     use std::io::{BufWriter, Seek, Write};
     use std::time::Instant;
 
@@ -1527,14 +2361,16 @@ mod tests {
     use file_store::File;
     use meili_snap::{json_string, snapshot};
     use meilisearch_auth::AuthFilter;
-    use meilisearch_types::document_formats::DocumentFormatError;
+    use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
     use meilisearch_types::error::ErrorCode;
     use meilisearch_types::index_uid_pattern::IndexUidPattern;
     use meilisearch_types::milli::obkv_to_json;
     use meilisearch_types::milli::update::IndexDocumentsMethod::{
         ReplaceDocuments, UpdateDocuments,
     };
-    use meilisearch_types::tasks::IndexSwap;
+    use meilisearch_types::milli::update::Setting;
+    use meilisearch_types::settings::Settings;
+    use meilisearch_types::tasks::{Details, IndexSwap};
     use meilisearch_types::VERSION_FILE_NAME;
     use tempfile::{NamedTempFile, TempDir};
     use time::Duration;
@@ -1590,7 +2426,15 @@ mod tests {
                 indexer_config,
                 autobatching_enabled: true,
                 max_number_of_tasks: 1_000_000,
+                task_retention_period: None,
+                task_retention_count: None,
+                max_indexing_payload_size: None,
+                allow_private_documents_urls: false,
+                max_concurrent_batches: 1,
                 instance_features: Default::default(),
+                search_cache_size: 0,
+                search_cache_ttl: std::time::Duration::from_secs(60),
+                search_thread_pool: None,
             };
             configuration(&mut options);
 
@@ -1657,6 +2501,7 @@ mod tests {
             content_file: Uuid::from_u128(content_file_uuid),
             documents_count,
             allow_index_creation: true,
+            duplicate_ids_policy: None,
         }
     }
 
@@ -1876,6 +2721,22 @@ mod tests {
         assert!(index_scheduler.is_task_processing().unwrap());
     }
 
+    #[test]
+    fn keep_the_request_id_of_the_tasks() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let task = index_scheduler
+            .register_with_request_id(index_creation_task("doggos", "id"), Some(S("my-request")))
+            .unwrap();
+        assert_eq!(task.request_id.as_deref(), Some("my-request"));
+
+        handle.advance_one_successful_batch();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, task.uid).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+        assert_eq!(task.request_id.as_deref(), Some("my-request"));
+    }
+
     /// We send a lot of tasks but notify the tasks scheduler only once as
     /// we send them very fast, we must make sure that they are all processed.
     #[test]
@@ -2071,35 +2932,163 @@ mod tests {
     }
 
     #[test]
-    fn document_addition() {
+    fn document_addition() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let content = r#"
+        {
+            "id": 1,
+            "doggo": "bob"
+        }"#;
+
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+                duplicate_ids_policy: None,
+            })
+            .unwrap();
+        snapshot!(snapshot_index_scheduler(&index_scheduler), name: "after_register");
+
+        handle.advance_till([Start, BatchCreated]);
+        snapshot!(snapshot_index_scheduler(&index_scheduler), name: "after_the_batch_creation");
+
+        handle.advance_till([InsideProcessBatch, ProcessBatchSucceeded, AfterProcessing]);
+        snapshot!(snapshot_index_scheduler(&index_scheduler), name: "once_everything_is_processed");
+    }
+
+    #[test]
+    fn document_addition_dry_run() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let payloads = [
+            r#"[{ "id": 1, "doggo": "bob" }]"#,
+            r#"[{ "id": 2, "doggo": "jean" }, { "doggo": "no id" }, { "id": "b@d" }]"#,
+        ];
+        for (i, content) in payloads.iter().enumerate() {
+            let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(i as u128).unwrap();
+            let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+            file.persist().unwrap();
+            let kind = if i == 0 {
+                KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: S("doggos"),
+                    primary_key: Some(S("id")),
+                    method: ReplaceDocuments,
+                    content_file: uuid,
+                    documents_count,
+                    allow_index_creation: true,
+                    duplicate_ids_policy: None,
+                }
+            } else {
+                KindWithContent::DocumentAdditionDryRun {
+                    index_uid: S("doggos"),
+                    primary_key: None,
+                    content_file: uuid,
+                    documents_count,
+                    duplicate_ids_policy: None,
+                }
+            };
+            index_scheduler.register(kind).unwrap();
+        }
+        handle.advance_n_successful_batches(2);
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 1).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+        let Some(Details::DocumentAdditionDryRun {
+            received_documents,
+            invalid_documents,
+            document_errors,
+        }) = task.details
+        else {
+            panic!("{:?}", task.details)
+        };
+        assert_eq!(received_documents, 3);
+        assert_eq!(invalid_documents, Some(2));
+        let errors: Vec<_> = document_errors
+            .iter()
+            .map(|error| {
+                let code = serde_json::to_value(&error.error).unwrap()["code"].clone();
+                (error.position, error.document_id.clone(), code)
+            })
+            .collect();
+        snapshot!(format!("{errors:?}"), @r###"[(1, None, String("missing_document_id")), (2, None, String("invalid_document_id"))]"###);
+
+        // the documents were only validated
+        let index = index_scheduler.index("doggos").unwrap();
+        let index_rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&index_rtxn).unwrap(), 1);
+        index_scheduler.assert_internally_consistent();
+    }
+
+    #[test]
+    fn processed_batches_are_stored() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
 
-        let content = r#"
-        {
-            "id": 1,
-            "doggo": "bob"
-        }"#;
+        for i in 0..2 {
+            let content =
+                format!(r#"[{{ "id": {i}, "doggo": "bob" }}, {{ "id": 1{i}, "doggo": "jean" }}]"#);
+            let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(i).unwrap();
+            let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+            file.persist().unwrap();
+            index_scheduler
+                .register(KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: S("doggos"),
+                    primary_key: Some(S("id")),
+                    method: ReplaceDocuments,
+                    content_file: uuid,
+                    documents_count,
+                    allow_index_creation: true,
+                    duplicate_ids_policy: None,
+                })
+                .unwrap();
+        }
+        index_scheduler.register(index_creation_task("catto", "mouse")).unwrap();
+        handle.advance_n_successful_batches(2);
 
-        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
-        let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
-        file.persist().unwrap();
-        index_scheduler
-            .register(KindWithContent::DocumentAdditionOrUpdate {
-                index_uid: S("doggos"),
-                primary_key: Some(S("id")),
-                method: ReplaceDocuments,
-                content_file: uuid,
-                documents_count,
-                allow_index_creation: true,
-            })
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(None, 20, &AuthFilter::default())
             .unwrap();
-        snapshot!(snapshot_index_scheduler(&index_scheduler), name: "after_register");
-
-        handle.advance_till([Start, BatchCreated]);
-        snapshot!(snapshot_index_scheduler(&index_scheduler), name: "after_the_batch_creation");
+        assert_eq!(batches.len(), 2);
+
+        // the most recent batch comes first
+        let creation = &batches[0];
+        assert_eq!(creation.uid, 1);
+        assert_eq!(creation.index_uid.as_deref(), Some("catto"));
+        assert_eq!(creation.task_uids, vec![2]);
+        assert_eq!(creation.stats.types.get("indexCreation"), Some(&1));
+
+        // both document additions were auto-batched together
+        let additions = &batches[1];
+        assert_eq!(additions.uid, 0);
+        assert_eq!(additions.index_uid.as_deref(), Some("doggos"));
+        assert_eq!(additions.task_uids, vec![0, 1]);
+        assert_eq!(additions.stats.total_nb_tasks, 2);
+        assert_eq!(additions.stats.status.get("succeeded"), Some(&2));
+        assert_eq!(additions.stats.indexed_documents, 4);
+        let steps: Vec<_> =
+            additions.stats.indexing_steps.iter().map(|step| step.step.as_str()).collect();
+        assert!(steps.contains(&"indexDocuments"), "{steps:?}");
+
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(Some(0), 20, &AuthFilter::default())
+            .unwrap();
+        assert_eq!(batches.len(), 1);
 
-        handle.advance_till([InsideProcessBatch, ProcessBatchSucceeded, AfterProcessing]);
-        snapshot!(snapshot_index_scheduler(&index_scheduler), name: "once_everything_is_processed");
+        let filters = AuthFilter::with_allowed_indexes(
+            vec![IndexUidPattern::new_unchecked("doggos")].into_iter().collect(),
+        );
+        let batches =
+            index_scheduler.get_batches_from_authorized_indexes(None, 20, &filters).unwrap();
+        assert_eq!(batches.len(), 1);
+        snapshot!(format!("{}", index_scheduler.get_batch_from_authorized_indexes(1, &filters).unwrap_err()), @"Batch `1` not found.");
     }
 
     #[test]
@@ -2128,6 +3117,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                duplicate_ids_policy: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_second_task");
@@ -2164,6 +3154,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                duplicate_ids_policy: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -2218,6 +3209,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                duplicate_ids_policy: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_second_task");
@@ -2394,6 +3386,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                duplicate_ids_policy: None,
             })
             .unwrap();
         index_scheduler
@@ -2552,6 +3545,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2599,6 +3593,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2648,6 +3643,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2698,6 +3694,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2749,6 +3746,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3247,6 +4245,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                duplicate_ids_policy: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -3285,6 +4284,7 @@ mod tests {
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                duplicate_ids_policy: None,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -3341,6 +4341,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3389,6 +4390,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3443,6 +4445,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3502,6 +4505,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3566,6 +4570,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3619,6 +4624,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3671,6 +4677,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3732,6 +4739,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3789,6 +4797,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3870,6 +4879,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -3953,6 +4963,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    duplicate_ids_policy: None,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -4075,6 +5086,409 @@ mod tests {
         handle.advance_one_failed_batch();
     }
 
+    #[test]
+    fn task_retention_policy() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.task_retention_period = Some(std::time::Duration::from_secs(24 * 60 * 60));
+                config.task_retention_count = Some(2);
+            });
+
+        for index_uid in ["catto", "doggo", "girafo"] {
+            index_scheduler.register(index_creation_task(index_uid, "id")).unwrap();
+        }
+        handle.advance_n_successful_batches(3);
+
+        // only the two most recent finished tasks are kept
+        let now = OffsetDateTime::now_utc();
+        index_scheduler.apply_task_retention_policy(now).unwrap();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        snapshot!(snapshot_bitmap(&index_scheduler.all_task_ids(&rtxn).unwrap()), @"[1,2,]");
+        drop(rtxn);
+
+        // nothing is deleted while a task is enqueued
+        index_scheduler.register(index_creation_task("catto", "id")).unwrap();
+        index_scheduler.apply_task_retention_policy(now + Duration::days(2)).unwrap();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        snapshot!(snapshot_bitmap(&index_scheduler.all_task_ids(&rtxn).unwrap()), @"[1,2,3,]");
+        drop(rtxn);
+
+        // the tasks finished for more than a day are deleted
+        handle.advance_one_failed_batch();
+        index_scheduler.apply_task_retention_policy(now + Duration::days(2)).unwrap();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        snapshot!(snapshot_bitmap(&index_scheduler.all_task_ids(&rtxn).unwrap()), @"[]");
+    }
+
+    #[test]
+    fn task_retention_period_beyond_the_earliest_date() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.task_retention_period = Some(std::time::Duration::from_secs(u64::MAX));
+            });
+
+        index_scheduler.register(index_creation_task("catto", "id")).unwrap();
+        handle.advance_one_successful_batch();
+
+        // no task is old enough to be deleted
+        index_scheduler.apply_task_retention_policy(OffsetDateTime::now_utc()).unwrap();
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        snapshot!(snapshot_bitmap(&index_scheduler.all_task_ids(&rtxn).unwrap()), @"[0,]");
+    }
+
+    #[test]
+    fn query_suggestions_wait_for_the_processing_batches() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("catto", "id")).unwrap();
+        handle.advance_one_successful_batch();
+        index_scheduler.query_suggestions().record("catto", "dragon");
+
+        // the index is written by a batch, the queries wait for the next call
+        index_scheduler.processing_tasks.write().unwrap().processing.insert(0);
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        index_scheduler.record_query_suggestions(&rtxn).unwrap();
+        assert!(!index_scheduler.query_suggestions.is_empty());
+        let index = index_scheduler.index("catto").unwrap();
+        let index_rtxn = index.read_txn().unwrap();
+        assert!(!index.suggestions_fst(&index_rtxn).unwrap().contains_key("dragon"));
+        drop(index_rtxn);
+
+        index_scheduler.processing_tasks.write().unwrap().processing.clear();
+        index_scheduler.record_query_suggestions(&rtxn).unwrap();
+        assert!(index_scheduler.query_suggestions.is_empty());
+        let index_rtxn = index.read_txn().unwrap();
+        assert!(index.suggestions_fst(&index_rtxn).unwrap().contains_key("dragon"));
+    }
+
+    #[test]
+    fn delete_orphan_update_files() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+        let content = r#"{ "id": 1, "doggo": "bob" }"#;
+        let mut uuids = Vec::new();
+        for i in 0..2 {
+            let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(i).unwrap();
+            read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+            file.persist().unwrap();
+            uuids.push(uuid);
+        }
+        let file_size = index_scheduler.update_files_size() / 2;
+
+        // only the first file is used by a task
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuids[0],
+                documents_count: 1,
+                allow_index_creation: true,
+                duplicate_ids_policy: None,
+            })
+            .unwrap();
+
+        index_scheduler.delete_orphan_update_files().unwrap();
+        let all_uuids: Vec<_> =
+            index_scheduler.file_store.all_uuids().unwrap().map(|uuid| uuid.unwrap()).collect();
+        assert_eq!(all_uuids, vec![uuids[0]]);
+        assert_eq!(index_scheduler.update_files_size(), file_size);
+    }
+
+    #[test]
+    fn create_concurrent_batches_on_distinct_indexes() {
+        let (index_scheduler, _handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.max_concurrent_batches = 2;
+            });
+
+        for index_uid in ["doggos", "catto", "doggos"] {
+            index_scheduler.register(index_creation_task(index_uid, "id")).unwrap();
+        }
+
+        let start = |index_uid: &str, task_id: TaskId| {
+            let progress = ProgressHandle::default();
+            index_scheduler.processing_tasks.write().unwrap().start_processing(
+                ProcessingBatch::new(
+                    Some(index_uid.to_string()),
+                    OffsetDateTime::now_utc(),
+                    RoaringBitmap::from_iter([task_id]),
+                    progress.clone(),
+                ),
+            );
+            progress
+        };
+        let next_batch = || {
+            let rtxn = index_scheduler.env.read_txn().unwrap();
+            index_scheduler.create_next_batch(&rtxn).unwrap().map(|batch| batch.ids())
+        };
+
+        // the tasks of the index of a processing batch are skipped
+        let doggos = start("doggos", 0);
+        assert_eq!(next_batch(), Some(vec![1]));
+
+        // no more batch is created once the limit is reached
+        let catto = start("catto", 1);
+        assert_eq!(next_batch(), None);
+
+        // the snapshot waits for the end of all the processing batches
+        index_scheduler.register(KindWithContent::SnapshotCreation).unwrap();
+        index_scheduler.processing_tasks.write().unwrap().stop_processing(&catto);
+        assert_eq!(next_batch(), None);
+        index_scheduler.processing_tasks.write().unwrap().stop_processing(&doggos);
+        assert_eq!(next_batch(), Some(vec![3]));
+    }
+
+    #[test]
+    fn cancel_only_the_batches_of_the_canceled_tasks() {
+        let (index_scheduler, _handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.max_concurrent_batches = 2;
+            });
+
+        for index_uid in ["doggos", "catto"] {
+            index_scheduler.register(index_creation_task(index_uid, "id")).unwrap();
+        }
+        for (index_uid, task_id) in [("doggos", 0), ("catto", 1)] {
+            index_scheduler.processing_tasks.write().unwrap().start_processing(
+                ProcessingBatch::new(
+                    Some(index_uid.to_string()),
+                    OffsetDateTime::now_utc(),
+                    RoaringBitmap::from_iter([task_id]),
+                    ProgressHandle::default(),
+                ),
+            );
+        }
+
+        index_scheduler
+            .register(KindWithContent::TaskCancelation {
+                query: "test_query".to_owned(),
+                tasks: RoaringBitmap::from_iter([0]),
+            })
+            .unwrap();
+
+        let processing = index_scheduler.processing_tasks.read().unwrap();
+        let stopped: Vec<_> = processing
+            .batches
+            .iter()
+            .map(|batch| (batch.index_uid.as_deref(), batch.must_stop_processing.get()))
+            .collect();
+        assert_eq!(stopped, vec![(Some("doggos"), true), (Some("catto"), false)]);
+    }
+
+    #[test]
+    fn download_credentials_are_not_persisted() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+        let kind = KindWithContent::DocumentAdditionFromUrl {
+            index_uid: S("doggos"),
+            primary_key: None,
+            method: ReplaceDocuments,
+            url: S("https://example.com/doggos.json"),
+            authorization: Some(S("Bearer doggo")),
+            has_authorization: true,
+            format: PayloadType::Json,
+            allow_index_creation: true,
+            duplicate_ids_policy: None,
+        };
+        let task = index_scheduler.register(kind).unwrap();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let persisted = index_scheduler.get_task(&rtxn, task.uid).unwrap().unwrap();
+        assert!(matches!(
+            persisted.kind,
+            KindWithContent::DocumentAdditionFromUrl { authorization: None, .. }
+        ));
+        let credentials = index_scheduler.download_credentials.read().unwrap();
+        assert_eq!(credentials.get(&task.uid).map(String::as_str), Some("Bearer doggo"));
+    }
+
+    #[test]
+    fn download_fails_without_its_lost_credentials() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let kind = KindWithContent::DocumentAdditionFromUrl {
+            index_uid: S("doggos"),
+            primary_key: None,
+            method: ReplaceDocuments,
+            url: S("https://example.com/doggos.json"),
+            authorization: Some(S("Bearer doggo")),
+            has_authorization: true,
+            format: PayloadType::Json,
+            allow_index_creation: true,
+            duplicate_ids_policy: None,
+        };
+        let task = index_scheduler.register(kind).unwrap();
+
+        // the credentials kept in memory are lost when the engine restarts
+        index_scheduler.download_credentials.write().unwrap().clear();
+        handle.advance_one_failed_batch();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, task.uid).unwrap().unwrap();
+        snapshot!(task.error.unwrap().message, @"The documents could not be downloaded from `https://example.com/doggos.json`: the authorization was lost when Meilisearch restarted, the documents must be added again.");
+    }
+
+    #[test]
+    fn refuse_tasks_while_shutting_down() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+        handle.advance_one_successful_batch();
+
+        assert!(index_scheduler.shutdown(std::time::Duration::from_secs(1)));
+        let err = index_scheduler.register(index_creation_task("catto", "id")).unwrap_err();
+        assert!(matches!(err, Error::ShuttingDown));
+    }
+
+    #[test]
+    fn drain_processing_batch_while_shutting_down() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+        handle.advance_till([Start, BatchCreated]);
+
+        std::thread::scope(|s| {
+            let shutdown = s.spawn(|| index_scheduler.shutdown(std::time::Duration::from_secs(5)));
+            while !index_scheduler.shutting_down.load(Relaxed) {
+                std::thread::yield_now();
+            }
+
+            // the shutdown waits for the end of the processing batch
+            std::thread::sleep(SHUTDOWN_CHECK_INTERVAL * 2);
+            assert!(!shutdown.is_finished());
+            while handle.advance() != ProcessBatchSucceeded {}
+            handle.advance_till([AfterProcessing]);
+            assert!(shutdown.join().unwrap());
+        });
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+    }
+
+    #[test]
+    fn abort_processing_batch_at_the_end_of_the_shutdown_grace_period() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let (file0, documents_count0) = sample_documents(&index_scheduler, 0, 0);
+        file0.persist().unwrap();
+        index_scheduler
+            .register(replace_document_import_task("catto", None, 0, documents_count0))
+            .unwrap();
+        handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+
+        std::thread::scope(|s| {
+            let shutdown = s.spawn(|| index_scheduler.shutdown(std::time::Duration::ZERO));
+            let is_aborted = || {
+                let processing_tasks = index_scheduler.processing_tasks.read().unwrap();
+                processing_tasks.running_batches().all(|batch| batch.must_stop_processing.get())
+            };
+            while !is_aborted() {
+                std::thread::yield_now();
+            }
+
+            // the batch is aborted instead of being finished
+            handle.advance_till([AbortedIndexation, Start]);
+            assert!(shutdown.join().unwrap());
+        });
+
+        // the aborted task is processed again at the next start of the engine
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        assert_eq!(task.status, Status::Enqueued);
+    }
+
+    #[test]
+    fn process_interrupted_batch_first() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+        for index_uid in ["doggos", "catto"] {
+            index_scheduler.register(index_creation_task(index_uid, "id")).unwrap();
+        }
+        index_scheduler.register(KindWithContent::SnapshotCreation).unwrap();
+
+        // the batch of the second task was processing when the engine crashed
+        let mut wtxn = index_scheduler.env.write_txn().unwrap();
+        let interrupted = RoaringBitmap::from_iter([1]);
+        index_scheduler.processing_batches.put(&mut wtxn, &BEU32::new(1), &interrupted).unwrap();
+        wtxn.commit().unwrap();
+
+        let next_batch = || {
+            let rtxn = index_scheduler.env.read_txn().unwrap();
+            index_scheduler.create_next_batch(&rtxn).unwrap().map(|batch| batch.ids())
+        };
+        assert_eq!(next_batch(), Some(vec![1]));
+
+        // once the batch is finished the snapshot is prioritized again
+        let mut wtxn = index_scheduler.env.write_txn().unwrap();
+        index_scheduler.processing_batches.delete(&mut wtxn, &BEU32::new(1)).unwrap();
+        wtxn.commit().unwrap();
+        assert_eq!(next_batch(), Some(vec![2]));
+    }
+
+    #[test]
+    fn do_not_index_an_applied_batch_twice() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+        handle.advance_one_successful_batch();
+
+        let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+        file.persist().unwrap();
+        index_scheduler
+            .register(replace_document_import_task("doggos", None, 0, documents_count))
+            .unwrap();
+
+        // the documents were indexed before the engine crashed, but the task was not updated
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let mut applied_task = index_scheduler.get_task(&rtxn, 1).unwrap().unwrap();
+        drop(rtxn);
+        applied_task.status = Status::Succeeded;
+        applied_task.details = Some(Details::DocumentAdditionOrUpdate {
+            received_documents: documents_count,
+            indexed_documents: Some(documents_count),
+            matched_percolate_queries: Default::default(),
+            duplicated_documents: None,
+        });
+        let index = index_scheduler.index("doggos").unwrap();
+        let mut wtxn = index.write_txn().unwrap();
+        index.put_last_applied_batch(&mut wtxn, &[applied_task.clone()]).unwrap();
+        wtxn.commit().unwrap();
+
+        handle.advance_one_successful_batch();
+
+        // the recorded task is returned without indexing the documents a second time
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 0);
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 1).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+        assert_eq!(task.details, applied_task.details);
+    }
+
+    #[test]
+    fn do_not_create_an_applied_index_twice() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+
+        // the index was created before the engine crashed, but the task was not updated
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let mut applied_task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        drop(rtxn);
+        applied_task.status = Status::Succeeded;
+        applied_task.details = Some(Details::IndexInfo { primary_key: Some(S("id")) });
+        let wtxn = index_scheduler.env.write_txn().unwrap();
+        let index = index_scheduler.index_mapper.create_index(wtxn, "doggos", None).unwrap();
+        let mut wtxn = index.write_txn().unwrap();
+        index.put_last_applied_batch(&mut wtxn, &[applied_task]).unwrap();
+        wtxn.commit().unwrap();
+
+        // the task succeeds instead of failing because the index already exists
+        handle.advance_one_successful_batch();
+    }
+
     #[test]
     fn test_auto_deletion_of_tasks() {
         let (index_scheduler, mut handle) =
@@ -4141,6 +5555,116 @@ mod tests {
         drop(rtxn);
     }
 
+    #[test]
+    fn apply_index_templates_to_new_indexes() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let template = |pattern: Option<&str>, filterable: &str| IndexTemplate {
+            uid_pattern: pattern.map(IndexUidPattern::new_unchecked),
+            settings: Settings {
+                filterable_attributes: Setting::Set(BTreeSet::from([S(filterable)])),
+                ..Default::default()
+            },
+        };
+        index_scheduler.put_index_template("default", &template(None, "id")).unwrap();
+        index_scheduler.put_index_template("logs", &template(Some("logs-*"), "level")).unwrap();
+        snapshot!(json_string!(index_scheduler.index_template("logs").unwrap()), @r###"
+        {
+          "uidPattern": "logs-*",
+          "settings": {
+            "filterableAttributes": [
+              "level"
+            ]
+          }
+        }
+        "###);
+
+        index_scheduler.register(index_creation_task("logs-2023", "id")).unwrap();
+        index_scheduler.register(index_creation_task("doggos", "id")).unwrap();
+        handle.advance_n_successful_batches(2);
+
+        let filterable_fields = |index_uid: &str| {
+            let index = index_scheduler.index(index_uid).unwrap();
+            let rtxn = index.read_txn().unwrap();
+            index.filterable_fields(&rtxn).unwrap()
+        };
+        snapshot!(format!("{:?}", filterable_fields("logs-2023")), @r###"{"level"}"###);
+        snapshot!(format!("{:?}", filterable_fields("doggos")), @r###"{"id"}"###);
+
+        // the indexes keep their settings when their template is deleted.
+        index_scheduler.delete_index_template("logs").unwrap();
+        snapshot!(format!("{}", index_scheduler.delete_index_template("logs").unwrap_err()), @"Index template `logs` not found.");
+        snapshot!(format!("{:?}", filterable_fields("logs-2023")), @r###"{"level"}"###);
+    }
+
+    #[test]
+    fn register_scheduled_tasks() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+        let cron: CronExpression = "0 0 1 1 *".parse().unwrap();
+        let schedule = index_scheduler
+            .put_schedule("snapshots", cron, KindWithContent::SnapshotCreation)
+            .unwrap();
+        let next_run_at = schedule.next_run_at.unwrap();
+
+        // nothing is registered before the date of the schedule
+        index_scheduler.register_scheduled_tasks(next_run_at - time::Duration::MINUTE).unwrap();
+        let rtxn = index_scheduler.read_txn().unwrap();
+        assert_eq!(index_scheduler.all_tasks.len(&rtxn).unwrap(), 0);
+        drop(rtxn);
+
+        index_scheduler.register_scheduled_tasks(next_run_at).unwrap();
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        assert_eq!(task.kind, KindWithContent::SnapshotCreation);
+        drop(rtxn);
+
+        // the schedule moved to the next year and is not registered twice
+        let schedule = index_scheduler.schedule("snapshots").unwrap();
+        assert_eq!(schedule.next_run_at.unwrap().year(), next_run_at.year() + 1);
+        index_scheduler.register_scheduled_tasks(next_run_at).unwrap();
+        let rtxn = index_scheduler.read_txn().unwrap();
+        assert_eq!(index_scheduler.all_tasks.len(&rtxn).unwrap(), 1);
+        drop(rtxn);
+
+        index_scheduler.delete_schedule("snapshots").unwrap();
+        snapshot!(format!("{}", index_scheduler.delete_schedule("snapshots").unwrap_err()), @"Schedule `snapshots` not found.");
+    }
+
+    #[test]
+    fn schedule_credentials_are_not_persisted() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+        let cron: CronExpression = "0 0 1 1 *".parse().unwrap();
+        let kind = KindWithContent::DocumentAdditionFromUrl {
+            index_uid: S("doggos"),
+            primary_key: None,
+            method: ReplaceDocuments,
+            url: S("https://example.com/doggos.json"),
+            authorization: Some(S("Bearer doggo")),
+            has_authorization: true,
+            format: PayloadType::Json,
+            allow_index_creation: true,
+            duplicate_ids_policy: None,
+        };
+        let schedule = index_scheduler.put_schedule("doggos", cron, kind).unwrap();
+
+        let persisted = index_scheduler.schedule("doggos").unwrap();
+        assert!(matches!(
+            persisted.kind,
+            KindWithContent::DocumentAdditionFromUrl { authorization: None, .. }
+        ));
+
+        // the registered tasks are given the credentials of their schedule
+        index_scheduler.register_scheduled_tasks(schedule.next_run_at.unwrap()).unwrap();
+        let credentials = index_scheduler.download_credentials.read().unwrap();
+        assert_eq!(credentials.get(&0).map(String::as_str), Some("Bearer doggo"));
+        drop(credentials);
+
+        index_scheduler.delete_schedule("doggos").unwrap();
+        assert!(index_scheduler.schedule_credentials.read().unwrap().is_empty());
+    }
+
     #[test]
     fn basic_get_stats() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -0,0 +1,157 @@
+//! The live progress of the indexing operations of the processing batch.
+
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::sync::Arc;
+
+use meilisearch_types::milli::update::UpdateIndexingStep::{self, *};
+use serde::Serialize;
+
+/// The name of the step copying the LMDB environment of an index, which follows the indexing steps.
+const COPY_INDEX_STEP: &str = "copyIndex";
+
+/// The progress of a processing task, shared by all the tasks of its batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    /// The name of the indexing step currently running.
+    pub step: &'static str,
+    /// The progress of the whole indexing operation, from 0 to 100.
+    pub percentage: u8,
+    pub processed_documents: u64,
+    /// `None` until the documents are counted, at the end of the first step.
+    pub total_documents: Option<u64>,
+}
+
+/// A handle to the progress of the indexing operations, cheap to clone and updated
+/// by the indexing pipeline without taking any lock.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct ProgressHandle(Arc<AtomicProgress>);
+
+#[derive(Default, Debug)]
+struct AtomicProgress {
+    /// The number of the current step plus one, zero when no step has been reached.
+    step: AtomicUsize,
+    /// What has been processed in the current step, documents or databases.
+    step_processed: AtomicU64,
+    /// Zero when the total of the current step is unknown.
+    step_total: AtomicU64,
+    processed_documents: AtomicU64,
+    /// Zero when the documents are not counted yet.
+    total_documents: AtomicU64,
+}
+
+impl ProgressHandle {
+    /// Stores the progress of the indexing step, returns `true` if it is a new step.
+    pub fn update(&self, step: UpdateIndexingStep) -> bool {
+        let progress = &self.0;
+        let (processed, total) = match step {
+            RemapDocumentAddition { documents_seen } => {
+                progress.processed_documents.store(documents_seen as u64, Relaxed);
+                progress.total_documents.store(0, Relaxed);
+                (documents_seen, 0)
+            }
+            ComputeIdsAndMergeDocuments { documents_seen, total_documents }
+            | IndexDocuments { documents_seen, total_documents } => {
+                progress.processed_documents.store(documents_seen as u64, Relaxed);
+                progress.total_documents.store(total_documents as u64, Relaxed);
+                (documents_seen, total_documents)
+            }
+            // the documents are all processed once their data is merged
+            MergeDataIntoFinalDatabase { databases_seen, total_databases } => {
+                (databases_seen, total_databases)
+            }
+        };
+        progress.step_processed.store(processed as u64, Relaxed);
+        progress.step_total.store(total as u64, Relaxed);
+
+        let step_number = step.step() + 1;
+        progress.step.swap(step_number, Relaxed) != step_number
+    }
+
+    /// Stores the progress of the copy of the LMDB environment of an index, in bytes.
+    pub fn update_copy(&self, copied_bytes: u64, total_bytes: u64) {
+        let progress = &self.0;
+        progress.step_processed.store(copied_bytes, Relaxed);
+        progress.step_total.store(total_bytes, Relaxed);
+        progress.step.store(UpdateIndexingStep::STEP_NAMES.len() + 1, Relaxed);
+    }
+
+    /// Returns `true` if both handles report the progress of the same indexing operations.
+    pub fn is_same(&self, other: &ProgressHandle) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Returns the current progress, `None` if no indexing step has been reached yet.
+    pub fn get(&self) -> Option<TaskProgress> {
+        let progress = &self.0;
+        let step = progress.step.load(Relaxed).checked_sub(1)?;
+        let step_processed = progress.step_processed.load(Relaxed);
+        let step_total = progress.step_total.load(Relaxed);
+        let total_documents = progress.total_documents.load(Relaxed);
+
+        let number_of_steps = UpdateIndexingStep::STEP_NAMES.len();
+        let step_ratio = match step_total {
+            0 => 0.0,
+            total => (step_processed as f64 / total as f64).min(1.0),
+        };
+        let (step, percentage) = match UpdateIndexingStep::STEP_NAMES.get(step) {
+            Some(name) => (*name, (step as f64 + step_ratio) / number_of_steps as f64 * 100.0),
+            None => (COPY_INDEX_STEP, step_ratio * 100.0),
+        };
+
+        Some(TaskProgress {
+            step,
+            percentage: percentage as u8,
+            processed_documents: progress.processed_documents.load(Relaxed),
+            total_documents: (total_documents != 0).then_some(total_documents),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_the_progress_of_the_indexing_steps() {
+        let handle = ProgressHandle::default();
+        assert_eq!(handle.get(), None);
+
+        assert!(handle.update(RemapDocumentAddition { documents_seen: 10 }));
+        assert!(!handle.update(RemapDocumentAddition { documents_seen: 20 }));
+        let progress = handle.get().unwrap();
+        assert_eq!(progress.step, "remapDocumentAddition");
+        assert_eq!(progress.percentage, 0);
+        assert_eq!((progress.processed_documents, progress.total_documents), (20, None));
+
+        assert!(handle.update(IndexDocuments { documents_seen: 10, total_documents: 20 }));
+        let progress = handle.get().unwrap();
+        assert_eq!(progress.step, "indexDocuments");
+        assert_eq!(progress.percentage, 62);
+        assert_eq!((progress.processed_documents, progress.total_documents), (10, Some(20)));
+
+        // the documents stay counted while their data is merged
+        assert!(handle.update(MergeDataIntoFinalDatabase { databases_seen: 3, total_databases: 4 }));
+        let progress = handle.get().unwrap();
+        assert_eq!(progress.percentage, 93);
+        assert_eq!((progress.processed_documents, progress.total_documents), (10, Some(20)));
+    }
+
+    #[test]
+    fn report_the_progress_of_an_index_copy() {
+        let handle = ProgressHandle::default();
+
+        handle.update_copy(0, 0);
+        let progress = handle.get().unwrap();
+        assert_eq!(progress.step, "copyIndex");
+        assert_eq!(progress.percentage, 0);
+
+        handle.update_copy(300, 1200);
+        assert_eq!(handle.get().unwrap().percentage, 25);
+
+        // the copy of a compacted index can't be bigger than the index
+        handle.update_copy(1500, 1200);
+        assert_eq!(handle.get().unwrap().percentage, 100);
+    }
+}
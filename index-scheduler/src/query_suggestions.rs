@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Mutex;
+
+/// The maximum number of distinct queries waiting to be recorded for each index,
+/// the new queries are dropped once it is reached.
+const MAX_PENDING_QUERIES_PER_INDEX: usize = 10_000;
+
+/// Buffers the queries of the searches until they are recorded in the suggestions of their index.
+///
+/// The searches can't write in their index, the queries are therefore kept in memory
+/// and recorded by the scheduler loop on the indexes that are not processing a batch.
+#[derive(Default)]
+pub struct QuerySuggestions {
+    pending: Mutex<HashMap<String, HashMap<String, u64>>>,
+}
+
+impl QuerySuggestions {
+    /// Buffers a query to be recorded in the suggestions of the index.
+    pub fn record(&self, index_uid: &str, query: &str) {
+        if query.trim().is_empty() {
+            return;
+        }
+
+        let mut pending = self.pending.lock().unwrap();
+        let queries = pending.entry(index_uid.to_string()).or_default();
+        match queries.get_mut(query) {
+            Some(popularity) => *popularity = popularity.saturating_add(1),
+            None if queries.len() >= MAX_PENDING_QUERIES_PER_INDEX => (),
+            None => {
+                queries.insert(query.to_string(), 1);
+            }
+        }
+    }
+
+    /// Returns `true` if no query is waiting to be recorded.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.lock().unwrap().is_empty()
+    }
+
+    /// Returns and forgets the queries buffered for the indexes matching the predicate,
+    /// with the number of times they were searched.
+    pub(crate) fn take(
+        &self,
+        mut predicate: impl FnMut(&str) -> bool,
+    ) -> HashMap<String, HashMap<String, u64>> {
+        let mut pending = self.pending.lock().unwrap();
+        let (taken, kept) =
+            mem::take(&mut *pending).into_iter().partition(|(index_uid, _)| predicate(index_uid));
+        *pending = kept;
+        taken
+    }
+
+    /// Forgets the queries made on a deleted index.
+    pub(crate) fn delete_index(&self, index_uid: &str) {
+        self.pending.lock().unwrap().remove(index_uid);
+    }
+}
@@ -0,0 +1,29 @@
+//! The ids of the HTTP requests that registered the tasks of the batch being processed,
+//! they correlate the log events of the processing with the ones of the requests.
+
+use std::cell::RefCell;
+
+thread_local! {
+    static BATCH_REQUEST_IDS: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+/// Returns the ids of the requests that registered the tasks of the batch processed by this thread.
+pub fn batch_request_ids() -> Vec<String> {
+    BATCH_REQUEST_IDS.with(|ids| ids.borrow().clone())
+}
+
+/// Runs the processing of a batch, the log events it emits are given the request ids of its tasks.
+pub(crate) fn scope_batch_request_ids<R>(request_ids: Vec<String>, f: impl FnOnce() -> R) -> R {
+    let _guard = BatchRequestIdsGuard(BATCH_REQUEST_IDS.with(|ids| ids.replace(request_ids)));
+    f()
+}
+
+/// Restores the previous request ids of the thread when dropped, even if the processing panicked.
+struct BatchRequestIdsGuard(Vec<String>);
+
+impl Drop for BatchRequestIdsGuard {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.0);
+        BATCH_REQUEST_IDS.with(|ids| *ids.borrow_mut() = previous);
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+
+use meilisearch_types::heed::types::{SerdeJson, Str};
+use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn};
+use meilisearch_types::schedules::Schedule;
+use time::OffsetDateTime;
+
+use crate::Result;
+
+const SCHEDULES: &str = "schedules";
+
+#[derive(Clone)]
+pub(crate) struct Schedules {
+    schedules: Database<Str, SerdeJson<Schedule>>,
+}
+
+impl Schedules {
+    pub fn new(env: &Env) -> Result<Self> {
+        let mut wtxn = env.write_txn()?;
+        let schedules = env.create_database(&mut wtxn, Some(SCHEDULES))?;
+        wtxn.commit()?;
+
+        Ok(Self { schedules })
+    }
+
+    pub fn all(&self, rtxn: &RoTxn) -> Result<BTreeMap<String, Schedule>> {
+        let mut schedules = BTreeMap::new();
+        for result in self.schedules.iter(rtxn)? {
+            let (name, schedule) = result?;
+            schedules.insert(name.to_string(), schedule);
+        }
+        Ok(schedules)
+    }
+
+    pub fn get(&self, rtxn: &RoTxn, name: &str) -> Result<Option<Schedule>> {
+        Ok(self.schedules.get(rtxn, name)?)
+    }
+
+    pub fn put(&self, wtxn: &mut RwTxn, name: &str, schedule: &Schedule) -> Result<()> {
+        Ok(self.schedules.put(wtxn, name, schedule)?)
+    }
+
+    pub fn delete(&self, wtxn: &mut RwTxn, name: &str) -> Result<bool> {
+        Ok(self.schedules.delete(wtxn, name)?)
+    }
+
+    /// Returns the schedules whose next date has come.
+    pub fn due(&self, rtxn: &RoTxn, now: OffsetDateTime) -> Result<Vec<(String, Schedule)>> {
+        let mut due = Vec::new();
+        for result in self.schedules.iter(rtxn)? {
+            let (name, schedule) = result?;
+            if schedule.is_due(now) {
+                due.push((name.to_string(), schedule));
+            }
+        }
+        Ok(due)
+    }
+}
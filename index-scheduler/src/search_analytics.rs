@@ -0,0 +1,222 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+use time::OffsetDateTime;
+
+/// The maximum number of searches kept for each index, the oldest ones are forgotten first.
+const MAX_SEARCHES_PER_INDEX: usize = 100_000;
+
+/// A search made on an index.
+#[derive(Debug, Clone)]
+struct RecordedSearch {
+    uid: u64,
+    query: String,
+    hits: u64,
+    processing_time_ms: u64,
+    date: OffsetDateTime,
+    clicked: bool,
+}
+
+/// The aggregated statistics of the searches made with the same query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryStats {
+    pub query: String,
+    /// The number of times the query was searched.
+    pub count: u64,
+    pub average_hits: f64,
+    pub average_processing_time_ms: f64,
+    /// The number of searches that led to a click on one of their hits.
+    pub clicks: u64,
+}
+
+#[derive(Default)]
+struct Searches {
+    next_uid: u64,
+    by_index: HashMap<String, VecDeque<RecordedSearch>>,
+}
+
+/// Records the searches made on each index to compute the most frequent queries.
+///
+/// The searches are only kept in memory and are lost when the instance restarts.
+#[derive(Default)]
+pub struct SearchAnalytics {
+    searches: RwLock<Searches>,
+}
+
+impl SearchAnalytics {
+    /// Records a search and returns its uid, to later mark it as clicked.
+    ///
+    /// The placeholder searches are not recorded.
+    pub fn record(
+        &self,
+        index_uid: &str,
+        query: &str,
+        hits: u64,
+        processing_time_ms: u64,
+    ) -> Option<u64> {
+        let query = normalize_query(query)?;
+        let mut searches = self.searches.write().unwrap();
+        let uid = searches.next_uid;
+        searches.next_uid += 1;
+
+        let index_searches = searches.by_index.entry(index_uid.to_string()).or_default();
+        if index_searches.len() >= MAX_SEARCHES_PER_INDEX {
+            index_searches.pop_front();
+        }
+        index_searches.push_back(RecordedSearch {
+            uid,
+            query,
+            hits,
+            processing_time_ms,
+            date: OffsetDateTime::now_utc(),
+            clicked: false,
+        });
+
+        Some(uid)
+    }
+
+    /// Marks a search as having led to a click, returns `false` if the search is unknown.
+    pub fn click(&self, index_uid: &str, search_uid: u64) -> bool {
+        let mut searches = self.searches.write().unwrap();
+        let Some(index_searches) = searches.by_index.get_mut(index_uid) else { return false };
+        // the searches are recorded by increasing uid.
+        match index_searches.binary_search_by_key(&search_uid, |search| search.uid) {
+            Ok(position) => {
+                index_searches[position].clicked = true;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the most frequent queries made between the given dates.
+    pub fn top_queries(
+        &self,
+        index_uid: &str,
+        after: Option<OffsetDateTime>,
+        before: Option<OffsetDateTime>,
+        limit: usize,
+    ) -> Vec<QueryStats> {
+        self.aggregate(index_uid, after, before, limit, |_| true)
+    }
+
+    /// Returns the most frequent queries that didn't return any hit between the given dates.
+    pub fn no_result_queries(
+        &self,
+        index_uid: &str,
+        after: Option<OffsetDateTime>,
+        before: Option<OffsetDateTime>,
+        limit: usize,
+    ) -> Vec<QueryStats> {
+        self.aggregate(index_uid, after, before, limit, |search| search.hits == 0)
+    }
+
+    /// Forgets the searches made on a deleted index.
+    pub fn delete_index(&self, index_uid: &str) {
+        self.searches.write().unwrap().by_index.remove(index_uid);
+    }
+
+    fn aggregate(
+        &self,
+        index_uid: &str,
+        after: Option<OffsetDateTime>,
+        before: Option<OffsetDateTime>,
+        limit: usize,
+        filter: impl Fn(&RecordedSearch) -> bool,
+    ) -> Vec<QueryStats> {
+        let searches = self.searches.read().unwrap();
+        let Some(index_searches) = searches.by_index.get(index_uid) else { return Vec::new() };
+
+        let mut totals: HashMap<&str, (u64, u64, u64, u64)> = HashMap::new();
+        for search in index_searches {
+            if after.map_or(false, |after| search.date <= after)
+                || before.map_or(false, |before| search.date >= before)
+                || !filter(search)
+            {
+                continue;
+            }
+            let (count, hits, processing_time_ms, clicks) =
+                totals.entry(search.query.as_str()).or_default();
+            *count += 1;
+            *hits += search.hits;
+            *processing_time_ms += search.processing_time_ms;
+            *clicks += search.clicked as u64;
+        }
+
+        let mut stats: Vec<_> = totals
+            .into_iter()
+            .map(|(query, (count, hits, processing_time_ms, clicks))| QueryStats {
+                query: query.to_string(),
+                count,
+                average_hits: hits as f64 / count as f64,
+                average_processing_time_ms: processing_time_ms as f64 / count as f64,
+                clicks,
+            })
+            .collect();
+        stats.sort_by(|left, right| {
+            right.count.cmp(&left.count).then_with(|| left.query.cmp(&right.query))
+        });
+        stats.truncate(limit);
+        stats
+    }
+}
+
+/// Lowercases the query and collapses its whitespaces, returns `None` if the query is empty.
+fn normalize_query(query: &str) -> Option<String> {
+    let query = query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if query.is_empty() {
+        None
+    } else {
+        Some(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_the_recorded_searches() {
+        let analytics = SearchAnalytics::default();
+        let first = analytics.record("movies", "Iron Man", 10, 2).unwrap();
+        analytics.record("movies", "iron   man", 20, 4).unwrap();
+        analytics.record("movies", "hulk", 0, 1).unwrap();
+        analytics.record("books", "hulk", 3, 1).unwrap();
+        assert_eq!(analytics.record("movies", "  ", 30, 1), None);
+
+        assert!(analytics.click("movies", first));
+        assert!(!analytics.click("books", first));
+
+        let top = analytics.top_queries("movies", None, None, 10);
+        assert_eq!(
+            top,
+            vec![
+                QueryStats {
+                    query: "iron man".to_string(),
+                    count: 2,
+                    average_hits: 15.0,
+                    average_processing_time_ms: 3.0,
+                    clicks: 1,
+                },
+                QueryStats {
+                    query: "hulk".to_string(),
+                    count: 1,
+                    average_hits: 0.0,
+                    average_processing_time_ms: 1.0,
+                    clicks: 0,
+                },
+            ]
+        );
+
+        let no_result = analytics.no_result_queries("movies", None, None, 10);
+        assert_eq!(no_result.len(), 1);
+        assert_eq!(no_result[0].query, "hulk");
+
+        let future = OffsetDateTime::now_utc() + time::Duration::hours(1);
+        assert!(analytics.top_queries("movies", Some(future), None, 10).is_empty());
+
+        analytics.delete_index("movies");
+        assert!(analytics.top_queries("movies", None, None, 10).is_empty());
+        assert_eq!(analytics.top_queries("books", None, None, 10).len(), 1);
+    }
+}
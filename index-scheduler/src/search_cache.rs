@@ -0,0 +1,161 @@
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Caches the responses of the searches made on each index, to answer the identical
+/// repeated searches without searching again.
+///
+/// The responses of an index are invalidated every time a task modifies the documents
+/// or the settings of the index. The cache is disabled when its size is zero.
+pub struct SearchCache {
+    /// The maximum number of responses kept, the oldest ones are evicted first.
+    size: usize,
+    /// The duration after which a response is considered outdated.
+    ttl: Duration,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    /// The revision of each index, incremented every time the index is invalidated.
+    revisions: HashMap<String, u64>,
+    responses: HashMap<String, HashMap<String, CachedResponse>>,
+    /// The index and key of each response by insertion order.
+    order: VecDeque<(String, String)>,
+}
+
+struct CachedResponse {
+    response: Arc<dyn Any + Send + Sync>,
+    inserted_at: Instant,
+}
+
+impl SearchCache {
+    pub fn new(size: usize, ttl: Duration) -> Self {
+        SearchCache { size, ttl, entries: Mutex::default() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.size > 0
+    }
+
+    /// Returns the current revision of the index.
+    ///
+    /// It must be read before searching, to not cache a response computed
+    /// on a version of the index that was modified in the meantime.
+    pub fn revision(&self, index_uid: &str) -> u64 {
+        let entries = self.entries.lock().unwrap();
+        entries.revisions.get(index_uid).copied().unwrap_or_default()
+    }
+
+    /// Returns the cached response of the search identified by the key, if it is not outdated.
+    pub fn get<T: Clone + 'static>(&self, index_uid: &str, key: &str) -> Option<T> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let entries = self.entries.lock().unwrap();
+        let cached = entries.responses.get(index_uid)?.get(key)?;
+        if cached.inserted_at.elapsed() >= self.ttl {
+            return None;
+        }
+        cached.response.downcast_ref::<T>().cloned()
+    }
+
+    /// Caches the response of a search made on the given revision of the index.
+    ///
+    /// The response is ignored if the index was invalidated since this revision.
+    pub fn insert<T: Send + Sync + 'static>(
+        &self,
+        index_uid: &str,
+        revision: u64,
+        key: String,
+        response: T,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.revisions.get(index_uid).copied().unwrap_or_default() != revision {
+            return;
+        }
+
+        let cached = CachedResponse { response: Arc::new(response), inserted_at: Instant::now() };
+        let responses = entries.responses.entry(index_uid.to_string()).or_default();
+        // an outdated response is replaced in place and keeps its position in the order.
+        if let Some(outdated) = responses.get_mut(&key) {
+            *outdated = cached;
+            return;
+        }
+        responses.insert(key.clone(), cached);
+        entries.order.push_back((index_uid.to_string(), key));
+
+        while entries.order.len() > self.size {
+            let Some((index_uid, key)) = entries.order.pop_front() else { break };
+            if let Some(responses) = entries.responses.get_mut(&index_uid) {
+                responses.remove(&key);
+                if responses.is_empty() {
+                    entries.responses.remove(&index_uid);
+                }
+            }
+        }
+    }
+
+    /// Forgets the responses of an index after it was modified.
+    pub(crate) fn invalidate(&self, index_uid: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        *entries.revisions.entry(index_uid.to_string()).or_default() += 1;
+        if entries.responses.remove(index_uid).is_some() {
+            entries.order.retain(|(uid, _)| uid != index_uid);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_and_invalidate_the_responses() {
+        let cache = SearchCache::new(2, Duration::from_secs(60));
+
+        let revision = cache.revision("movies");
+        cache.insert("movies", revision, "hulk".to_string(), 1_u64);
+        assert_eq!(cache.get::<u64>("movies", "hulk"), Some(1));
+        assert_eq!(cache.get::<u64>("books", "hulk"), None);
+        assert_eq!(cache.get::<String>("movies", "hulk"), None);
+
+        // the oldest response is evicted
+        cache.insert("movies", revision, "thor".to_string(), 2_u64);
+        cache.insert("books", cache.revision("books"), "hulk".to_string(), 3_u64);
+        assert_eq!(cache.get::<u64>("movies", "hulk"), None);
+        assert_eq!(cache.get::<u64>("movies", "thor"), Some(2));
+        assert_eq!(cache.get::<u64>("books", "hulk"), Some(3));
+
+        cache.invalidate("movies");
+        assert_eq!(cache.get::<u64>("movies", "thor"), None);
+        assert_eq!(cache.get::<u64>("books", "hulk"), Some(3));
+
+        // a response computed before the invalidation is not cached
+        cache.insert("movies", revision, "thor".to_string(), 2_u64);
+        assert_eq!(cache.get::<u64>("movies", "thor"), None);
+        cache.insert("movies", cache.revision("movies"), "thor".to_string(), 2_u64);
+        assert_eq!(cache.get::<u64>("movies", "thor"), Some(2));
+    }
+
+    #[test]
+    fn outdated_and_disabled_cache() {
+        let cache = SearchCache::new(10, Duration::ZERO);
+        cache.insert("movies", 0, "hulk".to_string(), 1_u64);
+        assert_eq!(cache.get::<u64>("movies", "hulk"), None);
+
+        let cache = SearchCache::new(0, Duration::from_secs(60));
+        cache.insert("movies", 0, "hulk".to_string(), 1_u64);
+        assert_eq!(cache.get::<u64>("movies", "hulk"), None);
+    }
+}
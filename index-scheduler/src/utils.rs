@@ -3,6 +3,7 @@
 use std::collections::{BTreeSet, HashSet};
 use std::ops::Bound;
 
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::heed::types::{DecodeIgnore, OwnedType};
 use meilisearch_types::heed::{Database, RoTxn, RwTxn};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
@@ -25,6 +26,11 @@ impl IndexScheduler {
         Ok(self.last_task_id(rtxn)?.unwrap_or_default())
     }
 
+    pub(crate) fn next_batch_id(&self, rtxn: &RoTxn) -> Result<BatchId> {
+        let last = self.batches.remap_data_type::<DecodeIgnore>().last(rtxn)?;
+        Ok(last.map_or(0, |(k, _)| k.get() + 1))
+    }
+
     pub(crate) fn get_task(&self, rtxn: &RoTxn, task_id: TaskId) -> Result<Option<Task>> {
         Ok(self.all_tasks.get(rtxn, &BEU32::new(task_id))?)
     }
@@ -118,6 +124,25 @@ impl IndexScheduler {
         Ok(self.status.get(rtxn, &status)?.unwrap_or_default())
     }
 
+    /// Returns the enqueued tasks of a batch that was interrupted by a crash, if any.
+    ///
+    /// The persisted batches that are neither being processed nor finished
+    /// are the ones left by a previous run of the engine.
+    pub(crate) fn get_interrupted_batch(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+    ) -> Result<Option<RoaringBitmap>> {
+        let processing = self.processing_tasks.read().unwrap().processing.clone();
+        for result in self.processing_batches.iter(rtxn)? {
+            let (_, tasks) = result?;
+            if tasks.is_disjoint(&processing) && !tasks.is_disjoint(enqueued) {
+                return Ok(Some(tasks & enqueued));
+            }
+        }
+        Ok(None)
+    }
+
     pub(crate) fn put_status(
         &self,
         wtxn: &mut RwTxn,
@@ -238,6 +263,8 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     let mut index_uids = vec![];
     match &mut task.kind {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentAdditionFromUrl { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentAdditionDryRun { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
@@ -245,6 +272,11 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
         K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexCompaction { index_uid } => index_uids.push(index_uid),
+        K::IndexDuplication { index_uid, new_index_uid } => {
+            index_uids.push(index_uid);
+            index_uids.push(new_index_uid);
+        }
         K::IndexSwap { swaps } => {
             for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
                 if lhs == swap.0 || lhs == swap.1 {
@@ -351,6 +383,7 @@ impl IndexScheduler {
                 details,
                 status,
                 kind,
+                request_id: _,
             } = task;
             assert_eq!(uid, task.uid);
             if let Some(task_index_uid) = &task_index_uid {
@@ -402,7 +435,12 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        matched_percolate_queries: _,
+                        duplicated_documents: _,
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(indexed_documents) => {
@@ -421,6 +459,26 @@ impl IndexScheduler {
                             }
                         }
                     }
+                    Details::DocumentAdditionDryRun {
+                        received_documents,
+                        invalid_documents,
+                        document_errors,
+                    } => {
+                        assert!(matches!(kind, KindWithContent::DocumentAdditionDryRun { .. }));
+                        match invalid_documents {
+                            Some(invalid_documents) => {
+                                assert!(matches!(
+                                    status,
+                                    Status::Succeeded | Status::Failed | Status::Canceled
+                                ));
+                                assert!(invalid_documents <= received_documents);
+                                assert!(document_errors.len() as u64 <= invalid_documents);
+                            }
+                            None => {
+                                assert!(matches!(status, Status::Enqueued | Status::Processing))
+                            }
+                        }
+                    }
                     Details::SettingsUpdate { settings: _ } => {
                         assert_eq!(kind.as_kind(), Kind::SettingsUpdate);
                     }
@@ -434,6 +492,14 @@ impl IndexScheduler {
                                 .contains(uid);
                             assert_eq!(&pk1, pk2);
                         }
+                        KindWithContent::IndexDuplication { new_index_uid, .. } => {
+                            assert!(self
+                                .index_tasks
+                                .get(&rtxn, new_index_uid.as_str())
+                                .unwrap()
+                                .unwrap()
+                                .contains(uid));
+                        }
                         _ => panic!(),
                     },
                     Details::DocumentDeletion {
@@ -532,6 +598,13 @@ impl IndexScheduler {
                     Details::Dump { dump_uid: _ } => {
                         assert_eq!(kind.as_kind(), Kind::DumpCreation);
                     }
+                    Details::IndexCompaction { pre_compaction_size, post_compaction_size } => {
+                        assert!(matches!(kind, KindWithContent::IndexCompaction { .. }));
+                        if status == Status::Succeeded {
+                            assert!(pre_compaction_size.is_some());
+                            assert!(post_compaction_size.is_some());
+                        }
+                    }
                 }
             }
 
@@ -214,6 +214,16 @@ impl AuthFilter {
                 .unwrap_or(true)
     }
 
+    /// Returns `true` if every index matched by the pattern is authorized.
+    pub fn is_index_pattern_authorized(&self, pattern: &IndexUidPattern) -> bool {
+        self.key_authorized_indexes.is_index_pattern_authorized(pattern)
+            && self
+                .search_rules
+                .as_ref()
+                .map(|search_rules| search_rules.is_index_pattern_authorized(pattern))
+                .unwrap_or(true)
+    }
+
     pub fn get_index_search_rules(&self, index: &str) -> Option<IndexSearchRules> {
         if !self.is_index_authorized(index) {
             return None;
@@ -253,6 +263,13 @@ impl SearchRules {
         }
     }
 
+    fn is_index_pattern_authorized(&self, pattern: &IndexUidPattern) -> bool {
+        match self {
+            Self::Set(set) => set.iter().any(|authorized| authorized.covers(pattern)),
+            Self::Map(map) => map.keys().any(|authorized| authorized.covers(pattern)),
+        }
+    }
+
     fn get_index_search_rules(&self, index: &str) -> Option<IndexSearchRules> {
         match self {
             Self::Set(_) => {
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::tasks::{Details, Task, TaskId};
+
+pub type BatchId = u32;
+
+/// The tasks processed together by the scheduler, kept once they are processed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Batch {
+    pub uid: BatchId,
+    /// The index the tasks were processed on, `None` for the batches unrelated to one index.
+    pub index_uid: Option<String>,
+    pub task_uids: Vec<TaskId>,
+    pub stats: BatchStats,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStats {
+    pub total_nb_tasks: u32,
+    /// The number of tasks of the batch by status.
+    pub status: BTreeMap<String, u32>,
+    /// The number of tasks of the batch by type.
+    pub types: BTreeMap<String, u32>,
+    pub indexed_documents: u64,
+    pub deleted_documents: u64,
+    /// The time spent in each indexing step, in the order they were first reached.
+    pub indexing_steps: Vec<IndexingStepDuration>,
+}
+
+impl BatchStats {
+    pub fn add_task(&mut self, task: &Task) {
+        self.total_nb_tasks += 1;
+        *self.status.entry(task.status.to_string()).or_default() += 1;
+        *self.types.entry(task.kind.as_kind().to_string()).or_default() += 1;
+
+        match task.details {
+            Some(Details::DocumentAdditionOrUpdate { indexed_documents, .. }) => {
+                self.indexed_documents += indexed_documents.unwrap_or_default();
+            }
+            Some(
+                Details::DocumentDeletion { deleted_documents, .. }
+                | Details::DocumentDeletionByFilter { deleted_documents, .. }
+                | Details::ClearAll { deleted_documents },
+            ) => {
+                self.deleted_documents += deleted_documents.unwrap_or_default();
+            }
+            _ => (),
+        }
+    }
+
+    /// Adds the time spent in an indexing step, the same step can be run by many operations.
+    pub fn add_indexing_step(&mut self, step: &str, duration: Duration) {
+        match self.indexing_steps.iter_mut().find(|s| s.step == step) {
+            Some(indexing_step) => indexing_step.duration += duration,
+            None => {
+                self.indexing_steps.push(IndexingStepDuration { step: step.to_string(), duration })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingStepDuration {
+    pub step: String,
+    pub duration: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::{KindWithContent, Status};
+
+    #[test]
+    fn aggregate_batch_stats() {
+        let task = |uid, status, details| Task {
+            uid,
+            enqueued_at: OffsetDateTime::UNIX_EPOCH,
+            started_at: None,
+            finished_at: None,
+            error: None,
+            canceled_by: None,
+            details: Some(details),
+            status,
+            kind: KindWithContent::DocumentClear { index_uid: "doggos".to_string() },
+            request_id: None,
+        };
+
+        let mut stats = BatchStats::default();
+        stats.add_task(&task(
+            0,
+            Status::Succeeded,
+            Details::ClearAll { deleted_documents: Some(3) },
+        ));
+        stats.add_task(&task(1, Status::Failed, Details::ClearAll { deleted_documents: Some(2) }));
+        stats.add_indexing_step("indexDocuments", Duration::from_secs(2));
+        stats.add_indexing_step("mergeDataIntoFinalDatabase", Duration::from_secs(1));
+        stats.add_indexing_step("indexDocuments", Duration::from_secs(3));
+
+        assert_eq!(stats.total_nb_tasks, 2);
+        assert_eq!(stats.status.get("succeeded"), Some(&1));
+        assert_eq!(stats.status.get("failed"), Some(&1));
+        assert_eq!(stats.types.get("documentDeletion"), Some(&2));
+        assert_eq!(stats.deleted_documents, 5);
+        assert_eq!(stats.indexing_steps.len(), 2);
+        assert_eq!(stats.indexing_steps[0].duration, Duration::from_secs(5));
+    }
+}
@@ -8,10 +8,12 @@ use deserr::{take_cf_content, DeserializeError, IntoValue, MergeWithError, Value
 
 use crate::error::deserr_codes::*;
 use crate::error::{
-    Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
+    Code, DeserrAttributeBoostError, DeserrMinimumShouldMatchError, DeserrParseBoolError,
+    DeserrParseIntError, DeserrRankingScoreThresholdError, ErrorCode, InvalidTaskDateError,
     ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
+use crate::locales::LocaleFormatError;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
 
 pub mod query_params;
@@ -151,10 +153,17 @@ make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
+make_missing_field_convenience_builder!(MissingDocumentUrl, missing_document_url);
 make_missing_field_convenience_builder!(
     MissingFacetSearchFacetName,
     missing_facet_search_facet_name
 );
+make_missing_field_convenience_builder!(
+    MissingSearchAnalyticsSearchUid,
+    missing_search_analytics_search_uid
+);
+make_missing_field_convenience_builder!(MissingScheduleCron, missing_schedule_cron);
+make_missing_field_convenience_builder!(MissingScheduleOperation, missing_schedule_operation);
 
 // Integrate a sub-error into a [`DeserrError`] by taking its error message but using
 // the default error code (C) from `Self`
@@ -182,9 +191,13 @@ macro_rules! merge_with_error_impl_take_error_message {
 // All these errors can be merged into a `DeserrError`
 merge_with_error_impl_take_error_message!(DeserrParseIntError);
 merge_with_error_impl_take_error_message!(DeserrParseBoolError);
+merge_with_error_impl_take_error_message!(DeserrRankingScoreThresholdError);
+merge_with_error_impl_take_error_message!(DeserrAttributeBoostError);
+merge_with_error_impl_take_error_message!(DeserrMinimumShouldMatchError);
 merge_with_error_impl_take_error_message!(uuid::Error);
 merge_with_error_impl_take_error_message!(InvalidTaskDateError);
 merge_with_error_impl_take_error_message!(ParseOffsetDateTimeError);
 merge_with_error_impl_take_error_message!(ParseTaskKindError);
 merge_with_error_impl_take_error_message!(ParseTaskStatusError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
+merge_with_error_impl_take_error_message!(LocaleFormatError);
@@ -6,19 +6,24 @@ use std::marker::PhantomData;
 use memmap2::MmapOptions;
 use milli::documents::{DocumentsBatchBuilder, Error};
 use milli::Object;
+use parquet::errors::ParquetError;
+use parquet::file::reader::{FileReader, SerializedFileReader};
 use serde::de::{SeqAccess, Visitor};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::error::Category;
+use serde_json::Value;
 
 use crate::error::{Code, ErrorCode};
 
 type Result<T> = std::result::Result<T, DocumentFormatError>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum PayloadType {
     Ndjson,
     Json,
     Csv { delimiter: u8 },
+    Parquet,
 }
 
 impl fmt::Display for PayloadType {
@@ -27,6 +32,7 @@ impl fmt::Display for PayloadType {
             PayloadType::Ndjson => f.write_str("ndjson"),
             PayloadType::Json => f.write_str("json"),
             PayloadType::Csv { .. } => f.write_str("csv"),
+            PayloadType::Parquet => f.write_str("parquet"),
         }
     }
 }
@@ -35,6 +41,7 @@ impl fmt::Display for PayloadType {
 pub enum DocumentFormatError {
     Io(io::Error),
     MalformedPayload(Error, PayloadType),
+    MalformedParquet(ParquetError),
 }
 
 impl Display for DocumentFormatError {
@@ -73,6 +80,9 @@ impl Display for DocumentFormatError {
                 }
                 _ => write!(f, "The `{}` payload provided is malformed: `{}`.", b, me),
             },
+            Self::MalformedParquet(e) => {
+                write!(f, "The `{}` payload provided is malformed: `{}`.", PayloadType::Parquet, e)
+            }
         }
     }
 }
@@ -88,6 +98,12 @@ impl From<(PayloadType, Error)> for DocumentFormatError {
     }
 }
 
+impl From<ParquetError> for DocumentFormatError {
+    fn from(error: ParquetError) -> Self {
+        Self::MalformedParquet(error)
+    }
+}
+
 impl From<io::Error> for DocumentFormatError {
     fn from(error: io::Error) -> Self {
         Self::Io(error)
@@ -99,6 +115,7 @@ impl ErrorCode for DocumentFormatError {
         match self {
             DocumentFormatError::Io(e) => e.error_code(),
             DocumentFormatError::MalformedPayload(_, _) => Code::MalformedPayload,
+            DocumentFormatError::MalformedParquet(_) => Code::MalformedPayload,
         }
     }
 }
@@ -154,14 +171,29 @@ pub fn read_json(file: &File, writer: impl Write + Seek) -> Result<u64> {
 pub fn read_ndjson(file: &File, writer: impl Write + Seek) -> Result<u64> {
     let mut builder = DocumentsBatchBuilder::new(writer);
     let mmap = unsafe { MmapOptions::new().map(file)? };
+    builder.append_ndjson(&mmap).map_err(|e| (PayloadType::Ndjson, e))?;
+
+    let count = builder.documents_count();
+    let _ = builder.into_inner().map_err(Into::into).map_err(DocumentFormatError::Io)?;
 
-    for result in serde_json::Deserializer::from_slice(&mmap).into_iter() {
-        let object = result.map_err(Error::Json).map_err(|e| (PayloadType::Ndjson, e))?;
-        builder.append_json_object(&object).map_err(Into::into).map_err(DocumentFormatError::Io)?;
+    Ok(count as u64)
+}
+
+/// Reads Parquet from temporary file and write an obkv batch to writer.
+///
+/// Every row is a document, the list and struct columns become arrays and objects
+/// that are flattened like any other nested field.
+pub fn read_parquet(file: &File, writer: impl Write + Seek) -> Result<u64> {
+    let mut builder = DocumentsBatchBuilder::new(writer);
+    let reader = SerializedFileReader::new(file.try_clone()?)?;
+    for row in reader.get_row_iter(None)? {
+        if let Value::Object(document) = row?.to_json_value() {
+            builder.append_json_object(&document)?;
+        }
     }
 
     let count = builder.documents_count();
-    let _ = builder.into_inner().map_err(Into::into).map_err(DocumentFormatError::Io)?;
+    let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
 
     Ok(count as u64)
 }
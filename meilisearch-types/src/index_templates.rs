@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::index_uid_pattern::IndexUidPattern;
+use crate::settings::{Settings, Unchecked};
+
+/// The settings given to the indexes created with an uid matching the pattern of the template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexTemplate {
+    /// The uids of the indexes the template applies to, every index when `None`.
+    pub uid_pattern: Option<IndexUidPattern>,
+    pub settings: Settings<Unchecked>,
+}
+
+impl IndexTemplate {
+    pub fn matches(&self, index_uid: &str) -> bool {
+        self.uid_pattern.as_ref().map_or(true, |pattern| pattern.matches_str(index_uid))
+    }
+}
@@ -48,6 +48,15 @@ impl IndexUidPattern {
             None => self.0 == uid,
         }
     }
+
+    /// Returns wether every index uid matched by the other pattern is matched by this one.
+    pub fn covers(&self, other: &IndexUidPattern) -> bool {
+        match (self.0.strip_suffix('*'), other.0.strip_suffix('*')) {
+            (Some(prefix), Some(other_prefix)) => other_prefix.starts_with(prefix),
+            (_, None) => self.matches_str(&other.0),
+            (None, Some(_)) => false,
+        }
+    }
 }
 
 impl Deref for IndexUidPattern {
@@ -335,7 +335,7 @@ impl Action {
 pub mod actions {
     use super::Action::*;
 
-    pub(crate) const ALL: u8 = All.repr();
+    pub const ALL: u8 = All.repr();
     pub const SEARCH: u8 = Search.repr();
     pub const DOCUMENTS_ALL: u8 = DocumentsAll.repr();
     pub const DOCUMENTS_ADD: u8 = DocumentsAdd.repr();
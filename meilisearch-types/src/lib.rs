@@ -1,12 +1,16 @@
+pub mod batches;
 pub mod compression;
 pub mod deserr;
 pub mod document_formats;
 pub mod error;
 pub mod facet_values_sort;
 pub mod features;
+pub mod index_templates;
 pub mod index_uid;
 pub mod index_uid_pattern;
 pub mod keys;
+pub mod locales;
+pub mod schedules;
 pub mod settings;
 pub mod star_or;
 pub mod tasks;
@@ -0,0 +1,140 @@
+use std::fmt;
+use std::str::FromStr;
+
+use deserr::Deserr;
+use milli::localized_attributes_rules::LocalizedAttributesRule;
+use milli::tokenizer::Language;
+use serde::{Deserialize, Serialize};
+
+macro_rules! make_locale {
+    ($($variant:ident => $name:literal),+ $(,)?) => {
+        /// A locale that can be forced on the tokenizer, named after its ISO 639-3 code.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Deserr)]
+        #[deserr(try_from(&String) = FromStr::from_str -> LocaleFormatError)]
+        pub enum Locale {
+            $(#[serde(rename = $name)] $variant,)+
+        }
+
+        impl Locale {
+            /// Returns the locale matching the given tokenizer language, if it is supported.
+            pub fn from_language(language: Language) -> Option<Locale> {
+                match language {
+                    $(Language::$variant => Some(Locale::$variant),)+
+                    _ => None,
+                }
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Locale::$variant => $name,)+
+                }
+            }
+
+            fn all() -> &'static [Locale] {
+                &[$(Locale::$variant),+]
+            }
+        }
+
+        impl From<Locale> for Language {
+            fn from(locale: Locale) -> Self {
+                match locale {
+                    $(Locale::$variant => Language::$variant,)+
+                }
+            }
+        }
+
+        impl FromStr for Locale {
+            type Err = LocaleFormatError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok(Locale::$variant),)+
+                    _ => Err(LocaleFormatError(s.to_owned())),
+                }
+            }
+        }
+    };
+}
+
+make_locale! {
+    Ara => "ara",
+    Bul => "bul",
+    Cat => "cat",
+    Ces => "ces",
+    Cmn => "cmn",
+    Dan => "dan",
+    Deu => "deu",
+    Ell => "ell",
+    Eng => "eng",
+    Fin => "fin",
+    Fra => "fra",
+    Heb => "heb",
+    Hin => "hin",
+    Hun => "hun",
+    Ind => "ind",
+    Ita => "ita",
+    Jpn => "jpn",
+    Kor => "kor",
+    Mar => "mar",
+    Nld => "nld",
+    Nob => "nob",
+    Pes => "pes",
+    Pol => "pol",
+    Por => "por",
+    Ron => "ron",
+    Rus => "rus",
+    Spa => "spa",
+    Swe => "swe",
+    Tha => "tha",
+    Tur => "tur",
+    Ukr => "ukr",
+    Vie => "vie",
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug)]
+pub struct LocaleFormatError(pub String);
+
+impl fmt::Display for LocaleFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unsupported locale `{}`, expected one of {}",
+            self.0,
+            Locale::all().iter().map(|l| format!("`{l}`")).collect::<Vec<_>>().join(", ")
+        )
+    }
+}
+
+impl std::error::Error for LocaleFormatError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct LocalizedAttributesRuleView {
+    pub attribute_patterns: Vec<String>,
+    pub locales: Vec<Locale>,
+}
+
+impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
+    fn from(view: LocalizedAttributesRuleView) -> Self {
+        LocalizedAttributesRule::new(
+            view.attribute_patterns,
+            view.locales.into_iter().map(Language::from).collect(),
+        )
+    }
+}
+
+impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
+    fn from(rule: LocalizedAttributesRule) -> Self {
+        LocalizedAttributesRuleView {
+            attribute_patterns: rule.attribute_patterns,
+            locales: rule.locales.into_iter().filter_map(Locale::from_language).collect(),
+        }
+    }
+}
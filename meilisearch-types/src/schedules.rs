@@ -0,0 +1,232 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+use crate::tasks::KindWithContent;
+
+/// How far in the future the next date matching a cron expression is searched.
+const MAX_SCHEDULE_DAYS: i64 = 5 * 366;
+
+/// A task registered periodically, at the dates matching its cron expression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schedule {
+    pub cron: CronExpression,
+    /// The task registered at each date of the schedule.
+    pub kind: KindWithContent,
+    /// The next date at which the task is registered, `None` if the expression never matches again.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub next_run_at: Option<OffsetDateTime>,
+}
+
+impl Schedule {
+    pub fn new(cron: CronExpression, kind: KindWithContent, now: OffsetDateTime) -> Schedule {
+        let next_run_at = cron.next_after(now);
+        Schedule { cron, kind, next_run_at }
+    }
+
+    pub fn is_due(&self, now: OffsetDateTime) -> bool {
+        self.next_run_at.map_or(false, |next_run_at| next_run_at <= now)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "`{0}` is not a valid cron expression. It must be made of five fields: \
+minute, hour, day of month, month and day of week, like `0 3 * * 1-5`."
+)]
+pub struct InvalidCronExpression(String);
+
+/// A cron expression made of the minute, hour, day of month, month and day of week fields,
+/// evaluated in UTC.
+///
+/// Each field accepts `*`, values, `a-b` ranges, `/n` steps and comma separated lists of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CronExpression {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    /// The days of the week, from Sunday (0) to Saturday (6).
+    weekdays: u64,
+    /// When both the days of month and of week are restricted,
+    /// a date matching any of them matches the expression.
+    restricted_days: bool,
+    restricted_weekdays: bool,
+}
+
+impl CronExpression {
+    /// Returns the first date, truncated to the minute, strictly after the given one that
+    /// matches the expression.
+    pub fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        let after = after.to_offset(time::UtcOffset::UTC);
+        let limit = after + Duration::days(MAX_SCHEDULE_DAYS);
+        let mut date = after.replace_second(0).ok()?.replace_nanosecond(0).ok()? + Duration::MINUTE;
+
+        while date <= limit {
+            if !contains(self.months, u8::from(date.month())) || !self.matches_day(date) {
+                date = date.date().next_day()?.midnight().assume_utc();
+            } else if !contains(self.hours, date.hour()) {
+                date = date.replace_minute(0).ok()? + Duration::HOUR;
+            } else if !contains(self.minutes, date.minute()) {
+                date += Duration::MINUTE;
+            } else {
+                return Some(date);
+            }
+        }
+
+        None
+    }
+
+    fn matches_day(&self, date: OffsetDateTime) -> bool {
+        let day = contains(self.days, date.day());
+        let weekday = contains(self.weekdays, date.weekday().number_days_from_sunday());
+        match (self.restricted_days, self.restricted_weekdays) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        }
+    }
+}
+
+fn contains(bits: u64, value: u8) -> bool {
+    bits & (1 << value) != 0
+}
+
+/// Parses a field of a cron expression into a bitset of the values it matches.
+fn parse_field(field: &str, min: u8, max: u8) -> Option<u64> {
+    let mut bits = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<usize>().ok().filter(|s| *s > 0)?)),
+            None => (part, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+            // a single value followed by a step goes up to the maximum
+            None if step.is_some() => (range.parse().ok()?, max),
+            None => (range.parse().ok()?, range.parse().ok()?),
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        for value in (start..=end).step_by(step.unwrap_or(1)) {
+            bits |= 1 << value;
+        }
+    }
+    Some(bits)
+}
+
+impl FromStr for CronExpression {
+    type Err = InvalidCronExpression;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || InvalidCronExpression(s.to_string());
+        let fields: Vec<_> = s.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(invalid());
+        };
+
+        let mut weekdays_bits = parse_field(weekdays, 0, 7).ok_or_else(invalid)?;
+        // both 0 and 7 are Sunday
+        if contains(weekdays_bits, 7) {
+            weekdays_bits = (weekdays_bits | 1) & !(1 << 7);
+        }
+
+        Ok(CronExpression {
+            expression: fields.join(" "),
+            minutes: parse_field(minutes, 0, 59).ok_or_else(invalid)?,
+            hours: parse_field(hours, 0, 23).ok_or_else(invalid)?,
+            days: parse_field(days, 1, 31).ok_or_else(invalid)?,
+            months: parse_field(months, 1, 12).ok_or_else(invalid)?,
+            weekdays: weekdays_bits,
+            restricted_days: !days.starts_with('*'),
+            restricted_weekdays: !weekdays.starts_with('*'),
+        })
+    }
+}
+
+impl TryFrom<String> for CronExpression {
+    type Error = InvalidCronExpression;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CronExpression> for String {
+    fn from(cron: CronExpression) -> Self {
+        cron.expression
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn parse_cron_expressions() {
+        for valid in ["* * * * *", "0 3 * * *", "*/15 8-18 * * 1-5", "0 0 1,15 * 7", "5/10 * * 2 *"]
+        {
+            let cron: CronExpression = valid.parse().unwrap();
+            assert_eq!(cron.to_string(), valid);
+        }
+
+        for invalid in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+            "* * * * * *",
+        ] {
+            assert!(invalid.parse::<CronExpression>().is_err(), "{invalid} should be invalid");
+        }
+    }
+
+    #[test]
+    fn next_date_of_cron_expressions() {
+        // a Wednesday
+        let now = datetime!(2023-05-10 10:30:45 UTC);
+
+        let cron: CronExpression = "* * * * *".parse().unwrap();
+        assert_eq!(cron.next_after(now), Some(datetime!(2023-05-10 10:31 UTC)));
+
+        let cron: CronExpression = "0 3 * * *".parse().unwrap();
+        assert_eq!(cron.next_after(now), Some(datetime!(2023-05-11 03:00 UTC)));
+
+        let cron: CronExpression = "*/20 10 * * *".parse().unwrap();
+        assert_eq!(cron.next_after(now), Some(datetime!(2023-05-10 10:40 UTC)));
+
+        let cron: CronExpression = "0 9 * * 1".parse().unwrap();
+        assert_eq!(cron.next_after(now), Some(datetime!(2023-05-15 09:00 UTC)));
+
+        // any of the day of month or day of week matches
+        let cron: CronExpression = "0 0 1 * 5".parse().unwrap();
+        assert_eq!(cron.next_after(now), Some(datetime!(2023-05-12 00:00 UTC)));
+
+        let cron: CronExpression = "0 0 29 2 *".parse().unwrap();
+        assert_eq!(cron.next_after(now), Some(datetime!(2024-02-29 00:00 UTC)));
+
+        // the 30th of February never happens
+        let cron: CronExpression = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(cron.next_after(now), None);
+    }
+}
@@ -6,8 +6,15 @@ use std::num::NonZeroUsize;
 use std::ops::ControlFlow;
 use std::str::FromStr;
 
-use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
+use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValueKind, ValuePointerRef};
 use fst::IntoStreamer;
+use milli::geo_rtree::{GeoLoadStrategy, GeoNodeSize};
+use milli::localized_attributes_rules::LocalizedAttributesRule;
+use milli::prefix_search::PrefixSearch;
+use milli::proximity::ProximityPrecision;
+use milli::reranker::RerankerConfig;
+use milli::stemming::StemmingLanguage;
+use milli::stop_words::StopWordsPreset;
 use milli::update::Setting;
 use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
 use serde::{Deserialize, Serialize, Serializer};
@@ -15,19 +22,27 @@ use serde::{Deserialize, Serialize, Serializer};
 use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
 use crate::facet_values_sort::FacetValuesSort;
+use crate::locales::LocalizedAttributesRuleView;
 
 /// The maximum number of results that the engine
 /// will be able to return in one search call.
 pub const DEFAULT_PAGINATION_MAX_TOTAL_HITS: usize = 1000;
 
-fn serialize_with_wildcard<S>(
-    field: &Setting<Vec<String>>,
+/// The number of best hits sent to the reranker when it is not specified.
+pub const DEFAULT_RERANKER_TOP_K: usize = 50;
+
+/// The time budget of the reranker when it is not specified.
+pub const DEFAULT_RERANKER_TIMEOUT_MS: u64 = 1000;
+
+fn serialize_with_wildcard<S, T>(
+    field: &Setting<Vec<T>>,
     s: S,
 ) -> std::result::Result<S::Ok, S::Error>
 where
     S: Serializer,
+    T: Serialize + From<String>,
 {
-    let wildcard = vec!["*".to_string()];
+    let wildcard = vec![T::from("*".to_string())];
     match field {
         Setting::Set(value) => Some(value),
         Setting::Reset => Some(&wildcard),
@@ -94,6 +109,9 @@ pub struct TypoSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub disable_on_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub disable_on_numbers: Setting<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -117,6 +135,83 @@ pub struct PaginationSettings {
     pub max_total_hits: Setting<usize>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct NormalizationSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub lowercase: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub remove_diacritics: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub compatibility: Setting<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct GeoIndexSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub node_size: Setting<GeoNodeSizeView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub load_strategy: Setting<GeoLoadStrategyView>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct VectorIndexSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub ef_construction: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub ef_search: Setting<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct RerankerSettings {
+    pub url: String,
+    #[serde(default)]
+    #[deserr(default)]
+    pub fields: Vec<String>,
+    #[serde(default = "default_reranker_top_k")]
+    #[deserr(default = DEFAULT_RERANKER_TOP_K)]
+    pub top_k: usize,
+    #[serde(default = "default_reranker_timeout_ms")]
+    #[deserr(default = DEFAULT_RERANKER_TIMEOUT_MS)]
+    pub timeout_ms: u64,
+}
+
+fn default_reranker_top_k() -> usize {
+    DEFAULT_RERANKER_TOP_K
+}
+
+fn default_reranker_timeout_ms() -> u64 {
+    DEFAULT_RERANKER_TIMEOUT_MS
+}
+
+impl From<RerankerSettings> for RerankerConfig {
+    fn from(settings: RerankerSettings) -> Self {
+        let RerankerSettings { url, fields, top_k, timeout_ms } = settings;
+        RerankerConfig { url, fields, top_k, timeout_ms }
+    }
+}
+
+impl From<RerankerConfig> for RerankerSettings {
+    fn from(config: RerankerConfig) -> Self {
+        let RerankerConfig { url, fields, top_k, timeout_ms } = config;
+        RerankerSettings { url, fields, top_k, timeout_ms }
+    }
+}
+
 impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRankingRules> {
     fn merge(
         _self_: Option<Self>,
@@ -156,7 +251,7 @@ pub struct Settings<T> {
         skip_serializing_if = "Setting::is_not_set"
     )]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchableAttributes>)]
-    pub searchable_attributes: Setting<Vec<String>>,
+    pub searchable_attributes: Setting<Vec<SearchableAttributeView>>,
 
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsFilterableAttributes>)]
@@ -169,7 +264,10 @@ pub struct Settings<T> {
     pub ranking_rules: Setting<Vec<RankingRuleView>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsStopWords>)]
-    pub stop_words: Setting<BTreeSet<String>>,
+    pub stop_words: Setting<StopWordsView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsIndexStopWords>)]
+    pub index_stop_words: Setting<bool>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsNonSeparatorTokens>)]
     pub non_separator_tokens: Setting<BTreeSet<String>>,
@@ -194,6 +292,60 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPagination>)]
     pub pagination: Setting<PaginationSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchCutoffMs>)]
+    pub search_cutoff_ms: Setting<u64>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsProximityPrecision>)]
+    pub proximity_precision: Setting<ProximityPrecisionView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
+    pub prefix_search: Setting<PrefixSearchSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSplitWords>)]
+    pub split_words: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsConcatenateWords>)]
+    pub concatenate_words: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRuleView>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsNormalizeNumbers>)]
+    pub normalize_numbers: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsNormalization>)]
+    pub normalization: Setting<NormalizationSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPercolateQueries>)]
+    pub percolate_queries: Setting<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsStemming>)]
+    pub stemming: Setting<BTreeSet<StemmingLanguageView>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsGeoFields>)]
+    pub geo_fields: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsGeoIndex>)]
+    pub geo_index: Setting<GeoIndexSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsBinaryQuantizedVectors>)]
+    pub binary_quantized_vectors: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsVectorIndex>)]
+    pub vector_index: Setting<VectorIndexSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsReranker>)]
+    pub reranker: Setting<RerankerSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsVersionAttribute>)]
+    pub version_attribute: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDocumentTimestamps>)]
+    pub document_timestamps: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsFacetSearch>)]
+    pub facet_search: Setting<bool>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -209,6 +361,7 @@ impl Settings<Checked> {
             sortable_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
+            index_stop_words: Setting::Reset,
             synonyms: Setting::Reset,
             non_separator_tokens: Setting::Reset,
             separator_tokens: Setting::Reset,
@@ -217,6 +370,24 @@ impl Settings<Checked> {
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
+            search_cutoff_ms: Setting::Reset,
+            proximity_precision: Setting::Reset,
+            prefix_search: Setting::Reset,
+            split_words: Setting::Reset,
+            concatenate_words: Setting::Reset,
+            localized_attributes: Setting::Reset,
+            normalize_numbers: Setting::Reset,
+            normalization: Setting::Reset,
+            percolate_queries: Setting::Reset,
+            stemming: Setting::Reset,
+            geo_fields: Setting::Reset,
+            geo_index: Setting::Reset,
+            binary_quantized_vectors: Setting::Reset,
+            vector_index: Setting::Reset,
+            reranker: Setting::Reset,
+            version_attribute: Setting::Reset,
+            document_timestamps: Setting::Reset,
+            facet_search: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -229,6 +400,7 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            index_stop_words,
             non_separator_tokens,
             separator_tokens,
             dictionary,
@@ -237,6 +409,24 @@ impl Settings<Checked> {
             typo_tolerance,
             faceting,
             pagination,
+            search_cutoff_ms,
+            proximity_precision,
+            prefix_search,
+            split_words,
+            concatenate_words,
+            localized_attributes,
+            normalize_numbers,
+            normalization,
+            percolate_queries,
+            stemming,
+            geo_fields,
+            geo_index,
+            binary_quantized_vectors,
+            vector_index,
+            reranker,
+            version_attribute,
+            document_timestamps,
+            facet_search,
             ..
         } = self;
 
@@ -247,6 +437,7 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            index_stop_words,
             non_separator_tokens,
             separator_tokens,
             dictionary,
@@ -255,12 +446,39 @@ impl Settings<Checked> {
             typo_tolerance,
             faceting,
             pagination,
+            search_cutoff_ms,
+            proximity_precision,
+            prefix_search,
+            split_words,
+            concatenate_words,
+            localized_attributes,
+            normalize_numbers,
+            normalization,
+            percolate_queries,
+            stemming,
+            geo_fields,
+            geo_index,
+            binary_quantized_vectors,
+            vector_index,
+            reranker,
+            version_attribute,
+            document_timestamps,
+            facet_search,
             _kind: PhantomData,
         }
     }
 }
 
 impl Settings<Unchecked> {
+    /// Returns the errors of the settings that can be detected before they are applied to an index.
+    pub fn validate(self) -> Result<Self, milli::Error> {
+        if let Setting::Set(ranking_rules) = &self.ranking_rules {
+            let criteria: Vec<_> = ranking_rules.iter().cloned().map(Criterion::from).collect();
+            milli::validate_criteria(&criteria).map_err(milli::UserError::CriterionError)?;
+        }
+        Ok(self)
+    }
+
     pub fn check(self) -> Settings<Checked> {
         let displayed_attributes = match self.displayed_attributes {
             Setting::Set(fields) => {
@@ -275,7 +493,7 @@ impl Settings<Unchecked> {
 
         let searchable_attributes = match self.searchable_attributes {
             Setting::Set(fields) => {
-                if fields.iter().any(|f| f == "*") {
+                if fields.iter().any(|f| f.attribute() == "*") {
                     Setting::Reset
                 } else {
                     Setting::Set(fields)
@@ -291,6 +509,7 @@ impl Settings<Unchecked> {
             sortable_attributes: self.sortable_attributes,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
+            index_stop_words: self.index_stop_words,
             synonyms: self.synonyms,
             non_separator_tokens: self.non_separator_tokens,
             separator_tokens: self.separator_tokens,
@@ -299,6 +518,72 @@ impl Settings<Unchecked> {
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
+            search_cutoff_ms: self.search_cutoff_ms,
+            proximity_precision: self.proximity_precision,
+            prefix_search: self.prefix_search,
+            split_words: self.split_words,
+            concatenate_words: self.concatenate_words,
+            localized_attributes: self.localized_attributes,
+            normalize_numbers: self.normalize_numbers,
+            normalization: self.normalization,
+            percolate_queries: self.percolate_queries,
+            stemming: self.stemming,
+            geo_fields: self.geo_fields,
+            geo_index: self.geo_index,
+            binary_quantized_vectors: self.binary_quantized_vectors,
+            vector_index: self.vector_index,
+            reranker: self.reranker,
+            version_attribute: self.version_attribute,
+            document_timestamps: self.document_timestamps,
+            facet_search: self.facet_search,
+            _kind: PhantomData,
+        }
+    }
+
+    /// Resets the settings missing from these settings, applying them then replaces
+    /// all the settings of an index.
+    pub fn reset_missing(self) -> Settings<Unchecked> {
+        fn reset_if_not_set<T>(setting: Setting<T>) -> Setting<T> {
+            match setting {
+                Setting::NotSet => Setting::Reset,
+                otherwise => otherwise,
+            }
+        }
+
+        Settings {
+            displayed_attributes: reset_if_not_set(self.displayed_attributes),
+            searchable_attributes: reset_if_not_set(self.searchable_attributes),
+            filterable_attributes: reset_if_not_set(self.filterable_attributes),
+            sortable_attributes: reset_if_not_set(self.sortable_attributes),
+            ranking_rules: reset_if_not_set(self.ranking_rules),
+            stop_words: reset_if_not_set(self.stop_words),
+            index_stop_words: reset_if_not_set(self.index_stop_words),
+            synonyms: reset_if_not_set(self.synonyms),
+            non_separator_tokens: reset_if_not_set(self.non_separator_tokens),
+            separator_tokens: reset_if_not_set(self.separator_tokens),
+            dictionary: reset_if_not_set(self.dictionary),
+            distinct_attribute: reset_if_not_set(self.distinct_attribute),
+            typo_tolerance: reset_if_not_set(self.typo_tolerance),
+            faceting: reset_if_not_set(self.faceting),
+            pagination: reset_if_not_set(self.pagination),
+            search_cutoff_ms: reset_if_not_set(self.search_cutoff_ms),
+            proximity_precision: reset_if_not_set(self.proximity_precision),
+            prefix_search: reset_if_not_set(self.prefix_search),
+            split_words: reset_if_not_set(self.split_words),
+            concatenate_words: reset_if_not_set(self.concatenate_words),
+            localized_attributes: reset_if_not_set(self.localized_attributes),
+            normalize_numbers: reset_if_not_set(self.normalize_numbers),
+            normalization: reset_if_not_set(self.normalization),
+            percolate_queries: reset_if_not_set(self.percolate_queries),
+            stemming: reset_if_not_set(self.stemming),
+            geo_fields: reset_if_not_set(self.geo_fields),
+            geo_index: reset_if_not_set(self.geo_index),
+            binary_quantized_vectors: reset_if_not_set(self.binary_quantized_vectors),
+            vector_index: reset_if_not_set(self.vector_index),
+            reranker: reset_if_not_set(self.reranker),
+            version_attribute: reset_if_not_set(self.version_attribute),
+            document_timestamps: reset_if_not_set(self.document_timestamps),
+            facet_search: reset_if_not_set(self.facet_search),
             _kind: PhantomData,
         }
     }
@@ -317,8 +602,23 @@ pub fn apply_settings_to_builder(
     builder: &mut milli::update::Settings,
 ) {
     match settings.searchable_attributes {
-        Setting::Set(ref names) => builder.set_searchable_fields(names.clone()),
-        Setting::Reset => builder.reset_searchable_fields(),
+        Setting::Set(ref attributes) => {
+            builder.set_searchable_fields(
+                attributes.iter().map(|attribute| attribute.attribute().to_string()).collect(),
+            );
+            builder.set_searchable_fields_weights(
+                attributes
+                    .iter()
+                    .filter_map(|attribute| {
+                        Some((attribute.attribute().to_string(), attribute.weight()?))
+                    })
+                    .collect(),
+            );
+        }
+        Setting::Reset => {
+            builder.reset_searchable_fields();
+            builder.reset_searchable_fields_weights();
+        }
         Setting::NotSet => (),
     }
 
@@ -351,8 +651,23 @@ pub fn apply_settings_to_builder(
     }
 
     match settings.stop_words {
-        Setting::Set(ref stop_words) => builder.set_stop_words(stop_words.clone()),
-        Setting::Reset => builder.reset_stop_words(),
+        Setting::Set(ref stop_words) => {
+            builder.set_stop_words(stop_words.words().clone());
+            match stop_words.preset() {
+                Some(preset) => builder.set_stop_words_preset(preset.into()),
+                None => builder.reset_stop_words_preset(),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_stop_words();
+            builder.reset_stop_words_preset();
+        }
+        Setting::NotSet => (),
+    }
+
+    match settings.index_stop_words {
+        Setting::Set(index_stop_words) => builder.set_index_stop_words(index_stop_words),
+        Setting::Reset => builder.reset_index_stop_words(),
         Setting::NotSet => (),
     }
 
@@ -433,6 +748,12 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_exact_attributes(),
                 Setting::NotSet => (),
             }
+
+            match value.disable_on_numbers {
+                Setting::Set(val) => builder.set_disable_typos_on_numbers(val),
+                Setting::Reset => builder.reset_disable_typos_on_numbers(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             // all typo settings need to be reset here.
@@ -441,6 +762,7 @@ pub fn apply_settings_to_builder(
             builder.reset_min_word_len_two_typos();
             builder.reset_exact_words();
             builder.reset_exact_attributes();
+            builder.reset_disable_typos_on_numbers();
         }
         Setting::NotSet => (),
     }
@@ -476,6 +798,166 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_pagination_max_total_hits(),
         Setting::NotSet => (),
     }
+
+    match settings.search_cutoff_ms {
+        Setting::Set(cutoff) => builder.set_search_cutoff(cutoff),
+        Setting::Reset => builder.reset_search_cutoff(),
+        Setting::NotSet => (),
+    }
+
+    match settings.proximity_precision {
+        Setting::Set(precision) => builder.set_proximity_precision(precision.into()),
+        Setting::Reset => builder.reset_proximity_precision(),
+        Setting::NotSet => (),
+    }
+
+    match settings.prefix_search {
+        Setting::Set(prefix_search) => builder.set_prefix_search(prefix_search.into()),
+        Setting::Reset => builder.reset_prefix_search(),
+        Setting::NotSet => (),
+    }
+
+    match settings.split_words {
+        Setting::Set(split_words) => builder.set_split_words(split_words),
+        Setting::Reset => builder.reset_split_words(),
+        Setting::NotSet => (),
+    }
+
+    match settings.concatenate_words {
+        Setting::Set(concatenate_words) => builder.set_concatenate_words(concatenate_words),
+        Setting::Reset => builder.reset_concatenate_words(),
+        Setting::NotSet => (),
+    }
+
+    match settings.localized_attributes {
+        Setting::Set(ref rules) => builder.set_localized_attributes_rules(
+            rules.iter().cloned().map(LocalizedAttributesRule::from).collect(),
+        ),
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
+    match settings.normalize_numbers {
+        Setting::Set(normalize_numbers) => builder.set_normalize_numbers(normalize_numbers),
+        Setting::Reset => builder.reset_normalize_numbers(),
+        Setting::NotSet => (),
+    }
+
+    match &settings.normalization {
+        Setting::Set(NormalizationSettings { lowercase, remove_diacritics, compatibility }) => {
+            match lowercase {
+                Setting::Set(val) => builder.set_normalization_lowercase(*val),
+                Setting::Reset => builder.reset_normalization_lowercase(),
+                Setting::NotSet => (),
+            }
+            match remove_diacritics {
+                Setting::Set(val) => builder.set_normalization_remove_diacritics(*val),
+                Setting::Reset => builder.reset_normalization_remove_diacritics(),
+                Setting::NotSet => (),
+            }
+            match compatibility {
+                Setting::Set(val) => builder.set_normalization_compatibility(*val),
+                Setting::Reset => builder.reset_normalization_compatibility(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_normalization_lowercase();
+            builder.reset_normalization_remove_diacritics();
+            builder.reset_normalization_compatibility();
+        }
+        Setting::NotSet => (),
+    }
+
+    match settings.percolate_queries {
+        Setting::Set(ref queries) => builder.set_percolate_queries(queries.clone()),
+        Setting::Reset => builder.reset_percolate_queries(),
+        Setting::NotSet => (),
+    }
+
+    match settings.stemming {
+        Setting::Set(ref languages) => builder.set_stemming_languages(
+            languages.iter().copied().map(StemmingLanguage::from).collect(),
+        ),
+        Setting::Reset => builder.reset_stemming_languages(),
+        Setting::NotSet => (),
+    }
+
+    match settings.geo_fields {
+        Setting::Set(ref fields) => builder.set_geo_fields(fields.clone()),
+        Setting::Reset => builder.reset_geo_fields(),
+        Setting::NotSet => (),
+    }
+
+    match &settings.geo_index {
+        Setting::Set(GeoIndexSettings { node_size, load_strategy }) => {
+            match node_size {
+                Setting::Set(val) => builder.set_geo_node_size((*val).into()),
+                Setting::Reset => builder.reset_geo_node_size(),
+                Setting::NotSet => (),
+            }
+            match load_strategy {
+                Setting::Set(val) => builder.set_geo_load_strategy((*val).into()),
+                Setting::Reset => builder.reset_geo_load_strategy(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_geo_node_size();
+            builder.reset_geo_load_strategy();
+        }
+        Setting::NotSet => (),
+    }
+
+    match settings.binary_quantized_vectors {
+        Setting::Set(binary_quantized) => builder.set_binary_quantized_vectors(binary_quantized),
+        Setting::Reset => builder.reset_binary_quantized_vectors(),
+        Setting::NotSet => (),
+    }
+
+    match &settings.vector_index {
+        Setting::Set(VectorIndexSettings { ef_construction, ef_search }) => {
+            match ef_construction {
+                Setting::Set(val) => builder.set_vector_ef_construction(*val),
+                Setting::Reset => builder.reset_vector_ef_construction(),
+                Setting::NotSet => (),
+            }
+            match ef_search {
+                Setting::Set(val) => builder.set_vector_ef_search(*val),
+                Setting::Reset => builder.reset_vector_ef_search(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_vector_ef_construction();
+            builder.reset_vector_ef_search();
+        }
+        Setting::NotSet => (),
+    }
+
+    match &settings.reranker {
+        Setting::Set(reranker) => builder.set_reranker(reranker.clone().into()),
+        Setting::Reset => builder.reset_reranker(),
+        Setting::NotSet => (),
+    }
+
+    match settings.version_attribute {
+        Setting::Set(ref attr) => builder.set_version_field(attr.clone()),
+        Setting::Reset => builder.reset_version_field(),
+        Setting::NotSet => (),
+    }
+
+    match settings.document_timestamps {
+        Setting::Set(flag) => builder.set_document_timestamps(flag),
+        Setting::Reset => builder.reset_document_timestamps(),
+        Setting::NotSet => (),
+    }
+
+    match settings.facet_search {
+        Setting::Set(flag) => builder.set_facet_search(flag),
+        Setting::Reset => builder.reset_facet_search(),
+        Setting::NotSet => (),
+    }
 }
 
 pub fn settings(
@@ -485,9 +967,19 @@ pub fn settings(
     let displayed_attributes =
         index.displayed_fields(rtxn)?.map(|fields| fields.into_iter().map(String::from).collect());
 
-    let searchable_attributes = index
-        .user_defined_searchable_fields(rtxn)?
-        .map(|fields| fields.into_iter().map(String::from).collect());
+    let searchable_weights = index.searchable_fields_weights(rtxn)?;
+    let searchable_attributes = index.user_defined_searchable_fields(rtxn)?.map(|fields| {
+        fields
+            .into_iter()
+            .map(|field| match searchable_weights.get(field) {
+                Some(&weight) => SearchableAttributeView::Weighted(WeightedSearchableAttribute {
+                    attribute: field.to_string(),
+                    weight,
+                }),
+                None => SearchableAttributeView::Name(field.to_string()),
+            })
+            .collect()
+    });
 
     let filterable_attributes = index.filterable_fields(rtxn)?.into_iter().collect();
 
@@ -495,13 +987,36 @@ pub fn settings(
 
     let criteria = index.criteria(rtxn)?;
 
-    let stop_words = index
-        .stop_words(rtxn)?
-        .map(|stop_words| -> Result<BTreeSet<_>, milli::Error> {
-            Ok(stop_words.stream().into_strs()?.into_iter().collect())
-        })
-        .transpose()?
-        .unwrap_or_default();
+    let preset = index.stop_words_preset(rtxn)?;
+    let stop_words = match index.user_defined_stop_words(rtxn)? {
+        Some(stop_words) => stop_words,
+        // The user-defined words were not kept by the older versions, the stored stop words
+        // contain the words of the preset, we only return the additional ones.
+        None => {
+            let preset_words = match preset {
+                Some(preset) => preset.words(index.normalization_params(rtxn)?),
+                None => BTreeSet::new(),
+            };
+            index
+                .stop_words(rtxn)?
+                .map(|stop_words| -> Result<BTreeSet<_>, milli::Error> {
+                    Ok(stop_words.stream().into_strs()?.into_iter().collect())
+                })
+                .transpose()?
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|w| !preset_words.contains(w))
+                .collect()
+        }
+    };
+
+    let stop_words = match preset {
+        Some(preset) => StopWordsView::Preset(StopWordsPresetSetting {
+            preset: preset.into(),
+            words: stop_words,
+        }),
+        None => StopWordsView::List(stop_words),
+    };
 
     let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
     let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
@@ -528,6 +1043,7 @@ pub fn settings(
         min_word_size_for_typos: Setting::Set(min_typo_word_len),
         disable_on_words: Setting::Set(disabled_words),
         disable_on_attributes: Setting::Set(disabled_attributes),
+        disable_on_numbers: Setting::Set(index.disable_typos_on_numbers(rtxn)?),
     };
 
     let faceting = FacetingSettings {
@@ -543,6 +1059,25 @@ pub fn settings(
         ),
     };
 
+    let normalization_params = index.normalization_params(rtxn)?;
+    let normalization = NormalizationSettings {
+        lowercase: Setting::Set(normalization_params.lowercase),
+        remove_diacritics: Setting::Set(normalization_params.remove_diacritics),
+        compatibility: Setting::Set(normalization_params.compatibility),
+    };
+
+    let geo_index_params = index.geo_index_params(rtxn)?;
+    let geo_index = GeoIndexSettings {
+        node_size: Setting::Set(geo_index_params.node_size.into()),
+        load_strategy: Setting::Set(geo_index_params.load_strategy.into()),
+    };
+
+    let vector_index_params = index.vector_index_params(rtxn)?;
+    let vector_index = VectorIndexSettings {
+        ef_construction: Setting::Set(vector_index_params.ef_construction),
+        ef_search: Setting::Set(vector_index_params.ef_search),
+    };
+
     let pagination = PaginationSettings {
         max_total_hits: Setting::Set(
             index.pagination_max_total_hits(rtxn)?.unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS),
@@ -562,6 +1097,7 @@ pub fn settings(
         sortable_attributes: Setting::Set(sortable_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
+        index_stop_words: Setting::Set(index.index_stop_words(rtxn)?),
         non_separator_tokens: Setting::Set(non_separator_tokens),
         separator_tokens: Setting::Set(separator_tokens),
         dictionary: Setting::Set(dictionary),
@@ -573,6 +1109,40 @@ pub fn settings(
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
+        search_cutoff_ms: match index.search_cutoff(rtxn)? {
+            Some(cutoff) => Setting::Set(cutoff),
+            None => Setting::Reset,
+        },
+        proximity_precision: Setting::Set(
+            index.proximity_precision(rtxn)?.unwrap_or_default().into(),
+        ),
+        prefix_search: Setting::Set(index.prefix_search(rtxn)?.into()),
+        split_words: Setting::Set(index.split_words(rtxn)?),
+        concatenate_words: Setting::Set(index.concatenate_words(rtxn)?),
+        localized_attributes: match index.localized_attributes_rules(rtxn)? {
+            Some(rules) => Setting::Set(rules.into_iter().map(Into::into).collect()),
+            None => Setting::Reset,
+        },
+        normalize_numbers: Setting::Set(index.normalize_numbers(rtxn)?),
+        normalization: Setting::Set(normalization),
+        percolate_queries: Setting::Set(index.percolate_queries(rtxn)?),
+        stemming: Setting::Set(
+            index.stemming_languages(rtxn)?.into_iter().map(Into::into).collect(),
+        ),
+        geo_fields: Setting::Set(index.geo_fields(rtxn)?.into_iter().collect()),
+        geo_index: Setting::Set(geo_index),
+        binary_quantized_vectors: Setting::Set(index.binary_quantized_vectors(rtxn)?),
+        vector_index: Setting::Set(vector_index),
+        reranker: match index.reranker(rtxn)? {
+            Some(reranker) => Setting::Set(reranker.into()),
+            None => Setting::Reset,
+        },
+        version_attribute: match index.version_field(rtxn)? {
+            Some(field) => Setting::Set(field.to_string()),
+            None => Setting::Reset,
+        },
+        document_timestamps: Setting::Set(index.document_timestamps(rtxn)?),
+        facet_search: Setting::Set(index.facet_search(rtxn)?),
         _kind: PhantomData,
     })
 }
@@ -599,6 +1169,18 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the freshness of the unix timestamp of the field specified.
+    Decay { field: String, half_life: u64 },
+}
+impl RankingRuleView {
+    /// Returns the field name parameter of this ranking rule.
+    pub fn field_name(&self) -> Option<&str> {
+        match self {
+            RankingRuleView::Asc(name) | RankingRuleView::Desc(name) => Some(name),
+            RankingRuleView::Decay { field, .. } => Some(field),
+            _otherwise => None,
+        }
+    }
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -655,6 +1237,7 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Decay { field, half_life } => RankingRuleView::Decay { field, half_life },
         }
     }
 }
@@ -669,6 +1252,323 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Decay { field, half_life } => Criterion::Decay { field, half_life },
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum ProximityPrecisionView {
+    /// The exact distance between the words is taken into account.
+    #[default]
+    ByWord,
+    /// Only whether the words appear in the same attribute is taken into account.
+    ByAttribute,
+}
+
+impl From<ProximityPrecision> for ProximityPrecisionView {
+    fn from(value: ProximityPrecision) -> Self {
+        match value {
+            ProximityPrecision::ByWord => ProximityPrecisionView::ByWord,
+            ProximityPrecision::ByAttribute => ProximityPrecisionView::ByAttribute,
+        }
+    }
+}
+
+impl From<ProximityPrecisionView> for ProximityPrecision {
+    fn from(value: ProximityPrecisionView) -> Self {
+        match value {
+            ProximityPrecisionView::ByWord => ProximityPrecision::ByWord,
+            ProximityPrecisionView::ByAttribute => ProximityPrecision::ByAttribute,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum PrefixSearchSettings {
+    /// The prefix databases are built while indexing the documents.
+    #[default]
+    IndexingTime,
+    /// The prefix databases are not built, the words of the query never match as prefixes.
+    Disabled,
+}
+
+impl From<PrefixSearch> for PrefixSearchSettings {
+    fn from(value: PrefixSearch) -> Self {
+        match value {
+            PrefixSearch::IndexingTime => PrefixSearchSettings::IndexingTime,
+            PrefixSearch::Disabled => PrefixSearchSettings::Disabled,
+        }
+    }
+}
+
+impl From<PrefixSearchSettings> for PrefixSearch {
+    fn from(value: PrefixSearchSettings) -> Self {
+        match value {
+            PrefixSearchSettings::IndexingTime => PrefixSearch::IndexingTime,
+            PrefixSearchSettings::Disabled => PrefixSearch::Disabled,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum GeoNodeSizeView {
+    /// Up to 6 points per node of the geo R-tree.
+    #[default]
+    Small,
+    /// Up to 16 points per node of the geo R-tree.
+    Medium,
+    /// Up to 64 points per node of the geo R-tree.
+    Large,
+}
+
+impl From<GeoNodeSize> for GeoNodeSizeView {
+    fn from(value: GeoNodeSize) -> Self {
+        match value {
+            GeoNodeSize::Small => GeoNodeSizeView::Small,
+            GeoNodeSize::Medium => GeoNodeSizeView::Medium,
+            GeoNodeSize::Large => GeoNodeSizeView::Large,
+        }
+    }
+}
+
+impl From<GeoNodeSizeView> for GeoNodeSize {
+    fn from(value: GeoNodeSizeView) -> Self {
+        match value {
+            GeoNodeSizeView::Small => GeoNodeSize::Small,
+            GeoNodeSizeView::Medium => GeoNodeSize::Medium,
+            GeoNodeSizeView::Large => GeoNodeSize::Large,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum GeoLoadStrategyView {
+    /// The new points are inserted one by one in the geo R-tree.
+    #[default]
+    Incremental,
+    /// The geo R-tree is rebuilt with all its points every time documents are indexed.
+    BulkLoad,
+}
+
+impl From<GeoLoadStrategy> for GeoLoadStrategyView {
+    fn from(value: GeoLoadStrategy) -> Self {
+        match value {
+            GeoLoadStrategy::Incremental => GeoLoadStrategyView::Incremental,
+            GeoLoadStrategy::BulkLoad => GeoLoadStrategyView::BulkLoad,
+        }
+    }
+}
+
+impl From<GeoLoadStrategyView> for GeoLoadStrategy {
+    fn from(value: GeoLoadStrategyView) -> Self {
+        match value {
+            GeoLoadStrategyView::Incremental => GeoLoadStrategy::Incremental,
+            GeoLoadStrategyView::BulkLoad => GeoLoadStrategy::BulkLoad,
+        }
+    }
+}
+
+/// A language whose words are indexed and searched along with their stem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum StemmingLanguageView {
+    En,
+    Fr,
+}
+
+impl From<StemmingLanguage> for StemmingLanguageView {
+    fn from(value: StemmingLanguage) -> Self {
+        match value {
+            StemmingLanguage::En => StemmingLanguageView::En,
+            StemmingLanguage::Fr => StemmingLanguageView::Fr,
+        }
+    }
+}
+
+impl From<StemmingLanguageView> for StemmingLanguage {
+    fn from(value: StemmingLanguageView) -> Self {
+        match value {
+            StemmingLanguageView::En => StemmingLanguage::En,
+            StemmingLanguageView::Fr => StemmingLanguage::Fr,
+        }
+    }
+}
+
+/// A searchable attribute, either its name alone or its name with a weight
+/// used by the attribute ranking rule instead of its position in the list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SearchableAttributeView {
+    Name(String),
+    Weighted(WeightedSearchableAttribute),
+}
+
+impl SearchableAttributeView {
+    pub fn attribute(&self) -> &str {
+        match self {
+            SearchableAttributeView::Name(attribute) => attribute,
+            SearchableAttributeView::Weighted(WeightedSearchableAttribute {
+                attribute, ..
+            }) => attribute,
+        }
+    }
+
+    /// The weight explicitly given by the user, the higher the more important the attribute is.
+    pub fn weight(&self) -> Option<u16> {
+        match self {
+            SearchableAttributeView::Name(_) => None,
+            SearchableAttributeView::Weighted(WeightedSearchableAttribute { weight, .. }) => {
+                Some(*weight)
+            }
+        }
+    }
+}
+
+impl From<String> for SearchableAttributeView {
+    fn from(attribute: String) -> Self {
+        SearchableAttributeView::Name(attribute)
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for SearchableAttributeView {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(attribute) => Ok(SearchableAttributeView::Name(attribute)),
+            deserr::Value::Map(_) => {
+                WeightedSearchableAttribute::deserialize_from_value(value, location)
+                    .map(SearchableAttributeView::Weighted)
+            }
+            value => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::String, ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase)]
+pub struct WeightedSearchableAttribute {
+    pub attribute: String,
+    pub weight: u16,
+}
+
+/// The stop words of an index, either an explicit list of words
+/// or a built-in preset completed by some additional words.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StopWordsView {
+    List(BTreeSet<String>),
+    Preset(StopWordsPresetSetting),
+}
+
+impl StopWordsView {
+    /// The stop words explicitly given by the user.
+    pub fn words(&self) -> &BTreeSet<String> {
+        match self {
+            StopWordsView::List(words) => words,
+            StopWordsView::Preset(StopWordsPresetSetting { words, .. }) => words,
+        }
+    }
+
+    pub fn preset(&self) -> Option<StopWordsPresetView> {
+        match self {
+            StopWordsView::List(_) => None,
+            StopWordsView::Preset(StopWordsPresetSetting { preset, .. }) => Some(*preset),
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for StopWordsView {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::Sequence(_) => {
+                BTreeSet::<String>::deserialize_from_value(value, location).map(StopWordsView::List)
+            }
+            deserr::Value::Map(_) => {
+                StopWordsPresetSetting::deserialize_from_value(value, location)
+                    .map(StopWordsView::Preset)
+            }
+            value => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::Sequence, ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase)]
+pub struct StopWordsPresetSetting {
+    pub preset: StopWordsPresetView,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    #[deserr(default)]
+    pub words: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum StopWordsPresetView {
+    En,
+    Fr,
+    De,
+    Es,
+    It,
+    Pt,
+    Nl,
+}
+
+impl From<StopWordsPreset> for StopWordsPresetView {
+    fn from(value: StopWordsPreset) -> Self {
+        match value {
+            StopWordsPreset::En => StopWordsPresetView::En,
+            StopWordsPreset::Fr => StopWordsPresetView::Fr,
+            StopWordsPreset::De => StopWordsPresetView::De,
+            StopWordsPreset::Es => StopWordsPresetView::Es,
+            StopWordsPreset::It => StopWordsPresetView::It,
+            StopWordsPreset::Pt => StopWordsPresetView::Pt,
+            StopWordsPreset::Nl => StopWordsPresetView::Nl,
+        }
+    }
+}
+
+impl From<StopWordsPresetView> for StopWordsPreset {
+    fn from(value: StopWordsPresetView) -> Self {
+        match value {
+            StopWordsPresetView::En => StopWordsPreset::En,
+            StopWordsPresetView::Fr => StopWordsPreset::Fr,
+            StopWordsPresetView::De => StopWordsPreset::De,
+            StopWordsPresetView::Es => StopWordsPreset::Es,
+            StopWordsPresetView::It => StopWordsPreset::It,
+            StopWordsPresetView::Pt => StopWordsPreset::Pt,
+            StopWordsPresetView::Nl => StopWordsPreset::Nl,
         }
     }
 }
@@ -682,11 +1582,12 @@ pub(crate) mod test {
         // test no changes
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![String::from("hello")]),
-            searchable_attributes: Setting::Set(vec![String::from("hello")]),
+            searchable_attributes: Setting::Set(vec![String::from("hello").into()]),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            index_stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
@@ -695,6 +1596,24 @@ pub(crate) mod test {
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
+            prefix_search: Setting::NotSet,
+            split_words: Setting::NotSet,
+            concatenate_words: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            normalize_numbers: Setting::NotSet,
+            normalization: Setting::NotSet,
+            percolate_queries: Setting::NotSet,
+            stemming: Setting::NotSet,
+            geo_fields: Setting::NotSet,
+            geo_index: Setting::NotSet,
+            binary_quantized_vectors: Setting::NotSet,
+            vector_index: Setting::NotSet,
+            reranker: Setting::NotSet,
+            version_attribute: Setting::NotSet,
+            document_timestamps: Setting::NotSet,
+            facet_search: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -706,11 +1625,15 @@ pub(crate) mod test {
         // test no changes
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![String::from("*")]),
-            searchable_attributes: Setting::Set(vec![String::from("hello"), String::from("*")]),
+            searchable_attributes: Setting::Set(vec![
+                String::from("hello").into(),
+                String::from("*").into(),
+            ]),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            index_stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
@@ -719,6 +1642,24 @@ pub(crate) mod test {
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
+            prefix_search: Setting::NotSet,
+            split_words: Setting::NotSet,
+            concatenate_words: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            normalize_numbers: Setting::NotSet,
+            normalization: Setting::NotSet,
+            percolate_queries: Setting::NotSet,
+            stemming: Setting::NotSet,
+            geo_fields: Setting::NotSet,
+            geo_index: Setting::NotSet,
+            binary_quantized_vectors: Setting::NotSet,
+            vector_index: Setting::NotSet,
+            reranker: Setting::NotSet,
+            version_attribute: Setting::NotSet,
+            document_timestamps: Setting::NotSet,
+            facet_search: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
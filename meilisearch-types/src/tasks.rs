@@ -1,15 +1,16 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
 use enum_iterator::Sequence;
-use milli::update::IndexDocumentsMethod;
+use milli::update::{DuplicateIdsPolicy, IndexDocumentsMethod};
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
+use crate::document_formats::PayloadType;
 use crate::error::ResponseError;
 use crate::keys::Key;
 use crate::settings::{Settings, Unchecked};
@@ -35,6 +36,11 @@ pub struct Task {
 
     pub status: Status,
     pub kind: KindWithContent,
+
+    /// The id of the HTTP request that registered the task, it correlates
+    /// the log events of its processing with the ones of the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl Task {
@@ -48,13 +54,17 @@ impl Task {
             | TaskDeletion { .. }
             | IndexSwap { .. } => None,
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentAdditionFromUrl { index_uid, .. }
+            | DocumentAdditionDryRun { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid }
             | IndexDeletion { index_uid } => Some(index_uid),
+            IndexDuplication { new_index_uid, .. } => Some(new_index_uid),
         }
     }
 
@@ -66,14 +76,18 @@ impl Task {
     /// Return the content-uuid if there is one
     pub fn content_uuid(&self) -> Option<Uuid> {
         match self.kind {
-            KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
-            KindWithContent::DocumentDeletion { .. }
+            KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+            | KindWithContent::DocumentAdditionDryRun { content_file, .. } => Some(content_file),
+            KindWithContent::DocumentAdditionFromUrl { .. }
+            | KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentDeletionByFilter { .. }
             | KindWithContent::DocumentClear { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
             | KindWithContent::IndexCreation { .. }
             | KindWithContent::IndexUpdate { .. }
+            | KindWithContent::IndexDuplication { .. }
+            | KindWithContent::IndexCompaction { .. }
             | KindWithContent::IndexSwap { .. }
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
@@ -93,6 +107,38 @@ pub enum KindWithContent {
         content_file: Uuid,
         documents_count: u64,
         allow_index_creation: bool,
+        /// The policy applied to the documents sharing an id, the one of the instance when `None`.
+        #[serde(default)]
+        duplicate_ids_policy: Option<DuplicateIdsPolicy>,
+    },
+    /// The documents are downloaded from the url when the task is processed.
+    DocumentAdditionFromUrl {
+        index_uid: String,
+        primary_key: Option<String>,
+        method: IndexDocumentsMethod,
+        url: String,
+        /// The value of the `Authorization` header sent to the url.
+        ///
+        /// It is never serialized, neither in the task queue nor in the dumps.
+        #[serde(skip)]
+        authorization: Option<String>,
+        /// Whether an `Authorization` header must be sent, it is serialized so that the task fails
+        /// instead of downloading the documents without the header once it is lost with a restart.
+        #[serde(default)]
+        has_authorization: bool,
+        format: PayloadType,
+        allow_index_creation: bool,
+        #[serde(default)]
+        duplicate_ids_policy: Option<DuplicateIdsPolicy>,
+    },
+    /// The documents are validated like they are when indexed, but nothing is written.
+    DocumentAdditionDryRun {
+        index_uid: String,
+        primary_key: Option<String>,
+        content_file: Uuid,
+        documents_count: u64,
+        #[serde(default)]
+        duplicate_ids_policy: Option<DuplicateIdsPolicy>,
     },
     DocumentDeletion {
         index_uid: String,
@@ -122,6 +168,15 @@ pub enum KindWithContent {
         index_uid: String,
         primary_key: Option<String>,
     },
+    /// Creates the `new_index_uid` index with a copy of the documents and settings of `index_uid`.
+    IndexDuplication {
+        index_uid: String,
+        new_index_uid: String,
+    },
+    /// Replaces the LMDB environment of the index by a compacted copy.
+    IndexCompaction {
+        index_uid: String,
+    },
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
@@ -150,6 +205,8 @@ impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
+            KindWithContent::DocumentAdditionFromUrl { .. } => Kind::DocumentAdditionOrUpdate,
+            KindWithContent::DocumentAdditionDryRun { .. } => Kind::DocumentAdditionDryRun,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
@@ -157,6 +214,8 @@ impl KindWithContent {
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
             KindWithContent::IndexUpdate { .. } => Kind::IndexUpdate,
+            KindWithContent::IndexDuplication { .. } => Kind::IndexCreation,
+            KindWithContent::IndexCompaction { .. } => Kind::IndexUpdate,
             KindWithContent::IndexSwap { .. } => Kind::IndexSwap,
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
@@ -174,13 +233,17 @@ impl KindWithContent {
             | TaskCancelation { .. }
             | TaskDeletion { .. } => vec![],
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentAdditionFromUrl { index_uid, .. }
+            | DocumentAdditionDryRun { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid }
             | IndexDeletion { index_uid } => vec![index_uid],
+            IndexDuplication { index_uid, new_index_uid } => vec![new_index_uid, index_uid],
             IndexSwap { swaps } => {
                 let mut indexes = HashSet::<&str>::default();
                 for swap in swaps {
@@ -200,6 +263,24 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    matched_percolate_queries: BTreeMap::new(),
+                    duplicated_documents: None,
+                })
+            }
+            // the documents are only counted once downloaded
+            KindWithContent::DocumentAdditionFromUrl { .. } => {
+                Some(Details::DocumentAdditionOrUpdate {
+                    received_documents: 0,
+                    indexed_documents: None,
+                    matched_percolate_queries: BTreeMap::new(),
+                    duplicated_documents: None,
+                })
+            }
+            KindWithContent::DocumentAdditionDryRun { documents_count, .. } => {
+                Some(Details::DocumentAdditionDryRun {
+                    received_documents: *documents_count,
+                    invalid_documents: None,
+                    document_errors: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -224,6 +305,14 @@ impl KindWithContent {
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            // the primary key is only known once the index is copied
+            KindWithContent::IndexDuplication { .. } => {
+                Some(Details::IndexInfo { primary_key: None })
+            }
+            KindWithContent::IndexCompaction { .. } => Some(Details::IndexCompaction {
+                pre_compaction_size: None,
+                post_compaction_size: None,
+            }),
             KindWithContent::IndexSwap { swaps } => {
                 Some(Details::IndexSwap { swaps: swaps.clone() })
             }
@@ -248,6 +337,23 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    matched_percolate_queries: BTreeMap::new(),
+                    duplicated_documents: None,
+                })
+            }
+            KindWithContent::DocumentAdditionFromUrl { .. } => {
+                Some(Details::DocumentAdditionOrUpdate {
+                    received_documents: 0,
+                    indexed_documents: Some(0),
+                    matched_percolate_queries: BTreeMap::new(),
+                    duplicated_documents: None,
+                })
+            }
+            KindWithContent::DocumentAdditionDryRun { documents_count, .. } => {
+                Some(Details::DocumentAdditionDryRun {
+                    received_documents: *documents_count,
+                    invalid_documents: Some(0),
+                    document_errors: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -273,6 +379,13 @@ impl KindWithContent {
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexDuplication { .. } => {
+                Some(Details::IndexInfo { primary_key: None })
+            }
+            KindWithContent::IndexCompaction { .. } => Some(Details::IndexCompaction {
+                pre_compaction_size: None,
+                post_compaction_size: None,
+            }),
             KindWithContent::IndexSwap { .. } => {
                 todo!()
             }
@@ -299,6 +412,23 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    matched_percolate_queries: BTreeMap::new(),
+                    duplicated_documents: None,
+                })
+            }
+            KindWithContent::DocumentAdditionFromUrl { .. } => {
+                Some(Details::DocumentAdditionOrUpdate {
+                    received_documents: 0,
+                    indexed_documents: None,
+                    matched_percolate_queries: BTreeMap::new(),
+                    duplicated_documents: None,
+                })
+            }
+            KindWithContent::DocumentAdditionDryRun { documents_count, .. } => {
+                Some(Details::DocumentAdditionDryRun {
+                    received_documents: *documents_count,
+                    invalid_documents: None,
+                    document_errors: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { .. } => None,
@@ -314,6 +444,13 @@ impl From<&KindWithContent> for Option<Details> {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexDuplication { .. } => {
+                Some(Details::IndexInfo { primary_key: None })
+            }
+            KindWithContent::IndexCompaction { .. } => Some(Details::IndexCompaction {
+                pre_compaction_size: None,
+                post_compaction_size: None,
+            }),
             KindWithContent::IndexSwap { .. } => None,
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
@@ -404,12 +541,15 @@ pub enum Kind {
     TaskDeletion,
     DumpCreation,
     SnapshotCreation,
+    // declared last to keep the bincode encoding of the kinds already stored in the task queue
+    DocumentAdditionDryRun,
 }
 
 impl Kind {
     pub fn related_to_one_index(&self) -> bool {
         match self {
             Kind::DocumentAdditionOrUpdate
+            | Kind::DocumentAdditionDryRun
             | Kind::DocumentDeletion
             | Kind::SettingsUpdate
             | Kind::IndexCreation
@@ -427,6 +567,7 @@ impl Display for Kind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
+            Kind::DocumentAdditionDryRun => write!(f, "documentAdditionDryRun"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
             Kind::IndexCreation => write!(f, "indexCreation"),
@@ -454,6 +595,8 @@ impl FromStr for Kind {
             Ok(Kind::IndexDeletion)
         } else if kind.eq_ignore_ascii_case("documentAdditionOrUpdate") {
             Ok(Kind::DocumentAdditionOrUpdate)
+        } else if kind.eq_ignore_ascii_case("documentAdditionDryRun") {
+            Ok(Kind::DocumentAdditionDryRun)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
@@ -495,16 +638,70 @@ impl std::error::Error for ParseTaskKindError {}
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum Details {
-    DocumentAdditionOrUpdate { received_documents: u64, indexed_documents: Option<u64> },
-    SettingsUpdate { settings: Box<Settings<Unchecked>> },
-    IndexInfo { primary_key: Option<String> },
-    DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
-    DocumentDeletionByFilter { original_filter: String, deleted_documents: Option<u64> },
-    ClearAll { deleted_documents: Option<u64> },
-    TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
-    TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
-    Dump { dump_uid: Option<String> },
-    IndexSwap { swaps: Vec<IndexSwap> },
+    DocumentAdditionOrUpdate {
+        received_documents: u64,
+        indexed_documents: Option<u64>,
+        /// The ids of the added documents matched by each percolate query of the index.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        matched_percolate_queries: BTreeMap<String, Vec<String>>,
+        /// The number of documents sharing their id with a previous document of the payload.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duplicated_documents: Option<u64>,
+    },
+    DocumentAdditionDryRun {
+        received_documents: u64,
+        invalid_documents: Option<u64>,
+        /// The errors of the first invalid documents of the payload.
+        document_errors: Vec<DocumentError>,
+    },
+    SettingsUpdate {
+        settings: Box<Settings<Unchecked>>,
+    },
+    IndexInfo {
+        primary_key: Option<String>,
+    },
+    DocumentDeletion {
+        provided_ids: usize,
+        deleted_documents: Option<u64>,
+    },
+    DocumentDeletionByFilter {
+        original_filter: String,
+        deleted_documents: Option<u64>,
+    },
+    ClearAll {
+        deleted_documents: Option<u64>,
+    },
+    TaskCancelation {
+        matched_tasks: u64,
+        canceled_tasks: Option<u64>,
+        original_filter: String,
+    },
+    TaskDeletion {
+        matched_tasks: u64,
+        deleted_tasks: Option<u64>,
+        original_filter: String,
+    },
+    Dump {
+        dump_uid: Option<String>,
+    },
+    IndexSwap {
+        swaps: Vec<IndexSwap>,
+    },
+    IndexCompaction {
+        /// The size of the index on the disk before the compaction, in bytes.
+        pre_compaction_size: Option<u64>,
+        post_compaction_size: Option<u64>,
+    },
+}
+
+/// An invalid document found by a [`KindWithContent::DocumentAdditionDryRun`].
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentError {
+    /// The position of the document in the payload, starting at zero.
+    pub position: u32,
+    pub document_id: Option<String>,
+    pub error: ResponseError,
 }
 
 impl Details {
@@ -514,6 +711,7 @@ impl Details {
             Self::DocumentAdditionOrUpdate { indexed_documents, .. } => {
                 *indexed_documents = Some(0)
             }
+            Self::DocumentAdditionDryRun { invalid_documents, .. } => *invalid_documents = Some(0),
             Self::DocumentDeletion { deleted_documents, .. } => *deleted_documents = Some(0),
             Self::DocumentDeletionByFilter { deleted_documents, .. } => {
                 *deleted_documents = Some(0)
@@ -524,7 +722,8 @@ impl Details {
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
-            | Self::IndexSwap { .. } => (),
+            | Self::IndexSwap { .. }
+            | Self::IndexCompaction { .. } => (),
         }
 
         details
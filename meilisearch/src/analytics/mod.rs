@@ -72,7 +72,7 @@ pub enum DocumentDeletionKind {
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DocumentFetchKind {
     PerDocumentId,
-    Normal { with_filter: bool, limit: usize, offset: usize },
+    Normal { with_filter: bool, with_sort: bool, limit: usize, offset: usize },
 }
 
 pub trait Analytics: Sync + Send {
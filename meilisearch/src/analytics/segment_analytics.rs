@@ -1,4 +1,4 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::mem::take;
 use std::path::{Path, PathBuf};
@@ -11,6 +11,8 @@ use byte_unit::Byte;
 use http::header::CONTENT_TYPE;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::update::DuplicateIdsPolicy;
 use meilisearch_types::InstanceUid;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -34,8 +36,8 @@ use crate::routes::indexes::facet_search::FacetSearchQuery;
 use crate::routes::tasks::TasksFilterQuery;
 use crate::routes::{create_all_stats, Stats};
 use crate::search::{
-    FacetSearchResult, MatchingStrategy, SearchQuery, SearchQueryWithIndex, SearchResult,
-    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    FacetSearchResult, GeoDistanceUnit, MatchingStrategy, SearchQuery, SearchQueryWithIndex,
+    SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
     DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
 };
 use crate::Opt;
@@ -251,11 +253,20 @@ struct Infos {
     env: String,
     experimental_enable_metrics: bool,
     experimental_reduce_indexing_memory_usage: bool,
+    experimental_search_analytics: bool,
+    experimental_query_suggestions: bool,
+    experimental_search_cache_size: usize,
+    experimental_search_cache_ttl_sec: u64,
+    experimental_max_search_threads: usize,
+    experimental_max_concurrent_batches: usize,
     db_path: bool,
     import_dump: bool,
     dump_dir: bool,
     ignore_missing_dump: bool,
     ignore_dump_if_db_exists: bool,
+    task_retention_days: Option<u64>,
+    task_retention_count: Option<u64>,
+    shutdown_grace_period_sec: u64,
     import_snapshot: bool,
     schedule_snapshot: Option<u64>,
     snapshot_dir: bool,
@@ -263,9 +274,17 @@ struct Infos {
     ignore_snapshot_if_db_exists: bool,
     http_addr: bool,
     http_payload_size_limit: Byte,
+    max_indexing_payload_size: Option<Byte>,
+    allow_private_documents_urls: bool,
+    http_compression_min_size: Byte,
+    http_compression_level: u32,
     log_level: String,
+    log_format: String,
     max_indexing_memory: MaxMemory,
     max_indexing_threads: MaxThreads,
+    max_document_fields: Option<usize>,
+    max_document_depth: Option<usize>,
+    duplicate_ids_policy: DuplicateIdsPolicy,
     with_configuration_file: bool,
     ssl_auth_path: bool,
     ssl_cert_path: bool,
@@ -285,12 +304,22 @@ impl From<Opt> for Infos {
             db_path,
             experimental_enable_metrics,
             experimental_reduce_indexing_memory_usage,
+            experimental_search_analytics,
+            experimental_query_suggestions,
+            experimental_search_cache_size,
+            experimental_search_cache_ttl_sec,
+            experimental_max_search_threads,
+            experimental_max_concurrent_batches,
             http_addr,
             master_key: _,
             env,
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
+            max_indexing_payload_size,
+            allow_private_documents_urls,
+            http_compression_min_size,
+            http_compression_level,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -307,7 +336,11 @@ impl From<Opt> for Infos {
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             dump_dir,
+            task_retention_days,
+            task_retention_count,
+            shutdown_grace_period_sec,
             log_level,
+            log_format,
             indexer_options,
             config_file_path,
             #[cfg(feature = "analytics")]
@@ -319,8 +352,14 @@ impl From<Opt> for Infos {
             ScheduleSnapshot::Enabled(interval) => Some(interval),
         };
 
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } =
-            indexer_options;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            max_document_fields,
+            max_document_depth,
+            duplicate_ids_policy,
+            skip_index_budget: _,
+        } = indexer_options;
 
         // We're going to override every sensible information.
         // We consider information sensible if it contains a path, an address, or a key.
@@ -328,11 +367,20 @@ impl From<Opt> for Infos {
             env,
             experimental_enable_metrics,
             experimental_reduce_indexing_memory_usage,
+            experimental_search_analytics,
+            experimental_query_suggestions,
+            experimental_search_cache_size,
+            experimental_search_cache_ttl_sec,
+            experimental_max_search_threads,
+            experimental_max_concurrent_batches,
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
             dump_dir: dump_dir != PathBuf::from("dumps/"),
             ignore_missing_dump,
             ignore_dump_if_db_exists,
+            task_retention_days,
+            task_retention_count,
+            shutdown_grace_period_sec,
             import_snapshot: import_snapshot.is_some(),
             schedule_snapshot,
             snapshot_dir: snapshot_dir != PathBuf::from("snapshots/"),
@@ -340,9 +388,17 @@ impl From<Opt> for Infos {
             ignore_snapshot_if_db_exists,
             http_addr: http_addr != default_http_addr(),
             http_payload_size_limit,
+            max_indexing_payload_size,
+            allow_private_documents_urls,
+            http_compression_min_size,
+            http_compression_level,
             log_level: log_level.to_string(),
+            log_format: log_format.to_string(),
             max_indexing_memory,
             max_indexing_threads,
+            max_document_fields,
+            max_document_depth,
+            duplicate_ids_policy,
             with_configuration_file: config_file_path.is_some(),
             ssl_auth_path: ssl_auth_path.is_some(),
             ssl_cert_path: ssl_cert_path.is_some(),
@@ -554,6 +610,7 @@ pub struct SearchAggregator {
     // requests
     total_received: usize,
     total_succeeded: usize,
+    total_degraded: usize,
     time_spent: BinaryHeap<usize>,
 
     // sort
@@ -576,6 +633,10 @@ pub struct SearchAggregator {
     // every time a search is done using attributes_to_search_on
     attributes_to_search_on_total_number_of_uses: usize,
 
+    // attribute_boosts
+    // every time a search is done using attribute_boosts
+    attribute_boosts_total_number_of_uses: usize,
+
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
@@ -586,6 +647,8 @@ pub struct SearchAggregator {
 
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
+    // whether a minimum number of words to match was given in a search
+    minimum_should_match: bool,
 
     // pagination
     max_limit: usize,
@@ -597,6 +660,7 @@ pub struct SearchAggregator {
     max_attributes_to_highlight: usize,
     highlight_pre_tag: bool,
     highlight_post_tag: bool,
+    highlight_searchable_only: bool,
     max_attributes_to_crop: usize,
     crop_marker: bool,
     show_matches_position: bool,
@@ -606,9 +670,44 @@ pub struct SearchAggregator {
     facets_sum_of_terms: usize,
     facets_total_number_of_facets: usize,
 
+    // distinct
+    distinct: bool,
+
+    // prefix_search
+    // whether the prefix search was disabled in a search
+    prefix_search_disabled: bool,
+
+    // locales
+    // every locale forced in a search
+    locales: BTreeSet<Locale>,
+
+    // stop words
+    keep_stop_words: bool,
+
+    // geo distance
+    // whether the `_geoDistance` was asked in another unit than the meters
+    geo_distance_unit: bool,
+
+    // group_by
+    group_by: bool,
+
+    // spelling suggestions
+    show_spelling_suggestions: bool,
+
+    // candidates handles
+    show_candidates_handle: bool,
+    within_candidates: bool,
+
+    // cursor pagination
+    search_after: bool,
+
+    // vectors
+    retrieve_vectors: bool,
+
     // scoring
     show_ranking_score: bool,
     show_ranking_score_details: bool,
+    ranking_score_threshold: bool,
 }
 
 impl SearchAggregator {
@@ -633,9 +732,25 @@ impl SearchAggregator {
             facets: _,
             highlight_pre_tag,
             highlight_post_tag,
+            highlight_searchable_only,
             crop_marker,
             matching_strategy,
+            minimum_should_match,
             attributes_to_search_on,
+            attribute_boosts,
+            ranking_score_threshold,
+            distinct,
+            prefix_search,
+            locales,
+            keep_stop_words,
+            geo_distance_unit,
+            group_by,
+            hits_per_group: _,
+            show_spelling_suggestions,
+            show_candidates_handle,
+            within_candidates,
+            search_after,
+            retrieve_vectors,
         } = query;
 
         let mut ret = Self::default();
@@ -679,6 +794,11 @@ impl SearchAggregator {
             ret.attributes_to_search_on_total_number_of_uses = 1;
         }
 
+        // attribute_boosts
+        if attribute_boosts.is_some() {
+            ret.attribute_boosts_total_number_of_uses = 1;
+        }
+
         if let Some(ref q) = q {
             ret.max_terms_number = q.split_whitespace().count();
         }
@@ -699,15 +819,29 @@ impl SearchAggregator {
         }
 
         ret.matching_strategy.insert(format!("{:?}", matching_strategy), 1);
+        ret.minimum_should_match = minimum_should_match.is_some();
 
         ret.highlight_pre_tag = *highlight_pre_tag != DEFAULT_HIGHLIGHT_PRE_TAG();
         ret.highlight_post_tag = *highlight_post_tag != DEFAULT_HIGHLIGHT_POST_TAG();
+        ret.highlight_searchable_only = *highlight_searchable_only;
         ret.crop_marker = *crop_marker != DEFAULT_CROP_MARKER();
         ret.crop_length = *crop_length != DEFAULT_CROP_LENGTH();
         ret.show_matches_position = *show_matches_position;
 
         ret.show_ranking_score = *show_ranking_score;
         ret.show_ranking_score_details = *show_ranking_score_details;
+        ret.ranking_score_threshold = ranking_score_threshold.is_some();
+        ret.distinct = distinct.is_some();
+        ret.prefix_search_disabled = *prefix_search == Some(false);
+        ret.locales = locales.iter().flatten().copied().collect();
+        ret.keep_stop_words = *keep_stop_words;
+        ret.geo_distance_unit = *geo_distance_unit != GeoDistanceUnit::default();
+        ret.group_by = group_by.is_some();
+        ret.show_spelling_suggestions = *show_spelling_suggestions;
+        ret.show_candidates_handle = *show_candidates_handle;
+        ret.within_candidates = within_candidates.is_some();
+        ret.search_after = search_after.is_some();
+        ret.retrieve_vectors = *retrieve_vectors;
 
         ret
     }
@@ -721,9 +855,18 @@ impl SearchAggregator {
             hits_info: _,
             facet_distribution: _,
             facet_stats: _,
+            groups: _,
+            spelling_suggestions: _,
+            candidates_handle: _,
+            next_search_after: _,
+            degraded,
+            search_uid: _,
         } = result;
 
         self.total_succeeded = self.total_succeeded.saturating_add(1);
+        if *degraded {
+            self.total_degraded = self.total_degraded.saturating_add(1);
+        }
         self.time_spent.push(*processing_time_ms as usize);
     }
 
@@ -734,6 +877,7 @@ impl SearchAggregator {
             user_agents,
             total_received,
             total_succeeded,
+            total_degraded,
             ref mut time_spent,
             sort_with_geo_point,
             sort_sum_of_criteria_terms,
@@ -744,9 +888,11 @@ impl SearchAggregator {
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
+            attribute_boosts_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             matching_strategy,
+            minimum_should_match,
             max_limit,
             max_offset,
             finite_pagination,
@@ -754,6 +900,7 @@ impl SearchAggregator {
             max_attributes_to_highlight,
             highlight_pre_tag,
             highlight_post_tag,
+            highlight_searchable_only,
             max_attributes_to_crop,
             crop_marker,
             show_matches_position,
@@ -762,6 +909,18 @@ impl SearchAggregator {
             facets_total_number_of_facets,
             show_ranking_score,
             show_ranking_score_details,
+            ranking_score_threshold,
+            distinct,
+            prefix_search_disabled,
+            locales,
+            keep_stop_words,
+            geo_distance_unit,
+            group_by,
+            show_spelling_suggestions,
+            show_candidates_handle,
+            within_candidates,
+            search_after,
+            retrieve_vectors,
         } = other;
 
         if self.timestamp.is_none() {
@@ -776,6 +935,7 @@ impl SearchAggregator {
         // request
         self.total_received = self.total_received.saturating_add(total_received);
         self.total_succeeded = self.total_succeeded.saturating_add(total_succeeded);
+        self.total_degraded = self.total_degraded.saturating_add(total_degraded);
         self.time_spent.append(time_spent);
 
         // sort
@@ -802,6 +962,11 @@ impl SearchAggregator {
             .attributes_to_search_on_total_number_of_uses
             .saturating_add(attributes_to_search_on_total_number_of_uses);
 
+        // attribute_boosts
+        self.attribute_boosts_total_number_of_uses = self
+            .attribute_boosts_total_number_of_uses
+            .saturating_add(attribute_boosts_total_number_of_uses);
+
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
 
@@ -820,6 +985,7 @@ impl SearchAggregator {
             self.max_attributes_to_highlight.max(max_attributes_to_highlight);
         self.highlight_pre_tag |= highlight_pre_tag;
         self.highlight_post_tag |= highlight_post_tag;
+        self.highlight_searchable_only |= highlight_searchable_only;
         self.max_attributes_to_crop = self.max_attributes_to_crop.max(max_attributes_to_crop);
         self.crop_marker |= crop_marker;
         self.show_matches_position |= show_matches_position;
@@ -835,10 +1001,43 @@ impl SearchAggregator {
             let matching_strategy = self.matching_strategy.entry(key).or_insert(0);
             *matching_strategy = matching_strategy.saturating_add(value);
         }
+        self.minimum_should_match |= minimum_should_match;
 
         // scoring
         self.show_ranking_score |= show_ranking_score;
         self.show_ranking_score_details |= show_ranking_score_details;
+        self.ranking_score_threshold |= ranking_score_threshold;
+
+        // distinct
+        self.distinct |= distinct;
+
+        // prefix_search
+        self.prefix_search_disabled |= prefix_search_disabled;
+
+        // locales
+        self.locales.extend(locales);
+
+        // stop words
+        self.keep_stop_words |= keep_stop_words;
+
+        // geo distance
+        self.geo_distance_unit |= geo_distance_unit;
+
+        // group_by
+        self.group_by |= group_by;
+
+        // spelling suggestions
+        self.show_spelling_suggestions |= show_spelling_suggestions;
+
+        // candidates handles
+        self.show_candidates_handle |= show_candidates_handle;
+        self.within_candidates |= within_candidates;
+
+        // cursor pagination
+        self.search_after |= search_after;
+
+        // vectors
+        self.retrieve_vectors |= retrieve_vectors;
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -847,6 +1046,7 @@ impl SearchAggregator {
             user_agents,
             total_received,
             total_succeeded,
+            total_degraded,
             time_spent,
             sort_with_geo_point,
             sort_sum_of_criteria_terms,
@@ -857,9 +1057,11 @@ impl SearchAggregator {
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
+            attribute_boosts_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             matching_strategy,
+            minimum_should_match,
             max_limit,
             max_offset,
             finite_pagination,
@@ -867,6 +1069,7 @@ impl SearchAggregator {
             max_attributes_to_highlight,
             highlight_pre_tag,
             highlight_post_tag,
+            highlight_searchable_only,
             max_attributes_to_crop,
             crop_marker,
             show_matches_position,
@@ -875,6 +1078,18 @@ impl SearchAggregator {
             facets_total_number_of_facets,
             show_ranking_score,
             show_ranking_score_details,
+            ranking_score_threshold,
+            distinct,
+            prefix_search_disabled,
+            locales,
+            keep_stop_words,
+            geo_distance_unit,
+            group_by,
+            show_spelling_suggestions,
+            show_candidates_handle,
+            within_candidates,
+            search_after,
+            retrieve_vectors,
         } = self;
 
         if total_received == 0 {
@@ -894,6 +1109,7 @@ impl SearchAggregator {
                     "total_succeeded": total_succeeded,
                     "total_failed": total_received.saturating_sub(total_succeeded), // just to be sure we never panics
                     "total_received": total_received,
+                    "total_degraded": total_degraded,
                 },
                 "sort": {
                     "with_geoPoint": sort_with_geo_point,
@@ -908,6 +1124,9 @@ impl SearchAggregator {
                 "attributes_to_search_on": {
                    "total_number_of_uses": attributes_to_search_on_total_number_of_uses,
                 },
+                "attribute_boosts": {
+                   "total_number_of_uses": attribute_boosts_total_number_of_uses,
+                },
                 "q": {
                    "max_terms_number": max_terms_number,
                 },
@@ -924,6 +1143,7 @@ impl SearchAggregator {
                     "max_attributes_to_highlight": max_attributes_to_highlight,
                     "highlight_pre_tag": highlight_pre_tag,
                     "highlight_post_tag": highlight_post_tag,
+                    "highlight_searchable_only": highlight_searchable_only,
                     "max_attributes_to_crop": max_attributes_to_crop,
                     "crop_marker": crop_marker,
                     "show_matches_position": show_matches_position,
@@ -934,10 +1154,27 @@ impl SearchAggregator {
                 },
                 "matching_strategy": {
                     "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
+                    "minimum_should_match": minimum_should_match,
+                },
+                "distinct": distinct,
+                "prefix_search": {
+                    "disabled": prefix_search_disabled,
+                },
+                "locales": locales,
+                "keep_stop_words": keep_stop_words,
+                "geo_distance_unit": geo_distance_unit,
+                "group_by": group_by,
+                "show_spelling_suggestions": show_spelling_suggestions,
+                "candidates_handle": {
+                    "show_candidates_handle": show_candidates_handle,
+                    "within_candidates": within_candidates,
                 },
+                "search_after": search_after,
+                "retrieve_vectors": retrieve_vectors,
                 "scoring": {
                     "show_ranking_score": show_ranking_score,
                     "show_ranking_score_details": show_ranking_score_details,
+                    "ranking_score_threshold": ranking_score_threshold,
                 },
             });
 
@@ -1006,9 +1243,25 @@ impl MultiSearchAggregator {
                     facets: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
+                    highlight_searchable_only: _,
                     crop_marker: _,
                     matching_strategy: _,
+                    minimum_should_match: _,
                     attributes_to_search_on: _,
+                    attribute_boosts: _,
+                    ranking_score_threshold: _,
+                    distinct: _,
+                    prefix_search: _,
+                    locales: _,
+                    keep_stop_words: _,
+                    geo_distance_unit: _,
+                    group_by: _,
+                    hits_per_group: _,
+                    show_spelling_suggestions: _,
+                    show_candidates_handle: _,
+                    within_candidates: _,
+                    search_after: _,
+                    retrieve_vectors: _,
                 } = query;
 
                 index_uid.as_str()
@@ -1281,7 +1534,12 @@ impl DocumentsAggregator {
         index_creation: bool,
         request: &HttpRequest,
     ) -> Self {
-        let UpdateDocumentsQuery { primary_key, csv_delimiter: _ } = documents_query;
+        let UpdateDocumentsQuery {
+            primary_key,
+            csv_delimiter: _,
+            dry_run: _,
+            duplicate_ids_policy: _,
+        } = documents_query;
 
         let mut primary_keys = HashSet::new();
         if let Some(primary_key) = primary_key.clone() {
@@ -1613,6 +1871,8 @@ pub struct DocumentsFetchAggregator {
     per_document_id: bool,
     // if a filter was used
     per_filter: bool,
+    // if a sort was used
+    per_sort: bool,
 
     // pagination
     #[serde(rename = "pagination.max_limit")]
@@ -1633,6 +1893,7 @@ impl DocumentsFetchAggregator {
             total_received: 1,
             per_document_id: matches!(query, DocumentFetchKind::PerDocumentId),
             per_filter: matches!(query, DocumentFetchKind::Normal { with_filter, .. } if *with_filter),
+            per_sort: matches!(query, DocumentFetchKind::Normal { with_sort, .. } if *with_sort),
             max_limit: limit,
             max_offset: offset,
         }
@@ -1646,6 +1907,7 @@ impl DocumentsFetchAggregator {
             total_received,
             per_document_id,
             per_filter,
+            per_sort,
             max_limit,
             max_offset,
         } = other;
@@ -1660,6 +1922,7 @@ impl DocumentsFetchAggregator {
         self.total_received = self.total_received.saturating_add(total_received);
         self.per_document_id |= per_document_id;
         self.per_filter |= per_filter;
+        self.per_sort |= per_sort;
 
         self.max_limit = self.max_limit.max(max_limit);
         self.max_offset = self.max_offset.max(max_offset);
@@ -21,6 +21,20 @@ pub enum MeilisearchHttpError {
     InvalidContentType(String, Vec<String>),
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
+    #[error("The candidates handle `{0}` is malformed.")]
+    MalformedCandidatesHandle(String),
+    #[error("The candidates handle has expired because the index has been updated since it was returned.")]
+    ExpiredCandidatesHandle,
+    #[error("The cursor `{0}` given to `searchAfter` is malformed.")]
+    MalformedSearchAfter(String),
+    #[error("The cursor given to `searchAfter` has expired because the index has been updated since it was returned.")]
+    ExpiredSearchAfter,
+    #[error("The `searchAfter` parameter cannot be used together with the `offset`, `page`, `hitsPerPage` or `groupBy` parameters.")]
+    SearchAfterWithPagination,
+    #[error("Search `{0}` not found, it may have been forgotten by the search analytics.")]
+    SearchNotFound(u64),
+    #[error("The facet search is disabled for this index.")]
+    FacetSearchDisabled,
     #[error("Sending an empty filter is forbidden.")]
     EmptyFilter,
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
@@ -61,6 +75,15 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
+            MeilisearchHttpError::MalformedCandidatesHandle(_) => {
+                Code::InvalidSearchWithinCandidates
+            }
+            MeilisearchHttpError::ExpiredCandidatesHandle => Code::InvalidSearchWithinCandidates,
+            MeilisearchHttpError::MalformedSearchAfter(_) => Code::InvalidSearchSearchAfter,
+            MeilisearchHttpError::ExpiredSearchAfter => Code::InvalidSearchSearchAfter,
+            MeilisearchHttpError::SearchAfterWithPagination => Code::InvalidSearchSearchAfter,
+            MeilisearchHttpError::SearchNotFound(_) => Code::SearchNotFound,
+            MeilisearchHttpError::FacetSearchDisabled => Code::FacetSearchDisabled,
             MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
             MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
             MeilisearchHttpError::PayloadTooLarge(_) => Code::PayloadTooLarge,
@@ -71,7 +94,7 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::IndexScheduler(e) => e.error_code(),
             MeilisearchHttpError::Milli(e) => e.error_code(),
             MeilisearchHttpError::Payload(e) => e.error_code(),
-            MeilisearchHttpError::FileStore(_) => Code::Internal,
+            MeilisearchHttpError::FileStore(e) => e.error_code(),
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
         }
@@ -2,6 +2,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use actix_http::encoding::Decoder as Decompress;
+use actix_web::http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
 use actix_web::{dev, web, FromRequest, HttpRequest};
 use futures::future::{ready, Ready};
 use futures::Stream;
@@ -41,6 +42,18 @@ impl FromRequest for Payload {
             .app_data::<PayloadConfig>()
             .map(|c| c.limit)
             .unwrap_or(PayloadConfig::default().limit);
+
+        // An uncompressed payload announcing a size over the limit is rejected before being read.
+        let content_length = req
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|length| length.to_str().ok()?.parse::<usize>().ok());
+        if !req.headers().contains_key(CONTENT_ENCODING)
+            && content_length.map_or(false, |length| length > limit)
+        {
+            return ready(Err(MeilisearchHttpError::PayloadTooLarge(limit)));
+        }
+
         ready(Ok(Payload {
             payload: Decompress::from_headers(payload.take(), req.headers()),
             limit,
@@ -107,4 +120,19 @@ mod tests {
             }
         }
     }
+
+    #[actix_rt::test]
+    async fn announced_payload_too_large() {
+        let (req, mut payload) = actix_web::test::TestRequest::default()
+            .insert_header((CONTENT_LENGTH, "4"))
+            .app_data(PayloadConfig::new(3))
+            .to_http_parts();
+
+        match Payload::from_request(&req, &mut payload).await {
+            Ok(_) => panic!("the payload should have been rejected"),
+            Err(err) => {
+                snapshot!(err, @"The provided payload reached the size limit. The maximum accepted payload size is 3 B.")
+            }
+        }
+    }
 }
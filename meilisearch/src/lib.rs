@@ -4,9 +4,11 @@ pub mod error;
 pub mod analytics;
 #[macro_use]
 pub mod extractors;
+pub mod logging;
 pub mod metrics;
 pub mod middleware;
 pub mod option;
+pub mod reranker;
 pub mod routes;
 pub mod search;
 
@@ -38,7 +40,7 @@ use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::versioning::{check_version_file, create_version_file};
 use meilisearch_types::{compression, milli, VERSION_FILE_NAME};
 pub use option::Opt;
-use option::ScheduleSnapshot;
+use option::{LogFormat, ScheduleSnapshot};
 
 use crate::error::MeilisearchHttpError;
 
@@ -126,7 +128,15 @@ pub fn create_app(
             .allow_any_method()
             .max_age(86_400), // 24h
     )
-    .wrap(actix_web::middleware::Logger::default())
+    .wrap(middleware::ResponseCompression::new(
+        opt.http_compression_min_size.get_bytes() as u64,
+        opt.http_compression_level,
+    ))
+    .wrap(middleware::RequestId::new(opt.log_format == LogFormat::Json))
+    .wrap(actix_web::middleware::Condition::new(
+        opt.log_format == LogFormat::Human,
+        actix_web::middleware::Logger::default(),
+    ))
     .wrap(actix_web::middleware::Compress::default())
     .wrap(actix_web::middleware::NormalizePath::new(actix_web::middleware::TrailingSlash::Trim))
 }
@@ -237,9 +247,20 @@ fn open_or_create_database_unchecked(
             indexer_config: (&opt.indexer_options).try_into()?,
             autobatching_enabled: true,
             max_number_of_tasks: 1_000_000,
+            task_retention_period: opt
+                .task_retention_days
+                .and_then(|days| days.checked_mul(24 * 60 * 60))
+                .map(Duration::from_secs),
+            task_retention_count: opt.task_retention_count,
+            max_indexing_payload_size: Some(opt.indexing_payload_size_limit()),
+            allow_private_documents_urls: opt.allow_private_documents_urls,
+            max_concurrent_batches: opt.experimental_max_concurrent_batches,
             index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().get_bytes() as usize,
             index_count: DEFAULT_INDEX_COUNT,
             instance_features,
+            search_cache_size: opt.experimental_search_cache_size,
+            search_cache_ttl: Duration::from_secs(opt.experimental_search_cache_ttl_sec),
+            search_thread_pool: opt.search_thread_pool()?,
         })?)
     };
 
@@ -309,9 +330,15 @@ fn import_dump(
         keys.push(key);
     }
 
-    // 3. Import the runtime features.
+    // 3. Import the runtime features, the index templates and the schedules.
     let features = dump_reader.features()?.unwrap_or_default();
     index_scheduler.put_runtime_features(features)?;
+    for (name, template) in dump_reader.index_templates()? {
+        index_scheduler.put_index_template(&name, &template)?;
+    }
+    for (name, schedule) in dump_reader.schedules()? {
+        index_scheduler.put_schedule(&name, schedule.cron, schedule.kind)?;
+    }
 
     let indexer_config = index_scheduler.indexer_config();
 
@@ -393,7 +420,7 @@ pub fn configure_data(
     opt: &Opt,
     analytics: Arc<dyn Analytics>,
 ) {
-    let http_payload_size_limit = opt.http_payload_size_limit.get_bytes() as usize;
+    let indexing_payload_size_limit = opt.indexing_payload_size_limit() as usize;
     config
         .app_data(index_scheduler)
         .app_data(auth)
@@ -416,7 +443,7 @@ pub fn configure_data(
                     err => PayloadError::from(err).into(),
                 }),
         )
-        .app_data(PayloadConfig::new(http_payload_size_limit))
+        .app_data(PayloadConfig::new(indexing_payload_size_limit))
         .app_data(
             web::QueryConfig::default().error_handler(|err, _req| PayloadError::from(err).into()),
         );
@@ -0,0 +1,169 @@
+//! The JSON format of the logs and the ids correlating the log events of an HTTP request.
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+
+use env_logger::fmt::Formatter;
+use index_scheduler::request_ids::batch_request_ids;
+use log::Record;
+use serde_json::{Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::task::JoinHandle;
+
+/// The header giving its id to a request, generated when the client doesn't send one.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+thread_local! {
+    /// The id of the request for which a blocking task is running.
+    static BLOCKING_REQUEST_ID: RefCell<Option<String>> = RefCell::new(None);
+    /// The fields added to the log events emitted by [`log_with_fields`].
+    static EVENT_FIELDS: RefCell<Vec<(&'static str, Value)>> = RefCell::new(Vec::new());
+}
+
+/// Returns the id of the HTTP request being handled, if any.
+pub fn request_id() -> Option<String> {
+    REQUEST_ID
+        .try_with(Clone::clone)
+        .ok()
+        .or_else(|| BLOCKING_REQUEST_ID.with(|id| id.borrow().clone()))
+}
+
+/// Runs the future of an HTTP request, the log events it emits are given the id of the request.
+pub async fn scope_request_id<F: std::future::Future>(request_id: String, f: F) -> F::Output {
+    REQUEST_ID.scope(request_id, f).await
+}
+
+/// Runs the function on a blocking thread, the log events it emits are given the id of the
+/// request that spawned it.
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let request_id = request_id();
+    tokio::task::spawn_blocking(move || {
+        let _guard = BlockingRequestIdGuard(BLOCKING_REQUEST_ID.with(|id| id.replace(request_id)));
+        f()
+    })
+}
+
+/// Restores the previous request id of the blocking thread when dropped, the id of a
+/// task that panicked is then not given to the next task running on the same thread.
+struct BlockingRequestIdGuard(Option<String>);
+
+impl Drop for BlockingRequestIdGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        BLOCKING_REQUEST_ID.with(|id| *id.borrow_mut() = previous);
+    }
+}
+
+/// Emits the log events of the function with the given fields, that are only written by the
+/// JSON format.
+pub fn log_with_fields(fields: Vec<(&'static str, Value)>, log: impl FnOnce()) {
+    let previous = EVENT_FIELDS.with(|event_fields| event_fields.replace(fields));
+    log();
+    EVENT_FIELDS.with(|event_fields| *event_fields.borrow_mut() = previous);
+}
+
+/// Writes a log event as a JSON object on a single line.
+pub fn format_json(buf: &mut Formatter, record: &Record) -> io::Result<()> {
+    serde_json::to_writer(&mut *buf, &json_event(record))?;
+    writeln!(buf)
+}
+
+fn json_event(record: &Record) -> Map<String, Value> {
+    let mut event = Map::new();
+    let time = OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default();
+    event.insert("time".to_string(), Value::String(time));
+    event.insert("level".to_string(), Value::String(record.level().to_string()));
+    event.insert("target".to_string(), Value::String(record.target().to_string()));
+    event.insert("message".to_string(), Value::String(record.args().to_string()));
+    if let Some(request_id) = request_id() {
+        event.insert("requestId".to_string(), Value::String(request_id));
+    } else {
+        // the events emitted while processing a batch are given the ids of the requests of its tasks
+        let request_ids = batch_request_ids();
+        if !request_ids.is_empty() {
+            let request_ids = request_ids.into_iter().map(Value::String).collect();
+            event.insert("requestIds".to_string(), Value::Array(request_ids));
+        }
+    }
+    EVENT_FIELDS.with(|fields| {
+        for (name, value) in fields.borrow().iter() {
+            event.insert(name.to_string(), value.clone());
+        }
+    });
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use log::Level;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn json_format() {
+        let mut event = json_event(
+            &Record::builder()
+                .args(format_args!("document addition done"))
+                .level(Level::Info)
+                .target("index_scheduler")
+                .build(),
+        );
+        assert!(event.remove("time").unwrap().is_string());
+        assert_eq!(
+            Value::Object(event),
+            json!({ "level": "INFO", "target": "index_scheduler", "message": "document addition done" })
+        );
+
+        // the events of a request are given its id and the fields of the event
+        BLOCKING_REQUEST_ID.with(|id| *id.borrow_mut() = Some(String::from("my-request")));
+        let mut event = Map::new();
+        log_with_fields(vec![("status", json!(200))], || {
+            event = json_event(
+                &Record::builder()
+                    .args(format_args!("GET /health"))
+                    .level(Level::Info)
+                    .target("meilisearch")
+                    .build(),
+            );
+        });
+        BLOCKING_REQUEST_ID.with(|id| *id.borrow_mut() = None);
+        event.remove("time");
+        assert_eq!(
+            Value::Object(event),
+            json!({
+                "level": "INFO",
+                "target": "meilisearch",
+                "message": "GET /health",
+                "requestId": "my-request",
+                "status": 200,
+            })
+        );
+    }
+
+    #[test]
+    fn blocking_request_id_is_reset_after_a_panic() {
+        let runtime =
+            tokio::runtime::Builder::new_current_thread().max_blocking_threads(1).build().unwrap();
+        runtime.block_on(async {
+            let result = scope_request_id(String::from("my-request"), async {
+                spawn_blocking(|| panic!("the blocking task panicked")).await
+            })
+            .await;
+            assert!(result.is_err());
+
+            // the next task runs on the same blocking thread
+            assert_eq!(spawn_blocking(request_id).await.unwrap(), None);
+        });
+    }
+}
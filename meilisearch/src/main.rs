@@ -2,14 +2,21 @@ use std::env;
 use std::io::{stderr, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
+#[cfg(unix)]
+use std::time::Duration;
 
 use actix_web::http::KeepAlive;
+#[cfg(unix)]
+use actix_web::rt::signal::unix::{signal, SignalKind};
+#[cfg(unix)]
+use actix_web::rt::task::spawn_blocking;
 use actix_web::web::Data;
 use actix_web::HttpServer;
 use index_scheduler::IndexScheduler;
 use is_terminal::IsTerminal;
 use meilisearch::analytics::Analytics;
-use meilisearch::{analytics, create_app, prototype_name, setup_meilisearch, Opt};
+use meilisearch::option::LogFormat;
+use meilisearch::{analytics, create_app, logging, prototype_name, setup_meilisearch, Opt};
 use meilisearch_auth::{generate_master_key, AuthController, MASTER_KEY_MIN_SIZE};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
@@ -20,6 +27,9 @@ static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 fn setup(opt: &Opt) -> anyhow::Result<()> {
     let mut log_builder = env_logger::Builder::new();
     log_builder.parse_filters(&opt.log_level.to_string());
+    if opt.log_format == LogFormat::Json {
+        log_builder.format(logging::format_json);
+    }
 
     log_builder.init();
 
@@ -90,6 +100,8 @@ async fn run_http(
 ) -> anyhow::Result<()> {
     let enable_dashboard = &opt.env == "development";
     let opt_clone = opt.clone();
+    #[cfg(unix)]
+    let scheduler = index_scheduler.clone();
     let index_scheduler = Data::from(index_scheduler);
     let auth_controller = Data::from(auth_controller);
 
@@ -102,15 +114,42 @@ async fn run_http(
             enable_dashboard,
         )
     })
-    // Disable signals allows the server to terminate immediately when a user enter CTRL-C
+    // Disable signals allows the server to terminate immediately when a user enter CTRL-C,
+    // SIGTERM is handled below to let the processing tasks finish.
     .disable_signals()
     .keep_alive(KeepAlive::Os);
 
-    if let Some(config) = opt_clone.get_ssl_config()? {
-        http_server.bind_rustls(opt_clone.http_addr, config)?.run().await?;
+    // HTTP/2 is negotiated with ALPN over TLS, and detected from the prior knowledge of the
+    // clients otherwise, the other clients keep using HTTP/1.1.
+    let server = if let Some(config) = opt_clone.get_ssl_config()? {
+        http_server.bind_rustls(opt_clone.http_addr, config)?.run()
     } else {
-        http_server.bind(&opt_clone.http_addr)?.run().await?;
+        http_server.bind_auto_h2c(&opt_clone.http_addr)?.run()
+    };
+
+    #[cfg(unix)]
+    {
+        let shutdown_grace_period = Duration::from_secs(opt_clone.shutdown_grace_period_sec);
+        let mut sigterm = signal(SignalKind::terminate())?;
+        let server_handle = server.handle();
+        actix_web::rt::spawn(async move {
+            sigterm.recv().await;
+            log::info!(
+                "Shutting down, waiting up to {}s for the processing tasks to finish.",
+                shutdown_grace_period.as_secs()
+            );
+            // the searches are still served while the processing tasks finish
+            let drained = spawn_blocking(move || scheduler.shutdown(shutdown_grace_period)).await;
+            let drained = drained.unwrap_or_else(|e| {
+                log::error!("Failed to wait for the processing tasks: {e}");
+                false
+            });
+            // the process exits once the server is stopped, even if some tasks are still processing
+            server_handle.stop(drained).await;
+        });
     }
+
+    server.await?;
     Ok(())
 }
 
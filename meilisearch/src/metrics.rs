@@ -30,6 +30,11 @@ lazy_static! {
         "Meilisearch Used DB Size In Bytes"
     ))
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_PENDING_PAYLOADS_SIZE_BYTES: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_pending_payloads_size_bytes",
+        "Meilisearch Pending Payloads Size In Bytes"
+    ))
+    .expect("Can't create a metric");
     pub static ref MEILISEARCH_INDEX_COUNT: IntGauge =
         register_int_gauge!(opts!("meilisearch_index_count", "Meilisearch Index Count"))
             .expect("Can't create a metric");
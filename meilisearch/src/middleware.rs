@@ -1,11 +1,29 @@
 //! Contains all the custom middleware used in meilisearch
 
+use std::error::Error as StdError;
 use std::future::{ready, Ready};
+use std::io::{self, Write};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Instant;
 
+use actix_web::body::{BodySize, EitherBody, MessageBody};
 use actix_web::dev::{self, Service, ServiceRequest, ServiceResponse, Transform};
-use actix_web::Error;
+use actix_web::http::header::{
+    self, AcceptEncoding, ContentEncoding, Encoding, HeaderName, HeaderValue,
+};
+use actix_web::http::StatusCode;
+use actix_web::{Error, HttpMessage};
+use bytes::Bytes;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use futures_util::future::LocalBoxFuture;
+use pin_project_lite::pin_project;
 use prometheus::HistogramTimer;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::logging;
 
 pub struct RouteMetrics;
 
@@ -73,3 +91,292 @@ where
         })
     }
 }
+
+/// Compresses the responses of the search and documents routes with gzip or brotli,
+/// following the `Accept-Encoding` header of the request.
+///
+/// The bodies are compressed as they are streamed, the ones smaller than the minimum size are
+/// sent as is and are not compressed by the [`Compress`](actix_web::middleware::Compress)
+/// middleware of the other routes either.
+pub struct ResponseCompression {
+    min_size: u64,
+    level: u32,
+}
+
+impl ResponseCompression {
+    pub fn new(min_size: u64, level: u32) -> Self {
+        ResponseCompression { min_size, level }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ResponseCompression
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<CompressedBody<B>, B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ResponseCompressionMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ResponseCompressionMiddleware {
+            service,
+            min_size: self.min_size,
+            level: self.level,
+        }))
+    }
+}
+
+pub struct ResponseCompressionMiddleware<S> {
+    service: S,
+    min_size: u64,
+    level: u32,
+}
+
+impl<S, B> Service<ServiceRequest> for ResponseCompressionMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<CompressedBody<B>, B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let encoding = req.get_header::<AcceptEncoding>().and_then(|accept_encoding| {
+            match accept_encoding.negotiate([Encoding::brotli(), Encoding::gzip()].iter()) {
+                Some(Encoding::Known(ContentEncoding::Brotli)) => Some(ContentEncoding::Brotli),
+                Some(Encoding::Known(ContentEncoding::Gzip)) => Some(ContentEncoding::Gzip),
+                _ => None,
+            }
+        });
+        let (min_size, level) = (self.min_size, self.level);
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+
+            let compressible = res.request().match_pattern().map_or(false, |pattern| {
+                pattern == "/multi-search"
+                    || (pattern.starts_with("/indexes/")
+                        && matches!(pattern.split('/').nth(3), Some("search" | "documents")))
+            });
+            let status = res.status();
+            if !compressible
+                || res.headers().contains_key(header::CONTENT_ENCODING)
+                || status == StatusCode::NO_CONTENT
+                || status == StatusCode::SWITCHING_PROTOCOLS
+            {
+                return Ok(res.map_into_right_body());
+            }
+
+            let encoding = match (encoding, res.response().body().size()) {
+                (_, BodySize::None) => None,
+                (_, BodySize::Sized(size)) if size < min_size => None,
+                (encoding, _) => encoding,
+            };
+            let Some(encoding) = encoding else {
+                // prevents the compression of the response by the outer middlewares
+                res.headers_mut()
+                    .insert(header::CONTENT_ENCODING, ContentEncoding::Identity.to_header_value());
+                return Ok(res.map_into_right_body());
+            };
+
+            let headers = res.headers_mut();
+            headers.insert(header::CONTENT_ENCODING, encoding.to_header_value());
+            headers.remove(header::CONTENT_LENGTH);
+            headers.append(header::VARY, HeaderValue::from_static("accept-encoding"));
+            Ok(res
+                .map_body(|_, body| CompressedBody::new(body, encoding, level))
+                .map_into_left_body())
+        })
+    }
+}
+
+pin_project! {
+    /// A body compressed chunk by chunk.
+    pub struct CompressedBody<B> {
+        #[pin]
+        body: B,
+        // `None` once the body is entirely compressed.
+        encoder: Option<Encoder>,
+    }
+}
+
+impl<B> CompressedBody<B> {
+    fn new(body: B, encoding: ContentEncoding, level: u32) -> Self {
+        let encoder = match encoding {
+            ContentEncoding::Brotli => Encoder::Brotli(Box::new(brotli::CompressorWriter::new(
+                Vec::new(),
+                BROTLI_BUFFER_SIZE,
+                level,
+                BROTLI_WINDOW_SIZE,
+            ))),
+            _ => Encoder::Gzip(GzEncoder::new(Vec::new(), Compression::new(level))),
+        };
+        CompressedBody { body, encoder: Some(encoder) }
+    }
+}
+
+const BROTLI_BUFFER_SIZE: usize = 32 * 1024;
+const BROTLI_WINDOW_SIZE: u32 = 22;
+
+enum Encoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+}
+
+impl Encoder {
+    /// Compresses the chunk and returns the compressed bytes that are ready to be sent.
+    fn write(&mut self, chunk: &[u8]) -> io::Result<Bytes> {
+        let output = match self {
+            Encoder::Gzip(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.get_mut()
+            }
+            Encoder::Brotli(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.get_mut()
+            }
+        };
+        Ok(Bytes::from(std::mem::take(output)))
+    }
+
+    /// Returns the remaining compressed bytes.
+    fn finish(self) -> io::Result<Bytes> {
+        let output = match self {
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            Encoder::Brotli(mut encoder) => {
+                encoder.flush()?;
+                encoder.into_inner()
+            }
+        };
+        Ok(Bytes::from(output))
+    }
+}
+
+impl<B: MessageBody> MessageBody for CompressedBody<B> {
+    type Error = Box<dyn StdError>;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let mut this = self.project();
+        loop {
+            let Some(encoder) = this.encoder.as_mut() else { return Poll::Ready(None) };
+            match ready!(this.body.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => {
+                    let bytes = encoder.write(&chunk)?;
+                    // the encoder may keep the chunk until it has enough bytes to compress
+                    if !bytes.is_empty() {
+                        return Poll::Ready(Some(Ok(bytes)));
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                None => {
+                    let bytes = this.encoder.take().unwrap().finish()?;
+                    return Poll::Ready(Some(Ok(bytes)));
+                }
+            }
+        }
+    }
+}
+
+/// Gives an id to each request, taken from its `X-Request-Id` header or generated, that is
+/// added to the log events emitted while handling it and sent back in the response headers.
+///
+/// When enabled, the end of each request is logged with its status and duration.
+pub struct RequestId {
+    log_requests: bool,
+}
+
+impl RequestId {
+    pub fn new(log_requests: bool) -> Self {
+        RequestId { log_requests }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestIdMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddleware { service, log_requests: self.log_requests }))
+    }
+}
+
+pub struct RequestIdMiddleware<S> {
+    service: S,
+    log_requests: bool,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = req
+            .headers()
+            .get(logging::REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH)
+            .map_or_else(|| Uuid::new_v4().to_string(), ToOwned::to_owned);
+        let method = req.method().to_string();
+        let path = req.path().to_string();
+        let log_requests = self.log_requests;
+        let started_at = Instant::now();
+
+        let fut = self.service.call(req);
+        Box::pin(logging::scope_request_id(request_id.clone(), async move {
+            let mut res = fut.await?;
+
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(logging::REQUEST_ID_HEADER), value);
+            }
+            if log_requests {
+                let status = res.status().as_u16();
+                let duration = started_at.elapsed();
+                logging::log_with_fields(
+                    vec![
+                        ("method", json!(method)),
+                        ("path", json!(path)),
+                        ("status", json!(status)),
+                        ("durationMs", json!(duration.as_secs_f64() * 1000.0)),
+                    ],
+                    || log::info!("{method} {path} {status} {duration:.2?}"),
+                );
+            }
+            Ok(res)
+        }))
+    }
+}
+
+const MAX_REQUEST_ID_LENGTH: usize = 128;
@@ -13,7 +13,8 @@ use std::{env, fmt, fs};
 use byte_unit::{Byte, ByteError};
 use clap::Parser;
 use meilisearch_types::features::InstanceTogglableFeatures;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::update::{DuplicateIdsPolicy, IndexerConfig};
+use rayon::ThreadPool;
 use rustls::server::{
     AllowAnyAnonymousOrAuthenticatedClient, AllowAnyAuthenticatedClient, ServerSessionMemoryCache,
 };
@@ -31,6 +32,10 @@ const MEILI_ENV: &str = "MEILI_ENV";
 #[cfg(feature = "analytics")]
 const MEILI_NO_ANALYTICS: &str = "MEILI_NO_ANALYTICS";
 const MEILI_HTTP_PAYLOAD_SIZE_LIMIT: &str = "MEILI_HTTP_PAYLOAD_SIZE_LIMIT";
+const MEILI_MAX_INDEXING_PAYLOAD_SIZE: &str = "MEILI_MAX_INDEXING_PAYLOAD_SIZE";
+const MEILI_ALLOW_PRIVATE_DOCUMENTS_URLS: &str = "MEILI_ALLOW_PRIVATE_DOCUMENTS_URLS";
+const MEILI_HTTP_COMPRESSION_MIN_SIZE: &str = "MEILI_HTTP_COMPRESSION_MIN_SIZE";
+const MEILI_HTTP_COMPRESSION_LEVEL: &str = "MEILI_HTTP_COMPRESSION_LEVEL";
 const MEILI_SSL_CERT_PATH: &str = "MEILI_SSL_CERT_PATH";
 const MEILI_SSL_KEY_PATH: &str = "MEILI_SSL_KEY_PATH";
 const MEILI_SSL_AUTH_PATH: &str = "MEILI_SSL_AUTH_PATH";
@@ -47,23 +52,43 @@ const MEILI_IMPORT_DUMP: &str = "MEILI_IMPORT_DUMP";
 const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
 const MEILI_DUMP_DIR: &str = "MEILI_DUMP_DIR";
+const MEILI_TASK_RETENTION_DAYS: &str = "MEILI_TASK_RETENTION_DAYS";
+const MEILI_TASK_RETENTION_COUNT: &str = "MEILI_TASK_RETENTION_COUNT";
+const MEILI_SHUTDOWN_GRACE_PERIOD_SEC: &str = "MEILI_SHUTDOWN_GRACE_PERIOD_SEC";
 const MEILI_LOG_LEVEL: &str = "MEILI_LOG_LEVEL";
+const MEILI_LOG_FORMAT: &str = "MEILI_LOG_FORMAT";
 const MEILI_EXPERIMENTAL_ENABLE_METRICS: &str = "MEILI_EXPERIMENTAL_ENABLE_METRICS";
 const MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE: &str =
     "MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE";
+const MEILI_EXPERIMENTAL_SEARCH_ANALYTICS: &str = "MEILI_EXPERIMENTAL_SEARCH_ANALYTICS";
+const MEILI_EXPERIMENTAL_QUERY_SUGGESTIONS: &str = "MEILI_EXPERIMENTAL_QUERY_SUGGESTIONS";
+const MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE: &str = "MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE";
+const MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL_SEC: &str = "MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL_SEC";
+const MEILI_EXPERIMENTAL_MAX_SEARCH_THREADS: &str = "MEILI_EXPERIMENTAL_MAX_SEARCH_THREADS";
+const MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES: &str = "MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES";
 
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
 const DEFAULT_ENV: &str = "development";
 const DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT: &str = "100 MB";
+const DEFAULT_HTTP_COMPRESSION_MIN_SIZE: &str = "1 KiB";
+const DEFAULT_HTTP_COMPRESSION_LEVEL: u32 = 6;
 const DEFAULT_SNAPSHOT_DIR: &str = "snapshots/";
 const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_SNAPSHOT_INTERVAL_SEC_STR: &str = "86400";
 const DEFAULT_DUMP_DIR: &str = "dumps/";
+const DEFAULT_SHUTDOWN_GRACE_PERIOD_SEC: u64 = 30;
+const MAX_TASK_RETENTION_DAYS: u64 = 36_500;
+const DEFAULT_SEARCH_CACHE_TTL_SEC: u64 = 60;
+const DEFAULT_MAX_SEARCH_THREADS: usize = 1;
+const DEFAULT_MAX_CONCURRENT_BATCHES: usize = 1;
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
+const MEILI_MAX_DOCUMENT_FIELDS: &str = "MEILI_MAX_DOCUMENT_FIELDS";
+const MEILI_MAX_DOCUMENT_DEPTH: &str = "MEILI_MAX_DOCUMENT_DEPTH";
+const MEILI_DUPLICATE_IDS_POLICY: &str = "MEILI_DUPLICATE_IDS_POLICY";
 const DEFAULT_LOG_EVERY_N: usize = 100_000;
 
 // Each environment (index and task-db) is taking space in the virtual address space.
@@ -131,6 +156,52 @@ impl FromStr for LogLevel {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct LogFormatError {
+    pub given_log_format: String,
+}
+
+impl Display for LogFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Log format '{}' is invalid. Accepted values are 'human' and 'json'.",
+            self.given_log_format
+        )
+    }
+}
+
+impl Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Human => Display::fmt("human", f),
+            LogFormat::Json => Display::fmt("json", f),
+        }
+    }
+}
+
+impl std::error::Error for LogFormatError {}
+
+impl FromStr for LogFormat {
+    type Err = LogFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "human" => Ok(LogFormat::Human),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(LogFormatError { given_log_format: s.to_owned() }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Parser, Deserialize)]
 #[clap(version, next_display_order = None)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
@@ -181,6 +252,33 @@ pub struct Opt {
     #[serde(default = "default_http_payload_size_limit")]
     pub http_payload_size_limit: Byte,
 
+    /// Sets the maximum size of the documents payloads. It is enforced on the raw bytes, while the
+    /// request body is received and while the documents are downloaded from an url.
+    /// Defaults to the `--http-payload-size-limit`. Value must be given in bytes or explicitly stating
+    /// a base unit (for instance: 107374182400, '107.7Gb', or '107374 Mb').
+    #[clap(long, env = MEILI_MAX_INDEXING_PAYLOAD_SIZE)]
+    pub max_indexing_payload_size: Option<Byte>,
+
+    /// Allows downloading the documents from the urls whose host resolves to a loopback, private
+    /// or link-local address. These urls are refused by default so that the documents additions
+    /// can't reach the services of the private network.
+    #[clap(long, env = MEILI_ALLOW_PRIVATE_DOCUMENTS_URLS)]
+    #[serde(default)]
+    pub allow_private_documents_urls: bool,
+
+    /// Sets the minimum size of the search and documents responses compressed with gzip or brotli,
+    /// following the `Accept-Encoding` header of the request. Value must be given in bytes or explicitly
+    /// stating a base unit (for instance: 1024, '1 KiB', or '10 Kb').
+    #[clap(long, env = MEILI_HTTP_COMPRESSION_MIN_SIZE, default_value_t = default_http_compression_min_size())]
+    #[serde(default = "default_http_compression_min_size")]
+    pub http_compression_min_size: Byte,
+
+    /// Sets the level of compression of the search and documents responses, from 1 for the fastest
+    /// compression to 9 for the smallest responses.
+    #[clap(long, env = MEILI_HTTP_COMPRESSION_LEVEL, default_value_t = default_http_compression_level(), value_parser = clap::value_parser!(u32).range(1..=9))]
+    #[serde(default = "default_http_compression_level")]
+    pub http_compression_level: u32,
+
     /// Sets the server's SSL certificates.
     #[clap(long, env = MEILI_SSL_CERT_PATH, value_parser)]
     pub ssl_cert_path: Option<PathBuf>,
@@ -282,6 +380,27 @@ pub struct Opt {
     #[serde(default = "default_dump_dir")]
     pub dump_dir: PathBuf,
 
+    /// Deletes the finished tasks older than the given number of days, at most 36500 days.
+    /// Finished tasks are kept by default.
+    ///
+    /// The tasks are deleted by the scheduler when no task is enqueued or processing.
+    #[clap(long, env = MEILI_TASK_RETENTION_DAYS, value_parser = clap::value_parser!(u64).range(1..=MAX_TASK_RETENTION_DAYS))]
+    pub task_retention_days: Option<u64>,
+
+    /// Sets the maximum number of finished tasks to keep, the oldest finished tasks beyond it are deleted.
+    ///
+    /// The tasks are deleted by the scheduler when no task is enqueued or processing.
+    #[clap(long, env = MEILI_TASK_RETENTION_COUNT)]
+    pub task_retention_count: Option<u64>,
+
+    /// Sets the number of seconds the processing tasks are given to finish when Meilisearch receives
+    /// a SIGTERM signal, the write operations are refused in the meantime.
+    ///
+    /// The tasks still processing afterward are aborted and processed again at the next start.
+    #[clap(long, env = MEILI_SHUTDOWN_GRACE_PERIOD_SEC, default_value_t = default_shutdown_grace_period_sec())]
+    #[serde(default = "default_shutdown_grace_period_sec")]
+    pub shutdown_grace_period_sec: u64,
+
     /// Defines how much detail should be present in Meilisearch's logs.
     ///
     /// Meilisearch currently supports six log levels, listed in order of increasing verbosity: OFF, ERROR, WARN, INFO, DEBUG, TRACE.
@@ -289,6 +408,14 @@ pub struct Opt {
     #[serde(default)]
     pub log_level: LogLevel,
 
+    /// Defines the format of Meilisearch's logs, either `human` or `json`.
+    ///
+    /// With the `json` format, each log event is written as a JSON object on a single line with the id of
+    /// the HTTP request it belongs to, and the end of each request is logged with its status and duration.
+    #[clap(long, env = MEILI_LOG_FORMAT, default_value_t)]
+    #[serde(default)]
+    pub log_format: LogFormat,
+
     /// Experimental metrics feature. For more information, see: <https://github.com/meilisearch/meilisearch/discussions/3518>
     ///
     /// Enables the Prometheus metrics on the `GET /metrics` endpoint.
@@ -301,6 +428,54 @@ pub struct Opt {
     #[serde(default)]
     pub experimental_reduce_indexing_memory_usage: bool,
 
+    /// Experimental search analytics feature.
+    ///
+    /// Records the searches made on each index and enables the `/indexes/{indexUid}/search-analytics` routes.
+    /// The recorded searches are kept in memory and lost when Meilisearch restarts.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_ANALYTICS)]
+    #[serde(default)]
+    pub experimental_search_analytics: bool,
+
+    /// Experimental query suggestions feature.
+    ///
+    /// Records the queries of the searches returning hits on each index and enables the
+    /// `/indexes/{indexUid}/suggestions` route, which completes a prefix with the recorded queries.
+    #[clap(long, env = MEILI_EXPERIMENTAL_QUERY_SUGGESTIONS)]
+    #[serde(default)]
+    pub experimental_query_suggestions: bool,
+
+    /// Experimental search cache feature.
+    ///
+    /// Sets the maximum number of search responses kept in memory to answer the identical repeated searches.
+    /// The responses of an index are invalidated as soon as its documents or settings are modified.
+    /// The cache is disabled when set to 0, which is the default.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE, default_value_t)]
+    #[serde(default)]
+    pub experimental_search_cache_size: usize,
+
+    /// Sets the duration, in seconds, after which a cached search response is considered outdated.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL_SEC, default_value_t = default_search_cache_ttl_sec())]
+    #[serde(default = "default_search_cache_ttl_sec")]
+    pub experimental_search_cache_ttl_sec: u64,
+
+    /// Experimental parallel search feature.
+    ///
+    /// Sets the number of threads shared by the searches to compute their facet distributions and
+    /// the documents of their words in parallel, allowing a single search to use multiple cores.
+    /// The searches are not parallelized when set to 1, which is the default.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_SEARCH_THREADS, default_value_t = default_max_search_threads())]
+    #[serde(default = "default_max_search_threads")]
+    pub experimental_max_search_threads: usize,
+
+    /// Experimental concurrent indexing feature.
+    ///
+    /// Sets the maximum number of batches of tasks processed at the same time, each on a distinct index,
+    /// so that a long indexing on an index doesn't delay the small updates of the other indexes.
+    /// The tasks are processed one batch at a time when set to 1, which is the default.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES, default_value_t = default_max_concurrent_batches())]
+    #[serde(default = "default_max_concurrent_batches")]
+    pub experimental_max_concurrent_batches: usize,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
@@ -371,6 +546,10 @@ impl Opt {
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
+            max_indexing_payload_size,
+            allow_private_documents_urls,
+            http_compression_min_size,
+            http_compression_level,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -381,7 +560,11 @@ impl Opt {
             snapshot_dir,
             schedule_snapshot,
             dump_dir,
+            task_retention_days,
+            task_retention_count,
+            shutdown_grace_period_sec,
             log_level,
+            log_format,
             indexer_options,
             import_snapshot: _,
             ignore_missing_snapshot: _,
@@ -394,6 +577,12 @@ impl Opt {
             no_analytics,
             experimental_enable_metrics: enable_metrics_route,
             experimental_reduce_indexing_memory_usage: reduce_indexing_memory_usage,
+            experimental_search_analytics,
+            experimental_query_suggestions,
+            experimental_search_cache_size,
+            experimental_search_cache_ttl_sec,
+            experimental_max_search_threads,
+            experimental_max_concurrent_batches,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
         export_to_env_if_not_present(MEILI_HTTP_ADDR, http_addr);
@@ -409,6 +598,24 @@ impl Opt {
             MEILI_HTTP_PAYLOAD_SIZE_LIMIT,
             http_payload_size_limit.to_string(),
         );
+        if let Some(max_indexing_payload_size) = max_indexing_payload_size {
+            export_to_env_if_not_present(
+                MEILI_MAX_INDEXING_PAYLOAD_SIZE,
+                max_indexing_payload_size.to_string(),
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_ALLOW_PRIVATE_DOCUMENTS_URLS,
+            allow_private_documents_urls.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_HTTP_COMPRESSION_MIN_SIZE,
+            http_compression_min_size.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_HTTP_COMPRESSION_LEVEL,
+            http_compression_level.to_string(),
+        );
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -430,7 +637,24 @@ impl Opt {
         }
 
         export_to_env_if_not_present(MEILI_DUMP_DIR, dump_dir);
+        if let Some(task_retention_days) = task_retention_days {
+            export_to_env_if_not_present(
+                MEILI_TASK_RETENTION_DAYS,
+                task_retention_days.to_string(),
+            );
+        }
+        if let Some(task_retention_count) = task_retention_count {
+            export_to_env_if_not_present(
+                MEILI_TASK_RETENTION_COUNT,
+                task_retention_count.to_string(),
+            );
+        }
+        export_to_env_if_not_present(
+            MEILI_SHUTDOWN_GRACE_PERIOD_SEC,
+            shutdown_grace_period_sec.to_string(),
+        );
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level.to_string());
+        export_to_env_if_not_present(MEILI_LOG_FORMAT, log_format.to_string());
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_ENABLE_METRICS,
             enable_metrics_route.to_string(),
@@ -439,10 +663,43 @@ impl Opt {
             MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE,
             reduce_indexing_memory_usage.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SEARCH_ANALYTICS,
+            experimental_search_analytics.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_QUERY_SUGGESTIONS,
+            experimental_query_suggestions.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE,
+            experimental_search_cache_size.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL_SEC,
+            experimental_search_cache_ttl_sec.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_SEARCH_THREADS,
+            experimental_max_search_threads.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES,
+            experimental_max_concurrent_batches.to_string(),
+        );
         indexer_options.export_to_env();
     }
 
     pub fn get_ssl_config(&self) -> anyhow::Result<Option<rustls::ServerConfig>> {
+        if self.ssl_cert_path.is_some() != self.ssl_key_path.is_some() {
+            anyhow::bail!("`--ssl-cert-path` and `--ssl-key-path` must be used together");
+        }
+        if self.ssl_require_auth && self.ssl_auth_path.is_none() {
+            anyhow::bail!(
+                "`--ssl-require-auth` requires the certificate authorities of `--ssl-auth-path`"
+            );
+        }
+
         if let (Some(cert_path), Some(key_path)) = (&self.ssl_cert_path, &self.ssl_key_path) {
             let config = rustls::ServerConfig::builder().with_safe_defaults();
 
@@ -451,7 +708,9 @@ impl Opt {
                     let roots = load_certs(auth_path.to_path_buf())?;
                     let mut client_auth_roots = RootCertStore::empty();
                     for root in roots {
-                        client_auth_roots.add(&root).unwrap();
+                        client_auth_roots.add(&root).map_err(|e| {
+                            anyhow::anyhow!("invalid certificate authority in {auth_path:?}: {e}")
+                        })?;
                     }
                     if self.ssl_require_auth {
                         let verifier = AllowAnyAuthenticatedClient::new(client_auth_roots);
@@ -489,7 +748,29 @@ impl Opt {
     }
 
     pub(crate) fn to_instance_features(&self) -> InstanceTogglableFeatures {
-        InstanceTogglableFeatures { metrics: self.experimental_enable_metrics }
+        InstanceTogglableFeatures {
+            metrics: self.experimental_enable_metrics,
+            search_analytics: self.experimental_search_analytics,
+            query_suggestions: self.experimental_query_suggestions,
+        }
+    }
+
+    /// The maximum size, in bytes, of the documents payloads.
+    pub(crate) fn indexing_payload_size_limit(&self) -> u64 {
+        self.max_indexing_payload_size.unwrap_or(self.http_payload_size_limit).get_bytes() as u64
+    }
+
+    /// Builds the threads shared by the searches, `None` when the searches are not parallelized.
+    pub(crate) fn search_thread_pool(&self) -> anyhow::Result<Option<ThreadPool>> {
+        if self.experimental_max_search_threads <= 1 {
+            return Ok(None);
+        }
+
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|index| format!("search-thread:{index}"))
+            .num_threads(self.experimental_max_search_threads)
+            .build()?;
+        Ok(Some(thread_pool))
     }
 }
 
@@ -508,6 +789,28 @@ pub struct IndexerOpts {
     #[serde(default)]
     pub max_indexing_threads: MaxThreads,
 
+    /// Sets the maximum number of fields a document can contain once its nested objects are
+    /// flattened. The documents over the limit are rejected. By default, there is no limit
+    /// other than the 65,535 fields of an index.
+    #[clap(long, env = MEILI_MAX_DOCUMENT_FIELDS)]
+    #[serde(default)]
+    pub max_document_fields: Option<usize>,
+
+    /// Sets the maximum number of levels of nested objects a document can contain, a document
+    /// without any nested object has a single level. The documents over the limit are rejected.
+    /// By default, there is no limit.
+    #[clap(long, env = MEILI_MAX_DOCUMENT_DEPTH)]
+    #[serde(default)]
+    pub max_document_depth: Option<usize>,
+
+    /// Sets what to do with the documents sharing the same id within a single payload:
+    /// `last-wins` indexes the last one, `first-wins` indexes the first one, and `reject`
+    /// fails the addition with an error listing the duplicated ids.
+    /// It can be overridden for a single addition with the `duplicateIdsPolicy` query parameter.
+    #[clap(long, env = MEILI_DUPLICATE_IDS_POLICY, default_value_t)]
+    #[serde(default)]
+    pub duplicate_ids_policy: DuplicateIdsPolicy,
+
     /// Whether or not we want to determine the budget of virtual memory address space we have available dynamically
     /// (the default), or statically.
     ///
@@ -521,7 +824,14 @@ pub struct IndexerOpts {
 impl IndexerOpts {
     /// Exports the values to their corresponding env vars if they are not set.
     pub fn export_to_env(self) {
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } = self;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            max_document_fields,
+            max_document_depth,
+            duplicate_ids_policy,
+            skip_index_budget: _,
+        } = self;
         if let Some(max_indexing_memory) = max_indexing_memory.0 {
             export_to_env_if_not_present(
                 MEILI_MAX_INDEXING_MEMORY,
@@ -532,6 +842,16 @@ impl IndexerOpts {
             MEILI_MAX_INDEXING_THREADS,
             max_indexing_threads.0.to_string(),
         );
+        if let Some(max_document_fields) = max_document_fields {
+            export_to_env_if_not_present(
+                MEILI_MAX_DOCUMENT_FIELDS,
+                max_document_fields.to_string(),
+            );
+        }
+        if let Some(max_document_depth) = max_document_depth {
+            export_to_env_if_not_present(MEILI_MAX_DOCUMENT_DEPTH, max_document_depth.to_string());
+        }
+        export_to_env_if_not_present(MEILI_DUPLICATE_IDS_POLICY, duplicate_ids_policy.to_string());
     }
 }
 
@@ -549,6 +869,9 @@ impl TryFrom<&IndexerOpts> for IndexerConfig {
             max_memory: other.max_indexing_memory.map(|b| b.get_bytes() as usize),
             thread_pool: Some(thread_pool),
             max_positions_per_attributes: None,
+            max_document_fields: other.max_document_fields,
+            max_document_depth: other.max_document_depth,
+            duplicate_ids_policy: other.duplicate_ids_policy,
             skip_index_budget: other.skip_index_budget,
             ..Default::default()
         })
@@ -727,6 +1050,14 @@ fn default_http_payload_size_limit() -> Byte {
     Byte::from_str(DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT).unwrap()
 }
 
+fn default_http_compression_min_size() -> Byte {
+    Byte::from_str(DEFAULT_HTTP_COMPRESSION_MIN_SIZE).unwrap()
+}
+
+fn default_http_compression_level() -> u32 {
+    DEFAULT_HTTP_COMPRESSION_LEVEL
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
@@ -739,6 +1070,22 @@ fn default_dump_dir() -> PathBuf {
     PathBuf::from(DEFAULT_DUMP_DIR)
 }
 
+fn default_shutdown_grace_period_sec() -> u64 {
+    DEFAULT_SHUTDOWN_GRACE_PERIOD_SEC
+}
+
+fn default_search_cache_ttl_sec() -> u64 {
+    DEFAULT_SEARCH_CACHE_TTL_SEC
+}
+
+fn default_max_search_threads() -> usize {
+    DEFAULT_MAX_SEARCH_THREADS
+}
+
+fn default_max_concurrent_batches() -> usize {
+    DEFAULT_MAX_CONCURRENT_BATCHES
+}
+
 /// Indicates if a snapshot was scheduled, and if yes with which interval.
 #[derive(Debug, Default, Copy, Clone, Deserialize, Serialize)]
 pub enum ScheduleSnapshot {
@@ -845,6 +1192,27 @@ mod test {
         assert!(Opt::try_parse_from(Some("")).is_ok());
     }
 
+    #[test]
+    fn test_invalid_ssl_options() {
+        let opt = Opt::try_parse_from(["meilisearch", "--ssl-cert-path", "cert.pem"]).unwrap();
+        assert!(opt.get_ssl_config().is_err());
+
+        let opt = Opt::try_parse_from(["meilisearch", "--ssl-require-auth"]).unwrap();
+        assert!(opt.get_ssl_config().is_err());
+
+        let opt = Opt::try_parse_from(["meilisearch"]).unwrap();
+        assert!(opt.get_ssl_config().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_task_retention_days_range() {
+        let opt = Opt::try_parse_from(["meilisearch", "--task-retention-days", "36500"]).unwrap();
+        assert_eq!(opt.task_retention_days, Some(36_500));
+
+        assert!(Opt::try_parse_from(["meilisearch", "--task-retention-days", "36501"]).is_err());
+        assert!(Opt::try_parse_from(["meilisearch", "--task-retention-days", "0"]).is_err());
+    }
+
     #[test]
     #[ignore]
     fn test_meilli_config_file_path_valid() {
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use log::warn;
+use meilisearch_types::milli::reranker::RerankerConfig;
+use meilisearch_types::milli::DocumentId;
+use meilisearch_types::Document;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// The client shared by the searches to reach the rerankers, it keeps the connections alive.
+static CLIENT: Lazy<reqwest::blocking::Client> = Lazy::new(reqwest::blocking::Client::new);
+
+/// A hit sent to the reranker.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RerankerHit {
+    pub id: DocumentId,
+    pub ranking_score: f64,
+    pub document: Document,
+}
+
+#[derive(Debug, Serialize)]
+struct RerankerRequest<'a> {
+    query: &'a str,
+    hits: &'a [RerankerHit],
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankerResponse {
+    scores: Vec<f64>,
+}
+
+/// Sends the hits to the reranker and returns their positions sorted by decreasing score.
+///
+/// It blocks until the reranker answers and must be called from a blocking thread, like the searches.
+///
+/// Returns `None`, with a warning, when the reranker fails, does not answer within its time budget,
+/// or does not give a score to each hit, the hits must then keep their original order.
+pub fn rerank(reranker: &RerankerConfig, query: &str, hits: &[RerankerHit]) -> Option<Vec<usize>> {
+    let request = RerankerRequest { query, hits };
+    let response = CLIENT
+        .post(&reranker.url)
+        .timeout(Duration::from_millis(reranker.timeout_ms))
+        .json(&request)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.json::<RerankerResponse>());
+
+    let scores = match response {
+        Ok(RerankerResponse { scores }) if scores.len() == hits.len() => scores,
+        Ok(RerankerResponse { scores }) => {
+            warn!(
+                "Ignoring the reranker: it returned {} scores for {} hits.",
+                scores.len(),
+                hits.len()
+            );
+            return None;
+        }
+        Err(error) => {
+            warn!("Ignoring the reranker: {error}.");
+            return None;
+        }
+    };
+
+    // The sort is stable, the hits with the same score keep their original order.
+    let mut positions: Vec<_> = (0..hits.len()).collect();
+    positions.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+    Some(positions)
+}
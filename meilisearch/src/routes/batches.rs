@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::batches::{Batch, BatchId, BatchStats, IndexingStepDuration};
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::tasks::{serialize_duration, TaskId};
+use serde::Serialize;
+use serde_json::json;
+use time::{Duration, OffsetDateTime};
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+const DEFAULT_LIMIT: u32 = 20;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_batches))))
+        .service(web::resource("/{batch_id}").route(web::get().to(SeqHandler(get_batch))));
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchView {
+    uid: BatchId,
+    index_uid: Option<String>,
+    task_uids: Vec<TaskId>,
+    stats: BatchStatsView,
+    #[serde(serialize_with = "serialize_duration")]
+    duration: Option<Duration>,
+    #[serde(with = "time::serde::rfc3339")]
+    started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    finished_at: OffsetDateTime,
+}
+
+impl BatchView {
+    fn from_batch(batch: Batch) -> BatchView {
+        let Batch { uid, index_uid, task_uids, stats, started_at, finished_at } = batch;
+        BatchView {
+            uid,
+            index_uid,
+            task_uids,
+            stats: BatchStatsView::from(stats),
+            duration: Some(finished_at - started_at),
+            started_at,
+            finished_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStatsView {
+    total_nb_tasks: u32,
+    status: BTreeMap<String, u32>,
+    types: BTreeMap<String, u32>,
+    indexed_documents: u64,
+    deleted_documents: u64,
+    indexing_steps: Vec<IndexingStepView>,
+}
+
+impl From<BatchStats> for BatchStatsView {
+    fn from(stats: BatchStats) -> Self {
+        let BatchStats {
+            total_nb_tasks,
+            status,
+            types,
+            indexed_documents,
+            deleted_documents,
+            indexing_steps,
+        } = stats;
+        let indexing_steps = indexing_steps
+            .into_iter()
+            .map(|IndexingStepDuration { step, duration }| IndexingStepView {
+                step,
+                duration: Duration::try_from(duration).ok(),
+            })
+            .collect();
+
+        BatchStatsView {
+            total_nb_tasks,
+            status,
+            types,
+            indexed_documents,
+            deleted_documents,
+            indexing_steps,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingStepView {
+    step: String,
+    #[serde(serialize_with = "serialize_duration")]
+    duration: Option<Duration>,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct BatchesFilterQuery {
+    #[deserr(default = Param(DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidBatchLimit>)]
+    pub limit: Param<u32>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidBatchFrom>)]
+    pub from: Option<Param<BatchId>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AllBatches {
+    results: Vec<BatchView>,
+    limit: u32,
+    from: Option<u32>,
+    next: Option<u32>,
+}
+
+async fn get_batches(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    params: AwebQueryParameter<BatchesFilterQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let BatchesFilterQuery { limit, from } = params.into_inner();
+    analytics.publish("Batches Seen".to_string(), json!({ "per_batch_uid": false }), Some(&req));
+
+    // We +1 just to know if there is more after this "page" or not.
+    let limit = limit.0.saturating_add(1);
+    let filters = index_scheduler.filters();
+    let batches = index_scheduler.get_batches_from_authorized_indexes(
+        from.as_deref().copied(),
+        limit as usize,
+        filters,
+    )?;
+    let mut results: Vec<_> = batches.into_iter().map(BatchView::from_batch).collect();
+
+    // If we were able to fetch the number +1 batches we asked
+    // it means that there is more to come.
+    let next = if results.len() == limit as usize { results.pop().map(|b| b.uid) } else { None };
+
+    let from = results.first().map(|b| b.uid);
+    let batches = AllBatches { results, limit: limit.saturating_sub(1), from, next };
+
+    Ok(HttpResponse::Ok().json(batches))
+}
+
+async fn get_batch(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    batch_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let batch_uid_string = batch_uid.into_inner();
+
+    let batch_uid: BatchId = match batch_uid_string.parse() {
+        Ok(id) => id,
+        Err(_e) => {
+            return Err(
+                index_scheduler::Error::InvalidBatchUids { batch_uid: batch_uid_string }.into()
+            )
+        }
+    };
+
+    analytics.publish("Batches Seen".to_string(), json!({ "per_batch_uid": true }), Some(&req));
+
+    let filters = index_scheduler.filters();
+    let batch = index_scheduler.get_batch_from_authorized_indexes(batch_uid, filters)?;
+    Ok(HttpResponse::Ok().json(BatchView::from_batch(batch)))
+}
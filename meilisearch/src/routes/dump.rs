@@ -11,6 +11,7 @@ use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::logging;
 use crate::routes::SummarizedTaskView;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -29,8 +30,11 @@ pub async fn create_dump(
         keys: auth_controller.list_keys()?,
         instance_uid: analytics.instance_uid().cloned(),
     };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
@@ -40,10 +40,11 @@ async fn get_features(
 #[derive(Debug, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct RuntimeTogglableFeatures {
-    #[deserr(default)]
-    pub score_details: Option<bool>,
     #[deserr(default)]
     pub vector_store: Option<bool>,
+    /// Deprecated since the ranking score details are stable, the value is ignored.
+    #[deserr(default)]
+    pub score_details: Option<bool>,
 }
 
 async fn patch_features(
@@ -60,20 +61,17 @@ async fn patch_features(
     let old_features = features.runtime_features();
 
     let new_features = meilisearch_types::features::RuntimeTogglableFeatures {
-        score_details: new_features.0.score_details.unwrap_or(old_features.score_details),
         vector_store: new_features.0.vector_store.unwrap_or(old_features.vector_store),
     };
 
     // explicitly destructure for analytics rather than using the `Serialize` implementation, because
     // the it renames to camelCase, which we don't want for analytics.
     // **Do not** ignore fields with `..` or `_` here, because we want to add them in the future.
-    let meilisearch_types::features::RuntimeTogglableFeatures { score_details, vector_store } =
-        new_features;
+    let meilisearch_types::features::RuntimeTogglableFeatures { vector_store } = new_features;
 
     analytics.publish(
         "Experimental features Updated".to_string(),
         json!({
-            "score_details": score_details,
             "vector_store": vector_store,
         }),
         Some(&req),
@@ -0,0 +1,149 @@
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::{IndexScheduler, IndexTemplate};
+use log::debug;
+use meilisearch_auth::AuthFilter;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::InvalidIndexTemplateUidPattern;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
+use meilisearch_types::settings::{Settings, Unchecked};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(list_index_templates)))).service(
+        web::resource("/{name}")
+            .route(web::get().to(SeqHandler(get_index_template)))
+            .route(web::put().to(SeqHandler(put_index_template)))
+            .route(web::delete().to(SeqHandler(delete_index_template))),
+    );
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexTemplateView {
+    name: String,
+    uid_pattern: Option<IndexUidPattern>,
+    settings: Settings<Unchecked>,
+}
+
+impl IndexTemplateView {
+    fn new(name: String, template: IndexTemplate) -> Self {
+        let IndexTemplate { uid_pattern, settings } = template;
+        IndexTemplateView { name, uid_pattern, settings }
+    }
+}
+
+/// A template without pattern applies to every index, the API key must then allow access to all of them.
+fn is_template_authorized(filters: &AuthFilter, uid_pattern: Option<&IndexUidPattern>) -> bool {
+    match uid_pattern {
+        Some(uid_pattern) => filters.is_index_pattern_authorized(uid_pattern),
+        None => filters.all_indexes_authorized(),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct IndexTemplatesResults {
+    results: Vec<IndexTemplateView>,
+}
+
+pub async fn list_index_templates(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let templates = index_scheduler.index_templates()?;
+    let results = templates
+        .into_iter()
+        .map(|(name, template)| IndexTemplateView::new(name, template))
+        .collect();
+    let results = IndexTemplatesResults { results };
+
+    debug!("returns: {:?}", results);
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn get_index_template(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    name: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let name = name.into_inner();
+    let template = index_scheduler.index_template(&name)?;
+    let template = IndexTemplateView::new(name, template);
+
+    debug!("returns: {:?}", template);
+    Ok(HttpResponse::Ok().json(template))
+}
+
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct IndexTemplatePayload {
+    #[deserr(default, error = DeserrJsonError<InvalidIndexTemplateUidPattern>)]
+    uid_pattern: Option<IndexUidPattern>,
+    #[deserr(default)]
+    settings: Settings<Unchecked>,
+}
+
+/// Creates or replaces a template, its settings are given to the indexes created afterward
+/// with an uid matching its pattern.
+pub async fn put_index_template(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    name: web::Path<String>,
+    body: AwebJson<IndexTemplatePayload, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let name = name.into_inner();
+    let IndexTemplatePayload { uid_pattern, settings } = body.into_inner();
+    let settings = settings.validate()?;
+
+    let filters = index_scheduler.filters();
+    let replaced = match index_scheduler.index_template(&name) {
+        Ok(template) => Some(template),
+        Err(index_scheduler::Error::IndexTemplateNotFound(_)) => None,
+        Err(e) => return Err(e.into()),
+    };
+    if !is_template_authorized(filters, uid_pattern.as_ref())
+        || replaced.map_or(false, |template| {
+            !is_template_authorized(filters, template.uid_pattern.as_ref())
+        })
+    {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    analytics.publish(
+        "Index Template Updated".to_string(),
+        json!({ "uid_pattern": uid_pattern.is_some() }),
+        Some(&req),
+    );
+
+    let template = IndexTemplate { uid_pattern, settings };
+    index_scheduler.put_index_template(&name, &template)?;
+    let template = IndexTemplateView::new(name, template);
+
+    debug!("returns: {:?}", template);
+    Ok(HttpResponse::Ok().json(template))
+}
+
+pub async fn delete_index_template(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    name: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let template = index_scheduler.index_template(&name)?;
+    if !is_template_authorized(index_scheduler.filters(), template.uid_pattern.as_ref()) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    analytics.publish("Index Template Deleted".to_string(), json!({}), Some(&req));
+
+    index_scheduler.delete_index_template(&name)?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,4 +1,5 @@
 use std::io::ErrorKind;
+use std::str::FromStr;
 
 use actix_web::http::header::CONTENT_TYPE;
 use actix_web::web::Data;
@@ -11,13 +12,16 @@ use index_scheduler::IndexScheduler;
 use log::debug;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_csv, read_json, read_ndjson, read_parquet, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
-use meilisearch_types::milli::DocumentId;
+use meilisearch_types::milli::update::{DuplicateIdsPolicy, IndexDocumentsMethod};
+use meilisearch_types::milli::{AscDesc, DocumentId, Member, SortError};
+use meilisearch_types::serde_cs::vec::CS;
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::{milli, Document, Index};
@@ -36,11 +40,17 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::payload::Payload;
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::logging;
 use crate::routes::{PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
 use crate::search::parse_filter;
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+        "application/vnd.apache.parquet".to_string(),
+    ]
 });
 
 /// Extracts the mime type from the content type and return
@@ -71,6 +81,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(SeqHandler(get_documents)))
             .route(web::post().to(SeqHandler(replace_documents)))
             .route(web::put().to(SeqHandler(update_documents)))
+            .route(web::patch().to(SeqHandler(merge_documents)))
             .route(web::delete().to(SeqHandler(clear_all_documents))),
     )
     // these routes need to be before the /documents/{document_id} to match properly
@@ -79,6 +90,11 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     )
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
+    .service(
+        web::resource("/from-url")
+            .route(web::post().to(SeqHandler(replace_documents_from_url)))
+            .route(web::put().to(SeqHandler(update_documents_from_url))),
+    )
     .service(
         web::resource("/{document_id}")
             .route(web::get().to(SeqHandler(get_document)))
@@ -91,6 +107,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 pub struct GetDocument {
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFields>)]
     fields: OptionStarOrList<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentRetrieveVectors>)]
+    retrieve_vectors: Param<bool>,
 }
 
 pub async fn get_document(
@@ -105,11 +123,17 @@ pub async fn get_document(
 
     analytics.get_fetch_documents(&DocumentFetchKind::PerDocumentId, &req);
 
-    let GetDocument { fields } = params.into_inner();
+    let GetDocument { fields, retrieve_vectors } = params.into_inner();
     let attributes_to_retrieve = fields.merge_star_and_none();
+    if retrieve_vectors.0 {
+        index_scheduler
+            .features()?
+            .check_vector("Passing `retrieveVectors` as a query parameter")?;
+    }
 
     let index = index_scheduler.index(&index_uid)?;
-    let document = retrieve_document(&index, &document_id, attributes_to_retrieve)?;
+    let document =
+        retrieve_document(&index, &document_id, attributes_to_retrieve, retrieve_vectors.0)?;
     debug!("returns: {:?}", document);
     Ok(HttpResponse::Ok().json(document))
 }
@@ -129,8 +153,11 @@ pub async fn delete_document(
         index_uid: index_uid.to_string(),
         documents_ids: vec![document_id],
     };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
 }
@@ -146,6 +173,10 @@ pub struct BrowseQueryGet {
     fields: OptionStarOrList<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFilter>)]
     filter: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentSort>)]
+    sort: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentRetrieveVectors>)]
+    retrieve_vectors: Param<bool>,
 }
 
 #[derive(Debug, Deserr)]
@@ -159,6 +190,10 @@ pub struct BrowseQuery {
     fields: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
     filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentSort>)]
+    sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentRetrieveVectors>)]
+    retrieve_vectors: bool,
 }
 
 pub async fn documents_by_query_post(
@@ -175,6 +210,7 @@ pub async fn documents_by_query_post(
     analytics.post_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: body.filter.is_some(),
+            with_sort: body.sort.is_some(),
             limit: body.limit,
             offset: body.offset,
         },
@@ -193,7 +229,8 @@ pub async fn get_documents(
 ) -> Result<HttpResponse, ResponseError> {
     debug!("called with params: {:?}", params);
 
-    let BrowseQueryGet { limit, offset, fields, filter } = params.into_inner();
+    let BrowseQueryGet { limit, offset, fields, filter, sort, retrieve_vectors } =
+        params.into_inner();
 
     let filter = match filter {
         Some(f) => match serde_json::from_str(&f) {
@@ -208,11 +245,14 @@ pub async fn get_documents(
         limit: limit.0,
         fields: fields.merge_star_and_none(),
         filter,
+        sort: sort.map(|sort| sort.into_iter().collect()),
+        retrieve_vectors: retrieve_vectors.0,
     };
 
     analytics.get_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: query.filter.is_some(),
+            with_sort: query.sort.is_some(),
             limit: query.limit,
             offset: query.offset,
         },
@@ -228,10 +268,14 @@ fn documents_by_query(
     query: BrowseQuery,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, filter } = query;
+    let BrowseQuery { offset, limit, fields, filter, sort, retrieve_vectors } = query;
+    if retrieve_vectors {
+        index_scheduler.features()?.check_vector("Passing `retrieveVectors` as a parameter")?;
+    }
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(&index, offset, limit, filter, fields)?;
+    let (total, documents) =
+        retrieve_documents(&index, offset, limit, filter, sort, fields, retrieve_vectors)?;
 
     let ret = PaginationView::new(offset, limit, total as usize, documents);
 
@@ -239,13 +283,19 @@ fn documents_by_query(
     Ok(HttpResponse::Ok().json(ret))
 }
 
-#[derive(Deserialize, Debug, Deserr)]
+#[derive(Debug, Deserr)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
 pub struct UpdateDocumentsQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidIndexPrimaryKey>)]
     pub primary_key: Option<String>,
     #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
     pub csv_delimiter: Option<u8>,
+    /// Only validates the documents and reports the invalid ones, nothing is indexed.
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentDryRun>)]
+    pub dry_run: Param<bool>,
+    /// What to do with the documents sharing an id in the payload, the policy of the instance by default.
+    #[deserr(default, try_from(&String) = from_str_duplicate_ids_policy -> DeserrQueryParamError<InvalidDocumentDuplicateIdsPolicy>, error = DeserrQueryParamError<InvalidDocumentDuplicateIdsPolicy>)]
+    pub duplicate_ids_policy: Option<DuplicateIdsPolicy>,
 }
 
 fn from_char_csv_delimiter(
@@ -261,6 +311,14 @@ fn from_char_csv_delimiter(
     }
 }
 
+fn from_str_duplicate_ids_policy(
+    s: &String,
+) -> Result<Option<DuplicateIdsPolicy>, DeserrQueryParamError<InvalidDocumentDuplicateIdsPolicy>> {
+    DuplicateIdsPolicy::from_str(s)
+        .map(Some)
+        .map_err(|error| DeserrQueryParamError::new(error, Code::InvalidDocumentDuplicateIdsPolicy))
+}
+
 pub async fn replace_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -286,6 +344,8 @@ pub async fn replace_documents(
         body,
         IndexDocumentsMethod::ReplaceDocuments,
         allow_index_creation,
+        params.dry_run.0,
+        params.duplicate_ids_policy,
     )
     .await?;
 
@@ -317,6 +377,43 @@ pub async fn update_documents(
         body,
         IndexDocumentsMethod::UpdateDocuments,
         allow_index_creation,
+        params.dry_run.0,
+        params.duplicate_ids_policy,
+    )
+    .await?;
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Updates the documents like `update_documents` but the
+/// nested objects are merged with the existing ones.
+pub async fn merge_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<UpdateDocumentsQuery, DeserrQueryParamError>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    debug!("called with params: {:?}", params);
+    let params = params.into_inner();
+
+    analytics.update_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let task = document_addition(
+        extract_mime_type(&req)?,
+        index_scheduler,
+        index_uid,
+        params.primary_key,
+        params.csv_delimiter,
+        body,
+        IndexDocumentsMethod::MergeDocuments,
+        allow_index_creation,
+        params.dry_run.0,
+        params.duplicate_ids_policy,
     )
     .await?;
 
@@ -333,6 +430,8 @@ async fn document_addition(
     mut body: Payload,
     method: IndexDocumentsMethod,
     allow_index_creation: bool,
+    dry_run: bool,
+    duplicate_ids_policy: Option<DuplicateIdsPolicy>,
 ) -> Result<SummarizedTaskView, MeilisearchHttpError> {
     let format = match (
         mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())),
@@ -342,6 +441,7 @@ async fn document_addition(
         (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
         (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
         (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
+        (Some(("application", "vnd.apache.parquet")), None) => PayloadType::Parquet,
 
         (Some(("application", "json")), Some(_)) => {
             return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
@@ -353,6 +453,11 @@ async fn document_addition(
                 "application/x-ndjson",
             )))
         }
+        (Some(("application", "vnd.apache.parquet")), Some(_)) => {
+            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "application/vnd.apache.parquet",
+            )))
+        }
         (Some((type_, subtype)), _) => {
             return Err(MeilisearchHttpError::InvalidContentType(
                 format!("{}/{}", type_, subtype),
@@ -408,6 +513,7 @@ async fn document_addition(
                 read_csv(&read_file, update_file.as_file_mut(), delimiter)?
             }
             PayloadType::Ndjson => read_ndjson(&read_file, update_file.as_file_mut())?,
+            PayloadType::Parquet => read_parquet(&read_file, update_file.as_file_mut())?,
         };
         // we NEED to persist the file here because we moved the `udpate_file` in another task.
         update_file.persist()?;
@@ -435,17 +541,32 @@ async fn document_addition(
         }
     };
 
-    let task = KindWithContent::DocumentAdditionOrUpdate {
-        method,
-        content_file: uuid,
-        documents_count,
-        primary_key,
-        allow_index_creation,
-        index_uid: index_uid.to_string(),
+    let task = if dry_run {
+        KindWithContent::DocumentAdditionDryRun {
+            index_uid: index_uid.to_string(),
+            primary_key,
+            content_file: uuid,
+            documents_count,
+            duplicate_ids_policy,
+        }
+    } else {
+        KindWithContent::DocumentAdditionOrUpdate {
+            method,
+            content_file: uuid,
+            documents_count,
+            primary_key,
+            allow_index_creation,
+            index_uid: index_uid.to_string(),
+            duplicate_ids_policy,
+        }
     };
 
     let scheduler = index_scheduler.clone();
-    let task = match tokio::task::spawn_blocking(move || scheduler.register(task)).await? {
+    let task = match logging::spawn_blocking(move || {
+        scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await?
+    {
         Ok(task) => task,
         Err(e) => {
             index_scheduler.delete_update_file(uuid)?;
@@ -457,6 +578,155 @@ async fn document_addition(
     Ok(task.into())
 }
 
+/// The formats of the documents that can be downloaded from an url.
+#[derive(Debug, Default, Clone, Copy, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum DocumentsFormat {
+    Json,
+    #[default]
+    Ndjson,
+    Csv,
+    Parquet,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentsFromUrl {
+    #[deserr(error = DeserrJsonError<InvalidDocumentUrl>, missing_field_error = DeserrJsonError::missing_document_url)]
+    url: String,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFormat>)]
+    format: DocumentsFormat,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentAuthorization>)]
+    authorization: Option<String>,
+}
+
+pub async fn replace_documents_from_url(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<UpdateDocumentsQuery, DeserrQueryParamError>,
+    body: AwebJson<DocumentsFromUrl, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    debug!("called with params: {:?}", params);
+    let params = params.into_inner();
+
+    analytics.add_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
+
+    let task = document_addition_from_url(
+        index_scheduler,
+        index_uid,
+        params,
+        body.into_inner(),
+        IndexDocumentsMethod::ReplaceDocuments,
+    )
+    .await?;
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+pub async fn update_documents_from_url(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<UpdateDocumentsQuery, DeserrQueryParamError>,
+    body: AwebJson<DocumentsFromUrl, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    debug!("called with params: {:?}", params);
+    let params = params.into_inner();
+
+    analytics.update_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
+
+    let task = document_addition_from_url(
+        index_scheduler,
+        index_uid,
+        params,
+        body.into_inner(),
+        IndexDocumentsMethod::UpdateDocuments,
+    )
+    .await?;
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Ensures that the documents can be downloaded from the url and returns the format of the payload.
+pub(crate) fn check_documents_url(
+    url: &str,
+    format: DocumentsFormat,
+    csv_delimiter: Option<u8>,
+) -> Result<PayloadType, ResponseError> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => (),
+        _ => {
+            return Err(ResponseError::from_msg(
+                format!(
+                    "The url `{url}` is invalid. Only the `http` and `https` urls are accepted."
+                ),
+                Code::InvalidDocumentUrl,
+            ))
+        }
+    }
+
+    match (format, csv_delimiter) {
+        (DocumentsFormat::Json, None) => Ok(PayloadType::Json),
+        (DocumentsFormat::Ndjson, None) => Ok(PayloadType::Ndjson),
+        (DocumentsFormat::Parquet, None) => Ok(PayloadType::Parquet),
+        (DocumentsFormat::Csv, delimiter) => {
+            Ok(PayloadType::Csv { delimiter: delimiter.unwrap_or(b',') })
+        }
+        (_, Some(_)) => Err(ResponseError::from_msg(
+            String::from("The csv delimiter can only be used with the `csv` format."),
+            Code::InvalidDocumentCsvDelimiter,
+        )),
+    }
+}
+
+/// Registers a task downloading the documents from the url when it is processed,
+/// the documents never go through the client.
+async fn document_addition_from_url(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: IndexUid,
+    params: UpdateDocumentsQuery,
+    body: DocumentsFromUrl,
+    method: IndexDocumentsMethod,
+) -> Result<SummarizedTaskView, ResponseError> {
+    let DocumentsFromUrl { url, format, authorization } = body;
+    let format = check_documents_url(&url, format, params.csv_delimiter)?;
+    if params.dry_run.0 {
+        return Err(ResponseError::from_msg(
+            String::from("The documents downloaded from an url cannot be validated with `dryRun`."),
+            Code::InvalidDocumentDryRun,
+        ));
+    }
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let task = KindWithContent::DocumentAdditionFromUrl {
+        index_uid: index_uid.into_inner(),
+        primary_key: params.primary_key,
+        method,
+        url,
+        has_authorization: authorization.is_some(),
+        authorization,
+        format,
+        allow_index_creation,
+        duplicate_ids_policy: params.duplicate_ids_policy,
+    };
+
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
+
+    debug!("returns: {:?}", task);
+    Ok(task)
+}
+
 pub async fn delete_documents_batch(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -476,8 +746,11 @@ pub async fn delete_documents_batch(
 
     let task =
         KindWithContent::DocumentDeletion { index_uid: index_uid.to_string(), documents_ids: ids };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
@@ -504,21 +777,29 @@ pub async fn delete_documents_by_filter(
 
     analytics.delete_documents(DocumentDeletionKind::PerFilter, &req);
 
-    // we ensure the filter is well formed before enqueuing it
-    || -> Result<_, ResponseError> {
-        Ok(crate::search::parse_filter(&filter)?.ok_or(MeilisearchHttpError::EmptyFilter)?)
-    }()
-    // and whatever was the error, the error code should always be an InvalidDocumentFilter
-    .map_err(|err| ResponseError::from_msg(err.message, Code::InvalidDocumentFilter))?;
+    check_document_filter(&filter)?;
     let task = KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr: filter };
 
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
 }
 
+/// Ensures the filter is well formed before enqueuing a deletion by filter.
+pub(crate) fn check_document_filter(filter: &Value) -> Result<(), ResponseError> {
+    || -> Result<_, ResponseError> {
+        Ok(crate::search::parse_filter(filter)?.ok_or(MeilisearchHttpError::EmptyFilter)?)
+    }()
+    // and whatever was the error, the error code should always be an InvalidDocumentFilter
+    .map_err(|err| ResponseError::from_msg(err.message, Code::InvalidDocumentFilter))?;
+    Ok(())
+}
+
 pub async fn clear_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -529,8 +810,11 @@ pub async fn clear_all_documents(
     analytics.delete_documents(DocumentDeletionKind::ClearAll, &req);
 
     let task = KindWithContent::DocumentClear { index_uid: index_uid.to_string() };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
@@ -556,7 +840,9 @@ fn retrieve_documents<S: AsRef<str>>(
     offset: usize,
     limit: usize,
     filter: Option<Value>,
+    sort: Option<Vec<String>>,
     attributes_to_retrieve: Option<Vec<S>>,
+    retrieve_vectors: bool,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
     let filter = &filter;
@@ -580,31 +866,58 @@ fn retrieve_documents<S: AsRef<str>>(
 
     let (it, number_of_documents) = {
         let number_of_documents = candidates.len();
-        (
-            some_documents(index, &rtxn, candidates.into_iter().skip(offset).take(limit))?,
-            number_of_documents,
-        )
+        let documents_ids: Vec<_> = match sort {
+            Some(sort) => {
+                let sort = parse_document_sort(&sort)?;
+                index
+                    .sorted_documents_ids(&rtxn, candidates, &sort, offset.saturating_add(limit))
+                    .map_err(|err| match err {
+                        milli::Error::UserError(milli::UserError::InvalidSortableAttribute {
+                            ..
+                        }) => ResponseError::from_msg(err.to_string(), Code::InvalidDocumentSort),
+                        e => e.into(),
+                    })?
+                    .into_iter()
+                    .skip(offset)
+                    .collect()
+            }
+            None => candidates.into_iter().skip(offset).take(limit).collect(),
+        };
+        (some_documents(index, &rtxn, documents_ids)?, number_of_documents)
     };
 
     let documents: Result<Vec<_>, ResponseError> = it
         .map(|document| {
-            Ok(match &attributes_to_retrieve {
-                Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
-                    &document?,
-                    attributes_to_retrieve.iter().map(|s| s.as_ref()),
-                ),
-                None => document?,
-            })
+            Ok(select_fields(document?, attributes_to_retrieve.as_deref(), retrieve_vectors))
         })
         .collect();
 
     Ok((number_of_documents, documents?))
 }
 
+/// Parses the sort criteria of the documents, the geo criteria are not supported.
+fn parse_document_sort(sort: &[String]) -> Result<Vec<AscDesc>, ResponseError> {
+    sort.iter()
+        .map(|s| {
+            let asc_desc = AscDesc::from_str(s).map_err(|err| {
+                ResponseError::from_msg(SortError::from(err).to_string(), Code::InvalidDocumentSort)
+            })?;
+            match asc_desc.member() {
+                Member::Field(_) => Ok(asc_desc),
+                Member::Geo(_) => Err(ResponseError::from_msg(
+                    format!("Invalid sort `{s}`: the documents cannot be sorted by `_geoPoint`."),
+                    Code::InvalidDocumentSort,
+                )),
+            }
+        })
+        .collect()
+}
+
 fn retrieve_document<S: AsRef<str>>(
     index: &Index,
     doc_id: &str,
     attributes_to_retrieve: Option<Vec<S>>,
+    retrieve_vectors: bool,
 ) -> Result<Document, ResponseError> {
     let txn = index.read_txn()?;
 
@@ -624,7 +937,19 @@ fn retrieve_document<S: AsRef<str>>(
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))?;
 
     let document = meilisearch_types::milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
-    let document = match &attributes_to_retrieve {
+
+    Ok(select_fields(document, attributes_to_retrieve.as_deref(), retrieve_vectors))
+}
+
+/// Selects the fields to retrieve, the `_vectors` field is only
+/// returned when asked for with `retrieveVectors`.
+fn select_fields<S: AsRef<str>>(
+    document: Document,
+    attributes_to_retrieve: Option<&[S]>,
+    retrieve_vectors: bool,
+) -> Document {
+    let vectors = document.get("_vectors").filter(|_| retrieve_vectors).cloned();
+    let mut document = match attributes_to_retrieve {
         Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
             &document,
             attributes_to_retrieve.iter().map(|s| s.as_ref()),
@@ -632,5 +957,9 @@ fn retrieve_document<S: AsRef<str>>(
         None => document,
     };
 
-    Ok(document)
+    document.remove("_vectors");
+    if let Some(vectors) = vectors {
+        document.insert("_vectors".to_string(), vectors);
+    }
+    document
 }
@@ -12,10 +12,11 @@ use serde_json::Value;
 use crate::analytics::{Analytics, FacetSearchAggregator};
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::logging;
 use crate::search::{
-    add_search_rules, perform_facet_search, MatchingStrategy, SearchQuery, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, perform_facet_search, GeoDistanceUnit, MatchingStrategy, SearchQuery,
+    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_HITS_PER_GROUP, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -69,7 +70,7 @@ pub async fn search(
 
     let index = index_scheduler.index(&index_uid)?;
     let features = index_scheduler.features()?;
-    let search_result = tokio::task::spawn_blocking(move || {
+    let search_result = logging::spawn_blocking(move || {
         perform_facet_search(&index, search_query, facet_query, facet_name, features)
     })
     .await?;
@@ -115,10 +116,26 @@ impl From<FacetSearchQuery> for SearchQuery {
             facets: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
+            highlight_searchable_only: false,
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
+            minimum_should_match: None,
             vector,
             attributes_to_search_on,
+            attribute_boosts: None,
+            ranking_score_threshold: None,
+            distinct: None,
+            prefix_search: None,
+            locales: None,
+            keep_stop_words: false,
+            geo_distance_unit: GeoDistanceUnit::default(),
+            group_by: None,
+            hits_per_group: DEFAULT_HITS_PER_GROUP(),
+            show_spelling_suggestions: false,
+            show_candidates_handle: false,
+            within_candidates: None,
+            search_after: None,
+            retrieve_vectors: false,
         }
     }
 }
@@ -22,11 +22,15 @@ use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::logging;
 
 pub mod documents;
 pub mod facet_search;
 pub mod search;
+pub mod search_analytics;
 pub mod settings;
+pub mod similar;
+pub mod suggestions;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -43,10 +47,15 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(web::resource("/duplicate").route(web::post().to(SeqHandler(duplicate_index))))
+            .service(web::resource("/compact").route(web::post().to(SeqHandler(compact_index))))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
+            .service(web::scope("/search-analytics").configure(search_analytics::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
-            .service(web::scope("/settings").configure(settings::configure)),
+            .service(web::scope("/settings").configure(settings::configure))
+            .service(web::scope("/suggestions").configure(suggestions::configure))
+            .service(web::scope("/similar").configure(similar::configure)),
     );
 }
 
@@ -135,8 +144,11 @@ pub async fn create_index(
         );
 
         let task = KindWithContent::IndexCreation { index_uid: uid.to_string(), primary_key };
-        let task: SummarizedTaskView =
-            tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+        let task: SummarizedTaskView = logging::spawn_blocking(move || {
+            index_scheduler.register_with_request_id(task, logging::request_id())
+        })
+        .await??
+        .into();
 
         Ok(HttpResponse::Accepted().json(task))
     } else {
@@ -144,6 +156,66 @@ pub async fn create_index(
     }
 }
 
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct IndexDuplicateRequest {
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    uid: IndexUid,
+}
+
+/// Enqueues a task creating the `uid` index with a copy of the documents and settings of the index.
+pub async fn duplicate_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexDuplicateRequest, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let IndexDuplicateRequest { uid } = body.into_inner();
+
+    if !index_scheduler.filters().allow_index_creation(&uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    analytics.publish("Index Duplicated".to_string(), json!({}), Some(&req));
+
+    let task = KindWithContent::IndexDuplication {
+        index_uid: index_uid.into_inner(),
+        new_index_uid: uid.into_inner(),
+    };
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Enqueues a task compacting the LMDB environment of the index to reclaim its free pages.
+pub async fn compact_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    analytics.publish("Index Compacted".to_string(), json!({}), Some(&req));
+
+    let task = KindWithContent::IndexCompaction { index_uid: index_uid.into_inner() };
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 fn deny_immutable_fields_index(
     field: &str,
     accepted: &[&str],
@@ -203,8 +275,11 @@ pub async fn update_index(
         primary_key: body.primary_key,
     };
 
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
@@ -216,8 +291,11 @@ pub async fn delete_index(
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
     let task = KindWithContent::IndexDeletion { index_uid: index_uid.into_inner() };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     Ok(HttpResponse::Accepted().json(task))
 }
@@ -228,6 +306,10 @@ pub async fn delete_index(
 pub struct IndexStats {
     /// Number of documents in the index
     pub number_of_documents: u64,
+    /// Size taken up by the index' DB on the disk, in bytes.
+    pub database_size: u64,
+    /// Size taken by the used pages of the index' DB, in bytes.
+    pub used_database_size: u64,
     /// Whether the index is currently performing indexation, according to the scheduler.
     pub is_indexing: bool,
     /// Association of every field name with the number of times it occurs in the documents.
@@ -238,6 +320,8 @@ impl From<index_scheduler::IndexStats> for IndexStats {
     fn from(stats: index_scheduler::IndexStats) -> Self {
         IndexStats {
             number_of_documents: stats.inner_stats.number_of_documents,
+            database_size: stats.inner_stats.database_size,
+            used_database_size: stats.inner_stats.used_database_size,
             is_indexing: stats.is_indexing,
             field_distribution: stats.inner_stats.field_distribution,
         }
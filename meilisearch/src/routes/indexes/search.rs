@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
@@ -8,6 +10,7 @@ use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use meilisearch_types::serde_cs::vec::CS;
 use serde_json::Value;
 
@@ -16,9 +19,10 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::search::{
-    add_search_rules, perform_search, MatchingStrategy, SearchQuery, DEFAULT_CROP_LENGTH,
+    add_search_rules, perform_search_with_cache, record_search, AttributeBoost, GeoDistanceUnit,
+    MatchingStrategy, MinimumShouldMatch, RankingScoreThreshold, SearchQuery, DEFAULT_CROP_LENGTH,
     DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    DEFAULT_HITS_PER_GROUP, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -68,12 +72,44 @@ pub struct SearchQueryGet {
     highlight_pre_tag: String,
     #[deserr( default = DEFAULT_HIGHLIGHT_POST_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPostTag>)]
     highlight_post_tag: String,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchHighlightSearchableOnly>)]
+    highlight_searchable_only: Param<bool>,
     #[deserr(default = DEFAULT_CROP_MARKER(), error = DeserrQueryParamError<InvalidSearchCropMarker>)]
     crop_marker: String,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchMinimumShouldMatch>)]
+    pub minimum_should_match: Option<Param<MinimumShouldMatch>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToSearchOn>)]
     pub attributes_to_search_on: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributeBoosts>)]
+    pub attribute_boosts: Option<CS<AttributeBoost>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
+    pub ranking_score_threshold: Option<Param<RankingScoreThreshold>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDistinct>)]
+    distinct: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchPrefixSearch>)]
+    pub prefix_search: Option<Param<bool>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
+    pub locales: Option<CS<Locale>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchKeepStopWords>)]
+    pub keep_stop_words: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGeoDistanceUnit>)]
+    pub geo_distance_unit: GeoDistanceUnit,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = Param(DEFAULT_HITS_PER_GROUP()), error = DeserrQueryParamError<InvalidSearchHitsPerGroup>)]
+    pub hits_per_group: Param<usize>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowSpellingSuggestions>)]
+    pub show_spelling_suggestions: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowCandidatesHandle>)]
+    pub show_candidates_handle: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchWithinCandidates>)]
+    pub within_candidates: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRetrieveVectors>)]
+    pub retrieve_vectors: Param<bool>,
 }
 
 impl From<SearchQueryGet> for SearchQuery {
@@ -105,9 +141,25 @@ impl From<SearchQueryGet> for SearchQuery {
             facets: other.facets.map(|o| o.into_iter().collect()),
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
+            highlight_searchable_only: other.highlight_searchable_only.0,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
+            minimum_should_match: other.minimum_should_match.map(|o| o.0),
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
+            attribute_boosts: other.attribute_boosts.map(|o| o.into_iter().collect()),
+            ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
+            distinct: other.distinct,
+            prefix_search: other.prefix_search.map(|o| o.0),
+            locales: other.locales.map(|o| o.into_iter().collect()),
+            keep_stop_words: other.keep_stop_words.0,
+            geo_distance_unit: other.geo_distance_unit,
+            group_by: other.group_by,
+            hits_per_group: other.hits_per_group.0,
+            show_spelling_suggestions: other.show_spelling_suggestions.0,
+            show_candidates_handle: other.show_candidates_handle.0,
+            within_candidates: other.within_candidates,
+            search_after: other.search_after,
+            retrieve_vectors: other.retrieve_vectors.0,
         }
     }
 }
@@ -144,6 +196,7 @@ pub async fn search_with_url_query(
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
+    let started_at = Instant::now();
     debug!("called with params: {:?}", params);
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
@@ -158,10 +211,12 @@ pub async fn search_with_url_query(
 
     let index = index_scheduler.index(&index_uid)?;
     let features = index_scheduler.features()?;
-    let search_result =
-        tokio::task::spawn_blocking(move || perform_search(&index, query, features)).await?;
-    if let Ok(ref search_result) = search_result {
+    let mut search_result =
+        perform_search_with_cache(&index_scheduler, &index_uid, index, query, features, started_at)
+            .await;
+    if let Ok(ref mut search_result) = search_result {
         aggregate.succeed(search_result);
+        record_search(&index_scheduler, features, &index_uid, search_result);
     }
     analytics.get_search(aggregate);
 
@@ -178,6 +233,7 @@ pub async fn search_with_post(
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
+    let started_at = Instant::now();
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
     let mut query = params.into_inner();
@@ -193,10 +249,12 @@ pub async fn search_with_post(
     let index = index_scheduler.index(&index_uid)?;
 
     let features = index_scheduler.features()?;
-    let search_result =
-        tokio::task::spawn_blocking(move || perform_search(&index, query, features)).await?;
-    if let Ok(ref search_result) = search_result {
+    let mut search_result =
+        perform_search_with_cache(&index_scheduler, &index_uid, index, query, features, started_at)
+            .await;
+    if let Ok(ref mut search_result) = search_result {
         aggregate.succeed(search_result);
+        record_search(&index_scheduler, features, &index_uid, search_result);
     }
     analytics.post_search(aggregate);
 
@@ -0,0 +1,149 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use index_scheduler::search_analytics::QueryStats;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::star_or::OptionStarOr;
+use serde::Serialize;
+use serde_json::json;
+use time::OffsetDateTime;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::tasks::{deserialize_date_after, deserialize_date_before};
+
+const DEFAULT_LIMIT: usize = 20;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/top-queries").route(web::get().to(SeqHandler(get_top_queries))))
+        .service(
+            web::resource("/no-result-queries")
+                .route(web::get().to(SeqHandler(get_no_result_queries))),
+        )
+        .service(web::resource("/clicks").route(web::post().to(SeqHandler(report_click))));
+}
+
+#[derive(Debug, deserr::Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchAnalyticsQuery {
+    #[deserr(default = Param(DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidSearchAnalyticsLimit>)]
+    limit: Param<usize>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchAnalyticsAfter>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    after: OptionStarOr<OffsetDateTime>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchAnalyticsBefore>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    before: OptionStarOr<OffsetDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryStatsView {
+    query: String,
+    count: u64,
+    average_hits: f64,
+    average_processing_time_ms: f64,
+    clicks: u64,
+}
+
+impl From<QueryStats> for QueryStatsView {
+    fn from(stats: QueryStats) -> Self {
+        let QueryStats { query, count, average_hits, average_processing_time_ms, clicks } = stats;
+        QueryStatsView { query, count, average_hits, average_processing_time_ms, clicks }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct QueryStatsResults {
+    results: Vec<QueryStatsView>,
+}
+
+pub async fn get_top_queries(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<SearchAnalyticsQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features()?.check_search_analytics()?;
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let SearchAnalyticsQuery { limit, after, before } = params.into_inner();
+
+    analytics.publish("Top Queries Seen".to_string(), json!({ "limit": limit.0 }), Some(&req));
+
+    // ensures the index exists.
+    index_scheduler.index(&index_uid)?;
+    let results = index_scheduler.search_analytics().top_queries(
+        &index_uid,
+        after.merge_star_and_none(),
+        before.merge_star_and_none(),
+        limit.0,
+    );
+    let results = QueryStatsResults { results: results.into_iter().map(Into::into).collect() };
+
+    debug!("returns: {:?}", results);
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn get_no_result_queries(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<SearchAnalyticsQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features()?.check_search_analytics()?;
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let SearchAnalyticsQuery { limit, after, before } = params.into_inner();
+
+    analytics.publish(
+        "No Result Queries Seen".to_string(),
+        json!({ "limit": limit.0 }),
+        Some(&req),
+    );
+
+    // ensures the index exists.
+    index_scheduler.index(&index_uid)?;
+    let results = index_scheduler.search_analytics().no_result_queries(
+        &index_uid,
+        after.merge_star_and_none(),
+        before.merge_star_and_none(),
+        limit.0,
+    );
+    let results = QueryStatsResults { results: results.into_iter().map(Into::into).collect() };
+
+    debug!("returns: {:?}", results);
+    Ok(HttpResponse::Ok().json(results))
+}
+
+#[derive(Debug, deserr::Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct ClickReport {
+    #[deserr(error = DeserrJsonError<InvalidSearchAnalyticsSearchUid>, missing_field_error = DeserrJsonError::missing_search_analytics_search_uid)]
+    search_uid: u64,
+}
+
+/// Marks a search as having led to a click on one of its hits.
+pub async fn report_click(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<ClickReport, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features()?.check_search_analytics()?;
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let ClickReport { search_uid } = params.into_inner();
+    debug!("click reported on search {search_uid}");
+
+    if index_scheduler.search_analytics().click(&index_uid, search_uid) {
+        Ok(HttpResponse::NoContent().finish())
+    } else {
+        Err(MeilisearchHttpError::SearchNotFound(search_uid).into())
+    }
+}
@@ -14,6 +14,7 @@ use serde_json::json;
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::logging;
 use crate::routes::SummarizedTaskView;
 
 #[macro_export]
@@ -27,7 +28,7 @@ macro_rules! make_setting_route {
             use meilisearch_types::error::ResponseError;
             use meilisearch_types::index_uid::IndexUid;
             use meilisearch_types::milli::update::Setting;
-            use meilisearch_types::settings::{settings, Settings};
+            use meilisearch_types::settings::{settings, Settings, Unchecked};
             use meilisearch_types::tasks::KindWithContent;
             use $crate::analytics::Analytics;
             use $crate::extractors::authentication::policies::*;
@@ -55,10 +56,11 @@ macro_rules! make_setting_route {
                     is_deletion: true,
                     allow_index_creation,
                 };
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task))
-                        .await??
-                        .into();
+                let task: SummarizedTaskView = logging::spawn_blocking(move || {
+                    index_scheduler.register_with_request_id(task, logging::request_id())
+                })
+                .await??
+                .into();
 
                 debug!("returns: {:?}", task);
                 Ok(HttpResponse::Accepted().json(task))
@@ -80,13 +82,14 @@ macro_rules! make_setting_route {
 
                 $analytics(&body, &req);
 
-                let new_settings = Settings {
+                let new_settings = Settings::<Unchecked> {
                     $attr: match body {
                         Some(inner_body) => Setting::Set(inner_body).into(),
                         None => Setting::Reset.into(),
                     },
                     ..Default::default()
-                };
+                }
+                .validate()?;
 
                 let allow_index_creation =
                     index_scheduler.filters().allow_index_creation(&index_uid);
@@ -97,10 +100,11 @@ macro_rules! make_setting_route {
                     is_deletion: false,
                     allow_index_creation,
                 };
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task))
-                        .await??
-                        .into();
+                let task: SummarizedTaskView = logging::spawn_blocking(move || {
+                    index_scheduler.register_with_request_id(task, logging::request_id())
+                })
+                .await??
+                .into();
 
                 debug!("returns: {:?}", task);
                 Ok(HttpResponse::Accepted().json(task))
@@ -238,6 +242,9 @@ make_setting_route!(
                     "disable_on_words": setting
                         .as_ref()
                         .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
+                    "disable_on_numbers": setting
+                        .as_ref()
+                        .and_then(|s| s.disable_on_numbers.as_ref().set().copied()),
                     "min_word_size_for_one_typo": setting
                         .as_ref()
                         .and_then(|s| s.min_word_size_for_typos
@@ -262,14 +269,14 @@ make_setting_route!(
 make_setting_route!(
     "/searchable-attributes",
     put,
-    Vec<String>,
+    Vec<meilisearch_types::settings::SearchableAttributeView>,
     meilisearch_types::deserr::DeserrJsonError<
         meilisearch_types::error::deserr_codes::InvalidSettingsSearchableAttributes,
     >,
     searchable_attributes,
     "searchableAttributes",
     analytics,
-    |setting: &Option<Vec<String>>, req: &HttpRequest| {
+    |setting: &Option<Vec<meilisearch_types::settings::SearchableAttributeView>>, req: &HttpRequest| {
         use serde_json::json;
 
         analytics.publish(
@@ -277,7 +284,8 @@ make_setting_route!(
             json!({
                 "searchable_attributes": {
                     "total": setting.as_ref().map(|searchable| searchable.len()),
-                    "with_wildcard": setting.as_ref().map(|searchable| searchable.iter().any(|searchable| searchable == "*")),
+                    "with_wildcard": setting.as_ref().map(|searchable| searchable.iter().any(|searchable| searchable.attribute() == "*")),
+                    "with_weights": setting.as_ref().map(|searchable| searchable.iter().any(|searchable| searchable.weight().is_some())),
                 },
             }),
             Some(req),
@@ -288,21 +296,22 @@ make_setting_route!(
 make_setting_route!(
     "/stop-words",
     put,
-    std::collections::BTreeSet<String>,
+    meilisearch_types::settings::StopWordsView,
     meilisearch_types::deserr::DeserrJsonError<
         meilisearch_types::error::deserr_codes::InvalidSettingsStopWords,
     >,
     stop_words,
     "stopWords",
     analytics,
-    |stop_words: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+    |stop_words: &Option<meilisearch_types::settings::StopWordsView>, req: &HttpRequest| {
         use serde_json::json;
 
         analytics.publish(
             "StopWords Updated".to_string(),
             json!({
                 "stop_words": {
-                    "total": stop_words.as_ref().map(|stop_words| stop_words.len()),
+                    "total": stop_words.as_ref().map(|stop_words| stop_words.words().len()),
+                    "preset": stop_words.as_ref().and_then(|stop_words| stop_words.preset()),
                 },
             }),
             Some(req),
@@ -310,6 +319,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/index-stop-words",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsIndexStopWords,
+    >,
+    index_stop_words,
+    "indexStopWords",
+    analytics,
+    |index_stop_words: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "IndexStopWords Updated".to_string(),
+            json!({
+                "index_stop_words": {
+                    "enabled": index_stop_words,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/non-separator-tokens",
     put,
@@ -520,6 +554,473 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/search-cutoff-ms",
+    put,
+    u64,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSearchCutoffMs,
+    >,
+    search_cutoff_ms,
+    "searchCutoffMs",
+    analytics,
+    |setting: &Option<u64>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Search Cutoff Updated".to_string(),
+            json!({
+                "search_cutoff_ms": {
+                    "search_cutoff_ms": setting,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/proximity-precision",
+    put,
+    meilisearch_types::settings::ProximityPrecisionView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsProximityPrecision,
+    >,
+    proximity_precision,
+    "proximityPrecision",
+    analytics,
+    |precision: &Option<meilisearch_types::settings::ProximityPrecisionView>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "ProximityPrecision Updated".to_string(),
+            json!({
+                "proximity_precision": {
+                    "set": precision.is_some(),
+                    "value": precision,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/prefix-search",
+    put,
+    meilisearch_types::settings::PrefixSearchSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPrefixSearch,
+    >,
+    prefix_search,
+    "prefixSearch",
+    analytics,
+    |prefix_search: &Option<meilisearch_types::settings::PrefixSearchSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PrefixSearch Updated".to_string(),
+            json!({
+                "prefix_search": {
+                    "set": prefix_search.is_some(),
+                    "value": prefix_search,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/split-words",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSplitWords,
+    >,
+    split_words,
+    "splitWords",
+    analytics,
+    |split_words: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SplitWords Updated".to_string(),
+            json!({
+                "split_words": {
+                    "enabled": split_words,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/concatenate-words",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsConcatenateWords,
+    >,
+    concatenate_words,
+    "concatenateWords",
+    analytics,
+    |concatenate_words: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "ConcatenateWords Updated".to_string(),
+            json!({
+                "concatenate_words": {
+                    "enabled": concatenate_words,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    Vec<meilisearch_types::locales::LocalizedAttributesRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    analytics,
+    |rules: &Option<Vec<meilisearch_types::locales::LocalizedAttributesRuleView>>,
+     req: &HttpRequest| {
+        use std::collections::BTreeSet;
+
+        use serde_json::json;
+
+        let locales = rules.as_ref().map(|rules| {
+            rules.iter().flat_map(|rule| rule.locales.iter().copied()).collect::<BTreeSet<_>>()
+        });
+
+        analytics.publish(
+            "LocalizedAttributes Updated".to_string(),
+            json!({
+                "localized_attributes": {
+                    "total": rules.as_ref().map(|rules| rules.len()),
+                    "locales": locales,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/normalize-numbers",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsNormalizeNumbers,
+    >,
+    normalize_numbers,
+    "normalizeNumbers",
+    analytics,
+    |normalize_numbers: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "NormalizeNumbers Updated".to_string(),
+            json!({
+                "normalize_numbers": {
+                    "enabled": normalize_numbers,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/normalization",
+    patch,
+    meilisearch_types::settings::NormalizationSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsNormalization,
+    >,
+    normalization,
+    "normalization",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::NormalizationSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Normalization Updated".to_string(),
+            json!({
+                "normalization": {
+                    "lowercase": setting.as_ref().and_then(|s| s.lowercase.set()),
+                    "remove_diacritics": setting.as_ref().and_then(|s| s.remove_diacritics.set()),
+                    "compatibility": setting.as_ref().and_then(|s| s.compatibility.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/percolate-queries",
+    put,
+    std::collections::BTreeMap<String, String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPercolateQueries,
+    >,
+    percolate_queries,
+    "percolateQueries",
+    analytics,
+    |queries: &Option<std::collections::BTreeMap<String, String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PercolateQueries Updated".to_string(),
+            json!({
+                "percolate_queries": {
+                    "total": queries.as_ref().map(|queries| queries.len()),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/stemming",
+    put,
+    std::collections::BTreeSet<meilisearch_types::settings::StemmingLanguageView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsStemming,
+    >,
+    stemming,
+    "stemming",
+    analytics,
+    |languages: &Option<std::collections::BTreeSet<meilisearch_types::settings::StemmingLanguageView>>,
+     req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Stemming Updated".to_string(),
+            json!({
+                "stemming": {
+                    "languages": languages,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/geo-fields",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsGeoFields,
+    >,
+    geo_fields,
+    "geoFields",
+    analytics,
+    |fields: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "GeoFields Updated".to_string(),
+            json!({
+                "geo_fields": {
+                    "total": fields.as_ref().map(|fields| fields.len()),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/geo-index",
+    patch,
+    meilisearch_types::settings::GeoIndexSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsGeoIndex,
+    >,
+    geo_index,
+    "geoIndex",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::GeoIndexSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "GeoIndex Updated".to_string(),
+            json!({
+                "geo_index": {
+                    "node_size": setting.as_ref().and_then(|s| s.node_size.set()),
+                    "load_strategy": setting.as_ref().and_then(|s| s.load_strategy.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/binary-quantized-vectors",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsBinaryQuantizedVectors,
+    >,
+    binary_quantized_vectors,
+    "binaryQuantizedVectors",
+    analytics,
+    |binary_quantized_vectors: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "BinaryQuantizedVectors Updated".to_string(),
+            json!({
+                "binary_quantized_vectors": {
+                    "enabled": binary_quantized_vectors,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/vector-index",
+    patch,
+    meilisearch_types::settings::VectorIndexSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsVectorIndex,
+    >,
+    vector_index,
+    "vectorIndex",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::VectorIndexSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "VectorIndex Updated".to_string(),
+            json!({
+                "vector_index": {
+                    "ef_construction": setting.as_ref().and_then(|s| s.ef_construction.set()),
+                    "ef_search": setting.as_ref().and_then(|s| s.ef_search.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/reranker",
+    put,
+    meilisearch_types::settings::RerankerSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsReranker,
+    >,
+    reranker,
+    "reranker",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::RerankerSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Reranker Updated".to_string(),
+            json!({
+                "reranker": {
+                    "set": setting.is_some(),
+                    "total_fields": setting.as_ref().map(|s| s.fields.len()),
+                    "top_k": setting.as_ref().map(|s| s.top_k),
+                    "timeout_ms": setting.as_ref().map(|s| s.timeout_ms),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/version-attribute",
+    put,
+    String,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsVersionAttribute,
+    >,
+    version_attribute,
+    "versionAttribute",
+    analytics,
+    |version: &Option<String>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "VersionAttribute Updated".to_string(),
+            json!({
+                "version_attribute": {
+                    "set": version.is_some(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/document-timestamps",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDocumentTimestamps,
+    >,
+    document_timestamps,
+    "documentTimestamps",
+    analytics,
+    |document_timestamps: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "DocumentTimestamps Updated".to_string(),
+            json!({
+                "document_timestamps": {
+                    "enabled": document_timestamps,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/facet-search",
+    put,
+    bool,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsFacetSearch,
+    >,
+    facet_search,
+    "facetSearch",
+    analytics,
+    |facet_search: &Option<bool>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "FacetSearch Updated".to_string(),
+            json!({
+                "facet_search": {
+                    "enabled": facet_search,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 macro_rules! generate_configure {
     ($($mod:ident),*) => {
         pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -527,8 +1028,10 @@ macro_rules! generate_configure {
             cfg.service(
                 web::resource("")
                 .route(web::patch().to(SeqHandler(update_all)))
+                .route(web::put().to(SeqHandler(replace_all)))
                 .route(web::get().to(SeqHandler(get_all)))
                 .route(web::delete().to(SeqHandler(delete_all))))
+                .service(web::resource("/diff").route(web::post().to(SeqHandler(diff))))
                 $(.service($mod::resources()))*;
         }
     };
@@ -541,6 +1044,7 @@ generate_configure!(
     searchable_attributes,
     distinct_attribute,
     stop_words,
+    index_stop_words,
     separator_tokens,
     non_separator_tokens,
     dictionary,
@@ -548,7 +1052,25 @@ generate_configure!(
     ranking_rules,
     typo_tolerance,
     pagination,
-    faceting
+    faceting,
+    search_cutoff_ms,
+    proximity_precision,
+    prefix_search,
+    split_words,
+    concatenate_words,
+    localized_attributes,
+    normalize_numbers,
+    normalization,
+    percolate_queries,
+    stemming,
+    geo_fields,
+    geo_index,
+    binary_quantized_vectors,
+    vector_index,
+    reranker,
+    version_attribute,
+    document_timestamps,
+    facet_search
 );
 
 pub async fn update_all(
@@ -560,7 +1082,7 @@ pub async fn update_all(
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
-    let new_settings = body.into_inner();
+    let new_settings = body.into_inner().validate()?;
 
     analytics.publish(
         "Settings Updated".to_string(),
@@ -572,11 +1094,12 @@ pub async fn update_all(
                 "attribute_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Attribute))),
                 "sort_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Sort))),
                 "exactness_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Exactness))),
-                "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
+                "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_) | RankingRuleView::Decay { .. }) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
             },
             "searchable_attributes": {
                 "total": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.len()),
-                "with_wildcard": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable == "*")),
+                "with_wildcard": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable.attribute() == "*")),
+                "with_weights": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable.weight().is_some())),
             },
             "displayed_attributes": {
                 "total": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.len()),
@@ -607,6 +1130,11 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
+                "disable_on_numbers": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.disable_on_numbers.as_ref().set())
+                    .copied(),
                 "min_word_size_for_one_typo": new_settings.typo_tolerance
                     .as_ref()
                     .set()
@@ -646,8 +1174,95 @@ pub async fn update_all(
                     .set()
                     .and_then(|s| s.max_total_hits.as_ref().set()),
             },
+            "search_cutoff_ms": {
+                "search_cutoff_ms": new_settings.search_cutoff_ms.as_ref().set(),
+            },
+            "proximity_precision": {
+                "set": new_settings.proximity_precision.as_ref().set().is_some(),
+                "value": new_settings.proximity_precision.as_ref().set(),
+            },
+            "prefix_search": {
+                "set": new_settings.prefix_search.as_ref().set().is_some(),
+                "value": new_settings.prefix_search.as_ref().set(),
+            },
+            "split_words": {
+                "enabled": new_settings.split_words.as_ref().set(),
+            },
+            "concatenate_words": {
+                "enabled": new_settings.concatenate_words.as_ref().set(),
+            },
+            "localized_attributes": {
+                "total": new_settings.localized_attributes.as_ref().set().map(|rules| rules.len()),
+            },
+            "normalize_numbers": {
+                "enabled": new_settings.normalize_numbers.as_ref().set(),
+            },
+            "normalization": {
+                "lowercase": new_settings.normalization
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.lowercase.as_ref().set()),
+                "remove_diacritics": new_settings.normalization
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.remove_diacritics.as_ref().set()),
+                "compatibility": new_settings.normalization
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.compatibility.as_ref().set()),
+            },
+            "percolate_queries": {
+                "total": new_settings.percolate_queries.as_ref().set().map(|queries| queries.len()),
+            },
+            "stemming": {
+                "languages": new_settings.stemming.as_ref().set(),
+            },
+            "geo_fields": {
+                "total": new_settings.geo_fields.as_ref().set().map(|fields| fields.len()),
+            },
+            "geo_index": {
+                "node_size": new_settings.geo_index
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.node_size.as_ref().set()),
+                "load_strategy": new_settings.geo_index
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.load_strategy.as_ref().set()),
+            },
+            "binary_quantized_vectors": {
+                "enabled": new_settings.binary_quantized_vectors.as_ref().set(),
+            },
+            "vector_index": {
+                "ef_construction": new_settings.vector_index
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.ef_construction.as_ref().set()),
+                "ef_search": new_settings.vector_index
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.ef_search.as_ref().set()),
+            },
+            "reranker": {
+                "set": new_settings.reranker.as_ref().set().is_some(),
+                "top_k": new_settings.reranker.as_ref().set().map(|s| s.top_k),
+                "timeout_ms": new_settings.reranker.as_ref().set().map(|s| s.timeout_ms),
+            },
+            "version_attribute": {
+                "set": new_settings.version_attribute.as_ref().set().is_some(),
+            },
+            "document_timestamps": {
+                "enabled": new_settings.document_timestamps.as_ref().set(),
+            },
+            "facet_search": {
+                "enabled": new_settings.facet_search.as_ref().set(),
+            },
             "stop_words": {
-                "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.len()),
+                "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.words().len()),
+                "preset": new_settings.stop_words.as_ref().set().and_then(|stop_words| stop_words.preset()),
+            },
+            "index_stop_words": {
+                "enabled": new_settings.index_stop_words.as_ref().set(),
             },
             "synonyms": {
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
@@ -664,8 +1279,45 @@ pub async fn update_all(
         is_deletion: false,
         allow_index_creation,
     };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Replaces all the settings of the index, the settings missing from the body are reset.
+///
+/// The body can be the settings returned by `get_all`, to export the settings of an index
+/// and apply them again later in a single task.
+pub async fn replace_all(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Settings<Unchecked>, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let new_settings = body.into_inner().validate()?.reset_missing();
+
+    analytics.publish("Settings Replaced".to_string(), json!({}), Some(&req));
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let task = KindWithContent::SettingsUpdate {
+        index_uid: index_uid.into_inner(),
+        new_settings: Box::new(new_settings),
+        is_deletion: false,
+        allow_index_creation,
+    };
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
@@ -684,6 +1336,57 @@ pub async fn get_all(
     Ok(HttpResponse::Ok().json(new_settings))
 }
 
+/// The settings whose given value is merged into the current one instead of replacing it.
+const MERGED_SETTINGS: &[&str] =
+    &["typoTolerance", "faceting", "pagination", "geoIndex", "vectorIndex"];
+
+/// Returns the settings of the body that differ from the current settings of the index,
+/// with both their current and submitted values, without enqueuing any task.
+///
+/// A `null` value stands for a reset of the setting and differs from any non-null current value.
+pub async fn diff(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Settings<Unchecked>, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let current = json!(settings(&index, &rtxn)?);
+    let submitted = json!(body.into_inner());
+
+    let mut diff = serde_json::Map::new();
+    if let Some(submitted) = submitted.as_object() {
+        for (name, submitted) in submitted {
+            let current = current.get(name).cloned().unwrap_or_default();
+            let same = if MERGED_SETTINGS.contains(&name.as_str()) {
+                is_merged_into(submitted, &current)
+            } else {
+                *submitted == current
+            };
+            if !same {
+                diff.insert(name.clone(), json!({ "current": current, "submitted": submitted }));
+            }
+        }
+    }
+
+    debug!("returns: {:?}", diff);
+    Ok(HttpResponse::Ok().json(diff))
+}
+
+/// Returns `true` if merging the submitted value into the current one leaves it unchanged.
+fn is_merged_into(submitted: &serde_json::Value, current: &serde_json::Value) -> bool {
+    match (submitted, current) {
+        (serde_json::Value::Object(submitted), serde_json::Value::Object(current)) => {
+            submitted.iter().all(|(key, submitted)| {
+                current.get(key).map_or(false, |current| is_merged_into(submitted, current))
+            })
+        }
+        (submitted, current) => submitted == current,
+    }
+}
+
 pub async fn delete_all(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -700,8 +1403,11 @@ pub async fn delete_all(
         is_deletion: true,
         allow_index_creation,
     };
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+    let task: SummarizedTaskView = logging::spawn_blocking(move || {
+        index_scheduler.register_with_request_id(task, logging::request_id())
+    })
+    .await??
+    .into();
 
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
@@ -0,0 +1,111 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::serde_cs::vec::CS;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::logging;
+use crate::search::{
+    fuse_filters, perform_similar, SimilarQuery, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{document_id}").route(web::get().to(SeqHandler(similar))));
+}
+
+#[derive(Deserialize)]
+pub struct SimilarParam {
+    index_uid: String,
+    document_id: String,
+}
+
+#[derive(Debug, deserr::Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SimilarQueryGet {
+    #[deserr(default = Param(DEFAULT_SEARCH_OFFSET()), error = DeserrQueryParamError<InvalidSimilarOffset>)]
+    offset: Param<usize>,
+    #[deserr(default = Param(DEFAULT_SEARCH_LIMIT()), error = DeserrQueryParamError<InvalidSimilarLimit>)]
+    limit: Param<usize>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarFilter>)]
+    filter: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarAttributesToRetrieve>)]
+    attributes_to_retrieve: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarShowRankingScore>)]
+    show_ranking_score: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarRetrieveVectors>)]
+    retrieve_vectors: Param<bool>,
+}
+
+pub async fn similar(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    path: web::Path<SimilarParam>,
+    params: AwebQueryParameter<SimilarQueryGet, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", params);
+    let SimilarParam { index_uid, document_id } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let SimilarQueryGet {
+        offset,
+        limit,
+        filter,
+        attributes_to_retrieve,
+        show_ranking_score,
+        retrieve_vectors,
+    } = params.into_inner();
+    if retrieve_vectors.0 {
+        index_scheduler
+            .features()?
+            .check_vector("Passing `retrieveVectors` as a query parameter")?;
+    }
+    let mut filter = filter.map(|f| match serde_json::from_str(&f) {
+        Ok(v) => v,
+        _ => Value::String(f),
+    });
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        filter = fuse_filters(filter, search_rules.filter);
+    }
+
+    analytics.publish(
+        "Similar GET".to_string(),
+        json!({
+            "filter": { "with_filter": filter.is_some() },
+            "pagination": { "offset": offset.0, "limit": limit.0 },
+            "scoring": { "show_ranking_score": show_ranking_score.0 },
+            "vectors": { "retrieve_vectors": retrieve_vectors.0 },
+        }),
+        Some(&req),
+    );
+
+    let query = SimilarQuery {
+        id: document_id,
+        offset: offset.0,
+        limit: limit.0,
+        filter,
+        attributes_to_retrieve: attributes_to_retrieve.map(|o| o.into_iter().collect()),
+        show_ranking_score: show_ranking_score.0,
+        retrieve_vectors: retrieve_vectors.0,
+    };
+
+    let index = index_scheduler.index(&index_uid)?;
+    let similar_result = logging::spawn_blocking(move || perform_similar(&index, query)).await??;
+
+    debug!("returns: {:?}", similar_result);
+    Ok(HttpResponse::Ok().json(similar_result))
+}
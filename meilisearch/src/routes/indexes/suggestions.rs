@@ -0,0 +1,76 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::{Suggest, Suggestion};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+const DEFAULT_LIMIT: usize = 10;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_suggestions))));
+}
+
+#[derive(Debug, deserr::Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SuggestionsQuery {
+    #[deserr(default, error = DeserrQueryParamError<InvalidSuggestionsQ>)]
+    q: String,
+    #[deserr(default = Param(DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidSuggestionsLimit>)]
+    limit: Param<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestionView {
+    text: String,
+    popularity: u64,
+    typos: u8,
+}
+
+impl From<Suggestion> for SuggestionView {
+    fn from(suggestion: Suggestion) -> Self {
+        let Suggestion { text, popularity, typos } = suggestion;
+        SuggestionView { text, popularity, typos }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SuggestionsResults {
+    results: Vec<SuggestionView>,
+}
+
+/// Completes the given prefix with the queries previously searched on the index.
+pub async fn get_suggestions(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<SuggestionsQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features()?.check_query_suggestions()?;
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let SuggestionsQuery { q, limit } = params.into_inner();
+
+    analytics.publish("Suggestions Seen".to_string(), json!({ "limit": limit.0 }), Some(&req));
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let suggestions = Suggest::new(q, &rtxn, &index).limit(limit.0).execute()?;
+    let results = SuggestionsResults { results: suggestions.into_iter().map(Into::into).collect() };
+
+    debug!("returns: {:?}", results);
+    Ok(HttpResponse::Ok().json(results))
+}
@@ -33,6 +33,8 @@ pub async fn get_metrics(
 
     crate::metrics::MEILISEARCH_DB_SIZE_BYTES.set(response.database_size as i64);
     crate::metrics::MEILISEARCH_USED_DB_SIZE_BYTES.set(response.used_database_size as i64);
+    crate::metrics::MEILISEARCH_PENDING_PAYLOADS_SIZE_BYTES
+        .set(response.pending_payloads_size as i64);
     crate::metrics::MEILISEARCH_INDEX_COUNT.set(response.indexes.len() as i64);
 
     for (index, value) in response.indexes.iter() {
@@ -19,24 +19,30 @@ use crate::extractors::authentication::GuardedData;
 const PAGINATION_DEFAULT_LIMIT: usize = 20;
 
 mod api_key;
+mod batches;
 mod dump;
 pub mod features;
+mod index_templates;
 pub mod indexes;
 mod metrics;
 mod multi_search;
+mod schedules;
 mod snapshot;
 mod swap_indexes;
 pub mod tasks;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::scope("/tasks").configure(tasks::configure))
+        .service(web::scope("/batches").configure(batches::configure))
         .service(web::resource("/health").route(web::get().to(get_health)))
         .service(web::scope("/keys").configure(api_key::configure))
         .service(web::scope("/dumps").configure(dump::configure))
         .service(web::scope("/snapshots").configure(snapshot::configure))
+        .service(web::scope("/schedules").configure(schedules::configure))
         .service(web::resource("/stats").route(web::get().to(get_stats)))
         .service(web::resource("/version").route(web::get().to(get_version)))
         .service(web::scope("/indexes").configure(indexes::configure))
+        .service(web::scope("/index-templates").configure(index_templates::configure))
         .service(web::scope("/multi-search").configure(multi_search::configure))
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
         .service(web::scope("/metrics").configure(metrics::configure))
@@ -234,6 +240,8 @@ pub struct Stats {
     pub database_size: u64,
     #[serde(skip)]
     pub used_database_size: u64,
+    /// The size of the payloads of the tasks waiting to be processed.
+    pub pending_payloads_size: u64,
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     pub last_update: Option<OffsetDateTime>,
     pub indexes: BTreeMap<String, indexes::IndexStats>,
@@ -287,7 +295,13 @@ pub fn create_all_stats(
     database_size += auth_controller.size()?;
     used_database_size += auth_controller.used_size()?;
 
-    let stats = Stats { database_size, used_database_size, last_update: last_task, indexes };
+    let stats = Stats {
+        database_size,
+        used_database_size,
+        pending_payloads_size: index_scheduler.update_files_size(),
+        last_update: last_task,
+        indexes,
+    };
     Ok(stats)
 }
 
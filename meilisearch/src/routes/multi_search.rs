@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use actix_http::StatusCode;
 use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
@@ -14,7 +16,8 @@ use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::search::{
-    add_search_rules, perform_search, SearchQueryWithIndex, SearchResultWithIndex,
+    add_search_rules, perform_search_with_cache, record_search, SearchQueryWithIndex,
+    SearchResultWithIndex,
 };
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -52,6 +55,8 @@ pub async fn multi_search_with_post(
             for (query_index, (index_uid, mut query)) in
                 queries.into_iter().map(SearchQueryWithIndex::into_index_query).enumerate()
             {
+                // the queries are performed one after the other, each one has its own time budget.
+                let started_at = Instant::now();
                 debug!("multi-search #{query_index}: called with params: {:?}", query);
 
                 // Check index from API key
@@ -76,14 +81,21 @@ pub async fn multi_search_with_post(
                     })
                     .with_index(query_index)?;
 
-                let search_result =
-                    tokio::task::spawn_blocking(move || perform_search(&index, query, features))
-                        .await
-                        .with_index(query_index)?;
+                let search_result = perform_search_with_cache(
+                    &index_scheduler,
+                    &index_uid,
+                    index,
+                    query,
+                    features,
+                    started_at,
+                )
+                .await;
+                let mut search_result = search_result.with_index(query_index)?;
+                record_search(&index_scheduler, features, &index_uid, &mut search_result);
 
                 search_results.push(SearchResultWithIndex {
                     index_uid: index_uid.into_inner(),
-                    result: search_result.with_index(query_index)?,
+                    result: search_result,
                 });
             }
             Ok(search_results)
@@ -0,0 +1,217 @@
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::{CronExpression, IndexScheduler, Schedule};
+use log::debug;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::tasks::{Kind, KindWithContent};
+use serde::Serialize;
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::documents::{
+    check_document_filter, check_documents_url, DocumentsFormat,
+};
+use crate::routes::tasks::DetailsView;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(list_schedules)))).service(
+        web::resource("/{name}")
+            .route(web::get().to(SeqHandler(get_schedule)))
+            .route(web::put().to(SeqHandler(put_schedule)))
+            .route(web::delete().to(SeqHandler(delete_schedule))),
+    );
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleView {
+    name: String,
+    cron: CronExpression,
+    #[serde(rename = "type")]
+    kind: Kind,
+    index_uid: Option<String>,
+    details: Option<DetailsView>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    next_run_at: Option<OffsetDateTime>,
+}
+
+impl ScheduleView {
+    fn new(name: String, schedule: Schedule) -> Self {
+        let Schedule { cron, kind, next_run_at } = schedule;
+        ScheduleView {
+            name,
+            cron,
+            kind: kind.as_kind(),
+            index_uid: kind.indexes().first().map(|index_uid| index_uid.to_string()),
+            details: kind.default_details().map(DetailsView::from),
+            next_run_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SchedulesResults {
+    results: Vec<ScheduleView>,
+}
+
+pub async fn list_schedules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let schedules = index_scheduler.schedules()?;
+    let results =
+        schedules.into_iter().map(|(name, schedule)| ScheduleView::new(name, schedule)).collect();
+    let results = SchedulesResults { results };
+
+    debug!("returns: {:?}", results);
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn get_schedule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    name: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let name = name.into_inner();
+    let schedule = index_scheduler.schedule(&name)?;
+    let schedule = ScheduleView::new(name, schedule);
+
+    debug!("returns: {:?}", schedule);
+    Ok(HttpResponse::Ok().json(schedule))
+}
+
+/// The operations that can be registered periodically.
+#[derive(Debug, Clone, Copy, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum ScheduledOperation {
+    SnapshotCreation,
+    DocumentDeletionByFilter,
+    DocumentAdditionFromUrl,
+}
+
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SchedulePayload {
+    #[deserr(error = DeserrJsonError<InvalidScheduleCron>, missing_field_error = DeserrJsonError::missing_schedule_cron)]
+    cron: String,
+    #[deserr(error = DeserrJsonError<InvalidScheduleOperation>, missing_field_error = DeserrJsonError::missing_schedule_operation)]
+    operation: ScheduledOperation,
+    #[deserr(default, error = DeserrJsonError<InvalidIndexUid>)]
+    index_uid: Option<IndexUid>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentUrl>)]
+    url: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFormat>)]
+    format: DocumentsFormat,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentAuthorization>)]
+    authorization: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidIndexPrimaryKey>)]
+    primary_key: Option<String>,
+}
+
+/// Returns the field required by the operation or an error with the code of the field.
+fn required<T>(
+    value: Option<T>,
+    field: &str,
+    operation: &str,
+    code: Code,
+) -> Result<T, ResponseError> {
+    value.ok_or_else(|| {
+        ResponseError::from_msg(
+            format!("The `{field}` field is required by the `{operation}` operation."),
+            code,
+        )
+    })
+}
+
+/// Creates or replaces a schedule registering its task at each date matching its cron expression.
+///
+/// A schedule registers tasks with every right, so only the keys with every action can manage them.
+pub async fn put_schedule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALL }>, Data<IndexScheduler>>,
+    name: web::Path<String>,
+    body: AwebJson<SchedulePayload, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let name = name.into_inner();
+    let SchedulePayload {
+        cron,
+        operation,
+        index_uid,
+        filter,
+        url,
+        format,
+        authorization,
+        primary_key,
+    } = body.into_inner();
+
+    let cron: CronExpression = cron
+        .parse()
+        .map_err(|e| ResponseError::from_msg(format!("{e}"), Code::InvalidScheduleCron))?;
+
+    let kind = match operation {
+        ScheduledOperation::SnapshotCreation => KindWithContent::SnapshotCreation,
+        ScheduledOperation::DocumentDeletionByFilter => {
+            let operation = "documentDeletionByFilter";
+            let index_uid = required(index_uid, "indexUid", operation, Code::InvalidIndexUid)?;
+            let filter = required(filter, "filter", operation, Code::InvalidDocumentFilter)?;
+            check_document_filter(&filter)?;
+            KindWithContent::DocumentDeletionByFilter {
+                index_uid: index_uid.into_inner(),
+                filter_expr: filter,
+            }
+        }
+        ScheduledOperation::DocumentAdditionFromUrl => {
+            let operation = "documentAdditionFromUrl";
+            let index_uid = required(index_uid, "indexUid", operation, Code::InvalidIndexUid)?;
+            let url = required(url, "url", operation, Code::InvalidDocumentUrl)?;
+            let format = check_documents_url(&url, format, None)?;
+            let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+            KindWithContent::DocumentAdditionFromUrl {
+                index_uid: index_uid.into_inner(),
+                primary_key,
+                method: IndexDocumentsMethod::ReplaceDocuments,
+                url,
+                has_authorization: authorization.is_some(),
+                authorization,
+                format,
+                allow_index_creation,
+                duplicate_ids_policy: None,
+            }
+        }
+    };
+
+    analytics.publish(
+        "Schedule Updated".to_string(),
+        json!({ "type": kind.as_kind() }),
+        Some(&req),
+    );
+
+    let schedule = index_scheduler.put_schedule(&name, cron, kind)?;
+    let schedule = ScheduleView::new(name, schedule);
+
+    debug!("returns: {:?}", schedule);
+    Ok(HttpResponse::Ok().json(schedule))
+}
+
+pub async fn delete_schedule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALL }>, Data<IndexScheduler>>,
+    name: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    analytics.publish("Schedule Deleted".to_string(), json!({}), Some(&req));
+
+    index_scheduler.delete_schedule(&name)?;
+    Ok(HttpResponse::NoContent().finish())
+}
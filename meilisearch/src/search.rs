@@ -1,35 +1,50 @@
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::str::FromStr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use base64::Engine;
+use deserr::{
+    DeserializeError, Deserr, ErrorKind, IntoValue, MergeWithError, ValueKind, ValuePointerRef,
+};
 use either::Either;
-use index_scheduler::RoFeatures;
+use index_scheduler::{IndexScheduler, RoFeatures};
 use indexmap::IndexMap;
 use log::warn;
 use meilisearch_auth::IndexSearchRules;
+use meilisearch_types::deserr::query_params::FromQueryParameter;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{
+    Code, DeserrAttributeBoostError, DeserrMinimumShouldMatchError,
+    DeserrRankingScoreThresholdError, ResponseError,
+};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::{
     dot_product_similarity, FacetValueHit, InternalError, OrderBy, SearchForFacetValues,
 };
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
+use milli::localized_attributes_rules::{locales_allow_list, match_pattern};
+use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
     AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, VectorOrArrayOfVectors, DEFAULT_VALUES_PER_FACET,
+    SortError, SpellingSuggestions, TermsMatchingStrategy, TimeBudget, VectorOrArrayOfVectors,
+    DEFAULT_VALUES_PER_FACET,
 };
 use ordered_float::OrderedFloat;
+use rayon::ThreadPool;
 use regex::Regex;
-use serde::Serialize;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::error::MeilisearchHttpError;
+use crate::logging;
+use crate::reranker::{rerank, RerankerHit};
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
@@ -39,6 +54,10 @@ pub const DEFAULT_CROP_LENGTH: fn() -> usize = || 10;
 pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
+pub const DEFAULT_HITS_PER_GROUP: fn() -> usize = || 1;
+
+/// The spelling suggestions are only computed when the query has at most this number of hits.
+const SPELLING_SUGGESTIONS_MAX_HITS: u64 = 3;
 
 #[derive(Debug, Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -79,12 +98,44 @@ pub struct SearchQuery {
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
     pub highlight_post_tag: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightSearchableOnly>, default)]
+    pub highlight_searchable_only: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropMarker>, default = DEFAULT_CROP_MARKER())]
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMinimumShouldMatch>)]
+    pub minimum_should_match: Option<MinimumShouldMatch>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributeBoosts>, default)]
+    pub attribute_boosts: Option<Vec<AttributeBoost>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
+    pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
+    pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPrefixSearch>)]
+    pub prefix_search: Option<bool>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchKeepStopWords>, default)]
+    pub keep_stop_words: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoDistanceUnit>, default)]
+    pub geo_distance_unit: GeoDistanceUnit,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_HITS_PER_GROUP(), error = DeserrJsonError<InvalidSearchHitsPerGroup>)]
+    pub hits_per_group: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowSpellingSuggestions>, default)]
+    pub show_spelling_suggestions: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowCandidatesHandle>, default)]
+    pub show_candidates_handle: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchWithinCandidates>)]
+    pub within_candidates: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>, default)]
+    pub retrieve_vectors: bool,
 }
 
 impl SearchQuery {
@@ -93,6 +144,73 @@ impl SearchQuery {
     }
 }
 
+/// A ranking score threshold, guaranteed to be between `0.0` and `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(f64) = TryFrom::try_from -> DeserrRankingScoreThresholdError)]
+pub struct RankingScoreThreshold(f64);
+
+impl RankingScoreThreshold {
+    pub fn as_f64(&self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for RankingScoreThreshold {
+    type Error = DeserrRankingScoreThresholdError;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if (0.0..=1.0).contains(&f) {
+            Ok(RankingScoreThreshold(f))
+        } else {
+            Err(DeserrRankingScoreThresholdError(f.to_string()))
+        }
+    }
+}
+
+impl FromQueryParameter for RankingScoreThreshold {
+    type Err = DeserrRankingScoreThresholdError;
+
+    fn from_query_param(p: &str) -> Result<Self, Self::Err> {
+        p.parse::<f64>()
+            .map_err(|_| DeserrRankingScoreThresholdError(p.to_owned()))
+            .and_then(RankingScoreThreshold::try_from)
+    }
+}
+
+/// An attribute and the boost applied to it for the attribute ranking rule,
+/// written `attribute^boost`. The boost defaults to `1` when omitted.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr)]
+#[deserr(try_from(&String) = FromStr::from_str -> DeserrAttributeBoostError)]
+pub struct AttributeBoost {
+    pub attribute: String,
+    pub boost: u16,
+}
+
+impl FromStr for AttributeBoost {
+    type Err = DeserrAttributeBoostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (attribute, boost) = match s.rsplit_once('^') {
+            Some((attribute, boost)) => {
+                let boost = boost
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|&boost| boost > 0)
+                    .ok_or_else(|| DeserrAttributeBoostError(s.to_owned()))?;
+                (attribute.trim(), boost)
+            }
+            None => (s.trim(), 1),
+        };
+
+        if attribute.is_empty() {
+            return Err(DeserrAttributeBoostError(s.to_owned()));
+        }
+
+        Ok(AttributeBoost { attribute: attribute.to_owned(), boost })
+    }
+}
+
 /// A `SearchQuery` + an index UID.
 // This struct contains the fields of `SearchQuery` inline.
 // This is because neither deserr nor serde support `flatten` when using `deny_unknown_fields.
@@ -138,12 +256,44 @@ pub struct SearchQueryWithIndex {
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
     pub highlight_post_tag: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightSearchableOnly>, default)]
+    pub highlight_searchable_only: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropMarker>, default = DEFAULT_CROP_MARKER())]
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMinimumShouldMatch>)]
+    pub minimum_should_match: Option<MinimumShouldMatch>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributeBoosts>, default)]
+    pub attribute_boosts: Option<Vec<AttributeBoost>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
+    pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
+    pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPrefixSearch>)]
+    pub prefix_search: Option<bool>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchKeepStopWords>, default)]
+    pub keep_stop_words: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoDistanceUnit>, default)]
+    pub geo_distance_unit: GeoDistanceUnit,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_HITS_PER_GROUP(), error = DeserrJsonError<InvalidSearchHitsPerGroup>)]
+    pub hits_per_group: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowSpellingSuggestions>, default)]
+    pub show_spelling_suggestions: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowCandidatesHandle>, default)]
+    pub show_candidates_handle: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchWithinCandidates>)]
+    pub within_candidates: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>, default)]
+    pub retrieve_vectors: bool,
 }
 
 impl SearchQueryWithIndex {
@@ -168,9 +318,25 @@ impl SearchQueryWithIndex {
             facets,
             highlight_pre_tag,
             highlight_post_tag,
+            highlight_searchable_only,
             crop_marker,
             matching_strategy,
+            minimum_should_match,
             attributes_to_search_on,
+            attribute_boosts,
+            ranking_score_threshold,
+            distinct,
+            prefix_search,
+            locales,
+            keep_stop_words,
+            geo_distance_unit,
+            group_by,
+            hits_per_group,
+            show_spelling_suggestions,
+            show_candidates_handle,
+            within_candidates,
+            search_after,
+            retrieve_vectors,
         } = self;
         (
             index_uid,
@@ -193,9 +359,25 @@ impl SearchQueryWithIndex {
                 facets,
                 highlight_pre_tag,
                 highlight_post_tag,
+                highlight_searchable_only,
                 crop_marker,
                 matching_strategy,
+                minimum_should_match,
                 attributes_to_search_on,
+                attribute_boosts,
+                ranking_score_threshold,
+                distinct,
+                prefix_search,
+                locales,
+                keep_stop_words,
+                geo_distance_unit,
+                group_by,
+                hits_per_group,
+                show_spelling_suggestions,
+                show_candidates_handle,
+                within_candidates,
+                search_after,
+                retrieve_vectors,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -227,6 +409,103 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum GeoDistanceUnit {
+    /// Meters, rounded to the meter
+    M,
+    /// Kilometers
+    Km,
+    /// Miles
+    Mi,
+}
+
+impl Default for GeoDistanceUnit {
+    fn default() -> Self {
+        Self::M
+    }
+}
+
+impl GeoDistanceUnit {
+    /// Converts a distance in meters into this unit, the kilometers and the miles keep three decimals.
+    fn convert(self, meters: f64) -> Value {
+        let unit = match self {
+            GeoDistanceUnit::M => return json!(meters.round() as usize),
+            GeoDistanceUnit::Km => 1000.0,
+            GeoDistanceUnit::Mi => 1609.344,
+        };
+        json!((meters / unit * 1000.0).round() / 1000.0)
+    }
+}
+
+/// The minimum number of query words a document must match with the `last` matching strategy,
+/// either a positive integer or a percentage of the query words, e.g. `75%`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimumShouldMatch {
+    Count(usize),
+    Percentage(u8),
+}
+
+impl FromStr for MinimumShouldMatch {
+    type Err = DeserrMinimumShouldMatchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DeserrMinimumShouldMatchError(s.to_owned());
+        match s.trim().strip_suffix('%') {
+            Some(percentage) => percentage
+                .trim()
+                .parse()
+                .ok()
+                .filter(|&percentage| percentage <= 100)
+                .map(MinimumShouldMatch::Percentage)
+                .ok_or_else(error),
+            None => s.trim().parse().map(MinimumShouldMatch::Count).map_err(|_| error()),
+        }
+    }
+}
+
+impl FromQueryParameter for MinimumShouldMatch {
+    type Err = DeserrMinimumShouldMatchError;
+
+    fn from_query_param(p: &str) -> Result<Self, Self::Err> {
+        p.parse()
+    }
+}
+
+impl<E> Deserr<E> for MinimumShouldMatch
+where
+    E: DeserializeError + MergeWithError<DeserrMinimumShouldMatchError>,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::Integer(count) => Ok(MinimumShouldMatch::Count(count as usize)),
+            deserr::Value::String(s) => {
+                s.parse().map_err(|error| deserr::take_cf_content(E::merge(None, error, location)))
+            }
+            value => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::Integer, ValueKind::String],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+impl From<MinimumShouldMatch> for milli::MinimumShouldMatch {
+    fn from(other: MinimumShouldMatch) -> Self {
+        match other {
+            MinimumShouldMatch::Count(count) => Self::Count(count),
+            MinimumShouldMatch::Percentage(percentage) => Self::Percentage(percentage),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserr)]
 #[deserr(rename_all = camelCase)]
 pub enum FacetValuesSort {
@@ -277,6 +556,32 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    /// The hits grouped by the `groupBy` attribute, the `hits` are empty in this case.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<SearchGroup>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spelling_suggestions: Option<Vec<String>>,
+    /// An opaque handle to the candidates, to restrict a following search with `withinCandidates`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidates_handle: Option<String>,
+    /// The cursor to give as `searchAfter` to get the following page, absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_search_after: Option<String>,
+    /// The search didn't have the time to entirely sort the documents.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    /// Identifies the search in the search analytics, to report the clicks on its hits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_uid: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchGroup {
+    pub value: String,
+    /// The number of candidates having this value.
+    pub count: u64,
+    pub hits: Vec<SearchHit>,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -312,7 +617,12 @@ pub struct FacetSearchResult {
 
 /// Incorporate search rules in search query
 pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
-    query.filter = match (query.filter.take(), rules.filter) {
+    query.filter = fuse_filters(query.filter.take(), rules.filter);
+}
+
+/// Combines a filter with the filter of the search rules, both must match.
+pub fn fuse_filters(filter: Option<Value>, rules_filter: Option<Value>) -> Option<Value> {
+    match (filter, rules_filter) {
         (None, rules_filter) => rules_filter,
         (filter, None) => filter,
         (Some(filter), Some(rules_filter)) => {
@@ -330,12 +640,26 @@ pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
     }
 }
 
+/// Returns the time budget of the searches made on the index, started at the reception of the request.
+fn search_time_budget(
+    index: &Index,
+    rtxn: &RoTxn,
+    started_at: Instant,
+) -> Result<TimeBudget, MeilisearchHttpError> {
+    let time_budget = match index.search_cutoff(rtxn).map_err(milli::Error::from)? {
+        Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
+        None => TimeBudget::default(),
+    };
+    Ok(time_budget.started_at(started_at))
+}
+
 fn prepare_search<'t>(
     index: &'t Index,
     rtxn: &'t RoTxn,
     query: &'t SearchQuery,
     features: RoFeatures,
-) -> Result<(milli::Search<'t>, bool, usize, usize), MeilisearchHttpError> {
+    time_budget: TimeBudget,
+) -> Result<(milli::Search<'t>, bool, usize, usize, usize), MeilisearchHttpError> {
     let mut search = index.search(rtxn);
 
     if query.vector.is_some() && query.q.is_some() {
@@ -354,8 +678,25 @@ fn prepare_search<'t>(
         search.searchable_attributes(searchable);
     }
 
+    if let Some(ref boosts) = query.attribute_boosts {
+        search.attribute_boosts(boosts.iter().map(|b| (b.attribute.clone(), b.boost)).collect());
+    }
+
+    if let Some(prefix_search) = query.prefix_search {
+        search.prefix_search(prefix_search);
+    }
+
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.iter().copied().map(Language::from).collect());
+    }
+
+    search.keep_stop_words(query.keep_stop_words);
+
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
+    if let Some(minimum_should_match) = query.minimum_should_match {
+        search.minimum_should_match(minimum_should_match.into());
+    }
 
     let max_total_hits = index
         .pagination_max_total_hits(rtxn)
@@ -363,20 +704,45 @@ fn prepare_search<'t>(
         .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
 
     search.exhaustive_number_hits(is_finite_pagination);
-    search.scoring_strategy(if query.show_ranking_score || query.show_ranking_score_details {
+    // The scores of the last hit are part of the cursor to the next page.
+    let detailed_scores = query.show_ranking_score
+        || query.show_ranking_score_details
+        || query.search_after.is_some();
+    search.scoring_strategy(if detailed_scores {
         ScoringStrategy::Detailed
     } else {
         ScoringStrategy::Skip
     });
 
-    if query.show_ranking_score_details {
-        features.check_score_details()?;
+    if query.vector.is_some() {
+        features.check_vector("Passing `vector` as a query parameter")?;
     }
 
-    if query.vector.is_some() {
-        features.check_vector()?;
+    if query.retrieve_vectors {
+        features.check_vector("Passing `retrieveVectors` as a query parameter")?;
+    }
+
+    if let Some(ranking_score_threshold) = query.ranking_score_threshold {
+        search.ranking_score_threshold(ranking_score_threshold.as_f64());
     }
 
+    if let Some(distinct) = &query.distinct {
+        search.distinct(distinct.clone());
+    }
+
+    if let Some(handle) = &query.within_candidates {
+        let revision = docids_token_revision(index, rtxn)?;
+        let candidates = decode_docids_token(revision, handle).map_err(|error| match error {
+            InvalidDocidsToken::Malformed => {
+                MeilisearchHttpError::MalformedCandidatesHandle(handle.clone())
+            }
+            InvalidDocidsToken::Expired => MeilisearchHttpError::ExpiredCandidatesHandle,
+        })?;
+        search.candidates(candidates);
+    }
+
+    search.time_budget(time_budget);
+
     // compute the offset on the limit depending on the pagination mode.
     let (offset, limit) = if is_finite_pagination {
         let limit = query.hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -413,22 +779,203 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
-    Ok((search, is_finite_pagination, max_total_hits, offset))
+    Ok((search, is_finite_pagination, max_total_hits, offset, limit))
+}
+
+/// Returns the revision of the index the documents ids tokens are bound to.
+///
+/// The internal ids of the documents are reused once the documents are deleted, a token
+/// must therefore be rejected as soon as the index is updated, and not only its settings.
+fn docids_token_revision(index: &Index, rtxn: &RoTxn) -> Result<[u8; 16], MeilisearchHttpError> {
+    Ok(index.updated_at(rtxn)?.unix_timestamp_nanos().to_be_bytes())
+}
+
+/// Serializes documents ids with the revision of the index into an url-safe token.
+fn encode_docids_token(
+    index: &Index,
+    rtxn: &RoTxn,
+    docids: &RoaringBitmap,
+) -> Result<String, MeilisearchHttpError> {
+    let mut bytes = docids_token_revision(index, rtxn)?.to_vec();
+    docids.serialize_into(&mut bytes).map_err(milli::Error::from)?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// The reasons a documents ids token returned by a previous search is rejected.
+enum InvalidDocidsToken {
+    Malformed,
+    Expired,
+}
+
+/// Returns the content of a token returned by a previous search on the same index.
+fn decode_token_content(revision: [u8; 16], token: &str) -> Result<Vec<u8>, InvalidDocidsToken> {
+    let mut bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(token)
+        .map_err(|_| InvalidDocidsToken::Malformed)?;
+    if bytes.len() < revision.len() {
+        return Err(InvalidDocidsToken::Malformed);
+    }
+
+    if bytes[..revision.len()] != revision {
+        return Err(InvalidDocidsToken::Expired);
+    }
+    Ok(bytes.split_off(revision.len()))
+}
+
+/// Deserializes the documents ids of a token returned by a previous search on the same index.
+fn decode_docids_token(
+    revision: [u8; 16],
+    token: &str,
+) -> Result<RoaringBitmap, InvalidDocidsToken> {
+    let docids = decode_token_content(revision, token)?;
+    RoaringBitmap::deserialize_from(&docids[..]).map_err(|_| InvalidDocidsToken::Malformed)
+}
+
+/// The position of the last hit returned by a search, used to return the next page.
+#[derive(Serialize, Deserialize)]
+struct SearchAfterCursor {
+    /// The number of hits returned by the previous pages, a search never
+    /// returns more hits than the maximum total hits of the index.
+    returned: usize,
+    #[serde(flatten)]
+    after: milli::SearchAfter,
+}
+
+/// Serializes a cursor with the revision of the index into an url-safe token.
+fn encode_search_after(
+    index: &Index,
+    rtxn: &RoTxn,
+    cursor: &SearchAfterCursor,
+) -> Result<String, MeilisearchHttpError> {
+    let mut bytes = docids_token_revision(index, rtxn)?.to_vec();
+    serde_json::to_writer(&mut bytes, cursor)?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Deserializes the cursor returned by a previous search on the same index.
+fn decode_search_after(
+    revision: [u8; 16],
+    token: &str,
+) -> Result<SearchAfterCursor, InvalidDocidsToken> {
+    let cursor = decode_token_content(revision, token)?;
+    serde_json::from_slice(&cursor).map_err(|_| InvalidDocidsToken::Malformed)
 }
 
+/// Performs the search, its time budget and processing time are computed from `started_at`,
+/// the reception of the request.
 pub fn perform_search(
     index: &Index,
     query: SearchQuery,
     features: RoFeatures,
+    thread_pool: Option<&ThreadPool>,
+    started_at: Instant,
 ) -> Result<SearchResult, MeilisearchHttpError> {
-    let before_search = Instant::now();
     let rtxn = index.read_txn()?;
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, features)?;
+    let time_budget = search_time_budget(index, &rtxn, started_at)?;
+    let (mut search, is_finite_pagination, max_total_hits, offset, limit) =
+        prepare_search(index, &rtxn, &query, features, time_budget)?;
+    if let Some(thread_pool) = thread_pool {
+        search.thread_pool(thread_pool);
+    }
 
-    let milli::SearchResult { documents_ids, matching_words, candidates, document_scores, .. } =
-        search.execute()?;
+    // The cursor contains the scores and the id of the last hit returned by the previous
+    // page, the search resumes right after it instead of ranking and skipping the previous
+    // pages again. An empty cursor starts from the first page.
+    let returned = match query.search_after.as_deref() {
+        Some(_) if is_finite_pagination || query.offset != 0 || query.group_by.is_some() => {
+            return Err(MeilisearchHttpError::SearchAfterWithPagination);
+        }
+        Some("") => Some(0),
+        Some(cursor) => {
+            let revision = docids_token_revision(index, &rtxn)?;
+            let SearchAfterCursor { returned, after } = decode_search_after(revision, cursor)
+                .map_err(|error| match error {
+                    InvalidDocidsToken::Malformed => {
+                        MeilisearchHttpError::MalformedSearchAfter(cursor.to_string())
+                    }
+                    InvalidDocidsToken::Expired => MeilisearchHttpError::ExpiredSearchAfter,
+                })?;
+            search.search_after(after);
+            Some(returned)
+        }
+        None => None,
+    };
+
+    // The previous pages count in the maximum total hits of the index.
+    let limit = match returned {
+        Some(returned) => {
+            let limit = min(limit, max_total_hits.saturating_sub(returned));
+            search.limit(limit);
+            limit
+        }
+        None => limit,
+    };
+
+    // The best hits are retrieved to be reordered by the reranker,
+    // the requested hits are only selected once they are reranked.
+    let reranker = match index.reranker(&rtxn)? {
+        Some(reranker)
+            if query.q.as_deref().map_or(false, |q| !q.trim().is_empty())
+                && query.group_by.is_none()
+                && returned.is_none()
+                && limit != 0
+                && offset + limit <= reranker.top_k =>
+        {
+            search.offset(0);
+            search.limit(reranker.top_k);
+            Some(reranker)
+        }
+        _ => None,
+    };
+
+    // The documents of the groups are formatted one after the other
+    // and split back into their groups once formatted.
+    let (documents_ids, matching_words, candidates, document_scores, degraded, groups) =
+        match &query.group_by {
+            Some(group_by) => {
+                let milli::GroupsResult { matching_words, candidates, groups, degraded } =
+                    search.execute_groups(group_by, query.hits_per_group, max_total_hits)?;
+
+                let mut documents_ids = Vec::new();
+                let mut document_scores = Vec::new();
+                let mut groups_info = Vec::new();
+                for group in groups {
+                    groups_info.push((group.value, group.count, group.documents_ids.len()));
+                    documents_ids.extend(group.documents_ids);
+                    document_scores.extend(group.document_scores);
+                }
+                (
+                    documents_ids,
+                    matching_words,
+                    candidates,
+                    document_scores,
+                    degraded,
+                    Some(groups_info),
+                )
+            }
+            None => {
+                let milli::SearchResult {
+                    documents_ids,
+                    matching_words,
+                    candidates,
+                    document_scores,
+                    degraded,
+                } = search.execute()?;
+                (documents_ids, matching_words, candidates, document_scores, degraded, None)
+            }
+        };
+
+    // A page that isn't full is the last one, it is always the case
+    // once the maximum total hits of the index are reached.
+    let next_search_after = match (returned, documents_ids.last(), document_scores.last()) {
+        (Some(returned), Some(&docid), Some(scores)) if documents_ids.len() == limit => {
+            let after = milli::SearchAfter { scores: scores.clone(), docid };
+            let cursor = SearchAfterCursor { returned: returned + documents_ids.len(), after };
+            Some(encode_search_after(index, &rtxn, &cursor)?)
+        }
+        _ => None,
+    };
 
     let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
 
@@ -456,7 +1003,7 @@ pub fn perform_search(
     // but these attributes must be also be present
     // - in the fields_ids_map
     // - in the the displayed attributes
-    let to_retrieve_ids: BTreeSet<_> = query
+    let mut to_retrieve_ids: BTreeSet<_> = query
         .attributes_to_retrieve
         .as_ref()
         .map(fids)
@@ -464,9 +1011,46 @@ pub fn perform_search(
         .intersection(&displayed_ids)
         .cloned()
         .collect();
+    retrieve_vectors(&mut to_retrieve_ids, query.retrieve_vectors, &fields_ids_map, &displayed_ids);
+
+    let (documents_ids, document_scores) = match reranker {
+        Some(reranker) => {
+            // All the displayed attributes are sent when none are specified.
+            let reranker_fields: BTreeSet<_> = reranker.fields.iter().cloned().collect();
+            let reranker_ids = if reranker_fields.is_empty() {
+                displayed_ids.clone()
+            } else {
+                fids(&reranker_fields).intersection(&displayed_ids).cloned().collect()
+            };
+
+            let mut hits = Vec::with_capacity(documents_ids.len());
+            let documents = index.documents(&rtxn, documents_ids.iter().copied())?;
+            for ((id, obkv), score) in documents.into_iter().zip(&document_scores) {
+                let document = make_document(&reranker_ids, &fields_ids_map, obkv)?;
+                let ranking_score = ScoreDetails::global_score(score.iter());
+                hits.push(RerankerHit { id, ranking_score, document });
+            }
+
+            let q = query.q.as_deref().unwrap_or_default();
+            let positions =
+                rerank(&reranker, q, &hits).unwrap_or_else(|| (0..hits.len()).collect());
+            positions
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|position| (documents_ids[position], document_scores[position].clone()))
+                .unzip()
+        }
+        None => (documents_ids, document_scores),
+    };
 
     let attr_to_highlight = query.attributes_to_highlight.unwrap_or_default();
 
+    // The non-searchable attributes never contain any match, they are
+    // highlighted only if the searchable ones are not explicitly required.
+    let searchable_fields =
+        if query.highlight_searchable_only { index.searchable_fields(&rtxn)? } else { None };
+
     let attr_to_crop = query.attributes_to_crop.unwrap_or_default();
 
     // Attributes in `formatted_options` correspond to the attributes that will be in `_formatted`
@@ -476,6 +1060,7 @@ pub fn perform_search(
     // But these attributes must be also present in displayed attributes
     let formatted_options = compute_formatted_options(
         &attr_to_highlight,
+        searchable_fields.as_deref(),
         &attr_to_crop,
         query.crop_length,
         &to_retrieve_ids,
@@ -486,7 +1071,21 @@ pub fn perform_search(
     let mut tokenizer_builder = TokenizerBuilder::default();
     tokenizer_builder.create_char_map(true);
 
-    let script_lang_map = index.script_language(&rtxn)?;
+    // The stop words must be the same as the ones used at indexing and search time,
+    // otherwise the matching words of a phrase containing stop words are not highlighted.
+    let stop_words = index.stop_words(&rtxn)?;
+    if let Some(ref stop_words) = stop_words {
+        tokenizer_builder.stop_words(stop_words);
+    }
+
+    // The query locales must also be forced on the highlighted attributes.
+    let script_lang_map = match &query.locales {
+        Some(locales) => {
+            let locales: Vec<_> = locales.iter().copied().map(Language::from).collect();
+            locales_allow_list(&locales)
+        }
+        None => index.script_language(&rtxn)?,
+    };
     if !script_lang_map.is_empty() {
         tokenizer_builder.allow_list(&script_lang_map);
     }
@@ -506,10 +1105,18 @@ pub fn perform_search(
     }
 
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer_builder.build());
+    formatter_builder.normalize_numbers(index.normalize_numbers(&rtxn)?);
+    formatter_builder.normalization(index.normalization_params(&rtxn)?);
+    formatter_builder.stemming_languages(index.stemming_languages(&rtxn)?);
     formatter_builder.crop_marker(query.crop_marker);
     formatter_builder.highlight_prefix(query.highlight_pre_tag);
     formatter_builder.highlight_suffix(query.highlight_post_tag);
 
+    let geo_radius_center = match query.filter.as_ref().map(parse_filter).transpose()?.flatten() {
+        Some(filter) => filter.geo_radius_center(&rtxn, index)?,
+        None => None,
+    };
+
     let mut documents = Vec::new();
     let documents_iter = index.documents(&rtxn, documents_ids)?;
 
@@ -533,9 +1140,12 @@ pub fn perform_search(
             &displayed_ids,
         )?;
 
-        if let Some(sort) = query.sort.as_ref() {
-            insert_geo_distance(sort, &mut document);
-        }
+        insert_geo_distance(
+            query.sort.as_deref().unwrap_or_default(),
+            geo_radius_center,
+            query.geo_distance_unit,
+            &mut document,
+        );
 
         let semantic_score = match query.vector.as_ref() {
             Some(vector) => match extract_field("_vectors", &fields_ids_map, obkv)? {
@@ -561,6 +1171,35 @@ pub fn perform_search(
         documents.push(hit);
     }
 
+    let groups = groups.map(|groups| {
+        let mut hits = documents.drain(..);
+        groups
+            .into_iter()
+            .map(|(value, count, len)| SearchGroup {
+                value,
+                count,
+                hits: hits.by_ref().take(len).collect(),
+            })
+            .collect()
+    });
+
+    let spelling_suggestions = match &query.q {
+        Some(q) if query.show_spelling_suggestions => {
+            if candidates.len() <= SPELLING_SUGGESTIONS_MAX_HITS {
+                Some(SpellingSuggestions::new(q.as_str(), &rtxn, index).execute()?)
+            } else {
+                Some(Vec::new())
+            }
+        }
+        _ => None,
+    };
+
+    let candidates_handle = if query.show_candidates_handle {
+        Some(encode_docids_token(index, &rtxn, &candidates)?)
+    } else {
+        None
+    };
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = query.hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -582,33 +1221,40 @@ pub fn perform_search(
     let (facet_distribution, facet_stats) = match query.facets {
         Some(ref fields) => {
             let mut facet_distribution = index.facets_distribution(&rtxn);
+            if let Some(thread_pool) = thread_pool {
+                facet_distribution.thread_pool(thread_pool);
+            }
 
             let max_values_by_facet = index
                 .max_values_per_facet(&rtxn)
                 .map_err(milli::Error::from)?
                 .unwrap_or(DEFAULT_VALUES_PER_FACET);
             facet_distribution.max_values_per_facet(max_values_by_facet);
+            facet_distribution.time_budget(time_budget);
 
             let sort_facet_values_by =
                 index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
             let default_sort_facet_values_by =
                 sort_facet_values_by.get("*").copied().unwrap_or_default();
 
-            if fields.iter().all(|f| f != "*") {
-                let fields: Vec<_> = fields
-                    .iter()
-                    .map(|n| {
-                        (
-                            n,
-                            sort_facet_values_by
-                                .get(n)
-                                .copied()
-                                .unwrap_or(default_sort_facet_values_by),
-                        )
-                    })
-                    .collect();
-                facet_distribution.facets(fields);
-            }
+            // `*` means all the filterable attributes, but each of them must
+            // still be sorted according to its own `sortFacetValuesBy` setting.
+            let fields: Vec<_> = if fields.iter().any(|f| f == "*") {
+                index.filterable_fields(&rtxn).map_err(milli::Error::from)?.into_iter().collect()
+            } else {
+                fields.clone()
+            };
+            let fields: Vec<_> = fields
+                .into_iter()
+                .map(|n| {
+                    let order_by = sort_facet_values_by
+                        .get(&n)
+                        .copied()
+                        .unwrap_or(default_sort_facet_values_by);
+                    (n, order_by)
+                })
+                .collect();
+            facet_distribution.facets(fields);
             let distribution = facet_distribution
                 .candidates(candidates)
                 .default_order_by(default_sort_facet_values_by)
@@ -618,6 +1264,8 @@ pub fn perform_search(
         }
         None => (None, None),
     };
+    // the facet values are not all counted once the time budget is exceeded.
+    let degraded = degraded || (query.facets.is_some() && time_budget.exceeded());
 
     let facet_stats = facet_stats.map(|stats| {
         stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect()
@@ -628,13 +1276,87 @@ pub fn perform_search(
         hits_info,
         query: query.q.unwrap_or_default(),
         vector: query.vector,
-        processing_time_ms: before_search.elapsed().as_millis(),
+        processing_time_ms: started_at.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        groups,
+        spelling_suggestions,
+        candidates_handle,
+        next_search_after,
+        degraded,
+        search_uid: None,
     };
     Ok(result)
 }
 
+/// Performs the search on a blocking thread, or returns the response of an identical search
+/// made since the last modification of the index when the search cache is enabled.
+pub async fn perform_search_with_cache(
+    index_scheduler: &IndexScheduler,
+    index_uid: &str,
+    index: Index,
+    query: SearchQuery,
+    features: RoFeatures,
+    started_at: Instant,
+) -> Result<SearchResult, MeilisearchHttpError> {
+    let thread_pool = index_scheduler.search_thread_pool();
+    let cache = index_scheduler.search_cache();
+    if !cache.is_enabled() {
+        return logging::spawn_blocking(move || {
+            perform_search(&index, query, features, thread_pool.as_deref(), started_at)
+        })
+        .await?;
+    }
+
+    // The query contains every parameter of the search, including the filters of the tenant token.
+    let key = format!("{query:?}");
+    let revision = cache.revision(index_uid);
+    if let Some(mut result) = cache.get::<SearchResult>(index_uid, &key) {
+        // the cached processing time is the one of the search that filled the cache
+        result.processing_time_ms = started_at.elapsed().as_millis();
+        return Ok(result);
+    }
+
+    let result = logging::spawn_blocking(move || {
+        perform_search(&index, query, features, thread_pool.as_deref(), started_at)
+    })
+    .await??;
+    // a degraded response is not cached, searching again may return the complete results.
+    if !result.degraded {
+        cache.insert(index_uid, revision, key, result.clone());
+    }
+    Ok(result)
+}
+
+/// Records the search in the search analytics of the index, if they are enabled.
+///
+/// The query of a search returning hits is also recorded in the suggestions
+/// of the index, when the query suggestions are enabled.
+pub fn record_search(
+    index_scheduler: &IndexScheduler,
+    features: RoFeatures,
+    index_uid: &str,
+    result: &mut SearchResult,
+) {
+    let hits = match result.hits_info {
+        HitsInfo::Pagination { total_hits, .. } => total_hits,
+        HitsInfo::OffsetLimit { estimated_total_hits, .. } => estimated_total_hits,
+    };
+
+    if features.check_query_suggestions().is_ok() && hits > 0 {
+        index_scheduler.query_suggestions().record(index_uid, &result.query);
+    }
+
+    if features.check_search_analytics().is_ok() {
+        result.search_uid = index_scheduler.search_analytics().record(
+            index_uid,
+            &result.query,
+            hits as u64,
+            result.processing_time_ms as u64,
+        );
+    }
+}
+
 pub fn perform_facet_search(
     index: &Index,
     search_query: SearchQuery,
@@ -644,8 +1366,12 @@ pub fn perform_facet_search(
 ) -> Result<FacetSearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
+    if !index.facet_search(&rtxn)? {
+        return Err(MeilisearchHttpError::FacetSearchDisabled);
+    }
 
-    let (search, _, _, _) = prepare_search(index, &rtxn, &search_query, features)?;
+    let time_budget = search_time_budget(index, &rtxn, before_search)?;
+    let (search, _, _, _, _) = prepare_search(index, &rtxn, &search_query, features, time_budget)?;
     let mut facet_search = SearchForFacetValues::new(facet_name, search);
     if let Some(facet_query) = &facet_query {
         facet_search.query(facet_query);
@@ -658,18 +1384,151 @@ pub fn perform_facet_search(
     })
 }
 
-fn insert_geo_distance(sorts: &[String], document: &mut Document) {
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarQuery {
+    pub id: String,
+    pub offset: usize,
+    pub limit: usize,
+    pub filter: Option<Value>,
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+    pub show_ranking_score: bool,
+    pub retrieve_vectors: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarResult {
+    pub hits: Vec<SearchHit>,
+    pub id: String,
+    pub processing_time_ms: u128,
+    #[serde(flatten)]
+    pub hits_info: HitsInfo,
+}
+
+pub fn perform_similar(index: &Index, query: SimilarQuery) -> Result<SimilarResult, ResponseError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let internal_id = index
+        .external_documents_ids(&rtxn)?
+        .get(query.id.as_bytes())
+        .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(query.id.clone()))?;
+
+    let mut similar = milli::Similar::new(internal_id, &rtxn, index);
+    similar.offset(query.offset);
+    similar.limit(query.limit);
+
+    if let Some(ref filter) = query.filter {
+        if let Some(filter) = parse_filter(filter)
+            .map_err(|err| ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter))?
+        {
+            similar.filter(filter);
+        }
+    }
+
+    let milli::SearchResult { documents_ids, candidates, document_scores, .. } =
+        similar.execute().map_err(|err| match err {
+            milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+                ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
+            }
+            e => e.into(),
+        })?;
+
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let displayed_ids = index
+        .displayed_fields_ids(&rtxn)?
+        .map(|fields| fields.into_iter().collect::<BTreeSet<_>>())
+        .unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
+
+    let to_retrieve_ids: BTreeSet<_> = match &query.attributes_to_retrieve {
+        Some(attrs) if !attrs.contains("*") => {
+            attrs.iter().filter_map(|attr| fields_ids_map.id(attr)).collect()
+        }
+        _ => displayed_ids.clone(),
+    };
+    let mut to_retrieve_ids: BTreeSet<_> =
+        to_retrieve_ids.intersection(&displayed_ids).cloned().collect();
+    retrieve_vectors(&mut to_retrieve_ids, query.retrieve_vectors, &fields_ids_map, &displayed_ids);
+
+    let mut hits = Vec::new();
+    let documents_iter = index.documents(&rtxn, documents_ids)?;
+    for ((_id, obkv), score) in documents_iter.into_iter().zip(document_scores.into_iter()) {
+        let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
+        let attributes_to_retrieve = to_retrieve_ids
+            .iter()
+            .map(|&fid| fields_ids_map.name(fid).expect("Missing field name"));
+        let document =
+            permissive_json_pointer::select_values(&displayed_document, attributes_to_retrieve);
+
+        let ranking_score =
+            query.show_ranking_score.then(|| ScoreDetails::global_score(score.iter()));
+
+        hits.push(SearchHit {
+            document,
+            formatted: Document::new(),
+            matches_position: None,
+            ranking_score_details: None,
+            ranking_score,
+            semantic_score: None,
+        });
+    }
+
+    let max_total_hits = index
+        .pagination_max_total_hits(&rtxn)
+        .map_err(milli::Error::from)?
+        .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+
+    Ok(SimilarResult {
+        hits,
+        id: query.id,
+        processing_time_ms: before_search.elapsed().as_millis(),
+        hits_info: HitsInfo::OffsetLimit {
+            limit: query.limit,
+            offset: query.offset,
+            estimated_total_hits: min(candidates.len() as usize, max_total_hits),
+        },
+    })
+}
+
+/// Inserts the distance of the document to the `_geoPoint` of the sort or,
+/// if there is none, to the center of the `_geoRadius` of the filter.
+fn insert_geo_distance(
+    sorts: &[String],
+    geo_radius_center: Option<[f64; 2]>,
+    unit: GeoDistanceUnit,
+    document: &mut Document,
+) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
             Regex::new(r"_geoPoint\(\s*([[:digit:].\-]+)\s*,\s*([[:digit:].\-]+)\s*\)").unwrap();
     };
-    if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
-        // TODO: TAMO: milli encountered an internal error, what do we want to do?
-        let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
+    let geo_point_base =
+        sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)).map(|capture_group| {
+            // TODO: TAMO: milli encountered an internal error, what do we want to do?
+            [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()]
+        });
+    if let Some(base) = geo_point_base.or(geo_radius_center) {
         let geo_point = &document.get("_geo").unwrap_or(&json!(null));
         if let Some((lat, lng)) = geo_point["lat"].as_f64().zip(geo_point["lng"].as_f64()) {
             let distance = milli::distance_between_two_points(&base, &[lat, lng]);
-            document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
+            document.insert("_geoDistance".to_string(), unit.convert(distance));
+        }
+    }
+}
+
+/// The `_vectors` field is only returned when asked for with `retrieveVectors`,
+/// even if the other attributes are not explicitly selected.
+fn retrieve_vectors(
+    to_retrieve_ids: &mut BTreeSet<FieldId>,
+    retrieve_vectors: bool,
+    fields_ids_map: &FieldsIdsMap,
+    displayed_ids: &BTreeSet<FieldId>,
+) {
+    if let Some(vectors_fid) = fields_ids_map.id("_vectors") {
+        if retrieve_vectors && displayed_ids.contains(&vectors_fid) {
+            to_retrieve_ids.insert(vectors_fid);
+        } else {
+            to_retrieve_ids.remove(&vectors_fid);
         }
     }
 }
@@ -688,6 +1547,7 @@ fn compute_semantic_score(query: &[f32], vectors: Value) -> milli::Result<Option
 
 fn compute_formatted_options(
     attr_to_highlight: &HashSet<String>,
+    searchable_fields: Option<&[&str]>,
     attr_to_crop: &[String],
     query_crop_length: usize,
     to_retrieve_ids: &BTreeSet<FieldId>,
@@ -699,6 +1559,7 @@ fn compute_formatted_options(
     add_highlight_to_formatted_options(
         &mut formatted_options,
         attr_to_highlight,
+        searchable_fields,
         fields_ids_map,
         displayed_ids,
     );
@@ -719,26 +1580,36 @@ fn compute_formatted_options(
     formatted_options
 }
 
+/// The attributes to highlight are patterns, e.g. `*` or `title.*`, and the ones starting
+/// with a `-` exclude the matching attributes. When only exclusions are given, all the
+/// other displayed attributes are highlighted.
 fn add_highlight_to_formatted_options(
     formatted_options: &mut BTreeMap<FieldId, FormatOptions>,
     attr_to_highlight: &HashSet<String>,
+    searchable_fields: Option<&[&str]>,
     fields_ids_map: &FieldsIdsMap,
     displayed_ids: &BTreeSet<FieldId>,
 ) {
-    for attr in attr_to_highlight {
-        let new_format = FormatOptions { highlight: true, crop: None };
+    let (exclusions, mut inclusions): (Vec<_>, Vec<_>) =
+        attr_to_highlight.iter().map(String::as_str).partition(|attr| attr.starts_with('-'));
+    if inclusions.is_empty() && !exclusions.is_empty() {
+        inclusions.push("*");
+    }
 
-        if attr == "*" {
-            for id in displayed_ids {
-                formatted_options.insert(*id, new_format);
-            }
-            break;
-        }
+    let is_searchable = |name: &str| match searchable_fields {
+        Some(fields) => fields.iter().any(|field| {
+            name == *field
+                || name.strip_prefix(field).map_or(false, |nested| nested.starts_with('.'))
+        }),
+        None => true,
+    };
 
-        if let Some(id) = fields_ids_map.id(attr) {
-            if displayed_ids.contains(&id) {
-                formatted_options.insert(id, new_format);
-            }
+    for id in displayed_ids {
+        let Some(name) = fields_ids_map.name(*id) else { continue };
+        let included = inclusions.iter().any(|pattern| match_pattern(pattern, name));
+        let excluded = exclusions.iter().any(|pattern| match_pattern(&pattern[1..], name));
+        if included && !excluded && is_searchable(name) {
+            formatted_options.insert(*id, FormatOptions { highlight: true, crop: None });
         }
     }
 }
@@ -751,22 +1622,28 @@ fn add_crop_to_formatted_options(
     displayed_ids: &BTreeSet<FieldId>,
 ) {
     for attr in attr_to_crop {
-        let mut split = attr.rsplitn(2, ':');
-        let (attr_name, attr_len) = match split.next().zip(split.next()) {
-            Some((len, name)) => {
-                let crop_len = len.parse::<usize>().unwrap_or(crop_length);
-                (name, crop_len)
-            }
+        // Only treat the suffix as a crop length when it is a number, so that
+        // attribute names containing a `:` are still matched as a whole.
+        let (attr_name, attr_len) = match attr.rsplit_once(':') {
+            Some((name, len)) => match len.parse::<usize>() {
+                Ok(crop_len) => (name, crop_len),
+                Err(_) => (attr.as_str(), crop_length),
+            },
             None => (attr.as_str(), crop_length),
         };
 
         if attr_name == "*" {
+            // The wildcard must not override a crop length explicitly
+            // requested for a specific attribute.
             for id in displayed_ids {
                 formatted_options
                     .entry(*id)
-                    .and_modify(|f| f.crop = Some(attr_len))
+                    .and_modify(|f| {
+                        f.crop.get_or_insert(attr_len);
+                    })
                     .or_insert(FormatOptions { highlight: false, crop: Some(attr_len) });
             }
+            continue;
         }
 
         if let Some(id) = fields_ids_map.id(attr_name) {
@@ -1021,18 +1898,18 @@ mod test {
 
         let sorters = &["_geoPoint(50.629973371633746,3.0569447399419567):desc".to_string()];
         let mut document = value.clone();
-        insert_geo_distance(sorters, &mut document);
+        insert_geo_distance(sorters, None, GeoDistanceUnit::M, &mut document);
         assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 
         let sorters = &["_geoPoint(50.629973371633746, 3.0569447399419567):asc".to_string()];
         let mut document = value.clone();
-        insert_geo_distance(sorters, &mut document);
+        insert_geo_distance(sorters, None, GeoDistanceUnit::M, &mut document);
         assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 
         let sorters =
             &["_geoPoint(   50.629973371633746   ,  3.0569447399419567   ):desc".to_string()];
         let mut document = value.clone();
-        insert_geo_distance(sorters, &mut document);
+        insert_geo_distance(sorters, None, GeoDistanceUnit::M, &mut document);
         assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 
         let sorters = &[
@@ -1043,7 +1920,7 @@ mod test {
         ]
         .map(|s| s.to_string());
         let mut document = value.clone();
-        insert_geo_distance(sorters, &mut document);
+        insert_geo_distance(sorters, None, GeoDistanceUnit::M, &mut document);
         assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 
         // only the first geoPoint is used to compute the distance
@@ -1056,13 +1933,37 @@ mod test {
         ]
         .map(|s| s.to_string());
         let mut document = value.clone();
-        insert_geo_distance(sorters, &mut document);
+        insert_geo_distance(sorters, None, GeoDistanceUnit::M, &mut document);
         assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 
         // there was no _geoPoint so nothing is inserted in the document
         let sorters = &["chien:asc".to_string()];
-        let mut document = value;
-        insert_geo_distance(sorters, &mut document);
+        let mut document = value.clone();
+        insert_geo_distance(sorters, None, GeoDistanceUnit::M, &mut document);
         assert_eq!(document.get("_geoDistance"), None);
+
+        // without _geoPoint the center of the _geoRadius is used
+        let mut document = value.clone();
+        insert_geo_distance(
+            sorters,
+            Some([50.629973371633746, 3.0569447399419567]),
+            GeoDistanceUnit::M,
+            &mut document,
+        );
+        assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+        // the _geoPoint has the priority over the center of the _geoRadius
+        let sorters = &["_geoPoint(50.629973371633746, 3.0569447399419567):asc".to_string()];
+        let mut document = value;
+        insert_geo_distance(sorters, Some([50.0, 3.0]), GeoDistanceUnit::M, &mut document);
+        assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+    }
+
+    #[test]
+    fn test_geo_distance_unit() {
+        assert_eq!(GeoDistanceUnit::M.convert(1234.5), json!(1235));
+        assert_eq!(GeoDistanceUnit::Km.convert(1234.5678), json!(1.235));
+        assert_eq!(GeoDistanceUnit::Mi.convert(1609.344), json!(1.0));
+        assert_eq!(GeoDistanceUnit::Mi.convert(0.4), json!(0.0));
     }
 }
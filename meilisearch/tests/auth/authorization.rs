@@ -14,6 +14,7 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("POST",    "/multi-search") =>                                    hashset!{"search", "*"},
             ("POST",    "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("GET",     "/indexes/products/search") =>                         hashset!{"search", "*"},
+            ("GET",     "/indexes/products/similar/0") =>                      hashset!{"search", "*"},
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
@@ -32,6 +33,7 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("GET",     "/indexes") =>                                         hashset!{"indexes.get", "indexes.*", "*"},
             ("POST",    "/swap-indexes") =>                                    hashset!{"indexes.swap", "indexes.*", "*"},
             ("GET",     "/indexes/products/settings") =>                       hashset!{"settings.get", "settings.*", "*"},
+            ("POST",    "/indexes/products/settings/diff") =>                  hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/indexes/products/settings/filterable-attributes") => hashset!{"settings.get", "settings.*", "*"},
@@ -590,6 +592,56 @@ async fn raise_error_non_authorized_index_patterns() {
     assert_eq!(response["status"], "succeeded");
 }
 
+#[actix_rt::test]
+async fn raise_error_non_authorized_index_template_patterns() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key(MASTER_KEY).await;
+
+    // create key with the settings update access on indices with products_* pattern.
+    let content = json!({
+        "indexes": ["products_*"],
+        "actions": ["settings.update"],
+        "expiresAt": (OffsetDateTime::now_utc() + Duration::hours(1)).format(&Rfc3339).unwrap(),
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    // the templates matching only indexes covered by the key are accepted
+    let (response, code) =
+        server.put_index_template("products", json!({ "uidPattern": "products_*" })).await;
+    assert_eq!(200, code, "{:?}", &response);
+    let (response, code) =
+        server.put_index_template("shoes", json!({ "uidPattern": "products_shoes*" })).await;
+    assert_eq!(200, code, "{:?}", &response);
+    let (response, code) =
+        server.put_index_template("first", json!({ "uidPattern": "products_1" })).await;
+    assert_eq!(200, code, "{:?}", &response);
+
+    // the templates matching other indexes are rejected with 403 -- invalid_api_key
+    for uid_pattern in [json!("products*"), json!("test"), json!("*"), json!(null)] {
+        let (response, code) =
+            server.put_index_template("other", json!({ "uidPattern": uid_pattern })).await;
+        assert_eq!(403, code, "{:?}", &response);
+        assert_eq!(response, INVALID_RESPONSE.clone());
+    }
+
+    // a template can't be replaced by a key that couldn't have created it
+    server.use_api_key(MASTER_KEY);
+    let (response, code) = server.put_index_template("global", json!({})).await;
+    assert_eq!(200, code, "{:?}", &response);
+    server.use_api_key(key);
+    let (response, code) =
+        server.put_index_template("global", json!({ "uidPattern": "products_*" })).await;
+    assert_eq!(403, code, "{:?}", &response);
+    let (response, code) = server.service.delete("/index-templates/global").await;
+    assert_eq!(403, code, "{:?}", &response);
+
+    let (response, code) = server.service.delete("/index-templates/products").await;
+    assert_eq!(204, code, "{:?}", &response);
+}
+
 #[actix_rt::test]
 async fn pattern_indexes() {
     // Create server with master key
@@ -83,6 +83,16 @@ impl Index<'_> {
         self.service.delete(url).await
     }
 
+    pub async fn duplicate(&self, new_uid: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/duplicate", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, json!({ "uid": new_uid }), self.encoder).await
+    }
+
+    pub async fn compact(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/compact", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, json!({}), self.encoder).await
+    }
+
     pub async fn add_documents(
         &self,
         documents: Value,
@@ -126,6 +136,20 @@ impl Index<'_> {
         self.service.put_encoded(url, documents, self.encoder).await
     }
 
+    pub async fn merge_documents(
+        &self,
+        documents: Value,
+        primary_key: Option<&str>,
+    ) -> (Value, StatusCode) {
+        let url = match primary_key {
+            Some(key) => {
+                format!("/indexes/{}/documents?primaryKey={}", urlencode(self.uid.as_ref()), key)
+            }
+            None => format!("/indexes/{}/documents", urlencode(self.uid.as_ref())),
+        };
+        self.service.patch_encoded(url, documents, self.encoder).await
+    }
+
     pub async fn raw_update_documents(
         &self,
         payload: &str,
@@ -258,11 +282,21 @@ impl Index<'_> {
         self.service.get(url).await
     }
 
+    pub async fn replace_settings(&self, settings: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
+        self.service.put(url, settings).await
+    }
+
     pub async fn update_settings(&self, settings: Value) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
         self.service.patch_encoded(url, settings, self.encoder).await
     }
 
+    pub async fn settings_diff(&self, settings: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/diff", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, settings, self.encoder).await
+    }
+
     pub async fn update_settings_displayed_attributes(
         &self,
         settings: Value,
@@ -385,6 +419,11 @@ impl Index<'_> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn similar(&self, id: &str, query: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/similar/{}?{}", urlencode(self.uid.as_ref()), id, query);
+        self.service.get(url).await
+    }
+
     pub async fn update_distinct_attribute(&self, value: Value) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
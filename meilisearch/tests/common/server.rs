@@ -202,6 +202,14 @@ impl Server {
     pub async fn set_features(&self, value: Value) -> (Value, StatusCode) {
         self.service.patch("/experimental-features", value).await
     }
+
+    pub async fn put_index_template(&self, name: &str, value: Value) -> (Value, StatusCode) {
+        self.service.put(format!("/index-templates/{name}"), value).await
+    }
+
+    pub async fn put_schedule(&self, name: &str, value: Value) -> (Value, StatusCode) {
+        self.service.put(format!("/schedules/{name}"), value).await
+    }
 }
 
 pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
@@ -1,10 +1,17 @@
+use std::sync::Arc;
+
 use actix_web::test;
+use byte_unit::{Byte, ByteUnit};
 use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
 use crate::common::encoder::Encoder;
-use crate::common::{GetAllDocumentsOptions, Server, Value};
+use crate::common::{default_settings, GetAllDocumentsOptions, Server, Value};
 use crate::json;
 
 /// This is the basic usage of our API and every other tests uses the content-type application/json
@@ -279,6 +286,125 @@ async fn add_csv_document() {
     "###);
 }
 
+/// Writes two pets in a parquet file, with a struct column and a list column.
+fn parquet_pets() -> Vec<u8> {
+    let schema = "message pet {
+        required int64 id;
+        required binary name (UTF8);
+        required group owner {
+            required binary name (UTF8);
+        }
+        required group tags (LIST) {
+            repeated group list {
+                required binary element (UTF8);
+            }
+        }
+    }";
+    let schema = Arc::new(parse_message_type(schema).unwrap());
+    let mut writer = SerializedFileWriter::new(Vec::new(), schema, Default::default()).unwrap();
+    let mut row_group = writer.next_row_group().unwrap();
+
+    let mut column = row_group.next_column().unwrap().unwrap();
+    column.typed::<Int64Type>().write_batch(&[0, 1], None, None).unwrap();
+    column.close().unwrap();
+
+    let byte_array_columns: [(&[&str], Option<&[i16]>, Option<&[i16]>); 3] = [
+        (&["jean", "jorts"], None, None),
+        (&["kero", "tamo"], None, None),
+        (&["dog", "fluffy", "cat"], Some(&[1, 1, 1]), Some(&[0, 1, 0])),
+    ];
+    for (values, def_levels, rep_levels) in byte_array_columns {
+        let values: Vec<ByteArray> = values.iter().map(|value| ByteArray::from(*value)).collect();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column.typed::<ByteArrayType>().write_batch(&values, def_levels, rep_levels).unwrap();
+        column.close().unwrap();
+    }
+
+    row_group.close().unwrap();
+    writer.into_inner().unwrap()
+}
+
+#[actix_rt::test]
+async fn add_parquet_documents() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let req = test::TestRequest::put()
+        .uri("/indexes/pets/documents")
+        .set_payload(parquet_pets())
+        .insert_header(("content-type", "application/vnd.apache.parquet"));
+    let (response, code) = server.service.request(req).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 2,
+      "indexedDocuments": 2
+    }
+    "###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "id": 0,
+          "name": "jean",
+          "owner": {
+            "name": "kero"
+          },
+          "tags": [
+            "dog",
+            "fluffy"
+          ]
+        },
+        {
+          "id": 1,
+          "name": "jorts",
+          "owner": {
+            "name": "tamo"
+          },
+          "tags": [
+            "cat"
+          ]
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+
+    // the fields of the struct columns are flattened like any nested field
+    index.update_settings_filterable_attributes(json!(["owner.name"])).await;
+    index.wait_task(1).await;
+    let (response, code) = index
+        .search_post(json!({ "filter": "owner.name = tamo", "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_malformed_parquet_documents() {
+    let server = Server::new().await;
+
+    let req = test::TestRequest::put()
+        .uri("/indexes/pets/documents")
+        .set_payload("id,name\n0,jean")
+        .insert_header(("content-type", "application/vnd.apache.parquet"));
+    let (response, code) = server.service.request(req).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""malformed_payload""###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_types() {
     let server = Server::new().await;
@@ -479,7 +605,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -500,7 +626,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -534,7 +660,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -554,7 +680,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -1789,6 +1915,33 @@ async fn error_add_documents_payload_size() {
     "###);
 }
 
+#[actix_rt::test]
+async fn error_add_documents_max_indexing_payload_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = Opt {
+        max_indexing_payload_size: Some(Byte::from_unit(1.0, ByteUnit::KiB).unwrap()),
+        ..default_settings(dir.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..100).map(|id| json!({ "id": id, "content": "doggo" })).collect();
+    let (response, code) = index.add_documents(json!(documents), None).await;
+    snapshot!(code, @"413 Payload Too Large");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The provided payload reached the size limit. The maximum accepted payload size is 1.00 KiB.",
+      "code": "payload_too_large",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#payload_too_large"
+    }
+    "###);
+
+    // the limit only applies to the documents payloads
+    let (_, code) = server.create_index(json!({ "uid": "test", "primaryKey": "id" })).await;
+    snapshot!(code, @"202 Accepted");
+}
+
 #[actix_rt::test]
 async fn error_primary_key_inference() {
     let server = Server::new().await;
@@ -1982,3 +2135,281 @@ async fn batch_several_documents_addition() {
     assert_eq!(code, 200, "failed with `{}`", response);
     assert_eq!(response["results"].as_array().unwrap().len(), 120);
 }
+
+#[actix_rt::test]
+async fn add_documents_matching_percolate_queries() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["price", "brand"],
+            "percolateQueries": {
+                "cheap-acme": "price < 100 AND brand = acme",
+                "expensive": "price >= 1000",
+            },
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let documents = json!([
+        { "id": 1, "price": 50, "brand": "acme" },
+        { "id": 2, "price": 500, "brand": "acme" },
+        { "id": 3, "price": 80, "brand": "globex" },
+        { "id": 4, "price": 20, "brand": "acme" },
+    ]);
+    let (response, code) = index.add_documents(documents, Some("id")).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 4,
+      "indexedDocuments": 4,
+      "matchedPercolateQueries": {
+        "cheap-acme": [
+          "1",
+          "4"
+        ]
+      }
+    }
+    "###);
+
+    // updating a document that matches no query doesn't report any match
+    let (response, _code) =
+        index.add_documents(json!([{ "id": 3, "price": 90, "brand": "globex" }]), None).await;
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 1,
+      "indexedDocuments": 1
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_from_url() {
+    // a remote server serving the documents to the requests with the right authorization
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let remote = actix_web::HttpServer::new(|| {
+        actix_web::App::new().route(
+            "/pets.ndjson",
+            actix_web::web::get().to(|req: actix_web::HttpRequest| async move {
+                match req.headers().get("Authorization") {
+                    Some(value) if value == "Bearer doggo" => actix_web::HttpResponse::Ok().body(
+                        "{\"id\": 1, \"name\": \"kefir\"}\n{\"id\": 2, \"name\": \"intel\"}\n",
+                    ),
+                    _ => actix_web::HttpResponse::Unauthorized().finish(),
+                }
+            }),
+        )
+    })
+    .workers(1)
+    .listen(listener)
+    .unwrap()
+    .run();
+    actix_rt::spawn(remote);
+
+    // the remote server listens on the loopback address
+    let dir = tempfile::tempdir().unwrap();
+    let options = Opt { allow_private_documents_urls: true, ..default_settings(dir.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("pets");
+    let url = format!("http://{address}/pets.ndjson");
+
+    let (response, code) = server
+        .service
+        .post(
+            "/indexes/pets/documents/from-url",
+            json!({ "url": url, "authorization": "Bearer doggo" }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]", ".duration" => "[duration]" }), @r###"
+    {
+      "uid": 0,
+      "indexUid": "pets",
+      "status": "succeeded",
+      "type": "documentAdditionOrUpdate",
+      "canceledBy": null,
+      "details": {
+        "receivedDocuments": 2,
+        "indexedDocuments": 2
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    let (response, code) = index.get_document(2, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 2,
+      "name": "intel"
+    }
+    "###);
+
+    // the remote server refuses to send the documents without the authorization
+    let (response, code) =
+        server.service.put("/indexes/pets/documents/from-url", json!({ "url": url })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""document_download_failed""###);
+}
+
+#[actix_rt::test]
+async fn error_add_documents_from_private_url() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    // the loopback and the link-local addresses, like the one of the cloud metadata services
+    for url in ["http://127.0.0.1:1/pets.ndjson", "http://169.254.169.254/latest/meta-data"] {
+        let (response, code) =
+            server.service.post("/indexes/pets/documents/from-url", json!({ "url": url })).await;
+        snapshot!(code, @"202 Accepted");
+        let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+        snapshot!(response["status"], @r###""failed""###);
+        snapshot!(response["error"]["code"], @r###""document_download_failed""###);
+    }
+
+    let (response, _) = server
+        .service
+        .post("/indexes/pets/documents/from-url", json!({ "url": "http://10.0.0.1/pets.ndjson" }))
+        .await;
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["error"]["message"], @r###""The documents could not be downloaded from `http://10.0.0.1/pets.ndjson`: the host resolves to the non-public address `10.0.0.1`, which is not allowed.""###);
+}
+
+#[actix_rt::test]
+async fn add_documents_dry_run() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let documents = json!([
+        { "id": 1, "name": "kefir" },
+        { "name": "no id" },
+        { "id": "b@d", "name": "bad id" },
+    ]);
+    let (response, code) = index
+        .raw_add_documents(&documents.to_string(), Some("application/json"), "?dryRun=true")
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]", ".duration" => "[duration]" }), @r###"
+    {
+      "uid": 0,
+      "indexUid": "pets",
+      "status": "succeeded",
+      "type": "documentAdditionDryRun",
+      "canceledBy": null,
+      "details": {
+        "receivedDocuments": 3,
+        "invalidDocuments": 2,
+        "documentErrors": [
+          {
+            "position": 1,
+            "documentId": null,
+            "error": {
+              "message": "Document doesn't have a `id` attribute: `{\"name\":\"no id\"}`.",
+              "code": "missing_document_id",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#missing_document_id"
+            }
+          },
+          {
+            "position": 2,
+            "documentId": null,
+            "error": {
+              "message": "Document identifier `\"b@d\"` is invalid. A document identifier can be of type integer or string, only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).",
+              "code": "invalid_document_id",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_document_id"
+            }
+          }
+        ]
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    // nothing was written, not even the index
+    let (_, code) = index.get().await;
+    snapshot!(code, @"404 Not Found");
+
+    // the dry runs are not listed with the document additions
+    let (response, _) = index.filtered_tasks(&["documentAdditionOrUpdate"], &[], &[]).await;
+    snapshot!(response["total"], @"0");
+    let (response, _) = index.filtered_tasks(&["documentAdditionDryRun"], &[], &[]).await;
+    snapshot!(response["total"], @"1");
+
+    let (response, code) =
+        index.raw_add_documents("[]", Some("application/json"), "?dryRun=yes").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_dry_run""###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_a_duplicate_ids_policy() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let documents = json!([
+        { "id": 1, "name": "first" },
+        { "id": 2, "name": "alone" },
+        { "id": 1, "name": "second" },
+    ]);
+    let (response, code) = index
+        .raw_add_documents(
+            &documents.to_string(),
+            Some("application/json"),
+            "?duplicateIdsPolicy=first-wins",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 3,
+      "indexedDocuments": 2,
+      "duplicatedDocuments": 1
+    }
+    "###);
+    let (document, _) = index.get_document(1, None).await;
+    snapshot!(document["name"], @r###""first""###);
+
+    // the instance policy is used without the parameter
+    let (response, _) =
+        index.raw_add_documents(&documents.to_string(), Some("application/json"), "").await;
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["details"]["duplicatedDocuments"], @"1");
+    let (document, _) = index.get_document(1, None).await;
+    snapshot!(document["name"], @r###""second""###);
+
+    let (response, _) = index
+        .raw_add_documents(
+            &documents.to_string(),
+            Some("application/json"),
+            "?duplicateIdsPolicy=reject",
+        )
+        .await;
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""duplicate_document_id""###);
+
+    let (response, code) =
+        index.raw_add_documents("[]", Some("application/json"), "?duplicateIdsPolicy=random").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_duplicate_ids_policy""###);
+}
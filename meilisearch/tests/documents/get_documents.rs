@@ -1,5 +1,5 @@
 use actix_web::test;
-use http::header::ACCEPT_ENCODING;
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
 use meili_snap::*;
 use urlencoding::encode as urlencode;
 
@@ -169,6 +169,42 @@ async fn get_all_documents_no_options_with_response_compression() {
     assert_eq!(arr.len(), 20);
 }
 
+#[actix_rt::test]
+async fn get_documents_with_brotli_response_compression_above_min_size() {
+    let server = Server::new().await;
+    let index_uid = "test";
+    let index = server.index(index_uid);
+    index.load_test_set().await;
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri(&format!("/indexes/{}/documents?limit=20", urlencode(index_uid)))
+        .insert_header((ACCEPT_ENCODING, "gzip, br"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get(CONTENT_ENCODING).unwrap(), "br");
+
+    let bytes = test::read_body(res).await;
+    let decoded = Encoder::Brotli.decode(bytes);
+    let parsed_response =
+        serde_json::from_slice::<Value>(decoded.into().as_ref()).expect("Expecting valid json");
+    assert_eq!(parsed_response["results"].as_array().unwrap().len(), 20);
+
+    // the responses smaller than the minimum size are not compressed
+    let req = test::TestRequest::get()
+        .uri(&format!("/indexes/{}/documents?limit=1&fields=id", urlencode(index_uid)))
+        .insert_header((ACCEPT_ENCODING, "gzip, br"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get(CONTENT_ENCODING).unwrap(), "identity");
+
+    let bytes = test::read_body(res).await;
+    let parsed_response = serde_json::from_slice::<Value>(&bytes).expect("Expecting valid json");
+    assert_eq!(parsed_response["results"].as_array().unwrap().len(), 1);
+}
+
 #[actix_rt::test]
 async fn test_get_all_documents_limit() {
     let server = Server::new().await;
@@ -540,3 +576,155 @@ async fn get_document_by_filter() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn get_document_by_sort() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index.update_settings_sortable_attributes(json!(["color", "age"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "color": "red", "age": 3 },
+                { "id": 1, "color": "blue", "age": 1 },
+                { "id": 2, "color": "blue", "age": 2 },
+                { "id": 3 },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(2).await;
+
+    let (response, code) =
+        index.get_document_by_filter(json!({ "sort": ["color:asc", "age:desc"] })).await;
+    let (response2, code2) = index.get_all_documents_raw("?sort=color:asc,age:desc").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 2,
+          "color": "blue",
+          "age": 2
+        },
+        {
+          "id": 1,
+          "color": "blue",
+          "age": 1
+        },
+        {
+          "id": 0,
+          "color": "red",
+          "age": 3
+        },
+        {
+          "id": 3
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 4
+    }
+    "###);
+    assert_eq!(code, code2);
+    assert_eq!(response, response2);
+
+    let (response, code) = index
+        .get_document_by_filter(json!({
+            "offset": 1,
+            "limit": 1,
+            "filter": "color = blue",
+            "sort": ["age:asc"],
+            "fields": ["id"],
+        }))
+        .await;
+    let (response2, code2) = index
+        .get_all_documents_raw("?offset=1&limit=1&filter=color=blue&sort=age:asc&fields=id")
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 2
+        }
+      ],
+      "offset": 1,
+      "limit": 1,
+      "total": 2
+    }
+    "###);
+    assert_eq!(code, code2);
+    assert_eq!(response, response2);
+}
+
+#[actix_rt::test]
+async fn get_documents_with_vectors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (_, code) = server.set_features(json!({"vectorStore": true})).await;
+    snapshot!(code, @"200 OK");
+
+    index.add_documents(json!([{ "id": 0, "_vectors": [1.0, 0.0] }, { "id": 1 }]), None).await;
+    index.wait_task(0).await;
+
+    // the vectors are not returned by default
+    let (response, code) = index.get_all_documents_raw("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index.get_all_documents_raw("?fields=id&retrieveVectors=true").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "_vectors": [
+          1.0,
+          0.0
+        ]
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.get_document_by_filter(json!({ "limit": 1, "retrieveVectors": true })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "_vectors": [
+          1.0,
+          0.0
+        ]
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        server.service.get("/indexes/test/documents/0?retrieveVectors=true").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 0,
+      "_vectors": [
+        1.0,
+        0.0
+      ]
+    }
+    "###);
+}
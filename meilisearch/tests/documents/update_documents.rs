@@ -209,3 +209,52 @@ async fn error_update_documents_missing_document_id() {
         "https://docs.meilisearch.com/errors#missing_document_id"
     );
 }
+
+#[actix_rt::test]
+async fn merge_nested_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "doc_id": 1,
+            "author": { "name": "Victor Hugo", "birth": { "year": 1802, "city": "Besançon" } },
+            "tags": ["novel"],
+        }
+    ]);
+    let (_response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(0).await;
+
+    let documents = json!([
+        {
+            "doc_id": 1,
+            "author": { "birth": { "country": "France" } },
+            "tags": ["drama"],
+        }
+    ]);
+    let (response, code) = index.merge_documents(documents, None).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["type"], "documentAdditionOrUpdate");
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(response, @r###"
+    {
+      "doc_id": 1,
+      "author": {
+        "name": "Victor Hugo",
+        "birth": {
+          "year": 1802,
+          "city": "Besançon",
+          "country": "France"
+        }
+      },
+      "tags": [
+        "drama"
+      ]
+    }
+    "###);
+}
@@ -27,9 +27,24 @@ async fn import_dump_v1_movie_raw() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "genres": 53,
+        "id": 53,
+        "overview": 53,
+        "poster": 53,
+        "release_date": 53,
+        "title": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -54,6 +69,7 @@ async fn import_dump_v1_movie_raw() {
         "exactness"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -66,7 +82,8 @@ async fn import_dump_v1_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -76,7 +93,35 @@ async fn import_dump_v1_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -162,10 +207,12 @@ async fn import_dump_v1_movie_with_settings() {
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
     snapshot!(
-        json_string!(stats),
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
         @r###"
     {
       "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
       "isIndexing": false,
       "fieldDistribution": {
         "genres": 53,
@@ -214,6 +261,7 @@ async fn import_dump_v1_movie_with_settings() {
         "of",
         "the"
       ],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -226,7 +274,8 @@ async fn import_dump_v1_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -236,7 +285,35 @@ async fn import_dump_v1_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -321,9 +398,24 @@ async fn import_dump_v1_rubygems_with_settings() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "description": 53,
+        "id": 53,
+        "name": 53,
+        "summary": 53,
+        "total_downloads": 53,
+        "version": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -360,6 +452,7 @@ async fn import_dump_v1_rubygems_with_settings() {
         "total_downloads:desc"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -372,7 +465,8 @@ async fn import_dump_v1_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -382,7 +476,35 @@ async fn import_dump_v1_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -465,9 +587,24 @@ async fn import_dump_v2_movie_raw() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "genres": 53,
+        "id": 53,
+        "overview": 53,
+        "poster": 53,
+        "release_date": 53,
+        "title": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -492,6 +629,7 @@ async fn import_dump_v2_movie_raw() {
         "exactness"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -504,7 +642,8 @@ async fn import_dump_v2_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -514,7 +653,35 @@ async fn import_dump_v2_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -599,9 +766,24 @@ async fn import_dump_v2_movie_with_settings() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "genres": 53,
+        "id": 53,
+        "overview": 53,
+        "poster": 53,
+        "release_date": 53,
+        "title": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -636,6 +818,7 @@ async fn import_dump_v2_movie_with_settings() {
         "of",
         "the"
       ],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -648,7 +831,8 @@ async fn import_dump_v2_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -658,7 +842,35 @@ async fn import_dump_v2_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -743,9 +955,24 @@ async fn import_dump_v2_rubygems_with_settings() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "description": 53,
+        "id": 53,
+        "name": 53,
+        "summary": 53,
+        "total_downloads": 53,
+        "version": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -779,6 +1006,7 @@ async fn import_dump_v2_rubygems_with_settings() {
         "total_downloads:desc"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -791,7 +1019,8 @@ async fn import_dump_v2_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -801,7 +1030,35 @@ async fn import_dump_v2_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -884,9 +1141,24 @@ async fn import_dump_v3_movie_raw() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "genres": 53,
+        "id": 53,
+        "overview": 53,
+        "poster": 53,
+        "release_date": 53,
+        "title": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -911,6 +1183,7 @@ async fn import_dump_v3_movie_raw() {
         "exactness"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -923,7 +1196,8 @@ async fn import_dump_v3_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -933,7 +1207,35 @@ async fn import_dump_v3_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -1018,9 +1320,24 @@ async fn import_dump_v3_movie_with_settings() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "genres": 53,
+        "id": 53,
+        "overview": 53,
+        "poster": 53,
+        "release_date": 53,
+        "title": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -1055,6 +1372,7 @@ async fn import_dump_v3_movie_with_settings() {
         "of",
         "the"
       ],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -1067,7 +1385,8 @@ async fn import_dump_v3_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1077,7 +1396,35 @@ async fn import_dump_v3_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -1162,9 +1509,24 @@ async fn import_dump_v3_rubygems_with_settings() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "description": 53,
+        "id": 53,
+        "name": 53,
+        "summary": 53,
+        "total_downloads": 53,
+        "version": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -1198,6 +1560,7 @@ async fn import_dump_v3_rubygems_with_settings() {
         "total_downloads:desc"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -1210,7 +1573,8 @@ async fn import_dump_v3_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1220,7 +1584,35 @@ async fn import_dump_v3_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -1303,9 +1695,24 @@ async fn import_dump_v4_movie_raw() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "genres": 53,
+        "id": 53,
+        "overview": 53,
+        "poster": 53,
+        "release_date": 53,
+        "title": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -1330,6 +1737,7 @@ async fn import_dump_v4_movie_raw() {
         "exactness"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -1342,7 +1750,8 @@ async fn import_dump_v4_movie_raw() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1352,7 +1761,35 @@ async fn import_dump_v4_movie_raw() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -1437,9 +1874,24 @@ async fn import_dump_v4_movie_with_settings() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "genres": 53,
+        "id": 53,
+        "overview": 53,
+        "poster": 53,
+        "release_date": 53,
+        "title": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -1474,6 +1926,7 @@ async fn import_dump_v4_movie_with_settings() {
         "of",
         "the"
       ],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -1486,7 +1939,8 @@ async fn import_dump_v4_movie_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1496,7 +1950,35 @@ async fn import_dump_v4_movie_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -1581,9 +2063,24 @@ async fn import_dump_v4_rubygems_with_settings() {
 
     let (stats, code) = index.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(
-        stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+    snapshot!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        @r###"
+    {
+      "numberOfDocuments": 53,
+      "databaseSize": "[size]",
+      "usedDatabaseSize": "[size]",
+      "isIndexing": false,
+      "fieldDistribution": {
+        "description": 53,
+        "id": 53,
+        "name": 53,
+        "summary": 53,
+        "total_downloads": 53,
+        "version": 53
+      }
+    }
+    "###
     );
 
     let (settings, code) = index.settings().await;
@@ -1617,6 +2114,7 @@ async fn import_dump_v4_rubygems_with_settings() {
         "total_downloads:desc"
       ],
       "stopWords": [],
+      "indexStopWords": false,
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
@@ -1629,7 +2127,8 @@ async fn import_dump_v4_rubygems_with_settings() {
           "twoTypos": 9
         },
         "disableOnWords": [],
-        "disableOnAttributes": []
+        "disableOnAttributes": [],
+        "disableOnNumbers": false
       },
       "faceting": {
         "maxValuesPerFacet": 100,
@@ -1639,7 +2138,35 @@ async fn import_dump_v4_rubygems_with_settings() {
       },
       "pagination": {
         "maxTotalHits": 1000
-      }
+      },
+      "searchCutoffMs": null,
+      "proximityPrecision": "byWord",
+      "prefixSearch": "indexingTime",
+      "splitWords": true,
+      "concatenateWords": true,
+      "localizedAttributes": null,
+      "normalizeNumbers": false,
+      "normalization": {
+        "lowercase": true,
+        "removeDiacritics": true,
+        "compatibility": true
+      },
+      "percolateQueries": {},
+      "stemming": [],
+      "geoFields": [],
+      "geoIndex": {
+        "nodeSize": "small",
+        "loadStrategy": "incremental"
+      },
+      "binaryQuantizedVectors": false,
+      "vectorIndex": {
+        "efConstruction": 100,
+        "efSearch": 100
+      },
+      "reranker": null,
+      "versionAttribute": null,
+      "documentTimestamps": false,
+      "facetSearch": true
     }
     "###
     );
@@ -1728,6 +2255,8 @@ async fn import_dump_v5() {
 
     let expected_stats = json!({
         "numberOfDocuments": 10,
+        "databaseSize": "[size]",
+        "usedDatabaseSize": "[size]",
         "isIndexing": false,
         "fieldDistribution": {
             "cast": 10,
@@ -1752,7 +2281,10 @@ async fn import_dump_v5() {
 
     let (stats, code) = index1.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(stats, expected_stats);
+    assert_eq!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        json_string!(expected_stats)
+    );
 
     let (docs, code) = index2.get_all_documents(GetAllDocumentsOptions::default()).await;
     snapshot!(code, @"200 OK");
@@ -1763,7 +2295,10 @@ async fn import_dump_v5() {
 
     let (stats, code) = index2.stats().await;
     snapshot!(code, @"200 OK");
-    assert_eq!(stats, expected_stats);
+    assert_eq!(
+        json_string!(stats, { ".databaseSize" => "[size]", ".usedDatabaseSize" => "[size]" }),
+        json_string!(expected_stats)
+    );
 
     let (keys, code) = server.list_api_keys("").await;
     snapshot!(code, @"200 OK");
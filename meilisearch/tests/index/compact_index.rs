@@ -0,0 +1,46 @@
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn compact_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let documents: Vec<_> = (0..1000).map(|id| json!({ "id": id, "title": "Carol" })).collect();
+    index.add_documents(json!(documents), Some("id")).await;
+    index.update_settings(json!({ "filterableAttributes": ["title"] })).await;
+    index.delete_batch((0..900).collect()).await;
+    index.wait_task(2).await;
+
+    let (response, code) = index.compact().await;
+    assert_eq!(code, 202);
+    assert_eq!(response["indexUid"], "test");
+    assert_eq!(response["type"], "indexUpdate");
+
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "succeeded");
+    let pre_compaction_size = response["details"]["preCompactionSize"].as_u64().unwrap();
+    let post_compaction_size = response["details"]["postCompactionSize"].as_u64().unwrap();
+    assert!(post_compaction_size <= pre_compaction_size);
+
+    // the documents and settings are kept
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["total"], 100);
+    let (response, _) = index.settings().await;
+    assert_eq!(response["filterableAttributes"], json!(["title"]));
+
+    index.add_documents(json!([{ "id": 2000, "title": "Alice" }]), None).await;
+    let response = index.wait_task(4).await;
+    assert_eq!(response["status"], "succeeded");
+}
+
+#[actix_rt::test]
+async fn compact_unexisting_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.compact().await;
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "index_not_found");
+}
@@ -199,3 +199,78 @@ async fn error_create_with_invalid_index_uid() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn create_index_with_template() {
+    let server = Server::new().await;
+    let (response, code) = server
+        .put_index_template(
+            "logs",
+            json!({ "uidPattern": "logs-*", "settings": { "filterableAttributes": ["level"] } }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "name": "logs",
+      "uidPattern": "logs-*",
+      "settings": {
+        "filterableAttributes": [
+          "level"
+        ]
+      }
+    }
+    "###);
+
+    // the index is created by its first documents
+    let index = server.index("logs-2023");
+    index.add_documents(json!([{ "id": 1, "level": "error" }]), None).await;
+    index.wait_task(0).await;
+    let (response, _code) = index.settings().await;
+    snapshot!(json_string!(response["filterableAttributes"]), @r###"
+    [
+      "level"
+    ]
+    "###);
+
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(1).await;
+    let (response, _code) = index.settings().await;
+    snapshot!(json_string!(response["filterableAttributes"]), @"[]");
+
+    let (response, code) = server.service.get("/index-templates/unknown").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Index template `unknown` not found.",
+      "code": "index_template_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_template_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_create_index_template_with_invalid_settings() {
+    let server = Server::new().await;
+    let (response, code) = server
+        .put_index_template(
+            "logs",
+            json!({ "uidPattern": "logs-*", "settings": { "rankingRules": ["words", "words"] } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`words` ranking rule is present more than once. A ranking rule can only be used once.",
+      "code": "invalid_settings_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
+    }
+    "###);
+
+    // the invalid template was not stored
+    let (_response, code) = server.service.get("/index-templates/logs").await;
+    snapshot!(code, @"404 Not Found");
+}
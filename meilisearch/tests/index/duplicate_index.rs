@@ -0,0 +1,53 @@
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn duplicate_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "title": "Carol" }]), Some("id")).await;
+    index.update_settings(json!({ "filterableAttributes": ["title"] })).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.duplicate("copy").await;
+    assert_eq!(code, 202);
+    assert_eq!(response["indexUid"], "copy");
+    assert_eq!(response["type"], "indexCreation");
+
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["primaryKey"], "id");
+
+    let copy = server.index("copy");
+    let (response, code) = copy.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"], json!([{ "id": 1, "title": "Carol" }]));
+    let (response, _) = copy.settings().await;
+    assert_eq!(response["filterableAttributes"], json!(["title"]));
+
+    // the copy is independent from the duplicated index
+    copy.add_documents(json!([{ "id": 2, "title": "Alice" }]), None).await;
+    copy.wait_task(3).await;
+    let (response, _) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(response["results"].as_array().unwrap().len(), 1);
+}
+
+#[actix_rt::test]
+async fn duplicate_index_on_an_existing_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    server.index("copy").create(None).await;
+    index.wait_task(1).await;
+
+    index.duplicate("copy").await;
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "index_already_exists");
+
+    let index = server.index("unknown");
+    index.duplicate("other").await;
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "index_not_found");
+}
@@ -1,5 +1,7 @@
+mod compact_index;
 mod create_index;
 mod delete_index;
+mod duplicate_index;
 mod errors;
 mod get_index;
 mod stats;
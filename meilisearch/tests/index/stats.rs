@@ -39,6 +39,11 @@ async fn stats() {
 
     assert_eq!(code, 200);
     assert_eq!(response["numberOfDocuments"], 2);
+    assert!(response["databaseSize"].as_u64().unwrap() > 0);
+    assert!(
+        response["usedDatabaseSize"].as_u64().unwrap()
+            <= response["databaseSize"].as_u64().unwrap()
+    );
     assert!(response["isIndexing"] == false);
     assert_eq!(response["fieldDistribution"]["id"], 2);
     assert_eq!(response["fieldDistribution"]["name"], 1);
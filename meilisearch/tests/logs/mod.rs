@@ -0,0 +1,24 @@
+use actix_web::test;
+
+use crate::common::Server;
+
+#[actix_rt::test]
+async fn request_id_header() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    // the id sent by the client is kept
+    let req = test::TestRequest::get()
+        .uri("/health")
+        .insert_header(("x-request-id", "my-request"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("x-request-id").unwrap(), "my-request");
+
+    // otherwise an id is generated
+    let req = test::TestRequest::get().uri("/health").to_request();
+    let res = test::call_service(&app, req).await;
+    let request_id = res.headers().get("x-request-id").unwrap().to_str().unwrap();
+    assert!(uuid::Uuid::parse_str(request_id).is_ok());
+}
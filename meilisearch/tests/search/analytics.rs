@@ -0,0 +1,136 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use once_cell::sync::Lazy;
+
+use crate::common::{default_settings, Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "title": "the dragon of the mountain" },
+        { "id": 2, "title": "a dragon in the garden" },
+        { "id": 3, "title": "the merchant and the mountain" },
+    ])
+});
+
+#[actix_rt::test]
+async fn search_analytics_are_disabled_by_default() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "q": "dragon" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert!(response.get("searchUid").is_none());
+        })
+        .await;
+
+    let (response, code) = server.service.get("/indexes/test/search-analytics/top-queries").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Using the search analytics requires enabling the `search analytics` experimental feature. See https://www.meilisearch.com/docs/learn/configuration/instance_options",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn top_and_no_result_queries() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = Opt { experimental_search_analytics: true, ..default_settings(dir.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "Dragon" })).await;
+    snapshot!(code, @"200 OK");
+    let search_uid = response["searchUid"].as_u64().unwrap();
+    index.search_post(json!({ "q": "dragon" })).await;
+    index.search_post(json!({ "q": "mountain" })).await;
+    index.search_post(json!({ "q": "unicorn" })).await;
+    // the placeholder searches are not recorded
+    let (response, _) = index.search_post(json!({})).await;
+    assert!(response.get("searchUid").is_none());
+
+    let (response, code) = server
+        .service
+        .post("/indexes/test/search-analytics/clicks", json!({ "searchUid": search_uid }))
+        .await;
+    snapshot!(code, @"204 No Content");
+    snapshot!(json_string!(response), @"null");
+
+    let (response, code) = server
+        .service
+        .post("/indexes/test/search-analytics/clicks", json!({ "searchUid": 1000 }))
+        .await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Search `1000` not found, it may have been forgotten by the search analytics.",
+      "code": "search_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#search_not_found"
+    }
+    "###);
+
+    let (response, code) =
+        server.service.get("/indexes/test/search-analytics/top-queries?limit=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].averageProcessingTimeMs" => "[ms]" }), @r###"
+    {
+      "results": [
+        {
+          "query": "dragon",
+          "count": 2,
+          "averageHits": 2.0,
+          "averageProcessingTimeMs": "[ms]",
+          "clicks": 1
+        },
+        {
+          "query": "mountain",
+          "count": 1,
+          "averageHits": 2.0,
+          "averageProcessingTimeMs": "[ms]",
+          "clicks": 0
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) =
+        server.service.get("/indexes/test/search-analytics/no-result-queries").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].averageProcessingTimeMs" => "[ms]" }), @r###"
+    {
+      "results": [
+        {
+          "query": "unicorn",
+          "count": 1,
+          "averageHits": 0.0,
+          "averageProcessingTimeMs": "[ms]",
+          "clicks": 0
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) =
+        server.service.get("/indexes/test/search-analytics/top-queries?limit=ten").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `limit`: could not parse `ten` as a positive integer",
+      "code": "invalid_search_analytics_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_analytics_limit"
+    }
+    "###);
+}
@@ -0,0 +1,50 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn cached_responses_are_invalidated() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = Opt { experimental_search_cache_size: 10, ..default_settings(dir.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index.add_documents(json!([{ "id": 1, "title": "the dragon" }]), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "dragon" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "the dragon"
+      }
+    ]
+    "###);
+
+    // the identical search returns the cached response, with its own processing time
+    let (mut cached, code) = index.search_post(json!({ "q": "dragon" })).await;
+    snapshot!(code, @"200 OK");
+    assert!(cached["processingTimeMs"].is_u64());
+    cached["processingTimeMs"] = response["processingTimeMs"].clone();
+    assert_eq!(cached, response);
+
+    // the cache is invalidated when the documents are modified
+    index.add_documents(json!([{ "id": 2, "title": "a dragon" }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "q": "dragon" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"2");
+
+    // and when the settings are modified
+    index.update_settings(json!({ "searchableAttributes": ["id"] })).await;
+    index.wait_task(2).await;
+
+    let (response, code) = index.search_post(json!({ "q": "dragon" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"0");
+}
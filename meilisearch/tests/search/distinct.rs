@@ -0,0 +1,91 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "product": "shirt", "color": "red" },
+        { "id": 2, "product": "shirt", "color": "blue" },
+        { "id": 3, "product": "pants", "color": "red" },
+        { "id": 4, "product": "pants", "color": "green" },
+        { "id": 5, "product": "socks", "color": "blue" },
+    ])
+});
+
+#[actix_rt::test]
+async fn distinct_at_search_time() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_filterable_attributes(json!(["product", "color"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({ "distinct": "product", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 3
+              },
+              {
+                "id": 5
+              }
+            ]
+            "###);
+            },
+        )
+        .await;
+
+    // the distinct attribute of the request overrides the one of the settings
+    index.update_settings(json!({ "distinctAttribute": "product" })).await;
+    index.wait_task(2).await;
+
+    index
+        .search(json!({ "distinct": "color", "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              },
+              {
+                "id": 4
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn distinct_on_non_filterable_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "distinct": "product" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `product` is not filterable and thus, cannot be used as distinct attribute. Available filterable attributes are: `color`.",
+      "code": "invalid_search_distinct",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_distinct"
+    }
+    "###);
+}
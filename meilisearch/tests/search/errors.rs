@@ -535,6 +535,257 @@ async fn search_bad_matching_strategy() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_minimum_should_match() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"minimumShouldMatch": "120%"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.minimumShouldMatch`: could not parse `120%` as a minimum should match, expected a positive integer or a percentage between `0%` and `100%`, e.g. `75%`",
+      "code": "invalid_search_minimum_should_match",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_minimum_should_match"
+    }
+    "###);
+
+    let (response, code) = index.search_get("minimumShouldMatch=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `minimumShouldMatch`: could not parse `doggo` as a minimum should match, expected a positive integer or a percentage between `0%` and `100%`, e.g. `75%`",
+      "code": "invalid_search_minimum_should_match",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_minimum_should_match"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_ranking_score_threshold() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"rankingScoreThreshold": 1.2})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingScoreThreshold`: could not parse `1.2` as a ranking score threshold, expected a float between `0.0` and `1.0`",
+      "code": "invalid_search_ranking_score_threshold",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_score_threshold"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"rankingScoreThreshold": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.rankingScoreThreshold`: expected a number, but found a string: `\"doggo\"`",
+      "code": "invalid_search_ranking_score_threshold",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_score_threshold"
+    }
+    "###);
+
+    let (response, code) = index.search_get("rankingScoreThreshold=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `rankingScoreThreshold`: could not parse `doggo` as a ranking score threshold, expected a float between `0.0` and `1.0`",
+      "code": "invalid_search_ranking_score_threshold",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_score_threshold"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_attribute_boosts() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"attributeBoosts": ["title^0"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.attributeBoosts[0]`: could not parse `title^0` as an attribute boost, expected an attribute name optionally followed by `^` and a strictly positive integer, e.g. `title^3`",
+      "code": "invalid_search_attribute_boosts",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attribute_boosts"
+    }
+    "###);
+
+    let (response, code) = index.search_get("attributeBoosts=title^doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `attributeBoosts`: could not parse `title^doggo` as an attribute boost, expected an attribute name optionally followed by `^` and a strictly positive integer, e.g. `title^3`",
+      "code": "invalid_search_attribute_boosts",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attribute_boosts"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_prefix_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"prefixSearch": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.prefixSearch`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_prefix_search",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_prefix_search"
+    }
+    "###);
+
+    let (response, code) = index.search_get("prefixSearch=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `prefixSearch`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_prefix_search",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_prefix_search"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_keep_stop_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"keepStopWords": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.keepStopWords`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_keep_stop_words",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_keep_stop_words"
+    }
+    "###);
+
+    let (response, code) = index.search_get("keepStopWords=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `keepStopWords`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_keep_stop_words",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_keep_stop_words"
+    }
+    "###);
+
+    // the stop words must be indexed to be kept
+    let (_, code) = index.create(None).await;
+    server.wait_task(0).await;
+    snapshot!(code, @"202 Accepted");
+
+    let (response, code) = index.search_post(json!({"q": "to be", "keepStopWords": true})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The stop words of the query can only be kept when the `indexStopWords` setting of the index is enabled.",
+      "code": "invalid_search_keep_stop_words",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_keep_stop_words"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_retrieve_vectors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"retrieveVectors": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.retrieveVectors`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_retrieve_vectors",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_retrieve_vectors"
+    }
+    "###);
+
+    let (response, code) = index.search_get("retrieveVectors=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `retrieveVectors`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_retrieve_vectors",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_retrieve_vectors"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_geo_distance_unit() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"geoDistanceUnit": "parsec"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `parsec` at `.geoDistanceUnit`: expected one of `m`, `km`, `mi`",
+      "code": "invalid_search_geo_distance_unit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_distance_unit"
+    }
+    "###);
+
+    let (response, code) = index.search_get("geoDistanceUnit=parsec").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `parsec` for parameter `geoDistanceUnit`: expected one of `m`, `km`, `mi`",
+      "code": "invalid_search_geo_distance_unit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_distance_unit"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"locales": ["klingon"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.locales[0]`: unsupported locale `klingon`, expected one of `ara`, `bul`, `cat`, `ces`, `cmn`, `dan`, `deu`, `ell`, `eng`, `fin`, `fra`, `heb`, `hin`, `hun`, `ind`, `ita`, `jpn`, `kor`, `mar`, `nld`, `nob`, `pes`, `pol`, `por`, `ron`, `rus`, `spa`, `swe`, `tha`, `tur`, `ukr`, `vie`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+
+    let (response, code) = index.search_get("locales=jpn,klingon").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `locales`: unsupported locale `klingon`, expected one of `ara`, `bul`, `cat`, `ces`, `cmn`, `dan`, `deu`, `ell`, `eng`, `fin`, `fra`, `heb`, `hin`, `hun`, `ind`, `ita`, `jpn`, `kor`, `mar`, `nld`, `nob`, `pes`, `pol`, `por`, `ron`, `rus`, `spa`, `swe`, `tha`, `tur`, `ukr`, `vie`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn filter_invalid_syntax_object() {
     let server = Server::new().await;
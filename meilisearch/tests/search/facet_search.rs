@@ -1,4 +1,4 @@
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use once_cell::sync::Lazy;
 
 use crate::common::{Server, Value};
@@ -123,6 +123,40 @@ async fn non_filterable_facet_search_error() {
     assert_eq!(code, 400, "{}", response);
 }
 
+#[actix_rt::test]
+async fn disabled_facet_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index
+        .update_settings(json!({ "filterableAttributes": ["genres"], "facetSearch": false }))
+        .await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "a"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The facet search is disabled for this index.",
+      "code": "facet_search_disabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#facet_search_disabled"
+    }
+    "###);
+
+    // enabling it back builds the facet search from the existing documents
+    let (response, _) = index.update_settings(json!({ "facetSearch": true })).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "a"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetHits"].as_array().unwrap().len(), @"2");
+}
+
 #[actix_rt::test]
 async fn facet_search_dont_support_words() {
     let server = Server::new().await;
@@ -139,3 +173,24 @@ async fn facet_search_dont_support_words() {
     assert_eq!(code, 200, "{}", response);
     assert_eq!(response["facetHits"].as_array().unwrap().len(), 0);
 }
+
+#[actix_rt::test]
+async fn facet_search_on_nested_field_of_filterable_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "author": { "name": "Jules Verne" } },
+        { "id": 1, "author": { "name": "Jules Romains" } },
+        { "id": 2, "author": { "name": "Victor Hugo" } },
+    ]);
+    index.update_settings_filterable_attributes(json!(["author"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "author.name", "facetQuery": "jules"})).await;
+
+    assert_eq!(code, 200, "{}", response);
+    snapshot!(response["facetHits"], @r###"[{"value":"Jules Romains","count":1},{"value":"Jules Verne","count":1}]"###);
+}
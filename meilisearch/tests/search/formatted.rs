@@ -568,3 +568,108 @@ async fn test_cjk_highlight() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn crop_length_per_attribute_overrides_wildcard() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "title": "one two three four five six seven eight nine ten eleven twelve",
+            "description": "one two three four five six seven eight nine ten eleven twelve",
+        }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let count_words =
+        |value: &serde_json::Value| value.as_str().unwrap().split_whitespace().count();
+
+    for attributes_to_crop in [json!(["description:2", "*:4"]), json!(["*:4", "description:2"])] {
+        index
+            .search(
+                json!({ "q": "six", "attributesToCrop": attributes_to_crop }),
+                |response, code| {
+                    assert_eq!(code, 200, "{}", response);
+                    let formatted = &response["hits"][0]["_formatted"];
+                    assert_eq!(count_words(&formatted["title"]), 4, "{}", response);
+                    assert_eq!(count_words(&formatted["description"]), 2, "{}", response);
+                },
+            )
+            .await;
+    }
+}
+
+#[actix_rt::test]
+async fn highlight_patterns_and_exclusions() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "title": "a green apple",
+            "title_fr": "a green pomme",
+            "internal_notes": "green label",
+        }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    for attributes_to_highlight in
+        [json!(["*", "-internal_notes"]), json!(["-internal_notes"]), json!(["title*", "-id"])]
+    {
+        index
+            .search(
+                json!({ "q": "green", "attributesToHighlight": attributes_to_highlight }),
+                |response, code| {
+                    assert_eq!(code, 200, "{}", response);
+                    let formatted = &response["hits"][0]["_formatted"];
+                    assert_eq!(formatted["title"], json!("a <em>green</em> apple"), "{}", response);
+                    assert_eq!(formatted["title_fr"], json!("a <em>green</em> pomme"));
+                    assert_eq!(formatted["internal_notes"], json!("green label"));
+                },
+            )
+            .await;
+    }
+}
+
+#[actix_rt::test]
+async fn highlight_searchable_only() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    let documents = json!([
+        {
+            "id": 1,
+            "title": "a green apple",
+            "description": "a green fruit",
+        }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({ "q": "green", "attributesToHighlight": ["*"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let formatted = &response["hits"][0]["_formatted"];
+            assert_eq!(formatted["title"], json!("a <em>green</em> apple"));
+            assert_eq!(formatted["description"], json!("a <em>green</em> fruit"));
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "q": "green", "attributesToHighlight": ["*"], "highlightSearchableOnly": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let formatted = &response["hits"][0]["_formatted"];
+                assert_eq!(formatted["title"], json!("a <em>green</em> apple"));
+                assert_eq!(formatted["description"], json!("a green fruit"));
+            },
+        )
+        .await;
+}
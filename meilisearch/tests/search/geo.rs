@@ -117,3 +117,287 @@ async fn geo_bounding_box_with_string_and_number() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn geo_radius_returns_the_geo_distance() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(34.0522, -118.2437, 1000)",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "name": "Taco Truck",
+                    "address": "444 Salsa Street, Burritoville",
+                    "type": "Mexican",
+                    "rating": 9,
+                    "_geo": {
+                      "lat": 34.0522,
+                      "lng": -118.2437
+                    },
+                    "_geoDistance": 0
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_radius_and_geo_distance_with_units() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.472735, 9.184019, 2km)",
+                "geoDistanceUnit": "km",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "_geoDistance": 1.139
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.472735, 9.184019, 0.5mi)",
+                "geoDistanceUnit": "mi",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_radius_with_a_tuned_geo_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.add_documents(documents, None).await;
+    let (response, _) = index
+        .update_settings(json!({
+            "geoIndex": { "nodeSize": "large", "loadStrategy": "bulkLoad" },
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["geoIndex"]), @r###"
+    {
+      "nodeSize": "large",
+      "loadStrategy": "bulkLoad"
+    }
+    "###);
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.472735, 9.184019, 2000)",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "_geoDistance": 1139
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_intersects_with_geojson_shapes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "name": "Downtown delivery zone",
+            "_geojson": {
+                "type": "Polygon",
+                "coordinates": [[[2.33, 48.85], [2.36, 48.85], [2.36, 48.87], [2.33, 48.87], [2.33, 48.85]]]
+            }
+        },
+        {
+            "id": 2,
+            "name": "Riverside delivery zone",
+            "_geojson": {
+                "type": "Polygon",
+                "coordinates": [[[2.27, 48.83], [2.30, 48.83], [2.30, 48.85], [2.27, 48.85], [2.27, 48.83]]]
+            }
+        }
+    ]);
+    index.update_settings_filterable_attributes(json!(["_geojson"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoIntersects([[48.86, 2.34], [48.86, 2.35], [48.865, 2.35]])",
+                "attributesToRetrieve": ["id", "name"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "name": "Downtown delivery zone"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_radius_on_named_geo_fields() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "pickup": { "lat": 48.8566, "lng": 2.3522 },
+            "dropoff": { "lat": 48.8049, "lng": 2.1204 }
+        },
+        {
+            "id": 2,
+            "pickup": { "lat": 48.8049, "lng": 2.1204 },
+            "dropoff": { "lat": 48.8566, "lng": 2.3522 }
+        }
+    ]);
+    let (response, _) = index
+        .update_settings(json!({
+            "geoFields": ["pickup", "dropoff"],
+            "filterableAttributes": ["pickup", "dropoff"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(dropoff, 48.8566, 2.3522, 1000)",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_bounding_box_and_sort_on_named_geo_fields() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "pickup": { "lat": 48.8566, "lng": 2.3522 },
+            "dropoff": { "lat": 48.8049, "lng": 2.1204 }
+        },
+        {
+            "id": 2,
+            "pickup": { "lat": 48.8049, "lng": 2.1204 },
+            "dropoff": { "lat": 48.8566, "lng": 2.3522 }
+        }
+    ]);
+    let (response, _) = index
+        .update_settings(json!({
+            "geoFields": ["pickup", "dropoff"],
+            "filterableAttributes": ["pickup", "dropoff"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+    let (response, _) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoBoundingBox(pickup, [48.9, 2.4], [48.83, 2.3])",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the `_geoPoint` sort only applies to `_geo`
+    index
+        .search(json!({ "sort": ["_geoPoint(pickup, 48.8566, 2.3522):asc"] }), |response, code| {
+            snapshot!(code, @"400 Bad Request");
+            snapshot!(json_string!(response), @r###"
+            {
+              "message": "The `_geoPoint` sort only applies to the _geo field coordinates, the named geo field `pickup` can only be used in the `_geoRadius` and `_geoBoundingBox` filters.",
+              "code": "invalid_search_sort",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
+            }
+            "###);
+        })
+        .await;
+}
@@ -0,0 +1,113 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "product": "shirt", "color": "red" },
+        { "id": 2, "product": "shirt", "color": "blue" },
+        { "id": 3, "product": "pants", "color": "red" },
+        { "id": 4, "product": "pants", "color": "green" },
+        { "id": 5, "product": "socks", "color": "blue" },
+    ])
+});
+
+#[actix_rt::test]
+async fn group_by_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_filterable_attributes(json!(["product", "color"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({ "groupBy": "product", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @"[]");
+                snapshot!(json_string!(response["groups"]), @r###"
+                [
+                  {
+                    "value": "shirt",
+                    "count": 2,
+                    "hits": [
+                      {
+                        "id": 1
+                      }
+                    ]
+                  },
+                  {
+                    "value": "pants",
+                    "count": 2,
+                    "hits": [
+                      {
+                        "id": 3
+                      }
+                    ]
+                  },
+                  {
+                    "value": "socks",
+                    "count": 1,
+                    "hits": [
+                      {
+                        "id": 5
+                      }
+                    ]
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the limit applies to the groups
+    index
+        .search(
+            json!({ "groupBy": "product", "hitsPerGroup": 2, "limit": 1, "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["groups"]), @r###"
+                [
+                  {
+                    "value": "shirt",
+                    "count": 2,
+                    "hits": [
+                      {
+                        "id": 1
+                      },
+                      {
+                        "id": 2
+                      }
+                    ]
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn group_by_non_filterable_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "groupBy": "product" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `product` is not filterable and thus, cannot be used to group the documents. Available filterable attributes are: `color`.",
+      "code": "invalid_search_group_by",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_group_by"
+    }
+    "###);
+}
@@ -1,13 +1,21 @@
 // This modules contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod analytics;
+mod cache;
+mod distinct;
 mod errors;
 mod facet_search;
 mod formatted;
 mod geo;
+mod group_by;
 mod multi;
 mod pagination;
 mod restrict_searchable;
+mod search_after;
+mod spelling;
+mod suggestions;
+mod within_candidates;
 
 use once_cell::sync::Lazy;
 
@@ -173,6 +181,404 @@ async fn phrase_search_with_stop_word() {
         .await;
 }
 
+#[actix_rt::test]
+async fn phrase_search_with_stop_words_preset() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index.update_settings(json!({"stopWords": { "preset": "en" }})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, _) = index.settings().await;
+    meili_snap::snapshot!(meili_snap::json_string!(response["stopWords"]), @r###"
+    {
+      "preset": "en"
+    }
+    "###);
+
+    index
+        .search(
+            json!({"q": "\"train your dragon\"", "attributesToHighlight": ["title"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+                meili_snap::snapshot!(response["hits"][0]["_formatted"]["title"], @r###""How to <em>Train</em> <em>Your</em> <em>Dragon</em>: The Hidden World""###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn prefix_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "sku": "mei" },
+        { "id": 1, "sku": "meilisearch" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({"q": "mei"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "mei", "prefixSearch": false}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "sku": "mei"
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let (_, code) = index.update_settings(json!({"prefixSearch": "disabled"})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "mei"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "mei", "prefixSearch": true}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn minimum_should_match() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "the quick brown fox" },
+        { "id": 1, "title": "the quick brown dog" },
+        { "id": 2, "title": "the quick cat" },
+        { "id": 3, "title": "the lazy cat" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({"q": "the quick brown fox"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 4);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "the quick brown fox", "minimumShouldMatch": 3}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "title": "the quick brown fox"
+              },
+              {
+                "id": 1,
+                "title": "the quick brown dog"
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({"q": "the quick brown fox", "minimumShouldMatch": "50%"}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"].as_array().unwrap().len(), 3);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn keep_stop_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index
+        .update_settings(json!({"stopWords": ["to", "be", "or", "not"], "indexStopWords": true}))
+        .await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = json!([
+        { "id": 0, "title": "To be or not to be" },
+        { "id": 1, "title": "Let it be" },
+        { "id": 2, "title": "Hamlet" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    // the stop words are ignored, the query is handled as a placeholder search
+    index
+        .search(json!({"q": "to be or not to be ", "matchingStrategy": "all"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 3);
+        })
+        .await;
+
+    index
+        .search(
+            json!({
+                "q": "to be or not to be ",
+                "matchingStrategy": "all",
+                "keepStopWords": true,
+                "attributesToHighlight": ["title"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0,
+                    "title": "To be or not to be",
+                    "_formatted": {
+                      "id": "0",
+                      "title": "<em>To</em> <em>be</em> <em>or</em> <em>not</em> <em>to</em> <em>be</em>"
+                    }
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(json!({"q": "\"let it be\"", "keepStopWords": true}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1,
+                "title": "Let it be"
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_normalized_numbers() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index.update_settings(json!({"normalizeNumbers": true})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = json!([
+        { "id": 0, "text": "It costs 1,000 dollars" },
+        { "id": 1, "text": "1 000 euros" },
+        { "id": 2, "text": "100 euros" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "1000", "attributesToHighlight": ["text"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1,
+                "text": "1 000 euros",
+                "_formatted": {
+                  "id": "1",
+                  "text": "<em>1 000</em> euros"
+                }
+              },
+              {
+                "id": 0,
+                "text": "It costs 1,000 dollars",
+                "_formatted": {
+                  "id": "0",
+                  "text": "It costs <em>1,000</em> dollars"
+                }
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // the query numbers are normalized too
+    index
+        .search(json!({"q": "1 000 euros", "matchingStrategy": "all"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_kept_diacritics() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) =
+        index.update_settings(json!({"normalization": { "removeDiacritics": false }})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = json!([
+        { "id": 0, "text": "Le résumé du jugement" },
+        { "id": 1, "text": "Resume the trial" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "Résumé", "attributesToHighlight": ["text"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "text": "Le résumé du jugement",
+                "_formatted": {
+                  "id": "0",
+                  "text": "Le <em>résumé</em> du jugement"
+                }
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "resume"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1,
+                "text": "Resume the trial"
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_stemming() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index.update_settings(json!({"stemming": ["en"]})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = json!([
+        { "id": 0, "text": "she is running" },
+        { "id": 1, "text": "they run" },
+        { "id": 2, "text": "he runs" },
+        { "id": 3, "text": "a rung" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "runs", "attributesToHighlight": ["text"]}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 3, "{}", response);
+            // the literal match comes first
+            meili_snap::snapshot!(meili_snap::json_string!(hits[0]["_formatted"]["text"]), @r###""he <em>runs</em>""###);
+            let running = hits.iter().find(|hit| hit["id"] == 0).unwrap();
+            meili_snap::snapshot!(meili_snap::json_string!(running["_formatted"]["text"]), @r###""she is <em>running</em>""###);
+        })
+        .await;
+
+    // the stems are not matched once the stemming is disabled
+    let (_, code) = index.update_settings(json!({"stemming": []})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(2).await;
+
+    index
+        .search(json!({"q": "runs"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{}", response);
+        })
+        .await;
+}
+
+#[cfg(feature = "default")]
+#[actix_rt::test]
+async fn search_with_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index
+        .update_settings(
+            json!({"localizedAttributes": [{ "attributePatterns": ["*_ja"], "locales": ["jpn"] }]}),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = json!([
+        { "id": 0, "name_ja": "東京都庁" },
+        { "id": 1, "name_ja": "京都" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, _) = index.settings().await;
+    meili_snap::snapshot!(meili_snap::json_string!(response["localizedAttributes"]), @r###"
+    [
+      {
+        "attributePatterns": [
+          "*_ja"
+        ],
+        "locales": [
+          "jpn"
+        ]
+      }
+    ]
+    "###);
+
+    index
+        .search(
+            json!({"q": "東京", "locales": ["jpn"], "attributesToHighlight": ["name_ja"]}),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0,
+                    "name_ja": "東京都庁",
+                    "_formatted": {
+                      "id": "0",
+                      "name_ja": "<em>東京</em>都庁"
+                    }
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
 #[cfg(feature = "default")]
 #[actix_rt::test]
 async fn test_kanji_language_detection() {
@@ -566,6 +972,39 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn facet_distribution_sorted_by_count() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["color", "size"],
+            "faceting": { "sortFacetValuesBy": { "*": "alpha", "color": "count" } },
+        }))
+        .await;
+
+    let documents = json!([
+        { "id": 0, "color": "red", "size": "s" },
+        { "id": 1, "color": "blue", "size": "s" },
+        { "id": 2, "color": "blue", "size": "s" },
+        { "id": 3, "color": "green", "size": "m" },
+        { "id": 4, "color": "blue", "size": "m" },
+        { "id": 5, "color": "green", "size": "l" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    for facets in [json!(["color", "size"]), json!(["*"])] {
+        index
+            .search(json!({ "facets": facets }), |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(response["facetDistribution"], @r###"{"color":{"blue":3,"green":2,"red":1},"size":{"l":1,"m":2,"s":3}}"###);
+            })
+            .await;
+    }
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new().await;
@@ -755,7 +1194,7 @@ async fn faceting_max_values_per_facet() {
 }
 
 #[actix_rt::test]
-async fn experimental_feature_score_details() {
+async fn score_details() {
     let server = Server::new().await;
     let index = server.index("test");
 
@@ -764,30 +1203,6 @@ async fn experimental_feature_score_details() {
     index.add_documents(json!(documents), None).await;
     index.wait_task(0).await;
 
-    index
-        .search(
-            json!({
-                "q": "train dragon",
-                "showRankingScoreDetails": true,
-            }),
-            |response, code| {
-                meili_snap::snapshot!(code, @"400 Bad Request");
-                meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
-                {
-                  "message": "Computing score details requires enabling the `score details` experimental feature. See https://github.com/meilisearch/product/discussions/674",
-                  "code": "feature_not_enabled",
-                  "type": "invalid_request",
-                  "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
-                }
-                "###);
-            },
-        )
-        .await;
-
-    let (response, code) = server.set_features(json!({"scoreDetails": true})).await;
-    meili_snap::snapshot!(code, @"200 OK");
-    meili_snap::snapshot!(response["scoreDetails"], @"true");
-
     index
         .search(
             json!({
@@ -878,6 +1293,345 @@ async fn experimental_feature_vector_store() {
     meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @"[]");
 }
 
+#[actix_rt::test]
+async fn vector_search_ranking_score() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = server.set_features(json!({"vectorStore": true})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    let documents = json!([
+        { "id": 0, "_vectors": [1.0, 0.0, 0.0] },
+        { "id": 1, "_vectors": [0.0, 1.0, 0.0] },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0],
+            "attributesToRetrieve": ["id"],
+            "showRankingScore": true,
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "_rankingScore": 1.0,
+        "_semanticScore": 1.0
+      },
+      {
+        "id": 1,
+        "_rankingScore": 0.0,
+        "_semanticScore": 0.0
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn retrieve_vectors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "_vectors": [1.0, 0.0, 0.0] },
+        { "id": 1, "_vectors": [0.0, 1.0, 0.0] },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // the vectors can only be retrieved with the vector store
+    let (response, code) = index.search_post(json!({ "retrieveVectors": true })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Passing `retrieveVectors` as a query parameter requires enabling the `vector store` experimental feature. See https://github.com/meilisearch/product/discussions/677",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let (_, code) = server.set_features(json!({"vectorStore": true})).await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    // the vectors are not returned by default
+    let (response, code) = index.search_post(json!({ "vector": [1.0, 0.0, 0.0] })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "_semanticScore": 1.0
+      },
+      {
+        "id": 1,
+        "_semanticScore": 0.0
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0],
+            "attributesToRetrieve": ["id"],
+            "retrieveVectors": true,
+            "limit": 1,
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "_vectors": [
+          1.0,
+          0.0,
+          0.0
+        ],
+        "_semanticScore": 1.0
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn ranking_score_threshold() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "the quick brown fox" },
+        { "id": 1, "title": "the quick fox" },
+        { "id": 2, "title": "the fox" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(
+            json!({
+                "q": "quick brown fox",
+                "attributesToRetrieve": ["id"],
+                "rankingScoreThreshold": 0.8,
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0
+                  }
+                ]
+                "###);
+                meili_snap::snapshot!(response["estimatedTotalHits"], @"1");
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "q": "quick brown fox",
+                "attributesToRetrieve": ["id"],
+                "rankingScoreThreshold": 0.0,
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(response["estimatedTotalHits"], @"3");
+            },
+        )
+        .await;
+
+    // the total number of hits only counts the documents above the threshold
+    index
+        .search(
+            json!({
+                "q": "quick brown fox",
+                "attributesToRetrieve": ["id"],
+                "rankingScoreThreshold": 0.8,
+                "hitsPerPage": 1,
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0
+                  }
+                ]
+                "###);
+                meili_snap::snapshot!(response["totalHits"], @"1");
+                meili_snap::snapshot!(response["totalPages"], @"1");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_cutoff_returns_degraded_results() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "the quick brown fox" },
+        { "id": 1, "title": "the quick fox" },
+        { "id": 2, "title": "the fox" },
+    ]);
+    index.update_settings(json!({ "searchCutoffMs": 0 })).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({ "q": "quick brown fox", "showRankingScoreDetails": true }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(response["degraded"], @"true");
+                meili_snap::snapshot!(response["hits"].as_array().unwrap().len(), @"3");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"][0]["_rankingScoreDetails"]), @r###"
+                {
+                  "skipped": {
+                    "order": 0
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index.update_settings(json!({ "searchCutoffMs": null })).await;
+    index.wait_task(2).await;
+
+    index
+        .search(json!({ "q": "quick brown fox" }), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(response["degraded"], @"null");
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn attribute_boosts() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "a story", "plot": "the dog" },
+        { "id": 1, "title": "the dog", "plot": "a story" },
+    ]);
+    index.update_settings(json!({ "searchableAttributes": ["title", "plot"] })).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({ "q": "dog", "attributesToRetrieve": ["id"] }), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 0
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "q": "dog", "attributeBoosts": ["plot^3", "title"], "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0
+                  },
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn searchable_attributes_weights() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "a story", "plot": "the dog" },
+        { "id": 1, "title": "the dog", "plot": "a story" },
+    ]);
+    let (response, code) = index
+        .update_settings(json!({
+            "searchableAttributes": ["title", { "attribute": "plot", "weight": 3 }]
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["searchableAttributes"]), @r###"
+    [
+      "title",
+      {
+        "attribute": "plot",
+        "weight": 3
+      }
+    ]
+    "###);
+
+    index
+        .search(json!({ "q": "dog", "attributesToRetrieve": ["id"] }), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // the boosts of the search take precedence over the weights
+    index
+        .search(
+            json!({ "q": "dog", "attributeBoosts": ["title^5"], "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  },
+                  {
+                    "id": 0
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
 #[cfg(feature = "default")]
 #[actix_rt::test]
 async fn camelcased_words() {
@@ -1160,3 +1914,31 @@ async fn simple_search_with_strange_synonyms() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn reranker_falls_back_to_original_order() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let query = json!({ "q": "the", "offset": 1, "limit": 2 });
+    let (expected, code) = index.search_post(query.clone()).await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    // nothing listens on this port, the reranker fails and the hits keep their order
+    let (response, code) = index
+        .update_settings(json!({
+            "reranker": { "url": "http://127.0.0.1:1", "fields": ["title"], "timeoutMs": 100 }
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.search_post(query).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert_eq!(response["hits"], expected["hits"]);
+    assert_eq!(response["estimatedTotalHits"], expected["estimatedTotalHits"]);
+}
@@ -217,6 +217,58 @@ async fn word_ranking_rule_order_exact_words() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_on_exact_attribute_ignores_other_fields() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SIMPLE_SEARCH_DOCUMENTS).await;
+    index.update_settings_typo_tolerance(json!({"disableOnAttributes": ["title"]})).await;
+    index.wait_task(1).await;
+
+    // the words of the exact `title` attribute must not match when searching on `desc`.
+    index
+        .search(
+            json!({"q": "Captain Marvel", "attributesToSearchOn": ["desc"], "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]),
+                    @r###"
+                [
+                  {
+                    "id": "1"
+                  },
+                  {
+                    "id": "2"
+                  }
+                ]
+                "###
+                );
+            },
+        )
+        .await;
+
+    // the words of the exact `title` attribute must still match when searching on it.
+    index
+        .search(
+            json!({"q": "Captain Marvel", "attributesToSearchOn": ["title"], "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]),
+                    @r###"
+                [
+                  {
+                    "id": "3"
+                  },
+                  {
+                    "id": "2"
+                  }
+                ]
+                "###
+                );
+            },
+        )
+        .await;
+}
+
 #[actix_rt::test]
 async fn typo_ranking_rule_order() {
     let server = Server::new().await;
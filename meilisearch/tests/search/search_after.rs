@@ -0,0 +1,141 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "color": "red" },
+        { "id": 2, "color": "red" },
+        { "id": 3, "color": "blue" },
+        { "id": 4, "color": "green" },
+        { "id": 5, "color": "blue" },
+    ])
+});
+
+/// Returns the ids of the hits of each page, following the cursors until the last page.
+async fn search_pages(index: &Index<'_>, query: Value) -> Vec<Vec<serde_json::Value>> {
+    let mut cursor = Some(String::new());
+    let mut pages = Vec::new();
+    while let Some(search_after) = cursor {
+        let mut query = query.0.clone();
+        query["searchAfter"] = search_after.into();
+        query["attributesToRetrieve"] = serde_json::json!(["id"]);
+        let (response, code) = index.search_post(query.into()).await;
+        assert_eq!(code, 200, "{response}");
+        let hits = response["hits"].as_array().unwrap();
+        if !hits.is_empty() {
+            pages.push(hits.iter().map(|hit| hit["id"].clone()).collect());
+        }
+        cursor = response["nextSearchAfter"].as_str().map(String::from);
+    }
+    pages
+}
+
+#[actix_rt::test]
+async fn search_after_pages() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let pages = search_pages(&index, json!({ "limit": 2 })).await;
+    snapshot!(format!("{pages:?}"), @"[[Number(1), Number(2)], [Number(3), Number(4)], [Number(5)]]");
+}
+
+#[actix_rt::test]
+async fn search_after_with_sort() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_sortable_attributes(json!(["color"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    // the documents sharing a color are returned in the order of their ids
+    let pages = search_pages(&index, json!({ "sort": ["color:asc"], "limit": 2 })).await;
+    snapshot!(format!("{pages:?}"), @"[[Number(3), Number(5)], [Number(4), Number(1)], [Number(2)]]");
+
+    let pages = search_pages(&index, json!({ "sort": ["color:desc"], "limit": 2 })).await;
+    snapshot!(format!("{pages:?}"), @"[[Number(1), Number(2)], [Number(4), Number(3)], [Number(5)]]");
+}
+
+#[actix_rt::test]
+async fn search_after_max_total_hits() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_pagination(json!({ "maxTotalHits": 3 })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    // the previous pages count in the maximum total hits
+    let pages = search_pages(&index, json!({ "limit": 2 })).await;
+    snapshot!(format!("{pages:?}"), @"[[Number(1), Number(2)], [Number(3)]]");
+}
+
+#[actix_rt::test]
+async fn search_after_with_distinct() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let pages = search_pages(&index, json!({ "distinct": "color", "limit": 1 })).await;
+    // the documents sharing a color with a previous page are not returned again
+    snapshot!(format!("{pages:?}"), @"[[Number(1)], [Number(3)], [Number(4)]]");
+}
+
+#[actix_rt::test]
+async fn search_after_errors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "searchAfter": "", "page": 2 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `searchAfter` parameter cannot be used together with the `offset`, `page`, `hitsPerPage` or `groupBy` parameters.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "searchAfter": "doggo!" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The cursor `doggo!` given to `searchAfter` is malformed.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+
+    let (response, _code) = index.search_post(json!({ "searchAfter": "", "limit": 1 })).await;
+    let cursor = response["nextSearchAfter"].as_str().unwrap().to_string();
+
+    // the cursor expires once the index is updated
+    index.add_documents(json!([{ "id": 6, "color": "purple" }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "searchAfter": cursor })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The cursor given to `searchAfter` has expired because the index has been updated since it was returned.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+}
@@ -0,0 +1,69 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "title": "the dragon of the mountain" },
+        { "id": 2, "title": "a dragon in the garden" },
+        { "id": 3, "title": "the wagon of the merchant near the drain" },
+        { "id": 4, "title": "the merchant and the mountain" },
+    ])
+});
+
+#[actix_rt::test]
+async fn spelling_suggestions() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "q": "dragin", "showSpellingSuggestions": true }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["spellingSuggestions"]), @r###"
+            [
+              "dragon",
+              "drain"
+            ]
+            "###);
+        })
+        .await;
+
+    // the suggestions are not computed when the query has enough hits
+    index
+        .search(json!({ "q": "the", "showSpellingSuggestions": true }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["spellingSuggestions"]), @"[]");
+        })
+        .await;
+
+    // the suggestions are opt-in
+    index
+        .search(json!({ "q": "dragin" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["spellingSuggestions"]), @"null");
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn spelling_suggestions_bad_type() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({ "q": "dragin", "showSpellingSuggestions": "yes" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.showSpellingSuggestions`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_search_show_spelling_suggestions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_spelling_suggestions"
+    }
+    "###);
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use actix_rt::time::sleep;
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use once_cell::sync::Lazy;
+
+use crate::common::{default_settings, Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "title": "the dragon of the mountain" },
+        { "id": 2, "title": "a dragon in the garden" },
+        { "id": 3, "title": "the merchant and the mountain" },
+    ])
+});
+
+#[actix_rt::test]
+async fn query_suggestions_are_disabled_by_default() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server.service.get("/indexes/test/suggestions?q=drag").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Using the query suggestions requires enabling the `query suggestions` experimental feature. See https://www.meilisearch.com/docs/learn/configuration/instance_options",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggest_the_searched_queries() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = Opt { experimental_query_suggestions: true, ..default_settings(dir.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    index.search_post(json!({ "q": "Dragon" })).await;
+    index.search_post(json!({ "q": "dragon" })).await;
+    index.search_post(json!({ "q": "the  dragon" })).await;
+    index.search_post(json!({ "q": "mountain" })).await;
+    // the queries without any hit and the placeholder searches are not recorded
+    index.search_post(json!({ "q": "unicorn" })).await;
+    index.search_post(json!({})).await;
+
+    // the queries are recorded in the index by a background thread
+    sleep(Duration::from_secs(2)).await;
+
+    let (response, code) = server.service.get("/indexes/test/suggestions?q=drag").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "text": "dragon",
+          "popularity": 2,
+          "typos": 0
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = server.service.get("/indexes/test/suggestions?limit=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "text": "dragon",
+          "popularity": 2,
+          "typos": 0
+        },
+        {
+          "text": "mountain",
+          "popularity": 1,
+          "typos": 0
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = server.service.get("/indexes/test/suggestions?q=unic").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+
+    let (response, code) = server.service.get("/indexes/test/suggestions?limit=ten").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `limit`: could not parse `ten` as a positive integer",
+      "code": "invalid_suggestions_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggestions_limit"
+    }
+    "###);
+}
@@ -0,0 +1,94 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "title": "red shirt" },
+        { "id": 2, "title": "blue shirt" },
+        { "id": 3, "title": "red pants" },
+        { "id": 4, "title": "green pants" },
+    ])
+});
+
+#[actix_rt::test]
+async fn search_within_candidates() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "shirt", "showCandidatesHandle": true })).await;
+    snapshot!(code, @"200 OK");
+    let handle = response["candidatesHandle"].as_str().unwrap().to_string();
+
+    index
+        .search(
+            json!({ "q": "red", "withinCandidates": handle, "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+                snapshot!(json_string!(response["candidatesHandle"]), @"null");
+            },
+        )
+        .await;
+
+    // the handle expires once the index is updated
+    index.add_documents(json!([{ "id": 5, "title": "red socks" }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "red", "withinCandidates": handle })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The candidates handle has expired because the index has been updated since it was returned.",
+      "code": "invalid_search_within_candidates",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_within_candidates"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_within_malformed_candidates() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "red", "withinCandidates": "doggo!" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The candidates handle `doggo!` is malformed.",
+      "code": "invalid_search_within_candidates",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_within_candidates"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "q": "red", "showCandidatesHandle": "yes" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.showCandidatesHandle`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_search_show_candidates_handle",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_candidates_handle"
+    }
+    "###);
+}
@@ -57,6 +57,33 @@ async fn settings_bad_searchable_attributes() {
       "link": "https://docs.meilisearch.com/errors#invalid_settings_searchable_attributes"
     }
     "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "searchableAttributes": ["title", 3] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.searchableAttributes[1]`: expected a string or an object, but found a positive integer: `3`",
+      "code": "invalid_settings_searchable_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_searchable_attributes"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "searchableAttributes": [{ "attribute": "title", "weight": -1 }] }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.searchableAttributes[0].weight`: expected a positive integer, but found a negative integer: `-1`",
+      "code": "invalid_settings_searchable_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_searchable_attributes"
+    }
+    "###);
 }
 
 #[actix_rt::test]
@@ -143,6 +170,36 @@ async fn settings_bad_ranking_rules() {
     "###);
 }
 
+#[actix_rt::test]
+async fn settings_conflicting_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "rankingRules": ["words", "typo", "words"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`words` ranking rule is present more than once. A ranking rule can only be used once.",
+      "code": "invalid_settings_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings_ranking_rules(json!(["price:asc", "words", "price:desc"])).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`price:asc` and `price:desc` ranking rules both apply to the `price` attribute. An attribute can only be used by one custom ranking rule",
+      "code": "invalid_settings_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn settings_bad_stop_words() {
     let server = Server::new().await;
@@ -152,7 +209,7 @@ async fn settings_bad_stop_words() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid value type at `.stopWords`: expected an array, but found a string: `\"doggo\"`",
+      "message": "Invalid value type at `.stopWords`: expected an array or an object, but found a string: `\"doggo\"`",
       "code": "invalid_settings_stop_words",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_stop_words"
@@ -163,7 +220,19 @@ async fn settings_bad_stop_words() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Invalid value type: expected an array, but found a string: `\"doggo\"`",
+      "message": "Invalid value type: expected an array or an object, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_stop_words",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_stop_words"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "stopWords": { "preset": "klingon" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `klingon` at `.stopWords.preset`: expected one of `en`, `fr`, `de`, `es`, `it`, `pt`, `nl`",
       "code": "invalid_settings_stop_words",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_stop_words"
@@ -274,7 +343,7 @@ async fn settings_bad_typo_tolerance() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`",
+      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`, `disableOnNumbers`",
       "code": "invalid_settings_typo_tolerance",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_typo_tolerance"
@@ -337,3 +406,286 @@ async fn settings_bad_pagination() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "localizedAttributes": [{ "attributePatterns": ["title"], "locales": ["klingon"] }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.localizedAttributes[0].locales[0]`: unsupported locale `klingon`, expected one of `ara`, `bul`, `cat`, `ces`, `cmn`, `dan`, `deu`, `ell`, `eng`, `fin`, `fra`, `heb`, `hin`, `hun`, `ind`, `ita`, `jpn`, `kor`, `mar`, `nld`, `nob`, `pes`, `pol`, `por`, `ron`, `rus`, `spa`, `swe`, `tha`, `tur`, `ukr`, `vie`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "localizedAttributes": [{ "locales": ["jpn"] }] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `attributePatterns` inside `.localizedAttributes[0]`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_percolate_queries() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "percolateQueries": ["cheap"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.percolateQueries`: expected an object, but found an array: `[\"cheap\"]`",
+      "code": "invalid_settings_percolate_queries",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_percolate_queries"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "percolateQueries": { "cheap": "price <" } })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_settings_percolate_queries""###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_stemming() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "stemming": ["klingon"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `klingon` at `.stemming[0]`: expected one of `en`, `fr`",
+      "code": "invalid_settings_stemming",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_stemming"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_geo_fields() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "geoFields": "pickup" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.geoFields`: expected an array, but found a string: `\"pickup\"`",
+      "code": "invalid_settings_geo_fields",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_geo_fields"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_geo_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "geoIndex": { "nodeSize": "huge" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `huge` at `.geoIndex.nodeSize`: expected one of `small`, `medium`, `large`",
+      "code": "invalid_settings_geo_index",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_geo_index"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "geoIndex": { "loadStrategy": "lazy" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `lazy` at `.geoIndex.loadStrategy`: expected one of `incremental`, `bulkLoad`",
+      "code": "invalid_settings_geo_index",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_geo_index"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_binary_quantized_vectors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "binaryQuantizedVectors": "yes" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.binaryQuantizedVectors`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_settings_binary_quantized_vectors",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_binary_quantized_vectors"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_vector_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "vectorIndex": { "efSearch": -1 } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.vectorIndex.efSearch`: expected a positive integer, but found a negative integer: `-1`",
+      "code": "invalid_settings_vector_index",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_vector_index"
+    }
+    "###);
+
+    let (response, code) = index.update_settings(json!({ "vectorIndex": { "trees": 4 } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `trees` inside `.vectorIndex`: expected one of `efConstruction`, `efSearch`",
+      "code": "invalid_settings_vector_index",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_vector_index"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_reranker() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "reranker": { "topK": 10 } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `url` inside `.reranker`",
+      "code": "invalid_settings_reranker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_reranker"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "reranker": { "url": "http://localhost:3000", "timeoutMs": "1s" } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.reranker.timeoutMs`: expected a positive integer, but found a string: `\"1s\"`",
+      "code": "invalid_settings_reranker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_reranker"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_version_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "versionAttribute": 2 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.versionAttribute`: expected a string, but found a positive integer: `2`",
+      "code": "invalid_settings_version_attribute",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_version_attribute"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_document_timestamps() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "documentTimestamps": "yes" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.documentTimestamps`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_settings_document_timestamps",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_document_timestamps"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_facet_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "facetSearch": "yes" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetSearch`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_settings_facet_search",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_facet_search"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_prefix_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "prefixSearch": "onSearch" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `onSearch` at `.prefixSearch`: expected one of `indexingTime`, `disabled`",
+      "code": "invalid_settings_prefix_search",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_prefix_search"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_normalization() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "normalization": { "removeDiacritics": "no" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.normalization.removeDiacritics`: expected a boolean, but found a string: `\"no\"`",
+      "code": "invalid_settings_normalization",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_normalization"
+    }
+    "###);
+}
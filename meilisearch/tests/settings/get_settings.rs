@@ -16,6 +16,7 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"]),
     );
     map.insert("stop_words", json!([]));
+    map.insert("index_stop_words", json!(false));
     map.insert("non_separator_tokens", json!([]));
     map.insert("separator_tokens", json!([]));
     map.insert("dictionary", json!([]));
@@ -35,6 +36,39 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
             "maxTotalHits": json!(1000),
         }),
     );
+    map.insert("search_cutoff_ms", json!(null));
+    map.insert("proximity_precision", json!("byWord"));
+    map.insert("prefix_search", json!("indexingTime"));
+    map.insert("split_words", json!(true));
+    map.insert("concatenate_words", json!(true));
+    map.insert("localized_attributes", json!(null));
+    map.insert("normalize_numbers", json!(false));
+    map.insert(
+        "normalization",
+        json!({ "lowercase": true, "removeDiacritics": true, "compatibility": true }),
+    );
+    map.insert("percolate_queries", json!({}));
+    map.insert("stemming", json!([]));
+    map.insert("geo_fields", json!([]));
+    map.insert(
+        "geo_index",
+        json!({
+            "nodeSize": "small",
+            "loadStrategy": "incremental",
+        }),
+    );
+    map.insert("binary_quantized_vectors", json!(false));
+    map.insert(
+        "vector_index",
+        json!({
+            "efConstruction": 100,
+            "efSearch": 100,
+        }),
+    );
+    map.insert("reranker", json!(null));
+    map.insert("version_attribute", json!(null));
+    map.insert("document_timestamps", json!(false));
+    map.insert("facet_search", json!(true));
     map
 });
 
@@ -54,7 +88,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 14);
+    assert_eq!(settings.keys().len(), 33);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -65,6 +99,7 @@ async fn get_settings() {
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])
     );
     assert_eq!(settings["stopWords"], json!([]));
+    assert_eq!(settings["indexStopWords"], json!(false));
     assert_eq!(settings["nonSeparatorTokens"], json!([]));
     assert_eq!(settings["separatorTokens"], json!([]));
     assert_eq!(settings["dictionary"], json!([]));
@@ -83,6 +118,39 @@ async fn get_settings() {
             "maxTotalHits": 1000,
         })
     );
+    assert_eq!(settings["searchCutoffMs"], json!(null));
+    assert_eq!(settings["proximityPrecision"], json!("byWord"));
+    assert_eq!(settings["prefixSearch"], json!("indexingTime"));
+    assert_eq!(settings["splitWords"], json!(true));
+    assert_eq!(settings["concatenateWords"], json!(true));
+    assert_eq!(settings["localizedAttributes"], json!(null));
+    assert_eq!(settings["normalizeNumbers"], json!(false));
+    assert_eq!(
+        settings["normalization"],
+        json!({ "lowercase": true, "removeDiacritics": true, "compatibility": true })
+    );
+    assert_eq!(settings["percolateQueries"], json!({}));
+    assert_eq!(settings["stemming"], json!([]));
+    assert_eq!(settings["geoFields"], json!([]));
+    assert_eq!(
+        settings["geoIndex"],
+        json!({
+            "nodeSize": "small",
+            "loadStrategy": "incremental",
+        })
+    );
+    assert_eq!(settings["binaryQuantizedVectors"], json!(false));
+    assert_eq!(
+        settings["vectorIndex"],
+        json!({
+            "efConstruction": 100,
+            "efSearch": 100,
+        })
+    );
+    assert_eq!(settings["reranker"], json!(null));
+    assert_eq!(settings["versionAttribute"], json!(null));
+    assert_eq!(settings["documentTimestamps"], json!(false));
+    assert_eq!(settings["facetSearch"], json!(true));
 }
 
 #[actix_rt::test]
@@ -113,6 +181,35 @@ async fn test_partial_update() {
     assert_eq!(response["searchableAttributes"], json!(["bar"]));
 }
 
+#[actix_rt::test]
+async fn export_and_replace_all_settings() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index
+        .update_settings(json!({ "displayedAttributes": ["name"], "stopWords": ["the"], "documentTimestamps": true }))
+        .await;
+    index.wait_task(0).await;
+    let (exported, code) = index.settings().await;
+    assert_eq!(code, 200);
+
+    // the exported settings are applied as is on another index
+    let other = server.index("other");
+    let (_response, code) = other.replace_settings(exported.clone()).await;
+    assert_eq!(code, 202);
+    other.wait_task(1).await;
+    let (response, code) = other.settings().await;
+    assert_eq!(code, 200);
+    assert_eq!(response, exported);
+
+    // the settings missing from the body are reset
+    other.replace_settings(json!({ "stopWords": ["a"] })).await;
+    other.wait_task(2).await;
+    let (response, _code) = other.settings().await;
+    assert_eq!(response["stopWords"], json!(["a"]));
+    assert_eq!(response["displayedAttributes"], json!(["*"]));
+    assert_eq!(response["documentTimestamps"], json!(false));
+}
+
 #[actix_rt::test]
 async fn error_delete_settings_unexisting_index() {
     let server = Server::new().await;
@@ -278,13 +375,32 @@ test_setting_routes!(
     searchable_attributes put,
     distinct_attribute put,
     stop_words put,
+    index_stop_words put,
     separator_tokens put,
     non_separator_tokens put,
     dictionary put,
     ranking_rules put,
     synonyms put,
     pagination patch,
-    faceting patch
+    faceting patch,
+    search_cutoff_ms put,
+    proximity_precision put,
+    prefix_search put,
+    split_words put,
+    concatenate_words put,
+    localized_attributes put,
+    normalize_numbers put,
+    normalization patch,
+    percolate_queries put,
+    stemming put,
+    geo_fields put,
+    geo_index patch,
+    binary_quantized_vectors put,
+    vector_index patch,
+    reranker put,
+    version_attribute put,
+    document_timestamps put,
+    facet_search put
 );
 
 #[actix_rt::test]
@@ -325,3 +441,98 @@ async fn set_and_reset_distinct_attribute_with_dedicated_route() {
 
     assert_eq!(response, json!(null));
 }
+
+#[actix_rt::test]
+async fn stop_words_overlapping_the_preset() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    // `the` is part of the english preset, it is still returned as one of the user-defined words
+    let (response, _) = index
+        .update_settings(json!({ "stopWords": { "preset": "en", "words": ["The", "dragon"] } }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.settings().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["stopWords"]), @r###"
+    {
+      "preset": "en",
+      "words": [
+        "The",
+        "dragon"
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn diff_settings() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "synonyms": { "phone": ["mobile"] },
+            "typoTolerance": { "enabled": false },
+        }))
+        .await;
+    index.wait_task(0).await;
+
+    // The settings matching the current ones are not part of the diff
+    let (response, code) = index
+        .settings_diff(json!({
+            "synonyms": { "phone": ["mobile"] },
+            "typoTolerance": { "enabled": false },
+            "stopWords": [],
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @"{}");
+
+    let (response, code) = index
+        .settings_diff(json!({
+            "synonyms": { "phone": ["cellphone"] },
+            "typoTolerance": { "minWordSizeForTypos": { "oneTypo": 3 } },
+            "distinctAttribute": null,
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "synonyms": {
+        "current": {
+          "phone": [
+            "mobile"
+          ]
+        },
+        "submitted": {
+          "phone": [
+            "cellphone"
+          ]
+        }
+      },
+      "typoTolerance": {
+        "current": {
+          "enabled": false,
+          "minWordSizeForTypos": {
+            "oneTypo": 5,
+            "twoTypos": 9
+          },
+          "disableOnWords": [],
+          "disableOnAttributes": [],
+          "disableOnNumbers": false
+        },
+        "submitted": {
+          "minWordSizeForTypos": {
+            "oneTypo": 3
+          }
+        }
+      }
+    }
+    "###);
+
+    // Nothing was enqueued
+    let (response, _) = index.get_task(1).await;
+    assert_eq!(response["message"], json!("Task `1` not found."));
+}
@@ -0,0 +1,94 @@
+use meili_snap::*;
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "The Lord of the Rings, the Fellowship of the Ring", "genre": "fantasy" },
+        { "id": 1, "title": "The Lord of the Rings, the Two Towers", "genre": "fantasy" },
+        { "id": 2, "title": "The Hobbit", "genre": "fantasy" },
+        { "id": 3, "title": "The Rings of Power", "genre": "series" },
+        { "id": 4, "title": "Pride and Prejudice", "genre": "romance" },
+    ])
+});
+
+fn ids(response: &Value) -> Vec<u64> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].as_u64().unwrap()).collect()
+}
+
+#[actix_rt::test]
+async fn similar_by_shared_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_filterable_attributes(json!(["genre"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.similar("0", "").await;
+    snapshot!(code, @"200 OK");
+    // the reference document is never part of the hits.
+    snapshot!(format!("{:?}", ids(&response)), @"[1, 3, 2]");
+    snapshot!(json_string!(response, { ".hits" => "[hits]", ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "hits": "[hits]",
+      "id": "0",
+      "processingTimeMs": "[time]",
+      "limit": 20,
+      "offset": 0,
+      "estimatedTotalHits": 3
+    }
+    "###);
+
+    let (response, code) = index.similar("0", "filter=genre%20%3D%20fantasy").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", ids(&response)), @"[1, 2]");
+
+    let (response, code) = index.similar("0", "offset=1&limit=1&attributesToRetrieve=id").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 3
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn similar_errors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings_filterable_attributes(json!(["genre"])).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.similar("42", "").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Document `42` not found.",
+      "code": "document_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_not_found"
+    }
+    "###);
+
+    let (response, code) = index.similar("0", "offset=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `offset`: could not parse `doggo` as a positive integer",
+      "code": "invalid_similar_offset",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_offset"
+    }
+    "###);
+
+    let (response, code) = index.similar("0", "filter=title%20%3D%20hobbit").await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_similar_filter", "{}", response);
+}
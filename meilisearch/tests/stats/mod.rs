@@ -64,6 +64,8 @@ async fn stats() {
 
     assert_eq!(code, 200);
     assert!(response["databaseSize"].as_u64().unwrap() > 0);
+    // the payload is deleted once its task is processed
+    assert_eq!(response["pendingPayloadsSize"], 0);
     let last_update =
         OffsetDateTime::parse(response["lastUpdate"].as_str().unwrap(), &Rfc3339).unwrap();
     assert!(last_update - timestamp < time::Duration::SECOND);
@@ -1097,3 +1097,51 @@ async fn test_summarized_dump_creation() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn get_batches() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "content": "foobar" }]), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server.service.get("/batches").await;
+    assert_eq!(code, 200);
+    assert_json_snapshot!(response,
+        { ".results[].duration" => "[duration]", ".results[].startedAt" => "[date]", ".results[].finishedAt" => "[date]", ".results[].stats.indexingSteps" => "[steps]" },
+        @r###"
+    {
+      "results": [
+        {
+          "uid": 0,
+          "indexUid": "test",
+          "taskUids": [
+            0
+          ],
+          "stats": {
+            "totalNbTasks": 1,
+            "status": {
+              "succeeded": 1
+            },
+            "types": {
+              "documentAdditionOrUpdate": 1
+            },
+            "indexedDocuments": 1,
+            "deletedDocuments": 0,
+            "indexingSteps": "[steps]"
+          },
+          "duration": "[duration]",
+          "startedAt": "[date]",
+          "finishedAt": "[date]"
+        }
+      ],
+      "limit": 20,
+      "from": 0,
+      "next": null
+    }
+    "###);
+
+    let (response, code) = server.service.get("/batches/1").await;
+    assert_eq!(code, 404);
+    assert_eq!(response["code"], "batch_not_found");
+}
@@ -6,7 +6,7 @@ use std::time::Instant;
 use heed::EnvOpenOptions;
 use milli::{
     execute_search, DefaultSearchLogger, GeoSortStrategy, Index, SearchContext, SearchLogger,
-    TermsMatchingStrategy,
+    TermsMatchingStrategy, TimeBudget,
 };
 
 #[global_allocator]
@@ -55,13 +55,18 @@ fn main() -> Result<(), Box<dyn Error>> {
                 &None,
                 TermsMatchingStrategy::Last,
                 milli::score_details::ScoringStrategy::Skip,
+                None,
                 false,
                 &None,
                 &None,
+                &None,
+                &None,
+                &None,
                 GeoSortStrategy::default(),
                 0,
                 20,
                 None,
+                TimeBudget::max(),
                 &mut DefaultSearchLogger,
                 logger,
             )?;
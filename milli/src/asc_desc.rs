@@ -20,6 +20,8 @@ pub enum AscDescError {
     InvalidSyntax { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a asc/desc rule.")]
     ReservedKeyword { name: String },
+    #[error("The named geo field `{name}` can't be used in a `_geoPoint` asc/desc rule.")]
+    NamedGeoField { name: String },
 }
 
 impl From<BadGeoError> for AscDescError {
@@ -31,7 +33,7 @@ impl From<BadGeoError> for AscDescError {
 impl From<AscDescError> for CriterionError {
     fn from(error: AscDescError) -> Self {
         match error {
-            AscDescError::GeoError(_) => {
+            AscDescError::GeoError(_) | AscDescError::NamedGeoField { .. } => {
                 CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() }
             }
             AscDescError::InvalidSyntax { name } => CriterionError::InvalidName { name },
@@ -61,6 +63,12 @@ impl FromStr for Member {
     fn from_str(text: &str) -> Result<Member, Self::Err> {
         match text.strip_prefix("_geoPoint(").and_then(|text| text.strip_suffix(')')) {
             Some(point) => {
+                // the named geo fields can only be used in the `_geoRadius` and `_geoBoundingBox` filters
+                if let [name, _, _] = point.split(',').map(str::trim).collect::<Vec<_>>()[..] {
+                    if name.parse::<f64>().is_err() {
+                        return Err(AscDescError::NamedGeoField { name: name.to_string() });
+                    }
+                }
                 let (lat, lng) = point
                     .split_once(',')
                     .ok_or_else(|| AscDescError::ReservedKeyword { name: text.to_string() })
@@ -165,6 +173,9 @@ pub enum SortError {
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression. \
                     Use the _geoPoint(latitude, longitude) built-in rule to sort on _geo field coordinates.")]
     ReservedNameForFilter { name: String },
+    #[error("The `_geoPoint` sort only applies to the _geo field coordinates, the named geo field `{name}` \
+                    can only be used in the `_geoRadius` and `_geoBoundingBox` filters.")]
+    NamedGeoField { name: String },
 }
 
 impl From<AscDescError> for SortError {
@@ -172,6 +183,7 @@ impl From<AscDescError> for SortError {
         match error {
             AscDescError::GeoError(error) => SortError::ParseGeoError { error },
             AscDescError::InvalidSyntax { name } => SortError::InvalidName { name },
+            AscDescError::NamedGeoField { name } => SortError::NamedGeoField { name },
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 SortError::BadGeoPointUsage { name }
             }
@@ -262,6 +274,7 @@ mod tests {
             ),
             ("_geoPoint(35, 85, 75):asc", ReservedKeyword { name: S("_geoPoint(35, 85, 75)") }),
             ("_geoPoint(18):asc", ReservedKeyword { name: S("_geoPoint(18)") }),
+            ("_geoPoint(pickup, 35, 85):asc", NamedGeoField { name: S("pickup") }),
             ("_geoPoint(200, 200):asc", GeoError(BadGeoError::Lat(200.))),
             ("_geoPoint(90.000001, 0):asc", GeoError(BadGeoError::Lat(90.000001))),
             ("_geoPoint(0, -180.000001):desc", GeoError(BadGeoError::Lng(-180.000001))),
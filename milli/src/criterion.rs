@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt;
 use std::str::FromStr;
 
@@ -22,9 +23,23 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error(
+        "`{name}` ranking rule is present more than once. A ranking rule can only be used once."
+    )]
+    DuplicatedRankingRule { name: String },
+    #[error(
+        "`{first}` and `{second}` ranking rules both apply to the `{field}` attribute. \
+An attribute can only be used by one custom ranking rule"
+    )]
+    ConflictingCustomRankingRules { field: String, first: String, second: String },
+    #[error(
+        "`{name}` decay ranking rule is invalid. It must be written `decay(attribute, half_life)` \
+with a half life made of a positive number followed by `s`, `m`, `h` or `d`, like `decay(publishedAt, 7d)`."
+    )]
+    InvalidDecay { name: String },
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum Criterion {
     /// Sorted by decreasing number of matched query terms.
     /// Query words at the front of an attribute is considered better than if it was at the back.
@@ -45,6 +60,9 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the freshness of the unix timestamp (in seconds) of the field specified,
+    /// the documents are grouped by the number of half lives elapsed since their timestamp.
+    Decay { field: String, half_life: u64 },
 }
 
 impl Criterion {
@@ -52,6 +70,7 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Decay { field, .. } => Some(field),
             _otherwise => None,
         }
     }
@@ -68,6 +87,7 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            text if text.starts_with("decay(") => parse_decay(text),
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
@@ -79,6 +99,81 @@ impl FromStr for Criterion {
     }
 }
 
+/// Parses a `decay(attribute, half_life)` ranking rule.
+fn parse_decay(text: &str) -> Result<Criterion, CriterionError> {
+    let invalid = || CriterionError::InvalidDecay { name: text.to_string() };
+    let params =
+        text.strip_prefix("decay(").and_then(|s| s.strip_suffix(')')).ok_or_else(invalid)?;
+    let (field, half_life) = params.rsplit_once(',').ok_or_else(invalid)?;
+    let (field, half_life) = (field.trim(), half_life.trim());
+    if field.is_empty() {
+        return Err(invalid());
+    }
+    let field = match Member::from_str(field)? {
+        Member::Field(field) => field,
+        Member::Geo(_) => {
+            return Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })
+        }
+    };
+    let half_life = parse_half_life(half_life).ok_or_else(invalid)?;
+
+    Ok(Criterion::Decay { field, half_life })
+}
+
+/// Parses a duration like `30s`, `15m`, `12h` or `7d` into a number of seconds,
+/// a number without unit is a number of seconds.
+fn parse_half_life(text: &str) -> Option<u64> {
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => text.split_at(index),
+        None => (text, "s"),
+    };
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    let seconds = number.parse::<u64>().ok()?.checked_mul(multiplier)?;
+    (seconds > 0).then_some(seconds)
+}
+
+/// Formats a number of seconds with the largest unit it is a multiple of.
+pub(crate) fn format_half_life(seconds: u64) -> String {
+    [("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60)]
+        .into_iter()
+        .find(|(_, multiplier)| seconds % multiplier == 0)
+        .map_or_else(|| format!("{seconds}s"), |(unit, m)| format!("{}{unit}", seconds / m))
+}
+
+/// Ensures that no ranking rule is declared twice and that an attribute is only
+/// referenced by a single custom (`asc`/`desc`) ranking rule.
+///
+/// The built-in and custom ranking rules can otherwise be interleaved in any order.
+pub fn validate_criteria(criteria: &[Criterion]) -> Result<(), CriterionError> {
+    let mut seen = HashSet::new();
+    let mut custom_fields: Vec<&Criterion> = Vec::new();
+
+    for criterion in criteria {
+        if !seen.insert(criterion) {
+            return Err(CriterionError::DuplicatedRankingRule { name: criterion.to_string() });
+        }
+
+        if let Some(field) = criterion.field_name() {
+            if let Some(first) = custom_fields.iter().find(|c| c.field_name() == Some(field)) {
+                return Err(CriterionError::ConflictingCustomRankingRules {
+                    field: field.to_string(),
+                    first: first.to_string(),
+                    second: criterion.to_string(),
+                });
+            }
+            custom_fields.push(criterion);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn default_criteria() -> Vec<Criterion> {
     vec![
         Criterion::Words,
@@ -103,6 +198,9 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Decay { field, half_life } => {
+                write!(f, "decay({field}, {})", format_half_life(*half_life))
+            }
         }
     }
 }
@@ -129,6 +227,13 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            ("decay(date, 7d)", Criterion::Decay { field: S("date"), half_life: 7 * 24 * 3600 }),
+            ("decay(date,12h)", Criterion::Decay { field: S("date"), half_life: 12 * 3600 }),
+            (
+                "decay(release date, 90)",
+                Criterion::Decay { field: S("release date"), half_life: 90 },
+            ),
+            ("decay(a,b, 15m)", Criterion::Decay { field: S("a,b"), half_life: 15 * 60 }),
         ];
 
         for (input, expected) in valid_criteria {
@@ -164,6 +269,13 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("decay(date)", InvalidDecay { name: S("decay(date)") }),
+            ("decay(date, 7d", InvalidDecay { name: S("decay(date, 7d") }),
+            ("decay(, 7d)", InvalidDecay { name: S("decay(, 7d)") }),
+            ("decay(date, 7w)", InvalidDecay { name: S("decay(date, 7w)") }),
+            ("decay(date, 0d)", InvalidDecay { name: S("decay(date, 0d)") }),
+            ("decay(date, -1d)", InvalidDecay { name: S("decay(date, -1d)") }),
+            ("decay(_geo, 7d)", ReservedName { name: S("_geo") }),
         ];
 
         for (input, expected) in invalid_criteria {
@@ -185,4 +297,42 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn validate_criteria_composition() {
+        let valid = [
+            vec![Criterion::Asc(S("price")), Criterion::Words, Criterion::Desc(S("rank"))],
+            vec![
+                Criterion::Words,
+                Criterion::Desc(S("release_date")),
+                Criterion::Typo,
+                Criterion::Asc(S("price")),
+                Criterion::Proximity,
+                Criterion::Desc(S("rank")),
+            ],
+        ];
+        for criteria in valid {
+            assert!(validate_criteria(&criteria).is_ok(), "{criteria:?} should be valid");
+        }
+
+        let res = validate_criteria(&[Criterion::Words, Criterion::Typo, Criterion::Words]);
+        insta::assert_snapshot!(res.unwrap_err(), @"`words` ranking rule is present more than once. A ranking rule can only be used once.");
+
+        let res = validate_criteria(&[Criterion::Asc(S("price")), Criterion::Desc(S("price"))]);
+        insta::assert_snapshot!(res.unwrap_err(), @"`price:asc` and `price:desc` ranking rules both apply to the `price` attribute. An attribute can only be used by one custom ranking rule");
+
+        let res = validate_criteria(&[
+            Criterion::Decay { field: S("date"), half_life: 3600 },
+            Criterion::Desc(S("date")),
+        ]);
+        insta::assert_snapshot!(res.unwrap_err(), @"`decay(date, 1h)` and `date:desc` ranking rules both apply to the `date` attribute. An attribute can only be used by one custom ranking rule");
+    }
+
+    #[test]
+    fn display_decay_criterion() {
+        for input in ["decay(date, 7d)", "decay(date, 36h)", "decay(date, 90m)", "decay(date, 61s)"]
+        {
+            assert_eq!(input.parse::<Criterion>().unwrap().to_string(), input);
+        }
+    }
 }
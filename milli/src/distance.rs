@@ -1,10 +1,18 @@
 use std::ops;
 
-use instant_distance::Point;
+use instant_distance::{Hnsw, Point, PointId};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::normalize_vector;
 
+/// A point that can be stored in the vector HNSW.
+pub trait VectorPoint: Point + Clone + Serialize + DeserializeOwned {
+    fn from_vector(vector: Vec<f32>) -> Self;
+
+    fn dimensions(&self) -> usize;
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct NDotProductPoint(Vec<f32>);
 
@@ -34,8 +42,105 @@ impl Point for NDotProductPoint {
     }
 }
 
+impl VectorPoint for NDotProductPoint {
+    fn from_vector(vector: Vec<f32>) -> Self {
+        NDotProductPoint::new(vector)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// A vector quantized to a single bit per dimension, set when the dimension is positive.
+///
+/// It is 32 times smaller than the full vector and the proportion of bits that differ
+/// between two quantized vectors approximates the angle between the full vectors.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BinaryQuantizedPoint {
+    dimensions: u32,
+    bits: Vec<u64>,
+}
+
+impl BinaryQuantizedPoint {
+    pub fn new(vector: &[f32]) -> Self {
+        let mut bits = vec![0; vector.len().div_ceil(64)];
+        for (i, value) in vector.iter().enumerate() {
+            if *value > 0.0 {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+        BinaryQuantizedPoint { dimensions: vector.len() as u32, bits }
+    }
+}
+
+impl Point for BinaryQuantizedPoint {
+    fn distance(&self, other: &Self) -> f32 {
+        let different: u32 =
+            self.bits.iter().zip(&other.bits).map(|(a, b)| (a ^ b).count_ones()).sum();
+        different as f32 / self.dimensions.max(1) as f32
+    }
+}
+
+impl VectorPoint for BinaryQuantizedPoint {
+    fn from_vector(vector: Vec<f32>) -> Self {
+        BinaryQuantizedPoint::new(&vector)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions as usize
+    }
+}
+
+/// The parameters of the HNSW storing the vectors of the documents.
+///
+/// They are applied the next time the HNSW is built, when documents are added or deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorIndexParams {
+    /// The number of neighbors considered when inserting a point, higher values
+    /// build a graph giving a better recall but make the indexing slower.
+    pub ef_construction: usize,
+    /// The number of neighbors considered when searching, higher values
+    /// give a better recall but make the searches slower.
+    pub ef_search: usize,
+}
+
+impl Default for VectorIndexParams {
+    fn default() -> Self {
+        VectorIndexParams { ef_construction: 100, ef_search: 100 }
+    }
+}
+
+impl VectorIndexParams {
+    pub fn build_hnsw<P: VectorPoint>(&self, points: Vec<P>) -> (Hnsw<P>, Vec<PointId>) {
+        Hnsw::<P>::builder()
+            .ef_construction(self.ef_construction.max(1))
+            .ef_search(self.ef_search.max(1))
+            .build_hnsw(points)
+    }
+}
+
 /// Returns the dot product similarity score that will between 0.0 and 1.0
 /// if both vectors are normalized. The higher the more similar the vectors are.
 pub fn dot_product_similarity(a: &[f32], b: &[f32]) -> f32 {
     a.iter().zip(b).map(|(a, b)| a * b).sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_quantized_distance() {
+        let point = BinaryQuantizedPoint::new(&[0.5, -0.2, 0.1, -0.9]);
+        let same_signs = BinaryQuantizedPoint::new(&[0.1, -0.7, 0.3, -0.1]);
+        let one_different = BinaryQuantizedPoint::new(&[0.5, 0.2, 0.1, -0.9]);
+        let opposite = BinaryQuantizedPoint::new(&[-0.5, 0.2, -0.1, 0.9]);
+
+        assert_eq!(point.distance(&same_signs), 0.0);
+        assert_eq!(point.distance(&one_different), 0.25);
+        assert_eq!(point.distance(&opposite), 1.0);
+        assert_eq!(point.dimensions(), 4);
+    }
+}
@@ -87,6 +87,17 @@ impl<W: Write> DocumentsBatchBuilder<W> {
         de.deserialize_any(&mut visitor)?
     }
 
+    /// Appends a stream of JSON objects separated by whitespaces, usually one per line,
+    /// into the batch and updates the `DocumentsBatchIndex` accordingly.
+    ///
+    /// The objects are deserialized one after the other, the slice can be a memory-mapped file.
+    pub fn append_ndjson(&mut self, ndjson: &[u8]) -> Result<(), Error> {
+        for result in serde_json::Deserializer::from_slice(ndjson).into_iter::<Object>() {
+            self.append_json_object(&result?)?;
+        }
+        Ok(())
+    }
+
     /// Appends a new CSV file into the batch and updates the `DocumentsBatchIndex` accordingly.
     pub fn append_csv<R: io::Read>(&mut self, mut reader: csv::Reader<R>) -> Result<(), Error> {
         // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
@@ -113,55 +124,8 @@ impl<W: Write> DocumentsBatchBuilder<W> {
             for (i, (field_id, type_)) in typed_fields_ids.iter() {
                 self.value_buffer.clear();
 
-                let value = &record[*i];
-                let trimmed_value = value.trim();
-                match type_ {
-                    AllowedType::Number => {
-                        if trimmed_value.is_empty() {
-                            to_writer(&mut self.value_buffer, &Value::Null)?;
-                        } else if let Ok(integer) = trimmed_value.parse::<i64>() {
-                            to_writer(&mut self.value_buffer, &integer)?;
-                        } else {
-                            match trimmed_value.parse::<f64>() {
-                                Ok(float) => {
-                                    to_writer(&mut self.value_buffer, &float)?;
-                                }
-                                Err(error) => {
-                                    return Err(Error::ParseFloat {
-                                        error,
-                                        line,
-                                        value: value.to_string(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    AllowedType::Boolean => {
-                        if trimmed_value.is_empty() {
-                            to_writer(&mut self.value_buffer, &Value::Null)?;
-                        } else {
-                            match trimmed_value.parse::<bool>() {
-                                Ok(bool) => {
-                                    to_writer(&mut self.value_buffer, &bool)?;
-                                }
-                                Err(error) => {
-                                    return Err(Error::ParseBool {
-                                        error,
-                                        line,
-                                        value: value.to_string(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    AllowedType::String => {
-                        if value.is_empty() {
-                            to_writer(&mut self.value_buffer, &Value::Null)?;
-                        } else {
-                            to_writer(&mut self.value_buffer, value)?;
-                        }
-                    }
-                }
+                let value = parse_csv_value(type_, &record[*i], line)?;
+                to_writer(&mut self.value_buffer, &value)?;
 
                 // We insert into the obkv writer the value buffer that has been filled just above.
                 writer.insert(*field_id, &self.value_buffer)?;
@@ -194,22 +158,81 @@ enum AllowedType {
     String,
     Boolean,
     Number,
+    /// The values are separated by commas, e.g. `horror,comedy`.
+    Array(Box<AllowedType>),
 }
 
 fn parse_csv_header(header: &str) -> (&str, AllowedType) {
     // if there are several separators we only split on the last one.
     match header.rsplit_once(':') {
-        Some((field_name, field_type)) => match field_type {
-            "string" => (field_name, AllowedType::String),
-            "boolean" => (field_name, AllowedType::Boolean),
-            "number" => (field_name, AllowedType::Number),
-            // if the pattern isn't reconized, we keep the whole field.
-            _otherwise => (header, AllowedType::String),
-        },
+        Some((field_name, field_type)) => {
+            let (field_type, is_array) = match field_type.strip_suffix("[]") {
+                Some(field_type) => (field_type, true),
+                None => (field_type, false),
+            };
+            let type_ = match field_type {
+                "string" => AllowedType::String,
+                "boolean" => AllowedType::Boolean,
+                "number" => AllowedType::Number,
+                // if the pattern isn't reconized, we keep the whole field.
+                _otherwise => return (header, AllowedType::String),
+            };
+            if is_array {
+                (field_name, AllowedType::Array(Box::new(type_)))
+            } else {
+                (field_name, type_)
+            }
+        }
         None => (header, AllowedType::String),
     }
 }
 
+/// Converts the value of a CSV cell to the type of its column, an empty cell is `null`.
+fn parse_csv_value(type_: &AllowedType, value: &str, line: usize) -> Result<Value, Error> {
+    let trimmed_value = value.trim();
+    match type_ {
+        AllowedType::Number => {
+            if trimmed_value.is_empty() {
+                Ok(Value::Null)
+            } else if let Ok(integer) = trimmed_value.parse::<i64>() {
+                Ok(Value::from(integer))
+            } else {
+                match trimmed_value.parse::<f64>() {
+                    Ok(float) => Ok(Value::from(float)),
+                    Err(error) => Err(Error::ParseFloat { error, line, value: value.to_string() }),
+                }
+            }
+        }
+        AllowedType::Boolean => {
+            if trimmed_value.is_empty() {
+                Ok(Value::Null)
+            } else {
+                match trimmed_value.parse::<bool>() {
+                    Ok(bool) => Ok(Value::from(bool)),
+                    Err(error) => Err(Error::ParseBool { error, line, value: value.to_string() }),
+                }
+            }
+        }
+        AllowedType::String => {
+            if value.is_empty() {
+                Ok(Value::Null)
+            } else {
+                Ok(Value::from(value))
+            }
+        }
+        AllowedType::Array(type_) => {
+            if trimmed_value.is_empty() {
+                Ok(Value::Null)
+            } else {
+                trimmed_value
+                    .split(',')
+                    .map(|value| parse_csv_value(type_, value.trim(), line))
+                    .collect()
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::io::Cursor;
@@ -254,6 +277,36 @@ mod test {
         assert!(cursor.next_document().unwrap().is_none());
     }
 
+    #[test]
+    fn add_documents_ndjson() {
+        let ndjson_content =
+            "{ \"id\": 1, \"field\": \"hello!\" }\n{ \"id\": 2, \"other\": true }\n";
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.append_ndjson(ndjson_content.as_bytes()).unwrap();
+        assert_eq!(builder.documents_count(), 2);
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+        assert_eq!(index.len(), 3);
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(val, json!({ "id": 1, "field": "hello!" }));
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(val, json!({ "id": 2, "other": true }));
+        assert!(cursor.next_document().unwrap().is_none());
+
+        // a line which is not an object is rejected
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        let result = builder.append_ndjson(b"{ \"id\": 1 }\n[1, 2]\n");
+        assert!(matches!(result, Err(Error::Json(_))));
+    }
+
     #[test]
     fn add_documents_csv() {
         let csv_content = "id:number,field:string\n1,hello!\n2,blabla";
@@ -360,6 +413,51 @@ mod test {
         );
     }
 
+    #[test]
+    fn array_in_field() {
+        let csv_content = r#"city;tags:string[];areas:number[];capitals:boolean[]
+"Boston";"harbor, university";"125.4,232";"true"
+"Paris";"";"105";"false,true""#;
+        let csv = csv::ReaderBuilder::new().delimiter(b';').from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.append_csv(csv).unwrap();
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(
+            val,
+            json!({
+                "city": "Boston",
+                "tags": ["harbor", "university"],
+                "areas": [125.4, 232],
+                "capitals": [true],
+            })
+        );
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(
+            val,
+            json!({
+                "city": "Paris",
+                "tags": null,
+                "areas": [105],
+                "capitals": [false, true],
+            })
+        );
+
+        let csv_content = "city,areas:number[]\nBoston,\"125,big\"";
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        assert!(matches!(builder.append_csv(csv), Err(Error::ParseFloat { line: 1, .. })));
+    }
+
     #[test]
     fn integer_in_field() {
         let csv_content = r#"city,country,pop:number
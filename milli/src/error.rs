@@ -97,6 +97,12 @@ pub enum UserError {
     CriterionError(#[from] CriterionError),
     #[error("Maximum number of documents reached.")]
     DocumentLimitReached,
+    #[error("The payload contains several documents with the same id: `{}`.", .document_ids.join("`, `"))]
+    DuplicateDocumentIds { document_ids: Vec<String> },
+    #[error("The document `{document_id}` has {fields} fields once flattened but a document cannot contain more than {limit} fields.")]
+    DocumentFieldsLimitReached { document_id: String, fields: usize, limit: usize },
+    #[error("The document `{document_id}` has {depth} levels of nested objects but a document cannot be nested more than {limit} levels deep.")]
+    DocumentDepthLimitReached { document_id: String, depth: usize, limit: usize },
     #[error(
         "Document identifier `{}` is invalid. \
 A document identifier can be of type integer or string, \
@@ -118,6 +124,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidFilter(String),
     #[error("Invalid type for filter subexpression: expected: {}, found: {1}.", .0.join(", "))]
     InvalidFilterExpression(&'static [&'static str], Value),
+    #[error("The percolate query `{name}` is not a valid filter: {error}")]
+    InvalidPercolateQuery { name: String, error: String },
     #[error("Attribute `{}` is not sortable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -129,6 +137,30 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` is not filterable and thus, cannot be used as distinct attribute. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidDistinctAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` is not filterable and thus, cannot be used to group the documents. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidGroupByAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("The stop words of the query can only be kept when the `indexStopWords` setting of the index is enabled.")]
+    StopWordsNotIndexed,
     #[error("Attribute `{}` is not facet-searchable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -154,6 +186,12 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         valid_fields: BTreeSet<String>,
         hidden_fields: bool,
     },
+    #[error("Attribute `{}` cannot be boosted as it is not searchable. Available searchable attributes are: `{}{}`.",
+        .field,
+        .valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+        .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+    )]
+    InvalidAttributeBoost { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
     #[error("{}", HeedError::BadOpenOptions)]
     InvalidLmdbOpenOptions,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
@@ -202,6 +240,8 @@ pub enum GeoError {
     BadLatitude { document_id: Value, value: Value },
     #[error("Could not parse longitude in the document with the id: `{document_id}`. Was expecting a finite number but instead got `{value}`.")]
     BadLongitude { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not a valid geometry. Was expecting a GeoJSON `LineString` or `Polygon` but instead got `{value}`.")]
+    BadGeoJson { document_id: Value, value: Value },
 }
 
 fn format_invalid_filter_distribution(
@@ -0,0 +1,205 @@
+use rstar::{DefaultParams, RStarInsertionStrategy, RTree, RTreeParams};
+use serde::{Deserialize, Serialize};
+
+use crate::GeoPoint;
+
+/// The maximum number of points stored in each node of the geo R-tree.
+///
+/// Larger nodes make the tree shallower, which reduces the number of nodes
+/// visited by the queries on very large point sets.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GeoNodeSize {
+    /// Up to 6 points per node.
+    #[default]
+    Small,
+    /// Up to 16 points per node.
+    Medium,
+    /// Up to 64 points per node.
+    Large,
+}
+
+/// How the points of the newly indexed documents are added to the geo R-tree.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GeoLoadStrategy {
+    /// The points are inserted one by one in the existing tree.
+    #[default]
+    Incremental,
+    /// The whole tree is rebuilt with the bulk loading algorithm, which is slower
+    /// to index but produces a better packed tree.
+    BulkLoad,
+}
+
+/// The parameters of the R-trees storing the geo points of the documents.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoIndexParams {
+    pub node_size: GeoNodeSize,
+    pub load_strategy: GeoLoadStrategy,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MediumNodes;
+
+impl RTreeParams for MediumNodes {
+    const MIN_SIZE: usize = 6;
+    const MAX_SIZE: usize = 16;
+    const REINSERTION_COUNT: usize = 5;
+    type DefaultInsertionStrategy = RStarInsertionStrategy;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LargeNodes;
+
+impl RTreeParams for LargeNodes {
+    const MIN_SIZE: usize = 24;
+    const MAX_SIZE: usize = 64;
+    const REINSERTION_COUNT: usize = 20;
+    type DefaultInsertionStrategy = RStarInsertionStrategy;
+}
+
+/// An R-tree of geo points whose node size is chosen at runtime.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GeoRTree {
+    Small(RTree<GeoPoint, DefaultParams>),
+    Medium(RTree<GeoPoint, MediumNodes>),
+    Large(RTree<GeoPoint, LargeNodes>),
+}
+
+impl GeoRTree {
+    pub fn new(node_size: GeoNodeSize) -> GeoRTree {
+        GeoRTree::bulk_load(node_size, Vec::new())
+    }
+
+    pub fn bulk_load(node_size: GeoNodeSize, points: Vec<GeoPoint>) -> GeoRTree {
+        match node_size {
+            GeoNodeSize::Small => GeoRTree::Small(RTree::bulk_load_with_params(points)),
+            GeoNodeSize::Medium => GeoRTree::Medium(RTree::bulk_load_with_params(points)),
+            GeoNodeSize::Large => GeoRTree::Large(RTree::bulk_load_with_params(points)),
+        }
+    }
+
+    pub fn node_size(&self) -> GeoNodeSize {
+        match self {
+            GeoRTree::Small(_) => GeoNodeSize::Small,
+            GeoRTree::Medium(_) => GeoNodeSize::Medium,
+            GeoRTree::Large(_) => GeoNodeSize::Large,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            GeoRTree::Small(rtree) => rtree.size(),
+            GeoRTree::Medium(rtree) => rtree.size(),
+            GeoRTree::Large(rtree) => rtree.size(),
+        }
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = &GeoPoint> + '_> {
+        match self {
+            GeoRTree::Small(rtree) => Box::new(rtree.iter()),
+            GeoRTree::Medium(rtree) => Box::new(rtree.iter()),
+            GeoRTree::Large(rtree) => Box::new(rtree.iter()),
+        }
+    }
+
+    /// Returns the points sorted by increasing distance to the query point.
+    pub fn nearest_neighbor_iter(
+        &self,
+        query_point: &[f64; 3],
+    ) -> Box<dyn Iterator<Item = &GeoPoint> + '_> {
+        match self {
+            GeoRTree::Small(rtree) => Box::new(rtree.nearest_neighbor_iter(query_point)),
+            GeoRTree::Medium(rtree) => Box::new(rtree.nearest_neighbor_iter(query_point)),
+            GeoRTree::Large(rtree) => Box::new(rtree.nearest_neighbor_iter(query_point)),
+        }
+    }
+
+    pub fn insert(&mut self, point: GeoPoint) {
+        match self {
+            GeoRTree::Small(rtree) => rtree.insert(point),
+            GeoRTree::Medium(rtree) => rtree.insert(point),
+            GeoRTree::Large(rtree) => rtree.insert(point),
+        }
+    }
+
+    pub fn remove(&mut self, point: &GeoPoint) -> Option<GeoPoint> {
+        match self {
+            GeoRTree::Small(rtree) => rtree.remove(point),
+            GeoRTree::Medium(rtree) => rtree.remove(point),
+            GeoRTree::Large(rtree) => rtree.remove(point),
+        }
+    }
+
+    /// Adds the points to the tree following the load strategy.
+    pub fn extend(&mut self, points: Vec<GeoPoint>, strategy: GeoLoadStrategy) {
+        match strategy {
+            GeoLoadStrategy::Incremental => points.into_iter().for_each(|point| self.insert(point)),
+            GeoLoadStrategy::BulkLoad => {
+                let mut all_points: Vec<_> = self.iter().cloned().collect();
+                all_points.extend(points);
+                *self = GeoRTree::bulk_load(self.node_size(), all_points);
+            }
+        }
+    }
+
+    /// Rebuilds the tree with the given parameters, the tree is only rebuilt when
+    /// the node size changes or when switching to the bulk loading strategy.
+    pub fn rebuild(self, old: GeoIndexParams, new: GeoIndexParams) -> GeoRTree {
+        let bulk_load = new.load_strategy == GeoLoadStrategy::BulkLoad
+            && old.load_strategy != GeoLoadStrategy::BulkLoad;
+        if old.node_size == new.node_size && !bulk_load {
+            return self;
+        }
+
+        let points: Vec<_> = self.iter().cloned().collect();
+        match new.load_strategy {
+            GeoLoadStrategy::BulkLoad => GeoRTree::bulk_load(new.node_size, points),
+            GeoLoadStrategy::Incremental => {
+                let mut rtree = GeoRTree::new(new.node_size);
+                rtree.extend(points, GeoLoadStrategy::Incremental);
+                rtree
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(count: u32) -> Vec<GeoPoint> {
+        (0..count)
+            .map(|i| {
+                let point = [i as f64 / 10.0, i as f64 / 20.0];
+                GeoPoint::new(crate::lat_lng_to_xyz(&point), (i, point))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rebuild_with_other_parameters() {
+        let mut rtree = GeoRTree::new(GeoNodeSize::Small);
+        rtree.extend(points(100), GeoLoadStrategy::Incremental);
+        assert_eq!(rtree.size(), 100);
+
+        let small = GeoIndexParams::default();
+        let large = GeoIndexParams {
+            node_size: GeoNodeSize::Large,
+            load_strategy: GeoLoadStrategy::BulkLoad,
+        };
+        let mut rtree = rtree.rebuild(small, large);
+        assert_eq!(rtree.node_size(), GeoNodeSize::Large);
+        assert_eq!(rtree.size(), 100);
+
+        rtree.extend(points(10), GeoLoadStrategy::BulkLoad);
+        assert_eq!(rtree.size(), 110);
+        let nearest = rtree.nearest_neighbor_iter(&crate::lat_lng_to_xyz(&[0.0, 0.0])).next();
+        assert_eq!(nearest.map(|point| point.data.1), Some([0.0, 0.0]));
+
+        let removed = rtree.remove(&points(1)[0]);
+        assert!(removed.is_some());
+        assert_eq!(rtree.size(), 109);
+    }
+}
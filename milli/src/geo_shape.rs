@@ -0,0 +1,204 @@
+use serde_json::Value;
+
+/// A geometry of a document read from its `_geojson` field.
+///
+/// The points are expressed as `[latitude, longitude]` even though GeoJSON
+/// writes them longitude first, and the shapes are considered on a flat plane.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoShape {
+    LineString(Vec<[f64; 2]>),
+    /// The exterior ring of the polygon, its first and last points are the same.
+    /// The holes of the polygon are ignored.
+    Polygon(Vec<[f64; 2]>),
+}
+
+impl GeoShape {
+    /// Reads a GeoJSON `LineString` or `Polygon` geometry, returns `None` if the
+    /// geometry is of another type or is invalid.
+    pub fn from_geojson(geojson: &Value) -> Option<GeoShape> {
+        let coordinates = geojson.get("coordinates")?;
+        match geojson.get("type")?.as_str()? {
+            "LineString" => {
+                let points = parse_points(coordinates)?;
+                (points.len() >= 2).then_some(GeoShape::LineString(points))
+            }
+            "Polygon" => {
+                let points = parse_points(coordinates.as_array()?.first()?)?;
+                // a linear ring is closed and has at least four points
+                (points.len() >= 4 && points.first() == points.last())
+                    .then_some(GeoShape::Polygon(points))
+            }
+            _ => None,
+        }
+    }
+
+    fn points(&self) -> &[[f64; 2]] {
+        match self {
+            GeoShape::LineString(points) | GeoShape::Polygon(points) => points,
+        }
+    }
+
+    /// Returns the bottom left and the top right corners of the box enclosing the shape.
+    pub fn bounding_box(&self) -> ([f64; 2], [f64; 2]) {
+        bounding_box(self.points())
+    }
+
+    /// Returns `true` if the shape crosses, lies within, or contains the closed ring of the polygon.
+    pub fn intersects_polygon(&self, ring: &[[f64; 2]]) -> bool {
+        let points = self.points();
+        let crosses = segments(points)
+            .any(|segment| segments(ring).any(|edge| segments_intersect(segment, edge)));
+
+        crosses
+            || ring_contains(ring, points[0])
+            || matches!(self, GeoShape::Polygon(shape) if ring_contains(shape, ring[0]))
+    }
+}
+
+/// Returns the bottom left and the top right corners of the box enclosing the points.
+pub fn bounding_box(points: &[[f64; 2]]) -> ([f64; 2], [f64; 2]) {
+    let mut bottom_left = [f64::INFINITY; 2];
+    let mut top_right = [f64::NEG_INFINITY; 2];
+    for &[lat, lng] in points {
+        bottom_left = [bottom_left[0].min(lat), bottom_left[1].min(lng)];
+        top_right = [top_right[0].max(lat), top_right[1].max(lng)];
+    }
+    (bottom_left, top_right)
+}
+
+/// Parses an array of GeoJSON positions into `[latitude, longitude]` points.
+fn parse_points(positions: &Value) -> Option<Vec<[f64; 2]>> {
+    positions
+        .as_array()?
+        .iter()
+        .map(|position| match position.as_array()?.as_slice() {
+            [lng, lat, ..] => {
+                let point = [lat.as_f64()?, lng.as_f64()?];
+                let valid =
+                    (-90.0..=90.0).contains(&point[0]) && (-180.0..=180.0).contains(&point[1]);
+                valid.then_some(point)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The segments between the consecutive points.
+fn segments(points: &[[f64; 2]]) -> impl Iterator<Item = ([f64; 2], [f64; 2])> + '_ {
+    points.windows(2).map(|window| (window[0], window[1]))
+}
+
+/// The sign of the cross product tells on which side of the line `ab` the point `c` is.
+fn orientation(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Whether the point, known to be on the line `ab`, is between `a` and `b`.
+fn within_segment((a, b): ([f64; 2], [f64; 2]), point: [f64; 2]) -> bool {
+    (a[0].min(b[0])..=a[0].max(b[0])).contains(&point[0])
+        && (a[1].min(b[1])..=a[1].max(b[1])).contains(&point[1])
+}
+
+fn segments_intersect((a, b): ([f64; 2], [f64; 2]), (c, d): ([f64; 2], [f64; 2])) -> bool {
+    let d1 = orientation(c, d, a);
+    let d2 = orientation(c, d, b);
+    let d3 = orientation(a, b, c);
+    let d4 = orientation(a, b, d);
+
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+
+    // the segments touch at one of their ends
+    (d1 == 0.0 && within_segment((c, d), a))
+        || (d2 == 0.0 && within_segment((c, d), b))
+        || (d3 == 0.0 && within_segment((a, b), c))
+        || (d4 == 0.0 && within_segment((a, b), d))
+}
+
+/// Whether the point is inside the closed ring, following the even-odd rule.
+fn ring_contains(ring: &[[f64; 2]], point: [f64; 2]) -> bool {
+    let mut inside = false;
+    for (a, b) in segments(ring) {
+        if (a[1] > point[1]) != (b[1] > point[1]) {
+            let crossing = a[0] + (point[1] - a[1]) * (b[0] - a[0]) / (b[1] - a[1]);
+            if point[0] < crossing {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_geojson_shapes() {
+        let shape = GeoShape::from_geojson(&json!({
+            "type": "LineString",
+            "coordinates": [[2.0, 48.0], [3.0, 49.0]],
+        }));
+        assert_eq!(shape, Some(GeoShape::LineString(vec![[48.0, 2.0], [49.0, 3.0]])));
+
+        let shape = GeoShape::from_geojson(&json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]],
+        }))
+        .unwrap();
+        assert_eq!(shape.bounding_box(), ([0.0, 0.0], [1.0, 1.0]));
+
+        // the ring is not closed
+        let shape = GeoShape::from_geojson(&json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]],
+        }));
+        assert_eq!(shape, None);
+
+        // the latitude is out of bounds
+        let shape = GeoShape::from_geojson(&json!({
+            "type": "LineString",
+            "coordinates": [[2.0, 98.0], [3.0, 49.0]],
+        }));
+        assert_eq!(shape, None);
+
+        let shape = GeoShape::from_geojson(&json!({ "type": "Point", "coordinates": [2.0, 48.0] }));
+        assert_eq!(shape, None);
+    }
+
+    #[test]
+    fn intersect_polygons() {
+        let square = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0], [0.0, 0.0]];
+
+        // crosses the square
+        let line = GeoShape::LineString(vec![[-5.0, 5.0], [15.0, 5.0]]);
+        assert!(line.intersects_polygon(&square));
+        // within the square
+        let line = GeoShape::LineString(vec![[2.0, 2.0], [3.0, 3.0]]);
+        assert!(line.intersects_polygon(&square));
+        // outside the square
+        let line = GeoShape::LineString(vec![[11.0, 2.0], [13.0, 3.0]]);
+        assert!(!line.intersects_polygon(&square));
+
+        // contains the square
+        let polygon = GeoShape::Polygon(vec![
+            [-1.0, -1.0],
+            [-1.0, 11.0],
+            [11.0, 11.0],
+            [11.0, -1.0],
+            [-1.0, -1.0],
+        ]);
+        assert!(polygon.intersects_polygon(&square));
+        // shares a corner with the square
+        let polygon =
+            GeoShape::Polygon(vec![[10.0, 10.0], [10.0, 20.0], [20.0, 20.0], [10.0, 10.0]]);
+        assert!(polygon.intersects_polygon(&square));
+        // the bounding boxes intersect but not the triangles
+        let polygon = GeoShape::Polygon(vec![[6.0, 6.0], [10.0, 6.0], [10.0, 10.0], [6.0, 6.0]]);
+        let triangle = [[0.0, 0.0], [10.0, 0.0], [0.0, 10.0], [0.0, 0.0]];
+        assert!(!polygon.intersects_polygon(&triangle));
+    }
+}
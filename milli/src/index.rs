@@ -8,32 +8,48 @@ use charabia::{Language, Script};
 use heed::flags::Flags;
 use heed::types::*;
 use heed::{CompactionOption, Database, PolyDatabase, RoTxn, RwTxn};
+use itertools::Either;
 use roaring::RoaringBitmap;
 use rstar::RTree;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use time::OffsetDateTime;
 
-use crate::distance::NDotProductPoint;
+use crate::distance::{BinaryQuantizedPoint, NDotProductPoint, VectorIndexParams};
 use crate::error::{InternalError, UserError};
 use crate::facet::FacetType;
 use crate::fields_ids_map::FieldsIdsMap;
+use crate::geo_rtree::{GeoIndexParams, GeoNodeSize, GeoRTree, LargeNodes, MediumNodes};
 use crate::heed_codec::facet::{
     FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{
-    BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec,
+    BEU16StrCodec, ByteSliceRefCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec,
 };
+use crate::localized_attributes_rules::LocalizedAttributesRule;
+use crate::normalization::NormalizationParams;
+use crate::prefix_search::PrefixSearch;
+use crate::proximity::ProximityPrecision;
 use crate::readable_slices::ReadableSlices;
+use crate::reranker::RerankerConfig;
+use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
+use crate::stemming::StemmingLanguage;
+use crate::stop_words::StopWordsPreset;
 use crate::{
-    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
-    FacetDistribution, FieldDistribution, FieldId, FieldIdWordCountCodec, GeoPoint, ObkvCodec,
-    OrderBy, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, BEU16,
-    BEU32,
+    default_criteria, AscDesc, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
+    FacetDistribution, FieldDistribution, FieldId, FieldIdWordCountCodec, GeoPoint, GeoShapeBox,
+    Member, ObkvCodec, OrderBy, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search,
+    U8StrStrCodec, BEU16, BEU32, CREATED_AT_FIELD_NAME, UPDATED_AT_FIELD_NAME,
 };
 
 /// The HNSW data-structure that we serialize, fill and search in.
 pub type Hnsw = instant_distance::Hnsw<NDotProductPoint>;
 
+/// The HNSW of the binary quantized vectors, used instead of the [`Hnsw`] when the
+/// vectors of the index are binary quantized.
+pub type BinaryHnsw = instant_distance::Hnsw<BinaryQuantizedPoint>;
+
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
 
@@ -50,17 +66,26 @@ pub mod main_key {
     pub const FIELDS_IDS_MAP_KEY: &str = "fields-ids-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEOJSON_RTREE_KEY: &str = "geojson-rtree";
+    pub const NAMED_GEO_RTREES_KEY: &str = "named-geo-rtrees";
     /// The prefix of the key that is used to store the, potential big, HNSW structure.
     /// It is concatenated with a big-endian encoded number (non-human readable).
     /// e.g. vector-hnsw0x0032.
     pub const VECTOR_HNSW_KEY_PREFIX: &str = "vector-hnsw";
+    /// The prefix of the key that is used to store the HNSW of the binary quantized vectors,
+    /// it must not start with the prefix of the full precision HNSW.
+    pub const BINARY_VECTOR_HNSW_KEY_PREFIX: &str = "binary-vector-hnsw";
     pub const HARD_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "hard-external-documents-ids";
     pub const NUMBER_FACETED_DOCUMENTS_IDS_PREFIX: &str = "number-faceted-documents-ids";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
+    pub const SEARCHABLE_FIELDS_WEIGHTS_KEY: &str = "searchable-fields-weights";
     pub const SOFT_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "soft-external-documents-ids";
     pub const STOP_WORDS_KEY: &str = "stop-words";
+    pub const USER_DEFINED_STOP_WORDS_KEY: &str = "user-defined-stop-words";
+    pub const STOP_WORDS_PRESET_KEY: &str = "stop-words-preset";
+    pub const INDEX_STOP_WORDS: &str = "index-stop-words";
     pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
     pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const DICTIONARY_KEY: &str = "dictionary";
@@ -71,14 +96,35 @@ pub mod main_key {
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
     pub const CREATED_AT_KEY: &str = "created-at";
     pub const UPDATED_AT_KEY: &str = "updated-at";
+    pub const LAST_APPLIED_BATCH_KEY: &str = "last-applied-batch";
     pub const AUTHORIZE_TYPOS: &str = "authorize-typos";
     pub const ONE_TYPO_WORD_LEN: &str = "one-typo-word-len";
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
+    pub const DISABLE_TYPOS_ON_NUMBERS: &str = "disable-typos-on-numbers";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
+    pub const SEARCH_CUTOFF: &str = "search-cutoff";
+    pub const PROXIMITY_PRECISION: &str = "proximity-precision";
+    pub const PREFIX_SEARCH: &str = "prefix-search";
+    pub const SPLIT_WORDS: &str = "split-words";
+    pub const CONCATENATE_WORDS: &str = "concatenate-words";
+    pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized-attributes-rules";
+    pub const NORMALIZE_NUMBERS: &str = "normalize-numbers";
+    pub const NORMALIZATION_PARAMS: &str = "normalization-params";
+    pub const STEMMING_LANGUAGES: &str = "stemming-languages";
+    pub const PERCOLATE_QUERIES: &str = "percolate-queries";
+    pub const GEO_FIELDS: &str = "geo-fields";
+    pub const GEO_INDEX_PARAMS: &str = "geo-index-params";
+    pub const BINARY_QUANTIZED_VECTORS: &str = "binary-quantized-vectors";
+    pub const VECTOR_INDEX_PARAMS: &str = "vector-index-params";
+    pub const RERANKER: &str = "reranker";
+    pub const VERSION_FIELD_KEY: &str = "version-field";
+    pub const DOCUMENT_TIMESTAMPS_KEY: &str = "document-timestamps";
+    pub const FACET_SEARCH: &str = "facet-search";
+    pub const SUGGESTIONS_FST_KEY: &str = "suggestions-fst";
 }
 
 pub mod db_name {
@@ -480,12 +526,21 @@ impl Index {
     /* geo rtree */
 
     /// Writes the provided `rtree` which associates coordinates to documents ids.
-    pub(crate) fn put_geo_rtree(
-        &self,
-        wtxn: &mut RwTxn,
-        rtree: &RTree<GeoPoint>,
-    ) -> heed::Result<()> {
-        self.main.put::<_, Str, SerdeBincode<RTree<GeoPoint>>>(wtxn, main_key::GEO_RTREE_KEY, rtree)
+    ///
+    /// The tree is stored without its node size, which must match the one of the geo index params.
+    pub(crate) fn put_geo_rtree(&self, wtxn: &mut RwTxn, rtree: &GeoRTree) -> heed::Result<()> {
+        let key = main_key::GEO_RTREE_KEY;
+        match rtree {
+            GeoRTree::Small(rtree) => {
+                self.main.put::<_, Str, SerdeBincode<RTree<GeoPoint>>>(wtxn, key, rtree)
+            }
+            GeoRTree::Medium(rtree) => self
+                .main
+                .put::<_, Str, SerdeBincode<RTree<GeoPoint, MediumNodes>>>(wtxn, key, rtree),
+            GeoRTree::Large(rtree) => {
+                self.main.put::<_, Str, SerdeBincode<RTree<GeoPoint, LargeNodes>>>(wtxn, key, rtree)
+            }
+        }
     }
 
     /// Delete the `rtree` which associates coordinates to documents ids.
@@ -494,14 +549,83 @@ impl Index {
     }
 
     /// Returns the `rtree` which associates coordinates to documents ids.
-    pub fn geo_rtree(&self, rtxn: &RoTxn) -> Result<Option<RTree<GeoPoint>>> {
-        match self
+    pub fn geo_rtree(&self, rtxn: &RoTxn) -> Result<Option<GeoRTree>> {
+        let key = main_key::GEO_RTREE_KEY;
+        let rtree = match self.geo_index_params(rtxn)?.node_size {
+            GeoNodeSize::Small => self
+                .main
+                .get::<_, Str, SerdeBincode<RTree<GeoPoint>>>(rtxn, key)?
+                .map(GeoRTree::Small),
+            GeoNodeSize::Medium => self
+                .main
+                .get::<_, Str, SerdeBincode<RTree<GeoPoint, MediumNodes>>>(rtxn, key)?
+                .map(GeoRTree::Medium),
+            GeoNodeSize::Large => self
+                .main
+                .get::<_, Str, SerdeBincode<RTree<GeoPoint, LargeNodes>>>(rtxn, key)?
+                .map(GeoRTree::Large),
+        };
+        Ok(rtree)
+    }
+
+    /* named geo rtrees */
+
+    /// Writes the provided `rtrees` which associate, for each named geo field,
+    /// the coordinates of the field to documents ids.
+    pub(crate) fn put_named_geo_rtrees(
+        &self,
+        wtxn: &mut RwTxn,
+        rtrees: &BTreeMap<String, GeoRTree>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<BTreeMap<String, GeoRTree>>>(
+            wtxn,
+            main_key::NAMED_GEO_RTREES_KEY,
+            rtrees,
+        )
+    }
+
+    /// Delete the `rtrees` of the named geo fields.
+    pub(crate) fn delete_named_geo_rtrees(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::NAMED_GEO_RTREES_KEY)
+    }
+
+    /// Returns the `rtrees` which associate, for each named geo field,
+    /// the coordinates of the field to documents ids.
+    pub fn named_geo_rtrees(&self, rtxn: &RoTxn) -> Result<BTreeMap<String, GeoRTree>> {
+        Ok(self
             .main
-            .get::<_, Str, SerdeBincode<RTree<GeoPoint>>>(rtxn, main_key::GEO_RTREE_KEY)?
-        {
-            Some(rtree) => Ok(Some(rtree)),
-            None => Ok(None),
-        }
+            .get::<_, Str, SerdeBincode<BTreeMap<String, GeoRTree>>>(
+                rtxn,
+                main_key::NAMED_GEO_RTREES_KEY,
+            )?
+            .unwrap_or_default())
+    }
+
+    /* geojson rtree */
+
+    /// Writes the provided `rtree` which associates the bounding boxes of the shapes to documents ids.
+    pub(crate) fn put_geojson_rtree(
+        &self,
+        wtxn: &mut RwTxn,
+        rtree: &RTree<GeoShapeBox>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<RTree<GeoShapeBox>>>(
+            wtxn,
+            main_key::GEOJSON_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates the bounding boxes of the shapes to documents ids.
+    pub(crate) fn delete_geojson_rtree(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::GEOJSON_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates the bounding boxes of the shapes to documents ids.
+    pub fn geojson_rtree(&self, rtxn: &RoTxn) -> Result<Option<RTree<GeoShapeBox>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<RTree<GeoShapeBox>>>(rtxn, main_key::GEOJSON_RTREE_KEY)?)
     }
 
     /* geo faceted */
@@ -539,26 +663,61 @@ impl Index {
 
     /// Writes the provided `hnsw`.
     pub(crate) fn put_vector_hnsw(&self, wtxn: &mut RwTxn, hnsw: &Hnsw) -> heed::Result<()> {
+        self.put_hnsw_chunks(wtxn, main_key::VECTOR_HNSW_KEY_PREFIX, hnsw)
+    }
+
+    /// Delete the `hnsw`.
+    pub(crate) fn delete_vector_hnsw(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.delete_hnsw_chunks(wtxn, main_key::VECTOR_HNSW_KEY_PREFIX)
+    }
+
+    /// Returns the `hnsw`.
+    pub fn vector_hnsw(&self, rtxn: &RoTxn) -> Result<Option<Hnsw>> {
+        self.hnsw_from_chunks(rtxn, main_key::VECTOR_HNSW_KEY_PREFIX)
+    }
+
+    /// Writes the provided `hnsw` of the binary quantized vectors.
+    pub(crate) fn put_binary_vector_hnsw(
+        &self,
+        wtxn: &mut RwTxn,
+        hnsw: &BinaryHnsw,
+    ) -> heed::Result<()> {
+        self.put_hnsw_chunks(wtxn, main_key::BINARY_VECTOR_HNSW_KEY_PREFIX, hnsw)
+    }
+
+    /// Delete the `hnsw` of the binary quantized vectors.
+    pub(crate) fn delete_binary_vector_hnsw(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.delete_hnsw_chunks(wtxn, main_key::BINARY_VECTOR_HNSW_KEY_PREFIX)
+    }
+
+    /// Returns the `hnsw` of the binary quantized vectors.
+    pub fn binary_vector_hnsw(&self, rtxn: &RoTxn) -> Result<Option<BinaryHnsw>> {
+        self.hnsw_from_chunks(rtxn, main_key::BINARY_VECTOR_HNSW_KEY_PREFIX)
+    }
+
+    fn put_hnsw_chunks<T: Serialize>(
+        &self,
+        wtxn: &mut RwTxn,
+        prefix: &str,
+        hnsw: &T,
+    ) -> heed::Result<()> {
         // We must delete all the chunks before we write the new HNSW chunks.
-        self.delete_vector_hnsw(wtxn)?;
+        self.delete_hnsw_chunks(wtxn, prefix)?;
 
         let chunk_size = 1024 * 1024 * (1024 + 512); // 1.5 GiB
         let bytes = bincode::serialize(hnsw).map_err(|_| heed::Error::Encoding)?;
         for (i, chunk) in bytes.chunks(chunk_size).enumerate() {
             let i = i as u32;
-            let mut key = main_key::VECTOR_HNSW_KEY_PREFIX.as_bytes().to_vec();
+            let mut key = prefix.as_bytes().to_vec();
             key.extend_from_slice(&i.to_be_bytes());
             self.main.put::<_, ByteSlice, ByteSlice>(wtxn, &key, chunk)?;
         }
         Ok(())
     }
 
-    /// Delete the `hnsw`.
-    pub(crate) fn delete_vector_hnsw(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
-        let mut iter = self.main.prefix_iter_mut::<_, ByteSlice, DecodeIgnore>(
-            wtxn,
-            main_key::VECTOR_HNSW_KEY_PREFIX.as_bytes(),
-        )?;
+    fn delete_hnsw_chunks(&self, wtxn: &mut RwTxn, prefix: &str) -> heed::Result<bool> {
+        let mut iter =
+            self.main.prefix_iter_mut::<_, ByteSlice, DecodeIgnore>(wtxn, prefix.as_bytes())?;
         let mut deleted = false;
         while iter.next().transpose()?.is_some() {
             // We do not keep a reference to the key or the value.
@@ -567,12 +726,13 @@ impl Index {
         Ok(deleted)
     }
 
-    /// Returns the `hnsw`.
-    pub fn vector_hnsw(&self, rtxn: &RoTxn) -> Result<Option<Hnsw>> {
+    fn hnsw_from_chunks<T: DeserializeOwned>(
+        &self,
+        rtxn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Option<T>> {
         let mut slices = Vec::new();
-        for result in
-            self.main.prefix_iter::<_, Str, ByteSlice>(rtxn, main_key::VECTOR_HNSW_KEY_PREFIX)?
-        {
+        for result in self.main.prefix_iter::<_, Str, ByteSlice>(rtxn, prefix)? {
             let (_, slice) = result?;
             slices.push(slice);
         }
@@ -645,8 +805,18 @@ impl Index {
                 let fields_ids_map = self.fields_ids_map(rtxn)?;
                 let mut fields_ids = Vec::new();
                 for name in fields.into_iter() {
-                    if let Some(field_id) = fields_ids_map.id(name) {
-                        fields_ids.push(field_id);
+                    if name.contains('*') {
+                        // a pattern displays all the fields matching it
+                        for (field_id, field) in fields_ids_map.iter() {
+                            if crate::is_displayed(field, [name]) && !fields_ids.contains(&field_id)
+                            {
+                                fields_ids.push(field_id);
+                            }
+                        }
+                    } else if let Some(field_id) = fields_ids_map.id(name) {
+                        if !fields_ids.contains(&field_id) {
+                            fields_ids.push(field_id);
+                        }
                     }
                 }
                 Ok(Some(fields_ids))
@@ -667,8 +837,7 @@ impl Index {
         let fields_len = valid_fields.len();
 
         if let Some(dn) = self.displayed_fields(rtxn)? {
-            let displayable_names = dn.iter().map(|s| s.to_string()).collect();
-            valid_fields = &valid_fields & &displayable_names;
+            valid_fields.retain(|field| crate::is_displayed(field, &dn));
         }
 
         let hidden_fields = fields_len > valid_fields.len();
@@ -780,6 +949,33 @@ impl Index {
             .get::<_, Str, SerdeBincode<Vec<_>>>(rtxn, main_key::USER_DEFINED_SEARCHABLE_FIELDS_KEY)
     }
 
+    /// Writes the weights explicitly given to some of the user defined searchable fields.
+    pub(crate) fn put_searchable_fields_weights(
+        &self,
+        wtxn: &mut RwTxn,
+        weights: &BTreeMap<String, u16>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(
+            wtxn,
+            main_key::SEARCHABLE_FIELDS_WEIGHTS_KEY,
+            weights,
+        )
+    }
+
+    pub(crate) fn delete_searchable_fields_weights(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SEARCHABLE_FIELDS_WEIGHTS_KEY)
+    }
+
+    /// Returns the weights of the searchable fields used by the attribute ranking rule,
+    /// the higher the more important the field is. The fields without a weight are ranked
+    /// by their position in the searchable fields.
+    pub fn searchable_fields_weights(&self, rtxn: &RoTxn) -> heed::Result<BTreeMap<String, u16>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<_>>(rtxn, main_key::SEARCHABLE_FIELDS_WEIGHTS_KEY)?
+            .unwrap_or_default())
+    }
+
     /* filterable fields */
 
     /// Writes the filterable fields names in the database.
@@ -888,7 +1084,7 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc/decay fields.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
@@ -896,12 +1092,14 @@ impl Index {
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
                 Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Decay { field, .. } => Some(field),
                 _otherwise => None,
             });
 
         let mut faceted_fields = filterable_fields;
         faceted_fields.extend(sortable_fields);
         faceted_fields.extend(asc_desc_fields);
+        faceted_fields.extend(self.timestamps_fields(rtxn)?.iter().map(|field| field.to_string()));
         if let Some(field) = distinct_field {
             faceted_fields.insert(field.to_owned());
         }
@@ -1064,14 +1262,31 @@ impl Index {
         &self,
         wtxn: &mut RwTxn,
         fst: &fst::Set<A>,
+        user_defined_stop_words: &BTreeSet<String>,
     ) -> heed::Result<()> {
-        self.main.put::<_, Str, ByteSlice>(wtxn, main_key::STOP_WORDS_KEY, fst.as_fst().as_bytes())
+        self.main.put::<_, Str, ByteSlice>(
+            wtxn,
+            main_key::STOP_WORDS_KEY,
+            fst.as_fst().as_bytes(),
+        )?;
+        self.main.put::<_, Str, SerdeBincode<_>>(
+            wtxn,
+            main_key::USER_DEFINED_STOP_WORDS_KEY,
+            user_defined_stop_words,
+        )
     }
 
     pub(crate) fn delete_stop_words(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::USER_DEFINED_STOP_WORDS_KEY)?;
         self.main.delete::<_, Str>(wtxn, main_key::STOP_WORDS_KEY)
     }
 
+    /// The stop words as they were sent by the user, without the words of the preset
+    /// and before their normalization. `None` if they were stored before being kept.
+    pub fn user_defined_stop_words(&self, rtxn: &RoTxn) -> heed::Result<Option<BTreeSet<String>>> {
+        self.main.get::<_, Str, SerdeBincode<_>>(rtxn, main_key::USER_DEFINED_STOP_WORDS_KEY)
+    }
+
     pub fn stop_words<'t>(&self, rtxn: &'t RoTxn) -> Result<Option<fst::Set<&'t [u8]>>> {
         match self.main.get::<_, Str, ByteSlice>(rtxn, main_key::STOP_WORDS_KEY)? {
             Some(bytes) => Ok(Some(fst::Set::new(bytes)?)),
@@ -1079,6 +1294,45 @@ impl Index {
         }
     }
 
+    /// The built-in stop words list selected for this index, `None` if it has never been set.
+    pub fn stop_words_preset(&self, rtxn: &RoTxn) -> heed::Result<Option<StopWordsPreset>> {
+        self.main.get::<_, Str, SerdeJson<StopWordsPreset>>(rtxn, main_key::STOP_WORDS_PRESET_KEY)
+    }
+
+    pub(crate) fn put_stop_words_preset(
+        &self,
+        wtxn: &mut RwTxn,
+        preset: StopWordsPreset,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<StopWordsPreset>>(
+            wtxn,
+            main_key::STOP_WORDS_PRESET_KEY,
+            &preset,
+        )
+    }
+
+    pub(crate) fn delete_stop_words_preset(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::STOP_WORDS_PRESET_KEY)
+    }
+
+    /// Whether the stop words of the documents are indexed for the searches
+    /// keeping the stop words of their query, `false` by default.
+    pub fn index_stop_words(&self, rtxn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(rtxn, main_key::INDEX_STOP_WORDS)? {
+            Some(1) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    pub(crate) fn put_index_stop_words(&self, wtxn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(wtxn, main_key::INDEX_STOP_WORDS, &(flag as u8))
+    }
+
+    pub(crate) fn delete_index_stop_words(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::INDEX_STOP_WORDS)
+    }
+
     /* non separator tokens */
 
     pub(crate) fn put_non_separator_tokens(
@@ -1230,6 +1484,33 @@ impl Index {
         }
     }
 
+    /* suggestions fst */
+
+    /// Writes the FST of the suggestions associated with their popularity.
+    pub(crate) fn put_suggestions_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn,
+        fst: &fst::Map<A>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, ByteSlice>(
+            wtxn,
+            main_key::SUGGESTIONS_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_suggestions_fst(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SUGGESTIONS_FST_KEY)
+    }
+
+    /// Returns the FST of the suggestions associated with their popularity.
+    pub fn suggestions_fst<'t>(&self, rtxn: &'t RoTxn) -> Result<fst::Map<Cow<'t, [u8]>>> {
+        match self.main.get::<_, Str, ByteSlice>(rtxn, main_key::SUGGESTIONS_FST_KEY)? {
+            Some(bytes) => Ok(fst::Map::new(bytes)?.map_data(Cow::Borrowed)?),
+            None => Ok(fst::Map::default().map_data(Cow::Owned)?),
+        }
+    }
+
     /* word documents count */
 
     /// Returns the number of documents ids associated with the given word,
@@ -1269,6 +1550,103 @@ impl Index {
         self.iter_documents(rtxn, ids)?.collect()
     }
 
+    /// Returns the first `limit` ids of the candidates sorted by the given criteria.
+    ///
+    /// The documents without a value for a criterion come after the others and the ties are
+    /// returned by increasing internal id. The geo criteria are ignored.
+    pub fn sorted_documents_ids(
+        &self,
+        rtxn: &RoTxn,
+        candidates: RoaringBitmap,
+        sort: &[AscDesc],
+        limit: usize,
+    ) -> Result<Vec<DocumentId>> {
+        let sortable_fields = self.sortable_fields(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+
+        let mut criteria = Vec::new();
+        for asc_desc in sort {
+            let Member::Field(field) = asc_desc.member() else { continue };
+            if !crate::is_faceted(field, &sortable_fields) {
+                let (valid_fields, hidden_fields) =
+                    self.remove_hidden_fields(rtxn, sortable_fields)?;
+                return Err(UserError::InvalidSortableAttribute {
+                    field: field.to_string(),
+                    valid_fields,
+                    hidden_fields,
+                }
+                .into());
+            }
+            criteria.push((fields_ids_map.id(field), matches!(asc_desc, AscDesc::Asc(_))));
+        }
+
+        let mut output = Vec::new();
+        self.sort_documents_ids_into(rtxn, candidates, &criteria, limit, &mut output)?;
+        Ok(output)
+    }
+
+    /// Pushes the candidates sorted by the field ids and directions into the output, up to the limit.
+    fn sort_documents_ids_into(
+        &self,
+        rtxn: &RoTxn,
+        candidates: RoaringBitmap,
+        criteria: &[(Option<FieldId>, bool)],
+        limit: usize,
+        output: &mut Vec<DocumentId>,
+    ) -> Result<()> {
+        let mut remaining = candidates;
+        let next_criteria = match criteria.split_first() {
+            Some(((Some(field_id), is_ascending), next_criteria)) if remaining.len() > 1 => {
+                let number_db = self
+                    .facet_id_f64_docids
+                    .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+                let string_db = self
+                    .facet_id_string_docids
+                    .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+
+                for db in [number_db, string_db] {
+                    let buckets = if *is_ascending {
+                        Either::Left(ascending_facet_sort(rtxn, db, *field_id, remaining.clone())?)
+                    } else {
+                        Either::Right(descending_facet_sort(
+                            rtxn,
+                            db,
+                            *field_id,
+                            remaining.clone(),
+                        )?)
+                    };
+                    for result in buckets {
+                        if output.len() >= limit {
+                            return Ok(());
+                        }
+                        let (docids, _) = result?;
+                        let bucket = docids & &remaining;
+                        if !bucket.is_empty() {
+                            remaining -= &bucket;
+                            self.sort_documents_ids_into(
+                                rtxn,
+                                bucket,
+                                next_criteria,
+                                limit,
+                                output,
+                            )?;
+                        }
+                    }
+                }
+                next_criteria
+            }
+            Some((_, next_criteria)) if remaining.len() > 1 => next_criteria,
+            _ => {
+                let count = limit.saturating_sub(output.len());
+                output.extend(remaining.into_iter().take(count));
+                return Ok(());
+            }
+        };
+
+        // the documents without any value for the criterion
+        self.sort_documents_ids_into(rtxn, remaining, next_criteria, limit, output)
+    }
+
     /// Returns an iterator over all the documents in the index.
     pub fn all_documents<'a, 't: 'a>(
         &'a self,
@@ -1315,6 +1693,24 @@ impl Index {
         self.main.put::<_, Str, SerdeJson<OffsetDateTime>>(wtxn, main_key::UPDATED_AT_KEY, time)
     }
 
+    /* last applied batch */
+
+    /// Returns the record of the last batch of operations applied to the index.
+    ///
+    /// It is written by the caller in the same transaction as the batch itself, so that it can
+    /// tell whether a batch interrupted before being acknowledged was already committed.
+    pub fn last_applied_batch<T: DeserializeOwned>(&self, rtxn: &RoTxn) -> heed::Result<Option<T>> {
+        self.main.get::<_, Str, SerdeJson<T>>(rtxn, main_key::LAST_APPLIED_BATCH_KEY)
+    }
+
+    pub fn put_last_applied_batch<T: Serialize>(
+        &self,
+        wtxn: &mut RwTxn,
+        batch: &T,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<T>>(wtxn, main_key::LAST_APPLIED_BATCH_KEY, batch)
+    }
+
     pub fn authorize_typos(&self, txn: &RoTxn) -> heed::Result<bool> {
         // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
         // identify 0 as being false, and anything else as true. The absence of a value is true,
@@ -1370,6 +1766,31 @@ impl Index {
         Ok(())
     }
 
+    /// Whether the typos are disallowed on the words mostly composed of digits, false by default.
+    pub fn disable_typos_on_numbers(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)? {
+            Some(0) | None => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_disable_typos_on_numbers(
+        &self,
+        txn: &mut RwTxn,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(
+            txn,
+            main_key::DISABLE_TYPOS_ON_NUMBERS,
+            &(flag as u8),
+        )
+    }
+
+    pub(crate) fn delete_disable_typos_on_numbers(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)
+    }
+
     /// List the words on which typo are not allowed
     pub fn exact_words<'t>(&self, txn: &'t RoTxn) -> Result<Option<fst::Set<Cow<'t, [u8]>>>> {
         match self.main.get::<_, Str, ByteSlice>(txn, main_key::EXACT_WORDS)? {
@@ -1470,6 +1891,385 @@ impl Index {
         self.main.delete::<_, Str>(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
 
+    /// The time budget of a search, in milliseconds.
+    pub fn search_cutoff(&self, txn: &RoTxn) -> heed::Result<Option<u64>> {
+        self.main.get::<_, Str, OwnedType<u64>>(txn, main_key::SEARCH_CUTOFF)
+    }
+
+    pub(crate) fn put_search_cutoff(&self, txn: &mut RwTxn, val: u64) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u64>>(txn, main_key::SEARCH_CUTOFF, &val)
+    }
+
+    pub(crate) fn delete_search_cutoff(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::SEARCH_CUTOFF)
+    }
+
+    /// The granularity at which the word pair proximities are stored, `None` if it has never been set.
+    pub fn proximity_precision(&self, txn: &RoTxn) -> heed::Result<Option<ProximityPrecision>> {
+        self.main.get::<_, Str, SerdeJson<ProximityPrecision>>(txn, main_key::PROXIMITY_PRECISION)
+    }
+
+    pub(crate) fn put_proximity_precision(
+        &self,
+        txn: &mut RwTxn,
+        val: ProximityPrecision,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<ProximityPrecision>>(
+            txn,
+            main_key::PROXIMITY_PRECISION,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_proximity_precision(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::PROXIMITY_PRECISION)
+    }
+
+    /// When the prefix databases are built, at indexing time by default.
+    pub fn prefix_search(&self, txn: &RoTxn) -> heed::Result<PrefixSearch> {
+        // The setting used to be a boolean stored as a u8, where 0 means disabled.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::PREFIX_SEARCH)? {
+            Some(0) => Ok(PrefixSearch::Disabled),
+            _ => Ok(PrefixSearch::IndexingTime),
+        }
+    }
+
+    pub(crate) fn put_prefix_search(
+        &self,
+        txn: &mut RwTxn,
+        prefix_search: PrefixSearch,
+    ) -> heed::Result<()> {
+        let value = match prefix_search {
+            PrefixSearch::IndexingTime => 1u8,
+            PrefixSearch::Disabled => 0u8,
+        };
+        self.main.put::<_, Str, OwnedType<u8>>(txn, main_key::PREFIX_SEARCH, &value)
+    }
+
+    pub(crate) fn delete_prefix_search(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::PREFIX_SEARCH)
+    }
+
+    /// Whether a query word can be split into two words found in the index, `true` by default.
+    pub fn split_words(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::SPLIT_WORDS)? {
+            Some(0) => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_split_words(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(txn, main_key::SPLIT_WORDS, &(flag as u8))
+    }
+
+    pub(crate) fn delete_split_words(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::SPLIT_WORDS)
+    }
+
+    /// Whether adjacent query words can be concatenated into a single word, `true` by default.
+    pub fn concatenate_words(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::CONCATENATE_WORDS)? {
+            Some(0) => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_concatenate_words(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(txn, main_key::CONCATENATE_WORDS, &(flag as u8))
+    }
+
+    pub(crate) fn delete_concatenate_words(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::CONCATENATE_WORDS)
+    }
+
+    /// The rules forcing the locales of the attributes matching their patterns,
+    /// `None` if they have never been set.
+    pub fn localized_attributes_rules(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<Option<Vec<LocalizedAttributesRule>>> {
+        self.main.get::<_, Str, SerdeJson<Vec<LocalizedAttributesRule>>>(
+            rtxn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES,
+        )
+    }
+
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        wtxn: &mut RwTxn,
+        rules: &[LocalizedAttributesRule],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[LocalizedAttributesRule]>>(
+            wtxn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES,
+            &rules,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
+    /// Whether the numbers written with thousands separators are merged
+    /// into a single word, `false` by default.
+    pub fn normalize_numbers(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::NORMALIZE_NUMBERS)? {
+            Some(1) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    pub(crate) fn put_normalize_numbers(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(txn, main_key::NORMALIZE_NUMBERS, &(flag as u8))
+    }
+
+    pub(crate) fn delete_normalize_numbers(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::NORMALIZE_NUMBERS)
+    }
+
+    /// The normalization steps applied to the words, every step is applied by default.
+    pub fn normalization_params(&self, txn: &RoTxn) -> heed::Result<NormalizationParams> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<NormalizationParams>>(txn, main_key::NORMALIZATION_PARAMS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_normalization_params(
+        &self,
+        txn: &mut RwTxn,
+        params: NormalizationParams,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<NormalizationParams>>(
+            txn,
+            main_key::NORMALIZATION_PARAMS,
+            &params,
+        )
+    }
+
+    pub(crate) fn delete_normalization_params(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::NORMALIZATION_PARAMS)
+    }
+
+    /// Whether the vectors are stored quantized to one bit per dimension in the HNSW,
+    /// the nearest neighbors are then reranked with the full vectors of the documents.
+    /// `false` by default.
+    pub fn binary_quantized_vectors(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::BINARY_QUANTIZED_VECTORS)? {
+            Some(1) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    pub(crate) fn put_binary_quantized_vectors(
+        &self,
+        txn: &mut RwTxn,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(
+            txn,
+            main_key::BINARY_QUANTIZED_VECTORS,
+            &(flag as u8),
+        )
+    }
+
+    pub(crate) fn delete_binary_quantized_vectors(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::BINARY_QUANTIZED_VECTORS)
+    }
+
+    pub fn vector_index_params(&self, txn: &RoTxn) -> heed::Result<VectorIndexParams> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<VectorIndexParams>>(txn, main_key::VECTOR_INDEX_PARAMS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_vector_index_params(
+        &self,
+        txn: &mut RwTxn,
+        params: VectorIndexParams,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<VectorIndexParams>>(
+            txn,
+            main_key::VECTOR_INDEX_PARAMS,
+            &params,
+        )
+    }
+
+    pub(crate) fn delete_vector_index_params(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::VECTOR_INDEX_PARAMS)
+    }
+
+    /// The reranker of the best hits of the searches, none by default.
+    pub fn reranker(&self, txn: &RoTxn) -> heed::Result<Option<RerankerConfig>> {
+        self.main.get::<_, Str, SerdeJson<RerankerConfig>>(txn, main_key::RERANKER)
+    }
+
+    pub(crate) fn put_reranker(
+        &self,
+        txn: &mut RwTxn,
+        reranker: &RerankerConfig,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&RerankerConfig>>(txn, main_key::RERANKER, &reranker)
+    }
+
+    pub(crate) fn delete_reranker(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::RERANKER)
+    }
+
+    /// The top-level field holding the version of the documents, none by default.
+    ///
+    /// A document is not replaced by a document with a version lower or equal to its own.
+    pub fn version_field<'a>(&self, rtxn: &'a RoTxn) -> heed::Result<Option<&'a str>> {
+        self.main.get::<_, Str, Str>(rtxn, main_key::VERSION_FIELD_KEY)
+    }
+
+    pub(crate) fn put_version_field(
+        &self,
+        wtxn: &mut RwTxn,
+        version_field: &str,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, Str>(wtxn, main_key::VERSION_FIELD_KEY, version_field)
+    }
+
+    pub(crate) fn delete_version_field(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::VERSION_FIELD_KEY)
+    }
+
+    /// Whether the documents are stamped with the `_createdAt` and `_updatedAt`
+    /// timestamps when they are added, `false` by default.
+    pub fn document_timestamps(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::DOCUMENT_TIMESTAMPS_KEY)? {
+            Some(1) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns the timestamps fields of the documents when they are stamped with timestamps,
+    /// these fields are filterable and sortable without being part of the settings.
+    pub fn timestamps_fields(&self, txn: &RoTxn) -> heed::Result<&'static [&'static str]> {
+        if self.document_timestamps(txn)? {
+            Ok(&[CREATED_AT_FIELD_NAME, UPDATED_AT_FIELD_NAME])
+        } else {
+            Ok(&[])
+        }
+    }
+
+    pub(crate) fn put_document_timestamps(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(
+            txn,
+            main_key::DOCUMENT_TIMESTAMPS_KEY,
+            &(flag as u8),
+        )
+    }
+
+    pub(crate) fn delete_document_timestamps(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DOCUMENT_TIMESTAMPS_KEY)
+    }
+
+    /// Whether the structures used by the facet search are built, `true` by default.
+    pub fn facet_search(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::FACET_SEARCH)? {
+            Some(0) => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_facet_search(&self, txn: &mut RwTxn, flag: bool) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(txn, main_key::FACET_SEARCH, &(flag as u8))
+    }
+
+    pub(crate) fn delete_facet_search(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::FACET_SEARCH)
+    }
+
+    /// The languages used to stem the words at indexing and search time, none by default.
+    pub fn stemming_languages(&self, txn: &RoTxn) -> heed::Result<Vec<StemmingLanguage>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<StemmingLanguage>>>(txn, main_key::STEMMING_LANGUAGES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_stemming_languages(
+        &self,
+        txn: &mut RwTxn,
+        languages: &[StemmingLanguage],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[StemmingLanguage]>>(
+            txn,
+            main_key::STEMMING_LANGUAGES,
+            &languages,
+        )
+    }
+
+    pub(crate) fn delete_stemming_languages(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::STEMMING_LANGUAGES)
+    }
+
+    /// The named filters the new documents are matched against when they are indexed.
+    pub fn percolate_queries(&self, txn: &RoTxn) -> heed::Result<BTreeMap<String, String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<BTreeMap<String, String>>>(txn, main_key::PERCOLATE_QUERIES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_percolate_queries(
+        &self,
+        txn: &mut RwTxn,
+        queries: &BTreeMap<String, String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(txn, main_key::PERCOLATE_QUERIES, queries)
+    }
+
+    pub(crate) fn delete_percolate_queries(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::PERCOLATE_QUERIES)
+    }
+
+    /// The fields holding a `{ "lat": ..., "lng": ... }` object that can be used in
+    /// the `_geoRadius` and `_geoBoundingBox` filters in addition to `_geo`, none by default.
+    pub fn geo_fields(&self, txn: &RoTxn) -> heed::Result<Vec<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<String>>>(txn, main_key::GEO_FIELDS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_geo_fields(&self, txn: &mut RwTxn, fields: &[String]) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[String]>>(txn, main_key::GEO_FIELDS, &fields)
+    }
+
+    pub(crate) fn delete_geo_fields(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::GEO_FIELDS)
+    }
+
+    /// The node size and load strategy of the geo R-trees.
+    pub fn geo_index_params(&self, txn: &RoTxn) -> heed::Result<GeoIndexParams> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<GeoIndexParams>>(txn, main_key::GEO_INDEX_PARAMS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_geo_index_params(
+        &self,
+        txn: &mut RwTxn,
+        params: GeoIndexParams,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<GeoIndexParams>>(txn, main_key::GEO_INDEX_PARAMS, &params)
+    }
+
+    pub(crate) fn delete_geo_index_params(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::GEO_INDEX_PARAMS)
+    }
+
     /* script  language docids */
     /// Retrieve all the documents ids that correspond with (Script, Language) key, `None` if it is any.
     pub fn script_language_documents_ids(
@@ -1521,7 +2321,7 @@ pub(crate) mod tests {
 
     use big_s::S;
     use heed::{EnvOpenOptions, RwTxn};
-    use maplit::hashset;
+    use maplit::{btreeset, hashset};
     use tempfile::TempDir;
 
     use crate::documents::DocumentsBatchReader;
@@ -1531,7 +2331,7 @@ pub(crate) mod tests {
         self, DeleteDocuments, DeletionStrategy, IndexDocuments, IndexDocumentsConfig,
         IndexDocumentsMethod, IndexerConfig, Settings,
     };
-    use crate::{db_snap, obkv_to_json, Filter, Index, Search, SearchResult};
+    use crate::{db_snap, obkv_to_json, AscDesc, Filter, Index, Search, SearchResult};
 
     pub(crate) struct TempIndex {
         pub inner: Index,
@@ -1829,6 +2629,52 @@ pub(crate) mod tests {
         assert_eq!(user_defined, &["doggo", "name"]);
     }
 
+    #[test]
+    fn set_attribute_patterns_and_add_documents() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("title"), S("meta.*")]);
+                settings.set_displayed_fields(vec![S("id"), S("*_id"), S("meta.*")]);
+                settings.set_filterable_fields(hashset! { S("*_id") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "kevin", "user_id": 3, "meta": { "author": "bob" } },
+                { "id": 2, "title": "jean", "group_id": 4, "meta": { "lang": "fr" } },
+            ]))
+            .unwrap();
+
+        // the patterns are expanded with the fields of the new documents
+        let rtxn = index.read_txn().unwrap();
+
+        let real = index.searchable_fields(&rtxn).unwrap().unwrap();
+        assert_eq!(&real[..2], &["title", "meta.*"]);
+        assert!(real.contains(&"meta.author") && real.contains(&"meta.lang"));
+        assert!(!real.contains(&"meta"));
+
+        let faceted = index.faceted_fields(&rtxn).unwrap();
+        assert_eq!(faceted, hashset! { S("user_id"), S("group_id") });
+
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let displayed: HashSet<_> = index
+            .displayed_fields_ids(&rtxn)
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|id| fields_ids_map.name(id).unwrap())
+            .collect();
+        assert_eq!(displayed, hashset! { "id", "user_id", "group_id", "meta.author", "meta.lang" });
+
+        let (valid, hidden) =
+            index.remove_hidden_fields(&rtxn, ["title", "user_id", "meta.lang"]).unwrap();
+        assert_eq!(valid, btreeset! { S("user_id"), S("meta.lang") });
+        assert!(hidden);
+    }
+
     #[test]
     fn test_basic_geo_bounding_box() {
         let index = TempIndex::new();
@@ -2719,6 +3565,7 @@ pub(crate) mod tests {
             candidates: _,
             document_scores: _,
             mut documents_ids,
+            degraded: _,
         } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
@@ -2794,4 +3641,43 @@ pub(crate) mod tests {
 
         db_snap!(index, geo_faceted_documents_ids); // ensure that no documents were inserted
     }
+
+    #[test]
+    fn sort_documents_ids() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_string());
+                settings.set_sortable_fields(hashset! { S("price"), S("name") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "price": 10, "name": "b" },
+                { "id": 1, "price": 5 },
+                { "id": 2, "name": "a" },
+                { "id": 3, "price": 10, "name": "c" },
+                { "id": 4, "price": "cheap" },
+                { "id": 5 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let candidates = index.documents_ids(&rtxn).unwrap();
+        let sort: Vec<AscDesc> = vec!["price:asc".parse().unwrap(), "name:desc".parse().unwrap()];
+
+        let ids = index.sorted_documents_ids(&rtxn, candidates.clone(), &sort, 10).unwrap();
+        assert_eq!(ids, vec![1, 3, 0, 4, 2, 5]);
+        let ids = index.sorted_documents_ids(&rtxn, candidates.clone(), &sort, 3).unwrap();
+        assert_eq!(ids, vec![1, 3, 0]);
+
+        let sort: Vec<AscDesc> = vec!["id:asc".parse().unwrap()];
+        let err = index.sorted_documents_ids(&rtxn, candidates, &sort, 10).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UserError(crate::error::UserError::InvalidSortableAttribute { .. })
+        ));
+    }
 }
@@ -15,12 +15,22 @@ mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
+pub mod geo_rtree;
+pub mod geo_shape;
 pub mod heed_codec;
 pub mod index;
+pub mod localized_attributes_rules;
+pub mod normalization;
+pub mod number_normalization;
+pub mod percolator;
+pub mod prefix_search;
 pub mod proximity;
 mod readable_slices;
+pub mod reranker;
 pub mod score_details;
 mod search;
+pub mod stemming;
+pub mod stop_words;
 pub mod update;
 
 #[cfg(test)]
@@ -44,7 +54,7 @@ use serde_json::Value;
 pub use {charabia as tokenizer, heed};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::criterion::{default_criteria, validate_criteria, Criterion, CriterionError};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -58,9 +68,10 @@ pub use self::heed_codec::{
 };
 pub use self::index::Index;
 pub use self::search::{
-    FacetDistribution, FacetValueHit, Filter, FormatOptions, MatchBounds, MatcherBuilder,
-    MatchingWords, OrderBy, Search, SearchForFacetValues, SearchResult, TermsMatchingStrategy,
-    DEFAULT_VALUES_PER_FACET,
+    FacetDistribution, FacetValueHit, Filter, FormatOptions, Group, GroupsResult, MatchBounds,
+    MatcherBuilder, MatchingWords, MinimumShouldMatch, OrderBy, Search, SearchAfter,
+    SearchForFacetValues, SearchResult, Similar, SpellingSuggestions, Suggest, Suggestion,
+    TermsMatchingStrategy, TimeBudget, DEFAULT_SEARCH_CUTOFF_MS, DEFAULT_VALUES_PER_FACET,
 };
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -87,6 +98,11 @@ pub type SmallVec8<T> = smallvec::SmallVec<[T; 8]>;
 /// expressed in term of latitude and longitude.
 pub type GeoPoint = rstar::primitives::GeomWithData<[f64; 3], (DocumentId, [f64; 2])>;
 
+/// A GeoShapeBox is the bounding box of the `_geojson` shape of a document, its corners
+/// are expressed in term of latitude and longitude.
+pub type GeoShapeBox =
+    rstar::primitives::GeomWithData<rstar::primitives::Rectangle<[f64; 2]>, DocumentId>;
+
 /// The maximum length a LMDB key can be.
 ///
 /// Note that the actual allowed length is a little bit higher, but
@@ -104,6 +120,14 @@ pub const MAX_WORD_LENGTH: usize = MAX_LMDB_KEY_LENGTH / 2;
 
 pub const MAX_POSITION_PER_ATTRIBUTE: u32 = u16::MAX as u32 + 1;
 
+/// The field holding the date at which a document was first added, when the index
+/// stamps the documents with timestamps.
+pub const CREATED_AT_FIELD_NAME: &str = "_createdAt";
+
+/// The field holding the date at which a document was last added or updated, when the
+/// index stamps the documents with timestamps.
+pub const UPDATED_AT_FIELD_NAME: &str = "_updatedAt";
+
 // Convert an absolute word position into a relative position.
 // Return the field id of the attribute related to the absolute position
 // and the relative position in the attribute.
@@ -279,12 +303,63 @@ pub fn is_faceted(field: &str, faceted_fields: impl IntoIterator<Item = impl AsR
 /// assert!(!is_faceted_by("animaux.chien", "animaux.ch"));
 /// assert!(!is_faceted_by("animaux.chien", "animaux.chi"));
 /// assert!(!is_faceted_by("animaux.chien", "animaux.chie"));
+///
+/// // -- the patterns, a `*` matches any sequence of characters
+/// assert!(is_faceted_by("meta.author", "meta.*"));
+/// assert!(is_faceted_by("meta.author.name", "meta.*"));
+/// assert!(is_faceted_by("user_id", "*_id"));
+/// assert!(is_faceted_by("user_id.kind", "*_id"));
+/// assert!(is_faceted_by("animaux.chien", "anim*.chien"));
+/// assert!(!is_faceted_by("meta", "meta.*"));
+/// assert!(!is_faceted_by("user_ids", "*_id"));
 /// ```
 pub fn is_faceted_by(field: &str, facet: &str) -> bool {
+    if facet.contains('*') {
+        return field
+            .match_indices('.')
+            .map(|(i, _)| &field[..i])
+            .chain(std::iter::once(field))
+            .any(|prefix| matches_pattern(prefix, facet));
+    }
+
     field.starts_with(facet)
         && field[facet.len()..].chars().next().map(|c| c == '.').unwrap_or(true)
 }
 
+/// Returns `true` if the field is displayed by one of the displayed fields,
+/// either because it is one of them or because it matches one of their patterns.
+pub fn is_displayed(
+    field: &str,
+    displayed_fields: impl IntoIterator<Item = impl AsRef<str>>,
+) -> bool {
+    displayed_fields.into_iter().any(|displayed| {
+        let displayed = displayed.as_ref();
+        displayed == field || (displayed.contains('*') && matches_pattern(field, displayed))
+    })
+}
+
+/// Returns `true` if the whole text matches the pattern, where a `*` matches any sequence of characters.
+fn matches_pattern(text: &str, pattern: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else { return false };
+
+    let mut parts: Vec<_> = parts.collect();
+    let last = match parts.pop() {
+        Some(last) => last,
+        None => return rest.is_empty(),
+    };
+
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
 pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use charabia::{Language, Script};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A rule forcing the languages used to tokenize and normalize
+/// the attributes matching one of its patterns.
+///
+/// An attribute pattern is either an exact attribute name, or a name
+/// starting and/or ending with a `*` wildcard, e.g. `*_ja` or `title.*`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedAttributesRule {
+    pub attribute_patterns: Vec<String>,
+    #[serde(serialize_with = "serialize_locales", deserialize_with = "deserialize_locales")]
+    pub locales: Vec<Language>,
+}
+
+impl LocalizedAttributesRule {
+    pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
+        Self { attribute_patterns, locales }
+    }
+
+    /// Returns `true` if the given attribute matches one of the patterns of this rule.
+    pub fn match_attribute(&self, attribute: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| match_pattern(pattern, attribute))
+    }
+
+    /// Returns the allow list to give to the tokenizer to restrict
+    /// the language detection to the locales of this rule.
+    pub fn allow_list(&self) -> HashMap<Script, Vec<Language>> {
+        locales_allow_list(&self.locales)
+    }
+}
+
+/// Returns the first rule matching the given attribute, if any.
+pub fn find_rule<'a>(
+    rules: &'a [LocalizedAttributesRule],
+    attribute: &str,
+) -> Option<&'a LocalizedAttributesRule> {
+    rules.iter().find(|rule| rule.match_attribute(attribute))
+}
+
+/// Builds a tokenizer allow list from a list of locales by associating
+/// every locale to the scripts it is written with.
+pub fn locales_allow_list(locales: &[Language]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for &locale in locales {
+        for &script in locale_scripts(locale) {
+            let languages = allow_list.entry(script).or_default();
+            if !languages.contains(&locale) {
+                languages.push(locale);
+            }
+        }
+    }
+    allow_list
+}
+
+fn locale_scripts(locale: Language) -> &'static [Script] {
+    use Language::*;
+
+    match locale {
+        Cmn | Jpn => &[Script::Cj],
+        Kor => &[Script::Hangul],
+        Tha => &[Script::Thai],
+        Heb => &[Script::Hebrew],
+        Ell => &[Script::Greek],
+        Ara | Pes => &[Script::Arabic],
+        Hin | Mar => &[Script::Devanagari],
+        Rus | Ukr | Bul => &[Script::Cyrillic],
+        _ => &[Script::Latin],
+    }
+}
+
+/// Returns `true` if the attribute matches the pattern, an exact attribute name
+/// or a name starting and/or ending with a `*` wildcard.
+pub fn match_pattern(pattern: &str, attribute: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+        (Some(_), Some(_)) => attribute.contains(&pattern[1..pattern.len() - 1]),
+        (Some(suffix), None) => attribute.ends_with(suffix),
+        (None, Some(prefix)) => attribute.starts_with(prefix),
+        (None, None) => attribute == pattern,
+    }
+}
+
+fn serialize_locales<S: Serializer>(
+    locales: &[Language],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(locales.iter().map(|locale| locale.name()))
+}
+
+fn deserialize_locales<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Language>, D::Error> {
+    let names = Vec::<String>::deserialize(deserializer)?;
+    Ok(names.iter().map(|name| Language::from_name(name.as_str())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_attribute_patterns() {
+        let rule = LocalizedAttributesRule::new(
+            vec!["title".to_string(), "*_ja".to_string(), "description.*".to_string()],
+            vec![Language::Jpn],
+        );
+
+        assert!(rule.match_attribute("title"));
+        assert!(rule.match_attribute("name_ja"));
+        assert!(rule.match_attribute("description.short"));
+        assert!(!rule.match_attribute("subtitle"));
+        assert!(!rule.match_attribute("name_jap"));
+        assert!(!rule.match_attribute("description"));
+
+        let rule = LocalizedAttributesRule::new(vec!["*".to_string()], vec![Language::Cmn]);
+        assert!(rule.match_attribute("anything"));
+    }
+
+    #[test]
+    fn allow_list_groups_locales_by_script() {
+        let allow_list = locales_allow_list(&[Language::Jpn, Language::Eng, Language::Fra]);
+        assert_eq!(allow_list.get(&Script::Cj), Some(&vec![Language::Jpn]));
+        assert_eq!(allow_list.get(&Script::Latin), Some(&vec![Language::Eng, Language::Fra]));
+        assert_eq!(allow_list.get(&Script::Hangul), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::borrow::Cow;
+
+use charabia::{Script, Token, TokenKind, Tokenize};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// The normalization steps applied to the words of the documents and of the queries.
+///
+/// Every step is applied by default. Disabling some of them only changes the words
+/// written in an alphabetic script: latin, cyrillic or greek.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizationParams {
+    /// Lowercases the words, `É` is normalized into `é`.
+    pub lowercase: bool,
+    /// Removes the diacritics, `é` is normalized into `e`.
+    pub remove_diacritics: bool,
+    /// Applies the unicode compatibility decomposition, `ﬁ` is normalized into `fi`.
+    pub compatibility: bool,
+}
+
+impl Default for NormalizationParams {
+    fn default() -> Self {
+        Self { lowercase: true, remove_diacritics: true, compatibility: true }
+    }
+}
+
+/// Normalizes the words again from the original text when some normalization steps are disabled,
+/// the tokens are left untouched with the default parameters.
+///
+/// The char map of the tokens is rebuilt so that they can still be highlighted in the original text.
+/// The tokenizer classifies the stop words on their default normalization, the normalized words
+/// are therefore classified again against the given stop words, normalized with the same parameters.
+pub fn normalize_words<'t, 's, I>(
+    text: &'t str,
+    tokens: I,
+    params: NormalizationParams,
+    stop_words: Option<&'s fst::Set<&'s [u8]>>,
+) -> impl Iterator<Item = Token<'t>> + 's
+where
+    't: 's,
+    I: Iterator<Item = Token<'t>> + 's,
+{
+    tokens.map(move |token| {
+        let alphabetic = matches!(token.script, Script::Latin | Script::Cyrillic | Script::Greek);
+        if params == NormalizationParams::default() || token.is_separator() || !alphabetic {
+            return token;
+        }
+
+        let mut token = normalize_word(text, token, params);
+        if let Some(stop_words) = stop_words {
+            if token.is_word() || token.is_stopword() {
+                token.kind = if stop_words.contains(token.lemma()) {
+                    TokenKind::StopWord
+                } else {
+                    TokenKind::Word
+                };
+            }
+        }
+        token
+    })
+}
+
+/// Normalizes a whole text with the given parameters, the way the settings words are stored.
+pub(crate) fn normalize_text(text: &str, params: NormalizationParams) -> String {
+    normalize_words(text, text.tokenize(), params, None)
+        .map(|token| token.lemma().to_string())
+        .collect()
+}
+
+fn normalize_word<'t>(text: &str, mut token: Token<'t>, params: NormalizationParams) -> Token<'t> {
+    let original = &text[token.byte_start..token.byte_end];
+    let mut lemma = String::with_capacity(original.len());
+    let mut char_map = Vec::new();
+
+    let mut chars = original.chars().peekable();
+    while let Some(c) = chars.next() {
+        // a character is normalized along with the combining marks following it.
+        let mut cluster = String::from(c);
+        let mut marks = Vec::new();
+        while let Some(mark) = chars.next_if(|c| is_combining_mark(*c)) {
+            cluster.push(mark);
+            marks.push(mark);
+        }
+
+        let mut normalized: String = match (params.compatibility, params.remove_diacritics) {
+            (true, true) => cluster.nfkd().filter(|c| !is_combining_mark(*c)).collect(),
+            (true, false) => cluster.nfkc().collect(),
+            (false, true) => cluster.nfd().filter(|c| !is_combining_mark(*c)).collect(),
+            (false, false) => cluster.nfc().collect(),
+        };
+        if params.lowercase {
+            normalized = normalized.to_lowercase();
+        }
+
+        lemma.push_str(&normalized);
+        char_map.push((c.len_utf8() as u8, normalized.len() as u8));
+        char_map.extend(marks.iter().map(|mark| (mark.len_utf8() as u8, 0)));
+    }
+
+    token.lemma = Cow::Owned(lemma);
+    if token.char_map.is_some() {
+        token.char_map = Some(char_map);
+    }
+    token
+}
+
+#[cfg(test)]
+mod tests {
+    use charabia::TokenizerBuilder;
+
+    use super::*;
+
+    fn lemmas(text: &str, params: NormalizationParams) -> Vec<String> {
+        normalize_words(text, text.tokenize(), params, None)
+            .filter(|token| !token.is_separator())
+            .map(|token| token.lemma().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn disable_normalization_steps() {
+        let text = "Élégant ﬁnance";
+
+        let params = NormalizationParams::default();
+        assert_eq!(lemmas(text, params), ["elegant", "finance"]);
+
+        let params = NormalizationParams { remove_diacritics: false, ..Default::default() };
+        assert_eq!(lemmas(text, params), ["élégant", "finance"]);
+
+        let params = NormalizationParams { lowercase: false, ..Default::default() };
+        assert_eq!(lemmas(text, params), ["Elegant", "finance"]);
+
+        let params = NormalizationParams { compatibility: false, ..Default::default() };
+        assert_eq!(lemmas(text, params), ["elegant", "ﬁnance"]);
+
+        // the decomposed and composed forms of a character are normalized the same way
+        let params = NormalizationParams { remove_diacritics: false, ..Default::default() };
+        assert_eq!(lemmas("e\u{301}te\u{301}", params), ["été"]);
+    }
+
+    #[test]
+    fn classify_the_stop_words_with_the_normalization() {
+        let params = NormalizationParams { lowercase: false, ..Default::default() };
+        let stop_words = fst::Set::from_iter([normalize_text("The", params)]).unwrap();
+        let stop_words = fst::Set::new(stop_words.as_fst().as_bytes()).unwrap();
+
+        let mut builder = TokenizerBuilder::new();
+        builder.stop_words(&stop_words);
+        let tokenizer = builder.build();
+
+        let text = "The the";
+        let kinds: Vec<_> =
+            normalize_words(text, tokenizer.tokenize(text), params, Some(&stop_words))
+                .filter(|token| !token.is_separator())
+                .map(|token| (token.lemma().to_string(), token.is_stopword()))
+                .collect();
+        assert_eq!(kinds, [(String::from("The"), true), (String::from("the"), false)]);
+    }
+}
@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::mem;
+use std::ops::RangeInclusive;
+
+use charabia::{Token, TokenKind};
+
+/// The characters that can be used to separate the thousands of a number,
+/// e.g. `1,000`, `1 000` or `1 000`.
+const GROUP_SEPARATORS: &[char] = &[',', ' ', '\u{a0}', '\u{202f}'];
+
+/// Merges the digit groups of the numbers written with thousands separators into
+/// a single word, `1,000` and `1 000` are both normalized into `1000`.
+///
+/// The merged token spans the whole original number, separators included,
+/// so that it can still be highlighted in the original text.
+pub fn normalize_numbers<'t, I>(tokens: I, enabled: bool) -> NormalizeNumbers<'t, I>
+where
+    I: Iterator<Item = Token<'t>>,
+{
+    NormalizeNumbers { tokens, enabled, pending: VecDeque::new() }
+}
+
+pub struct NormalizeNumbers<'t, I> {
+    tokens: I,
+    enabled: bool,
+    /// The tokens that were read ahead but couldn't be merged.
+    pending: VecDeque<Token<'t>>,
+}
+
+impl<'t, I: Iterator<Item = Token<'t>>> NormalizeNumbers<'t, I> {
+    fn next_token(&mut self) -> Option<Token<'t>> {
+        self.pending.pop_front().or_else(|| self.tokens.next())
+    }
+}
+
+impl<'t, I: Iterator<Item = Token<'t>>> Iterator for NormalizeNumbers<'t, I> {
+    type Item = Token<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut token = self.next_token()?;
+        if !self.enabled || token.kind != TokenKind::Word {
+            return Some(token);
+        }
+
+        // the tokenizer may keep a number and its separators in a single token.
+        if is_grouped_number(&token.lemma) {
+            return Some(strip_group_separators(token));
+        }
+
+        if !is_digit_group(&token.lemma, 1..=3) {
+            return Some(token);
+        }
+
+        // otherwise we merge the following `separator, group of 3 digits` tokens.
+        loop {
+            let Some(separator) = self.next_token() else { break };
+            if !is_group_separator(&separator) {
+                self.pending.push_front(separator);
+                break;
+            }
+
+            match self.next_token() {
+                Some(group)
+                    if group.kind == TokenKind::Word && is_digit_group(&group.lemma, 3..=3) =>
+                {
+                    token = merge_group(token, &separator, group);
+                }
+                Some(other) => {
+                    self.pending.push_front(other);
+                    self.pending.push_front(separator);
+                    break;
+                }
+                None => {
+                    self.pending.push_front(separator);
+                    break;
+                }
+            }
+        }
+
+        Some(token)
+    }
+}
+
+fn is_digit_group(s: &str, len: RangeInclusive<usize>) -> bool {
+    len.contains(&s.len()) && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_grouped_number(s: &str) -> bool {
+    let mut groups = s.split(GROUP_SEPARATORS);
+    match groups.next() {
+        Some(first) if is_digit_group(first, 1..=3) => (),
+        _ => return false,
+    }
+
+    let mut count = 0;
+    for group in groups {
+        if !is_digit_group(group, 3..=3) {
+            return false;
+        }
+        count += 1;
+    }
+
+    count > 0
+}
+
+fn is_group_separator(token: &Token) -> bool {
+    token.is_separator()
+        && token.char_end - token.char_start == 1
+        && token.lemma.chars().all(|c| GROUP_SEPARATORS.contains(&c))
+}
+
+fn strip_group_separators(mut token: Token) -> Token {
+    if let Some(char_map) = &mut token.char_map {
+        // the separators are kept in the original text but removed from the lemma.
+        for ((_, normalized_len), c) in char_map.iter_mut().zip(token.lemma.chars()) {
+            if !c.is_ascii_digit() {
+                *normalized_len = 0;
+            }
+        }
+    }
+
+    let lemma = token.lemma.chars().filter(char::is_ascii_digit).collect();
+    token.lemma = Cow::Owned(lemma);
+    token
+}
+
+fn merge_group<'t>(mut token: Token<'t>, separator: &Token, group: Token<'t>) -> Token<'t> {
+    let mut lemma = mem::take(&mut token.lemma).into_owned();
+    lemma.push_str(&group.lemma);
+    token.lemma = Cow::Owned(lemma);
+    token.char_end = group.char_end;
+    token.byte_end = group.byte_end;
+
+    if let Some(char_map) = &mut token.char_map {
+        char_map.push(((separator.byte_end - separator.byte_start) as u8, 0));
+        match group.char_map {
+            Some(group_char_map) => char_map.extend(group_char_map),
+            None => char_map.extend(group.lemma.bytes().map(|_| (1, 1))),
+        }
+    }
+
+    token
+}
+
+#[cfg(test)]
+mod tests {
+    use charabia::TokenizerBuilder;
+
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        let tokenizer = TokenizerBuilder::default().into_tokenizer();
+        normalize_numbers(tokenizer.tokenize(text), true)
+            .filter(|token| token.kind == TokenKind::Word)
+            .map(|token| token.lemma().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn merge_thousands_groups() {
+        assert_eq!(words("1000 bikes"), ["1000", "bikes"]);
+        assert_eq!(words("1,000 bikes"), ["1000", "bikes"]);
+        assert_eq!(words("1 000 bikes"), ["1000", "bikes"]);
+        assert_eq!(words("1 000 000 bikes"), ["1000000", "bikes"]);
+        assert_eq!(words("12,345,678"), ["12345678"]);
+    }
+
+    #[test]
+    fn keep_unrelated_numbers_apart() {
+        assert_eq!(words("the 12 100m runs"), ["the", "12", "100m", "runs"]);
+        assert_eq!(words("1000 000"), ["1000", "000"]);
+        assert_eq!(words("1 10 bikes"), ["1", "10", "bikes"]);
+    }
+
+    #[test]
+    fn disabled_normalization() {
+        let tokenizer = TokenizerBuilder::default().into_tokenizer();
+        let words: Vec<_> = normalize_numbers(tokenizer.tokenize("1 000"), false)
+            .filter(|token| token.kind == TokenKind::Word)
+            .map(|token| token.lemma().to_string())
+            .collect();
+        assert_eq!(words, ["1", "000"]);
+    }
+}
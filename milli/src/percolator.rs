@@ -0,0 +1,151 @@
+use std::collections::{BTreeMap, HashMap};
+
+use heed::RoTxn;
+use log::warn;
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use crate::{DocumentId, FieldId, Filter, Index, InternalError, Result};
+
+/// Matches the given documents against the percolate queries of the index.
+///
+/// Returns the external ids of the documents matched by each query,
+/// the queries matching none of the documents are omitted.
+pub fn percolate(
+    index: &Index,
+    rtxn: &RoTxn,
+    documents_ids: &RoaringBitmap,
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let queries = index.percolate_queries(rtxn)?;
+    // the documents may have been deleted since they were added.
+    let documents_ids = documents_ids & index.documents_ids(rtxn)?;
+    if queries.is_empty() || documents_ids.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+
+    let mut matches = BTreeMap::new();
+    for (name, filter) in &queries {
+        // an empty filter matches all the documents.
+        let matched = match Filter::from_str(filter) {
+            Ok(Some(filter)) => match filter.evaluate(rtxn, index) {
+                Ok(docids) => docids & &documents_ids,
+                Err(error) => {
+                    // the filtered attributes may no longer be filterable.
+                    warn!("Skipping the percolate query `{name}`: {error}");
+                    continue;
+                }
+            },
+            Ok(None) => documents_ids.clone(),
+            Err(error) => {
+                warn!("Skipping the percolate query `{name}`: {error}");
+                continue;
+            }
+        };
+
+        if !matched.is_empty() {
+            matches.insert(name.clone(), matched);
+        }
+    }
+
+    let mut external_ids = ExternalIds::new(index, rtxn)?;
+    matches
+        .into_iter()
+        .map(|(name, docids)| {
+            let ids: Result<Vec<_>> =
+                docids.into_iter().map(|docid| external_ids.get(docid)).collect();
+            Ok((name, ids?))
+        })
+        .collect()
+}
+
+/// Retrieves the external ids of the documents from their primary key field.
+struct ExternalIds<'t> {
+    index: &'t Index,
+    rtxn: &'t RoTxn<'t>,
+    primary_key_id: Option<FieldId>,
+    /// Only built when the primary key is nested and can't be read from the documents.
+    reversed: Option<HashMap<DocumentId, String>>,
+}
+
+impl<'t> ExternalIds<'t> {
+    fn new(index: &'t Index, rtxn: &'t RoTxn) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let primary_key_id =
+            index.primary_key(rtxn)?.and_then(|primary_key| fields_ids_map.id(primary_key));
+        Ok(ExternalIds { index, rtxn, primary_key_id, reversed: None })
+    }
+
+    fn get(&mut self, docid: DocumentId) -> Result<String> {
+        if let Some(primary_key_id) = self.primary_key_id {
+            let (_, obkv) = self.index.iter_documents(self.rtxn, Some(docid))?.next().unwrap()?;
+            if let Some(value) = obkv.get(primary_key_id) {
+                match serde_json::from_slice(value).map_err(InternalError::SerdeJson)? {
+                    Value::String(id) => return Ok(id),
+                    Value::Number(id) => return Ok(id.to_string()),
+                    _ => (),
+                }
+            }
+        }
+
+        if self.reversed.is_none() {
+            let external_documents_ids = self.index.external_documents_ids(self.rtxn)?;
+            let reversed = external_documents_ids
+                .to_hash_map()
+                .into_iter()
+                .map(|(external, internal)| (internal, external))
+                .collect();
+            self.reversed = Some(reversed);
+        }
+
+        Ok(self.reversed.as_ref().and_then(|ids| ids.get(&docid)).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::{btreemap, hashset};
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn percolate_new_documents() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|s| {
+                s.set_primary_key("id".to_owned());
+                s.set_filterable_fields(hashset! { "price".to_owned(), "brand".to_owned() });
+                s.set_percolate_queries(btreemap! {
+                    "cheap-acme".to_owned() => "price < 100 AND brand = acme".to_owned(),
+                    "expensive".to_owned() => "price >= 1000".to_owned(),
+                    "everything".to_owned() => "".to_owned(),
+                });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "price": 50, "brand": "acme" },
+                { "id": "one", "price": 500, "brand": "acme" },
+                { "id": 2, "price": 80, "brand": "globex" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let documents_ids = index.documents_ids(&rtxn).unwrap();
+        let matches = percolate(&index, &rtxn, &documents_ids).unwrap();
+        insta::assert_debug_snapshot!(matches, @r###"
+        {
+            "cheap-acme": [
+                "0",
+            ],
+            "everything": [
+                "0",
+                "one",
+                "2",
+            ],
+        }
+        "###);
+    }
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// When the prefix databases, used to match the last word of a query as a prefix, are built.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrefixSearch {
+    /// The prefix databases are built while indexing the documents.
+    #[default]
+    IndexingTime,
+    /// The prefix databases are not built and the last word of the query
+    /// must match a word of the documents exactly.
+    Disabled,
+}
@@ -1,9 +1,24 @@
 use std::cmp;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{relative_from_absolute_position, Position};
 
 pub const MAX_DISTANCE: u32 = 8;
 
+/// The granularity at which the proximity between words is stored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProximityPrecision {
+    /// The exact distance between words is stored in the word pair proximity databases.
+    #[default]
+    ByWord,
+    /// Only whether two words appear in the same attribute is taken into account.
+    /// The word pair proximity databases are left empty and the relation is
+    /// recomputed at search time from the word fid databases.
+    ByAttribute,
+}
+
 pub fn index_proximity(lhs: u32, rhs: u32) -> u32 {
     if lhs <= rhs {
         cmp::min(rhs - lhs, MAX_DISTANCE)
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A reranker reordering the best hits of the searches before they are paginated.
+///
+/// The hits are sent to a REST endpoint which answers with a score for each of them,
+/// the hits are then sorted by decreasing score.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RerankerConfig {
+    /// The URL of the endpoint the hits are sent to.
+    pub url: String,
+    /// The attributes of the documents sent to the reranker, all the displayed ones when empty.
+    pub fields: Vec<String>,
+    /// The number of best hits that are reranked.
+    pub top_k: usize,
+    /// The time budget of the reranker, the hits keep their original order when it is exceeded.
+    pub timeout_ms: u64,
+}
@@ -1,8 +1,11 @@
-use serde::Serialize;
+use std::cmp::Ordering;
 
+use serde::{Deserialize, Serialize};
+
+use crate::criterion::format_half_life;
 use crate::distance_between_two_points;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ScoreDetails {
     Words(Words),
     Typo(Typo),
@@ -13,6 +16,10 @@ pub enum ScoreDetails {
     ExactWords(ExactWords),
     Sort(Sort),
     GeoSort(GeoSort),
+    Vector(Vector),
+    Decay(Decay),
+    /// The search was stopped before this ranking rule could sort the documents.
+    Skipped,
 }
 
 impl ScoreDetails {
@@ -31,6 +38,9 @@ impl ScoreDetails {
             ScoreDetails::ExactWords(details) => Some(details.rank()),
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
+            ScoreDetails::Vector(details) => Some(details.rank()),
+            ScoreDetails::Decay(details) => Some(details.rank()),
+            ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
 
@@ -38,6 +48,39 @@ impl ScoreDetails {
         Rank::global_score(details.filter_map(Self::rank))
     }
 
+    /// Compares the scores that a same ranking rule gave to two documents,
+    /// [`Ordering::Greater`] meaning that `self` is ranked before `other`.
+    ///
+    /// The skipped ranking rules and the scores of different ranking rules are equal.
+    pub fn rank_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (ScoreDetails::Sort(left), ScoreDetails::Sort(right)) => left.rank_cmp(right),
+            (ScoreDetails::GeoSort(left), ScoreDetails::GeoSort(right)) => left.rank_cmp(right),
+            (ScoreDetails::Vector(left), ScoreDetails::Vector(right)) => {
+                let left = left.similarity.unwrap_or(f32::NEG_INFINITY);
+                let right = right.similarity.unwrap_or(f32::NEG_INFINITY);
+                left.total_cmp(&right)
+            }
+            (ScoreDetails::Skipped, _) | (_, ScoreDetails::Skipped) => Ordering::Equal,
+            (left, right) if std::mem::discriminant(left) == std::mem::discriminant(right) => {
+                match (left.rank(), right.rank()) {
+                    (Some(left), Some(right)) => left.rank.cmp(&right.rank),
+                    _ => Ordering::Equal,
+                }
+            }
+            _ => Ordering::Equal,
+        }
+    }
+
+    /// Compares the scores of all the ranking rules of two documents, in the order of the rules.
+    pub fn rank_cmp_all(left: &[Self], right: &[Self]) -> Ordering {
+        left.iter()
+            .zip(right)
+            .map(|(left, right)| left.rank_cmp(right))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
     /// Panics
     ///
     /// - If Position is not preceded by Fid
@@ -181,6 +224,37 @@ impl ScoreDetails {
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::Vector(details) => {
+                    let vector_details = serde_json::json!({
+                        "order": order,
+                        "similarity": details.similarity,
+                        "score": details.rank().local_score(),
+                    });
+                    details_map.insert("vectorSort".into(), vector_details);
+                    order += 1;
+                }
+                ScoreDetails::Decay(details) => {
+                    let decay = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        format!(
+                            "decay({}, {})",
+                            details.field_name,
+                            format_half_life(details.half_life)
+                        )
+                    };
+                    let decay_details = serde_json::json!({
+                        "order": order,
+                        "halfLives": details.half_lives,
+                        "score": details.rank().local_score(),
+                    });
+                    details_map.insert(decay, decay_details);
+                    order += 1;
+                }
+                ScoreDetails::Skipped => {
+                    details_map.insert("skipped".into(), serde_json::json!({ "order": order }));
+                    order += 1;
+                }
             }
         }
         details_map
@@ -205,7 +279,7 @@ pub enum ScoringStrategy {
     Detailed,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Words {
     pub matching_words: u32,
     pub max_matching_words: u32,
@@ -225,7 +299,7 @@ impl Words {
 ///
 /// In exactness, the number of matching words can actually be 0 with a non-zero score,
 /// if no words from the query appear exactly in the document.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct ExactWords {
     pub matching_words: u32,
     pub max_matching_words: u32,
@@ -247,7 +321,7 @@ impl ExactWords {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Typo {
     pub typo_count: u32,
     pub max_typo_count: u32,
@@ -276,7 +350,7 @@ impl Typo {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Rank {
     /// The ordinal rank, such that `max_rank` is the first rank, and 0 is the last rank.
     ///
@@ -308,7 +382,7 @@ impl Rank {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ExactAttribute {
     ExactMatch,
@@ -327,7 +401,7 @@ impl ExactAttribute {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Sort {
     pub field_name: String,
     pub ascending: bool,
@@ -335,7 +409,32 @@ pub struct Sort {
     pub value: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+impl Sort {
+    /// The numbers are ranked before the strings and the documents without a value are last,
+    /// the numbers and the strings are ranked in the order of the sort.
+    fn rank_cmp(&self, other: &Self) -> Ordering {
+        use serde_json::Value;
+
+        let ordering = match (&self.value, &other.value) {
+            (Value::Number(left), Value::Number(right)) => {
+                let left = left.as_f64().unwrap_or_default();
+                let right = right.as_f64().unwrap_or_default();
+                right.total_cmp(&left)
+            }
+            (Value::String(left), Value::String(right)) => right.cmp(left),
+            (Value::Number(_), _) | (Value::String(_), Value::Null) => return Ordering::Greater,
+            (_, Value::Number(_)) | (Value::Null, Value::String(_)) => return Ordering::Less,
+            _ => return Ordering::Equal,
+        };
+        if self.ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct GeoSort {
     pub target_point: [f64; 2],
     pub ascending: bool,
@@ -346,4 +445,62 @@ impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
     }
+
+    /// The documents without a geo point are ranked last.
+    fn rank_cmp(&self, other: &Self) -> Ordering {
+        match (self.distance(), other.distance()) {
+            (Some(left), Some(right)) if self.ascending => right.total_cmp(&left),
+            (Some(left), Some(right)) => left.total_cmp(&right),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Vector {
+    /// The dot product similarity between the query vector and the closest vector of the document.
+    pub similarity: Option<f32>,
+}
+
+impl Vector {
+    /// The number of distinct ranks the similarity is quantized into.
+    const MAX_RANK: u32 = 10_000;
+
+    /// Quantizes the similarity in a rank so that it can be combined with the other ranks.
+    ///
+    /// Documents without a vector or with a negative similarity get the last rank (0).
+    pub fn rank(&self) -> Rank {
+        let similarity = self.similarity.unwrap_or_default().clamp(0.0, 1.0);
+        let rank = (similarity * Self::MAX_RANK as f32).round() as u32;
+        Rank { rank, max_rank: Self::MAX_RANK }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decay {
+    pub field_name: String,
+    /// The half life of the decay, in seconds.
+    pub half_life: u64,
+    pub redacted: bool,
+    /// The number of half lives elapsed since the timestamp of the document.
+    ///
+    /// `None` if the document has no timestamp or if it is older than [`Decay::MAX_HALF_LIVES`].
+    pub half_lives: Option<u32>,
+}
+
+impl Decay {
+    /// The number of half lives after which the documents all share the last rank.
+    pub const MAX_HALF_LIVES: u32 = 16;
+
+    /// Each elapsed half life lowers the rank by one, the documents without a recent
+    /// enough timestamp get the last non-zero rank.
+    pub fn rank(&self) -> Rank {
+        let max_rank = Self::MAX_HALF_LIVES + 1;
+        let rank = self
+            .half_lives
+            .map_or(1, |half_lives| max_rank - half_lives.min(Self::MAX_HALF_LIVES - 1));
+        Rank { rank, max_rank }
+    }
 }
@@ -5,6 +5,8 @@ use std::{fmt, mem};
 use heed::types::ByteSlice;
 use heed::BytesDecode;
 use indexmap::IndexMap;
+use rayon::prelude::*;
+use rayon::ThreadPool;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
@@ -17,7 +19,7 @@ use crate::heed_codec::{ByteSliceRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
-use crate::{FieldId, Index, Result};
+use crate::{FieldId, Index, Result, TimeBudget};
 
 /// The default number of values by facets that will
 /// be fetched from the key-value store.
@@ -42,6 +44,8 @@ pub struct FacetDistribution<'a> {
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
+    thread_pool: Option<&'a ThreadPool>,
+    time_budget: TimeBudget,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -53,6 +57,8 @@ impl<'a> FacetDistribution<'a> {
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
+            thread_pool: None,
+            time_budget: TimeBudget::max(),
             rtxn,
             index,
         }
@@ -81,6 +87,19 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Computes the distributions of the facets in parallel on the given threads.
+    pub fn thread_pool(&mut self, thread_pool: &'a ThreadPool) -> &mut Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Once the time budget is exceeded the facet values are not counted anymore,
+    /// the distribution only contains the values counted so far.
+    pub fn time_budget(&mut self, time_budget: TimeBudget) -> &mut Self {
+        self.time_budget = time_budget;
+        self
+    }
+
     pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Self {
         self.candidates = Some(candidates);
         self
@@ -102,6 +121,9 @@ impl<'a> FacetDistribution<'a> {
                 let distribution_prelength = distribution.len();
                 let db = self.index.field_id_docid_facet_f64s;
                 for docid in candidates {
+                    if self.time_budget.exceeded() {
+                        break;
+                    }
                     key_buffer.truncate(mem::size_of::<FieldId>());
                     key_buffer.extend_from_slice(&docid.to_be_bytes());
                     let iter = db
@@ -126,6 +148,9 @@ impl<'a> FacetDistribution<'a> {
 
                 let db = self.index.field_id_docid_facet_strings;
                 'outer: for docid in candidates {
+                    if self.time_budget.exceeded() {
+                        break;
+                    }
                     key_buffer.truncate(mem::size_of::<FieldId>());
                     key_buffer.extend_from_slice(&docid.to_be_bytes());
                     let iter = db
@@ -180,7 +205,7 @@ impl<'a> FacetDistribution<'a> {
             |facet_key, nbr_docids, _| {
                 let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
                 distribution.insert(facet_key.to_string(), nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                if distribution.len() == self.max_values_per_facet || self.time_budget.exceeded() {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -220,7 +245,7 @@ impl<'a> FacetDistribution<'a> {
                     .to_owned();
 
                 distribution.insert(original_string, nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                if distribution.len() == self.max_values_per_facet || self.time_budget.exceeded() {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -356,19 +381,36 @@ impl<'a> FacetDistribution<'a> {
             None => filterable_fields,
         };
 
-        let mut distribution = BTreeMap::new();
+        let mut faceted = Vec::new();
         for (fid, name) in fields_ids_map.iter() {
             if crate::is_faceted(name, &fields) {
+                // a nested field inherits the order of the most specific facet it has been
+                // selected by, `doggo.name` is ordered by `doggo.name` rather than by `doggo`.
                 let order_by = self
                     .facets
                     .as_ref()
-                    .and_then(|facets| facets.get(name).copied())
+                    .and_then(|facets| {
+                        facets
+                            .iter()
+                            .filter(|(facet, _)| crate::is_faceted_by(name, facet))
+                            .max_by_key(|(facet, _)| (facet.len(), facet.as_str()))
+                            .map(|(_, order_by)| *order_by)
+                    })
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
-                distribution.insert(name.to_string(), values);
+                faceted.push((fid, name, order_by));
             }
         }
 
+        // The distribution of each field is independent from the others.
+        let facet_values = |&(fid, name, order_by): &(FieldId, &str, OrderBy)| {
+            self.facet_values(fid, order_by).map(|values| (name.to_string(), values))
+        };
+        let distribution = match self.thread_pool {
+            Some(thread_pool) if faceted.len() > 1 => thread_pool
+                .install(|| faceted.par_iter().map(facet_values).collect::<heed::Result<_>>())?,
+            _ => faceted.iter().map(facet_values).collect::<heed::Result<_>>()?,
+        };
+
         Ok(distribution)
     }
 }
@@ -380,6 +422,8 @@ impl fmt::Debug for FacetDistribution<'_> {
             candidates,
             max_values_per_facet,
             default_order_by,
+            thread_pool: _,
+            time_budget,
             rtxn: _,
             index: _,
         } = self;
@@ -389,6 +433,7 @@ impl fmt::Debug for FacetDistribution<'_> {
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
+            .field("time_budget", time_budget)
             .finish()
     }
 }
@@ -396,13 +441,14 @@ impl fmt::Debug for FacetDistribution<'_> {
 #[cfg(test)]
 mod tests {
     use std::iter;
+    use std::time::{Duration, Instant};
 
     use big_s::S;
     use maplit::hashset;
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, OrderBy};
+    use crate::{milli_snap, FacetDistribution, OrderBy, TimeBudget};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -479,6 +525,75 @@ mod tests {
         milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 2}}"###);
     }
 
+    #[test]
+    fn nested_facets_ordered_by_the_most_specific_facet() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("doggo") }))
+            .unwrap();
+
+        let documents = documents!([
+            { "doggo": { "name": "bobby", "breed": "labrador" } },
+            { "doggo": { "name": "bobby", "breed": "poodle" } },
+            { "doggo": { "name": "albert", "breed": "poodle" } }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets([("doggo", OrderBy::Count), ("doggo.name", OrderBy::Lexicographic)])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{:?}", map["doggo.name"]), @r###"{"albert": 1, "bobby": 2}"###);
+        milli_snap!(format!("{:?}", map["doggo.breed"]), @r###"{"poodle": 2, "labrador": 1}"###);
+    }
+
+    #[test]
+    fn stop_counting_once_the_time_budget_is_exceeded() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("colour") }))
+            .unwrap();
+
+        let documents = documents!([
+            { "colour": "Blue" },
+            { "colour": "  blue" },
+            { "colour": "RED" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let time_budget = TimeBudget::new(Duration::from_millis(10))
+            .started_at(Instant::now() - Duration::from_secs(1));
+
+        // the documents are not iterated anymore
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates([0, 1, 2].iter().copied().collect())
+            .time_budget(time_budget)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {}}"###);
+
+        // the facet levels are not iterated after the first value
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::Count)))
+            .time_budget(time_budget)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 2}}"###);
+    }
+
     #[test]
     fn many_candidates_few_facet_values() {
         let mut index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -836,4 +951,49 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn parallel_facet_distribution() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("colour"), S("size"), S("shape") })
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "colour": "Blue", "size": 1, "shape": "round" },
+            { "colour": "blue", "size": 2, "shape": "square" },
+            { "colour": "RED", "size": 2 }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets([
+                ("colour", OrderBy::default()),
+                ("size", OrderBy::Count),
+                ("shape", OrderBy::default()),
+            ])
+            .thread_pool(&thread_pool)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 2, "RED": 1}, "shape": {"round": 1, "square": 1}, "size": {"2": 2, "1": 1}}"###);
+
+        let sequential = FacetDistribution::new(&txn, &index)
+            .facets([
+                ("colour", OrderBy::default()),
+                ("size", OrderBy::Count),
+                ("shape", OrderBy::default()),
+            ])
+            .execute()
+            .unwrap();
+        assert_eq!(map, sequential);
+    }
 }
@@ -5,14 +5,19 @@ use std::ops::Bound::{self, Excluded, Included};
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Span, Token};
 use roaring::RoaringBitmap;
+use rstar::AABB;
 use serde_json::Value;
 
 use super::facet_range_search;
-use crate::error::{Error, UserError};
+use crate::error::{Error, InternalError, UserError};
+use crate::geo_shape::{bounding_box, GeoShape};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
-use crate::{distance_between_two_points, lat_lng_to_xyz, FieldId, Index, Result};
+use crate::update::extract_finite_float_from_value;
+use crate::{
+    distance_between_two_points, lat_lng_to_xyz, DocumentId, FieldId, Index, Result, BEU32,
+};
 
 /// The maximum number of filters the filter AST can process.
 const MAX_FILTER_DEPTH: usize = 2000;
@@ -55,6 +60,9 @@ impl Display for BadGeoError {
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_fields: HashSet<String> },
     ParseGeoError(BadGeoError),
+    GeoDocumentNotFound(&'a str),
+    GeoDocumentWithoutGeo(&'a str),
+    NotAGeoField(&'a str),
     TooDeep,
 }
 impl<'a> std::error::Error for FilterError<'a> {}
@@ -96,6 +104,17 @@ impl<'a> Display for FilterError<'a> {
                 MAX_FILTER_DEPTH
             ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
+            Self::GeoDocumentNotFound(document_id) => {
+                write!(f, "Document `{document_id}` used as the center of a `_geoRadius` not found.")
+            }
+            Self::GeoDocumentWithoutGeo(document_id) => write!(
+                f,
+                "Document `{document_id}` used as the center of a `_geoRadius` does not have a valid `_geo` field."
+            ),
+            Self::NotAGeoField(field) => write!(
+                f,
+                "Attribute `{field}` is not a geo field. The geo fields must be declared in the `geoFields` setting."
+            ),
         }
     }
 }
@@ -224,13 +243,44 @@ impl<'a> Filter<'a> {
     pub fn evaluate(&self, rtxn: &heed::RoTxn, index: &Index) -> Result<RoaringBitmap> {
         // to avoid doing this for each recursive call we're going to do it ONCE ahead of time
         let soft_deleted_documents = index.soft_deleted_documents_ids(rtxn)?;
-        let filterable_fields = index.filterable_fields(rtxn)?;
+        let mut filterable_fields = index.filterable_fields(rtxn)?;
+        filterable_fields.extend(index.timestamps_fields(rtxn)?.iter().map(|f| f.to_string()));
 
         // and finally we delete all the soft_deleted_documents, again, only once at the very end
         self.inner_evaluate(rtxn, index, &filterable_fields)
             .map(|result| result - soft_deleted_documents)
     }
 
+    /// Returns the center of the first `_geoRadius` on `_geo` of the filter that is not negated,
+    /// `None` if there is none or if it references a document without `_geo`.
+    pub fn geo_radius_center(&self, rtxn: &heed::RoTxn, index: &Index) -> Result<Option<[f64; 2]>> {
+        fn find_geo_radius<'a, 'b>(
+            condition: &'b FilterCondition<'a>,
+        ) -> Option<&'b FilterCondition<'a>> {
+            match condition {
+                FilterCondition::GeoLowerThan { field: None, .. }
+                | FilterCondition::GeoLowerThanDocument { .. } => Some(condition),
+                FilterCondition::Or(subfilters) | FilterCondition::And(subfilters) => {
+                    subfilters.iter().find_map(find_geo_radius)
+                }
+                _ => None,
+            }
+        }
+
+        match find_geo_radius(&self.condition) {
+            Some(FilterCondition::GeoLowerThan { point, .. }) => {
+                Ok(Some([point[0].parse_finite_float()?, point[1].parse_finite_float()?]))
+            }
+            Some(FilterCondition::GeoLowerThanDocument { document_id, .. }) => {
+                match index.external_documents_ids(rtxn)?.get(document_id.value()) {
+                    Some(docid) => Self::document_geo_point(rtxn, index, docid),
+                    None => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn evaluate_operator(
         rtxn: &heed::RoTxn,
         index: &Index,
@@ -330,6 +380,95 @@ impl<'a> Filter<'a> {
         Ok(())
     }
 
+    /// Returns the documents whose point is within the radius of the base point, the points
+    /// are read from the named geo field if there is one and from `_geo` otherwise.
+    fn geo_radius(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        geo_field: Option<&str>,
+        base_point: [f64; 2],
+        radius: f64,
+    ) -> Result<RoaringBitmap> {
+        let rtree = match geo_field {
+            Some(geo_field) => index.named_geo_rtrees(rtxn)?.remove(geo_field),
+            None => index.geo_rtree(rtxn)?,
+        };
+        let rtree = match rtree {
+            Some(rtree) => rtree,
+            None => return Ok(RoaringBitmap::new()),
+        };
+
+        let xyz_base_point = lat_lng_to_xyz(&base_point);
+
+        let result = rtree
+            .nearest_neighbor_iter(&xyz_base_point)
+            .take_while(|point| {
+                distance_between_two_points(&base_point, &point.data.1) <= radius + f64::EPSILON
+            })
+            .map(|point| point.data.0)
+            .collect();
+
+        Ok(result)
+    }
+
+    /// Returns the documents whose `_geojson` shape intersects the closed ring of the polygon.
+    fn geo_intersects(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        ring: &[[f64; 2]],
+    ) -> Result<RoaringBitmap> {
+        let rtree = match index.geojson_rtree(rtxn)? {
+            Some(rtree) => rtree,
+            None => return Ok(RoaringBitmap::new()),
+        };
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let Some(geojson_fid) = fields_ids_map.id("_geojson") else {
+            return Ok(RoaringBitmap::new());
+        };
+
+        let (bottom_left, top_right) = bounding_box(ring);
+
+        // the bounding boxes only preselect the documents, their shapes are then checked one by one
+        let mut result = RoaringBitmap::new();
+        for shape_box in
+            rtree.locate_in_envelope_intersecting(&AABB::from_corners(bottom_left, top_right))
+        {
+            let docid = shape_box.data;
+            let Some(document) = index.documents.get(rtxn, &BEU32::new(docid))? else { continue };
+            let Some(geojson) = document.get(geojson_fid) else { continue };
+            let geojson: Value =
+                serde_json::from_slice(geojson).map_err(InternalError::SerdeJson)?;
+            if GeoShape::from_geojson(&geojson)
+                .map_or(false, |shape| shape.intersects_polygon(ring))
+            {
+                result.insert(docid);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Reads the `_geo` field of a document, `None` if it has no valid `_geo`.
+    fn document_geo_point(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        docid: DocumentId,
+    ) -> Result<Option<[f64; 2]>> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let Some(geo_fid) = fields_ids_map.id("_geo") else { return Ok(None) };
+        let Some((_, document)) = index.documents(rtxn, Some(docid))?.pop() else {
+            return Ok(None);
+        };
+        let Some(value) = document.get(geo_fid) else { return Ok(None) };
+        let mut geo: Value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+        let lat = geo.get_mut("lat").map(Value::take).map(extract_finite_float_from_value);
+        let lng = geo.get_mut("lng").map(Value::take).map(extract_finite_float_from_value);
+        match (lat, lng) {
+            (Some(Ok(lat)), Some(Ok(lng))) => Ok(Some([lat, lng])),
+            _ => Ok(None),
+        }
+    }
+
     fn inner_evaluate(
         &self,
         rtxn: &heed::RoTxn,
@@ -418,8 +557,16 @@ impl<'a> Filter<'a> {
                     Ok(RoaringBitmap::new())
                 }
             }
-            FilterCondition::GeoLowerThan { point, radius } => {
-                if filterable_fields.contains("_geo") {
+            FilterCondition::GeoLowerThan { field, point, radius } => {
+                let geo_field = field.as_ref().map(|field| field.value());
+                if let (Some(field), Some(name)) = (field, geo_field) {
+                    if !index.geo_fields(rtxn)?.iter().any(|geo_field| geo_field == name) {
+                        return Err(field.as_external_error(FilterError::NotAGeoField(name)))?;
+                    }
+                }
+
+                let attribute = geo_field.unwrap_or("_geo");
+                if filterable_fields.contains(attribute) {
                     let base_point: [f64; 2] =
                         [point[0].parse_finite_float()?, point[1].parse_finite_float()?];
                     if !(-90.0..=90.0).contains(&base_point[0]) {
@@ -429,32 +576,75 @@ impl<'a> Filter<'a> {
                         return Err(point[1].as_external_error(BadGeoError::Lng(base_point[1])))?;
                     }
                     let radius = radius.parse_finite_float()?;
-                    let rtree = match index.geo_rtree(rtxn)? {
-                        Some(rtree) => rtree,
-                        None => return Ok(RoaringBitmap::new()),
-                    };
-
-                    let xyz_base_point = lat_lng_to_xyz(&base_point);
-
-                    let result = rtree
-                        .nearest_neighbor_iter(&xyz_base_point)
-                        .take_while(|point| {
-                            distance_between_two_points(&base_point, &point.data.1)
-                                <= radius + f64::EPSILON
-                        })
-                        .map(|point| point.data.0)
-                        .collect();
-
-                    Ok(result)
+                    Self::geo_radius(rtxn, index, geo_field, base_point, radius)
                 } else {
                     Err(point[0].as_external_error(FilterError::AttributeNotFilterable {
-                        attribute: "_geo",
+                        attribute,
                         filterable_fields: filterable_fields.clone(),
                     }))?
                 }
             }
-            FilterCondition::GeoBoundingBox { top_right_point, bottom_left_point } => {
+            FilterCondition::GeoLowerThanDocument { document_id, radius } => {
                 if filterable_fields.contains("_geo") {
+                    let external_documents_ids = index.external_documents_ids(rtxn)?;
+                    let docid = match external_documents_ids.get(document_id.value()) {
+                        Some(docid) => docid,
+                        None => {
+                            return Err(document_id.as_external_error(
+                                FilterError::GeoDocumentNotFound(document_id.value()),
+                            ))?
+                        }
+                    };
+                    let base_point = match Self::document_geo_point(rtxn, index, docid)? {
+                        Some(base_point) => base_point,
+                        None => {
+                            return Err(document_id.as_external_error(
+                                FilterError::GeoDocumentWithoutGeo(document_id.value()),
+                            ))?
+                        }
+                    };
+                    let radius = radius.parse_finite_float()?;
+                    Self::geo_radius(rtxn, index, None, base_point, radius)
+                } else {
+                    Err(document_id.as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: "_geo",
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+            FilterCondition::GeoIntersects { polygon } => {
+                if filterable_fields.contains("_geojson") {
+                    let mut ring = Vec::with_capacity(polygon.len() + 1);
+                    for [lat, lng] in polygon {
+                        let point = [lat.parse_finite_float()?, lng.parse_finite_float()?];
+                        if !(-90.0..=90.0).contains(&point[0]) {
+                            return Err(lat.as_external_error(BadGeoError::Lat(point[0])))?;
+                        }
+                        if !(-180.0..=180.0).contains(&point[1]) {
+                            return Err(lng.as_external_error(BadGeoError::Lng(point[1])))?;
+                        }
+                        ring.push(point);
+                    }
+                    // the polygon is closed by going back to its first point
+                    ring.push(ring[0]);
+                    Self::geo_intersects(rtxn, index, &ring)
+                } else {
+                    Err(polygon[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: "_geojson",
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+            FilterCondition::GeoBoundingBox { field, top_right_point, bottom_left_point } => {
+                let geo_field = field.as_ref().map(|field| field.value());
+                if let (Some(field), Some(name)) = (field, geo_field) {
+                    if !index.geo_fields(rtxn)?.iter().any(|geo_field| geo_field == name) {
+                        return Err(field.as_external_error(FilterError::NotAGeoField(name)))?;
+                    }
+                }
+
+                let attribute = geo_field.unwrap_or("_geo");
+                if filterable_fields.contains(attribute) {
                     let top_right: [f64; 2] = [
                         top_right_point[0].parse_finite_float()?,
                         top_right_point[1].parse_finite_float()?,
@@ -490,12 +680,13 @@ impl<'a> Filter<'a> {
                     // Instead of writing a custom `GeoBoundingBox` filter we're simply going to re-use the range
                     // filter to create the following filter;
                     // `_geo.lat {top_right[0]} TO {bottom_left[0]} AND _geo.lng {top_right[1]} TO {bottom_left[1]}`
+                    // or the same filter on the `lat` and `lng` of the named geo field.
                     // As we can see, we need to use a bunch of tokens that don't exist in the original filter,
                     // thus we're going to create tokens that point to a random span but contain our text.
 
                     let geo_lat_token = Token::new(
                         top_right_point[0].original_span(),
-                        Some("_geo.lat".to_string()),
+                        Some(format!("{attribute}.lat")),
                     );
 
                     let condition_lat = FilterCondition::Condition {
@@ -514,7 +705,7 @@ impl<'a> Filter<'a> {
 
                     let geo_lng_token = Token::new(
                         top_right_point[1].original_span(),
-                        Some("_geo.lng".to_string()),
+                        Some(format!("{attribute}.lng")),
                     );
                     let selected_lng = if top_right[1] < bottom_left[1] {
                         // In this case the bounding box is wrapping around the earth (going from 180 to -180).
@@ -577,7 +768,7 @@ impl<'a> Filter<'a> {
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
-                            attribute: "_geo",
+                            attribute,
                             filterable_fields: filterable_fields.clone(),
                         },
                     ))?
@@ -600,7 +791,7 @@ mod tests {
 
     use big_s::S;
     use either::Either;
-    use maplit::hashset;
+    use maplit::{btreeset, hashset};
     use roaring::RoaringBitmap;
 
     use crate::index::tests::TempIndex;
@@ -835,6 +1026,230 @@ mod tests {
         assert_eq!(documents_ids, vec![0]);
     }
 
+    #[test]
+    fn geo_radius_geohash_and_document_centers() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": "napiz", "_geo": { "lat": 45.4777599, "lng": 9.1967508 } },
+              { "id": "artico", "_geo": { "lat": 45.4632046, "lng": 9.1719421 } },
+              { "id": "nowhere" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // the center of the geohash cell is 72 meters away from the first document
+        let filter = Filter::from_str("_geoRadius(geohash:u0nd9qb, 100)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [0]);
+
+        // the documents are 2.5 kilometers away from each other
+        let filter = Filter::from_str("_geoRadius(document:napiz, 2000)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [0]);
+        let filter = Filter::from_str("_geoRadius(document:artico, 3000)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [0, 1]);
+
+        let filter = Filter::from_str("_geoRadius(document:unknown, 3000)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Document `unknown` used as the center of a `_geoRadius` not found."));
+
+        let filter = Filter::from_str("_geoRadius(document:nowhere, 3000)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Document `nowhere` used as the center of a `_geoRadius` does not have a valid `_geo` field."
+        ));
+
+        let filter = Filter::from_str(
+            "NOT _geoRadius(1, 2, 3) OR (_geoRadius(document:artico, 3000) AND _geoRadius(1, 2, 3))",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(filter.geo_radius_center(&rtxn, &index).unwrap(), Some([45.4632046, 9.1719421]));
+        let filter = Filter::from_str("_geoBoundingBox([89, 179], [-89, -179])").unwrap().unwrap();
+        assert_eq!(filter.geo_radius_center(&rtxn, &index).unwrap(), None);
+
+        let error = Filter::from_str("_geoRadius(geohash:u0nd9qa, 100)").unwrap_err();
+        assert!(error.to_string().starts_with(
+            "The geohash `u0nd9qa` is invalid. A geohash only contains the characters `0123456789bcdefghjkmnpqrstuvwxyz`."
+        ));
+    }
+
+    #[test]
+    fn geo_intersects() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geojson") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              {
+                "id": "square",
+                "_geojson": {
+                  "type": "Polygon",
+                  "coordinates": [[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]]]
+                }
+              },
+              {
+                "id": "road",
+                "_geojson": { "type": "LineString", "coordinates": [[20, 0], [20, 30]] }
+              },
+              { "id": "nowhere" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // the filter polygon is written latitude first
+        let filter =
+            Filter::from_str("_geoIntersects([[5, 5], [5, 25], [6, 25]])").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [0, 1]);
+        let filter = Filter::from_str("_geoIntersects([[1, 1], [2, 1], [2, 2]])").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [0]);
+        // the bounding boxes intersect but not the shapes
+        let filter =
+            Filter::from_str("_geoIntersects([[40, 12], [40, 21], [28, 12]])").unwrap().unwrap();
+        assert!(filter.evaluate(&rtxn, &index).unwrap().is_empty());
+
+        let filter =
+            Filter::from_str("_geoIntersects([[95, 5], [5, 25], [6, 25]])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Bad latitude `95`. Latitude must be contained between -90 and 90 degrees."
+        ));
+
+        drop(rtxn);
+        index
+            .add_documents(documents!([
+              { "id": "point", "_geojson": { "type": "Point", "coordinates": [0, 0] } },
+            ]))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn geo_radius_on_named_geo_fields() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_geo_fields(btreeset! { S("pickup"), S("dropoff") });
+                settings.set_filterable_fields(hashset! { S("pickup"), S("dropoff"), S("price") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              {
+                "id": "ride",
+                "pickup": { "lat": 45.4777599, "lng": 9.1967508 },
+                "dropoff": { "lat": 45.4632046, "lng": 9.1719421 },
+                "price": 12,
+              },
+              {
+                "id": "return",
+                "pickup": { "lat": 45.4632046, "lng": 9.1719421 },
+                "dropoff": { "lat": 45.4777599, "lng": 9.1967508 },
+                "price": 11,
+              },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter =
+            Filter::from_str("_geoRadius(pickup, 45.4777599, 9.1967508, 100)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [0]);
+        let filter =
+            Filter::from_str("_geoRadius(dropoff, 45.4777599, 9.1967508, 100)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [1]);
+        // the named geo fields are not part of `_geo`
+        let filter = Filter::from_str("_geoRadius(45.4777599, 9.1967508, 100)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("Attribute `_geo` is not filterable."));
+
+        let filter =
+            Filter::from_str("_geoRadius(price, 45.4777599, 9.1967508, 100)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `price` is not a geo field. The geo fields must be declared in the `geoFields` setting."
+        ));
+
+        drop(rtxn);
+        index.delete_document("ride");
+        let rtxn = index.read_txn().unwrap();
+        let filter =
+            Filter::from_str("_geoRadius(pickup, 45.4777599, 9.1967508, 100)").unwrap().unwrap();
+        assert!(filter.evaluate(&rtxn, &index).unwrap().is_empty());
+        let filter =
+            Filter::from_str("_geoRadius(pickup, 45.4632046, 9.1719421, 100)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn geo_bounding_box_on_named_geo_fields() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_geo_fields(btreeset! { S("pickup"), S("dropoff") });
+                settings.set_filterable_fields(hashset! { S("pickup"), S("dropoff"), S("price") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              {
+                "id": "ride",
+                "pickup": { "lat": 45.4777599, "lng": 9.1967508 },
+                "dropoff": { "lat": 45.4632046, "lng": 9.1719421 },
+                "price": 12,
+              },
+              {
+                "id": "return",
+                "pickup": { "lat": 45.4632046, "lng": 9.1719421 },
+                "dropoff": { "lat": 45.4777599, "lng": 9.1967508 },
+                "price": 11,
+              },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("_geoBoundingBox(pickup, [45.48, 9.2], [45.47, 9.19])")
+            .unwrap()
+            .unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [0]);
+        let filter = Filter::from_str("_geoBoundingBox(dropoff, [45.48, 9.2], [45.47, 9.19])")
+            .unwrap()
+            .unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>(), [1]);
+        // the named geo fields are not part of `_geo`
+        let filter =
+            Filter::from_str("_geoBoundingBox([45.48, 9.2], [45.47, 9.19])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("Attribute `_geo` is not filterable."));
+
+        let filter = Filter::from_str("_geoBoundingBox(price, [45.48, 9.2], [45.47, 9.19])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `price` is not a geo field. The geo fields must be declared in the `geoFields` setting."
+        ));
+    }
+
     #[test]
     fn geo_radius_error() {
         let index = TempIndex::new();
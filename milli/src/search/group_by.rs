@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::mem;
+
+use heed::types::ByteSlice;
+use roaring::RoaringBitmap;
+
+use super::{MatchingWords, Search, SearchResult};
+use crate::error::UserError;
+use crate::heed_codec::facet::{
+    FacetGroupKey, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
+};
+use crate::score_details::ScoreDetails;
+use crate::{DocumentId, FieldId, Result};
+
+/// The documents sharing the same value for the attribute used to group the results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// The original value of the attribute, numbers are written in their decimal form.
+    pub value: String,
+    /// The number of candidates having this value.
+    pub count: u64,
+    /// The best documents of the group, in ranking order.
+    pub documents_ids: Vec<DocumentId>,
+    pub document_scores: Vec<Vec<ScoreDetails>>,
+}
+
+#[derive(Default, Debug)]
+pub struct GroupsResult {
+    pub matching_words: MatchingWords,
+    pub candidates: RoaringBitmap,
+    /// The groups, ordered by the rank of their best document.
+    pub groups: Vec<Group>,
+    /// Whether the time budget was exceeded and the documents were not entirely sorted.
+    pub degraded: bool,
+}
+
+/// Identifies a group by the normalized facet value of its documents.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum GroupKey {
+    String(String),
+    Number(u64),
+}
+
+impl<'a> Search<'a> {
+    /// Groups the ranked documents by the value of the given filterable attribute.
+    ///
+    /// The offset and the limit of the search apply to the groups, which are ordered by the rank
+    /// of their best document, and at most `hits_per_group` documents are kept for each group.
+    /// The documents without any value for the attribute are not part of any group, and the ones
+    /// with several values are grouped by the first of them.
+    ///
+    /// The documents are ranked only once and at most `max_total_hits` of them are considered,
+    /// the groups whose documents are ranked beyond this bound are therefore incomplete.
+    pub fn execute_groups(
+        &self,
+        field: &str,
+        hits_per_group: usize,
+        max_total_hits: usize,
+    ) -> Result<GroupsResult> {
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        if !crate::is_faceted(field, &filterable_fields) {
+            let (valid_fields, hidden_fields) =
+                self.index.remove_hidden_fields(self.rtxn, filterable_fields)?;
+            return Err(UserError::InvalidGroupByAttribute {
+                field: field.to_string(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+
+        let groups_len = self.offset.saturating_add(self.limit);
+        let Some(field_id) = self.index.fields_ids_map(self.rtxn)?.id(field) else {
+            let SearchResult { matching_words, candidates, degraded, .. } =
+                self.execute_range(0, 0)?;
+            return Ok(GroupsResult { matching_words, candidates, groups: Vec::new(), degraded });
+        };
+
+        let SearchResult { matching_words, candidates, documents_ids, document_scores, degraded } =
+            self.execute_range(0, max_total_hits)?;
+
+        // The ranked documents are dispatched in their groups in a single pass
+        // that stops as soon as the requested groups are complete.
+        let mut groups: Vec<Group> = Vec::new();
+        let mut positions = HashMap::new();
+        let mut incomplete = 0;
+        for (docid, scores) in documents_ids.into_iter().zip(document_scores) {
+            let Some((key, value)) = self.group_of(field_id, docid)? else { continue };
+            let position = match positions.get(&key) {
+                Some(&position) => position,
+                None if groups.len() >= groups_len => continue,
+                None => {
+                    let count = self.group_docids(field_id, &key)?.intersection_len(&candidates);
+                    let group = Group {
+                        value,
+                        count,
+                        documents_ids: Vec::new(),
+                        document_scores: Vec::new(),
+                    };
+                    positions.insert(key, groups.len());
+                    groups.push(group);
+                    if hits_per_group > 0 {
+                        incomplete += 1;
+                    }
+                    groups.len() - 1
+                }
+            };
+
+            // A group is complete when it is full or contains all the candidates of its value.
+            let group = &mut groups[position];
+            if group.documents_ids.len() < hits_per_group {
+                group.documents_ids.push(docid);
+                group.document_scores.push(scores);
+                let len = group.documents_ids.len();
+                if len >= hits_per_group || len as u64 >= group.count {
+                    incomplete -= 1;
+                }
+            }
+
+            if groups.len() >= groups_len && incomplete == 0 {
+                break;
+            }
+        }
+
+        let groups = groups.into_iter().skip(self.offset).take(self.limit).collect();
+
+        Ok(GroupsResult { matching_words, candidates, groups, degraded })
+    }
+
+    /// Returns the key and the original value of the group of the document, if any.
+    fn group_of(&self, field_id: FieldId, docid: DocumentId) -> Result<Option<(GroupKey, String)>> {
+        let mut prefix = [0; mem::size_of::<FieldId>() + mem::size_of::<DocumentId>()];
+        prefix[..mem::size_of::<FieldId>()].copy_from_slice(&field_id.to_be_bytes());
+        prefix[mem::size_of::<FieldId>()..].copy_from_slice(&docid.to_be_bytes());
+
+        let mut strings = self
+            .index
+            .field_id_docid_facet_strings
+            .remap_key_type::<ByteSlice>()
+            .prefix_iter(self.rtxn, &prefix)?
+            .remap_key_type::<FieldDocIdFacetStringCodec>();
+        if let Some(result) = strings.next() {
+            let ((_, _, normalized), original) = result?;
+            return Ok(Some((GroupKey::String(normalized.to_owned()), original.to_owned())));
+        }
+
+        let mut numbers = self
+            .index
+            .field_id_docid_facet_f64s
+            .remap_key_type::<ByteSlice>()
+            .prefix_iter(self.rtxn, &prefix)?
+            .remap_key_type::<FieldDocIdFacetF64Codec>();
+        if let Some(result) = numbers.next() {
+            let ((_, _, number), ()) = result?;
+            return Ok(Some((GroupKey::Number(number.to_bits()), number.to_string())));
+        }
+
+        Ok(None)
+    }
+
+    /// Returns all the documents of the index belonging to the given group.
+    fn group_docids(&self, field_id: FieldId, key: &GroupKey) -> Result<RoaringBitmap> {
+        let docids = match key {
+            GroupKey::String(value) => {
+                let key = FacetGroupKey { field_id, level: 0, left_bound: value.as_str() };
+                self.index.facet_id_string_docids.get(self.rtxn, &key)?
+            }
+            GroupKey::Number(bits) => {
+                let key = FacetGroupKey { field_id, level: 0, left_bound: f64::from_bits(*bits) };
+                self.index.facet_id_f64_docids.get(self.rtxn, &key)?
+            }
+        };
+        Ok(docids.map(|value| value.bitmap).unwrap_or_default())
+    }
+}
@@ -1,20 +1,30 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
 
 use charabia::normalizer::NormalizerOption;
-use charabia::Normalize;
+use charabia::{Language, Normalize};
 use fst::automaton::{Automaton, Str};
 use fst::{IntoStreamer, Streamer};
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use log::error;
 use once_cell::sync::Lazy;
+use rayon::ThreadPool;
 use roaring::bitmap::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 
 pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::group_by::{Group, GroupsResult};
 pub use self::new::matches::{FormatOptions, MatchBounds, Matcher, MatcherBuilder, MatchingWords};
 use self::new::PartialSearchResult;
+pub use self::similar::Similar;
+pub use self::spelling::SpellingSuggestions;
+pub(crate) use self::suggest::normalize_suggestion;
+pub use self::suggest::{Suggest, Suggestion};
 use crate::error::UserError;
 use crate::heed_codec::facet::{FacetGroupKey, FacetGroupValue};
+use crate::localized_attributes_rules::find_rule;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::{
     execute_search, AscDesc, DefaultSearchLogger, DocumentId, FieldId, Index, Result,
@@ -29,24 +39,88 @@ static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 /// The maximum number of facets returned by the facet search route.
 const MAX_NUMBER_OF_FACETS: usize = 100;
 
+/// The default time budget of a search, in milliseconds.
+pub const DEFAULT_SEARCH_CUTOFF_MS: u64 = 1500;
+
+/// The time a search is allowed to take before returning degraded results.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeBudget {
+    started_at: Instant,
+    budget: Duration,
+}
+
+impl Default for TimeBudget {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(DEFAULT_SEARCH_CUTOFF_MS))
+    }
+}
+
+impl TimeBudget {
+    /// Starts a new budget from now.
+    pub fn new(budget: Duration) -> Self {
+        Self { started_at: Instant::now(), budget }
+    }
+
+    /// Starts the budget at the given instant, usually the reception of the request,
+    /// instead of now.
+    pub fn started_at(self, started_at: Instant) -> Self {
+        Self { started_at, ..self }
+    }
+
+    /// A budget that is never exceeded.
+    pub fn max() -> Self {
+        Self::new(Duration::MAX)
+    }
+
+    pub fn exceeded(&self) -> bool {
+        self.started_at.elapsed() > self.budget
+    }
+}
+
+/// The position of the last document returned by a previous page of a search,
+/// the next page starts with the document ranked right after it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchAfter {
+    /// The scores of the document for each ranking rule, the documents
+    /// ranked before these scores are not returned again.
+    pub scores: Vec<ScoreDetails>,
+    /// The documents sharing all their scores are returned in the order of their ids.
+    pub docid: DocumentId,
+}
+
 pub mod facet;
 mod fst_utils;
+mod group_by;
 pub mod new;
+mod similar;
+mod spelling;
+mod suggest;
 
 pub struct Search<'a> {
     query: Option<String>,
     vector: Option<Vec<f32>>,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
+    candidates: Option<RoaringBitmap>,
+    search_after: Option<SearchAfter>,
+    distinct: Option<String>,
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
     searchable_attributes: Option<&'a [String]>,
+    attribute_boosts: Option<BTreeMap<String, u16>>,
+    prefix_search: Option<bool>,
+    locales: Option<Vec<Language>>,
+    keep_stop_words: bool,
     geo_strategy: new::GeoSortStrategy,
     terms_matching_strategy: TermsMatchingStrategy,
+    minimum_should_match: Option<MinimumShouldMatch>,
     scoring_strategy: ScoringStrategy,
+    ranking_score_threshold: Option<f64>,
     words_limit: usize,
     exhaustive_number_hits: bool,
+    time_budget: TimeBudget,
+    thread_pool: Option<&'a ThreadPool>,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -57,15 +131,26 @@ impl<'a> Search<'a> {
             query: None,
             vector: None,
             filter: None,
+            candidates: None,
+            search_after: None,
+            distinct: None,
             offset: 0,
             limit: 20,
             sort_criteria: None,
             searchable_attributes: None,
+            attribute_boosts: None,
+            prefix_search: None,
+            locales: None,
+            keep_stop_words: false,
             geo_strategy: new::GeoSortStrategy::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
+            minimum_should_match: None,
             scoring_strategy: Default::default(),
+            ranking_score_threshold: None,
             exhaustive_number_hits: false,
+            time_budget: TimeBudget::max(),
             words_limit: 10,
+            thread_pool: None,
             rtxn,
             index,
         }
@@ -101,16 +186,66 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Boosts the given attributes for the attribute ranking rule, the attributes with the
+    /// highest boosts are considered the most important ones for this search only.
+    pub fn attribute_boosts(&mut self, boosts: BTreeMap<String, u16>) -> &mut Search<'a> {
+        self.attribute_boosts = Some(boosts);
+        self
+    }
+
+    /// Overrides the prefix search setting of the index for this search only,
+    /// when disabled the last word of the query must match exactly.
+    pub fn prefix_search(&mut self, value: bool) -> &mut Search<'a> {
+        self.prefix_search = Some(value);
+        self
+    }
+
+    /// Forces the languages used to tokenize and normalize the query
+    /// instead of relying on the language detection.
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
+    /// Matches the stop words of the query like the other words instead of ignoring them,
+    /// for the queries made of stop words such as `to be or not to be`.
+    ///
+    /// The stop words of the documents must be indexed, see [`Index::index_stop_words`].
+    pub fn keep_stop_words(&mut self, value: bool) -> &mut Search<'a> {
+        self.keep_stop_words = value;
+        self
+    }
+
+    /// Parallelizes the independent computations of the search on the given threads.
+    pub fn thread_pool(&mut self, thread_pool: &'a ThreadPool) -> &mut Search<'a> {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
     pub fn terms_matching_strategy(&mut self, value: TermsMatchingStrategy) -> &mut Search<'a> {
         self.terms_matching_strategy = value;
         self
     }
 
+    /// Stops removing the query terms with the `Last` strategy once this minimum is reached,
+    /// the documents matching fewer terms are not returned.
+    pub fn minimum_should_match(&mut self, value: MinimumShouldMatch) -> &mut Search<'a> {
+        self.minimum_should_match = Some(value);
+        self
+    }
+
     pub fn scoring_strategy(&mut self, value: ScoringStrategy) -> &mut Search<'a> {
         self.scoring_strategy = value;
         self
     }
 
+    /// Removes the documents whose ranking score is strictly below the given threshold,
+    /// both from the returned documents and from the candidates.
+    pub fn ranking_score_threshold(&mut self, value: f64) -> &mut Search<'a> {
+        self.ranking_score_threshold = Some(value);
+        self
+    }
+
     pub fn words_limit(&mut self, value: usize) -> &mut Search<'a> {
         self.words_limit = value;
         self
@@ -121,6 +256,25 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Restricts the search to the given documents, in addition to the filter.
+    pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Search<'a> {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    /// Resumes the search right after the last document returned by a previous page, the
+    /// documents sharing a distinct value with the previously returned ones are not returned.
+    pub fn search_after(&mut self, search_after: SearchAfter) -> &mut Search<'a> {
+        self.search_after = Some(search_after);
+        self
+    }
+
+    /// Overrides the distinct attribute of the index settings for this search only.
+    pub fn distinct(&mut self, distinct: String) -> &mut Search<'a> {
+        self.distinct = Some(distinct);
+        self
+    }
+
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
         self.geo_strategy = strategy;
@@ -134,30 +288,93 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Once the time budget is exceeded, the ranking rules stop sorting the documents
+    /// and the best documents found so far are returned as degraded results.
+    pub fn time_budget(&mut self, time_budget: TimeBudget) -> &mut Search<'a> {
+        self.time_budget = time_budget;
+        self
+    }
+
     pub fn execute(&self) -> Result<SearchResult> {
+        self.execute_range(self.offset, self.limit)
+    }
+
+    /// Executes the search but returns the documents between `from` and `from + length`
+    /// instead of the ones selected by the offset and limit of the search.
+    ///
+    /// The documents beyond the maximum total hits of the index are never returned.
+    fn execute_range(&self, from: usize, length: usize) -> Result<SearchResult> {
+        let (from, length) = match self.index.pagination_max_total_hits(self.rtxn)? {
+            Some(max_total_hits) => {
+                let from = from.min(max_total_hits);
+                (from, length.min(max_total_hits - from))
+            }
+            None => (from, length),
+        };
+
         let mut ctx = SearchContext::new(self.index, self.rtxn);
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.searchable_attributes(searchable_attributes)?;
         }
 
-        let PartialSearchResult { located_query_terms, candidates, documents_ids, document_scores } =
-            execute_search(
-                &mut ctx,
-                &self.query,
-                &self.vector,
-                self.terms_matching_strategy,
-                self.scoring_strategy,
-                self.exhaustive_number_hits,
-                &self.filter,
-                &self.sort_criteria,
-                self.geo_strategy,
-                self.offset,
-                self.limit,
-                Some(self.words_limit),
-                &mut DefaultSearchLogger,
-                &mut DefaultSearchLogger,
-            )?;
+        // The boosts of the search override the weights of the searchable attributes.
+        let mut attribute_boosts = self.index.searchable_fields_weights(self.rtxn)?;
+        if let Some(boosts) = &self.attribute_boosts {
+            attribute_boosts.extend(boosts.iter().map(|(field, &boost)| (field.clone(), boost)));
+        }
+        if !attribute_boosts.is_empty() {
+            ctx.attribute_boosts(&attribute_boosts)?;
+        }
+
+        ctx.prefix_search = self.prefix_search;
+        ctx.locales = match &self.locales {
+            Some(locales) => Some(locales.clone()),
+            None => self.localized_attributes_locales()?,
+        };
+        if self.keep_stop_words && !self.index.index_stop_words(self.rtxn)? {
+            return Err(UserError::StopWordsNotIndexed.into());
+        }
+        ctx.keep_stop_words = self.keep_stop_words;
+        ctx.minimum_should_match = self.minimum_should_match;
+        ctx.thread_pool = self.thread_pool;
+
+        // The scores must be computed to be compared against the threshold
+        // and against the scores of the document the search resumes after.
+        let scoring_strategy =
+            if self.ranking_score_threshold.is_some() || self.search_after.is_some() {
+                ScoringStrategy::Detailed
+            } else {
+                self.scoring_strategy
+            };
+
+        let PartialSearchResult {
+            located_query_terms,
+            candidates,
+            documents_ids,
+            document_scores,
+            degraded,
+        } = execute_search(
+            &mut ctx,
+            &self.query,
+            &self.vector,
+            self.terms_matching_strategy,
+            scoring_strategy,
+            self.ranking_score_threshold,
+            self.exhaustive_number_hits,
+            &self.filter,
+            &self.candidates,
+            &self.search_after,
+            &self.distinct,
+            &self.sort_criteria,
+            self.geo_strategy,
+            from,
+            length,
+            Some(self.words_limit),
+            self.time_budget,
+            &mut DefaultSearchLogger,
+            &mut DefaultSearchLogger,
+        )?;
 
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
@@ -165,7 +382,42 @@ impl<'a> Search<'a> {
             None => MatchingWords::default(),
         };
 
-        Ok(SearchResult { matching_words, candidates, document_scores, documents_ids })
+        Ok(SearchResult { matching_words, candidates, document_scores, documents_ids, degraded })
+    }
+
+    /// Returns the locales of the localized attributes rules matching the searched attributes,
+    /// the query is then tokenized the same way as these attributes were at indexing time.
+    ///
+    /// Returns `None` if one of the searched attributes is not localized.
+    fn localized_attributes_locales(&self) -> Result<Option<Vec<Language>>> {
+        let rules = match self.index.localized_attributes_rules(self.rtxn)? {
+            Some(rules) if !rules.is_empty() => rules,
+            _ => return Ok(None),
+        };
+
+        let searchable_attributes: Vec<String> = match self.searchable_attributes {
+            Some(attributes) => attributes.to_vec(),
+            None => match self.index.searchable_fields(self.rtxn)? {
+                Some(fields) => fields.into_iter().map(String::from).collect(),
+                None => self.index.fields_ids_map(self.rtxn)?.names().map(String::from).collect(),
+            },
+        };
+
+        let mut locales = Vec::new();
+        for attribute in &searchable_attributes {
+            match find_rule(&rules, attribute) {
+                Some(rule) => {
+                    for &locale in &rule.locales {
+                        if !locales.contains(&locale) {
+                            locales.push(locale);
+                        }
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+
+        Ok((!locales.is_empty()).then_some(locales))
     }
 }
 
@@ -175,15 +427,26 @@ impl fmt::Debug for Search<'_> {
             query,
             vector: _,
             filter,
+            candidates,
+            search_after,
+            distinct,
             offset,
             limit,
             sort_criteria,
             searchable_attributes,
+            attribute_boosts,
+            prefix_search,
+            locales,
+            keep_stop_words,
             geo_strategy: _,
             terms_matching_strategy,
+            minimum_should_match,
             scoring_strategy,
+            ranking_score_threshold,
             words_limit,
             exhaustive_number_hits,
+            time_budget,
+            thread_pool: _,
             rtxn: _,
             index: _,
         } = self;
@@ -191,14 +454,24 @@ impl fmt::Debug for Search<'_> {
             .field("query", query)
             .field("vector", &"[...]")
             .field("filter", filter)
+            .field("candidates", candidates)
+            .field("search_after", search_after)
+            .field("distinct", distinct)
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
             .field("searchable_attributes", searchable_attributes)
+            .field("attribute_boosts", attribute_boosts)
+            .field("prefix_search", prefix_search)
+            .field("locales", locales)
+            .field("keep_stop_words", keep_stop_words)
             .field("terms_matching_strategy", terms_matching_strategy)
+            .field("minimum_should_match", minimum_should_match)
             .field("scoring_strategy", scoring_strategy)
+            .field("ranking_score_threshold", ranking_score_threshold)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
+            .field("time_budget", time_budget)
             .finish()
     }
 }
@@ -209,6 +482,8 @@ pub struct SearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// Whether the time budget was exceeded and the documents were not entirely sorted.
+    pub degraded: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -225,6 +500,29 @@ impl Default for TermsMatchingStrategy {
     }
 }
 
+/// The minimum number of query terms a document must match with the `Last` strategy,
+/// the terms are still removed from last to first until this minimum is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimumShouldMatch {
+    /// An absolute number of terms.
+    Count(usize),
+    /// A percentage of the terms of the query, rounded down.
+    Percentage(u8),
+}
+
+impl MinimumShouldMatch {
+    /// Returns the number of terms to match among the given number of query terms.
+    pub fn required_terms(&self, terms_count: usize) -> usize {
+        let required = match *self {
+            MinimumShouldMatch::Count(count) => count,
+            MinimumShouldMatch::Percentage(percentage) => {
+                terms_count * percentage.min(100) as usize / 100
+            }
+        };
+        required.min(terms_count)
+    }
+}
+
 fn get_first(s: &str) -> &str {
     match s.chars().next() {
         Some(c) => &s[..c.len_utf8()],
@@ -279,7 +577,8 @@ impl<'a> SearchForFacetValues<'a> {
         let rtxn = self.search_query.rtxn;
 
         let filterable_fields = index.filterable_fields(rtxn)?;
-        if !filterable_fields.contains(&self.facet) {
+        // a nested field can be searched as soon as one of its parents is filterable
+        if !crate::is_faceted(&self.facet, &filterable_fields) {
             let (valid_fields, hidden_fields) =
                 index.remove_hidden_fields(rtxn, filterable_fields)?;
 
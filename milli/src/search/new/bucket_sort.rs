@@ -1,3 +1,6 @@
+use std::cmp::Ordering;
+use std::ops::Bound;
+
 use roaring::RoaringBitmap;
 
 use super::logger::SearchLogger;
@@ -5,12 +8,14 @@ use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::{apply_distinct_rule, distinct_single_docid, DistinctOutput};
-use crate::Result;
+use crate::search::SearchAfter;
+use crate::{Result, TimeBudget};
 
 pub struct BucketSortOutput {
     pub docids: Vec<u32>,
     pub scores: Vec<Vec<ScoreDetails>>,
     pub all_candidates: RoaringBitmap,
+    pub degraded: bool,
 }
 
 // TODO: would probably be good to regroup some of these inside of a struct?
@@ -20,19 +25,23 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     mut ranking_rules: Vec<BoxRankingRule<'ctx, Q>>,
     query: &Q,
     universe: &RoaringBitmap,
+    distinct: Option<&str>,
+    search_after: Option<&SearchAfter>,
     from: usize,
     length: usize,
     scoring_strategy: ScoringStrategy,
+    ranking_score_threshold: Option<f64>,
+    exhaustive_number_hits: bool,
+    time_budget: TimeBudget,
     logger: &mut dyn SearchLogger<Q>,
 ) -> Result<BucketSortOutput> {
     logger.initial_query(query);
     logger.ranking_rules(&ranking_rules);
     logger.initial_universe(universe);
 
-    let distinct_fid = if let Some(field) = ctx.index.distinct_field(ctx.txn)? {
-        ctx.index.fields_ids_map(ctx.txn)?.id(field)
-    } else {
-        None
+    let distinct_fid = match distinct {
+        Some(field) => ctx.index.fields_ids_map(ctx.txn)?.id(field),
+        None => None,
     };
 
     if universe.len() < from as u64 {
@@ -40,11 +49,15 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             docids: vec![],
             scores: vec![],
             all_candidates: universe.clone(),
+            degraded: false,
         });
     }
     if ranking_rules.is_empty() {
+        // Without ranking rules, the documents are returned in the order of their ids.
+        let returned = |docid: u32| search_after.map_or(false, |after| docid <= after.docid);
         if let Some(distinct_fid) = distinct_fid {
             let mut excluded = RoaringBitmap::new();
+            let mut previous_results = vec![];
             let mut results = vec![];
             for docid in universe.iter() {
                 if results.len() >= from + length {
@@ -54,21 +67,33 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     continue;
                 }
                 distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
-                results.push(docid);
+                if returned(docid) {
+                    previous_results.push(docid);
+                } else {
+                    results.push(docid);
+                }
             }
             let mut all_candidates = universe - excluded;
+            all_candidates.extend(previous_results.iter().copied());
             all_candidates.extend(results.iter().copied());
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); results.len()],
                 docids: results,
                 all_candidates,
+                degraded: false,
             });
         } else {
-            let docids: Vec<u32> = universe.iter().skip(from).take(length).collect();
+            let docids: Vec<u32> = universe
+                .iter()
+                .skip_while(|&docid| returned(docid))
+                .skip(from)
+                .take(length)
+                .collect();
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); docids.len()],
                 docids,
                 all_candidates: universe.clone(),
+                degraded: false,
             });
         };
     }
@@ -141,7 +166,36 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         };
     }
 
-    while valid_docids.len() < length {
+    // When a ranking score threshold is given, the candidates are only known once
+    // all the buckets have been evaluated against it, we therefore keep iterating
+    // after the page is full if the number of hits must be exhaustive.
+    let exhaustive_threshold = exhaustive_number_hits && ranking_score_threshold.is_some();
+    while valid_docids.len() < length || exhaustive_threshold {
+        // The time budget is exceeded, we stop sorting the documents and return them
+        // in the order of the buckets already computed, from the deepest one to the
+        // first ranking rule. The unsorted part of their score is skipped.
+        if time_budget.exceeded() {
+            loop {
+                let bucket = std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
+                ranking_rule_scores.push(ScoreDetails::Skipped);
+                maybe_add_to_results!(bucket);
+                ranking_rule_scores.pop();
+
+                if cur_ranking_rule_index == 0 {
+                    break;
+                }
+                cur_ranking_rule_index -= 1;
+                ranking_rule_scores.pop();
+            }
+
+            return Ok(BucketSortOutput {
+                docids: valid_docids,
+                scores: valid_scores,
+                all_candidates,
+                degraded: true,
+            });
+        }
+
         // The universe for this bucket is zero, so we don't need to sort
         // anything, just go back to the parent ranking rule.
         if ranking_rule_universes[cur_ranking_rule_index].is_empty()
@@ -178,6 +232,59 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         );
         ranking_rule_universes[cur_ranking_rule_index] -= &next_bucket.candidates;
 
+        // The documents ranked before the document the search resumes after were returned
+        // by the previous pages, they are skipped but still exclude the documents sharing
+        // their distinct value. The documents sharing all the scores of the document the
+        // search resumes after are returned in the order of their ids.
+        if let Some(search_after) = search_after {
+            let ordering = ScoreDetails::rank_cmp_all(&ranking_rule_scores, &search_after.scores);
+            let last_ranking_rule = cur_ranking_rule_index == ranking_rules_len - 1;
+            match ordering {
+                Ordering::Greater => {
+                    skip_returned_documents(
+                        ctx,
+                        distinct_fid,
+                        &mut all_candidates,
+                        &mut ranking_rule_universes,
+                        &next_bucket.candidates,
+                    )?;
+                    ranking_rule_scores.pop();
+                    continue;
+                }
+                Ordering::Equal if last_ranking_rule => {
+                    let mut returned = next_bucket.candidates.clone();
+                    returned.remove_range((Bound::Excluded(search_after.docid), Bound::Unbounded));
+                    skip_returned_documents(
+                        ctx,
+                        distinct_fid,
+                        &mut all_candidates,
+                        &mut ranking_rule_universes,
+                        &returned,
+                    )?;
+                    let candidates = (next_bucket.candidates - returned) & &all_candidates;
+                    maybe_add_to_results!(candidates);
+                    ranking_rule_scores.pop();
+                    continue;
+                }
+                _ => (),
+            }
+        }
+
+        // The score of an incomplete list of ranking rules is the best score
+        // that the documents of this bucket can reach. As the buckets are returned
+        // in decreasing order of score, neither this bucket nor the remaining
+        // documents of the current ranking rule can reach the threshold.
+        if let Some(ranking_score_threshold) = ranking_score_threshold {
+            let current_score = ScoreDetails::global_score(ranking_rule_scores.iter());
+            if current_score < ranking_score_threshold {
+                all_candidates -= &next_bucket.candidates;
+                all_candidates -= &ranking_rule_universes[cur_ranking_rule_index];
+                ranking_rule_scores.pop();
+                back!();
+                continue;
+            }
+        }
+
         if cur_ranking_rule_index == ranking_rules_len - 1
             || (scoring_strategy == ScoringStrategy::Skip && next_bucket.candidates.len() <= 1)
             || cur_offset + (next_bucket.candidates.len() as usize) < from
@@ -203,7 +310,33 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         )?;
     }
 
-    Ok(BucketSortOutput { docids: valid_docids, scores: valid_scores, all_candidates })
+    Ok(BucketSortOutput {
+        docids: valid_docids,
+        scores: valid_scores,
+        all_candidates,
+        degraded: false,
+    })
+}
+
+/// Skips the candidates returned by the previous pages of a search, the documents sharing
+/// their distinct value are removed from the universes as they were already returned too.
+fn skip_returned_documents<'ctx>(
+    ctx: &mut SearchContext<'ctx>,
+    distinct_fid: Option<u16>,
+    all_candidates: &mut RoaringBitmap,
+    ranking_rule_universes: &mut [RoaringBitmap],
+    candidates: &RoaringBitmap,
+) -> Result<()> {
+    if let Some(distinct_fid) = distinct_fid {
+        let DistinctOutput { remaining, excluded } =
+            apply_distinct_rule(ctx, distinct_fid, candidates)?;
+        for universe in ranking_rule_universes.iter_mut() {
+            *universe -= &excluded;
+        }
+        *all_candidates -= &excluded;
+        *all_candidates |= remaining;
+    }
+    Ok(())
 }
 
 /// Add the candidates to the results. Take `distinct`, `from`, `length`, and `cur_offset`
@@ -10,11 +10,15 @@ use roaring::RoaringBitmap;
 use super::interner::Interned;
 use super::Word;
 use crate::heed_codec::{BytesDecodeOwned, StrBEU16Codec};
+use crate::proximity::ProximityPrecision;
 use crate::update::{merge_cbo_roaring_bitmaps, MergeFn};
 use crate::{
     CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, Result, RoaringBitmapCodec, SearchContext,
 };
 
+type WordPairCache<'ctx> =
+    FxHashMap<(u8, Interned<String>, Interned<String>), Option<Cow<'ctx, [u8]>>>;
+
 /// A cache storing pointers to values in the LMDB databases.
 ///
 /// Used for performance reasons only. By using this cache, we avoid performing a
@@ -22,12 +26,9 @@ use crate::{
 /// local HashMap lookup.
 #[derive(Default)]
 pub struct DatabaseCache<'ctx> {
-    pub word_pair_proximity_docids:
-        FxHashMap<(u8, Interned<String>, Interned<String>), Option<Cow<'ctx, [u8]>>>,
-    pub word_prefix_pair_proximity_docids:
-        FxHashMap<(u8, Interned<String>, Interned<String>), Option<Cow<'ctx, [u8]>>>,
-    pub prefix_word_pair_proximity_docids:
-        FxHashMap<(u8, Interned<String>, Interned<String>), Option<Cow<'ctx, [u8]>>>,
+    pub word_pair_proximity_docids: WordPairCache<'ctx>,
+    pub word_prefix_pair_proximity_docids: WordPairCache<'ctx>,
+    pub prefix_word_pair_proximity_docids: WordPairCache<'ctx>,
     pub word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub exact_word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub word_prefix_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
@@ -43,6 +44,8 @@ pub struct DatabaseCache<'ctx> {
     pub word_prefix_fid_docids: FxHashMap<(Interned<String>, u16), Option<Cow<'ctx, [u8]>>>,
     pub word_fids: FxHashMap<Interned<String>, Vec<u16>>,
     pub word_prefix_fids: FxHashMap<Interned<String>, Vec<u16>>,
+
+    pub proximity_precision: Option<ProximityPrecision>,
 }
 impl<'ctx> DatabaseCache<'ctx> {
     fn get_value<'v, K1, KC, DC>(
@@ -157,7 +160,8 @@ impl<'ctx> SearchContext<'ctx> {
         match &self.restricted_fids {
             Some(restricted_fids) => {
                 let interned = self.word_interner.get(word).as_str();
-                let keys: Vec<_> = restricted_fids.iter().map(|fid| (interned, *fid)).collect();
+                let keys: Vec<_> =
+                    restricted_fids.tolerant.iter().map(|fid| (interned, *fid)).collect();
 
                 DatabaseCache::get_value_from_keys::<_, _, CboRoaringBitmapCodec>(
                     self.txn,
@@ -182,13 +186,29 @@ impl<'ctx> SearchContext<'ctx> {
         &mut self,
         word: Interned<String>,
     ) -> Result<Option<RoaringBitmap>> {
-        DatabaseCache::get_value::<_, _, RoaringBitmapCodec>(
-            self.txn,
-            word,
-            self.word_interner.get(word).as_str(),
-            &mut self.db_cache.exact_word_docids,
-            self.index.exact_word_docids.remap_data_type::<ByteSlice>(),
-        )
+        match &self.restricted_fids {
+            Some(restricted_fids) => {
+                let interned = self.word_interner.get(word).as_str();
+                let keys: Vec<_> =
+                    restricted_fids.exact.iter().map(|fid| (interned, *fid)).collect();
+
+                DatabaseCache::get_value_from_keys::<_, _, CboRoaringBitmapCodec>(
+                    self.txn,
+                    word,
+                    &keys[..],
+                    &mut self.db_cache.exact_word_docids,
+                    self.index.word_fid_docids.remap_data_type::<ByteSlice>(),
+                    merge_cbo_roaring_bitmaps,
+                )
+            }
+            None => DatabaseCache::get_value::<_, _, RoaringBitmapCodec>(
+                self.txn,
+                word,
+                self.word_interner.get(word).as_str(),
+                &mut self.db_cache.exact_word_docids,
+                self.index.exact_word_docids.remap_data_type::<ByteSlice>(),
+            ),
+        }
     }
 
     pub fn word_prefix_docids(&mut self, prefix: Word) -> Result<Option<RoaringBitmap>> {
@@ -219,7 +239,8 @@ impl<'ctx> SearchContext<'ctx> {
         match &self.restricted_fids {
             Some(restricted_fids) => {
                 let interned = self.word_interner.get(prefix).as_str();
-                let keys: Vec<_> = restricted_fids.iter().map(|fid| (interned, *fid)).collect();
+                let keys: Vec<_> =
+                    restricted_fids.tolerant.iter().map(|fid| (interned, *fid)).collect();
 
                 DatabaseCache::get_value_from_keys::<_, _, CboRoaringBitmapCodec>(
                     self.txn,
@@ -244,13 +265,98 @@ impl<'ctx> SearchContext<'ctx> {
         &mut self,
         prefix: Interned<String>,
     ) -> Result<Option<RoaringBitmap>> {
-        DatabaseCache::get_value::<_, _, RoaringBitmapCodec>(
-            self.txn,
-            prefix,
-            self.word_interner.get(prefix).as_str(),
-            &mut self.db_cache.exact_word_prefix_docids,
-            self.index.exact_word_prefix_docids.remap_data_type::<ByteSlice>(),
-        )
+        match &self.restricted_fids {
+            Some(restricted_fids) => {
+                let interned = self.word_interner.get(prefix).as_str();
+                let keys: Vec<_> =
+                    restricted_fids.exact.iter().map(|fid| (interned, *fid)).collect();
+
+                DatabaseCache::get_value_from_keys::<_, _, CboRoaringBitmapCodec>(
+                    self.txn,
+                    prefix,
+                    &keys[..],
+                    &mut self.db_cache.exact_word_prefix_docids,
+                    self.index.word_prefix_fid_docids.remap_data_type::<ByteSlice>(),
+                    merge_cbo_roaring_bitmaps,
+                )
+            }
+            None => DatabaseCache::get_value::<_, _, RoaringBitmapCodec>(
+                self.txn,
+                prefix,
+                self.word_interner.get(prefix).as_str(),
+                &mut self.db_cache.exact_word_prefix_docids,
+                self.index.exact_word_prefix_docids.remap_data_type::<ByteSlice>(),
+            ),
+        }
+    }
+
+    fn proximity_precision(&mut self) -> Result<ProximityPrecision> {
+        match self.db_cache.proximity_precision {
+            Some(precision) => Ok(precision),
+            None => {
+                let precision = self.index.proximity_precision(self.txn)?.unwrap_or_default();
+                self.db_cache.proximity_precision = Some(precision);
+                Ok(precision)
+            }
+        }
+    }
+
+    /// Retrieves the documents in which the left and right words (or prefixes) appear in the
+    /// same attribute.
+    ///
+    /// This is used in place of the word pair proximity databases when the proximity precision
+    /// is `ByAttribute`, as these databases are then left empty. All the proximities are
+    /// equivalent at this precision, so the result is cached under the proximity `0`.
+    fn get_db_same_attribute_docids<DC>(
+        &mut self,
+        (left, left_is_prefix): (Interned<String>, bool),
+        (right, right_is_prefix): (Interned<String>, bool),
+        cache: fn(&mut DatabaseCache<'ctx>) -> &mut WordPairCache<'ctx>,
+    ) -> Result<Option<DC::DItem>>
+    where
+        DC: BytesDecodeOwned,
+    {
+        let cache_key = (0, left, right);
+        if !cache(&mut self.db_cache).contains_key(&cache_key) {
+            let left_fids = if left_is_prefix {
+                self.get_db_word_prefix_fids(left)?
+            } else {
+                self.get_db_word_fids(left)?
+            };
+
+            let mut docids = RoaringBitmap::new();
+            for fid in left_fids {
+                let left_docids = if left_is_prefix {
+                    self.get_db_word_prefix_fid_docids(left, fid)?
+                } else {
+                    self.get_db_word_fid_docids(left, fid)?
+                };
+                let right_docids = if right_is_prefix {
+                    self.get_db_word_prefix_fid_docids(right, fid)?
+                } else {
+                    self.get_db_word_fid_docids(right, fid)?
+                };
+                if let (Some(left_docids), Some(right_docids)) = (left_docids, right_docids) {
+                    docids |= left_docids & right_docids;
+                }
+            }
+
+            let bytes = if docids.is_empty() {
+                None
+            } else {
+                let mut bytes = Vec::new();
+                CboRoaringBitmapCodec::serialize_into(&docids, &mut bytes);
+                Some(Cow::Owned(bytes))
+            };
+            cache(&mut self.db_cache).insert(cache_key, bytes);
+        }
+
+        match cache(&mut self.db_cache).get(&cache_key).unwrap() {
+            Some(bytes) => {
+                DC::bytes_decode_owned(bytes).ok_or(heed::Error::Decoding.into()).map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
     pub fn get_db_word_pair_proximity_docids(
@@ -259,6 +365,14 @@ impl<'ctx> SearchContext<'ctx> {
         word2: Interned<String>,
         proximity: u8,
     ) -> Result<Option<RoaringBitmap>> {
+        if self.proximity_precision()? == ProximityPrecision::ByAttribute {
+            return self.get_db_same_attribute_docids::<CboRoaringBitmapCodec>(
+                (word1, false),
+                (word2, false),
+                |cache| &mut cache.word_pair_proximity_docids,
+            );
+        }
+
         DatabaseCache::get_value::<_, _, CboRoaringBitmapCodec>(
             self.txn,
             (proximity, word1, word2),
@@ -278,6 +392,14 @@ impl<'ctx> SearchContext<'ctx> {
         word2: Interned<String>,
         proximity: u8,
     ) -> Result<Option<u64>> {
+        if self.proximity_precision()? == ProximityPrecision::ByAttribute {
+            return self.get_db_same_attribute_docids::<CboRoaringBitmapLenCodec>(
+                (word1, false),
+                (word2, false),
+                |cache| &mut cache.word_pair_proximity_docids,
+            );
+        }
+
         DatabaseCache::get_value::<_, _, CboRoaringBitmapLenCodec>(
             self.txn,
             (proximity, word1, word2),
@@ -297,6 +419,14 @@ impl<'ctx> SearchContext<'ctx> {
         prefix2: Interned<String>,
         proximity: u8,
     ) -> Result<Option<RoaringBitmap>> {
+        if self.proximity_precision()? == ProximityPrecision::ByAttribute {
+            return self.get_db_same_attribute_docids::<CboRoaringBitmapCodec>(
+                (word1, false),
+                (prefix2, true),
+                |cache| &mut cache.word_prefix_pair_proximity_docids,
+            );
+        }
+
         DatabaseCache::get_value::<_, _, CboRoaringBitmapCodec>(
             self.txn,
             (proximity, word1, prefix2),
@@ -315,6 +445,14 @@ impl<'ctx> SearchContext<'ctx> {
         right: Interned<String>,
         proximity: u8,
     ) -> Result<Option<RoaringBitmap>> {
+        if self.proximity_precision()? == ProximityPrecision::ByAttribute {
+            return self.get_db_same_attribute_docids::<CboRoaringBitmapCodec>(
+                (left_prefix, true),
+                (right, false),
+                |cache| &mut cache.prefix_word_pair_proximity_docids,
+            );
+        }
+
         DatabaseCache::get_value::<_, _, CboRoaringBitmapCodec>(
             self.txn,
             (proximity, left_prefix, right),
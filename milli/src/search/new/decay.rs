@@ -0,0 +1,138 @@
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+use time::OffsetDateTime;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::criterion::format_half_life;
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::ByteSliceRefCodec;
+use crate::score_details::{self, ScoreDetails};
+use crate::search::facet::descending_facet_sort;
+use crate::{FieldId, Index, Result};
+
+/// Ranks the documents by the freshness of the unix timestamp, in seconds, of a field.
+///
+/// The documents are grouped by the number of half lives elapsed between their timestamp
+/// and the time of the search, so that the following ranking rules can still sort the
+/// documents that are about as fresh. The documents older than
+/// [`score_details::Decay::MAX_HALF_LIVES`] half lives or without a timestamp come last.
+pub struct Decay<Query> {
+    field_name: String,
+    field_id: Option<FieldId>,
+    half_life: u64,
+    must_redact: bool,
+    original_query: Option<Query>,
+    /// The buckets of documents and their number of elapsed half lives, the freshest last.
+    buckets: Vec<(u32, RoaringBitmap)>,
+}
+
+impl<Query> Decay<Query> {
+    pub fn new(
+        index: &Index,
+        rtxn: &heed::RoTxn,
+        field_name: String,
+        half_life: u64,
+    ) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_id = fields_ids_map.id(&field_name);
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => !crate::is_displayed(&field_name, displayed_fields),
+            None => false,
+        };
+
+        Ok(Self {
+            field_name,
+            field_id,
+            half_life,
+            must_redact,
+            original_query: None,
+            buckets: Vec::new(),
+        })
+    }
+
+    fn score(&self, half_lives: Option<u32>) -> ScoreDetails {
+        ScoreDetails::Decay(score_details::Decay {
+            field_name: self.field_name.clone(),
+            half_life: self.half_life,
+            redacted: self.must_redact,
+            half_lives,
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Decay<Query> {
+    fn id(&self) -> String {
+        format!("decay({}, {})", self.field_name, format_half_life(self.half_life))
+    }
+
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let mut buckets: Vec<(u32, RoaringBitmap)> = Vec::new();
+        if let Some(field_id) = self.field_id {
+            let number_db = ctx
+                .index
+                .facet_id_f64_docids
+                .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+            let now = OffsetDateTime::now_utc().unix_timestamp() as f64;
+            let half_life = self.half_life as f64;
+
+            // the timestamps are iterated from the most recent one
+            let iter =
+                descending_facet_sort(ctx.txn, number_db, field_id, parent_candidates.clone())?;
+            for result in iter {
+                let (docids, bytes) = result?;
+                let timestamp = OrderedF64Codec::bytes_decode(bytes).expect("some number");
+                let half_lives = ((now - timestamp).max(0.0) / half_life).floor();
+                if half_lives >= score_details::Decay::MAX_HALF_LIVES as f64 {
+                    break;
+                }
+
+                let half_lives = half_lives as u32;
+                match buckets.last_mut() {
+                    Some((last, bucket)) if *last == half_lives => *bucket |= docids,
+                    _ => buckets.push((half_lives, docids)),
+                }
+            }
+        }
+
+        buckets.reverse();
+        self.buckets = buckets;
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((half_lives, mut candidates)) = self.buckets.pop() {
+            // a document with many timestamps was already returned with its freshest one
+            candidates &= universe;
+            if !candidates.is_empty() {
+                let score = self.score(Some(half_lives));
+                return Ok(Some(RankingRuleOutput { query, candidates, score }));
+            }
+        }
+
+        // the documents that are too old or without timestamp
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(None) }))
+    }
+
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
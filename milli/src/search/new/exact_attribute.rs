@@ -184,11 +184,17 @@ impl State {
             return Ok(State::Empty(query_graph.clone()));
         }
 
-        let searchable_fields_ids = {
-            if let Some(fids) = ctx.index.searchable_fields_ids(ctx.txn)? {
-                fids
-            } else {
-                ctx.index.fields_ids_map(ctx.txn)?.ids().collect()
+        // only consider the attributes the search has been restricted to, if any
+        let searchable_fields_ids = match &ctx.restricted_fids {
+            Some(restricted_fids) => {
+                restricted_fids.tolerant.iter().chain(&restricted_fids.exact).copied().collect()
+            }
+            None => {
+                if let Some(fids) = ctx.index.searchable_fields_ids(ctx.txn)? {
+                    fids
+                } else {
+                    ctx.index.fields_ids_map(ctx.txn)?.ids().collect()
+                }
             }
         };
 
@@ -4,15 +4,14 @@ use std::iter::FromIterator;
 use heed::types::{ByteSlice, Unit};
 use heed::{RoPrefix, RoTxn};
 use roaring::RoaringBitmap;
-use rstar::RTree;
 
 use super::facet_string_values;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use crate::geo_rtree::GeoRTree;
 use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
 use crate::score_details::{self, ScoreDetails};
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
-    SearchLogger,
+    distance_between_two_points, lat_lng_to_xyz, Index, Result, SearchContext, SearchLogger,
 };
 
 const FID_SIZE: usize = 2;
@@ -80,7 +79,7 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
     ascending: bool,
     point: [f64; 2],
     field_ids: Option<[u16; 2]>,
-    rtree: Option<RTree<GeoPoint>>,
+    rtree: Option<GeoRTree>,
 
     cached_sorted_docids: VecDeque<(u32, [f64; 2])>,
     geo_candidates: RoaringBitmap,
@@ -7,6 +7,7 @@ use charabia::Token;
 use super::super::interner::Interned;
 use super::super::query_term::LocatedQueryTerm;
 use super::super::{DedupInterner, Phrase};
+use crate::stop_words::indexed_stop_word;
 use crate::SearchContext;
 
 pub struct LocatedMatchingPhrase {
@@ -170,7 +171,11 @@ impl<'a> PartialMatch<'a> {
         let Self { mut matching_words, ids, .. } = self;
 
         let is_matching = match matching_words.first()? {
-            Some(word) => &token.lemma() == word,
+            // a kept stop word of the phrase is matched through its indexed form.
+            Some(word) => {
+                &token.lemma() == word
+                    || (token.is_stopword() && *word == indexed_stop_word(token.lemma()))
+            }
             // a None value in the phrase corresponds to a stop word,
             // the walue is considered a match if the current token is categorized as a stop word.
             None => token.is_stopword(),
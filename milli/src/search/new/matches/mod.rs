@@ -5,6 +5,11 @@ pub use matching_words::MatchingWords;
 use matching_words::{MatchType, PartialMatch, WordId};
 use serde::Serialize;
 
+use crate::normalization::{normalize_words, NormalizationParams};
+use crate::number_normalization::normalize_numbers;
+use crate::stemming::{indexed_stems, StemmingLanguage};
+use crate::stop_words::indexed_stop_word;
+
 pub mod matching_words;
 
 const DEFAULT_CROP_MARKER: &str = "…";
@@ -18,6 +23,9 @@ pub struct MatcherBuilder<'m> {
     crop_marker: Option<String>,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
+    normalize_numbers: bool,
+    normalization: NormalizationParams,
+    stemming_languages: Vec<StemmingLanguage>,
 }
 
 impl<'m> MatcherBuilder<'m> {
@@ -28,6 +36,9 @@ impl<'m> MatcherBuilder<'m> {
             crop_marker: None,
             highlight_prefix: None,
             highlight_suffix: None,
+            normalize_numbers: false,
+            normalization: NormalizationParams::default(),
+            stemming_languages: Vec::new(),
         }
     }
 
@@ -46,6 +57,26 @@ impl<'m> MatcherBuilder<'m> {
         self
     }
 
+    /// Merges the numbers written with thousands separators before matching them,
+    /// it must be enabled if the index normalizes the numbers.
+    pub fn normalize_numbers(&mut self, enabled: bool) -> &Self {
+        self.normalize_numbers = enabled;
+        self
+    }
+
+    /// Normalizes the words of the text, it must be given the normalization parameters of the index.
+    pub fn normalization(&mut self, params: NormalizationParams) -> &Self {
+        self.normalization = params;
+        self
+    }
+
+    /// Matches the inflections of the query words, it must be given
+    /// the stemming languages of the index.
+    pub fn stemming_languages(&mut self, languages: Vec<StemmingLanguage>) -> &Self {
+        self.stemming_languages = languages;
+        self
+    }
+
     pub fn build<'t>(&'m self, text: &'t str) -> Matcher<'t, 'm> {
         let crop_marker = match &self.crop_marker {
             Some(marker) => marker.as_str(),
@@ -67,6 +98,9 @@ impl<'m> MatcherBuilder<'m> {
             crop_marker,
             highlight_prefix,
             highlight_suffix,
+            normalize_numbers: self.normalize_numbers,
+            normalization: self.normalization,
+            stemming_languages: &self.stemming_languages,
             matches: None,
         }
     }
@@ -110,6 +144,9 @@ pub struct Matcher<'t, 'm> {
     crop_marker: &'m str,
     highlight_prefix: &'m str,
     highlight_suffix: &'m str,
+    normalize_numbers: bool,
+    normalization: NormalizationParams,
+    stemming_languages: &'m [StemmingLanguage],
     matches: Option<(Vec<Token<'t>>, Vec<Match>)>,
 }
 
@@ -169,7 +206,9 @@ impl<'t> Matcher<'t, '_> {
             false
         }
 
-        let tokens: Vec<_> = self.tokenizer.tokenize(self.text).collect();
+        let tokens = self.tokenizer.tokenize(self.text);
+        let tokens = normalize_words(self.text, tokens, self.normalization, None);
+        let tokens: Vec<_> = normalize_numbers(tokens, self.normalize_numbers).collect();
         let mut matches = Vec::new();
 
         let mut words_positions = tokens
@@ -187,34 +226,48 @@ impl<'t> Matcher<'t, '_> {
             .filter(|(_, _, token)| !token.is_separator());
 
         while let Some((token_position, word_position, word)) = words_positions.next() {
-            for match_type in self.matching_words.match_token(word) {
-                match match_type {
-                    // we match, we save the current token as a match,
-                    // then we continue the rest of the tokens.
-                    MatchType::Full { char_len, ids } => {
-                        let ids: Vec<_> = ids.clone().collect();
-                        matches.push(Match {
-                            match_len: char_len,
-                            ids,
-                            word_position,
-                            token_position,
-                        });
-                        break;
-                    }
-                    // we match partially, iterate over next tokens to check if we can complete the match.
-                    MatchType::Partial(partial) => {
-                        // if match is completed, we break the matching loop over the current token,
+            // the stems of the word match the inflections of the query words,
+            // and the indexed form of a stop word matches the kept stop words of the query.
+            let alternatives: Vec<_> = if word.is_stopword() {
+                vec![indexed_stop_word(word.lemma())]
+            } else {
+                indexed_stems(self.stemming_languages, word.lemma())
+            };
+            let alternatives: Vec<_> = alternatives
+                .into_iter()
+                .map(|lemma| Token { lemma: Cow::Owned(lemma), ..word.clone() })
+                .collect();
+
+            'matching: for token in std::iter::once(word).chain(&alternatives) {
+                for match_type in self.matching_words.match_token(token) {
+                    match match_type {
+                        // we match, we save the current token as a match,
                         // then we continue the rest of the tokens.
-                        let mut wp = words_positions.clone();
-                        if compute_partial_match(
-                            partial,
-                            token_position,
-                            word_position,
-                            &mut wp,
-                            &mut matches,
-                        ) {
-                            words_positions = wp;
-                            break;
+                        MatchType::Full { char_len, ids } => {
+                            let ids: Vec<_> = ids.clone().collect();
+                            matches.push(Match {
+                                match_len: char_len,
+                                ids,
+                                word_position,
+                                token_position,
+                            });
+                            break 'matching;
+                        }
+                        // we match partially, iterate over next tokens to check if we can complete the match.
+                        MatchType::Partial(partial) => {
+                            // if match is completed, we break the matching loop over the current token,
+                            // then we continue the rest of the tokens.
+                            let mut wp = words_positions.clone();
+                            if compute_partial_match(
+                                partial,
+                                token_position,
+                                word_position,
+                                &mut wp,
+                                &mut matches,
+                            ) {
+                                words_positions = wp;
+                                break 'matching;
+                            }
                         }
                     }
                 }
@@ -447,6 +500,11 @@ impl<'t> Matcher<'t, '_> {
                                 continue;
                             }
 
+                            // skip matches overlapping the text that was already highlighted.
+                            if token.byte_start < byte_index {
+                                continue;
+                            }
+
                             if byte_index < token.byte_start {
                                 formatted.push(&self.text[byte_index..token.byte_start]);
                             }
@@ -456,7 +514,17 @@ impl<'t> Matcher<'t, '_> {
                                 .enumerate()
                                 .find(|(i, _)| *i == m.match_len)
                                 .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
-                            formatted.push(self.highlight_prefix);
+
+                            // merge the highlight with the previous one if they are adjacent,
+                            // rather than closing and directly reopening it.
+                            let adjacent = byte_index == token.byte_start
+                                && byte_index > byte_start
+                                && formatted.last() == Some(&self.highlight_suffix);
+                            if adjacent {
+                                formatted.pop();
+                            } else {
+                                formatted.push(self.highlight_prefix);
+                            }
                             formatted.push(&self.text[token.byte_start..highlight_byte_index]);
                             formatted.push(self.highlight_suffix);
                             // if it's a prefix highlight, we put the end of the word after the highlight marker.
@@ -509,13 +577,18 @@ mod tests {
                 &None,
                 crate::TermsMatchingStrategy::default(),
                 crate::score_details::ScoringStrategy::Skip,
+                None,
                 false,
                 &None,
                 &None,
+                &None,
+                &None,
+                &None,
                 crate::search::new::GeoSortStrategy::default(),
                 0,
                 100,
                 Some(10),
+                crate::TimeBudget::max(),
                 &mut crate::DefaultSearchLogger,
                 &mut crate::DefaultSearchLogger,
             )
@@ -639,6 +712,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_highlight_custom_tags() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let mut builder = MatcherBuilder::new_test(&rtxn, &temp_index, "split the world");
+        builder.highlight_prefix("<mark>".to_string());
+        builder.highlight_suffix("</mark>".to_string());
+
+        let format_options = FormatOptions { highlight: true, crop: None };
+
+        let text = "Emily Henry: The Love That Split The World.";
+        let mut matcher = builder.build(text);
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"Emily Henry: <mark>The</mark> Love That <mark>Split</mark> <mark>The</mark> <mark>World</mark>."
+        );
+    }
+
+    #[test]
+    fn format_highlight_adjacent_and_overlapping_matches() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let mut builder = MatcherBuilder::new_test(&rtxn, &temp_index, "split the world");
+        builder.highlight_prefix("<mark>".to_string());
+        builder.highlight_suffix("</mark>".to_string());
+
+        let format_options = FormatOptions { highlight: true, crop: None };
+
+        // the tokenizer never splits `splitworld`, the matched tokens are therefore built by hand.
+        let text = "splitworld";
+        let mut matcher = builder.build(text);
+        let word = matcher.compute_matches().matches.take().unwrap().0.remove(0);
+        let token = |byte_start, byte_end| Token { byte_start, byte_end, ..word.clone() };
+        let matched = |token_position, match_len| Match {
+            match_len,
+            ids: vec![0],
+            word_position: token_position,
+            token_position,
+        };
+
+        // adjacent matches are highlighted together.
+        matcher.matches =
+            Some((vec![token(0, 5), token(5, 10)], vec![matched(0, 5), matched(1, 5)]));
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"<mark>splitworld</mark>"
+        );
+
+        // a match overlapping an already highlighted one is skipped.
+        matcher.matches =
+            Some((vec![token(0, 5), token(3, 8)], vec![matched(0, 5), matched(1, 5)]));
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"<mark>split</mark>world"
+        );
+    }
+
     #[test]
     fn format_crop() {
         let temp_index = temp_index_with_documents();
@@ -1,5 +1,6 @@
 mod bucket_sort;
 mod db_cache;
+mod decay;
 mod distinct;
 mod geo_sort;
 mod graph_based_ranking_rule;
@@ -20,12 +21,15 @@ mod sort;
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashSet};
 
 use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
+use decay::Decay;
 use exact_attribute::ExactAttribute;
+use fxhash::FxHashMap;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
 use instant_distance::Search;
@@ -33,10 +37,12 @@ use interner::{DedupInterner, Interner};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
+pub(crate) use query_term::number_of_typos_allowed;
 use query_term::{located_query_terms_from_tokens, LocatedQueryTerm, Phrase, QueryTerm};
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
+use rayon::ThreadPool;
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
 use sort::Sort;
@@ -46,14 +52,26 @@ use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
-use crate::distance::NDotProductPoint;
+use crate::distance::{dot_product_similarity, BinaryQuantizedPoint, NDotProductPoint};
 use crate::error::FieldIdMapMissingEntry;
-use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::localized_attributes_rules::locales_allow_list;
+use crate::normalization::normalize_words;
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
+use crate::search::SearchAfter;
 use crate::{
-    AscDesc, DocumentId, Filter, Index, Member, Result, TermsMatchingStrategy, UserError, BEU32,
+    AscDesc, DocumentId, FieldId, Filter, Index, Member, MinimumShouldMatch, Result,
+    TermsMatchingStrategy, TimeBudget, UserError, VectorOrArrayOfVectors, BEU32,
 };
 
+/// The maximum number of filtered documents for which the vector search only compares
+/// the vectors of the filtered documents instead of traversing the HNSW graph.
+///
+/// Above this limit the graph is traversed and its neighbors are filtered afterward,
+/// the filtered documents are compared one by one only when too few neighbors remain
+/// to fill the requested page.
+const MAX_CANDIDATES_FOR_PREFILTERED_VECTOR_SEARCH: u64 = 10_000;
+
 /// A structure used throughout the execution of a search query.
 pub struct SearchContext<'ctx> {
     pub index: &'ctx Index,
@@ -63,7 +81,18 @@ pub struct SearchContext<'ctx> {
     pub phrase_interner: DedupInterner<Phrase>,
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
-    pub restricted_fids: Option<Vec<u16>>,
+    pub restricted_fids: Option<RestrictedFids>,
+    pub fid_weights: Option<FxHashMap<FieldId, u16>>,
+    /// Overrides the prefix search setting of the index when set.
+    pub prefix_search: Option<bool>,
+    /// Forces the languages used to tokenize the query when set.
+    pub locales: Option<Vec<Language>>,
+    /// Matches the stop words of the query instead of ignoring them.
+    pub keep_stop_words: bool,
+    /// The minimum number of query terms to match with the `Last` matching strategy.
+    pub minimum_should_match: Option<MinimumShouldMatch>,
+    /// The threads used to parallelize the independent computations of the search.
+    pub thread_pool: Option<&'ctx ThreadPool>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -77,14 +106,21 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            fid_weights: None,
+            prefix_search: None,
+            locales: None,
+            keep_stop_words: false,
+            minimum_should_match: None,
+            thread_pool: None,
         }
     }
 
     pub fn searchable_attributes(&mut self, searchable_attributes: &'ctx [String]) -> Result<()> {
         let fids_map = self.index.fields_ids_map(self.txn)?;
         let searchable_names = self.index.searchable_fields(self.txn)?;
+        let exact_attributes_ids = self.index.exact_attributes_ids(self.txn)?;
 
-        let mut restricted_fids = Vec::new();
+        let mut restricted_fids = RestrictedFids::default();
         let mut contains_wildcard = false;
         for field_name in searchable_attributes {
             if field_name == "*" {
@@ -123,13 +159,91 @@ impl<'ctx> SearchContext<'ctx> {
                 }
             };
 
-            restricted_fids.push(fid);
+            if exact_attributes_ids.contains(&fid) {
+                restricted_fids.exact.push(fid);
+            } else {
+                restricted_fids.tolerant.push(fid);
+            }
         }
 
         self.restricted_fids = (!contains_wildcard).then_some(restricted_fids);
 
         Ok(())
     }
+
+    /// Reorders the searchable attributes for the attribute ranking rule according to the
+    /// given boosts. The higher the boost, the more important the attribute is, the attributes
+    /// without a boost have a boost of `1` and keep their relative searchable attributes order.
+    /// The boost of an attribute also applies to its nested fields and the fields matching its pattern.
+    pub fn attribute_boosts(&mut self, boosts: &BTreeMap<String, u16>) -> Result<()> {
+        let fids_map = self.index.fields_ids_map(self.txn)?;
+        let searchable_names = self.index.searchable_fields(self.txn)?;
+        let searchable_fids = match self.index.searchable_fields_ids(self.txn)? {
+            Some(fids) => fids,
+            None => fids_map.ids().collect(),
+        };
+
+        let mut fid_boosts: Vec<_> = searchable_fids.into_iter().map(|fid| (fid, 1)).collect();
+        for (field_name, &boost) in boosts {
+            let is_searchable = searchable_names
+                .as_ref()
+                .map_or(true, |sn| sn.iter().any(|name| name == field_name));
+            if !is_searchable {
+                let (valid_fields, hidden_fields) = match searchable_names {
+                    Some(sn) => self.index.remove_hidden_fields(self.txn, sn)?,
+                    None => self.index.remove_hidden_fields(self.txn, fids_map.names())?,
+                };
+
+                return Err(UserError::InvalidAttributeBoost {
+                    field: field_name.to_string(),
+                    valid_fields,
+                    hidden_fields,
+                }
+                .into());
+            }
+
+            for (fid, fid_boost) in fid_boosts.iter_mut() {
+                if fids_map.name(*fid).map_or(false, |name| crate::is_faceted_by(name, field_name))
+                {
+                    *fid_boost = boost;
+                }
+            }
+        }
+
+        fid_boosts.sort_by_key(|&(fid, boost)| (Reverse(boost), fid));
+        self.fid_weights = Some(
+            fid_boosts
+                .into_iter()
+                .enumerate()
+                .map(|(weight, (fid, _))| (fid, weight as u16))
+                .collect(),
+        );
+
+        Ok(())
+    }
+
+    /// The weight of a field for the attribute ranking rule, the lower the better.
+    ///
+    /// It is the field id itself unless the attributes have been boosted for this search.
+    pub fn fid_weight(&self, fid: FieldId) -> u16 {
+        self.fid_weights.as_ref().and_then(|weights| weights.get(&fid).copied()).unwrap_or(fid)
+    }
+}
+
+/// The fields a search is restricted to, split by whether they belong to the exact attributes.
+///
+/// The words of the exact attributes are stored in dedicated databases, we must know which
+/// of the restricted fields are exact to be able to only fetch the matching docids.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestrictedFids {
+    pub tolerant: Vec<FieldId>,
+    pub exact: Vec<FieldId>,
+}
+
+impl RestrictedFids {
+    pub fn contains(&self, fid: &FieldId) -> bool {
+        self.tolerant.contains(fid) || self.exact.contains(fid)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
@@ -236,6 +350,13 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay { field, half_life } => {
+                if sorted_fields.contains(&field) {
+                    continue;
+                }
+                sorted_fields.insert(field.clone());
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, field, half_life)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -345,6 +466,13 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay { field, half_life } => {
+                if sorted_fields.contains(&field) {
+                    continue;
+                }
+                sorted_fields.insert(field.clone());
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, field, half_life)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -412,13 +540,18 @@ pub fn execute_search(
     vector: &Option<Vec<f32>>,
     terms_matching_strategy: TermsMatchingStrategy,
     scoring_strategy: ScoringStrategy,
+    ranking_score_threshold: Option<f64>,
     exhaustive_number_hits: bool,
     filters: &Option<Filter>,
+    candidates: &Option<RoaringBitmap>,
+    search_after: &Option<SearchAfter>,
+    distinct: &Option<String>,
     sort_criteria: &Option<Vec<AscDesc>>,
     geo_strategy: geo_sort::Strategy,
     from: usize,
     length: usize,
     words_limit: Option<usize>,
+    time_budget: TimeBudget,
     placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery>,
     query_graph_logger: &mut dyn SearchLogger<QueryGraph>,
 ) -> Result<PartialSearchResult> {
@@ -428,40 +561,107 @@ pub fn execute_search(
         ctx.index.documents_ids(ctx.txn)?
     };
 
+    if let Some(candidates) = candidates {
+        universe &= candidates;
+    }
+
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
-    if let Some(vector) = vector {
-        let mut search = Search::default();
-        let docids = match ctx.index.vector_hnsw(ctx.txn)? {
-            Some(hnsw) => {
-                let vector = NDotProductPoint::new(vector.clone());
-                let neighbors = hnsw.search(&vector, &mut search);
-
-                let mut docids = Vec::new();
-                let mut uniq_docids = RoaringBitmap::new();
-                for instant_distance::Item { distance: _, pid, point: _ } in neighbors {
-                    let index = BEU32::new(pid.into_inner());
-                    let docid = ctx.index.vector_id_docid.get(ctx.txn, &index)?.unwrap().get();
-                    if universe.contains(docid) && uniq_docids.insert(docid) {
-                        docids.push(docid);
-                        if docids.len() == (from + length) {
-                            break;
-                        }
-                    }
+    // The distinct attribute given at search time overrides the one of the settings.
+    let distinct = match distinct {
+        Some(distinct) => {
+            let filterable_fields = ctx.index.filterable_fields(ctx.txn)?;
+            if !crate::is_faceted(distinct, &filterable_fields) {
+                let (valid_fields, hidden_fields) =
+                    ctx.index.remove_hidden_fields(ctx.txn, filterable_fields)?;
+                return Err(UserError::InvalidDistinctAttribute {
+                    field: distinct.clone(),
+                    valid_fields,
+                    hidden_fields,
                 }
+                .into());
+            }
+            Some(distinct.as_str())
+        }
+        None => ctx.index.distinct_field(ctx.txn)?,
+    };
 
-                // return the nearest documents that are also part of the candidates
-                // along with a dummy list of scores that are useless in this context.
-                docids.into_iter().skip(from).take(length).collect()
+    if let Some(vector) = vector {
+        let vector = NDotProductPoint::new(vector.clone());
+        // When the filters keep few documents the approximate search could miss most
+        // of them, only the vectors of the filtered documents are compared to the query.
+        let restricted = filters.is_some() || candidates.is_some();
+        let (neighbors, degraded) = if restricted
+            && universe.len() <= MAX_CANDIDATES_FOR_PREFILTERED_VECTOR_SEARCH
+        {
+            prefiltered_nearest_neighbors(ctx.index, ctx.txn, &vector, &universe, time_budget)?
+        } else {
+            let neighbors = approximate_nearest_neighbors(ctx.index, ctx.txn, &vector, &universe)?
+                .unwrap_or_default();
+            // The neighbors found in the graph are filtered afterward, when the filters
+            // discard too many of them the filtered documents are searched directly,
+            // unless the time budget is already exceeded.
+            if restricted && neighbors.len() < from + length {
+                if time_budget.exceeded() {
+                    (neighbors, true)
+                } else {
+                    prefiltered_nearest_neighbors(
+                        ctx.index,
+                        ctx.txn,
+                        &vector,
+                        &universe,
+                        time_budget,
+                    )?
+                }
+            } else {
+                (neighbors, false)
             }
-            None => Vec::new(),
         };
 
+        let mut docids = Vec::new();
+        for (docid, similarity) in neighbors {
+            let score = score_details::Vector { similarity: Some(similarity) };
+            // the documents ranked before the document the search resumes after were
+            // returned by the previous pages.
+            if let Some(search_after) = search_after {
+                let scores = [ScoreDetails::Vector(score)];
+                match ScoreDetails::rank_cmp_all(&scores, &search_after.scores) {
+                    Ordering::Greater => continue,
+                    Ordering::Equal if docid <= search_after.docid => continue,
+                    _ => (),
+                }
+            }
+            if ranking_score_threshold
+                .map_or(false, |threshold| score.rank().local_score() < threshold)
+            {
+                // the neighbors are sorted by distance, all the following
+                // documents are below the threshold too.
+                break;
+            }
+            docids.push((docid, similarity));
+            if docids.len() == (from + length) {
+                break;
+            }
+        }
+
+        // return the nearest documents that are also part of the candidates.
+        let docids: Vec<_> = docids.into_iter().skip(from).take(length).collect();
+
+        let (documents_ids, document_scores) = docids
+            .into_iter()
+            .map(|(docid, similarity)| {
+                let score =
+                    ScoreDetails::Vector(score_details::Vector { similarity: Some(similarity) });
+                (docid, vec![score])
+            })
+            .unzip();
+
         return Ok(PartialSearchResult {
             candidates: universe,
-            document_scores: vec![Vec::new(); docids.len()],
-            documents_ids: docids,
+            document_scores,
+            documents_ids,
             located_query_terms: None,
+            degraded,
         });
     }
 
@@ -489,13 +689,18 @@ pub fn execute_search(
             tokbuilder.words_dict(dictionary);
         }
 
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
+        let script_lang_map = match &ctx.locales {
+            Some(locales) => locales_allow_list(locales),
+            None => ctx.index.script_language(ctx.txn)?,
+        };
         if !script_lang_map.is_empty() {
             tokbuilder.allow_list(&script_lang_map);
         }
 
         let tokenizer = tokbuilder.build();
         let tokens = tokenizer.tokenize(query);
+        let normalization = ctx.index.normalization_params(ctx.txn)?;
+        let tokens = normalize_words(query, tokens, normalization, stop_words.as_ref());
 
         let query_terms = located_query_terms_from_tokens(ctx, tokens, words_limit)?;
         if query_terms.is_empty() {
@@ -526,9 +731,14 @@ pub fn execute_search(
             ranking_rules,
             &graph,
             &universe,
+            distinct,
+            search_after.as_ref(),
             from,
             length,
             scoring_strategy,
+            ranking_score_threshold,
+            exhaustive_number_hits,
+            time_budget,
             query_graph_logger,
         )?
     } else {
@@ -539,20 +749,25 @@ pub fn execute_search(
             ranking_rules,
             &PlaceholderQuery,
             &universe,
+            distinct,
+            search_after.as_ref(),
             from,
             length,
             scoring_strategy,
+            ranking_score_threshold,
+            exhaustive_number_hits,
+            time_budget,
             placeholder_search_logger,
         )?
     };
 
-    let BucketSortOutput { docids, scores, mut all_candidates } = bucket_sort_output;
+    let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
     // The candidates is the universe unless the exhaustive number of hits
     // is requested and a distinct attribute is set.
     if exhaustive_number_hits {
-        if let Some(f) = ctx.index.distinct_field(ctx.txn)? {
+        if let Some(f) = distinct {
             if let Some(distinct_fid) = fields_ids_map.id(f) {
                 all_candidates = apply_distinct_rule(ctx, distinct_fid, &all_candidates)?.remaining;
             }
@@ -564,9 +779,163 @@ pub fn execute_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms,
+        degraded,
     })
 }
 
+/// Returns the documents of the universe sorted by decreasing similarity, according
+/// to the nearest neighbors found in the HNSW, or `None` if there is no HNSW.
+pub(crate) fn approximate_nearest_neighbors(
+    index: &Index,
+    rtxn: &RoTxn,
+    vector: &NDotProductPoint,
+    universe: &RoaringBitmap,
+) -> Result<Option<Vec<(DocumentId, f32)>>> {
+    let mut search = Search::default();
+
+    if let Some(hnsw) = index.binary_vector_hnsw(rtxn)? {
+        // The binary quantized vectors only give a coarse ordering, the neighbors
+        // they find are reranked by comparing their full vectors to the query.
+        let query = BinaryQuantizedPoint::new(vector);
+        let mut candidates = RoaringBitmap::new();
+        for instant_distance::Item { pid, .. } in hnsw.search(&query, &mut search) {
+            let index_pid = BEU32::new(pid.into_inner());
+            let docid = index.vector_id_docid.get(rtxn, &index_pid)?.unwrap().get();
+            if universe.contains(docid) {
+                candidates.insert(docid);
+            }
+        }
+        let (neighbors, _) =
+            exact_nearest_neighbors(index, rtxn, vector, &candidates, TimeBudget::max())?;
+        return Ok(Some(neighbors));
+    }
+
+    let hnsw = match index.vector_hnsw(rtxn)? {
+        Some(hnsw) => hnsw,
+        None => return Ok(None),
+    };
+
+    let mut neighbors = Vec::new();
+    let mut uniq_docids = RoaringBitmap::new();
+    for instant_distance::Item { distance, pid, point: _ } in hnsw.search(vector, &mut search) {
+        let index_pid = BEU32::new(pid.into_inner());
+        let docid = index.vector_id_docid.get(rtxn, &index_pid)?.unwrap().get();
+        if universe.contains(docid) && uniq_docids.insert(docid) {
+            // the distance is the opposite of the dot product similarity.
+            neighbors.push((docid, 1.0 - distance));
+        }
+    }
+
+    Ok(Some(neighbors))
+}
+
+/// Returns the documents of the universe sorted by decreasing similarity, by comparing
+/// the query to the points of the HNSW that belong to the documents of the universe.
+///
+/// Once the time budget is exceeded the remaining documents are not compared,
+/// the documents already compared are returned along with `true`.
+fn prefiltered_nearest_neighbors(
+    index: &Index,
+    rtxn: &RoTxn,
+    vector: &NDotProductPoint,
+    universe: &RoaringBitmap,
+    time_budget: TimeBudget,
+) -> Result<(Vec<(DocumentId, f32)>, bool)> {
+    let mut pids_docids = FxHashMap::default();
+    for result in index.vector_id_docid.iter(rtxn)? {
+        let (pid, docid) = result?;
+        if universe.contains(docid.get()) {
+            pids_docids.insert(pid.get(), docid.get());
+        }
+    }
+
+    let hnsw = match index.vector_hnsw(rtxn)? {
+        Some(hnsw) => hnsw,
+        // The binary quantized points are too coarse to rank the documents,
+        // the full vectors of the filtered documents are compared instead.
+        None => {
+            let docids: RoaringBitmap = pids_docids.into_values().collect();
+            return exact_nearest_neighbors(index, rtxn, vector, &docids, time_budget);
+        }
+    };
+
+    // a document with multiple vectors is as similar as its most similar vector.
+    let mut similarities: FxHashMap<DocumentId, f32> = FxHashMap::default();
+    let mut degraded = false;
+    for (pid, point) in hnsw.iter() {
+        if time_budget.exceeded() {
+            degraded = true;
+            break;
+        }
+        let docid = match pids_docids.get(&pid.into_inner()) {
+            Some(docid) => *docid,
+            None => continue,
+        };
+        if point.len() == vector.len() {
+            let similarity = dot_product_similarity(vector, point);
+            let best = similarities.entry(docid).or_insert(similarity);
+            *best = best.max(similarity);
+        }
+    }
+
+    let mut neighbors: Vec<_> = similarities.into_iter().collect();
+    neighbors
+        .sort_by(|(ldocid, left), (rdocid, right)| right.total_cmp(left).then(ldocid.cmp(rdocid)));
+
+    Ok((neighbors, degraded))
+}
+
+/// Returns the documents of the universe sorted by decreasing similarity, by comparing
+/// the query to every vector of the documents.
+///
+/// Once the time budget is exceeded the remaining documents are not compared,
+/// the documents already compared are returned along with `true`.
+pub(crate) fn exact_nearest_neighbors(
+    index: &Index,
+    rtxn: &RoTxn,
+    vector: &NDotProductPoint,
+    universe: &RoaringBitmap,
+    time_budget: TimeBudget,
+) -> Result<(Vec<(DocumentId, f32)>, bool)> {
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let vectors_fid = match fields_ids_map.id("_vectors") {
+        Some(fid) => fid,
+        None => return Ok((Vec::new(), false)),
+    };
+
+    let mut neighbors = Vec::new();
+    let mut degraded = false;
+    for result in index.iter_documents(rtxn, universe.iter())? {
+        if time_budget.exceeded() {
+            degraded = true;
+            break;
+        }
+        let (docid, obkv) = result?;
+        let vectors = match obkv.get(vectors_fid) {
+            Some(bytes) => serde_json::from_slice::<VectorOrArrayOfVectors>(bytes)
+                .ok()
+                .and_then(VectorOrArrayOfVectors::into_array_of_vectors)
+                .unwrap_or_default(),
+            None => continue,
+        };
+
+        // a document with multiple vectors is as similar as its most similar vector.
+        let similarity = vectors
+            .into_iter()
+            .filter(|document_vector| document_vector.len() == vector.len())
+            .map(|document_vector| {
+                dot_product_similarity(vector, &NDotProductPoint::new(document_vector))
+            })
+            .max_by(f32::total_cmp);
+        if let Some(similarity) = similarity {
+            neighbors.push((docid, similarity));
+        }
+    }
+    neighbors.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+
+    Ok((neighbors, degraded))
+}
+
 fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>) -> Result<()> {
     let sort_criteria = if let Some(sort_criteria) = sort_criteria {
         sort_criteria
@@ -587,7 +956,8 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
 
     // We check that we are allowed to use the sort criteria, we check
     // that they are declared in the sortable fields.
-    let sortable_fields = ctx.index.sortable_fields(ctx.txn)?;
+    let mut sortable_fields = ctx.index.sortable_fields(ctx.txn)?;
+    sortable_fields.extend(ctx.index.timestamps_fields(ctx.txn)?.iter().map(|f| f.to_string()));
     for asc_desc in sort_criteria {
         match asc_desc.member() {
             Member::Field(ref field) if !crate::is_faceted(field, &sortable_fields) => {
@@ -622,4 +992,5 @@ pub struct PartialSearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    pub degraded: bool,
 }
@@ -98,6 +98,7 @@ impl QueryGraph {
         let mut new_located_query_terms = terms.to_vec();
 
         let nbr_typos = number_of_typos_allowed(ctx)?;
+        let concatenate_words = ctx.index.concatenate_words(ctx.txn)?;
 
         let mut nodes_data: Vec<QueryNodeData> = vec![QueryNodeData::Start, QueryNodeData::End];
         let root_node = 0;
@@ -121,7 +122,7 @@ impl QueryGraph {
             );
             new_nodes.push(new_node_idx);
 
-            if !prev1.is_empty() {
+            if concatenate_words && !prev1.is_empty() {
                 if let Some(ngram) =
                     query_term::make_ngram(ctx, &terms[term_idx - 1..=term_idx], &nbr_typos)?
                 {
@@ -137,7 +138,7 @@ impl QueryGraph {
                     new_nodes.push(ngram_idx);
                 }
             }
-            if !prev2.is_empty() {
+            if concatenate_words && !prev2.is_empty() {
                 if let Some(ngram) =
                     query_term::make_ngram(ctx, &terms[term_idx - 2..=term_idx], &nbr_typos)?
                 {
@@ -340,6 +341,12 @@ impl QueryGraph {
         if !at_least_one_mandatory_term {
             res.pop();
         }
+        // each removal step removes a single term, they stop once the minimum is reached.
+        if let Some(minimum_should_match) = ctx.minimum_should_match {
+            let terms_count = (last_term_idx - first_term_idx) as usize + 1;
+            let required_terms = minimum_should_match.required_terms(terms_count);
+            res.truncate(terms_count - required_terms);
+        }
         res
     }
 
@@ -11,6 +11,8 @@ use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::query_term::TwoTypoTerm;
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
+use crate::stemming::{is_indexed_stem, query_stems};
+use crate::stop_words::{indexed_stop_word, is_indexed_stop_word};
 use crate::{Result, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -130,6 +132,10 @@ fn find_zero_one_two_typo_derivations(
 
     while let Some((derived_word, state)) = stream.next() {
         let derived_word = std::str::from_utf8(derived_word)?;
+        // a typo on the first letter must not match the prefixed stems and stop words.
+        if is_indexed_stem(derived_word) || is_indexed_stop_word(derived_word) {
+            continue;
+        }
         let derived_word_interned = word_interner.insert(derived_word.to_owned());
         // in the case the typo is on the first letter, we know the number of typo
         // is two
@@ -218,6 +224,17 @@ pub fn partially_initialized_term_from_word(
         zero_typo = Some(word_interned);
     }
 
+    // the inflections of the word are matched through their stem, like single word synonyms.
+    let stemming_languages = ctx.index.stemming_languages(ctx.txn)?;
+    let stems: Vec<_> = query_stems(&stemming_languages, word)
+        .into_iter()
+        .filter(|stem| fst.contains(stem))
+        .collect();
+
+    // the stop words are only matched by the searches keeping them.
+    let stop_word = Some(indexed_stop_word(word))
+        .filter(|stop_word| ctx.keep_stop_words && fst.contains(stop_word));
+
     if is_prefix && use_prefix_db.is_none() {
         find_zero_typo_prefix_derivations(
             word_interned,
@@ -235,7 +252,7 @@ pub fn partially_initialized_term_from_word(
     }
     let synonyms = ctx.index.synonyms(ctx.txn)?;
     let mut synonym_word_count = 0;
-    let synonyms = synonyms
+    let mut synonyms: BTreeSet<_> = synonyms
         .get(&vec![word.to_owned()])
         .cloned()
         .unwrap_or_default()
@@ -250,6 +267,11 @@ pub fn partially_initialized_term_from_word(
             Some(ctx.phrase_interner.insert(Phrase { words }))
         })
         .collect();
+
+    for single_word in stems.into_iter().chain(stop_word) {
+        let words = vec![Some(ctx.word_interner.insert(single_word))];
+        synonyms.insert(ctx.phrase_interner.insert(Phrase { words }));
+    }
     let zero_typo =
         ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
 
@@ -265,6 +287,10 @@ pub fn partially_initialized_term_from_word(
 }
 
 fn find_split_words(ctx: &mut SearchContext, word: &str) -> Result<Option<Interned<Phrase>>> {
+    if !ctx.index.split_words(ctx.txn)? {
+        return Ok(None);
+    }
+
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
         Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
     } else {
@@ -1,16 +1,22 @@
-use charabia::normalizer::NormalizedTokenIter;
-use charabia::{SeparatorKind, TokenKind};
+use charabia::{SeparatorKind, Token, TokenKind};
 
 use super::*;
+use crate::number_normalization;
+use crate::prefix_search::PrefixSearch;
+use crate::stop_words::indexed_stop_word;
 use crate::{Result, SearchContext, MAX_WORD_LENGTH};
 
 /// Convert the tokenised search query into a list of located query terms.
-pub fn located_query_terms_from_tokens(
+pub fn located_query_terms_from_tokens<'t>(
     ctx: &mut SearchContext,
-    query: NormalizedTokenIter,
+    query: impl Iterator<Item = Token<'t>>,
     words_limit: Option<usize>,
 ) -> Result<Vec<LocatedQueryTerm>> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
+    let prefix_search = match ctx.prefix_search {
+        Some(prefix_search) => prefix_search,
+        None => ctx.index.prefix_search(ctx.txn)? == PrefixSearch::IndexingTime,
+    };
 
     let mut located_terms = Vec::new();
 
@@ -21,6 +27,8 @@ pub fn located_query_terms_from_tokens(
     // start with the last position as we will wrap around to position 0 at the beginning of the loop below.
     let mut position = u16::MAX;
 
+    let normalize_numbers = ctx.index.normalize_numbers(ctx.txn)?;
+    let query = number_normalization::normalize_numbers(query, normalize_numbers);
     let mut peekable = query.take(super::limits::MAX_TOKEN_COUNT).peekable();
     while let Some(token) = peekable.next() {
         if token.lemma().is_empty() {
@@ -38,12 +46,16 @@ pub fn located_query_terms_from_tokens(
 
                 // 1. if the word is quoted we push it in a phrase-buffer waiting for the ending quote,
                 // 2. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
-                // 3. if the word is the last token of the query we push it as a prefix word.
+                //    the stop words are pushed as well when the search keeps them,
+                // 3. if the word is the last token of the query we push it as a prefix word,
+                //    unless the prefix search is disabled.
                 if let Some(phrase) = &mut phrase {
                     phrase.push_word(ctx, &token, position)
                 } else if peekable.peek().is_some() {
                     match token.kind {
-                        TokenKind::Word => {
+                        TokenKind::Word | TokenKind::StopWord
+                            if token.is_word() || ctx.keep_stop_words =>
+                        {
                             let word = token.lemma();
                             let term = partially_initialized_term_from_word(
                                 ctx,
@@ -66,7 +78,7 @@ pub fn located_query_terms_from_tokens(
                         ctx,
                         word,
                         nbr_typos(word),
-                        true,
+                        prefix_search,
                         false,
                     )?;
                     let located_term = LocatedQueryTerm {
@@ -140,14 +152,19 @@ pub fn number_of_typos_allowed<'ctx>(
     let min_len_two_typos = ctx.index.min_word_len_two_typos(ctx.txn)?;
 
     let exact_words = ctx.index.exact_words(ctx.txn)?;
+    let disable_typos_on_numbers = ctx.index.disable_typos_on_numbers(ctx.txn)?;
 
     Ok(Box::new(move |word: &str| {
+        // The minimum word sizes are expressed in characters, not in bytes,
+        // so that non-ASCII words are not granted more typos than expected.
+        let word_len = word.chars().count();
         if !authorize_typos
-            || word.len() < min_len_one_typo as usize
+            || word_len < min_len_one_typo as usize
             || exact_words.as_ref().map_or(false, |fst| fst.contains(word))
+            || (disable_typos_on_numbers && is_mostly_numeric(word))
         {
             0
-        } else if word.len() < min_len_two_typos as usize {
+        } else if word_len < min_len_two_typos as usize {
             1
         } else {
             2
@@ -155,6 +172,19 @@ pub fn number_of_typos_allowed<'ctx>(
     }))
 }
 
+/// Whether more than half of the characters of the word are digits,
+/// like the phone numbers, zip codes or product references.
+fn is_mostly_numeric(word: &str) -> bool {
+    let (digits, others) = word.chars().fold((0, 0), |(digits, others), c| {
+        if c.is_ascii_digit() {
+            (digits + 1, others)
+        } else {
+            (digits, others + 1)
+        }
+    });
+    digits > others
+}
+
 pub fn make_ngram(
     ctx: &mut SearchContext,
     terms: &[LocatedQueryTerm],
@@ -245,7 +275,11 @@ impl PhraseBuilder {
         }
         self.end = position;
         if let TokenKind::StopWord = token.kind {
-            self.words.push(None);
+            // the kept stop words are matched through their prefixed indexed form.
+            let word = ctx
+                .keep_stop_words
+                .then(|| ctx.word_interner.insert(indexed_stop_word(token.lemma())));
+            self.words.push(word);
         } else {
             // token has kind Word
             let word = ctx.word_interner.insert(token.lemma().to_string());
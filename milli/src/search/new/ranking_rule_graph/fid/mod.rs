@@ -71,28 +71,24 @@ impl RankingRuleGraphTrait for FidGraph {
         let mut edges = vec![];
         for fid in all_fields.iter().copied() {
             edges.push((
-                fid as u32 * term.term_ids.len() as u32,
+                ctx.fid_weight(fid) as u32 * term.term_ids.len() as u32,
                 conditions_interner.insert(FidCondition { term: term.clone(), fid }),
             ));
         }
 
         // always lookup the max_fid if we don't already and add an artificial condition for max scoring
         let max_fid: Option<u16> = {
-            if let Some(max_fid) = ctx
-                .index
-                .searchable_fields_ids(ctx.txn)?
-                .map(|field_ids| field_ids.into_iter().max())
-            {
-                max_fid
-            } else {
-                ctx.index.fields_ids_map(ctx.txn)?.ids().max()
-            }
+            let searchable_fids = match ctx.index.searchable_fields_ids(ctx.txn)? {
+                Some(field_ids) => field_ids,
+                None => ctx.index.fields_ids_map(ctx.txn)?.ids().collect(),
+            };
+            searchable_fids.into_iter().max_by_key(|&fid| ctx.fid_weight(fid))
         };
 
         if let Some(max_fid) = max_fid {
             if !all_fields.contains(&max_fid) {
                 edges.push((
-                    max_fid as u32 * term.term_ids.len() as u32, // TODO improve the fid score i.e. fid^10.
+                    ctx.fid_weight(max_fid) as u32 * term.term_ids.len() as u32, // TODO improve the fid score i.e. fid^10.
                     conditions_interner.insert(FidCondition {
                         term: term.clone(), // TODO remove this ugly clone
                         fid: max_fid,
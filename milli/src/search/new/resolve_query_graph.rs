@@ -3,6 +3,7 @@
 use std::collections::VecDeque;
 
 use fxhash::FxHashMap;
+use rayon::prelude::*;
 use roaring::{MultiOps, RoaringBitmap};
 
 use super::interner::Interned;
@@ -29,16 +30,27 @@ impl<'ctx> SearchContext<'ctx> {
         Ok(docids)
     }
 }
+/// Unions the docids of the words of a term, on the thread pool of the search when there is one.
+fn union_words_docids(ctx: &SearchContext, words_docids: Vec<RoaringBitmap>) -> RoaringBitmap {
+    match ctx.thread_pool {
+        Some(thread_pool) if words_docids.len() > 1 => thread_pool.install(|| {
+            words_docids.into_par_iter().reduce(RoaringBitmap::new, |left, right| left | right)
+        }),
+        _ => words_docids.union(),
+    }
+}
+
 pub fn compute_query_term_subset_docids(
     ctx: &mut SearchContext,
     term: &QueryTermSubset,
 ) -> Result<RoaringBitmap> {
-    let mut docids = RoaringBitmap::new();
+    let mut words_docids = Vec::new();
     for word in term.all_single_words_except_prefix_db(ctx)? {
         if let Some(word_docids) = ctx.word_docids(word)? {
-            docids |= word_docids;
+            words_docids.push(word_docids);
         }
     }
+    let mut docids = union_words_docids(ctx, words_docids);
     for phrase in term.all_phrases(ctx)? {
         docids |= ctx.get_phrase_docids(phrase)?;
     }
@@ -57,12 +69,13 @@ pub fn compute_query_term_subset_docids_within_field_id(
     term: &QueryTermSubset,
     fid: u16,
 ) -> Result<RoaringBitmap> {
-    let mut docids = RoaringBitmap::new();
+    let mut words_docids = Vec::new();
     for word in term.all_single_words_except_prefix_db(ctx)? {
         if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(word.interned(), fid)? {
-            docids |= word_fid_docids;
+            words_docids.push(word_fid_docids);
         }
     }
+    let mut docids = union_words_docids(ctx, words_docids);
 
     for phrase in term.all_phrases(ctx)? {
         // There may be false positives when resolving a phrase, so we're not
@@ -90,14 +103,15 @@ pub fn compute_query_term_subset_docids_within_position(
     term: &QueryTermSubset,
     position: u16,
 ) -> Result<RoaringBitmap> {
-    let mut docids = RoaringBitmap::new();
+    let mut words_docids = Vec::new();
     for word in term.all_single_words_except_prefix_db(ctx)? {
         if let Some(word_position_docids) =
             ctx.get_db_word_position_docids(word.interned(), position)?
         {
-            docids |= word_position_docids;
+            words_docids.push(word_position_docids);
         }
     }
+    let mut docids = union_words_docids(ctx, words_docids);
 
     for phrase in term.all_phrases(ctx)? {
         // It's difficult to know the expected position of the words in the phrase,
@@ -79,7 +79,7 @@ impl<'ctx, Query> Sort<'ctx, Query> {
             return Ok(false);
         };
 
-        Ok(!displayed_fields.iter().any(|&field| field == field_name))
+        Ok(!crate::is_displayed(field_name, displayed_fields))
     }
 }
 
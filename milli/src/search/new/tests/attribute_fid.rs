@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::index::tests::TempIndex;
 use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
 
@@ -146,3 +148,47 @@ fn test_attribute_fid_ngrams() {
         documents_ids.iter().zip(document_scores.into_iter()).collect();
     insta::assert_snapshot!(format!("{document_ids_scores:#?}"));
 }
+
+#[test]
+fn test_attribute_fid_boosts() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "plot".to_owned()]);
+            s.set_criteria(vec![Criterion::Attribute]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "a story", "plot": "the dog" },
+            { "id": 1, "title": "the dog", "plot": "a story" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("dog");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0]");
+
+    // the boosted `plot` attribute becomes more important than `title`
+    let boosts = BTreeMap::from([("plot".to_owned(), 3)]);
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.attribute_boosts(boosts);
+    s.query("dog");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    // boosting an attribute that isn't searchable is an error
+    let boosts = BTreeMap::from([("author".to_owned(), 3)]);
+    let mut s = Search::new(&txn, &index);
+    s.attribute_boosts(boosts);
+    s.query("dog");
+    assert!(s.execute().is_err());
+}
@@ -0,0 +1,107 @@
+/*!
+This module tests the `decay(field, half_life)` ranking rule:
+
+1. the documents are grouped by the number of half lives elapsed since their timestamp
+2. the documents of a same group are sorted by the following ranking rules
+3. a document with many timestamps is ranked with its most recent one
+4. the documents that are too old or without a timestamp appear at the end
+*/
+
+use big_s::S;
+use time::OffsetDateTime;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+const DAY: i64 = 24 * 60 * 60;
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![
+                Criterion::Decay { field: S("date"), half_life: DAY as u64 },
+                Criterion::Asc(S("rank")),
+            ]);
+        })
+        .unwrap();
+
+    // one hour of margin keeps the documents away from the bounds of the buckets
+    let now = OffsetDateTime::now_utc().unix_timestamp() - 3600;
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "rank": 0, "date": now - 3 * DAY },
+            { "id": 1, "text": "the fox", "rank": 2, "date": now },
+            { "id": 2, "text": "the quick brown fox", "rank": 0 },
+            { "id": 3, "text": "the quick fox", "rank": 1, "date": now - 2 * DAY },
+            { "id": 4, "text": "the quick brown fox", "rank": 0, "date": now - 30 * DAY },
+            { "id": 5, "text": "the fox", "rank": 1, "date": now },
+            {
+                "id": 6,
+                "text": "the quick brown fox",
+                "rank": 1,
+                "date": [now - 40 * DAY, now - 2 * DAY],
+            },
+            { "id": 7, "text": "the fox", "rank": 3, "date": now - 3 * DAY },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_decay() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, [5, 1, 3, 6, 0, 7, 2, 4]);
+
+    let half_lives: Vec<_> = document_scores
+        .iter()
+        .map(|scores| match &scores[0] {
+            ScoreDetails::Decay(decay) => decay.half_lives,
+            other => panic!("unexpected score details {other:?}"),
+        })
+        .collect();
+    assert_eq!(half_lives, [Some(0), Some(0), Some(2), Some(2), Some(3), Some(3), None, None]);
+
+    let decay = score_details::Decay {
+        field_name: S("date"),
+        half_life: DAY as u64,
+        redacted: false,
+        half_lives: Some(0),
+    };
+    assert_eq!(decay.rank().local_score(), 1.0);
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    assert_eq!(
+        serde_json::Value::Object(details)["decay(date, 1d)"],
+        serde_json::json!({ "order": 0, "halfLives": 0, "score": 1.0 })
+    );
+}
+
+#[test]
+fn test_decay_with_words() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Decay { field: S("date"), half_life: DAY as u64 },
+                Criterion::Asc(S("rank")),
+            ]);
+        })
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    // the freshness only sorts the documents matching the same number of words
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, [6, 0, 2, 4, 3]);
+}
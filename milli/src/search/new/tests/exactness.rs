@@ -932,3 +932,49 @@ fn test_typo_followed_by_exactness() {
     ]
     "###);
 }
+
+#[test]
+fn test_exactness_restricted_searchable_attributes() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Exactness]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "the best sunflower oil brand",
+                "description": "sunflower oil",
+            },
+            {
+                "id": 1,
+                "title": "sunflower oil",
+                "description": "the best sunflower oil brand",
+            },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // without restriction both documents have an attribute equal to the query
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("sunflower oil");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    // the exact match in the `description` must be ignored when searching on `title` only
+    let searchable_attributes = vec!["title".to_owned()];
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.searchable_attributes(&searchable_attributes);
+    s.query("sunflower oil");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0]");
+}
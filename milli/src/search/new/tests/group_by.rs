@@ -0,0 +1,146 @@
+/*!
+This module tests the grouping of the search results by a filterable attribute:
+
+1. the groups are ordered by the rank of their best document
+2. at most `hits_per_group` documents are returned for each group, along with the number of candidates of the group
+3. the offset and the limit of the search apply to the groups
+4. the documents without any value for the attribute are ignored
+5. an error is returned if the attribute is not filterable
+6. only the documents ranked within the maximum total hits are grouped
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Group, GroupsResult, Member, Search};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(hashset! { S("family") });
+            s.set_sortable_fields(hashset! { S("price") });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "family": "shoes", "price": 30 },
+            { "id": 1, "family": "Shirts", "price": 10 },
+            { "id": 2, "family": "shoes", "price": 20 },
+            { "id": 3, "family": "hats", "price": 50 },
+            { "id": 4, "family": "shirts", "price": 40 },
+            { "id": 5, "family": "shoes", "price": 5 },
+            { "id": 6, "price": 1 },
+        ]))
+        .unwrap();
+    index
+}
+
+fn summarize(groups: &[Group]) -> Vec<(String, u64, Vec<u32>)> {
+    groups
+        .iter()
+        .map(|group| (group.value.clone(), group.count, group.documents_ids.clone()))
+        .collect()
+}
+
+#[test]
+fn test_group_by() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("price")))]);
+    let GroupsResult { groups, .. } = s.execute_groups("family", 2, 1000).unwrap();
+    insta::assert_debug_snapshot!(summarize(&groups), @r###"
+    [
+        (
+            "shoes",
+            3,
+            [
+                5,
+                2,
+            ],
+        ),
+        (
+            "Shirts",
+            2,
+            [
+                1,
+                4,
+            ],
+        ),
+        (
+            "hats",
+            1,
+            [
+                3,
+            ],
+        ),
+    ]
+    "###);
+}
+
+#[test]
+fn test_group_by_pagination() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("price")))]);
+    s.offset(1);
+    s.limit(1);
+    let GroupsResult { groups, .. } = s.execute_groups("family", 1, 1000).unwrap();
+    insta::assert_debug_snapshot!(summarize(&groups), @r###"
+    [
+        (
+            "Shirts",
+            2,
+            [
+                1,
+            ],
+        ),
+    ]
+    "###);
+}
+
+#[test]
+fn test_group_by_max_total_hits() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("price")))]);
+    let GroupsResult { groups, .. } = s.execute_groups("family", 2, 3).unwrap();
+    insta::assert_debug_snapshot!(summarize(&groups), @r###"
+    [
+        (
+            "shoes",
+            3,
+            [
+                5,
+            ],
+        ),
+        (
+            "Shirts",
+            2,
+            [
+                1,
+            ],
+        ),
+    ]
+    "###);
+}
+
+#[test]
+fn test_group_by_not_filterable() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let s = Search::new(&txn, &index);
+    let error = s.execute_groups("price", 1, 1000).unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `price` is not filterable and thus, cannot be used to group the documents. Available filterable attributes are: `family`.");
+}
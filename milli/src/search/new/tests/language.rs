@@ -1,4 +1,7 @@
+use charabia::Language;
+
 use crate::index::tests::TempIndex;
+use crate::localized_attributes_rules::LocalizedAttributesRule;
 use crate::{Search, SearchResult};
 
 #[test]
@@ -21,3 +24,119 @@ fn test_kanji_language_detection() {
 
     insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
 }
+
+#[test]
+fn test_chinese_segmentation() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "我喜欢吃苹果" },
+            { "id": 1, "title": "我喜欢喝茶" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut search = Search::new(&txn, &index);
+
+    // without segmentation the whole sentence would be a single unsearchable token
+    search.query("苹果");
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
+
+#[test]
+fn test_thai_segmentation() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "สบู่สมุนไพรดอกดาวเรือง 100 กรัม" },
+            { "id": 1, "title": "ชาเขียว 100 กรัม" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut search = Search::new(&txn, &index);
+
+    search.query("สมุนไพร");
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
+
+#[test]
+fn test_forced_locales() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_localized_attributes_rules(vec![LocalizedAttributesRule::new(
+                vec!["title_ja".to_string()],
+                vec![Language::Jpn],
+            )]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title_ja": "東京都庁" },
+            { "id": 1, "title_ja": "京都" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut search = Search::new(&txn, &index);
+
+    // a short kanji-only query would otherwise be detected as chinese
+    search.query("東京");
+    search.locales(vec![Language::Jpn]);
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
+
+#[test]
+fn test_localized_attributes_locales() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_searchable_fields(vec!["title_ja".to_string(), "title_zh".to_string()]);
+            s.set_localized_attributes_rules(vec![
+                LocalizedAttributesRule::new(vec!["*_ja".to_string()], vec![Language::Jpn]),
+                LocalizedAttributesRule::new(vec!["*_zh".to_string()], vec![Language::Cmn]),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title_ja": "東京都庁" },
+            { "id": 1, "title_ja": "京都" },
+            { "id": 2, "title_zh": "东京的天气很好" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let searchable_attributes = ["title_ja".to_string()];
+
+    // the index contains chinese, a kanji-only query is then detected as chinese
+    // and segmented differently than the japanese documents, unless its locales are given
+    let mut search = Search::new(&txn, &index);
+    search.query("東京都庁");
+    search.searchable_attributes(&searchable_attributes);
+    search.locales(vec![Language::Jpn]);
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    // without locales, the query is tokenized with the locales of the searched attributes
+    let mut search = Search::new(&txn, &index);
+    search.query("東京都庁");
+    search.searchable_attributes(&searchable_attributes);
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
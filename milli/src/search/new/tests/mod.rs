@@ -1,18 +1,26 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod decay;
 pub mod distinct;
 pub mod exactness;
 pub mod geo_sort;
+pub mod group_by;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 pub mod language;
 pub mod ngram_split_words;
+pub mod pagination;
+pub mod prefix_search;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod similar;
 pub mod sort;
+pub mod spelling;
 pub mod stop_words;
+pub mod suggest;
 pub mod typo;
 pub mod typo_proximity;
+pub mod vector_search;
 pub mod words_tms;
 
 fn collect_field_values(
@@ -15,6 +15,8 @@ This module tests the following properties:
 12. Prefix tolerance is disabled for the last word if a space follows it
 13. Ngrams cannot be formed by combining a phrase and a word or two phrases
 14. Split words are not disabled by the `disableOnAttribute` or `disableOnWords` typo settings
+15. The split words can be disabled with the `splitWords` setting
+16. The ngrams can be disabled with the `concatenateWords` setting
 */
 
 use crate::index::tests::TempIndex;
@@ -426,3 +428,59 @@ fn test_split_words_never_disabled() {
     ]
     "###);
 }
+
+#[test]
+fn test_split_words_setting_disabled() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_split_words(false);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("sunflower ");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    // no more documents with `sun flower`
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 3, 4]");
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"the sunflowers are pretty\"",
+        "\"the sunflower is tall\"",
+        "\"the sunflawer is tall\"",
+    ]
+    "###);
+}
+
+#[test]
+fn test_concatenate_words_setting_disabled() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_autorize_typos(false);
+            s.set_concatenate_words(false);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("sun flower");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    // no more documents with `sunflower`
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"the sun flowers are pretty\"",
+        "\"the sun flower is tall\"",
+    ]
+    "###);
+}
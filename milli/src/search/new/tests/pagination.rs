@@ -0,0 +1,55 @@
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_pagination_max_total_hits(3);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the dog" },
+            { "id": 1, "title": "the cat" },
+            { "id": 2, "title": "the bird" },
+            { "id": 3, "title": "the fish" },
+            { "id": 4, "title": "the horse" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_limit_capped_by_max_total_hits() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.limit(10);
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+    // the candidates are not truncated
+    assert_eq!(candidates.len(), 5);
+}
+
+#[test]
+fn test_offset_capped_by_max_total_hits() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.offset(2);
+    s.limit(10);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.offset(4);
+    s.limit(10);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
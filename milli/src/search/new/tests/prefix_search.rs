@@ -0,0 +1,94 @@
+/*!
+This module tests the following properties about the prefix search setting:
+- by default, the last word of the query is matched as a prefix
+- when disabled in the settings, the prefix databases are not built and
+the last word of the query must match exactly
+- the setting can be overridden for a single search
+*/
+
+use crate::index::tests::TempIndex;
+use crate::prefix_search::PrefixSearch;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["sku".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "sku": "mei",
+            },
+            {
+                "id": 1,
+                "sku": "meilisearch",
+            },
+            {
+                "id": 2,
+                "sku": "meili search",
+            },
+            {
+                "id": 3,
+                "sku": "mai",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_prefix_search_enabled_by_default() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("mei");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+}
+
+#[test]
+fn test_prefix_search_disabled() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_prefix_search(PrefixSearch::Disabled);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("mei");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    // the search can enable the prefix search again
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.prefix_search(true);
+    s.query("mei");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+}
+
+#[test]
+fn test_prefix_search_disabled_for_a_single_search() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.prefix_search(false);
+    s.query("mei");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
@@ -18,6 +18,7 @@ they store fewer sprximities than the regular word sprximity DB.
 use std::collections::BTreeMap;
 
 use crate::index::tests::TempIndex;
+use crate::proximity::ProximityPrecision;
 use crate::search::new::tests::collect_field_values;
 use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
 
@@ -488,3 +489,58 @@ fn test_proximity_prefix_db() {
     ]
     "###);
 }
+
+#[test]
+fn test_proximity_precision_by_attribute() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Proximity]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "the quick brown and smart fox",
+            },
+            {
+                "id": 1,
+                "title": "the quick dog",
+                "description": "the fox",
+            },
+            {
+                "id": 2,
+                "title": "the quick fox",
+            },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("quick fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1]");
+    drop(txn);
+
+    index
+        .update_settings(|s| {
+            s.set_proximity_precision(ProximityPrecision::ByAttribute);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    assert!(index.word_pair_proximity_docids.is_empty(&txn).unwrap());
+
+    // the distance between the words is ignored, only sharing an attribute matters
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("quick fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 1]");
+}
@@ -0,0 +1,50 @@
+use std::collections::HashSet;
+
+use crate::index::tests::TempIndex;
+use crate::{Filter, SearchResult, Similar};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(HashSet::from(["kind".to_owned()]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "kind": "book", "title": "the hobbit, an unexpected journey through the misty mountains" },
+            { "id": 1, "kind": "movie", "title": "the hobbit, the desolation of smaug in the misty mountains" },
+            { "id": 2, "kind": "book", "title": "a journey through the misty mountains" },
+            { "id": 3, "kind": "book", "title": "the hunger games" },
+            { "id": 4, "kind": "book", "title": "an unexpected hobbit" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_similar_by_shared_words() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let similar = Similar::new(0, &txn, &index);
+    let SearchResult { documents_ids, .. } = similar.execute().unwrap();
+
+    // the document itself is never returned and the rarest shared words weigh the most.
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 4, 1, 3]");
+}
+
+#[test]
+fn test_similar_with_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut similar = Similar::new(0, &txn, &index);
+    similar.filter(Filter::from_str("kind = movie").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = similar.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+}
@@ -0,0 +1,47 @@
+use crate::index::tests::TempIndex;
+use crate::SpellingSuggestions;
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the dragon of the mountain" },
+            { "id": 1, "title": "a dragon in the garden" },
+            { "id": 2, "title": "the wagon of the merchant near the drain" },
+            { "id": 3, "title": "the merchant and the mountain" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_spelling_suggestions() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // the closest and then the most frequent words are preferred.
+    let suggestions = SpellingSuggestions::new("Dragin montain", &txn, &index).execute().unwrap();
+    insta::assert_debug_snapshot!(suggestions, @r###"
+    [
+        "dragon mountain",
+        "drain mountain",
+    ]
+    "###);
+}
+
+#[test]
+fn test_no_spelling_suggestions() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // the known words and the short words are never corrected.
+    let suggestions = SpellingSuggestions::new("dragon of teh", &txn, &index).execute().unwrap();
+    insta::assert_debug_snapshot!(suggestions, @"[]");
+}
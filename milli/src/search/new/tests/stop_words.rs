@@ -1,7 +1,7 @@
 /*!
 This module tests the following properties about stop words:
-- they are not indexed
-- they are not searchable
+- they are not indexed, unless the index is asked to index them for the searches keeping them
+- they are not searchable, unless the search keeps them
 - they are case sensitive
 - they are ignored in phrases
 - If a query consists only of stop words, a placeholder query is used instead
@@ -458,3 +458,52 @@ fn test_stop_words_in_phrase() {
     // As a result the scores are empty lists
     insta::assert_snapshot!(format!("{document_scores:#?}"));
 }
+
+#[test]
+fn test_keep_stop_words() {
+    let index = create_index();
+
+    // the stop words can't be kept as long as they are not indexed
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("to ");
+    s.keep_stop_words(true);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"The stop words of the query can only be kept when the `indexStopWords` setting of the index is enabled.");
+    drop(txn);
+
+    index.update_settings(|s| s.set_index_stop_words(true)).unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // `to` is ignored, the search is handled as a placeholder search
+    let mut s = Search::new(&txn, &index);
+    s.query("to ");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5, 6]");
+
+    // `to` is kept and only matches the documents containing it
+    let mut s = Search::new(&txn, &index);
+    s.query("to ");
+    s.keep_stop_words(true);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 5, 6]");
+
+    // the kept stop words of a phrase must be in the documents
+    let mut s = Search::new(&txn, &index);
+    s.query("\"how to attempt\"");
+    s.keep_stop_words(true);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"how xyz train\"");
+    s.keep_stop_words(true);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
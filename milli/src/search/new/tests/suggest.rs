@@ -0,0 +1,72 @@
+use crate::index::tests::TempIndex;
+use crate::update::RecordSuggestions;
+use crate::{Suggest, Suggestion};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    let mut wtxn = index.write_txn().unwrap();
+    let mut builder = RecordSuggestions::new(&mut wtxn, &index);
+    builder
+        .add_with_popularity("iron man", 10)
+        .add_with_popularity("iron man 2", 3)
+        .add_with_popularity("iron giant", 5)
+        .add_with_popularity("irish coffee", 1)
+        .add_with_popularity("the incredible hulk", 7);
+    builder.execute().unwrap();
+    wtxn.commit().unwrap();
+
+    index
+}
+
+fn texts(suggestions: Vec<Suggestion>) -> Vec<String> {
+    suggestions
+        .into_iter()
+        .map(|Suggestion { text, popularity, typos }| format!("{text} ({popularity}, {typos})"))
+        .collect()
+}
+
+#[test]
+fn test_suggest_by_popularity() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let suggestions = Suggest::new("IR", &txn, &index).execute().unwrap();
+    insta::assert_debug_snapshot!(texts(suggestions), @r###"
+    [
+        "iron man (10, 0)",
+        "iron giant (5, 0)",
+        "iron man 2 (3, 0)",
+        "irish coffee (1, 0)",
+    ]
+    "###);
+
+    let mut suggest = Suggest::new("", &txn, &index);
+    suggest.limit(2);
+    insta::assert_debug_snapshot!(texts(suggest.execute().unwrap()), @r###"
+    [
+        "iron man (10, 0)",
+        "the incredible hulk (7, 0)",
+    ]
+    "###);
+}
+
+#[test]
+fn test_suggest_with_typos() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // the exact completions come before the ones with typos.
+    let suggestions = Suggest::new("iron gian", &txn, &index).execute().unwrap();
+    insta::assert_debug_snapshot!(texts(suggestions), @r###"
+    [
+        "iron giant (5, 0)",
+        "iron man (10, 2)",
+        "iron man 2 (3, 2)",
+    ]
+    "###);
+
+    // the short prefixes don't tolerate typos.
+    let suggestions = Suggest::new("irx", &txn, &index).execute().unwrap();
+    insta::assert_debug_snapshot!(texts(suggestions), @"[]");
+}
@@ -16,6 +16,7 @@ if `words` doesn't exist before it.
 12. Prefix tolerance costs nothing according to the typo ranking rule
 13. Split words cost 1 typo according to the typo ranking rule
 14. Synonyms cost nothing according to the typo ranking rule
+15. Words mostly composed of digits are not typo tolerant when typos are disabled on numbers
 */
 
 use std::collections::BTreeMap;
@@ -230,6 +231,78 @@ fn test_default_typo() {
     "###);
 }
 
+#[test]
+fn test_typo_word_size_counts_characters() {
+    let index = create_index();
+    index
+        .add_documents(documents!([
+            {
+                "id": 26,
+                "text": "привет"
+            },
+        ]))
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    // "привет" is 6 characters long but 12 bytes long, it must only accept one typo
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("привот");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[26]");
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("пмивот");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
+
+#[test]
+fn test_typo_disabled_on_numbers() {
+    let index = create_index();
+    index
+        .add_documents(documents!([
+            {
+                "id": 26,
+                "text": "call 0123456789"
+            },
+            {
+                "id": 27,
+                "text": "reference ab12345"
+            },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    for (query, expected) in [("0123456780", [26]), ("ab12346", [27])] {
+        let mut s = Search::new(&txn, &index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query(query);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        assert_eq!(documents_ids, expected, "{query} should match with a typo");
+    }
+    drop(txn);
+
+    index.update_settings(|s| s.set_disable_typos_on_numbers(true)).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    for query in ["0123456780", "ab12346"] {
+        let mut s = Search::new(&txn, &index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query(query);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        assert!(documents_ids.is_empty(), "{query} should not match with a typo");
+    }
+
+    // the words with a few digits are still typo tolerant
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("referense");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[27]");
+}
+
 #[test]
 fn test_phrase_no_typo_allowed() {
     let index = create_index();
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use crate::index::tests::TempIndex;
+use crate::{Filter, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(HashSet::from(["kind".to_owned()]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "kind": "a", "_vectors": [1.0, 0.0] },
+            { "id": 1, "kind": "b", "_vectors": [0.9, 0.1] },
+            { "id": 2, "kind": "a", "_vectors": [0.0, 1.0] },
+            { "id": 3, "kind": "b", "_vectors": [0.5, 0.5] },
+            { "id": 4, "kind": "a", "_vectors": [[0.0, 1.0], [0.8, 0.2]] },
+            { "id": 5, "kind": "a" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_vector_search() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = index.search(&txn);
+    s.vector(vec![1.0, 0.0]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4, 3, 2]");
+}
+
+#[test]
+fn test_vector_search_with_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = index.search(&txn);
+    s.vector(vec![1.0, 0.0]);
+    s.filter(Filter::from_str("kind = a").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    // the documents are compared to the query one by one, the document
+    // without vectors is ignored and the most similar vector of a document is used.
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 4, 2]");
+
+    let mut s = index.search(&txn);
+    s.vector(vec![1.0, 0.0]);
+    s.filter(Filter::from_str("kind = a").unwrap().unwrap());
+    s.offset(1);
+    s.limit(1);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4]");
+}
+
+#[test]
+fn test_vector_search_with_binary_quantized_vectors() {
+    let index = create_index();
+    index.update_settings(|s| s.set_binary_quantized_vectors(true)).unwrap();
+    let txn = index.read_txn().unwrap();
+    assert!(index.vector_hnsw(&txn).unwrap().is_none());
+    assert!(index.binary_vector_hnsw(&txn).unwrap().is_some());
+
+    // the neighbors found with the quantized vectors are reranked with the full vectors.
+    let mut s = index.search(&txn);
+    s.vector(vec![1.0, 0.0]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4, 3, 2]");
+}
+
+#[test]
+fn test_vector_search_with_filter_and_binary_quantized_vectors() {
+    let index = create_index();
+    index.update_settings(|s| s.set_binary_quantized_vectors(true)).unwrap();
+    let txn = index.read_txn().unwrap();
+
+    // only the full vectors of the filtered documents are compared to the query.
+    let mut s = index.search(&txn);
+    s.vector(vec![1.0, 0.0]);
+    s.filter(Filter::from_str("kind = b").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 3]");
+}
@@ -10,11 +10,12 @@ account by the proximity ranking rule.
 5. Unclosed double quotes still make a phrase
 6. The `all` term matching strategy does not remove any term from the query
 7. The search is capable of returning no results if no documents match the query
+8. The minimum should match stops the `last` term matching strategy from removing more terms
 */
 
 use crate::index::tests::TempIndex;
 use crate::search::new::tests::collect_field_values;
-use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+use crate::{Criterion, MinimumShouldMatch, Search, SearchResult, TermsMatchingStrategy};
 
 fn create_index() -> TempIndex {
     let index = TempIndex::new();
@@ -458,3 +459,38 @@ fn test_words_tms_all() {
     let texts = collect_field_values(&index, &txn, "text", &documents_ids);
     insta::assert_debug_snapshot!(texts, @"[]");
 }
+
+#[test]
+fn test_words_tms_minimum_should_match() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox jumps");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 6, 7, 8, 9, 10, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 4, 11, 12, 3, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox jumps");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.minimum_should_match(MinimumShouldMatch::Count(3));
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 6, 7, 8, 9, 10, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 4, 11, 12]");
+
+    // half of the four terms are required
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox jumps");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.minimum_should_match(MinimumShouldMatch::Percentage(50));
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 6, 7, 8, 9, 10, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 4, 11, 12, 3]");
+
+    // a minimum greater than the number of terms requires all of them
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox jumps");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.minimum_should_match(MinimumShouldMatch::Count(10));
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 6, 7, 8, 9, 10, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22]");
+}
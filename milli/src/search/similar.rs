@@ -0,0 +1,220 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use charabia::TokenizerBuilder;
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use super::new::approximate_nearest_neighbors;
+use crate::distance::NDotProductPoint;
+use crate::error::UserError;
+use crate::score_details::{self, ScoreDetails};
+use crate::{
+    DocumentId, FieldId, Filter, Index, InternalError, Result, SearchResult,
+    VectorOrArrayOfVectors, MAX_WORD_LENGTH,
+};
+
+/// The maximum number of words of the reference document used to find the similar ones.
+const MAX_SIGNIFICANT_WORDS: usize = 25;
+
+/// Retrieves the documents that are the most similar to a reference document.
+///
+/// When the reference document has vectors, its nearest neighbors are returned.
+/// Otherwise the documents are ranked by the rare words they share with it.
+pub struct Similar<'a> {
+    id: DocumentId,
+    filter: Option<Filter<'a>>,
+    offset: usize,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> Similar<'a> {
+    pub fn new(id: DocumentId, rtxn: &'a heed::RoTxn, index: &'a Index) -> Similar<'a> {
+        Similar { id, filter: None, offset: 0, limit: 20, rtxn, index }
+    }
+
+    pub fn offset(&mut self, offset: usize) -> &mut Similar<'a> {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Similar<'a> {
+        self.limit = limit;
+        self
+    }
+
+    pub fn filter(&mut self, filter: Filter<'a>) -> &mut Similar<'a> {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn execute(&self) -> Result<SearchResult> {
+        let mut universe = match &self.filter {
+            Some(filter) => filter.evaluate(self.rtxn, self.index)?,
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+        // the reference document is never similar to itself.
+        universe.remove(self.id);
+
+        let (_, obkv) = self
+            .index
+            .documents(self.rtxn, Some(self.id))?
+            .pop()
+            .ok_or(UserError::UnknownInternalDocumentId { document_id: self.id })?;
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let vectors_fid = fields_ids_map.id("_vectors");
+
+        let vector = match vectors_fid.and_then(|fid| obkv.get(fid)) {
+            Some(bytes) => serde_json::from_slice::<VectorOrArrayOfVectors>(bytes)
+                .ok()
+                .and_then(VectorOrArrayOfVectors::into_array_of_vectors)
+                .and_then(|vectors| vectors.into_iter().next()),
+            None => None,
+        };
+
+        let neighbors = match vector {
+            Some(vector) => approximate_nearest_neighbors(
+                self.index,
+                self.rtxn,
+                &NDotProductPoint::new(vector),
+                &universe,
+            )?,
+            None => None,
+        };
+
+        let scored = match neighbors {
+            Some(neighbors) => neighbors,
+            None => {
+                let searchable_fields = self.index.searchable_fields_ids(self.rtxn)?;
+                let fields = obkv.iter().filter(|(fid, _)| {
+                    Some(*fid) != vectors_fid
+                        && searchable_fields.as_ref().map_or(true, |fids| fids.contains(fid))
+                });
+                self.score_by_shared_words(fields, &universe)?
+            }
+        };
+
+        let candidates: RoaringBitmap = scored.iter().map(|(docid, _)| *docid).collect();
+        let (documents_ids, document_scores) = scored
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit)
+            .map(|(docid, similarity)| {
+                let score =
+                    ScoreDetails::Vector(score_details::Vector { similarity: Some(similarity) });
+                (docid, vec![score])
+            })
+            .unzip();
+
+        Ok(SearchResult {
+            matching_words: Default::default(),
+            candidates,
+            documents_ids,
+            document_scores,
+            degraded: false,
+        })
+    }
+
+    /// Ranks the documents of the universe by the sum of the inverse document
+    /// frequencies of the rarest words they share with the given fields.
+    ///
+    /// The similarity is normalized by the sum of the frequencies of all the
+    /// selected words, a document containing all of them has a similarity of 1.
+    fn score_by_shared_words<'t>(
+        &self,
+        fields: impl Iterator<Item = (FieldId, &'t [u8])>,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<(DocumentId, f32)>> {
+        let mut tokbuilder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.rtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            tokbuilder.stop_words(stop_words);
+        }
+
+        let separators = self.index.allowed_separators(self.rtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            tokbuilder.separators(separators);
+        }
+
+        let dictionary = self.index.dictionary(self.rtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            tokbuilder.words_dict(dictionary);
+        }
+        let tokenizer = tokbuilder.build();
+
+        let mut words = BTreeSet::new();
+        let mut buffer = Vec::new();
+        for (_, bytes) in fields {
+            let value: Value = serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)?;
+            buffer.clear();
+            collect_strings(&value, &mut buffer);
+            for text in &buffer {
+                for token in tokenizer.tokenize(text).filter(|token| token.is_word()) {
+                    let word = token.lemma().trim();
+                    if !word.is_empty() && word.len() <= MAX_WORD_LENGTH {
+                        words.insert(word.to_string());
+                    }
+                }
+            }
+        }
+
+        // only keep the rarest words that are shared with at least one other document.
+        let mut words_docids = Vec::new();
+        for word in words {
+            if let Some(docids) = self.index.word_docids.get(self.rtxn, word.as_str())? {
+                let docids = docids & universe;
+                if !docids.is_empty() {
+                    words_docids.push(docids);
+                }
+            }
+        }
+        words_docids.sort_by_key(|docids| docids.len());
+        words_docids.truncate(MAX_SIGNIFICANT_WORDS);
+
+        let number_of_documents = self.index.number_of_documents(self.rtxn)? as f32;
+        let mut total_weight = 0.0;
+        let mut scores: HashMap<DocumentId, f32> = HashMap::new();
+        for docids in words_docids {
+            let weight = (1.0 + number_of_documents / docids.len() as f32).ln();
+            total_weight += weight;
+            for docid in docids {
+                *scores.entry(docid).or_default() += weight;
+            }
+        }
+
+        let mut scored: Vec<_> =
+            scores.into_iter().map(|(docid, score)| (docid, score / total_weight)).collect();
+        scored.sort_by(|(ldocid, lscore), (rdocid, rscore)| {
+            rscore.partial_cmp(lscore).unwrap().then(ldocid.cmp(rdocid))
+        });
+        Ok(scored)
+    }
+}
+
+impl fmt::Debug for Similar<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Similar { id, filter, offset, limit, rtxn: _, index: _ } = self;
+        f.debug_struct("Similar")
+            .field("id", id)
+            .field("filter", filter)
+            .field("offset", offset)
+            .field("limit", limit)
+            .finish()
+    }
+}
+
+fn collect_strings(value: &Value, output: &mut Vec<String>) {
+    match value {
+        Value::Null | Value::Bool(_) => (),
+        Value::Number(number) => output.push(number.to_string()),
+        Value::String(string) => output.push(string.clone()),
+        Value::Array(values) => values.iter().for_each(|value| collect_strings(value, output)),
+        Value::Object(object) => object.values().for_each(|value| collect_strings(value, output)),
+    }
+}
@@ -0,0 +1,143 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::ops::Range;
+
+use charabia::TokenizerBuilder;
+use fst::{IntoStreamer, Streamer};
+
+use super::build_dfa;
+use super::new::number_of_typos_allowed;
+use crate::stemming::is_indexed_stem;
+use crate::stop_words::is_indexed_stop_word;
+use crate::{Index, Result, SearchContext, MAX_WORD_LENGTH};
+
+/// The maximum number of alternative spellings kept for each unknown word.
+const MAX_ALTERNATIVES_PER_WORD: usize = 3;
+
+/// Computes alternate spellings of a query, to be offered as corrections
+/// when the query yields too few results.
+///
+/// The words of the query that are not part of the index are replaced by the
+/// words of the index that are within the typo tolerance, preferring the closest
+/// and then the most frequent ones.
+pub struct SpellingSuggestions<'a> {
+    query: String,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> SpellingSuggestions<'a> {
+    pub fn new(
+        query: impl Into<String>,
+        rtxn: &'a heed::RoTxn,
+        index: &'a Index,
+    ) -> SpellingSuggestions<'a> {
+        SpellingSuggestions { query: query.into(), limit: 3, rtxn, index }
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut SpellingSuggestions<'a> {
+        self.limit = limit;
+        self
+    }
+
+    /// Returns the corrected queries, the first one corrects every unknown word
+    /// with its best alternative.
+    pub fn execute(&self) -> Result<Vec<String>> {
+        let ctx = SearchContext::new(self.index, self.rtxn);
+        let number_of_typos_allowed = number_of_typos_allowed(&ctx)?;
+        let words_fst = self.index.words_fst(self.rtxn)?;
+
+        let mut tokbuilder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.rtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            tokbuilder.stop_words(stop_words);
+        }
+
+        let separators = self.index.allowed_separators(self.rtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            tokbuilder.separators(separators);
+        }
+
+        let dictionary = self.index.dictionary(self.rtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            tokbuilder.words_dict(dictionary);
+        }
+        let tokenizer = tokbuilder.build();
+
+        let mut corrections: Vec<(Range<usize>, Vec<String>)> = Vec::new();
+        for token in tokenizer.tokenize(&self.query).filter(|token| token.is_word()) {
+            let word = token.lemma();
+            if word.is_empty() || word.len() > MAX_WORD_LENGTH || words_fst.contains(word) {
+                continue;
+            }
+
+            let typos = number_of_typos_allowed(word);
+            if typos == 0 {
+                continue;
+            }
+
+            let dfa = build_dfa(word, typos, false);
+            let mut stream = words_fst.search_with_state(&dfa).into_stream();
+            let mut alternatives = Vec::new();
+            while let Some((alternative, state)) = stream.next() {
+                let alternative = std::str::from_utf8(alternative)?;
+                if is_indexed_stem(alternative) || is_indexed_stop_word(alternative) {
+                    continue;
+                }
+                let count = self.index.word_documents_count(self.rtxn, alternative)?;
+                let distance = dfa.distance(state).to_u8();
+                alternatives.push((distance, Reverse(count), alternative.to_string()));
+            }
+
+            if !alternatives.is_empty() {
+                alternatives.sort_unstable();
+                alternatives.truncate(MAX_ALTERNATIVES_PER_WORD);
+                let alternatives = alternatives.into_iter().map(|(_, _, word)| word).collect();
+                corrections.push((token.byte_start..token.byte_end, alternatives));
+            }
+        }
+
+        if corrections.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // The following suggestions replace a single word by its next alternatives.
+        let best: Vec<_> = corrections.iter().map(|(_, alternatives)| &alternatives[0]).collect();
+        let mut suggestions = vec![self.correct(&corrections, &best)];
+        for (i, (_, alternatives)) in corrections.iter().enumerate() {
+            for alternative in &alternatives[1..] {
+                let mut words = best.clone();
+                words[i] = alternative;
+                suggestions.push(self.correct(&corrections, &words));
+            }
+        }
+        suggestions.truncate(self.limit);
+
+        Ok(suggestions)
+    }
+
+    /// Replaces the misspelled words of the query by the given ones.
+    fn correct(&self, corrections: &[(Range<usize>, Vec<String>)], words: &[&String]) -> String {
+        let mut query = String::with_capacity(self.query.len());
+        let mut last = 0;
+        for ((range, _), word) in corrections.iter().zip(words) {
+            query.push_str(&self.query[last..range.start]);
+            query.push_str(word);
+            last = range.end;
+        }
+        query.push_str(&self.query[last..]);
+        query
+    }
+}
+
+impl fmt::Debug for SpellingSuggestions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SpellingSuggestions { query, limit, rtxn: _, index: _ } = self;
+        f.debug_struct("SpellingSuggestions").field("query", query).field("limit", limit).finish()
+    }
+}
@@ -0,0 +1,109 @@
+use std::fmt;
+
+use fst::{IntoStreamer, Streamer};
+
+use super::build_dfa;
+use crate::{normalize_facet, Index, Result};
+
+/// The maximum length of a suggestion in bytes, the longer queries are not recorded.
+const MAX_SUGGESTION_LENGTH: usize = 250;
+
+/// A recorded query that starts with the prefix given to [`Suggest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub text: String,
+    /// The number of times the suggestion was recorded.
+    pub popularity: u64,
+    /// The number of typos between the prefix and the beginning of the suggestion.
+    pub typos: u8,
+}
+
+/// Suggests the recorded queries that complete a prefix.
+///
+/// The suggestions with the fewest typos come first,
+/// then the most popular ones are preferred.
+pub struct Suggest<'a> {
+    prefix: String,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> Suggest<'a> {
+    pub fn new(prefix: impl Into<String>, rtxn: &'a heed::RoTxn, index: &'a Index) -> Suggest<'a> {
+        Suggest { prefix: prefix.into(), limit: 10, rtxn, index }
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Suggest<'a> {
+        self.limit = limit;
+        self
+    }
+
+    pub fn execute(&self) -> Result<Vec<Suggestion>> {
+        let suggestions_fst = self.index.suggestions_fst(self.rtxn)?;
+        if suggestions_fst.is_empty() || self.limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let prefix = match normalize_suggestion(&self.prefix) {
+            Some(prefix) => prefix,
+            // an empty prefix suggests the most popular queries.
+            None if self.prefix.trim().is_empty() => String::new(),
+            None => return Ok(Vec::new()),
+        };
+
+        // The typos follow the same rules as the words of a search query.
+        let typos = if self.index.authorize_typos(self.rtxn)? {
+            let len = prefix.chars().count();
+            let one_typo = self.index.min_word_len_one_typo(self.rtxn)? as usize;
+            let two_typos = self.index.min_word_len_two_typos(self.rtxn)? as usize;
+            if len >= two_typos {
+                2
+            } else if len >= one_typo {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        };
+
+        let dfa = build_dfa(&prefix, typos, true);
+        let mut stream = suggestions_fst.search_with_state(&dfa).into_stream();
+        let mut suggestions = Vec::new();
+        while let Some((text, popularity, state)) = stream.next() {
+            let text = std::str::from_utf8(text)?.to_string();
+            let typos = dfa.distance(state).to_u8();
+            suggestions.push(Suggestion { text, popularity, typos });
+        }
+
+        suggestions.sort_by(|left, right| {
+            left.typos
+                .cmp(&right.typos)
+                .then(right.popularity.cmp(&left.popularity))
+                .then_with(|| left.text.cmp(&right.text))
+        });
+        suggestions.truncate(self.limit);
+
+        Ok(suggestions)
+    }
+}
+
+impl fmt::Debug for Suggest<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Suggest { prefix, limit, rtxn: _, index: _ } = self;
+        f.debug_struct("Suggest").field("prefix", prefix).field("limit", limit).finish()
+    }
+}
+
+/// Normalizes a query the same way for it to be recorded and suggested,
+/// returns `None` if the query is empty or too long.
+pub(crate) fn normalize_suggestion(query: &str) -> Option<String> {
+    let query = normalize_facet(query);
+    let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+    if query.is_empty() || query.len() > MAX_SUGGESTION_LENGTH {
+        None
+    } else {
+        Some(query)
+    }
+}
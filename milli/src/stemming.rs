@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+
+/// The stems are indexed with this prefix to not be confused with the words themselves,
+/// this way a document only containing `apples` does not exactly contain `apple`.
+const STEM_PREFIX: char = '\u{1}';
+
+/// A language whose words can be reduced to their stem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StemmingLanguage {
+    En,
+    Fr,
+}
+
+impl StemmingLanguage {
+    /// Reduces a normalized word to its stem, the inflections of a word share the same stem.
+    pub fn stem(&self, word: &str) -> String {
+        match self {
+            StemmingLanguage::En => stem_english(word),
+            StemmingLanguage::Fr => stem_french(word),
+        }
+    }
+}
+
+/// Returns the stems of a word in the given languages, prefixed to be indexed.
+///
+/// The stems equal to the word are omitted, the word itself being indexed.
+pub(crate) fn indexed_stems(languages: &[StemmingLanguage], word: &str) -> Vec<String> {
+    let mut stems = Vec::new();
+    for language in languages {
+        let stem = language.stem(word);
+        if stem != word {
+            let stem = format!("{STEM_PREFIX}{stem}");
+            if !stems.contains(&stem) {
+                stems.push(stem);
+            }
+        }
+    }
+    stems
+}
+
+/// Returns the words to search for to match the inflections of a query word.
+///
+/// The prefixed stems match the documents containing an inflection of the word,
+/// and the unprefixed ones the documents containing the stem as a word.
+pub(crate) fn query_stems(languages: &[StemmingLanguage], word: &str) -> Vec<String> {
+    let mut stems = Vec::new();
+    for language in languages {
+        let stem = language.stem(word);
+        let prefixed = format!("{STEM_PREFIX}{stem}");
+        if !stems.contains(&prefixed) {
+            stems.push(prefixed);
+        }
+        if stem != word && !stems.contains(&stem) {
+            stems.push(stem);
+        }
+    }
+    stems
+}
+
+/// Returns `true` if the indexed word is a stem and not a word of the documents.
+pub(crate) fn is_indexed_stem(word: &str) -> bool {
+    word.starts_with(STEM_PREFIX)
+}
+
+/// The first step of the Porter stemmer, removing the plurals and the `-ed` or `-ing` suffixes.
+fn stem_english(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+
+    let mut w = word.as_bytes().to_vec();
+
+    // Step 1a
+    if w.ends_with(b"sses") || w.ends_with(b"ies") {
+        w.truncate(w.len() - 2);
+    } else if w.ends_with(b"s") && !w.ends_with(b"ss") {
+        w.pop();
+    }
+
+    // Step 1b
+    if w.ends_with(b"eed") {
+        if measure(&w[..w.len() - 3]) > 0 {
+            w.pop();
+        }
+    } else {
+        let suffix_len = if w.ends_with(b"ed") {
+            2
+        } else if w.ends_with(b"ing") {
+            3
+        } else {
+            0
+        };
+
+        if suffix_len > 0 && contains_vowel(&w[..w.len() - suffix_len]) {
+            w.truncate(w.len() - suffix_len);
+            if w.ends_with(b"at") || w.ends_with(b"bl") || w.ends_with(b"iz") {
+                w.push(b'e');
+            } else if ends_with_double_consonant(&w)
+                && !matches!(w.last(), Some(b'l' | b's' | b'z'))
+            {
+                w.pop();
+            } else if measure(&w) == 1 && ends_with_cvc(&w) {
+                w.push(b'e');
+            }
+        }
+    }
+
+    // Step 1c
+    if w.ends_with(b"y") && contains_vowel(&w[..w.len() - 1]) {
+        w.pop();
+        w.push(b'i');
+    }
+
+    String::from_utf8(w).unwrap()
+}
+
+fn is_consonant(w: &[u8], i: usize) -> bool {
+    match w[i] {
+        b'a' | b'e' | b'i' | b'o' | b'u' => false,
+        b'y' => i == 0 || !is_consonant(w, i - 1),
+        _ => true,
+    }
+}
+
+/// The number of vowels-consonants sequences of the word.
+fn measure(w: &[u8]) -> usize {
+    let mut count = 0;
+    let mut previous_is_vowel = false;
+    for i in 0..w.len() {
+        let is_vowel = !is_consonant(w, i);
+        if previous_is_vowel && !is_vowel {
+            count += 1;
+        }
+        previous_is_vowel = is_vowel;
+    }
+    count
+}
+
+fn contains_vowel(w: &[u8]) -> bool {
+    (0..w.len()).any(|i| !is_consonant(w, i))
+}
+
+fn ends_with_double_consonant(w: &[u8]) -> bool {
+    let len = w.len();
+    len >= 2 && w[len - 1] == w[len - 2] && is_consonant(w, len - 1)
+}
+
+/// Whether the word ends with a consonant-vowel-consonant sequence,
+/// where the last consonant is not `w`, `x` or `y`.
+fn ends_with_cvc(w: &[u8]) -> bool {
+    let len = w.len();
+    len >= 3
+        && is_consonant(w, len - 3)
+        && !is_consonant(w, len - 2)
+        && is_consonant(w, len - 1)
+        && !matches!(w[len - 1], b'w' | b'x' | b'y')
+}
+
+/// A minimal French stemmer removing the plurals and the feminine forms.
+fn stem_french(word: &str) -> String {
+    let mut w: Vec<char> = word.chars().collect();
+    if w.len() < 6 || !w.iter().all(|c| c.is_alphabetic()) {
+        return word.to_string();
+    }
+
+    if w.last() == Some(&'x') {
+        // `chevaux` becomes `cheval`
+        if w[w.len() - 3] == 'a' && w[w.len() - 2] == 'u' {
+            w[w.len() - 2] = 'l';
+        }
+        w.pop();
+        return w.into_iter().collect();
+    }
+
+    for suffix in ['s', 'r', 'e', 'é'] {
+        if w.last() == Some(&suffix) {
+            w.pop();
+        }
+    }
+
+    let len = w.len();
+    if w[len - 1] == w[len - 2] {
+        w.pop();
+    }
+
+    w.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stem_english_words() {
+        let stem = |word| StemmingLanguage::En.stem(word);
+
+        assert_eq!(stem("apples"), "apple");
+        assert_eq!(stem("apple"), "apple");
+        assert_eq!(stem("caresses"), "caress");
+        assert_eq!(stem("ponies"), "poni");
+        assert_eq!(stem("pony"), "poni");
+        assert_eq!(stem("running"), "run");
+        assert_eq!(stem("runs"), "run");
+        assert_eq!(stem("hoping"), "hope");
+        assert_eq!(stem("agreed"), "agree");
+        assert_eq!(stem("falling"), "fall");
+        assert_eq!(stem("sing"), "sing");
+        assert_eq!(stem("café"), "café");
+    }
+
+    #[test]
+    fn stem_french_words() {
+        let stem = |word| StemmingLanguage::Fr.stem(word);
+
+        assert_eq!(stem("chevaux"), "cheval");
+        assert_eq!(stem("cheval"), "cheval");
+        assert_eq!(stem("maisons"), "maison");
+        assert_eq!(stem("chanteuse"), "chanteus");
+        assert_eq!(stem("chanteuses"), "chanteus");
+        assert_eq!(stem("chat"), "chat");
+    }
+
+    #[test]
+    fn indexed_and_query_stems() {
+        let languages = [StemmingLanguage::En];
+
+        assert_eq!(indexed_stems(&languages, "apples"), ["\u{1}apple"]);
+        assert!(indexed_stems(&languages, "apple").is_empty());
+        assert_eq!(query_stems(&languages, "apples"), ["\u{1}apple", "apple"]);
+        assert_eq!(query_stems(&languages, "apple"), ["\u{1}apple"]);
+        assert!(is_indexed_stem("\u{1}apple"));
+        assert!(!is_indexed_stem("apple"));
+    }
+}
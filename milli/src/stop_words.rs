@@ -0,0 +1,60 @@
+use std::collections::BTreeSet;
+
+use charabia::Normalize;
+use serde::{Deserialize, Serialize};
+
+use crate::normalization::{normalize_text, NormalizationParams};
+
+/// A built-in list of stop words for a given language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StopWordsPreset {
+    En,
+    Fr,
+    De,
+    Es,
+    It,
+    Pt,
+    Nl,
+}
+
+impl StopWordsPreset {
+    /// Returns the words of this preset, normalized the same way
+    /// as the user-defined stop words are before being stored.
+    pub fn words(&self, normalization: NormalizationParams) -> BTreeSet<String> {
+        let list = match self {
+            StopWordsPreset::En => include_str!("stop_words/en.txt"),
+            StopWordsPreset::Fr => include_str!("stop_words/fr.txt"),
+            StopWordsPreset::De => include_str!("stop_words/de.txt"),
+            StopWordsPreset::Es => include_str!("stop_words/es.txt"),
+            StopWordsPreset::It => include_str!("stop_words/it.txt"),
+            StopWordsPreset::Pt => include_str!("stop_words/pt.txt"),
+            StopWordsPreset::Nl => include_str!("stop_words/nl.txt"),
+        };
+
+        list.split_whitespace().map(|word| normalize_stop_word(word, normalization)).collect()
+    }
+}
+
+/// Apply an unlossy normalization on a stop word, with the normalization steps of the index.
+pub(crate) fn normalize_stop_word(word: &str, normalization: NormalizationParams) -> String {
+    if normalization == NormalizationParams::default() {
+        word.normalize(&Default::default()).into_owned()
+    } else {
+        normalize_text(word, normalization)
+    }
+}
+
+/// The stop words are indexed with this prefix to only be matched by the searches
+/// explicitly keeping the stop words of their query.
+const STOP_WORD_PREFIX: char = '\u{2}';
+
+/// Returns the stop word prefixed to be indexed.
+pub(crate) fn indexed_stop_word(word: &str) -> String {
+    format!("{STOP_WORD_PREFIX}{word}")
+}
+
+/// Returns `true` if the indexed word is a stop word and not a regular word of the documents.
+pub(crate) fn is_indexed_stop_word(word: &str) -> bool {
+    word.starts_with(STOP_WORD_PREFIX)
+}
@@ -62,7 +62,10 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
+        self.index.delete_geojson_rtree(self.wtxn)?;
+        self.index.delete_named_geo_rtrees(self.wtxn)?;
         self.index.delete_vector_hnsw(self.wtxn)?;
+        self.index.delete_binary_vector_hnsw(self.wtxn)?;
 
         // We clean all the faceted documents ids.
         for field_id in faceted_fields {
@@ -2,7 +2,7 @@ use std::collections::btree_map::Entry;
 use std::collections::{BTreeSet, HashMap, HashSet};
 
 use fst::IntoStreamer;
-use heed::types::{ByteSlice, DecodeIgnore, Str, UnalignedSlice};
+use heed::types::{ByteSlice, DecodeIgnore, OwnedType, Str, UnalignedSlice};
 use heed::{BytesDecode, BytesEncode, Database, RwIter};
 use instant_distance::PointId;
 use roaring::RoaringBitmap;
@@ -11,11 +11,11 @@ use time::OffsetDateTime;
 
 use super::facet::delete::FacetsDelete;
 use super::ClearDocuments;
+use crate::distance::{VectorIndexParams, VectorPoint};
 use crate::error::InternalError;
 use crate::facet::FacetType;
 use crate::heed_codec::facet::FieldDocIdFacetCodec;
 use crate::heed_codec::CboRoaringBitmapCodec;
-use crate::index::Hnsw;
 use crate::{
     ExternalDocumentsIds, FieldId, FieldIdMapMissingEntry, Index, Result, RoaringBitmapCodec, BEU32,
 };
@@ -381,6 +381,35 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             self.index.put_geo_faceted_documents_ids(self.wtxn, &geo_faceted_doc_ids)?;
         }
 
+        if let Some(mut rtree) = self.index.geojson_rtree(self.wtxn)? {
+            let shapes_to_remove: Vec<_> = rtree
+                .iter()
+                .filter(|&shape| self.to_delete_docids.contains(shape.data))
+                .cloned()
+                .collect();
+            shapes_to_remove.iter().for_each(|shape| {
+                rtree.remove(shape);
+            });
+
+            self.index.put_geojson_rtree(self.wtxn, &rtree)?;
+        }
+
+        let mut named_geo_rtrees = self.index.named_geo_rtrees(self.wtxn)?;
+        if !named_geo_rtrees.is_empty() {
+            for rtree in named_geo_rtrees.values_mut() {
+                let points_to_remove: Vec<_> = rtree
+                    .iter()
+                    .filter(|&point| self.to_delete_docids.contains(point.data.0))
+                    .cloned()
+                    .collect();
+                points_to_remove.iter().for_each(|point| {
+                    rtree.remove(point);
+                });
+            }
+
+            self.index.put_named_geo_rtrees(self.wtxn, &named_geo_rtrees)?;
+        }
+
         for facet_type in [FacetType::Number, FacetType::String] {
             let mut affected_facet_values = HashMap::new();
             for field_id in self.index.faceted_fields_ids(self.wtxn)? {
@@ -436,29 +465,27 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             &self.to_delete_docids,
         )?;
 
-        // An ugly and slow way to remove the vectors from the HNSW
-        // It basically reconstructs the HNSW from scratch without editing the current one.
+        let vector_index_params = self.index.vector_index_params(self.wtxn)?;
         if let Some(current_hnsw) = self.index.vector_hnsw(self.wtxn)? {
-            let mut points = Vec::new();
-            let mut docids = Vec::new();
-            for result in vector_id_docid.iter(self.wtxn)? {
-                let (vector_id, docid) = result?;
-                if !self.to_delete_docids.contains(docid.get()) {
-                    let pid = PointId::from(vector_id.get());
-                    let vector = current_hnsw[pid].clone();
-                    points.push(vector);
-                    docids.push(docid);
-                }
-            }
-
-            let (new_hnsw, pids) = Hnsw::builder().build_hnsw(points);
-
-            vector_id_docid.clear(self.wtxn)?;
-            for (pid, docid) in pids.into_iter().zip(docids) {
-                vector_id_docid.put(self.wtxn, &BEU32::new(pid.into_inner()), &docid)?;
-            }
+            let new_hnsw = remove_vector_points(
+                self.wtxn,
+                *vector_id_docid,
+                &current_hnsw,
+                &self.to_delete_docids,
+                vector_index_params,
+            )?;
             self.index.put_vector_hnsw(self.wtxn, &new_hnsw)?;
         }
+        if let Some(current_hnsw) = self.index.binary_vector_hnsw(self.wtxn)? {
+            let new_hnsw = remove_vector_points(
+                self.wtxn,
+                *vector_id_docid,
+                &current_hnsw,
+                &self.to_delete_docids,
+                vector_index_params,
+            )?;
+            self.index.put_binary_vector_hnsw(self.wtxn, &new_hnsw)?;
+        }
 
         self.index.put_soft_deleted_documents_ids(self.wtxn, &RoaringBitmap::new())?;
 
@@ -493,6 +520,37 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
     }
 }
 
+/// An ugly and slow way to remove the vectors from the HNSW
+/// It basically reconstructs the HNSW from scratch without editing the current one.
+fn remove_vector_points<P: VectorPoint>(
+    wtxn: &mut heed::RwTxn,
+    vector_id_docid: Database<OwnedType<BEU32>, OwnedType<BEU32>>,
+    current_hnsw: &instant_distance::Hnsw<P>,
+    to_delete_docids: &RoaringBitmap,
+    params: VectorIndexParams,
+) -> Result<instant_distance::Hnsw<P>> {
+    let mut points = Vec::new();
+    let mut docids = Vec::new();
+    for result in vector_id_docid.iter(wtxn)? {
+        let (vector_id, docid) = result?;
+        if !to_delete_docids.contains(docid.get()) {
+            let pid = PointId::from(vector_id.get());
+            let vector = current_hnsw[pid].clone();
+            points.push(vector);
+            docids.push(docid);
+        }
+    }
+
+    let (new_hnsw, pids) = params.build_hnsw(points);
+
+    vector_id_docid.clear(wtxn)?;
+    for (pid, docid) in pids.into_iter().zip(docids) {
+        vector_id_docid.put(wtxn, &BEU32::new(pid.into_inner()), &docid)?;
+    }
+
+    Ok(new_hnsw)
+}
+
 fn remove_from_word_prefix_docids(
     txn: &mut heed::RwTxn,
     db: &Database<Str, RoaringBitmapCodec>,
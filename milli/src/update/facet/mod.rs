@@ -166,93 +166,103 @@ impl<'i> FacetsUpdate<'i> {
             incremental_update.execute(wtxn)?;
         }
 
-        // We clear the list of normalized-for-search facets
-        // and the previous FSTs to compute everything from scratch
-        self.index.facet_id_normalized_string_strings.clear(wtxn)?;
-        self.index.facet_id_string_fst.clear(wtxn)?;
-
-        // As we can't use the same write transaction to read and write in two different databases
-        // we must create a temporary sorter that we will write into LMDB afterward.
-        // As multiple unnormalized facet values can become the same normalized facet value
-        // we must merge them together.
-        let mut sorter = create_sorter(
-            SortAlgorithm::Unstable,
-            merge_btreeset_string,
-            CompressionType::None,
-            None,
-            None,
-            None,
-        );
-
-        // We iterate on the list of original, semi-normalized, facet values
-        // and normalize them for search, inserting them in LMDB in any given order.
-        let options = NormalizerOption { lossy: true, ..Default::default() };
-        let database = self.index.facet_id_string_docids.remap_data_type::<DecodeIgnore>();
-        for result in database.iter(wtxn)? {
-            let (facet_group_key, ()) = result?;
-            if let FacetGroupKey { field_id, level: 0, left_bound } = facet_group_key {
-                let mut normalized_facet = left_bound.normalize(&options);
-                let normalized_truncated_facet: String;
-                if normalized_facet.len() > MAX_FACET_VALUE_LENGTH {
-                    normalized_truncated_facet = normalized_facet
-                        .char_indices()
-                        .take_while(|(idx, _)| *idx < MAX_FACET_VALUE_LENGTH)
-                        .map(|(_, c)| c)
-                        .collect();
-                    normalized_facet = normalized_truncated_facet.into();
-                }
-                let set = BTreeSet::from_iter(std::iter::once(left_bound));
-                let key = (field_id, normalized_facet.as_ref());
-                let key = BEU16StrCodec::bytes_encode(&key).ok_or(heed::Error::Encoding)?;
-                let val = SerdeJson::bytes_encode(&set).ok_or(heed::Error::Encoding)?;
-                sorter.insert(key, val)?;
-            }
+        if !self.index.facet_search(wtxn)? {
+            return Ok(());
         }
 
-        // In this loop we don't need to take care of merging bitmaps
-        // as the grenad sorter already merged them for us.
-        let mut merger_iter = sorter.into_stream_merger_iter()?;
-        while let Some((key_bytes, btreeset_bytes)) = merger_iter.next()? {
-            self.index
-                .facet_id_normalized_string_strings
-                .remap_types::<ByteSlice, ByteSlice>()
-                .put(wtxn, key_bytes, btreeset_bytes)?;
+        index_facet_search(wtxn, self.index)
+    }
+}
+
+/// Builds the normalized facet strings and the FSTs used by the facet search
+/// from the string facet values of the index.
+pub(crate) fn index_facet_search(wtxn: &mut heed::RwTxn, index: &Index) -> Result<()> {
+    // We clear the list of normalized-for-search facets
+    // and the previous FSTs to compute everything from scratch
+    index.facet_id_normalized_string_strings.clear(wtxn)?;
+    index.facet_id_string_fst.clear(wtxn)?;
+
+    // As we can't use the same write transaction to read and write in two different databases
+    // we must create a temporary sorter that we will write into LMDB afterward.
+    // As multiple unnormalized facet values can become the same normalized facet value
+    // we must merge them together.
+    let mut sorter = create_sorter(
+        SortAlgorithm::Unstable,
+        merge_btreeset_string,
+        CompressionType::None,
+        None,
+        None,
+        None,
+    );
+
+    // We iterate on the list of original, semi-normalized, facet values
+    // and normalize them for search, inserting them in LMDB in any given order.
+    let options = NormalizerOption { lossy: true, ..Default::default() };
+    let database = index.facet_id_string_docids.remap_data_type::<DecodeIgnore>();
+    for result in database.iter(wtxn)? {
+        let (facet_group_key, ()) = result?;
+        if let FacetGroupKey { field_id, level: 0, left_bound } = facet_group_key {
+            let mut normalized_facet = left_bound.normalize(&options);
+            let normalized_truncated_facet: String;
+            if normalized_facet.len() > MAX_FACET_VALUE_LENGTH {
+                normalized_truncated_facet = normalized_facet
+                    .char_indices()
+                    .take_while(|(idx, _)| *idx < MAX_FACET_VALUE_LENGTH)
+                    .map(|(_, c)| c)
+                    .collect();
+                normalized_facet = normalized_truncated_facet.into();
+            }
+            let set = BTreeSet::from_iter(std::iter::once(left_bound));
+            let key = (field_id, normalized_facet.as_ref());
+            let key = BEU16StrCodec::bytes_encode(&key).ok_or(heed::Error::Encoding)?;
+            let val = SerdeJson::bytes_encode(&set).ok_or(heed::Error::Encoding)?;
+            sorter.insert(key, val)?;
         }
+    }
 
-        // We compute one FST by string facet
-        let mut text_fsts = vec![];
-        let mut current_fst: Option<(u16, fst::SetBuilder<Vec<u8>>)> = None;
-        let database =
-            self.index.facet_id_normalized_string_strings.remap_data_type::<DecodeIgnore>();
-        for result in database.iter(wtxn)? {
-            let ((field_id, normalized_facet), _) = result?;
-            current_fst = match current_fst.take() {
-                Some((fid, fst_builder)) if fid != field_id => {
-                    let fst = fst_builder.into_set();
-                    text_fsts.push((fid, fst));
-                    Some((field_id, fst::SetBuilder::memory()))
-                }
-                Some((field_id, fst_builder)) => Some((field_id, fst_builder)),
-                None => Some((field_id, fst::SetBuilder::memory())),
-            };
+    // In this loop we don't need to take care of merging bitmaps
+    // as the grenad sorter already merged them for us.
+    let mut merger_iter = sorter.into_stream_merger_iter()?;
+    while let Some((key_bytes, btreeset_bytes)) = merger_iter.next()? {
+        index.facet_id_normalized_string_strings.remap_types::<ByteSlice, ByteSlice>().put(
+            wtxn,
+            key_bytes,
+            btreeset_bytes,
+        )?;
+    }
 
-            if let Some((_, fst_builder)) = current_fst.as_mut() {
-                fst_builder.insert(normalized_facet)?;
+    // We compute one FST by string facet
+    let mut text_fsts = vec![];
+    let mut current_fst: Option<(u16, fst::SetBuilder<Vec<u8>>)> = None;
+    let database = index.facet_id_normalized_string_strings.remap_data_type::<DecodeIgnore>();
+    for result in database.iter(wtxn)? {
+        let ((field_id, normalized_facet), _) = result?;
+        current_fst = match current_fst.take() {
+            Some((fid, fst_builder)) if fid != field_id => {
+                let fst = fst_builder.into_set();
+                text_fsts.push((fid, fst));
+                Some((field_id, fst::SetBuilder::memory()))
             }
-        }
+            Some((field_id, fst_builder)) => Some((field_id, fst_builder)),
+            None => Some((field_id, fst::SetBuilder::memory())),
+        };
 
-        if let Some((field_id, fst_builder)) = current_fst {
-            let fst = fst_builder.into_set();
-            text_fsts.push((field_id, fst));
+        if let Some((_, fst_builder)) = current_fst.as_mut() {
+            fst_builder.insert(normalized_facet)?;
         }
+    }
 
-        // We write those FSTs in LMDB now
-        for (field_id, fst) in text_fsts {
-            self.index.facet_id_string_fst.put(wtxn, &BEU16::new(field_id), &fst)?;
-        }
+    if let Some((field_id, fst_builder)) = current_fst {
+        let fst = fst_builder.into_set();
+        text_fsts.push((field_id, fst));
+    }
 
-        Ok(())
+    // We write those FSTs in LMDB now
+    for (field_id, fst) in text_fsts {
+        index.facet_id_string_fst.put(wtxn, &BEU16::new(field_id), &fst)?;
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::io::{Read, Seek};
 use std::result::Result as StdResult;
 use std::{fmt, iter};
@@ -5,10 +6,12 @@ use std::{fmt, iter};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::transform::check_document_limits;
 use crate::documents::{DocumentsBatchIndex, DocumentsBatchReader, EnrichedDocumentsBatchReader};
 use crate::error::{GeoError, InternalError, UserError};
 use crate::update::index_documents::{obkv_to_object, writer_into_reader};
-use crate::{FieldId, Index, Object, Result};
+use crate::update::{DuplicateIdsPolicy, IndexerConfig};
+use crate::{Error, FieldId, Index, Object, Result};
 
 /// The symbol used to define levels in a nested primary key.
 const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
@@ -60,21 +63,7 @@ pub fn enrich_documents_batch<R: Read + Seek>(
             }
         },
         None => {
-            let mut guesses: Vec<(u16, &str)> = documents_batch_index
-                .iter()
-                .filter(|(_, name)| name.to_lowercase().ends_with(DEFAULT_PRIMARY_KEY))
-                .map(|(field_id, name)| (*field_id, name.as_str()))
-                .collect();
-
-            // sort the keys in a deterministic, obvious way, so that fields are always in the same order.
-            guesses.sort_by(|(_, left_name), (_, right_name)| {
-                // shortest name first
-                left_name.len().cmp(&right_name.len()).then_with(
-                    // then alphabetical order
-                    || left_name.cmp(right_name),
-                )
-            });
-
+            let guesses = primary_key_guesses(&documents_batch_index);
             match guesses.as_slice() {
                 [] if autogenerate_docids => PrimaryKey::flat(
                     DEFAULT_PRIMARY_KEY,
@@ -146,6 +135,172 @@ pub fn enrich_documents_batch<R: Read + Seek>(
     Ok(Ok(reader))
 }
 
+/// The reason why a document of a payload would be rejected by the indexation.
+#[derive(Debug)]
+pub struct DocumentValidationError {
+    /// The position of the document in the payload, starting at zero.
+    pub position: u32,
+    /// The id of the document, unless it is the reason of the error.
+    pub document_id: Option<String>,
+    pub error: UserError,
+}
+
+/// The report of the validation of a payload, see [`validate_documents_batch`].
+#[derive(Debug, Default)]
+pub struct DocumentsValidation {
+    pub invalid_documents: u64,
+    /// The errors of the first invalid documents.
+    pub errors: Vec<DocumentValidationError>,
+}
+
+/// This function checks the documents of a payload like they are when indexed, but it
+/// reports the error of every invalid document instead of stopping at the first one and
+/// never writes anything. Only the first `max_errors` errors are kept in the report.
+/// The documents sharing an id are only invalid when the `duplicate_ids_policy` rejects them.
+///
+/// The `index` is `None` if it doesn't exist yet, the primary key is then the given one
+/// or guessed from the documents, and the `_geo` field isn't checked.
+pub fn validate_documents_batch<R: Read + Seek>(
+    index: Option<(&heed::RoTxn, &Index)>,
+    primary_key: Option<&str>,
+    indexer_config: &IndexerConfig,
+    duplicate_ids_policy: DuplicateIdsPolicy,
+    max_errors: usize,
+    reader: DocumentsBatchReader<R>,
+) -> Result<StdResult<DocumentsValidation, UserError>> {
+    puffin::profile_function!();
+
+    let (mut cursor, mut documents_batch_index) = reader.into_cursor_and_fields_index();
+    let mut uuid_buffer = [0; uuid::fmt::Hyphenated::LENGTH];
+
+    let index_primary_key = match index {
+        Some((rtxn, index)) => index.primary_key(rtxn)?,
+        None => None,
+    };
+    let primary_key_name = match (index_primary_key, primary_key) {
+        (Some(index_primary_key), Some(primary_key)) if index_primary_key != primary_key => {
+            return Ok(Err(UserError::PrimaryKeyCannotBeChanged(index_primary_key.to_string())));
+        }
+        (Some(primary_key), _) | (None, Some(primary_key)) => primary_key.to_string(),
+        (None, None) => match primary_key_guesses(&documents_batch_index).as_slice() {
+            [] => return Ok(Err(UserError::NoPrimaryKeyCandidateFound)),
+            [(_, name)] => name.to_string(),
+            multiple => {
+                return Ok(Err(UserError::MultiplePrimaryKeyCandidatesFound {
+                    candidates: multiple
+                        .iter()
+                        .map(|(_, candidate)| candidate.to_string())
+                        .collect(),
+                }));
+            }
+        },
+    };
+
+    // the documents without the primary key are reported one by one.
+    let primary_key = if primary_key_name.contains(PRIMARY_KEY_SPLIT_SYMBOL) {
+        PrimaryKey::nested(&primary_key_name)
+    } else {
+        let field_id = match documents_batch_index.id(&primary_key_name) {
+            Some(field_id) => field_id,
+            None => documents_batch_index.insert(&primary_key_name),
+        };
+        PrimaryKey::flat(&primary_key_name, field_id)
+    };
+
+    let geo_field_id = match (documents_batch_index.id("_geo"), index) {
+        (Some(geo_field_id), Some((rtxn, index)))
+            if index.sortable_fields(rtxn)?.contains("_geo")
+                || index.filterable_fields(rtxn)?.contains("_geo") =>
+        {
+            Some(geo_field_id)
+        }
+        _otherwise => None,
+    };
+
+    let reject_duplicates = duplicate_ids_policy == DuplicateIdsPolicy::Reject;
+    let mut seen_ids = HashSet::new();
+    let mut validation = DocumentsValidation::default();
+
+    let mut position = 0;
+    while let Some(document) = cursor.next_document()? {
+        let (document_id, result) = match fetch_or_generate_document_id(
+            &document,
+            &documents_batch_index,
+            primary_key,
+            false,
+            &mut uuid_buffer,
+            position,
+        )? {
+            Ok(document_id) => {
+                let result =
+                    validate_document(&document, &document_id, geo_field_id, indexer_config)?;
+                let document_id = document_id.value().to_string();
+                let result = match result {
+                    Ok(()) if reject_duplicates && !seen_ids.insert(document_id.clone()) => {
+                        Err(UserError::DuplicateDocumentIds {
+                            document_ids: vec![document_id.clone()],
+                        })
+                    }
+                    result => result,
+                };
+                (Some(document_id), result)
+            }
+            Err(user_error) => (None, Err(user_error)),
+        };
+
+        if let Err(error) = result {
+            validation.invalid_documents += 1;
+            if validation.errors.len() < max_errors {
+                validation.errors.push(DocumentValidationError { position, document_id, error });
+            }
+        }
+
+        position += 1;
+    }
+
+    Ok(Ok(validation))
+}
+
+/// Checks the `_geo` field, when it must be, and the limits of a document whose id is valid.
+fn validate_document(
+    document: &obkv::KvReader<FieldId>,
+    document_id: &DocumentId,
+    geo_field_id: Option<FieldId>,
+    indexer_config: &IndexerConfig,
+) -> Result<StdResult<(), UserError>> {
+    if let Some(geo_value) = geo_field_id.and_then(|fid| document.get(fid)) {
+        if let Err(user_error) = validate_geo_from_json(document_id, geo_value)? {
+            return Ok(Err(UserError::from(user_error)));
+        }
+    }
+
+    match check_document_limits(indexer_config, document_id.value(), document) {
+        Ok(()) => Ok(Ok(())),
+        Err(Error::UserError(user_error)) => Ok(Err(user_error)),
+        Err(error) => Err(error),
+    }
+}
+
+/// Returns the fields that can be the primary key of the documents, in a deterministic,
+/// obvious way, so that fields are always in the same order.
+fn primary_key_guesses(documents_batch_index: &DocumentsBatchIndex) -> Vec<(FieldId, &str)> {
+    let mut guesses: Vec<(FieldId, &str)> = documents_batch_index
+        .iter()
+        .filter(|(_, name)| name.to_lowercase().ends_with(DEFAULT_PRIMARY_KEY))
+        .map(|(field_id, name)| (*field_id, name.as_str()))
+        .collect();
+
+    guesses.sort_by(|(_, left_name), (_, right_name)| {
+        // shortest name first
+        left_name.len().cmp(&right_name.len()).then_with(
+            // then alphabetical order
+            || left_name.cmp(right_name),
+        )
+    });
+
+    guesses
+}
+
 /// Retrieve the document id after validating it, returning a `UserError`
 /// if the id is invalid or can't be guessed.
 fn fetch_or_generate_document_id(
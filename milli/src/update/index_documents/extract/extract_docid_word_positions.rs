@@ -10,6 +10,11 @@ use serde_json::Value;
 
 use super::helpers::{concat_u32s_array, create_sorter, sorter_into_reader, GrenadParameters};
 use crate::error::{InternalError, SerializationError};
+use crate::localized_attributes_rules::locales_allow_list;
+use crate::normalization::{normalize_words, NormalizationParams};
+use crate::number_normalization;
+use crate::stemming::{self, StemmingLanguage};
+use crate::stop_words::indexed_stop_word;
 use crate::update::index_documents::MergeFn;
 use crate::{
     absolute_from_relative_position, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
@@ -27,9 +32,14 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
     searchable_fields: &Option<HashSet<FieldId>>,
+    localized_fields: &HashMap<FieldId, Vec<Language>>,
     stop_words: Option<&fst::Set<&[u8]>>,
+    index_stop_words: bool,
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
+    normalize_numbers: bool,
+    normalization: NormalizationParams,
+    stemming_languages: &[StemmingLanguage],
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(RoaringBitmap, grenad::Reader<File>, ScriptLanguageDocidsMap)> {
     puffin::profile_function!();
@@ -62,6 +72,33 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     }
     let tokenizer = tokenizer_builder.build();
 
+    // build a tokenizer for every field forcing its locales.
+    let localized_allow_lists: HashMap<_, _> = localized_fields
+        .iter()
+        .map(|(field_id, locales)| (*field_id, locales_allow_list(locales)))
+        .collect();
+    let mut localized_tokenizer_builders: HashMap<_, _> = localized_allow_lists
+        .iter()
+        .map(|(field_id, allow_list)| {
+            let mut tokenizer_builder = TokenizerBuilder::new();
+            if let Some(stop_words) = stop_words {
+                tokenizer_builder.stop_words(stop_words);
+            }
+            if let Some(dictionary) = dictionary {
+                tokenizer_builder.words_dict(dictionary);
+            }
+            if let Some(separators) = allowed_separators {
+                tokenizer_builder.separators(separators);
+            }
+            tokenizer_builder.allow_list(allow_list);
+            (*field_id, tokenizer_builder)
+        })
+        .collect();
+    let localized_tokenizers: HashMap<_, _> = localized_tokenizer_builders
+        .iter_mut()
+        .map(|(field_id, tokenizer_builder)| (*field_id, tokenizer_builder.build()))
+        .collect();
+
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
         let document_id = key
@@ -80,6 +117,12 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
             &obkv,
             searchable_fields,
             &tokenizer,
+            &localized_tokenizers,
+            stop_words,
+            index_stop_words,
+            normalize_numbers,
+            normalization,
+            stemming_languages,
             max_positions_per_attributes,
             &mut buffers,
             &mut script_language_word_count,
@@ -116,6 +159,12 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     searchable_fields,
                     &tokenizer,
+                    &localized_tokenizers,
+                    stop_words,
+                    index_stop_words,
+                    normalize_numbers,
+                    normalization,
+                    stemming_languages,
                     max_positions_per_attributes,
                     &mut buffers,
                     &mut script_language_word_count,
@@ -142,6 +191,12 @@ fn extract_tokens_from_document(
     obkv: &KvReader<FieldId>,
     searchable_fields: &Option<HashSet<FieldId>>,
     tokenizer: &Tokenizer,
+    localized_tokenizers: &HashMap<FieldId, Tokenizer>,
+    stop_words: Option<&fst::Set<&[u8]>>,
+    index_stop_words: bool,
+    normalize_numbers: bool,
+    normalization: NormalizationParams,
+    stemming_languages: &[StemmingLanguage],
     max_positions_per_attributes: u32,
     buffers: &mut Buffers,
     script_language_word_count: &mut HashMap<Script, Vec<(Language, usize)>>,
@@ -152,10 +207,33 @@ fn extract_tokens_from_document(
             let value = serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
             buffers.field_buffer.clear();
             if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
-                let tokens = process_tokens(tokenizer.tokenize(field))
+                // the fields matching a localized attributes rule use their own tokenizer.
+                let tokenizer = localized_tokenizers.get(&field_id).unwrap_or(tokenizer);
+                let tokens = tokenizer.tokenize(field);
+                let tokens = normalize_words(field, tokens, normalization, stop_words);
+                let tokens = number_normalization::normalize_numbers(tokens, normalize_numbers);
+                let tokens = process_tokens(tokens, index_stop_words)
                     .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
                 for (index, token) in tokens {
+                    // the stop words are indexed prefixed when asked, to only be matched by the
+                    // searches keeping them.
+                    if token.is_stopword() {
+                        let word = indexed_stop_word(token.lemma().trim());
+                        if word.len() > 1 && word.len() <= MAX_WORD_LENGTH {
+                            buffers.key_buffer.truncate(mem::size_of::<u32>());
+                            buffers.key_buffer.extend_from_slice(word.as_bytes());
+
+                            let position: u16 = index
+                                .try_into()
+                                .map_err(|_| SerializationError::InvalidNumberSerialization)?;
+                            let position = absolute_from_relative_position(field_id, position);
+                            docid_word_positions_sorter
+                                .insert(&buffers.key_buffer, position.to_ne_bytes())?;
+                        }
+                        continue;
+                    }
+
                     // if a language has been detected for the token, we update the counter.
                     if let Some(language) = token.language {
                         let script = token.script;
@@ -177,6 +255,16 @@ fn extract_tokens_from_document(
                         let position = absolute_from_relative_position(field_id, position);
                         docid_word_positions_sorter
                             .insert(&buffers.key_buffer, position.to_ne_bytes())?;
+
+                        // the stems are indexed at the position of the word they come from.
+                        for stem in stemming::indexed_stems(stemming_languages, token) {
+                            if stem.len() <= MAX_WORD_LENGTH {
+                                buffers.key_buffer.truncate(mem::size_of::<u32>());
+                                buffers.key_buffer.extend_from_slice(stem.as_bytes());
+                                docid_word_positions_sorter
+                                    .insert(&buffers.key_buffer, position.to_ne_bytes())?;
+                            }
+                        }
                     }
                 }
             }
@@ -221,8 +309,11 @@ fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a st
 /// take an iterator on tokens and compute their relative position depending on separator kinds
 /// if it's an `Hard` separator we add an additional relative proximity of 8 between words,
 /// else we keep the standard proximity of 1 between words.
+///
+/// The stop words are only kept when they must be indexed.
 fn process_tokens<'a>(
     tokens: impl Iterator<Item = Token<'a>>,
+    index_stop_words: bool,
 ) -> impl Iterator<Item = (usize, Token<'a>)> {
     tokens
         .skip_while(|token| token.is_separator())
@@ -248,7 +339,7 @@ fn process_tokens<'a>(
             }
             Some((*offset, token))
         })
-        .filter(|(_, t)| t.is_word())
+        .filter(move |(_, t)| t.is_word() || (index_stop_words && t.is_stopword()))
 }
 
 fn potential_language_detection_error(languages_frequency: &[(Language, usize)]) -> bool {
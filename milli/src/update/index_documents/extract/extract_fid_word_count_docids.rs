@@ -10,6 +10,8 @@ use super::helpers::{
 };
 use crate::error::SerializationError;
 use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::stemming::is_indexed_stem;
+use crate::stop_words::is_indexed_stop_word;
 use crate::{relative_from_absolute_position, DocumentId, FieldId, Result};
 
 /// Extracts the field id word count and the documents ids where
@@ -41,7 +43,7 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
 
     let mut cursor = docid_word_positions.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
-        let (document_id_bytes, _word_bytes) = try_split_array_at(key)
+        let (document_id_bytes, word_bytes) = try_split_array_at(key)
             .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
         let document_id = u32::from_be_bytes(document_id_bytes);
 
@@ -55,6 +57,14 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
             current_document_id = Some(document_id);
         }
 
+        // the stems share the positions of their words and must not be counted twice,
+        // and the stop words are not counted as words of the attribute.
+        if std::str::from_utf8(word_bytes)
+            .map_or(false, |word| is_indexed_stem(word) || is_indexed_stop_word(word))
+        {
+            continue;
+        }
+
         for position in read_u32_ne_bytes(value) {
             let (field_id, _) = relative_from_absolute_position(position);
 
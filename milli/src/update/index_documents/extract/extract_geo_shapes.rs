@@ -0,0 +1,69 @@
+use std::fs::File;
+use std::io;
+
+use concat_arrays::concat_arrays;
+use serde_json::Value;
+
+use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use crate::error::GeoError;
+use crate::geo_shape::GeoShape;
+use crate::{FieldId, InternalError, Result};
+
+/// Extracts the GeoJSON geometry contained in each document under the `_geojson` field.
+///
+/// Returns the generated grenad reader containing the docid as key associated to the
+/// bounding box of the geometry (bottom latitude, left longitude, top latitude, right longitude)
+#[logging_timer::time]
+pub fn extract_geo_shapes<R: io::Read + io::Seek>(
+    obkv_documents: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    primary_key_id: FieldId,
+    geojson_fid: FieldId,
+) -> Result<grenad::Reader<File>> {
+    puffin::profile_function!();
+
+    let mut writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+
+    let mut cursor = obkv_documents.into_cursor()?;
+    while let Some((docid_bytes, value)) = cursor.move_on_next()? {
+        let obkv = obkv::KvReader::new(value);
+        // since we only needs the primary key when we throw an error we create this getter to
+        // lazily get it when needed
+        let document_id = || -> Value {
+            let document_id = obkv.get(primary_key_id).unwrap();
+            serde_json::from_slice(document_id).unwrap()
+        };
+
+        let geojson: Value = match obkv.get(geojson_fid) {
+            Some(geojson) => serde_json::from_slice(geojson).map_err(InternalError::SerdeJson)?,
+            None => continue,
+        };
+        // the _geojson object was `null`, there is nothing to do
+        if geojson.is_null() {
+            continue;
+        }
+
+        let shape = match GeoShape::from_geojson(&geojson) {
+            Some(shape) => shape,
+            None => {
+                return Err(GeoError::BadGeoJson { document_id: document_id(), value: geojson })?
+            }
+        };
+
+        let ([bottom, left], [top, right]) = shape.bounding_box();
+        #[allow(clippy::drop_non_drop)]
+        let bytes: [u8; 32] = concat_arrays![
+            bottom.to_ne_bytes(),
+            left.to_ne_bytes(),
+            top.to_ne_bytes(),
+            right.to_ne_bytes()
+        ];
+        writer.insert(docid_bytes, bytes)?;
+    }
+
+    writer_into_reader(writer)
+}
@@ -4,15 +4,17 @@ mod extract_facet_string_docids;
 mod extract_fid_docid_facet_values;
 mod extract_fid_word_count_docids;
 mod extract_geo_points;
+mod extract_geo_shapes;
 mod extract_vector_points;
 mod extract_word_docids;
 mod extract_word_fid_docids;
 mod extract_word_pair_proximity_docids;
 mod extract_word_position_docids;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 
+use charabia::Language;
 use crossbeam_channel::Sender;
 use log::debug;
 use rayon::prelude::*;
@@ -23,6 +25,7 @@ use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
 use self::extract_geo_points::extract_geo_points;
+use self::extract_geo_shapes::extract_geo_shapes;
 use self::extract_vector_points::extract_vector_points;
 use self::extract_word_docids::extract_word_docids;
 use self::extract_word_fid_docids::extract_word_fid_docids;
@@ -33,6 +36,9 @@ use super::helpers::{
     GrenadParameters, MergeFn, MergeableReader,
 };
 use super::{helpers, TypedChunk};
+use crate::normalization::NormalizationParams;
+use crate::proximity::ProximityPrecision;
+use crate::stemming::StemmingLanguage;
 use crate::{FieldId, Result};
 
 /// Extract data for each databases from obkv documents in parallel.
@@ -44,15 +50,23 @@ pub(crate) fn data_from_obkv_documents(
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     searchable_fields: Option<HashSet<FieldId>>,
+    localized_fields: HashMap<FieldId, Vec<Language>>,
     faceted_fields: HashSet<FieldId>,
     primary_key_id: FieldId,
     geo_fields_ids: Option<(FieldId, FieldId)>,
+    named_geo_fields_ids: &[(String, (FieldId, FieldId))],
+    geojson_field_id: Option<FieldId>,
     vectors_field_id: Option<FieldId>,
     stop_words: Option<fst::Set<&[u8]>>,
+    index_stop_words: bool,
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
+    normalize_numbers: bool,
+    normalization: NormalizationParams,
+    stemming_languages: &[StemmingLanguage],
     max_positions_per_attributes: Option<u32>,
     exact_attributes: HashSet<FieldId>,
+    proximity_precision: ProximityPrecision,
 ) -> Result<()> {
     puffin::profile_function!();
 
@@ -63,6 +77,7 @@ pub(crate) fn data_from_obkv_documents(
                 original_documents_chunk,
                 indexer,
                 lmdb_writer_sx.clone(),
+                geojson_field_id,
                 vectors_field_id,
                 primary_key_id,
             )
@@ -79,12 +94,18 @@ pub(crate) fn data_from_obkv_documents(
                     indexer,
                     lmdb_writer_sx.clone(),
                     &searchable_fields,
+                    &localized_fields,
                     &faceted_fields,
                     primary_key_id,
                     geo_fields_ids,
+                    named_geo_fields_ids,
                     &stop_words,
+                    index_stop_words,
                     &allowed_separators,
                     &dictionary,
+                    normalize_numbers,
+                    normalization,
+                    stemming_languages,
                     max_positions_per_attributes,
                 )
             })
@@ -152,15 +173,19 @@ pub(crate) fn data_from_obkv_documents(
         });
     }
 
-    spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
-        docid_word_positions_chunks.clone(),
-        indexer,
-        lmdb_writer_sx.clone(),
-        extract_word_pair_proximity_docids,
-        merge_cbo_roaring_bitmaps,
-        TypedChunk::WordPairProximityDocids,
-        "word-pair-proximity-docids",
-    );
+    // the word pair proximities are only stored when they are computed by word,
+    // otherwise they are deduced from the word fid docids at search time.
+    if proximity_precision == ProximityPrecision::ByWord {
+        spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
+            docid_word_positions_chunks.clone(),
+            indexer,
+            lmdb_writer_sx.clone(),
+            extract_word_pair_proximity_docids,
+            merge_cbo_roaring_bitmaps,
+            TypedChunk::WordPairProximityDocids,
+            "word-pair-proximity-docids",
+        );
+    }
 
     spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
         docid_word_positions_chunks.clone(),
@@ -272,12 +297,31 @@ fn send_original_documents_data(
     original_documents_chunk: Result<grenad::Reader<File>>,
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
+    geojson_field_id: Option<FieldId>,
     vectors_field_id: Option<FieldId>,
     primary_key_id: FieldId,
 ) -> Result<()> {
     let original_documents_chunk =
         original_documents_chunk.and_then(|c| unsafe { as_cloneable_grenad(&c) })?;
 
+    // the shapes are read from the original documents to not be flattened
+    if let Some(geojson_field_id) = geojson_field_id {
+        let documents_chunk_cloned = original_documents_chunk.clone();
+        let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        rayon::spawn(move || {
+            let result = extract_geo_shapes(
+                documents_chunk_cloned,
+                indexer,
+                primary_key_id,
+                geojson_field_id,
+            );
+            let _ = match result {
+                Ok(geo_shapes) => lmdb_writer_sx_cloned.send(Ok(TypedChunk::GeoShapes(geo_shapes))),
+                Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
+            };
+        });
+    }
+
     if let Some(vectors_field_id) = vectors_field_id {
         let documents_chunk_cloned = original_documents_chunk.clone();
         let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
@@ -315,12 +359,18 @@ fn send_and_extract_flattened_documents_data(
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     searchable_fields: &Option<HashSet<FieldId>>,
+    localized_fields: &HashMap<FieldId, Vec<Language>>,
     faceted_fields: &HashSet<FieldId>,
     primary_key_id: FieldId,
     geo_fields_ids: Option<(FieldId, FieldId)>,
+    named_geo_fields_ids: &[(String, (FieldId, FieldId))],
     stop_words: &Option<fst::Set<&[u8]>>,
+    index_stop_words: bool,
     allowed_separators: &Option<&[&str]>,
     dictionary: &Option<&[&str]>,
+    normalize_numbers: bool,
+    normalization: NormalizationParams,
+    stemming_languages: &[StemmingLanguage],
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(
     grenad::Reader<CursorClonableMmap>,
@@ -348,6 +398,23 @@ fn send_and_extract_flattened_documents_data(
         });
     }
 
+    for (field, geo_fields_ids) in named_geo_fields_ids {
+        let field = field.clone();
+        let geo_fields_ids = *geo_fields_ids;
+        let documents_chunk_cloned = flattened_documents_chunk.clone();
+        let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        rayon::spawn(move || {
+            let result =
+                extract_geo_points(documents_chunk_cloned, indexer, primary_key_id, geo_fields_ids);
+            let _ = match result {
+                Ok(geo_points) => {
+                    lmdb_writer_sx_cloned.send(Ok(TypedChunk::NamedGeoPoints(field, geo_points)))
+                }
+                Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
+            };
+        });
+    }
+
     let (docid_word_positions_chunk, docid_fid_facet_values_chunks): (Result<_>, Result<_>) =
         rayon::join(
             || {
@@ -356,9 +423,14 @@ fn send_and_extract_flattened_documents_data(
                         flattened_documents_chunk.clone(),
                         indexer,
                         searchable_fields,
+                        localized_fields,
                         stop_words.as_ref(),
+                        index_stop_words,
                         *allowed_separators,
                         *dictionary,
+                        normalize_numbers,
+                        normalization,
+                        stemming_languages,
                         max_positions_per_attributes,
                     )?;
 
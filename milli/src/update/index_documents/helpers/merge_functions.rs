@@ -4,6 +4,7 @@ use std::io;
 use std::result::Result as StdResult;
 
 use roaring::RoaringBitmap;
+use serde_json::{Map, Value};
 
 use crate::heed_codec::CboRoaringBitmapCodec;
 use crate::update::index_documents::transform::Operation;
@@ -91,10 +92,69 @@ pub fn merge_two_obkvs(base: obkv::KvReaderU16, update: obkv::KvReaderU16, buffe
     writer.finish().unwrap();
 }
 
+/// Merges two obkvs like `merge_two_obkvs` but the values that are JSON objects
+/// in both obkvs are merged recursively instead of being replaced.
+pub fn deep_merge_two_obkvs(
+    base: obkv::KvReaderU16,
+    update: obkv::KvReaderU16,
+    buffer: &mut Vec<u8>,
+) {
+    use itertools::merge_join_by;
+    use itertools::EitherOrBoth::{Both, Left, Right};
+
+    buffer.clear();
+
+    let mut writer = obkv::KvWriter::new(buffer);
+    for eob in merge_join_by(base.iter(), update.iter(), |(b, _), (u, _)| b.cmp(u)) {
+        match eob {
+            Both((_, base), (k, update)) => {
+                match (serde_json::from_slice(base), serde_json::from_slice(update)) {
+                    (Ok(Value::Object(mut base)), Ok(Value::Object(update))) => {
+                        deep_merge_objects(&mut base, update);
+                        writer.insert(k, serde_json::to_vec(&base).unwrap()).unwrap()
+                    }
+                    _ => writer.insert(k, update).unwrap(),
+                }
+            }
+            Left((k, v)) | Right((k, v)) => writer.insert(k, v).unwrap(),
+        }
+    }
+
+    writer.finish().unwrap();
+}
+
+fn deep_merge_objects(base: &mut Map<String, Value>, update: Map<String, Value>) {
+    for (key, value) in update {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Object(base)), Value::Object(update)) => deep_merge_objects(base, update),
+            (Some(base), value) => *base = value,
+            (None, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 /// Merge all the obks in the order we see them.
 pub fn merge_obkvs_and_operations<'a>(
+    key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    merge_obkvs_and_operations_with(key, obkvs, merge_two_obkvs)
+}
+
+/// Merge all the obks in the order we see them, the nested objects are merged recursively.
+pub fn deep_merge_obkvs_and_operations<'a>(
+    key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    merge_obkvs_and_operations_with(key, obkvs, deep_merge_two_obkvs)
+}
+
+fn merge_obkvs_and_operations_with<'a>(
     _key: &[u8],
     obkvs: &[Cow<'a, [u8]>],
+    merge_two_obkvs: fn(obkv::KvReaderU16, obkv::KvReaderU16, &mut Vec<u8>),
 ) -> Result<Cow<'a, [u8]>> {
     // [add, add, delete, add, add]
     // we can ignore everything that happened before the last delete.
@@ -4,11 +4,13 @@ mod helpers;
 mod transform;
 mod typed_chunk;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::io::{Cursor, Read, Seek};
 use std::iter::FromIterator;
 use std::num::NonZeroU32;
 use std::result::Result as StdResult;
+use std::str::FromStr;
 
 use crossbeam_channel::{Receiver, Sender};
 use heed::types::Str;
@@ -22,7 +24,8 @@ use typed_chunk::{write_typed_chunk_into_index, TypedChunk};
 use self::enrich::enrich_documents_batch;
 pub use self::enrich::{
     extract_finite_float_from_value, validate_document_id, validate_document_id_value,
-    validate_geo_from_json, DocumentId,
+    validate_documents_batch, validate_geo_from_json, DocumentId, DocumentValidationError,
+    DocumentsValidation,
 };
 pub use self::helpers::{
     as_cloneable_grenad, create_sorter, create_writer, fst_stream_into_hashset,
@@ -33,6 +36,8 @@ use self::helpers::{grenad_obkv_into_chunks, GrenadParameters};
 pub use self::transform::{Transform, TransformOutput};
 use crate::documents::{obkv_to_object, DocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
+use crate::localized_attributes_rules::find_rule;
+use crate::prefix_search::PrefixSearch;
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
     self, DeletionStrategy, IndexerConfig, PrefixWordPairsProximityDocids, UpdateIndexingStep,
@@ -62,6 +67,10 @@ pub enum IndexDocumentsMethod {
     /// Merge the previous version of the document with the new version,
     /// replacing old attributes values with the new ones and add the new attributes.
     UpdateDocuments,
+
+    /// Merge the previous version of the document with the new version like `UpdateDocuments`,
+    /// but the attributes that are objects in both versions are merged recursively.
+    MergeDocuments,
 }
 
 impl Default for IndexDocumentsMethod {
@@ -70,6 +79,44 @@ impl Default for IndexDocumentsMethod {
     }
 }
 
+/// What to do with the documents sharing the same id within a single payload.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateIdsPolicy {
+    /// The last document with the id is indexed.
+    #[default]
+    LastWins,
+    /// The first document with the id is indexed, the next ones are ignored.
+    FirstWins,
+    /// The whole payload is rejected with an error listing the duplicated ids.
+    Reject,
+}
+
+impl fmt::Display for DuplicateIdsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuplicateIdsPolicy::LastWins => write!(f, "last-wins"),
+            DuplicateIdsPolicy::FirstWins => write!(f, "first-wins"),
+            DuplicateIdsPolicy::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+impl FromStr for DuplicateIdsPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        match s {
+            "last-wins" => Ok(DuplicateIdsPolicy::LastWins),
+            "first-wins" => Ok(DuplicateIdsPolicy::FirstWins),
+            "reject" => Ok(DuplicateIdsPolicy::Reject),
+            _ => Err(format!(
+                "Unsupported duplicate ids policy `{s}`, expected one of `last-wins`, `first-wins` or `reject`."
+            )),
+        }
+    }
+}
+
 pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     wtxn: &'t mut heed::RwTxn<'i, 'u>,
     index: &'i Index,
@@ -80,6 +127,13 @@ pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     should_abort: FA,
     added_documents: u64,
     deleted_documents: u64,
+    /// The policy applied to the documents sharing an id in the next calls to `add_documents`.
+    duplicate_ids_policy: DuplicateIdsPolicy,
+    /// The internal ids of the documents inserted by every successful call to `add_documents`.
+    additions_documents_ids: Vec<RoaringBitmap>,
+    /// The number of documents sharing their id with a previous document of the same
+    /// payload, for every successful call to `add_documents`.
+    additions_duplicated_documents: Vec<u64>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -124,9 +178,18 @@ where
             index,
             added_documents: 0,
             deleted_documents: 0,
+            duplicate_ids_policy: indexer_config.duplicate_ids_policy,
+            additions_documents_ids: Vec::new(),
+            additions_duplicated_documents: Vec::new(),
         })
     }
 
+    /// Sets the policy applied to the documents sharing an id in the next calls
+    /// to [`Self::add_documents`], it defaults to the one of the [`IndexerConfig`].
+    pub fn set_duplicate_ids_policy(&mut self, duplicate_ids_policy: DuplicateIdsPolicy) {
+        self.duplicate_ids_policy = duplicate_ids_policy;
+    }
+
     /// Adds a batch of documents to the current builder.
     ///
     /// Since the documents are progressively added to the writer, a failure will cause only
@@ -157,19 +220,46 @@ where
             Err(user_error) => return Ok((self, Err(user_error))),
         };
 
-        let indexed_documents =
-            self.transform.as_mut().expect("Invalid document addition state").read_documents(
-                enriched_documents_reader,
-                self.wtxn,
-                &self.progress,
-                &self.should_abort,
-            )? as u64;
+        let transform = self.transform.as_mut().expect("Invalid document addition state");
+        let previous_documents_ids = transform.new_documents_ids().clone();
+        let (indexed_documents, duplicated_documents) = match transform.read_documents(
+            enriched_documents_reader,
+            self.wtxn,
+            self.duplicate_ids_policy,
+            &self.progress,
+            &self.should_abort,
+        ) {
+            Ok(counts) => counts,
+            // The payload is rejected before any document is transformed,
+            // the builder can still be used for the next ones.
+            Err(Error::UserError(user_error @ UserError::DuplicateDocumentIds { .. })) => {
+                return Ok((self, Err(user_error)))
+            }
+            Err(error) => return Err(error),
+        };
+        let indexed_documents = indexed_documents as u64;
 
         self.added_documents += indexed_documents;
+        self.additions_documents_ids.push(transform.new_documents_ids() - &previous_documents_ids);
+        self.additions_duplicated_documents.push(duplicated_documents as u64);
 
         Ok((self, Ok(indexed_documents)))
     }
 
+    /// Returns the internal ids of the documents inserted by each successful call
+    /// to [`Self::add_documents`], in order.
+    ///
+    /// A document sent in several additions is only attributed to the first one.
+    pub fn additions_documents_ids(&self) -> &[RoaringBitmap] {
+        &self.additions_documents_ids
+    }
+
+    /// Returns the number of documents sharing their id with a previous document of
+    /// the same payload for each successful call to [`Self::add_documents`], in order.
+    pub fn additions_duplicated_documents(&self) -> &[u64] {
+        &self.additions_duplicated_documents
+    }
+
     /// Remove a batch of documents from the current builder.
     ///
     /// Returns the number of documents deleted from the builder.
@@ -290,6 +380,15 @@ where
         // get searchable fields for word databases
         let searchable_fields =
             self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter);
+        // get the locales forced on the fields matching a localized attributes rule
+        let localized_attributes_rules =
+            self.index.localized_attributes_rules(self.wtxn)?.unwrap_or_default();
+        let localized_fields: HashMap<_, _> = fields_ids_map
+            .iter()
+            .filter_map(|(fid, name)| {
+                find_rule(&localized_attributes_rules, name).map(|rule| (fid, rule.locales.clone()))
+            })
+            .collect();
         // get filterable fields for facet databases
         let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
         // get the fid of the `_geo.lat` and `_geo.lng` fields.
@@ -312,10 +411,29 @@ where
             }
             None => None,
         };
+        // get the fid of the `lat` and `lng` of the filterable named geo fields.
+        let filterable_fields = self.index.filterable_fields(self.wtxn)?;
+        let named_geo_fields_ids: Vec<_> = self
+            .index
+            .geo_fields(self.wtxn)?
+            .into_iter()
+            .filter(|field| filterable_fields.contains(field))
+            .filter_map(|field| {
+                let lat = fields_ids_map.id(&format!("{field}.lat"))?;
+                let lng = fields_ids_map.id(&format!("{field}.lng"))?;
+                Some((field, (lat, lng)))
+            })
+            .collect();
+        // get the fid of the `_geojson` field if it is filterable.
+        let geojson_field_id = match self.index.fields_ids_map(self.wtxn)?.id("_geojson") {
+            Some(fid) if self.index.filterable_fields_ids(self.wtxn)?.contains(&fid) => Some(fid),
+            _ => None,
+        };
         // get the fid of the `_vectors` field.
         let vectors_field_id = self.index.fields_ids_map(self.wtxn)?.id("_vectors");
 
         let stop_words = self.index.stop_words(self.wtxn)?;
+        let index_stop_words = self.index.index_stop_words(self.wtxn)?;
         let separators = self.index.allowed_separators(self.wtxn)?;
         let separators: Option<Vec<_>> =
             separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
@@ -323,6 +441,10 @@ where
         let dictionary: Option<Vec<_>> =
             dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
         let exact_attributes = self.index.exact_attributes_ids(self.wtxn)?;
+        let normalize_numbers = self.index.normalize_numbers(self.wtxn)?;
+        let normalization = self.index.normalization_params(self.wtxn)?;
+        let stemming_languages = self.index.stemming_languages(self.wtxn)?;
+        let proximity_precision = self.index.proximity_precision(self.wtxn)?.unwrap_or_default();
 
         let pool_params = GrenadParameters {
             chunk_compression_type: self.indexer_config.chunk_compression_type,
@@ -354,15 +476,23 @@ where
                     pool_params,
                     lmdb_writer_sx.clone(),
                     searchable_fields,
+                    localized_fields,
                     faceted_fields,
                     primary_key_id,
                     geo_fields_ids,
+                    &named_geo_fields_ids,
+                    geojson_field_id,
                     vectors_field_id,
                     stop_words,
+                    index_stop_words,
                     separators.as_deref(),
                     dictionary.as_deref(),
+                    normalize_numbers,
+                    normalization,
+                    &stemming_languages,
                     max_positions_per_attributes,
                     exact_attributes,
+                    proximity_precision,
                 )
             });
 
@@ -513,6 +643,11 @@ where
             return Err(Error::InternalError(InternalError::AbortedIndexation));
         }
 
+        // The prefix databases are left empty when the prefix search is disabled.
+        if self.index.prefix_search(self.wtxn)? == PrefixSearch::Disabled {
+            return Ok(());
+        }
+
         let previous_words_prefixes_fst =
             self.index.words_prefixes_fst(self.wtxn)?.map_data(|cow| cow.into_owned())?;
 
@@ -719,7 +854,7 @@ mod tests {
     use crate::index::tests::TempIndex;
     use crate::search::TermsMatchingStrategy;
     use crate::update::DeleteDocuments;
-    use crate::{db_snap, BEU16};
+    use crate::{db_snap, BEU16, CREATED_AT_FIELD_NAME, UPDATED_AT_FIELD_NAME};
 
     #[test]
     fn simple_document_replacement() {
@@ -825,6 +960,46 @@ mod tests {
         drop(rtxn);
     }
 
+    #[test]
+    fn deep_document_merge() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::MergeDocuments;
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "info": { "name": "kevin", "address": { "city": "Paris" } } },
+                { "id": 1, "info": { "address": { "zip": "75001" } }, "tags": ["a"] },
+            ]))
+            .unwrap();
+        // the nested objects are merged but the other values are replaced
+        index
+            .add_documents(documents!([
+                { "id": 1, "info": { "name": "benoit" }, "tags": ["b"] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let (_, obkv) = index.all_documents(&rtxn).unwrap().next().unwrap().unwrap();
+        let document = crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap();
+        assert_eq!(
+            serde_json::Value::from(document),
+            serde_json::json!({
+                "id": 1,
+                "info": { "name": "benoit", "address": { "city": "Paris", "zip": "75001" } },
+                "tags": ["b"],
+            })
+        );
+
+        // the flattened fields of the merged document are searchable
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.query("paris");
+        search.terms_matching_strategy(TermsMatchingStrategy::default());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids.len(), 1);
+    }
+
     #[test]
     fn not_auto_generated_documents_ids() {
         let index = TempIndex::new();
@@ -1349,6 +1524,388 @@ mod tests {
         assert!(matches!(error, crate::Error::UserError(crate::UserError::InvalidFilter(_))));
     }
 
+    #[test]
+    fn index_documents_with_nested_arrays_of_objects() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("author.name"), S("reviews.comment")]);
+                settings.set_filterable_fields(hashset!(S("reviews.rating")));
+                settings.set_sortable_fields(hashset!(S("author.name"), S("reviews.rating")));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                {
+                    "id": 0,
+                    "author": { "name": "Jane" },
+                    "reviews": [{ "rating": 3, "comment": "nice" }, { "rating": 5 }],
+                },
+                {
+                    "id": 1,
+                    "author": { "name": "John" },
+                    "reviews": [{ "rating": 1, "comment": "bad" }],
+                },
+                {
+                    "id": 2,
+                    "author": { "name": "Ada" },
+                    "reviews": [],
+                },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.query("jane");
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![0]);
+        search.query("bad");
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![1]);
+
+        // every value of the array of objects is filterable
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(crate::Filter::from_str("reviews.rating = 5").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![0]);
+        search.filter(crate::Filter::from_str("reviews.rating < 3").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![1]);
+
+        // a document is sorted by its lowest value, the documents without values come last
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.sort_criteria(vec![crate::AscDesc::Asc(crate::Member::Field(S("reviews.rating")))]);
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![1, 0, 2]);
+        search.sort_criteria(vec![crate::AscDesc::Asc(crate::Member::Field(S("author.name")))]);
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn reject_documents_over_the_limits() {
+        let mut index = TempIndex::new();
+        index.indexer_config.max_document_fields = Some(3);
+        index.indexer_config.max_document_depth = Some(2);
+
+        // `id`, `author` and `author.name`
+        index.add_documents(documents!([{ "id": 0, "author": { "name": "Jane" } }])).unwrap();
+
+        let err = index
+            .add_documents(documents!([{ "id": 1, "author": { "name": "John", "age": 32 } }]))
+            .unwrap_err();
+        insta::assert_display_snapshot!(err, @"The document `1` has 4 fields once flattened but a document cannot contain more than 3 fields.");
+
+        index.indexer_config.max_document_fields = None;
+        let err =
+            index.add_documents(documents!([{ "id": 2, "a": { "b": [{ "c": 1 }] } }])).unwrap_err();
+        insta::assert_display_snapshot!(err, @"The document `2` has 3 levels of nested objects but a document cannot be nested more than 2 levels deep.");
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+    }
+
+    #[test]
+    fn duplicate_ids_policies() {
+        let mut index = TempIndex::new();
+        let documents = || {
+            documents!([
+                { "id": 1, "name": "first" },
+                { "id": 2, "name": "alone" },
+                { "id": 1, "name": "second" },
+            ])
+        };
+        let name_of_one = |index: &TempIndex| {
+            let rtxn = index.read_txn().unwrap();
+            let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+            let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+            let fid = index.fields_ids_map(&rtxn).unwrap().id("name").unwrap();
+            serde_json::from_slice::<serde_json::Value>(document.get(fid).unwrap()).unwrap()
+        };
+
+        index.add_documents(documents()).unwrap();
+        assert_eq!(name_of_one(&index), "second");
+
+        index.indexer_config.duplicate_ids_policy = DuplicateIdsPolicy::FirstWins;
+        index.add_documents(documents()).unwrap();
+        assert_eq!(name_of_one(&index), "first");
+
+        index.indexer_config.duplicate_ids_policy = DuplicateIdsPolicy::Reject;
+        let err = index
+            .add_documents(documents!([
+                { "id": 1, "name": "third" },
+                { "id": 3 },
+                { "id": 1 },
+                { "id": 3 },
+            ]))
+            .unwrap_err();
+        insta::assert_display_snapshot!(err, @"The payload contains several documents with the same id: `1`, `3`.");
+        assert_eq!(name_of_one(&index), "first");
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+    }
+
+    #[test]
+    fn duplicate_ids_policy_of_each_addition() {
+        let index = TempIndex::new();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        // the rejected payload doesn't prevent the next additions
+        builder.set_duplicate_ids_policy(DuplicateIdsPolicy::Reject);
+        let (mut builder, user_error) =
+            builder.add_documents(documents!([{ "id": 1 }, { "id": 1 }])).unwrap();
+        insta::assert_display_snapshot!(user_error.unwrap_err(), @"The payload contains several documents with the same id: `1`.");
+
+        builder.set_duplicate_ids_policy(DuplicateIdsPolicy::FirstWins);
+        let (mut builder, user_result) = builder
+            .add_documents(documents!([
+                { "id": 1, "name": "first" },
+                { "id": 2 },
+                { "id": 1, "name": "second" },
+                { "id": 1, "name": "third" },
+            ]))
+            .unwrap();
+        assert_eq!(user_result.unwrap(), 2);
+
+        builder.set_duplicate_ids_policy(DuplicateIdsPolicy::LastWins);
+        let (builder, user_result) =
+            builder.add_documents(documents!([{ "id": 3 }, { "id": 4 }])).unwrap();
+        assert_eq!(user_result.unwrap(), 2);
+
+        assert_eq!(builder.additions_duplicated_documents(), &[2, 0]);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 4);
+    }
+
+    #[test]
+    fn duplicate_ids_are_only_searched_in_the_same_payload() {
+        let index = TempIndex::new();
+        index.add_documents(documents!([{ "id": 1, "name": "stored" }])).unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        // the stored documents and the ones of the previous payloads are not duplicates
+        let (builder, user_result) = builder
+            .add_documents(documents!([{ "id": 1, "name": "stored" }, { "id": 2 }]))
+            .unwrap();
+        assert_eq!(user_result.unwrap(), 2);
+        let (builder, user_result) = builder
+            .add_documents(documents!([
+                { "id": 1, "name": "stored" },
+                { "id": 2 },
+                { "id": 1, "name": "updated" },
+            ]))
+            .unwrap();
+        assert_eq!(user_result.unwrap(), 3);
+
+        assert_eq!(builder.additions_duplicated_documents(), &[0, 1]);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+    }
+
+    #[test]
+    fn rejected_duplicate_ids_are_capped() {
+        let mut index = TempIndex::new();
+        index.indexer_config.duplicate_ids_policy = DuplicateIdsPolicy::Reject;
+
+        let content = {
+            let documents_iter =
+                (0..50).chain(0..50).filter_map(|i| match serde_json::json!({ "id": i }) {
+                    serde_json::Value::Object(object) => Some(object),
+                    _ => None,
+                });
+            documents_batch_reader_from_objects(documents_iter)
+        };
+        let err = index.add_documents(content).unwrap_err();
+        insta::assert_display_snapshot!(err, @"The payload contains several documents with the same id: `0`, `1`, `10`, `11`, `12`, `13`, `14`, `15`, `16`, `17`, `18`, `19`, `2`, `3`, `4`, `5`, `6`, `7`, `8`, `9`.");
+    }
+
+    #[test]
+    fn validate_documents_without_indexing_them() {
+        let mut index = TempIndex::new();
+        index.indexer_config.max_document_fields = Some(2);
+        index.add_documents(documents!([{ "id": 0, "name": "kefir" }])).unwrap();
+
+        let documents = documents!([
+            { "id": 1, "name": "intel" },
+            { "name": "no id" },
+            { "id": "a b", "name": "bad id" },
+            { "id": 1, "name": "duplicate" },
+            { "id": 2, "name": "too", "many": "fields" },
+            { "id": 3 },
+        ]);
+        let rtxn = index.read_txn().unwrap();
+        let validation = validate_documents_batch(
+            Some((&rtxn, &index.inner)),
+            None,
+            &index.indexer_config,
+            DuplicateIdsPolicy::Reject,
+            3,
+            documents,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(validation.invalid_documents, 4);
+        let errors: Vec<_> = validation
+            .errors
+            .iter()
+            .map(|e| (e.position, e.document_id.clone(), e.error.to_string()))
+            .collect();
+        insta::assert_debug_snapshot!(errors, @r###"
+        [
+            (
+                1,
+                None,
+                "Document doesn't have a `id` attribute: `{\"name\":\"no id\"}`.",
+            ),
+            (
+                2,
+                None,
+                "Document identifier `\"a b\"` is invalid. A document identifier can be of type integer or string, only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).",
+            ),
+            (
+                3,
+                Some(
+                    "1",
+                ),
+                "The payload contains several documents with the same id: `1`.",
+            ),
+        ]
+        "###);
+
+        // the primary key of the index cannot be changed
+        let err = validate_documents_batch(
+            Some((&rtxn, &index.inner)),
+            Some("name"),
+            &index.indexer_config,
+            DuplicateIdsPolicy::Reject,
+            3,
+            documents!([{ "id": 4 }]),
+        )
+        .unwrap()
+        .unwrap_err();
+        insta::assert_display_snapshot!(err, @"Index already has a primary key: `id`.");
+
+        // nothing was written
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+    }
+
+    #[test]
+    fn ignore_the_outdated_document_versions() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_version_field(S("version"));
+            })
+            .unwrap();
+
+        let name_of_one = |index: &TempIndex| {
+            let rtxn = index.read_txn().unwrap();
+            let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+            let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+            let fid = index.fields_ids_map(&rtxn).unwrap().id("name").unwrap();
+            serde_json::from_slice::<serde_json::Value>(document.get(fid).unwrap()).unwrap()
+        };
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "version": 2, "name": "second" },
+                { "id": 1, "version": 1, "name": "first" },
+            ]))
+            .unwrap();
+        assert_eq!(name_of_one(&index), "second");
+
+        // the stored version is compared with the new ones
+        index.add_documents(documents!([{ "id": 1, "version": 2, "name": "again" }])).unwrap();
+        assert_eq!(name_of_one(&index), "second");
+        index.add_documents(documents!([{ "id": 1, "version": 3, "name": "third" }])).unwrap();
+        assert_eq!(name_of_one(&index), "third");
+
+        // a document without a numeric version always replaces the previous one
+        index.add_documents(documents!([{ "id": 1, "version": "x", "name": "none" }])).unwrap();
+        assert_eq!(name_of_one(&index), "none");
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+    }
+
+    #[test]
+    fn stamp_the_documents_with_timestamps() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_document_timestamps(true);
+            })
+            .unwrap();
+
+        let timestamps_of_one = |index: &TempIndex| {
+            let rtxn = index.read_txn().unwrap();
+            let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+            let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+            let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+            let timestamp = |name| {
+                let fid = fields_ids_map.id(name).unwrap();
+                serde_json::from_slice::<i64>(document.get(fid).unwrap()).unwrap()
+            };
+            (timestamp(CREATED_AT_FIELD_NAME), timestamp(UPDATED_AT_FIELD_NAME))
+        };
+
+        // the timestamps sent with the documents are replaced
+        index.add_documents(documents!([{ "id": 1, "name": "kevin", "_createdAt": 5 }])).unwrap();
+        let (created_at, updated_at) = timestamps_of_one(&index);
+        assert_ne!(created_at, 5);
+        assert_eq!(created_at, updated_at);
+
+        // the creation date of a replaced document is kept
+        index.add_documents(documents!([{ "id": 1, "name": "kevina" }])).unwrap();
+        let (replaced_created_at, replaced_updated_at) = timestamps_of_one(&index);
+        assert_eq!(replaced_created_at, created_at);
+        assert!(replaced_updated_at >= updated_at);
+
+        let rtxn = index.read_txn().unwrap();
+        let timestamps = hashset!(S(CREATED_AT_FIELD_NAME), S(UPDATED_AT_FIELD_NAME));
+        // the timestamps are faceted without being added to the settings
+        assert!(index.filterable_fields(&rtxn).unwrap().is_empty());
+        assert!(index.sortable_fields(&rtxn).unwrap().is_empty());
+        assert_eq!(index.faceted_fields(&rtxn).unwrap(), timestamps);
+
+        let filter = format!("{CREATED_AT_FIELD_NAME} = {created_at}");
+        let filter = crate::Filter::from_str(&filter).unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().len(), 1);
+        let mut search = index.search(&rtxn);
+        search.sort_criteria(vec![crate::AscDesc::Desc(crate::Member::Field(S(
+            UPDATED_AT_FIELD_NAME,
+        )))]);
+        assert_eq!(search.execute().unwrap().documents_ids.len(), 1);
+    }
+
     #[test]
     fn index_documents_with_nested_primary_key() {
         let index = TempIndex::new();
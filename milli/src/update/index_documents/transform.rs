@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::io::{Read, Seek};
 
@@ -11,17 +11,23 @@ use obkv::{KvReader, KvWriter};
 use roaring::RoaringBitmap;
 use serde_json::Value;
 use smartstring::SmartString;
+use time::OffsetDateTime;
 
 use super::helpers::{
-    create_sorter, create_writer, keep_latest_obkv, merge_obkvs_and_operations, MergeFn,
+    create_sorter, create_writer, deep_merge_obkvs_and_operations, keep_latest_obkv,
+    merge_obkvs_and_operations, MergeFn,
+};
+use super::{DuplicateIdsPolicy, IndexDocumentsMethod, IndexerConfig};
+use crate::documents::{
+    DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchCursor,
+    EnrichedDocumentsBatchReader,
 };
-use super::{IndexDocumentsMethod, IndexerConfig};
-use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
 use crate::index::{db_name, main_key};
 use crate::update::{AvailableDocumentsIds, ClearDocuments, UpdateIndexingStep};
 use crate::{
-    FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index, Result, BEU32,
+    ExternalDocumentsIds, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index,
+    Result, BEU32, CREATED_AT_FIELD_NAME, UPDATED_AT_FIELD_NAME,
 };
 
 pub struct TransformOutput {
@@ -61,6 +67,8 @@ pub struct Transform<'a, 'i> {
     new_documents_ids: RoaringBitmap,
     // To increase the cache locality and decrease the heap usage we use compact smartstring.
     new_external_documents_ids_builder: FxHashMap<SmartString<smartstring::Compact>, u64>,
+    // The highest version of each document read by this transform, when the index has a version field.
+    documents_versions: HashMap<String, f64>,
     documents_count: usize,
 }
 
@@ -72,6 +80,65 @@ pub enum Operation {
     Deletion,
 }
 
+/// Reads the version of a document, only the numbers are valid versions.
+fn numeric_version(value: &[u8]) -> Option<f64> {
+    serde_json::from_slice::<Value>(value).ok()?.as_f64()
+}
+
+/// Returns the number of levels of nested objects of the value, the arrays are not counted.
+fn nested_objects_depth(value: &Value) -> usize {
+    match value {
+        Value::Object(object) => 1 + object.values().map(nested_objects_depth).max().unwrap_or(0),
+        Value::Array(array) => array.iter().map(nested_objects_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Returns an error if the document has more fields once flattened, or more levels of
+/// nested objects, than allowed by the indexer settings.
+pub(crate) fn check_document_limits(
+    indexer_settings: &IndexerConfig,
+    external_id: &str,
+    obkv: &KvReader<FieldId>,
+) -> Result<()> {
+    let max_fields = indexer_settings.max_document_fields;
+    let max_depth = indexer_settings.max_document_depth;
+    if max_fields.is_none() && max_depth.is_none() {
+        return Ok(());
+    }
+
+    let mut fields = 0;
+    let mut depth = 1;
+    let mut nested = serde_json::Map::new();
+    for (key, value) in obkv.iter() {
+        if json_depth_checker::should_flatten_from_unchecked_slice(value) {
+            let value = serde_json::from_slice::<Value>(value)
+                .map_err(crate::error::InternalError::SerdeJson)?;
+            depth = depth.max(1 + nested_objects_depth(&value));
+            nested.insert(key.to_string(), value);
+        } else {
+            fields += 1;
+        }
+    }
+    fields += flatten_serde_json::flatten(&nested).len();
+
+    match (max_fields, max_depth) {
+        (Some(limit), _) if fields > limit => Err(UserError::DocumentFieldsLimitReached {
+            document_id: external_id.to_string(),
+            fields,
+            limit,
+        }
+        .into()),
+        (_, Some(limit)) if depth > limit => Err(UserError::DocumentDepthLimitReached {
+            document_id: external_id.to_string(),
+            depth,
+            limit,
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
 /// Create a mapping between the field ids found in the document batch and the one that were
 /// already present in the index.
 ///
@@ -108,6 +175,13 @@ impl<'a, 'i> Transform<'a, 'i> {
         let merge_function = match index_documents_method {
             IndexDocumentsMethod::ReplaceDocuments => keep_latest_obkv,
             IndexDocumentsMethod::UpdateDocuments => merge_obkvs_and_operations,
+            IndexDocumentsMethod::MergeDocuments => deep_merge_obkvs_and_operations,
+        };
+        // The flattened documents have no nested objects, merging their fields
+        // gives the flattened version of the deeply merged documents.
+        let flattened_merge_function = match index_documents_method {
+            IndexDocumentsMethod::MergeDocuments => merge_obkvs_and_operations,
+            _ => merge_function,
         };
 
         // We initialize the sorter with the user indexing settings.
@@ -123,7 +197,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         // We initialize the sorter with the user indexing settings.
         let flattened_sorter = create_sorter(
             grenad::SortAlgorithm::Stable,
-            merge_function,
+            flattened_merge_function,
             indexer_settings.chunk_compression_type,
             indexer_settings.chunk_compression_level,
             indexer_settings.max_nb_chunks,
@@ -147,35 +221,79 @@ impl<'a, 'i> Transform<'a, 'i> {
             replaced_documents_ids: RoaringBitmap::new(),
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
+            documents_versions: HashMap::new(),
             documents_count: 0,
         })
     }
 
+    /// The internal ids of the documents inserted by this transform so far.
+    pub fn new_documents_ids(&self) -> &RoaringBitmap {
+        &self.new_documents_ids
+    }
+
+    /// Returns the number of documents read and the number of them sharing
+    /// their id with a previous document of the payload.
     pub fn read_documents<R, FP, FA>(
         &mut self,
         reader: EnrichedDocumentsBatchReader<R>,
         wtxn: &mut heed::RwTxn,
+        duplicate_ids_policy: DuplicateIdsPolicy,
         progress_callback: FP,
         should_abort: FA,
-    ) -> Result<usize>
+    ) -> Result<(usize, usize)>
     where
         R: Read + Seek,
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
     {
         let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
+
+        // The payload is rejected before any document is transformed,
+        // the transform can then still be used for the next payloads.
+        if duplicate_ids_policy == DuplicateIdsPolicy::Reject {
+            let document_ids = find_duplicate_ids(&mut cursor)?;
+            if !document_ids.is_empty() {
+                return Err(UserError::DuplicateDocumentIds { document_ids }.into());
+            }
+        }
+
         let external_documents_ids = self.index.external_documents_ids(wtxn)?;
         let mapping = create_fields_mapping(&mut self.fields_ids_map, &fields_index)?;
 
         let primary_key = cursor.primary_key().to_string();
         let primary_key_id =
             self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
+        let version_fid = match self.index.version_field(wtxn)? {
+            Some(version_field) => self.fields_ids_map.id(version_field),
+            None => None,
+        };
+        // The documents are stamped with the unix timestamp, in seconds, of the transform.
+        let timestamps_fids = if self.index.document_timestamps(wtxn)? {
+            let created_at_fid = self
+                .fields_ids_map
+                .insert(CREATED_AT_FIELD_NAME)
+                .ok_or(UserError::AttributeLimitReached)?;
+            let updated_at_fid = self
+                .fields_ids_map
+                .insert(UPDATED_AT_FIELD_NAME)
+                .ok_or(UserError::AttributeLimitReached)?;
+            Some((created_at_fid, updated_at_fid))
+        } else {
+            None
+        };
+        let now = serde_json::to_vec(&OffsetDateTime::now_utc().unix_timestamp())
+            .map_err(InternalError::SerdeJson)?;
 
         let mut obkv_buffer = Vec::new();
         let mut document_sorter_buffer = Vec::new();
         let mut documents_count = 0;
         let mut docid_buffer: Vec<u8> = Vec::new();
         let mut field_buffer: Vec<(u16, Cow<[u8]>)> = Vec::new();
+        let keep_first = duplicate_ids_policy == DuplicateIdsPolicy::FirstWins;
+        // The internal ids the documents of this payload were given, to find the next ones
+        // sharing their external id without keeping all the external ids of the payload.
+        let mut payload_documents_ids = RoaringBitmap::new();
+        let mut duplicated_documents = 0;
         while let Some(enriched_document) = cursor.next_enriched_document()? {
             let EnrichedDocument { document, document_id } = enriched_document;
 
@@ -183,6 +301,19 @@ impl<'a, 'i> Transform<'a, 'i> {
                 return Err(Error::InternalError(InternalError::AbortedIndexation));
             }
 
+            // The documents with an id already seen in this payload are counted,
+            // and ignored when the first document with the id is kept.
+            let external_id = document_id.value();
+            if self
+                .current_docid(&external_documents_ids, external_id)
+                .map_or(false, |docid| payload_documents_ids.contains(docid))
+            {
+                duplicated_documents += 1;
+                if keep_first {
+                    continue;
+                }
+            }
+
             // drop_and_reuse is called instead of .clear() to communicate to the compiler that field_buffer
             // does not keep references from the cursor between loop iterations
             let mut field_buffer_cache = drop_and_reuse(field_buffer);
@@ -194,7 +325,6 @@ impl<'a, 'i> Transform<'a, 'i> {
 
             // When the document id has been auto-generated by the `enrich_documents_batch`
             // we must insert this document id into the remaped document.
-            let external_id = document_id.value();
             if document_id.is_generated() {
                 serde_json::to_writer(&mut docid_buffer, external_id)
                     .map_err(InternalError::SerdeJson)?;
@@ -207,6 +337,21 @@ impl<'a, 'i> Transform<'a, 'i> {
                 field_buffer_cache.push((mapped_id, Cow::from(v)));
             }
 
+            // The timestamps sent with the document are replaced by the ones of the engine.
+            if let Some((created_at_fid, updated_at_fid)) = timestamps_fids {
+                field_buffer_cache
+                    .retain(|(fid, _)| *fid != created_at_fid && *fid != updated_at_fid);
+                let created_at = self.stored_creation_date(
+                    wtxn,
+                    &external_documents_ids,
+                    external_id,
+                    created_at_fid,
+                )?;
+                let created_at = created_at.map_or_else(|| Cow::from(&now[..]), Cow::from);
+                field_buffer_cache.push((created_at_fid, created_at));
+                field_buffer_cache.push((updated_at_fid, Cow::from(&now[..])));
+            }
+
             // Insertion in a obkv need to be done with keys ordered. For now they are ordered
             // according to the document addition key order, so we sort it according to the
             // fieldids map keys order.
@@ -217,6 +362,31 @@ impl<'a, 'i> Transform<'a, 'i> {
             for (k, v) in field_buffer_cache.iter() {
                 writer.insert(*k, v)?;
             }
+            check_document_limits(
+                self.indexer_settings,
+                external_id,
+                &KvReader::new(&obkv_buffer),
+            )?;
+
+            // A document older than the previous version of the document is ignored.
+            if let Some(version_fid) = version_fid {
+                let document = KvReader::new(&obkv_buffer);
+                if !self.is_newer_version(
+                    wtxn,
+                    &external_documents_ids,
+                    external_id,
+                    version_fid,
+                    document,
+                )? {
+                    if let Some(docid) = self.current_docid(&external_documents_ids, external_id) {
+                        payload_documents_ids.insert(docid);
+                    }
+                    field_buffer = drop_and_reuse(field_buffer_cache);
+                    docid_buffer.clear();
+                    obkv_buffer.clear();
+                    continue;
+                }
+            }
 
             let mut original_docid = None;
 
@@ -260,6 +430,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                     self.replaced_documents_ids.remove(original_docid);
                     // and we need to put back the original id as it was before
                     self.new_external_documents_ids_builder.remove(external_id);
+                    payload_documents_ids.insert(original_docid);
                     skip_insertion = true;
                 } else {
                     // we associate the base document with the new key, everything will get merged later.
@@ -285,6 +456,7 @@ impl<'a, 'i> Transform<'a, 'i> {
 
             if !skip_insertion {
                 self.new_documents_ids.insert(docid);
+                payload_documents_ids.insert(docid);
 
                 document_sorter_buffer.clear();
                 document_sorter_buffer.push(Operation::Addition as u8);
@@ -325,7 +497,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         self.documents_count += documents_count;
         // Now that we have a valid sorter that contains the user id and the obkv we
         // give it to the last transforming function which returns the TransformOutput.
-        Ok(documents_count)
+        Ok((documents_count, duplicated_documents))
     }
 
     /// The counter part of `read_documents` that removes documents either from the transform or the database.
@@ -359,6 +531,8 @@ impl<'a, 'i> Transform<'a, 'i> {
                 return Err(Error::InternalError(InternalError::AbortedIndexation));
             }
 
+            self.documents_versions.remove(&to_remove);
+
             match self.new_external_documents_ids_builder.entry((*to_remove).into()) {
                 // if the document was added in a previous iteration of the transform we make it as deleted in the sorters.
                 Entry::Occupied(entry) => {
@@ -395,6 +569,93 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_deleted)
     }
 
+    /// Returns the internal id of the document with the given external id in
+    /// the documents transformed so far or, if there is none, in the index.
+    fn current_docid(
+        &self,
+        external_documents_ids: &ExternalDocumentsIds,
+        external_id: &str,
+    ) -> Option<u32> {
+        match self.new_external_documents_ids_builder.get(external_id) {
+            Some(docid) => Some(*docid as u32),
+            None => external_documents_ids.get(external_id),
+        }
+    }
+
+    /// Returns `false` if the document and the previous version of the document with the same id,
+    /// read earlier by this transform or stored in the index, both have a numeric version and the
+    /// version of the document is not greater.
+    fn is_newer_version(
+        &mut self,
+        wtxn: &heed::RwTxn,
+        external_documents_ids: &ExternalDocumentsIds,
+        external_id: &str,
+        version_fid: FieldId,
+        document: KvReader<FieldId>,
+    ) -> Result<bool> {
+        let Some(version) = document.get(version_fid).and_then(numeric_version) else {
+            return Ok(true);
+        };
+
+        let previous_version = match self.documents_versions.get(external_id) {
+            Some(previous_version) => Some(*previous_version),
+            // the documents replaced or removed by this transform have no previous version
+            None => match external_documents_ids.get(external_id) {
+                Some(docid) if !self.replaced_documents_ids.contains(docid) => {
+                    let stored = self
+                        .index
+                        .documents
+                        .remap_data_type::<heed::types::ByteSlice>()
+                        .get(wtxn, &BEU32::new(docid))?
+                        .ok_or(InternalError::DatabaseMissingEntry {
+                            db_name: db_name::DOCUMENTS,
+                            key: None,
+                        })?;
+                    KvReader::new(stored).get(version_fid).and_then(numeric_version)
+                }
+                _ => None,
+            },
+        };
+
+        if previous_version.map_or(false, |previous_version| version <= previous_version) {
+            return Ok(false);
+        }
+        self.documents_versions.insert(external_id.to_string(), version);
+        Ok(true)
+    }
+
+    /// Returns the `_createdAt` timestamp of the stored document with the same id, unless this
+    /// document was removed by this transform.
+    fn stored_creation_date(
+        &self,
+        wtxn: &heed::RwTxn,
+        external_documents_ids: &ExternalDocumentsIds,
+        external_id: &str,
+        created_at_fid: FieldId,
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(docid) = external_documents_ids.get(external_id) else {
+            return Ok(None);
+        };
+        // a stored document replaced by this transform is only removed when it is not
+        // associated with a new document anymore.
+        if self.replaced_documents_ids.contains(docid)
+            && !self.new_external_documents_ids_builder.contains_key(external_id)
+        {
+            return Ok(None);
+        }
+
+        let stored = self
+            .index
+            .documents
+            .remap_data_type::<heed::types::ByteSlice>()
+            .get(wtxn, &BEU32::new(docid))?
+            .ok_or(InternalError::DatabaseMissingEntry {
+                db_name: db_name::DOCUMENTS,
+                key: None,
+            })?;
+        Ok(KvReader::new(stored).get(created_at_fid).map(<[u8]>::to_vec))
+    }
+
     // Flatten a document from the fields ids map contained in self and insert the new
     // created fields. Returns `None` if the document doesn't need to be flattened.
     fn flatten_from_fields_ids_map(&mut self, obkv: KvReader<FieldId>) -> Result<Option<Vec<u8>>> {
@@ -793,6 +1054,28 @@ impl<'a, 'i> Transform<'a, 'i> {
     }
 }
 
+/// The maximum number of ids listed in the error rejecting a payload with duplicated ids.
+const MAX_REPORTED_DUPLICATE_IDS: usize = 20;
+
+/// Returns the ids shared by several documents of the payload, at most [`MAX_REPORTED_DUPLICATE_IDS`]
+/// of them, the cursor is reset to be read again.
+fn find_duplicate_ids<R: Read + Seek>(
+    cursor: &mut EnrichedDocumentsBatchCursor<R>,
+) -> Result<Vec<String>> {
+    let mut payload_ids = HashSet::new();
+    let mut duplicate_ids = BTreeSet::new();
+    while let Some(EnrichedDocument { document_id, .. }) = cursor.next_enriched_document()? {
+        if !payload_ids.insert(document_id.value().to_string()) {
+            duplicate_ids.insert(document_id.value().to_string());
+            if duplicate_ids.len() >= MAX_REPORTED_DUPLICATE_IDS {
+                break;
+            }
+        }
+    }
+    cursor.reset();
+    Ok(duplicate_ids.into_iter().collect())
+}
+
 /// Drops all the value of type `U` in vec, and reuses the allocation to create a `Vec<T>`.
 ///
 /// The size and alignment of T and U must match.
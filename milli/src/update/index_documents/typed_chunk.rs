@@ -10,18 +10,21 @@ use grenad::MergerBuilder;
 use heed::types::ByteSlice;
 use heed::RwTxn;
 use roaring::RoaringBitmap;
+use rstar::primitives::Rectangle;
 
 use super::helpers::{
     self, merge_ignore_values, serialize_roaring_bitmap, valid_lmdb_key, CursorClonableMmap,
 };
 use super::{ClonableMmap, MergeFn};
-use crate::distance::NDotProductPoint;
+use crate::distance::VectorPoint;
 use crate::error::UserError;
 use crate::facet::FacetType;
-use crate::index::Hnsw;
+use crate::geo_rtree::GeoRTree;
 use crate::update::facet::FacetsUpdate;
 use crate::update::index_documents::helpers::{as_cloneable_grenad, try_split_array_at};
-use crate::{lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, GeoPoint, Index, Result, BEU32};
+use crate::{
+    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, GeoPoint, GeoShapeBox, Index, Result, BEU32,
+};
 
 pub(crate) enum TypedChunk {
     FieldIdDocidFacetStrings(grenad::Reader<CursorClonableMmap>),
@@ -42,6 +45,8 @@ pub(crate) enum TypedChunk {
     FieldIdFacetIsNullDocids(grenad::Reader<File>),
     FieldIdFacetIsEmptyDocids(grenad::Reader<File>),
     GeoPoints(grenad::Reader<File>),
+    NamedGeoPoints(String, grenad::Reader<File>),
+    GeoShapes(grenad::Reader<File>),
     VectorPoints(grenad::Reader<File>),
     ScriptLanguageDocids(HashMap<(Script, Language), RoaringBitmap>),
 }
@@ -96,6 +101,12 @@ impl TypedChunk {
             TypedChunk::GeoPoints(grenad) => {
                 format!("GeoPoints {{ number_of_entries: {} }}", grenad.len())
             }
+            TypedChunk::NamedGeoPoints(field, grenad) => {
+                format!("NamedGeoPoints {{ field: {field}, number_of_entries: {} }}", grenad.len())
+            }
+            TypedChunk::GeoShapes(grenad) => {
+                format!("GeoShapes {{ number_of_entries: {} }}", grenad.len())
+            }
             TypedChunk::VectorPoints(grenad) => {
                 format!("VectorPoints {{ number_of_entries: {} }}", grenad.len())
             }
@@ -268,9 +279,13 @@ pub(crate) fn write_typed_chunk_into_index(
             }
         }
         TypedChunk::GeoPoints(geo_points) => {
-            let mut rtree = index.geo_rtree(wtxn)?.unwrap_or_default();
+            let params = index.geo_index_params(wtxn)?;
+            let mut rtree =
+                index.geo_rtree(wtxn)?.unwrap_or_else(|| GeoRTree::new(params.node_size));
             let mut geo_faceted_docids = index.geo_faceted_documents_ids(wtxn)?;
 
+            let mut points = Vec::new();
+
             let mut cursor = geo_points.into_cursor()?;
             while let Some((key, value)) = cursor.move_on_next()? {
                 // convert the key back to a u32 (4 bytes)
@@ -282,62 +297,69 @@ pub(crate) fn write_typed_chunk_into_index(
                 let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
                 let xyz_point = lat_lng_to_xyz(&point);
 
-                rtree.insert(GeoPoint::new(xyz_point, (docid, point)));
+                points.push(GeoPoint::new(xyz_point, (docid, point)));
                 geo_faceted_docids.insert(docid);
             }
+            rtree.extend(points, params.load_strategy);
             index.put_geo_rtree(wtxn, &rtree)?;
             index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_docids)?;
         }
-        TypedChunk::VectorPoints(vector_points) => {
-            let (pids, mut points): (Vec<_>, Vec<_>) = match index.vector_hnsw(wtxn)? {
-                Some(hnsw) => hnsw.iter().map(|(pid, point)| (pid, point.clone())).unzip(),
-                None => Default::default(),
-            };
+        TypedChunk::NamedGeoPoints(field, geo_points) => {
+            let params = index.geo_index_params(wtxn)?;
+            let mut rtrees = index.named_geo_rtrees(wtxn)?;
+            let rtree = rtrees.entry(field).or_insert_with(|| GeoRTree::new(params.node_size));
 
-            // Convert the PointIds into DocumentIds
-            let mut docids = Vec::new();
-            for pid in pids {
-                let docid =
-                    index.vector_id_docid.get(wtxn, &BEU32::new(pid.into_inner()))?.unwrap();
-                docids.push(docid.get());
-            }
+            let mut points = Vec::new();
 
-            let mut expected_dimensions = points.get(0).map(|p| p.len());
-            let mut cursor = vector_points.into_cursor()?;
+            let mut cursor = geo_points.into_cursor()?;
             while let Some((key, value)) = cursor.move_on_next()? {
                 // convert the key back to a u32 (4 bytes)
-                let (left, _index) = try_split_array_at(key).unwrap();
-                let docid = DocumentId::from_be_bytes(left);
-                // convert the vector back to a Vec<f32>
-                let vector: Vec<f32> = pod_collect_to_vec(value);
-
-                // TODO Inform the user about the document that has a wrong `_vectors`
-                let found = vector.len();
-                let expected = *expected_dimensions.get_or_insert(found);
-                if expected != found {
-                    return Err(UserError::InvalidVectorDimensions { expected, found })?;
-                }
+                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
 
-                points.push(NDotProductPoint::new(vector));
-                docids.push(docid);
-            }
+                // convert the latitude and longitude back to a f64 (8 bytes)
+                let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
+                let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+                let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
+                let xyz_point = lat_lng_to_xyz(&point);
 
-            assert_eq!(docids.len(), points.len());
+                points.push(GeoPoint::new(xyz_point, (docid, point)));
+            }
+            rtree.extend(points, params.load_strategy);
+            index.put_named_geo_rtrees(wtxn, &rtrees)?;
+        }
+        TypedChunk::GeoShapes(geo_shapes) => {
+            let mut rtree = index.geojson_rtree(wtxn)?.unwrap_or_default();
 
-            let hnsw_length = points.len();
-            let (new_hnsw, pids) = Hnsw::builder().build_hnsw(points);
+            let mut cursor = geo_shapes.into_cursor()?;
+            while let Some((key, value)) = cursor.move_on_next()? {
+                // convert the key back to a u32 (4 bytes)
+                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
 
-            index.vector_id_docid.clear(wtxn)?;
-            for (docid, pid) in docids.into_iter().zip(pids) {
-                index.vector_id_docid.put(
-                    wtxn,
-                    &BEU32::new(pid.into_inner()),
-                    &BEU32::new(docid),
-                )?;
+                // convert the corners of the bounding box back to f64s (8 bytes each)
+                let (bottom, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
+                let (left, tail) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+                let (top, tail) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+                let (right, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+                let bottom_left = [f64::from_ne_bytes(bottom), f64::from_ne_bytes(left)];
+                let top_right = [f64::from_ne_bytes(top), f64::from_ne_bytes(right)];
+
+                rtree.insert(GeoShapeBox::new(
+                    Rectangle::from_corners(bottom_left, top_right),
+                    docid,
+                ));
+            }
+            index.put_geojson_rtree(wtxn, &rtree)?;
+        }
+        TypedChunk::VectorPoints(vector_points) => {
+            if index.binary_quantized_vectors(wtxn)? {
+                let hnsw = index.binary_vector_hnsw(wtxn)?;
+                let new_hnsw = insert_vector_points(wtxn, index, hnsw, vector_points)?;
+                index.put_binary_vector_hnsw(wtxn, &new_hnsw)?;
+            } else {
+                let hnsw = index.vector_hnsw(wtxn)?;
+                let new_hnsw = insert_vector_points(wtxn, index, hnsw, vector_points)?;
+                index.put_vector_hnsw(wtxn, &new_hnsw)?;
             }
-
-            log::debug!("There are {} entries in the HNSW so far", hnsw_length);
-            index.put_vector_hnsw(wtxn, &new_hnsw)?;
         }
         TypedChunk::ScriptLanguageDocids(hash_pair) => {
             let mut buffer = Vec::new();
@@ -362,6 +384,61 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
+/// Builds a new HNSW with the points of the current one and the new vectors,
+/// and maps the ids of the new points to the documents ids.
+fn insert_vector_points<P: VectorPoint>(
+    wtxn: &mut RwTxn,
+    index: &Index,
+    hnsw: Option<instant_distance::Hnsw<P>>,
+    vector_points: grenad::Reader<File>,
+) -> Result<instant_distance::Hnsw<P>> {
+    let (pids, mut points): (Vec<_>, Vec<_>) = match hnsw {
+        Some(hnsw) => hnsw.iter().map(|(pid, point)| (pid, point.clone())).unzip(),
+        None => Default::default(),
+    };
+
+    // Convert the PointIds into DocumentIds
+    let mut docids = Vec::new();
+    for pid in pids {
+        let docid = index.vector_id_docid.get(wtxn, &BEU32::new(pid.into_inner()))?.unwrap();
+        docids.push(docid.get());
+    }
+
+    let mut expected_dimensions = points.first().map(|p| p.dimensions());
+    let mut cursor = vector_points.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        // convert the key back to a u32 (4 bytes)
+        let (left, _index) = try_split_array_at(key).unwrap();
+        let docid = DocumentId::from_be_bytes(left);
+        // convert the vector back to a Vec<f32>
+        let vector: Vec<f32> = pod_collect_to_vec(value);
+
+        // TODO Inform the user about the document that has a wrong `_vectors`
+        let found = vector.len();
+        let expected = *expected_dimensions.get_or_insert(found);
+        if expected != found {
+            return Err(UserError::InvalidVectorDimensions { expected, found })?;
+        }
+
+        points.push(P::from_vector(vector));
+        docids.push(docid);
+    }
+
+    assert_eq!(docids.len(), points.len());
+
+    let hnsw_length = points.len();
+    let params = index.vector_index_params(wtxn)?;
+    let (new_hnsw, pids) = params.build_hnsw(points);
+
+    index.vector_id_docid.clear(wtxn)?;
+    for (docid, pid) in docids.into_iter().zip(pids) {
+        index.vector_id_docid.put(wtxn, &BEU32::new(pid.into_inner()), &BEU32::new(docid))?;
+    }
+
+    log::debug!("There are {} entries in the HNSW so far", hnsw_length);
+    Ok(new_hnsw)
+}
+
 fn merge_word_docids_reader_into_fst(
     word_docids_iter: grenad::Reader<io::Cursor<ClonableMmap>>,
     exact_word_docids_iter: grenad::Reader<io::Cursor<ClonableMmap>>,
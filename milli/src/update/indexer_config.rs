@@ -1,6 +1,8 @@
 use grenad::CompressionType;
 use rayon::ThreadPool;
 
+use crate::update::DuplicateIdsPolicy;
+
 #[derive(Debug)]
 pub struct IndexerConfig {
     pub log_every_n: Option<usize>,
@@ -11,6 +13,11 @@ pub struct IndexerConfig {
     pub chunk_compression_level: Option<u32>,
     pub thread_pool: Option<ThreadPool>,
     pub max_positions_per_attributes: Option<u32>,
+    /// The maximum number of fields of a document once flattened.
+    pub max_document_fields: Option<usize>,
+    /// The maximum number of levels of nested objects of a document.
+    pub max_document_depth: Option<usize>,
+    pub duplicate_ids_policy: DuplicateIdsPolicy,
     pub skip_index_budget: bool,
 }
 
@@ -25,6 +32,9 @@ impl Default for IndexerConfig {
             chunk_compression_level: None,
             thread_pool: None,
             max_positions_per_attributes: None,
+            max_document_fields: None,
+            max_document_depth: None,
+            duplicate_ids_policy: DuplicateIdsPolicy::default(),
             skip_index_budget: false,
         }
     }
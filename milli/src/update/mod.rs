@@ -3,10 +3,12 @@ pub use self::clear_documents::ClearDocuments;
 pub use self::delete_documents::{DeleteDocuments, DeletionStrategy, DocumentDeletionResult};
 pub use self::facet::bulk::FacetsUpdateBulk;
 pub use self::facet::incremental::FacetsUpdateIncrementalInner;
+pub(crate) use self::index_documents::extract_finite_float_from_value;
 pub use self::index_documents::{
     merge_btreeset_string, merge_cbo_roaring_bitmaps, merge_roaring_bitmaps,
-    DocumentAdditionResult, DocumentId, IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod,
-    MergeFn,
+    validate_documents_batch, DocumentAdditionResult, DocumentId, DocumentValidationError,
+    DocumentsValidation, DuplicateIdsPolicy, IndexDocuments, IndexDocumentsConfig,
+    IndexDocumentsMethod, MergeFn,
 };
 pub use self::indexer_config::IndexerConfig;
 pub use self::prefix_word_pairs::{
@@ -14,6 +16,7 @@ pub use self::prefix_word_pairs::{
     MAX_PROXIMITY_FOR_PREFIX_PROXIMITY_DB,
 };
 pub use self::settings::{Setting, Settings};
+pub use self::suggestions::RecordSuggestions;
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
 pub use self::words_prefix_integer_docids::WordPrefixIntegerDocids;
@@ -27,6 +30,7 @@ mod index_documents;
 mod indexer_config;
 mod prefix_word_pairs;
 mod settings;
+mod suggestions;
 mod update_step;
 mod word_prefix_docids;
 mod words_prefix_integer_docids;
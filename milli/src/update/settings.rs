@@ -1,20 +1,30 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::result::Result as StdResult;
 
-use charabia::{Normalize, Tokenizer, TokenizerBuilder};
+use charabia::{Tokenizer, TokenizerBuilder};
 use deserr::{DeserializeError, Deserr};
 use itertools::Itertools;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
 
+use super::facet::index_facet_search;
 use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
-use crate::criterion::Criterion;
+use crate::criterion::{validate_criteria, Criterion};
+use crate::distance::VectorIndexParams;
 use crate::error::UserError;
+use crate::geo_rtree::{GeoIndexParams, GeoLoadStrategy, GeoNodeSize};
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
+use crate::localized_attributes_rules::LocalizedAttributesRule;
+use crate::normalization::{normalize_words, NormalizationParams};
+use crate::prefix_search::PrefixSearch;
+use crate::proximity::ProximityPrecision;
+use crate::reranker::RerankerConfig;
+use crate::stemming::StemmingLanguage;
+use crate::stop_words::{normalize_stop_word, StopWordsPreset};
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
-use crate::{FieldsIdsMap, Index, OrderBy, Result};
+use crate::{FieldsIdsMap, Filter, Index, OrderBy, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -107,11 +117,14 @@ pub struct Settings<'a, 't, 'u, 'i> {
     indexer_config: &'a IndexerConfig,
 
     searchable_fields: Setting<Vec<String>>,
+    searchable_fields_weights: Setting<BTreeMap<String, u16>>,
     displayed_fields: Setting<Vec<String>>,
     filterable_fields: Setting<HashSet<String>>,
     sortable_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
+    stop_words_preset: Setting<StopWordsPreset>,
+    index_stop_words: Setting<bool>,
     non_separator_tokens: Setting<BTreeSet<String>>,
     separator_tokens: Setting<BTreeSet<String>>,
     dictionary: Setting<BTreeSet<String>>,
@@ -124,9 +137,32 @@ pub struct Settings<'a, 't, 'u, 'i> {
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    disable_typos_on_numbers: Setting<bool>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<HashMap<String, OrderBy>>,
     pagination_max_total_hits: Setting<usize>,
+    search_cutoff: Setting<u64>,
+    proximity_precision: Setting<ProximityPrecision>,
+    prefix_search: Setting<PrefixSearch>,
+    split_words: Setting<bool>,
+    concatenate_words: Setting<bool>,
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    normalize_numbers: Setting<bool>,
+    normalization_lowercase: Setting<bool>,
+    normalization_remove_diacritics: Setting<bool>,
+    normalization_compatibility: Setting<bool>,
+    percolate_queries: Setting<BTreeMap<String, String>>,
+    stemming_languages: Setting<BTreeSet<StemmingLanguage>>,
+    geo_fields: Setting<BTreeSet<String>>,
+    geo_node_size: Setting<GeoNodeSize>,
+    geo_load_strategy: Setting<GeoLoadStrategy>,
+    binary_quantized_vectors: Setting<bool>,
+    vector_ef_construction: Setting<usize>,
+    vector_ef_search: Setting<usize>,
+    reranker: Setting<RerankerConfig>,
+    version_field: Setting<String>,
+    document_timestamps: Setting<bool>,
+    facet_search: Setting<bool>,
 }
 
 impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
@@ -139,11 +175,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             wtxn,
             index,
             searchable_fields: Setting::NotSet,
+            searchable_fields_weights: Setting::NotSet,
             displayed_fields: Setting::NotSet,
             filterable_fields: Setting::NotSet,
             sortable_fields: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
+            stop_words_preset: Setting::NotSet,
+            index_stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
@@ -155,9 +194,32 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            disable_typos_on_numbers: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
+            search_cutoff: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
+            prefix_search: Setting::NotSet,
+            split_words: Setting::NotSet,
+            concatenate_words: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
+            normalize_numbers: Setting::NotSet,
+            normalization_lowercase: Setting::NotSet,
+            normalization_remove_diacritics: Setting::NotSet,
+            normalization_compatibility: Setting::NotSet,
+            percolate_queries: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            geo_fields: Setting::NotSet,
+            geo_node_size: Setting::NotSet,
+            geo_load_strategy: Setting::NotSet,
+            binary_quantized_vectors: Setting::NotSet,
+            vector_ef_construction: Setting::NotSet,
+            vector_ef_search: Setting::NotSet,
+            reranker: Setting::NotSet,
+            version_field: Setting::NotSet,
+            document_timestamps: Setting::NotSet,
+            facet_search: Setting::NotSet,
             indexer_config,
         }
     }
@@ -170,6 +232,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.searchable_fields = Setting::Set(names);
     }
 
+    pub fn reset_searchable_fields_weights(&mut self) {
+        self.searchable_fields_weights = Setting::Reset;
+    }
+
+    pub fn set_searchable_fields_weights(&mut self, weights: BTreeMap<String, u16>) {
+        self.searchable_fields_weights = Setting::Set(weights);
+    }
+
     pub fn reset_displayed_fields(&mut self) {
         self.displayed_fields = Setting::Reset;
     }
@@ -211,6 +281,22 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) }
     }
 
+    pub fn reset_stop_words_preset(&mut self) {
+        self.stop_words_preset = Setting::Reset;
+    }
+
+    pub fn set_stop_words_preset(&mut self, preset: StopWordsPreset) {
+        self.stop_words_preset = Setting::Set(preset);
+    }
+
+    pub fn set_index_stop_words(&mut self, value: bool) {
+        self.index_stop_words = Setting::Set(value);
+    }
+
+    pub fn reset_index_stop_words(&mut self) {
+        self.index_stop_words = Setting::Reset;
+    }
+
     pub fn reset_non_separator_tokens(&mut self) {
         self.non_separator_tokens = Setting::Reset;
     }
@@ -300,6 +386,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.exact_words = Setting::Reset;
     }
 
+    pub fn set_disable_typos_on_numbers(&mut self, val: bool) {
+        self.disable_typos_on_numbers = Setting::Set(val);
+    }
+
+    pub fn reset_disable_typos_on_numbers(&mut self) {
+        self.disable_typos_on_numbers = Setting::Reset;
+    }
+
     pub fn set_exact_attributes(&mut self, attrs: HashSet<String>) {
         self.exact_attributes = Setting::Set(attrs);
     }
@@ -332,6 +426,182 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.pagination_max_total_hits = Setting::Reset;
     }
 
+    pub fn set_search_cutoff(&mut self, value: u64) {
+        self.search_cutoff = Setting::Set(value);
+    }
+
+    pub fn reset_search_cutoff(&mut self) {
+        self.search_cutoff = Setting::Reset;
+    }
+
+    pub fn set_proximity_precision(&mut self, value: ProximityPrecision) {
+        self.proximity_precision = Setting::Set(value);
+    }
+
+    pub fn reset_proximity_precision(&mut self) {
+        self.proximity_precision = Setting::Reset;
+    }
+
+    pub fn set_prefix_search(&mut self, value: PrefixSearch) {
+        self.prefix_search = Setting::Set(value);
+    }
+
+    pub fn reset_prefix_search(&mut self) {
+        self.prefix_search = Setting::Reset;
+    }
+
+    pub fn set_split_words(&mut self, value: bool) {
+        self.split_words = Setting::Set(value);
+    }
+
+    pub fn reset_split_words(&mut self) {
+        self.split_words = Setting::Reset;
+    }
+
+    pub fn set_concatenate_words(&mut self, value: bool) {
+        self.concatenate_words = Setting::Set(value);
+    }
+
+    pub fn reset_concatenate_words(&mut self) {
+        self.concatenate_words = Setting::Reset;
+    }
+
+    pub fn set_localized_attributes_rules(&mut self, value: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules = Setting::Set(value);
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
+    pub fn set_normalize_numbers(&mut self, value: bool) {
+        self.normalize_numbers = Setting::Set(value);
+    }
+
+    pub fn reset_normalize_numbers(&mut self) {
+        self.normalize_numbers = Setting::Reset;
+    }
+
+    pub fn set_normalization_lowercase(&mut self, value: bool) {
+        self.normalization_lowercase = Setting::Set(value);
+    }
+
+    pub fn reset_normalization_lowercase(&mut self) {
+        self.normalization_lowercase = Setting::Reset;
+    }
+
+    pub fn set_normalization_remove_diacritics(&mut self, value: bool) {
+        self.normalization_remove_diacritics = Setting::Set(value);
+    }
+
+    pub fn reset_normalization_remove_diacritics(&mut self) {
+        self.normalization_remove_diacritics = Setting::Reset;
+    }
+
+    pub fn set_normalization_compatibility(&mut self, value: bool) {
+        self.normalization_compatibility = Setting::Set(value);
+    }
+
+    pub fn reset_normalization_compatibility(&mut self) {
+        self.normalization_compatibility = Setting::Reset;
+    }
+
+    pub fn set_stemming_languages(&mut self, value: BTreeSet<StemmingLanguage>) {
+        self.stemming_languages = Setting::Set(value);
+    }
+
+    pub fn reset_stemming_languages(&mut self) {
+        self.stemming_languages = Setting::Reset;
+    }
+
+    pub fn set_percolate_queries(&mut self, value: BTreeMap<String, String>) {
+        self.percolate_queries = Setting::Set(value);
+    }
+
+    pub fn reset_percolate_queries(&mut self) {
+        self.percolate_queries = Setting::Reset;
+    }
+
+    pub fn set_geo_fields(&mut self, value: BTreeSet<String>) {
+        self.geo_fields = Setting::Set(value);
+    }
+
+    pub fn reset_geo_fields(&mut self) {
+        self.geo_fields = Setting::Reset;
+    }
+
+    pub fn set_geo_node_size(&mut self, value: GeoNodeSize) {
+        self.geo_node_size = Setting::Set(value);
+    }
+
+    pub fn reset_geo_node_size(&mut self) {
+        self.geo_node_size = Setting::Reset;
+    }
+
+    pub fn set_geo_load_strategy(&mut self, value: GeoLoadStrategy) {
+        self.geo_load_strategy = Setting::Set(value);
+    }
+
+    pub fn reset_geo_load_strategy(&mut self) {
+        self.geo_load_strategy = Setting::Reset;
+    }
+
+    pub fn set_binary_quantized_vectors(&mut self, value: bool) {
+        self.binary_quantized_vectors = Setting::Set(value);
+    }
+
+    pub fn reset_binary_quantized_vectors(&mut self) {
+        self.binary_quantized_vectors = Setting::Reset;
+    }
+
+    pub fn set_vector_ef_construction(&mut self, value: usize) {
+        self.vector_ef_construction = Setting::Set(value);
+    }
+
+    pub fn reset_vector_ef_construction(&mut self) {
+        self.vector_ef_construction = Setting::Reset;
+    }
+
+    pub fn set_vector_ef_search(&mut self, value: usize) {
+        self.vector_ef_search = Setting::Set(value);
+    }
+
+    pub fn reset_vector_ef_search(&mut self) {
+        self.vector_ef_search = Setting::Reset;
+    }
+
+    pub fn set_reranker(&mut self, value: RerankerConfig) {
+        self.reranker = Setting::Set(value);
+    }
+
+    pub fn reset_reranker(&mut self) {
+        self.reranker = Setting::Reset;
+    }
+
+    pub fn set_version_field(&mut self, version_field: String) {
+        self.version_field = Setting::Set(version_field);
+    }
+
+    pub fn reset_version_field(&mut self) {
+        self.version_field = Setting::Reset;
+    }
+
+    pub fn set_document_timestamps(&mut self, value: bool) {
+        self.document_timestamps = Setting::Set(value);
+    }
+
+    pub fn reset_document_timestamps(&mut self) {
+        self.document_timestamps = Setting::Reset;
+    }
+
+    pub fn set_facet_search(&mut self, value: bool) {
+        self.facet_search = Setting::Set(value);
+    }
+
+    pub fn reset_facet_search(&mut self) {
+        self.facet_search = Setting::Reset;
+    }
+
     fn reindex<FP, FA>(
         &mut self,
         progress_callback: &FP,
@@ -460,35 +730,74 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
-    fn update_stop_words(&mut self) -> Result<bool> {
-        match self.stop_words {
-            Setting::Set(ref stop_words) => {
-                let current = self.index.stop_words(self.wtxn)?;
-
-                // Apply an unlossy normalization on stop_words
-                let stop_words: BTreeSet<String> = stop_words
-                    .iter()
-                    .map(|w| w.as_str().normalize(&Default::default()).into_owned())
-                    .collect();
-
-                // since we can't compare a BTreeSet with an FST we are going to convert the
-                // BTreeSet to an FST and then compare bytes per bytes the two FSTs.
-                let fst = fst::Set::from_iter(stop_words.into_iter())?;
+    fn update_stop_words(&mut self, normalization_updated: bool) -> Result<bool> {
+        let previous_preset = self.index.stop_words_preset(self.wtxn)?;
+        let preset = match self.stop_words_preset {
+            Setting::Set(preset) => {
+                self.index.put_stop_words_preset(self.wtxn, preset)?;
+                Some(preset)
+            }
+            Setting::Reset => {
+                self.index.delete_stop_words_preset(self.wtxn)?;
+                None
+            }
+            Setting::NotSet => previous_preset,
+        };
 
-                // Does the new FST differ from the previous one?
-                if current
-                    .map_or(true, |current| current.as_fst().as_bytes() != fst.as_fst().as_bytes())
-                {
-                    // we want to re-create our FST.
-                    self.index.put_stop_words(self.wtxn, &fst)?;
-                    Ok(true)
-                } else {
-                    Ok(false)
+        // The stored FST contains the normalized words of both the preset and the user,
+        // when only the preset or the normalization changes we must normalize the user-defined words again.
+        let user_stop_words: BTreeSet<String> = match self.stop_words {
+            Setting::Set(ref stop_words) => stop_words.clone(),
+            Setting::Reset => BTreeSet::new(),
+            Setting::NotSet if preset != previous_preset || normalization_updated => {
+                match self.index.user_defined_stop_words(self.wtxn)? {
+                    Some(stop_words) => stop_words,
+                    // the user-defined words were not kept before the normalization could be changed,
+                    // we retrieve them from the FST normalized with the default parameters.
+                    None => {
+                        let previous_preset_words = previous_preset
+                            .map(|preset| preset.words(NormalizationParams::default()))
+                            .unwrap_or_default();
+                        match self.index.stop_words(self.wtxn)? {
+                            Some(current) => current
+                                .stream()
+                                .into_strs()?
+                                .into_iter()
+                                .filter(|word| !previous_preset_words.contains(word))
+                                .collect(),
+                            None => BTreeSet::new(),
+                        }
+                    }
                 }
             }
-            Setting::Reset => Ok(self.index.delete_stop_words(self.wtxn)?),
-            Setting::NotSet => Ok(false),
+            Setting::NotSet => return Ok(false),
+        };
+
+        // Apply an unlossy normalization on stop_words, with the normalization steps of the index
+        let normalization = self.index.normalization_params(self.wtxn)?;
+        let mut stop_words: BTreeSet<String> =
+            user_stop_words.iter().map(|w| normalize_stop_word(w, normalization)).collect();
+
+        if let Some(preset) = preset {
+            stop_words.extend(preset.words(normalization));
+        }
+
+        if stop_words.is_empty() {
+            return Ok(self.index.delete_stop_words(self.wtxn)?);
         }
+
+        // since we can't compare a BTreeSet with an FST we are going to convert the
+        // BTreeSet to an FST and then compare bytes per bytes the two FSTs.
+        let fst = fst::Set::from_iter(stop_words.into_iter())?;
+        let current = self.index.stop_words(self.wtxn)?;
+
+        // Does the new FST differ from the previous one?
+        let updated =
+            current.map_or(true, |current| current.as_fst().as_bytes() != fst.as_fst().as_bytes());
+
+        // the user-defined words are kept even when they are normalized into the same FST.
+        self.index.put_stop_words(self.wtxn, &fst, &user_stop_words)?;
+        Ok(updated)
     }
 
     fn update_non_separator_tokens(&mut self) -> Result<bool> {
@@ -569,9 +878,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
     fn update_synonyms(&mut self) -> Result<bool> {
         match self.synonyms {
             Setting::Set(ref user_synonyms) => {
-                fn normalize(tokenizer: &Tokenizer, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
+                fn normalize(
+                    tokenizer: &Tokenizer,
+                    text: &str,
+                    normalization: NormalizationParams,
+                    stop_words: Option<&fst::Set<&[u8]>>,
+                ) -> Vec<String> {
+                    let tokens = tokenizer.tokenize(text);
+                    normalize_words(text, tokens, normalization, stop_words)
                         .filter_map(|token| {
                             if token.is_word() && !token.lemma().is_empty() {
                                 Some(token.lemma().to_string())
@@ -603,14 +917,16 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                 }
 
                 let tokenizer = builder.build();
+                let normalization = self.index.normalization_params(self.wtxn)?;
+                let stop_words = stop_words.as_ref();
 
                 let mut new_synonyms = HashMap::new();
                 for (word, synonyms) in user_synonyms {
                     // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
+                    let normalized_word = normalize(&tokenizer, word, normalization, stop_words);
                     let normalized_synonyms: Vec<_> = synonyms
                         .iter()
-                        .map(|synonym| normalize(&tokenizer, synonym))
+                        .map(|synonym| normalize(&tokenizer, synonym, normalization, stop_words))
                         .filter(|synonym| !synonym.is_empty())
                         .collect();
 
@@ -698,6 +1014,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
     fn update_criteria(&mut self) -> Result<()> {
         match &self.criteria {
             Setting::Set(criteria) => {
+                validate_criteria(criteria).map_err(UserError::CriterionError)?;
                 self.index.put_criteria(self.wtxn, criteria)?;
             }
             Setting::Reset => {
@@ -787,31 +1104,434 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
-    fn update_exact_words(&mut self) -> Result<()> {
-        match self.exact_words {
-            Setting::Set(ref mut words) => {
-                fn normalize(tokenizer: &Tokenizer, text: &str) -> String {
-                    tokenizer.tokenize(text).map(|token| token.lemma().to_string()).collect()
-                }
+    fn update_disable_typos_on_numbers(&mut self) -> Result<()> {
+        match self.disable_typos_on_numbers {
+            Setting::Set(flag) => self.index.put_disable_typos_on_numbers(self.wtxn, flag)?,
+            Setting::Reset => {
+                self.index.delete_disable_typos_on_numbers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    fn update_exact_words(&mut self) -> Result<()> {
+        match self.exact_words {
+            Setting::Set(ref mut words) => {
+                fn normalize(tokenizer: &Tokenizer, text: &str) -> String {
+                    tokenizer.tokenize(text).map(|token| token.lemma().to_string()).collect()
+                }
+
+                let mut builder = TokenizerBuilder::new();
+                let stop_words = self.index.stop_words(self.wtxn)?;
+                if let Some(ref stop_words) = stop_words {
+                    builder.stop_words(stop_words);
+                }
+                let tokenizer = builder.build();
+
+                let mut words: Vec<_> =
+                    words.iter().map(|word| normalize(&tokenizer, word)).collect();
+
+                // normalization could reorder words
+                words.sort_unstable();
+
+                let words = fst::Set::from_iter(words.iter())?;
+                self.index.put_exact_words(self.wtxn, &words)?;
+            }
+            Setting::Reset => {
+                self.index.put_exact_words(self.wtxn, &fst::Set::default())?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_max_values_per_facet(&mut self) -> Result<()> {
+        match self.max_values_per_facet {
+            Setting::Set(max) => {
+                self.index.put_max_values_per_facet(self.wtxn, max)?;
+            }
+            Setting::Reset => {
+                self.index.delete_max_values_per_facet(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_sort_facet_values_by(&mut self) -> Result<()> {
+        match self.sort_facet_values_by.as_ref() {
+            Setting::Set(value) => {
+                self.index.put_sort_facet_values_by(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_sort_facet_values_by(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_pagination_max_total_hits(&mut self) -> Result<()> {
+        match self.pagination_max_total_hits {
+            Setting::Set(max) => {
+                self.index.put_pagination_max_total_hits(self.wtxn, max)?;
+            }
+            Setting::Reset => {
+                self.index.delete_pagination_max_total_hits(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_search_cutoff(&mut self) -> Result<()> {
+        match self.search_cutoff {
+            Setting::Set(cutoff) => {
+                self.index.put_search_cutoff(self.wtxn, cutoff)?;
+            }
+            Setting::Reset => {
+                self.index.delete_search_cutoff(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_proximity_precision(&mut self) -> Result<bool> {
+        let old = self.index.proximity_precision(self.wtxn)?.unwrap_or_default();
+        let new = match self.proximity_precision {
+            Setting::Set(new) => {
+                self.index.put_proximity_precision(self.wtxn, new)?;
+                new
+            }
+            Setting::Reset => {
+                self.index.delete_proximity_precision(self.wtxn)?;
+                ProximityPrecision::default()
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        Ok(old != new)
+    }
+
+    /// Returns `true` if the prefix databases must be built by reindexing the documents.
+    fn update_prefix_search(&mut self) -> Result<bool> {
+        let old = self.index.prefix_search(self.wtxn)?;
+        let new = match self.prefix_search {
+            Setting::Set(new) => {
+                self.index.put_prefix_search(self.wtxn, new)?;
+                new
+            }
+            Setting::Reset => {
+                self.index.delete_prefix_search(self.wtxn)?;
+                PrefixSearch::default()
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        match (old, new) {
+            (PrefixSearch::Disabled, PrefixSearch::IndexingTime) => Ok(true),
+            (PrefixSearch::IndexingTime, PrefixSearch::Disabled) => {
+                self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
+                self.index.word_prefix_docids.clear(self.wtxn)?;
+                self.index.exact_word_prefix_docids.clear(self.wtxn)?;
+                self.index.word_prefix_pair_proximity_docids.clear(self.wtxn)?;
+                self.index.prefix_word_pair_proximity_docids.clear(self.wtxn)?;
+                self.index.word_prefix_position_docids.clear(self.wtxn)?;
+                self.index.word_prefix_fid_docids.clear(self.wtxn)?;
+                Ok(false)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn update_split_words(&mut self) -> Result<()> {
+        match self.split_words {
+            Setting::Set(flag) => {
+                self.index.put_split_words(self.wtxn, flag)?;
+            }
+            Setting::Reset => {
+                self.index.delete_split_words(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_concatenate_words(&mut self) -> Result<()> {
+        match self.concatenate_words {
+            Setting::Set(flag) => {
+                self.index.put_concatenate_words(self.wtxn, flag)?;
+            }
+            Setting::Reset => {
+                self.index.delete_concatenate_words(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        let old = self.index.localized_attributes_rules(self.wtxn)?.unwrap_or_default();
+        let new = match self.localized_attributes_rules {
+            Setting::Set(ref rules) => {
+                self.index.put_localized_attributes_rules(self.wtxn, rules)?;
+                rules.clone()
+            }
+            Setting::Reset => {
+                self.index.delete_localized_attributes_rules(self.wtxn)?;
+                Vec::new()
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        Ok(old != new)
+    }
+
+    fn update_index_stop_words(&mut self) -> Result<bool> {
+        let old = self.index.index_stop_words(self.wtxn)?;
+        let new = match self.index_stop_words {
+            Setting::Set(flag) => {
+                self.index.put_index_stop_words(self.wtxn, flag)?;
+                flag
+            }
+            Setting::Reset => {
+                self.index.delete_index_stop_words(self.wtxn)?;
+                false
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        Ok(old != new)
+    }
+
+    fn update_normalize_numbers(&mut self) -> Result<bool> {
+        let old = self.index.normalize_numbers(self.wtxn)?;
+        let new = match self.normalize_numbers {
+            Setting::Set(flag) => {
+                self.index.put_normalize_numbers(self.wtxn, flag)?;
+                flag
+            }
+            Setting::Reset => {
+                self.index.delete_normalize_numbers(self.wtxn)?;
+                false
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        Ok(old != new)
+    }
+
+    fn update_normalization_params(&mut self) -> Result<bool> {
+        let old = self.index.normalization_params(self.wtxn)?;
+        let default = NormalizationParams::default();
+        let mut new = old;
+        match self.normalization_lowercase {
+            Setting::Set(flag) => new.lowercase = flag,
+            Setting::Reset => new.lowercase = default.lowercase,
+            Setting::NotSet => (),
+        }
+        match self.normalization_remove_diacritics {
+            Setting::Set(flag) => new.remove_diacritics = flag,
+            Setting::Reset => new.remove_diacritics = default.remove_diacritics,
+            Setting::NotSet => (),
+        }
+        match self.normalization_compatibility {
+            Setting::Set(flag) => new.compatibility = flag,
+            Setting::Reset => new.compatibility = default.compatibility,
+            Setting::NotSet => (),
+        }
+
+        if old == new {
+            return Ok(false);
+        }
+
+        if new == default {
+            self.index.delete_normalization_params(self.wtxn)?;
+        } else {
+            self.index.put_normalization_params(self.wtxn, new)?;
+        }
+
+        Ok(true)
+    }
+
+    fn update_document_timestamps(&mut self) -> Result<()> {
+        match self.document_timestamps {
+            Setting::Set(flag) => self.index.put_document_timestamps(self.wtxn, flag)?,
+            Setting::Reset => {
+                self.index.delete_document_timestamps(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    /// Builds or removes the facet search structures when the setting changes,
+    /// without reindexing the documents.
+    fn update_facet_search(&mut self) -> Result<()> {
+        let old = self.index.facet_search(self.wtxn)?;
+        match self.facet_search {
+            Setting::Set(flag) => self.index.put_facet_search(self.wtxn, flag)?,
+            Setting::Reset => {
+                self.index.delete_facet_search(self.wtxn)?;
+            }
+            Setting::NotSet => return Ok(()),
+        }
+
+        let new = self.index.facet_search(self.wtxn)?;
+        if old == new {
+            return Ok(());
+        }
+
+        if new {
+            index_facet_search(self.wtxn, self.index)?;
+        } else {
+            self.index.facet_id_normalized_string_strings.clear(self.wtxn)?;
+            self.index.facet_id_string_fst.clear(self.wtxn)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_binary_quantized_vectors(&mut self) -> Result<bool> {
+        let old = self.index.binary_quantized_vectors(self.wtxn)?;
+        let new = match self.binary_quantized_vectors {
+            Setting::Set(flag) => {
+                self.index.put_binary_quantized_vectors(self.wtxn, flag)?;
+                flag
+            }
+            Setting::Reset => {
+                self.index.delete_binary_quantized_vectors(self.wtxn)?;
+                false
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        Ok(old != new)
+    }
+
+    fn update_stemming_languages(&mut self) -> Result<bool> {
+        let old = self.index.stemming_languages(self.wtxn)?;
+        let new = match self.stemming_languages.as_ref() {
+            Setting::Set(languages) if !languages.is_empty() => {
+                let languages: Vec<_> = languages.iter().copied().collect();
+                self.index.put_stemming_languages(self.wtxn, &languages)?;
+                languages
+            }
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_stemming_languages(self.wtxn)?;
+                Vec::new()
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        Ok(old != new)
+    }
+
+    fn update_geo_fields(&mut self) -> Result<bool> {
+        let old = self.index.geo_fields(self.wtxn)?;
+        let new = match self.geo_fields.as_ref() {
+            Setting::Set(fields) if !fields.is_empty() => {
+                let fields: Vec<_> = fields.iter().cloned().collect();
+                self.index.put_geo_fields(self.wtxn, &fields)?;
+                fields
+            }
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_geo_fields(self.wtxn)?;
+                Vec::new()
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        Ok(old != new)
+    }
+
+    /// Updates the parameters of the geo R-trees and rebuilds the existing trees
+    /// in place, the documents don't need to be reindexed.
+    fn update_geo_index_params(&mut self) -> Result<()> {
+        let old = self.index.geo_index_params(self.wtxn)?;
+        let mut new = old;
+        match self.geo_node_size {
+            Setting::Set(node_size) => new.node_size = node_size,
+            Setting::Reset => new.node_size = GeoNodeSize::default(),
+            Setting::NotSet => (),
+        }
+        match self.geo_load_strategy {
+            Setting::Set(load_strategy) => new.load_strategy = load_strategy,
+            Setting::Reset => new.load_strategy = GeoLoadStrategy::default(),
+            Setting::NotSet => (),
+        }
+
+        if old == new {
+            return Ok(());
+        }
+
+        // the trees must be read with the old parameters as the node size is not stored with them.
+        let rtree = self.index.geo_rtree(self.wtxn)?;
+        let named_rtrees = self.index.named_geo_rtrees(self.wtxn)?;
+
+        if new == GeoIndexParams::default() {
+            self.index.delete_geo_index_params(self.wtxn)?;
+        } else {
+            self.index.put_geo_index_params(self.wtxn, new)?;
+        }
+
+        if let Some(rtree) = rtree {
+            self.index.put_geo_rtree(self.wtxn, &rtree.rebuild(old, new))?;
+        }
+        if !named_rtrees.is_empty() {
+            let named_rtrees: BTreeMap<_, _> = named_rtrees
+                .into_iter()
+                .map(|(field, rtree)| (field, rtree.rebuild(old, new)))
+                .collect();
+            self.index.put_named_geo_rtrees(self.wtxn, &named_rtrees)?;
+        }
+
+        Ok(())
+    }
+
+    /// The new parameters are applied the next time the HNSW is built.
+    fn update_vector_index_params(&mut self) -> Result<()> {
+        let old = self.index.vector_index_params(self.wtxn)?;
+        let default = VectorIndexParams::default();
+        let mut new = old;
+        match self.vector_ef_construction {
+            Setting::Set(ef_construction) => new.ef_construction = ef_construction,
+            Setting::Reset => new.ef_construction = default.ef_construction,
+            Setting::NotSet => (),
+        }
+        match self.vector_ef_search {
+            Setting::Set(ef_search) => new.ef_search = ef_search,
+            Setting::Reset => new.ef_search = default.ef_search,
+            Setting::NotSet => (),
+        }
 
-                let mut builder = TokenizerBuilder::new();
-                let stop_words = self.index.stop_words(self.wtxn)?;
-                if let Some(ref stop_words) = stop_words {
-                    builder.stop_words(stop_words);
-                }
-                let tokenizer = builder.build();
+        if old == new {
+            return Ok(());
+        }
 
-                let mut words: Vec<_> =
-                    words.iter().map(|word| normalize(&tokenizer, word)).collect();
+        if new == default {
+            self.index.delete_vector_index_params(self.wtxn)?;
+        } else {
+            self.index.put_vector_index_params(self.wtxn, new)?;
+        }
 
-                // normalization could reorder words
-                words.sort_unstable();
+        Ok(())
+    }
 
-                let words = fst::Set::from_iter(words.iter())?;
-                self.index.put_exact_words(self.wtxn, &words)?;
-            }
+    fn update_reranker(&mut self) -> Result<()> {
+        match self.reranker.as_ref() {
+            Setting::Set(reranker) => self.index.put_reranker(self.wtxn, reranker)?,
             Setting::Reset => {
-                self.index.put_exact_words(self.wtxn, &fst::Set::default())?;
+                self.index.delete_reranker(self.wtxn)?;
             }
             Setting::NotSet => (),
         }
@@ -819,13 +1539,13 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
-    fn update_max_values_per_facet(&mut self) -> Result<()> {
-        match self.max_values_per_facet {
-            Setting::Set(max) => {
-                self.index.put_max_values_per_facet(self.wtxn, max)?;
+    fn update_searchable_fields_weights(&mut self) -> Result<()> {
+        match self.searchable_fields_weights.as_ref() {
+            Setting::Set(weights) if !weights.is_empty() => {
+                self.index.put_searchable_fields_weights(self.wtxn, weights)?
             }
-            Setting::Reset => {
-                self.index.delete_max_values_per_facet(self.wtxn)?;
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_searchable_fields_weights(self.wtxn)?;
             }
             Setting::NotSet => (),
         }
@@ -833,13 +1553,11 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
-    fn update_sort_facet_values_by(&mut self) -> Result<()> {
-        match self.sort_facet_values_by.as_ref() {
-            Setting::Set(value) => {
-                self.index.put_sort_facet_values_by(self.wtxn, value)?;
-            }
+    fn update_version_field(&mut self) -> Result<()> {
+        match self.version_field.as_ref() {
+            Setting::Set(field) => self.index.put_version_field(self.wtxn, field)?,
             Setting::Reset => {
-                self.index.delete_sort_facet_values_by(self.wtxn)?;
+                self.index.delete_version_field(self.wtxn)?;
             }
             Setting::NotSet => (),
         }
@@ -847,13 +1565,22 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
-    fn update_pagination_max_total_hits(&mut self) -> Result<()> {
-        match self.pagination_max_total_hits {
-            Setting::Set(max) => {
-                self.index.put_pagination_max_total_hits(self.wtxn, max)?;
+    fn update_percolate_queries(&mut self) -> Result<()> {
+        match self.percolate_queries.as_ref() {
+            Setting::Set(queries) => {
+                for (name, filter) in queries {
+                    if let Err(error) = Filter::from_str(filter) {
+                        return Err(UserError::InvalidPercolateQuery {
+                            name: name.clone(),
+                            error: error.to_string(),
+                        }
+                        .into());
+                    }
+                }
+                self.index.put_percolate_queries(self.wtxn, queries)?;
             }
             Setting::Reset => {
-                self.index.delete_pagination_max_total_hits(self.wtxn)?;
+                self.index.delete_percolate_queries(self.wtxn)?;
             }
             Setting::NotSet => (),
         }
@@ -880,9 +1607,21 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
         self.update_exact_words()?;
+        self.update_disable_typos_on_numbers()?;
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
+        self.update_search_cutoff()?;
+        self.update_split_words()?;
+        self.update_concatenate_words()?;
+        self.update_percolate_queries()?;
+        self.update_geo_index_params()?;
+        self.update_vector_index_params()?;
+        self.update_reranker()?;
+        self.update_searchable_fields_weights()?;
+        self.update_version_field()?;
+        self.update_document_timestamps()?;
+        self.update_facet_search()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
         // index new fields as facets. It means that the distinct attribute,
@@ -890,13 +1629,27 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let new_faceted_fields = self.index.user_defined_faceted_fields(self.wtxn)?;
         let faceted_updated = old_faceted_fields != new_faceted_fields;
 
-        let stop_words_updated = self.update_stop_words()?;
+        // the stop words and the synonyms are normalized with the normalization steps of the index.
+        let normalization_updated = self.update_normalization_params()?;
+        if normalization_updated && self.synonyms == Setting::NotSet {
+            self.synonyms = Setting::Set(self.index.user_defined_synonyms(self.wtxn)?);
+        }
+
+        let stop_words_updated = self.update_stop_words(normalization_updated)?;
         let non_separator_tokens_updated = self.update_non_separator_tokens()?;
         let separator_tokens_updated = self.update_separator_tokens()?;
         let dictionary_updated = self.update_dictionary()?;
         let synonyms_updated = self.update_synonyms()?;
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
+        let proximity_precision_updated = self.update_proximity_precision()?;
+        let prefix_search_updated = self.update_prefix_search()?;
+        let localized_attributes_rules_updated = self.update_localized_attributes_rules()?;
+        let index_stop_words_updated = self.update_index_stop_words()?;
+        let normalize_numbers_updated = self.update_normalize_numbers()?;
+        let stemming_languages_updated = self.update_stemming_languages()?;
+        let geo_fields_updated = self.update_geo_fields()?;
+        let binary_quantized_vectors_updated = self.update_binary_quantized_vectors()?;
 
         if stop_words_updated
             || non_separator_tokens_updated
@@ -906,6 +1659,15 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             || synonyms_updated
             || searchable_updated
             || exact_attributes_updated
+            || proximity_precision_updated
+            || prefix_search_updated
+            || localized_attributes_rules_updated
+            || index_stop_words_updated
+            || normalize_numbers_updated
+            || normalization_updated
+            || stemming_languages_updated
+            || geo_fields_updated
+            || binary_quantized_vectors_updated
         {
             self.reindex(&progress_callback, &should_abort, old_fields_ids_map)?;
         }
@@ -924,7 +1686,7 @@ mod tests {
     use crate::error::Error;
     use crate::index::tests::TempIndex;
     use crate::update::{ClearDocuments, DeleteDocuments};
-    use crate::{Criterion, Filter, SearchResult};
+    use crate::{Criterion, SearchResult};
 
     #[test]
     fn set_and_reset_searchable_fields() {
@@ -1363,6 +2125,229 @@ mod tests {
         assert_eq!(result.documents_ids.len(), 1); // there is one benoit in our data
     }
 
+    #[test]
+    fn set_and_reset_stop_words_preset() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "maxim": "I love dogs" },
+                { "id": 1, "maxim": "Doggos are the best" },
+                { "id": 2, "maxim": "The crepes are really good" },
+            ]))
+            .unwrap();
+
+        // Select the english preset along with a user-defined stop word
+        index
+            .update_settings(|settings| {
+                settings.set_stop_words(btreeset! { S("crepes") });
+                settings.set_stop_words_preset(StopWordsPreset::En);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.stop_words_preset(&rtxn).unwrap(), Some(StopWordsPreset::En));
+        let stop_words = index.stop_words(&rtxn).unwrap().unwrap();
+        assert!(stop_words.contains("the"));
+        assert!(stop_words.contains("crepes"));
+
+        let result = index.search(&rtxn).query("the ").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 3);
+        let result = index.search(&rtxn).query("crepes ").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 3);
+        drop(rtxn);
+
+        // Changing the preset alone must keep the user-defined stop words
+        index
+            .update_settings(|settings| {
+                settings.set_stop_words_preset(StopWordsPreset::Fr);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let stop_words = index.stop_words(&rtxn).unwrap().unwrap();
+        assert!(!stop_words.contains("the"));
+        assert!(stop_words.contains("le"));
+        assert!(stop_words.contains("crepes"));
+
+        let result = index.search(&rtxn).query("the").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+        drop(rtxn);
+
+        // Resetting the preset only leaves the user-defined stop words
+        index
+            .update_settings(|settings| {
+                settings.reset_stop_words_preset();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.stop_words_preset(&rtxn).unwrap(), None);
+        let stop_words = index.stop_words(&rtxn).unwrap().unwrap();
+        let expected = fst::Set::from_iter(["crepes"]).unwrap();
+        assert_eq!(stop_words.as_fst().as_bytes(), expected.as_fst().as_bytes());
+    }
+
+    #[test]
+    fn normalize_stop_words_and_synonyms_with_the_normalization() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "maxim": "The Crêpes are good" },
+                { "id": 1, "maxim": "the crepes are good" },
+            ]))
+            .unwrap();
+
+        index
+            .update_settings(|settings| {
+                settings.set_stop_words(btreeset! { S("The") });
+                settings.set_synonyms(btreemap! { S("Blini") => vec![S("Crêpes")] });
+                settings.set_normalization_lowercase(false);
+                settings.set_normalization_remove_diacritics(false);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let stop_words = index.stop_words(&rtxn).unwrap().unwrap();
+        let expected = fst::Set::from_iter(["The"]).unwrap();
+        assert_eq!(stop_words.as_fst().as_bytes(), expected.as_fst().as_bytes());
+        let synonyms = index.synonyms(&rtxn).unwrap();
+        assert_eq!(synonyms.get(&vec![S("Blini")]), Some(&vec![vec![S("Crêpes")]]));
+
+        let result = index.search(&rtxn).query("The ").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0, 1]);
+        let result = index.search(&rtxn).query("Blini").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+        drop(rtxn);
+
+        // Enabling the normalization again must normalize the stored words again
+        index
+            .update_settings(|settings| {
+                settings.reset_normalization_lowercase();
+                settings.reset_normalization_remove_diacritics();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.user_defined_stop_words(&rtxn).unwrap(), Some(btreeset! { S("The") }));
+        let stop_words = index.stop_words(&rtxn).unwrap().unwrap();
+        let expected = fst::Set::from_iter(["the"]).unwrap();
+        assert_eq!(stop_words.as_fst().as_bytes(), expected.as_fst().as_bytes());
+        let synonyms = index.synonyms(&rtxn).unwrap();
+        assert_eq!(synonyms.get(&vec![S("blini")]), Some(&vec![vec![S("crepes")]]));
+
+        let result = index.search(&rtxn).query("blini").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0, 1]);
+    }
+
+    #[test]
+    fn disable_and_enable_facet_search() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("color") });
+                settings.set_facet_search(false);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "color": "Blue" },
+                { "id": 1, "color": "Red" },
+            ]))
+            .unwrap();
+
+        // The facet search structures are not built while disabled
+        let rtxn = index.read_txn().unwrap();
+        assert!(!index.facet_search(&rtxn).unwrap());
+        assert!(index.facet_id_string_fst.is_empty(&rtxn).unwrap());
+        assert!(index.facet_id_normalized_string_strings.is_empty(&rtxn).unwrap());
+        drop(rtxn);
+
+        // Enabling the facet search builds them from the existing facet values
+        index
+            .update_settings(|settings| {
+                settings.reset_facet_search();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.facet_search(&rtxn).unwrap());
+        assert_eq!(index.facet_id_string_fst.len(&rtxn).unwrap(), 1);
+        assert_eq!(index.facet_id_normalized_string_strings.len(&rtxn).unwrap(), 2);
+        drop(rtxn);
+
+        // Disabling it again removes them
+        index
+            .update_settings(|settings| {
+                settings.set_facet_search(false);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.facet_id_string_fst.is_empty(&rtxn).unwrap());
+        assert!(index.facet_id_normalized_string_strings.is_empty(&rtxn).unwrap());
+    }
+
+    #[test]
+    fn disable_and_enable_prefix_search() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_prefix_search(PrefixSearch::Disabled);
+            })
+            .unwrap();
+
+        // Enough words share the same prefixes to be part of the prefix databases
+        let documents = (0..150u8)
+            .map(|i| {
+                let word = format!("word{}{}", (b'a' + i / 26) as char, (b'a' + i % 26) as char);
+                serde_json::json!({ "id": i, "title": word })
+            })
+            .filter_map(|json| match json {
+                serde_json::Value::Object(object) => Some(object),
+                _ => None,
+            });
+        let documents = crate::documents::documents_batch_reader_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        // The prefix databases are not built while disabled
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.prefix_search(&rtxn).unwrap(), PrefixSearch::Disabled);
+        assert!(index.words_prefixes_fst(&rtxn).unwrap().is_empty());
+        assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
+        assert!(index.word_prefix_position_docids.is_empty(&rtxn).unwrap());
+        drop(rtxn);
+
+        // Enabling the prefix search builds them from the indexed documents
+        index
+            .update_settings(|settings| {
+                settings.reset_prefix_search();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.prefix_search(&rtxn).unwrap(), PrefixSearch::IndexingTime);
+        assert!(index.words_prefixes_fst(&rtxn).unwrap().contains("word"));
+        let docids = index.word_prefix_docids.get(&rtxn, "word").unwrap().unwrap();
+        assert_eq!(docids.len(), 150);
+        drop(rtxn);
+
+        // Disabling it again removes them
+        index
+            .update_settings(|settings| {
+                settings.set_prefix_search(PrefixSearch::Disabled);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.words_prefixes_fst(&rtxn).unwrap().is_empty());
+        assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
+        assert!(index.word_prefix_position_docids.is_empty(&rtxn).unwrap());
+    }
+
     #[test]
     fn set_and_reset_synonyms() {
         let mut index = TempIndex::new();
@@ -1700,6 +2685,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_geo_index_params_rebuilds_the_rtree() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "_geo": { "lat": 45.4777599, "lng": 9.1967508 } },
+                { "id": 1, "_geo": { "lat": 45.4627619, "lng": 9.1946823 } },
+            ]))
+            .unwrap();
+
+        index
+            .update_settings(|settings| {
+                settings.set_geo_node_size(GeoNodeSize::Large);
+                settings.set_geo_load_strategy(GeoLoadStrategy::BulkLoad);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let params = index.geo_index_params(&rtxn).unwrap();
+        assert_eq!(params.node_size, GeoNodeSize::Large);
+        assert_eq!(params.load_strategy, GeoLoadStrategy::BulkLoad);
+        let rtree = index.geo_rtree(&rtxn).unwrap().unwrap();
+        assert_eq!(rtree.node_size(), GeoNodeSize::Large);
+        assert_eq!(rtree.size(), 2);
+        drop(rtxn);
+
+        index
+            .add_documents(documents!([
+                { "id": 2, "_geo": { "lat": 45.4662, "lng": 9.1885 } },
+            ]))
+            .unwrap();
+
+        index
+            .update_settings(|settings| {
+                settings.reset_geo_node_size();
+                settings.reset_geo_load_strategy();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.geo_index_params(&rtxn).unwrap(), GeoIndexParams::default());
+        let rtree = index.geo_rtree(&rtxn).unwrap().unwrap();
+        assert_eq!(rtree.node_size(), GeoNodeSize::Small);
+        assert_eq!(rtree.size(), 3);
+    }
+
+    #[test]
+    fn update_vector_index_params() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_vector_ef_construction(40);
+                settings.set_vector_ef_search(200);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "_vectors": [1.0, 0.0] },
+                { "id": 1, "_vectors": [0.0, 1.0] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let params = index.vector_index_params(&rtxn).unwrap();
+        assert_eq!(params, VectorIndexParams { ef_construction: 40, ef_search: 200 });
+        assert_eq!(index.vector_hnsw(&rtxn).unwrap().unwrap().iter().count(), 2);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_vector_ef_search();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let params = index.vector_index_params(&rtxn).unwrap();
+        assert_eq!(params, VectorIndexParams { ef_construction: 40, ef_search: 100 });
+        drop(rtxn);
+
+        index.update_settings(|settings| settings.reset_vector_ef_construction()).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.vector_index_params(&rtxn).unwrap(), VectorIndexParams::default());
+    }
+
     #[test]
     fn test_correct_settings_init() {
         let index = TempIndex::new();
@@ -1712,11 +2788,14 @@ mod tests {
                     index: _,
                     indexer_config: _,
                     searchable_fields,
+                    searchable_fields_weights,
                     displayed_fields,
                     filterable_fields,
                     sortable_fields,
                     criteria,
                     stop_words,
+                    stop_words_preset,
+                    index_stop_words,
                     non_separator_tokens,
                     separator_tokens,
                     dictionary,
@@ -1728,16 +2807,42 @@ mod tests {
                     min_word_len_one_typo,
                     exact_words,
                     exact_attributes,
+                    disable_typos_on_numbers,
                     max_values_per_facet,
                     sort_facet_values_by,
                     pagination_max_total_hits,
+                    search_cutoff,
+                    proximity_precision,
+                    prefix_search,
+                    split_words,
+                    concatenate_words,
+                    localized_attributes_rules,
+                    normalize_numbers,
+                    normalization_lowercase,
+                    normalization_remove_diacritics,
+                    normalization_compatibility,
+                    percolate_queries,
+                    stemming_languages,
+                    geo_fields,
+                    geo_node_size,
+                    geo_load_strategy,
+                    binary_quantized_vectors,
+                    vector_ef_construction,
+                    vector_ef_search,
+                    reranker,
+                    version_field,
+                    document_timestamps,
+                    facet_search,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
+                assert!(matches!(searchable_fields_weights, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
                 assert!(matches!(filterable_fields, Setting::NotSet));
                 assert!(matches!(sortable_fields, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
+                assert!(matches!(stop_words_preset, Setting::NotSet));
+                assert!(matches!(index_stop_words, Setting::NotSet));
                 assert!(matches!(non_separator_tokens, Setting::NotSet));
                 assert!(matches!(separator_tokens, Setting::NotSet));
                 assert!(matches!(dictionary, Setting::NotSet));
@@ -1749,9 +2854,32 @@ mod tests {
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
+                assert!(matches!(disable_typos_on_numbers, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(sort_facet_values_by, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
+                assert!(matches!(search_cutoff, Setting::NotSet));
+                assert!(matches!(proximity_precision, Setting::NotSet));
+                assert!(matches!(prefix_search, Setting::NotSet));
+                assert!(matches!(split_words, Setting::NotSet));
+                assert!(matches!(concatenate_words, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
+                assert!(matches!(normalize_numbers, Setting::NotSet));
+                assert!(matches!(normalization_lowercase, Setting::NotSet));
+                assert!(matches!(normalization_remove_diacritics, Setting::NotSet));
+                assert!(matches!(normalization_compatibility, Setting::NotSet));
+                assert!(matches!(percolate_queries, Setting::NotSet));
+                assert!(matches!(stemming_languages, Setting::NotSet));
+                assert!(matches!(geo_fields, Setting::NotSet));
+                assert!(matches!(geo_node_size, Setting::NotSet));
+                assert!(matches!(geo_load_strategy, Setting::NotSet));
+                assert!(matches!(binary_quantized_vectors, Setting::NotSet));
+                assert!(matches!(vector_ef_construction, Setting::NotSet));
+                assert!(matches!(vector_ef_search, Setting::NotSet));
+                assert!(matches!(reranker, Setting::NotSet));
+                assert!(matches!(version_field, Setting::NotSet));
+                assert!(matches!(document_timestamps, Setting::NotSet));
+                assert!(matches!(facet_search, Setting::NotSet));
             })
             .unwrap();
     }
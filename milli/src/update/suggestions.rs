@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::iter::Peekable;
+
+use fst::{IntoStreamer, MapBuilder, Streamer};
+
+use crate::search::normalize_suggestion;
+use crate::{Index, Result};
+
+/// Records the queries that can later be suggested by [`crate::Suggest`],
+/// the popularity of a query is the number of times it was recorded.
+pub struct RecordSuggestions<'t, 'u, 'i> {
+    wtxn: &'t mut heed::RwTxn<'i, 'u>,
+    index: &'i Index,
+    queries: BTreeMap<String, u64>,
+    reset: bool,
+}
+
+impl<'t, 'u, 'i> RecordSuggestions<'t, 'u, 'i> {
+    pub fn new(
+        wtxn: &'t mut heed::RwTxn<'i, 'u>,
+        index: &'i Index,
+    ) -> RecordSuggestions<'t, 'u, 'i> {
+        RecordSuggestions { wtxn, index, queries: BTreeMap::new(), reset: false }
+    }
+
+    /// Records a query, the empty queries are ignored.
+    pub fn add(&mut self, query: &str) -> &mut Self {
+        self.add_with_popularity(query, 1)
+    }
+
+    /// Records a query as if it was recorded `popularity` times.
+    pub fn add_with_popularity(&mut self, query: &str, popularity: u64) -> &mut Self {
+        if let Some(query) = normalize_suggestion(query) {
+            let entry = self.queries.entry(query).or_default();
+            *entry = entry.saturating_add(popularity);
+        }
+        self
+    }
+
+    /// Forgets the previously recorded queries before recording the new ones.
+    pub fn reset(&mut self) -> &mut Self {
+        self.reset = true;
+        self
+    }
+
+    pub fn execute(self) -> Result<()> {
+        puffin::profile_function!();
+
+        if self.reset && self.queries.is_empty() {
+            self.index.delete_suggestions_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let suggestions_fst = if self.reset {
+            fst::Map::default().map_data(Cow::Owned)?
+        } else {
+            self.index.suggestions_fst(self.wtxn)?
+        };
+
+        // Both the recorded queries and the new ones are sorted, we merge
+        // them and sum the popularity of the queries that appear in both.
+        let mut builder = MapBuilder::memory();
+        let mut stream = suggestions_fst.stream();
+        let mut queries = self.queries.iter().peekable();
+        while let Some((query, popularity)) = stream.next() {
+            insert_queries_before(&mut builder, &mut queries, query)?;
+            let popularity = match queries.next_if(|(q, _)| q.as_bytes() == query) {
+                Some((_, count)) => popularity.saturating_add(*count),
+                None => popularity,
+            };
+            builder.insert(query, popularity)?;
+        }
+        for (query, popularity) in queries {
+            builder.insert(query, *popularity)?;
+        }
+
+        let suggestions_fst = builder.into_map();
+        self.index.put_suggestions_fst(self.wtxn, &suggestions_fst)?;
+
+        Ok(())
+    }
+}
+
+/// Inserts the new queries that are strictly lower than the given recorded query.
+fn insert_queries_before<'q>(
+    builder: &mut MapBuilder<Vec<u8>>,
+    queries: &mut Peekable<impl Iterator<Item = (&'q String, &'q u64)>>,
+    query: &[u8],
+) -> Result<()> {
+    while let Some((new_query, popularity)) = queries.next_if(|(q, _)| q.as_bytes() < query) {
+        builder.insert(new_query, *popularity)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn record_and_merge_suggestions() {
+        let index = TempIndex::new();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = RecordSuggestions::new(&mut wtxn, &index);
+        builder.add("Iron Man").add("iron  man").add("hulk").add("   ");
+        builder.execute().unwrap();
+
+        let mut builder = RecordSuggestions::new(&mut wtxn, &index);
+        builder.add("iron man").add("black widow");
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let suggestions_fst = index.suggestions_fst(&rtxn).unwrap();
+        let suggestions: Vec<_> = suggestions_fst
+            .into_stream()
+            .into_str_vec()
+            .unwrap()
+            .into_iter()
+            .map(|(query, popularity)| format!("{query}: {popularity}"))
+            .collect();
+        insta::assert_debug_snapshot!(suggestions, @r###"
+        [
+            "black widow: 1",
+            "hulk: 1",
+            "iron man: 3",
+        ]
+        "###);
+        drop(rtxn);
+
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = RecordSuggestions::new(&mut wtxn, &index);
+        builder.reset();
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.suggestions_fst(&rtxn).unwrap().is_empty());
+    }
+}
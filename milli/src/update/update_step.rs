@@ -20,6 +20,14 @@ pub enum UpdateIndexingStep {
 }
 
 impl UpdateIndexingStep {
+    /// The names of the steps, in the order in which they are run.
+    pub const STEP_NAMES: [&'static str; 4] = [
+        "remapDocumentAddition",
+        "computeIdsAndMergeDocuments",
+        "indexDocuments",
+        "mergeDataIntoFinalDatabase",
+    ];
+
     pub const fn step(&self) -> usize {
         match self {
             RemapDocumentAddition { .. } => 0,
@@ -29,7 +37,11 @@ impl UpdateIndexingStep {
         }
     }
 
+    pub const fn name(&self) -> &'static str {
+        Self::STEP_NAMES[self.step()]
+    }
+
     pub const fn number_of_steps(&self) -> usize {
-        4
+        Self::STEP_NAMES.len()
     }
 }